@@ -0,0 +1,43 @@
+//! The BabyBear packed fields of the build against the M31 ones, of the same backend whatever the
+//! build, i.e., the Montgomery products of BabyBear against the Mersenne ones of M31:
+//! `cargo bench --bench babybear`.
+
+use arith::Field;
+use ark_std::test_rng;
+use babybear::{BabyBearExt4x16, BabyBearx16};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, Criterion,
+};
+use mersenne31::{M31Ext3x16, M31x16};
+
+fn bench_mul<F: Field>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str) {
+    let mut rng = test_rng();
+    let elements = [(); 4].map(|_| F::random_unsafe(&mut rng));
+    group.bench_function(name, |b| {
+        b.iter_batched(
+            || elements,
+            |[mut x, mut y, mut z, mut w]| {
+                for _ in 0..25 {
+                    (x, y, z, w) = (x * y, y * z, z * w, w * x);
+                }
+                (x, y, z, w)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn babybear_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mul-throughput<BabyBearx16> 100x");
+    bench_mul::<BabyBearx16>(&mut group, BabyBearx16::NAME);
+    bench_mul::<M31x16>(&mut group, M31x16::NAME);
+    group.finish();
+
+    let mut group = c.benchmark_group("mul-throughput<BabyBearExt4x16> 100x");
+    bench_mul::<BabyBearExt4x16>(&mut group, BabyBearExt4x16::NAME);
+    bench_mul::<M31Ext3x16>(&mut group, M31Ext3x16::NAME);
+    group.finish();
+}
+
+criterion_group!(bench, babybear_benchmark);
+criterion_main!(bench);
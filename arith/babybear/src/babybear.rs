@@ -0,0 +1,207 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldForECC, FieldSerde, FieldSerdeError, FieldSerdeResult};
+use rand::RngCore;
+
+/// 15 * 2^27 + 1
+pub const BABYBEAR_MOD: u32 = 0x7800_0001;
+
+#[inline(always)]
+pub(crate) fn mod_reduce_u32(x: u32) -> u32 {
+    if x >= BABYBEAR_MOD {
+        x - BABYBEAR_MOD
+    } else {
+        x
+    }
+}
+
+#[inline(always)]
+pub(crate) fn mod_reduce_u64(x: u64) -> u32 {
+    (x % BABYBEAR_MOD as u64) as u32
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BabyBear {
+    /// Always reduced modulo `BABYBEAR_MOD`.
+    pub v: u32,
+}
+
+field_common!(BabyBear);
+
+impl FieldSerde for BabyBear {
+    const SERIALIZED_SIZE: usize = 32 / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(self.v.to_le_bytes().as_ref())?;
+        Ok(())
+    }
+
+    /// Fails on non canonical encodings, i.e., values not smaller than the modulus.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut u = [0u8; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut u)?;
+        let v = u32::from_le_bytes(u);
+        if v >= BABYBEAR_MOD {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(BabyBear { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buf = [0u8; 32];
+        reader.read_exact(&mut buf)?;
+        if buf.iter().skip(4).any(|&x| x != 0) {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Self::deserialize_from(&buf[..4])
+    }
+}
+
+impl Field for BabyBear {
+    const NAME: &'static str = "BabyBear";
+
+    const SIZE: usize = 32 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = BabyBear { v: 0 };
+
+    const ONE: Self = BabyBear { v: 1 };
+
+    /// (p + 1) / 2
+    const INV_2: Self = BabyBear {
+        v: (BABYBEAR_MOD >> 1) + 1,
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        BabyBear {
+            v: mod_reduce_u64(rng.next_u64()),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        (rng.next_u32() & 1).into()
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// a^(p - 2)
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.exp(BABYBEAR_MOD as u128 - 2))
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        BabyBear {
+            v: mod_reduce_u64(u64::from_le_bytes(bytes[..8].try_into().unwrap())),
+        }
+    }
+}
+
+impl FieldForECC for BabyBear {
+    fn modulus() -> ethnum::U256 {
+        ethnum::U256::from(BABYBEAR_MOD)
+    }
+
+    fn from_u256(x: ethnum::U256) -> Self {
+        BabyBear {
+            v: (x % ethnum::U256::from(BABYBEAR_MOD)).as_u32(),
+        }
+    }
+
+    fn to_u256(&self) -> ethnum::U256 {
+        ethnum::U256::from(self.v)
+    }
+}
+
+impl Neg for BabyBear {
+    type Output = BabyBear;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        BabyBear {
+            v: if self.v == 0 {
+                0
+            } else {
+                BABYBEAR_MOD - self.v
+            },
+        }
+    }
+}
+
+impl From<u32> for BabyBear {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        BabyBear {
+            v: if x < BABYBEAR_MOD {
+                x
+            } else {
+                x % BABYBEAR_MOD
+            },
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &BabyBear, b: &BabyBear) -> BabyBear {
+    // both are below 2^31, so the sum does not overflow
+    BabyBear {
+        v: mod_reduce_u32(a.v + b.v),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &BabyBear, b: &BabyBear) -> BabyBear {
+    BabyBear {
+        v: mod_reduce_u32(a.v + BABYBEAR_MOD - b.v),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &BabyBear, b: &BabyBear) -> BabyBear {
+    BabyBear {
+        v: mod_reduce_u64(a.v as u64 * b.v as u64),
+    }
+}
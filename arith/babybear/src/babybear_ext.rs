@@ -0,0 +1,289 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult};
+use rand::RngCore;
+
+use crate::babybear::{mod_reduce_u64, BabyBear};
+
+/// 11 is not a square mod p, and p = 1 mod 4, so x^4 - 11 is irreducible, as in Plonky3.
+const NON_RESIDUE: BabyBear = BabyBear { v: 11 };
+
+/// a0 + a1 * x + a2 * x^2 + a3 * x^3, modulo x^4 - 11
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BabyBearExt4 {
+    pub v: [BabyBear; 4],
+}
+
+field_common!(BabyBearExt4);
+
+impl FieldSerde for BabyBearExt4 {
+    const SERIALIZED_SIZE: usize = (32 / 8) * 4;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|coef| coef.serialize_into(&mut writer))
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        Ok(BabyBearExt4 {
+            v: [
+                BabyBear::deserialize_from(&mut reader)?,
+                BabyBear::deserialize_from(&mut reader)?,
+                BabyBear::deserialize_from(&mut reader)?,
+                BabyBear::deserialize_from(&mut reader)?,
+            ],
+        })
+    }
+
+    #[inline]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::from(BabyBear::try_deserialize_from_ecc_format(
+            reader,
+        )?))
+    }
+}
+
+impl Field for BabyBearExt4 {
+    const NAME: &'static str = "BabyBear Extension 4";
+
+    const SIZE: usize = 32 / 8 * 4;
+
+    const FIELD_SIZE: usize = 32 * 4;
+
+    const ZERO: Self = BabyBearExt4 {
+        v: [BabyBear::ZERO; 4],
+    };
+
+    const ONE: Self = BabyBearExt4 {
+        v: [
+            BabyBear::ONE,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+        ],
+    };
+
+    const INV_2: Self = BabyBearExt4 {
+        v: [
+            BabyBear::INV_2,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+        ],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|coef| coef.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+            ],
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::random_bool(&mut rng),
+                BabyBear::ZERO,
+                BabyBear::ZERO,
+                BabyBear::ZERO,
+            ],
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// With y = x^2, a = A + B * x for A = a0 + a2 * y and B = a1 + a3 * y, and
+    /// a * (A - B * x) = A^2 - B^2 * y is in the quadratic extension by y^2 = 11, whose inverse
+    /// is its conjugate over its norm.
+    fn inv(&self) -> Option<Self> {
+        let [a0, a1, a2, a3] = self.v;
+        let c0 = a0.square() + NON_RESIDUE * a2.square() - NON_RESIDUE * (a1 * a3).double();
+        let c1 = (a0 * a2).double() - a1.square() - NON_RESIDUE * a3.square();
+        let norm_inv = (c0.square() - NON_RESIDUE * c1.square()).inv()?;
+        let conjugate = BabyBearExt4 {
+            v: [a0, -a1, a2, -a3],
+        };
+        let n_inv = BabyBearExt4 {
+            v: [
+                c0 * norm_inv,
+                BabyBear::ZERO,
+                -c1 * norm_inv,
+                BabyBear::ZERO,
+            ],
+        };
+        Some(conjugate * n_inv)
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        BabyBearExt4 {
+            v: square_internal(&self.v),
+        }
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v[0].as_u32_unchecked()
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        BabyBearExt4 {
+            v: std::array::from_fn(|i| BabyBear {
+                v: mod_reduce_u64(u64::from_le_bytes(
+                    bytes[i * 8..(i + 1) * 8].try_into().unwrap(),
+                )),
+            }),
+        }
+    }
+}
+
+impl ExtensionField for BabyBearExt4 {
+    const DEGREE: usize = 4;
+
+    /// Extension Field
+    const W: u32 = 11;
+
+    const X: Self = BabyBearExt4 {
+        v: [
+            BabyBear::ZERO,
+            BabyBear::ONE,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+        ],
+    };
+
+    /// Base field for the extension
+    type BaseField = BabyBear;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        BabyBearExt4 {
+            v: self.v.map(|coef| coef * base),
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        let mut v = self.v;
+        v[0] += base;
+        BabyBearExt4 { v }
+    }
+
+    /// (a0 + a1 * x + a2 * x^2 + a3 * x^3) * x = 11 * a3 + a0 * x + a1 * x^2 + a2 * x^3
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        BabyBearExt4 {
+            v: [NON_RESIDUE * self.v[3], self.v[0], self.v[1], self.v[2]],
+        }
+    }
+}
+
+impl Neg for BabyBearExt4 {
+    type Output = BabyBearExt4;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        BabyBearExt4 {
+            v: self.v.map(|coef| -coef),
+        }
+    }
+}
+
+impl From<u32> for BabyBearExt4 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        BabyBearExt4::from(BabyBear::from(x))
+    }
+}
+
+impl From<BabyBear> for BabyBearExt4 {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        BabyBearExt4 {
+            v: [x, BabyBear::ZERO, BabyBear::ZERO, BabyBear::ZERO],
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    BabyBearExt4 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    BabyBearExt4 {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+// (a0 + a1*x + a2*x^2 + a3*x^3) * (b0 + b1*x + b2*x^2 + b3*x^3) mod (x^4 - 11)
+// = a0*b0 + 11*(a1*b3 + a2*b2 + a3*b1)
+// + (a0*b1 + a1*b0 + 11*(a2*b3 + a3*b2))*x
+// + (a0*b2 + a1*b1 + a2*b0 + 11*a3*b3)*x^2
+// + (a0*b3 + a1*b2 + a2*b1 + a3*b0)*x^3
+#[inline(always)]
+fn mul_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    let a = &a.v;
+    let b = &b.v;
+    BabyBearExt4 {
+        v: [
+            a[0] * b[0] + NON_RESIDUE * (a[1] * b[3] + a[2] * b[2] + a[3] * b[1]),
+            a[0] * b[1] + a[1] * b[0] + NON_RESIDUE * (a[2] * b[3] + a[3] * b[2]),
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + NON_RESIDUE * a[3] * b[3],
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0],
+        ],
+    }
+}
+
+// same as mul; merge identical terms
+#[inline(always)]
+fn square_internal(a: &[BabyBear; 4]) -> [BabyBear; 4] {
+    [
+        a[0].square() + NON_RESIDUE * ((a[1] * a[3]).double() + a[2].square()),
+        (a[0] * a[1] + NON_RESIDUE * a[2] * a[3]).double(),
+        (a[0] * a[2]).double() + a[1].square() + NON_RESIDUE * a[3].square(),
+        (a[0] * a[3] + a[1] * a[2]).double(),
+    ]
+}
@@ -0,0 +1,58 @@
+// A BabyBearx16 stores 512 bits of data, as M31x16 does.
+// With AVX512 it stores a single __m512i element.
+// With AVX2 it stores two __m256i elements.
+// With NEON it stores four uint32x4_t elements.
+// Otherwise, e.g., on wasm, it stores 16 BabyBear elements.
+// The lanes of the AVX and NEON backends are in Montgomery form, x * 2^32 mod p, for products
+// to be reduced with multiplications only, and are converted when packed, unpacked, and
+// serialized: the bytes are the same whatever the backend.
+
+#[cfg(target_arch = "aarch64")]
+pub mod babybear_neon;
+
+#[cfg(target_arch = "x86_64")]
+pub mod babybear_avx512;
+
+#[cfg(target_arch = "x86_64")]
+pub mod babybear_avx256;
+
+pub mod babybear_portable;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub(crate) use montgomery::*;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+mod montgomery {
+    use crate::babybear::{BabyBear, BABYBEAR_MOD};
+
+    /// p^-1 mod 2^32
+    pub(crate) const MONTY_MU: u32 = 0x8800_0001;
+
+    /// 2^32 mod p, the Montgomery form of one
+    pub(crate) const MONTY_ONE: u32 = 0x0fff_fffe;
+
+    /// 2^31 mod p, the Montgomery form of 1 / 2
+    pub(crate) const MONTY_INV_2: u32 = 0x07ff_ffff;
+
+    /// x * 2^32 mod p
+    #[inline(always)]
+    pub(crate) fn to_monty(x: BabyBear) -> u32 {
+        (((x.v as u64) << 32) % BABYBEAR_MOD as u64) as u32
+    }
+
+    /// x * 2^-32 mod p
+    #[inline(always)]
+    pub(crate) fn from_monty(x: u32) -> BabyBear {
+        // x - q * p is a multiple of 2^32 of the same residue, whose top half is above -p
+        let q = x.wrapping_mul(MONTY_MU);
+        let qp_hi = ((q as u64 * BABYBEAR_MOD as u64) >> 32) as u32;
+        let (v, borrow) = 0u32.overflowing_sub(qp_hi);
+        BabyBear {
+            v: if borrow {
+                v.wrapping_add(BABYBEAR_MOD)
+            } else {
+                v
+            },
+        }
+    }
+}
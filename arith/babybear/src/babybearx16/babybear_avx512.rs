@@ -0,0 +1,348 @@
+use std::{
+    arch::x86_64::*,
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::{
+    babybear::{BabyBear, BABYBEAR_MOD},
+    babybearx16::{from_monty, to_monty, MONTY_INV_2, MONTY_MU, MONTY_ONE},
+};
+
+const BABYBEAR_PACK_SIZE: usize = 16;
+const PACKED_MOD: __m512i = unsafe { transmute([BABYBEAR_MOD; BABYBEAR_PACK_SIZE]) };
+const PACKED_MU: __m512i = unsafe { transmute([MONTY_MU; BABYBEAR_PACK_SIZE]) };
+const PACKED_0: __m512i = unsafe { transmute([0u32; BABYBEAR_PACK_SIZE]) };
+
+/// AVXBabyBear packs 16 BabyBear elements, in Montgomery form
+#[derive(Clone, Copy)]
+pub struct AVXBabyBear {
+    pub v: __m512i,
+}
+
+impl AVXBabyBear {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: BabyBear) -> AVXBabyBear {
+        AVXBabyBear {
+            v: unsafe { _mm512_set1_epi32(to_monty(x) as i32) },
+        }
+    }
+
+    /// The lanes, in Montgomery form
+    #[inline(always)]
+    fn lanes(&self) -> [u32; BABYBEAR_PACK_SIZE] {
+        unsafe { transmute::<__m512i, [u32; BABYBEAR_PACK_SIZE]>(self.v) }
+    }
+
+    #[inline(always)]
+    fn from_lanes(lanes: [u32; BABYBEAR_PACK_SIZE]) -> AVXBabyBear {
+        AVXBabyBear {
+            v: unsafe { transmute::<[u32; BABYBEAR_PACK_SIZE], __m512i>(lanes) },
+        }
+    }
+}
+
+field_common!(AVXBabyBear);
+
+impl FieldSerde for AVXBabyBear {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    /// The canonical lanes, as `BabyBear` serializes them.
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.unpack()
+            .iter()
+            .try_for_each(|lane| lane.serialize_into(&mut writer))
+    }
+
+    /// Fails on non canonical lanes, as `BabyBear` does.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [BabyBear::ZERO; BABYBEAR_PACK_SIZE];
+        for lane in &mut v {
+            *lane = BabyBear::deserialize_from(&mut reader)?;
+        }
+        Ok(Self::pack(&v))
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(BabyBear::try_deserialize_from_ecc_format(
+            reader,
+        )?))
+    }
+}
+
+impl Field for AVXBabyBear {
+    const NAME: &'static str = "AVX Packed BabyBear";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self { v: PACKED_0 };
+
+    const ONE: Self = Self {
+        v: unsafe { transmute::<[u32; 16], __m512i>([MONTY_ONE; BABYBEAR_PACK_SIZE]) },
+    };
+
+    const INV_2: Self = Self {
+        v: unsafe { transmute::<[u32; 16], __m512i>([MONTY_INV_2; BABYBEAR_PACK_SIZE]) },
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        // zero is the only Montgomery form of zero
+        unsafe { _mm512_cmpeq_epi32_mask(self.v, PACKED_0) == 0xFFFF }
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        // the Montgomery forms of uniform elements are uniform
+        Self::from_lanes(std::array::from_fn(|_| BabyBear::random_unsafe(&mut rng).v))
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        let lanes: [BabyBear; BABYBEAR_PACK_SIZE] =
+            std::array::from_fn(|_| BabyBear::random_bool(&mut rng));
+        Self::pack(&lanes)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// Lane by lane, none if any lane is zero.
+    fn inv(&self) -> Option<Self> {
+        let lanes = self.unpack();
+        if lanes.iter().any(|lane| lane.is_zero()) {
+            return None;
+        }
+        let inverses = lanes
+            .iter()
+            .map(|lane| lane.inv().unwrap())
+            .collect::<Vec<_>>();
+        Some(Self::pack(&inverses))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(BabyBear::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for AVXBabyBear {
+    type Scalar = BabyBear;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        BABYBEAR_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert_eq!(base_vec.len(), BABYBEAR_PACK_SIZE);
+        Self::from_lanes(std::array::from_fn(|i| to_monty(base_vec[i])))
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.lanes().map(from_monty).to_vec()
+    }
+}
+
+impl From<BabyBear> for AVXBabyBear {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        AVXBabyBear::pack_full(x)
+    }
+}
+
+impl Debug for AVXBabyBear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.unpack().iter().map(|lane| lane.v).collect::<Vec<_>>();
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "mm512i<16 x {}>", data[0])
+        } else {
+            write!(f, "mm512i<{:?}>", data)
+        }
+    }
+}
+
+impl Default for AVXBabyBear {
+    fn default() -> Self {
+        AVXBabyBear::zero()
+    }
+}
+
+impl PartialEq for AVXBabyBear {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        // the Montgomery forms are below the modulus, so equal elements are of equal bits
+        unsafe { _mm512_cmpeq_epi32_mask(self.v, other.v) == 0xFFFF }
+    }
+}
+
+impl Eq for AVXBabyBear {}
+
+impl Mul<&BabyBear> for AVXBabyBear {
+    type Output = AVXBabyBear;
+
+    #[inline(always)]
+    fn mul(self, rhs: &BabyBear) -> Self::Output {
+        mul_internal(&self, &AVXBabyBear::pack_full(*rhs))
+    }
+}
+
+impl Mul<BabyBear> for AVXBabyBear {
+    type Output = AVXBabyBear;
+
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: BabyBear) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<BabyBear> for AVXBabyBear {
+    type Output = AVXBabyBear;
+
+    #[inline(always)]
+    fn add(self, rhs: BabyBear) -> Self::Output {
+        self + AVXBabyBear::pack_full(rhs)
+    }
+}
+
+impl From<u32> for AVXBabyBear {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        AVXBabyBear::pack_full(BabyBear::from(x))
+    }
+}
+
+impl Neg for AVXBabyBear {
+    type Output = AVXBabyBear;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        sub_internal(&AVXBabyBear::ZERO, &self)
+    }
+}
+
+#[inline]
+#[must_use]
+fn movehdup_epi32(x: __m512i) -> __m512i {
+    // The instruction is only available in the floating-point flavor; this distinction is only for
+    // historical reasons and no longer matters. We cast to floats, duplicate, and cast back.
+    unsafe { _mm512_castps_si512(_mm512_movehdup_ps(_mm512_castsi512_ps(x))) }
+}
+
+#[inline]
+#[must_use]
+fn mask_movehdup_epi32(src: __m512i, k: __mmask16, a: __m512i) -> __m512i {
+    // The instruction is only available in the floating-point flavor; this distinction is only for
+    // historical reasons and no longer matters. We cast to floats, do the thing, and cast back.
+    unsafe {
+        let src = _mm512_castsi512_ps(src);
+        let a = _mm512_castsi512_ps(a);
+        _mm512_castps_si512(_mm512_mask_movehdup_ps(src, k, a))
+    }
+}
+
+const EVENS: __mmask16 = 0b0101010101010101;
+
+#[inline(always)]
+fn add_internal(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    // the backend is only used on the cpus of avx512f, those of the builds for avx512f or those
+    // checked with `SimdBackend::is_available`
+    unsafe { add_avx512(a, b) }
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn add_avx512(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    // the lanes are below 2^31, so the sums fit in a u32, and the minimum with the sum minus
+    // the modulus, wrapping below zero, is the reduced sum
+    let t = _mm512_add_epi32(a.v, b.v);
+    AVXBabyBear {
+        v: _mm512_min_epu32(t, _mm512_sub_epi32(t, PACKED_MOD)),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    // the backend is only used on the cpus of avx512f, those of the builds for avx512f or those
+    // checked with `SimdBackend::is_available`
+    unsafe { sub_avx512(a, b) }
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn sub_avx512(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    let t = _mm512_sub_epi32(a.v, b.v);
+    AVXBabyBear {
+        v: _mm512_min_epu32(t, _mm512_add_epi32(t, PACKED_MOD)),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    // the backend is only used on the cpus of avx512f, those of the builds for avx512f or those
+    // checked with `SimdBackend::is_available`
+    unsafe { mul_avx512(a, b) }
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn mul_avx512(a: &AVXBabyBear, b: &AVXBabyBear) -> AVXBabyBear {
+    // the Montgomery reduction of Plonky3's monty-31, of the even and the odd lanes apart:
+    // with q = a * b / p mod 2^32, a * b - q * p is a multiple of 2^32, whose top half is
+    // a * b / 2^32 mod p, minus p or not
+    let prod_evn = _mm512_mul_epu32(a.v, b.v);
+    let prod_odd = _mm512_mul_epu32(movehdup_epi32(a.v), movehdup_epi32(b.v));
+    let q_evn = _mm512_mul_epu32(prod_evn, PACKED_MU);
+    let q_odd = _mm512_mul_epu32(prod_odd, PACKED_MU);
+    let q_p_evn = _mm512_mul_epu32(q_evn, PACKED_MOD);
+    let q_p_odd = _mm512_mul_epu32(q_odd, PACKED_MOD);
+
+    // the top halves of the even products moved down to the even lanes
+    let prod_hi = mask_movehdup_epi32(prod_odd, EVENS, prod_evn);
+    let q_p_hi = mask_movehdup_epi32(q_p_odd, EVENS, q_p_evn);
+    let t = _mm512_sub_epi32(prod_hi, q_p_hi);
+    AVXBabyBear {
+        v: _mm512_min_epu32(t, _mm512_add_epi32(t, PACKED_MOD)),
+    }
+}
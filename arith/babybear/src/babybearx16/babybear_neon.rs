@@ -0,0 +1,311 @@
+use std::{
+    arch::aarch64::*,
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::{
+    babybear::{BabyBear, BABYBEAR_MOD},
+    babybearx16::{from_monty, to_monty, MONTY_INV_2, MONTY_MU, MONTY_ONE},
+};
+
+const BABYBEAR_PACK_SIZE: usize = 16;
+const PACKED_MOD: uint32x4_t = unsafe { transmute([BABYBEAR_MOD; BABYBEAR_PACK_SIZE / 4]) };
+const PACKED_MU: uint32x4_t = unsafe { transmute([MONTY_MU; BABYBEAR_PACK_SIZE / 4]) };
+const PACKED_0: uint32x4_t = unsafe { transmute([0u32; BABYBEAR_PACK_SIZE / 4]) };
+
+/// NeonBabyBear packs 16 BabyBear elements, in Montgomery form
+#[derive(Clone, Copy)]
+pub struct NeonBabyBear {
+    pub v: [uint32x4_t; 4],
+}
+
+impl NeonBabyBear {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: BabyBear) -> NeonBabyBear {
+        let v = unsafe { vdupq_n_u32(to_monty(x)) };
+        NeonBabyBear { v: [v; 4] }
+    }
+
+    /// The lanes, in Montgomery form
+    #[inline(always)]
+    fn lanes(&self) -> [u32; BABYBEAR_PACK_SIZE] {
+        unsafe { transmute::<[uint32x4_t; 4], [u32; BABYBEAR_PACK_SIZE]>(self.v) }
+    }
+
+    #[inline(always)]
+    fn from_lanes(lanes: [u32; BABYBEAR_PACK_SIZE]) -> NeonBabyBear {
+        NeonBabyBear {
+            v: unsafe { transmute::<[u32; BABYBEAR_PACK_SIZE], [uint32x4_t; 4]>(lanes) },
+        }
+    }
+}
+
+field_common!(NeonBabyBear);
+
+impl FieldSerde for NeonBabyBear {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    /// The canonical lanes, as `BabyBear` serializes them.
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.unpack()
+            .iter()
+            .try_for_each(|lane| lane.serialize_into(&mut writer))
+    }
+
+    /// Fails on non canonical lanes, as `BabyBear` does.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [BabyBear::ZERO; BABYBEAR_PACK_SIZE];
+        for lane in &mut v {
+            *lane = BabyBear::deserialize_from(&mut reader)?;
+        }
+        Ok(Self::pack(&v))
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(BabyBear::try_deserialize_from_ecc_format(
+            reader,
+        )?))
+    }
+}
+
+impl Field for NeonBabyBear {
+    const NAME: &'static str = "Neon Packed BabyBear";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self { v: [PACKED_0; 4] };
+
+    const ONE: Self = Self {
+        v: unsafe { transmute::<[u32; 16], [uint32x4_t; 4]>([MONTY_ONE; BABYBEAR_PACK_SIZE]) },
+    };
+
+    const INV_2: Self = Self {
+        v: unsafe { transmute::<[u32; 16], [uint32x4_t; 4]>([MONTY_INV_2; BABYBEAR_PACK_SIZE]) },
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        // zero is the only Montgomery form of zero
+        self.lanes().iter().all(|&x| x == 0)
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        // the Montgomery forms of uniform elements are uniform
+        Self::from_lanes(std::array::from_fn(|_| BabyBear::random_unsafe(&mut rng).v))
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        let lanes: [BabyBear; BABYBEAR_PACK_SIZE] =
+            std::array::from_fn(|_| BabyBear::random_bool(&mut rng));
+        Self::pack(&lanes)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// Lane by lane, none if any lane is zero.
+    fn inv(&self) -> Option<Self> {
+        let lanes = self.unpack();
+        if lanes.iter().any(|lane| lane.is_zero()) {
+            return None;
+        }
+        let inverses = lanes
+            .iter()
+            .map(|lane| lane.inv().unwrap())
+            .collect::<Vec<_>>();
+        Some(Self::pack(&inverses))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(BabyBear::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for NeonBabyBear {
+    type Scalar = BabyBear;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        BABYBEAR_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert_eq!(base_vec.len(), BABYBEAR_PACK_SIZE);
+        Self::from_lanes(std::array::from_fn(|i| to_monty(base_vec[i])))
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.lanes().map(from_monty).to_vec()
+    }
+}
+
+impl From<BabyBear> for NeonBabyBear {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        NeonBabyBear::pack_full(x)
+    }
+}
+
+impl Debug for NeonBabyBear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.unpack().iter().map(|lane| lane.v).collect::<Vec<_>>();
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "uint32x4_t<16 x {}>", data[0])
+        } else {
+            write!(f, "uint32x4_t<{:?}>", data)
+        }
+    }
+}
+
+impl Default for NeonBabyBear {
+    fn default() -> Self {
+        NeonBabyBear::zero()
+    }
+}
+
+impl PartialEq for NeonBabyBear {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        // the Montgomery forms are below the modulus, so equal elements are of equal bits
+        self.lanes() == other.lanes()
+    }
+}
+
+impl Eq for NeonBabyBear {}
+
+impl Mul<&BabyBear> for NeonBabyBear {
+    type Output = NeonBabyBear;
+
+    #[inline(always)]
+    fn mul(self, rhs: &BabyBear) -> Self::Output {
+        mul_internal(&self, &NeonBabyBear::pack_full(*rhs))
+    }
+}
+
+impl Mul<BabyBear> for NeonBabyBear {
+    type Output = NeonBabyBear;
+
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: BabyBear) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<BabyBear> for NeonBabyBear {
+    type Output = NeonBabyBear;
+
+    #[inline(always)]
+    fn add(self, rhs: BabyBear) -> Self::Output {
+        self + NeonBabyBear::pack_full(rhs)
+    }
+}
+
+impl From<u32> for NeonBabyBear {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        NeonBabyBear::pack_full(BabyBear::from(x))
+    }
+}
+
+impl Neg for NeonBabyBear {
+    type Output = NeonBabyBear;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        sub_internal(&NeonBabyBear::ZERO, &self)
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &NeonBabyBear, b: &NeonBabyBear) -> NeonBabyBear {
+    // the lanes are below 2^31, so the sums fit in a u32, and the minimum with the sum minus
+    // the modulus, wrapping below zero, is the reduced sum
+    NeonBabyBear {
+        v: std::array::from_fn(|i| unsafe {
+            let t = vaddq_u32(a.v[i], b.v[i]);
+            vminq_u32(t, vsubq_u32(t, PACKED_MOD))
+        }),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &NeonBabyBear, b: &NeonBabyBear) -> NeonBabyBear {
+    NeonBabyBear {
+        v: std::array::from_fn(|i| unsafe {
+            let t = vsubq_u32(a.v[i], b.v[i]);
+            vminq_u32(t, vaddq_u32(t, PACKED_MOD))
+        }),
+    }
+}
+
+#[inline]
+fn mul_internal(a: &NeonBabyBear, b: &NeonBabyBear) -> NeonBabyBear {
+    // the Montgomery reduction of Plonky3's monty-31, on the doubled high halves of vqdmulhq:
+    // with q = a * b / p mod 2^32, a * b - q * p is a multiple of 2^32, whose top half is
+    // a * b / 2^32 mod p, minus p or not
+    NeonBabyBear {
+        v: std::array::from_fn(|i| unsafe {
+            let lhs = vreinterpretq_s32_u32(a.v[i]);
+            let rhs = vreinterpretq_s32_u32(b.v[i]);
+            let mu = vreinterpretq_s32_u32(PACKED_MU);
+            let p = vreinterpretq_s32_u32(PACKED_MOD);
+
+            let prod_hi = vqdmulhq_s32(lhs, rhs);
+            let q = vmulq_s32(lhs, vmulq_s32(rhs, mu));
+            let q_p_hi = vqdmulhq_s32(q, p);
+
+            // halving the difference of the doubled halves, adding p back below zero
+            let t = vreinterpretq_u32_s32(vhsubq_s32(prod_hi, q_p_hi));
+            let underflow = vcltq_s32(prod_hi, q_p_hi);
+            vmlsq_u32(t, underflow, PACKED_MOD)
+        }),
+    }
+}
@@ -0,0 +1,249 @@
+// The lanes are operated on one after the other, without branches, for the compiler to
+// vectorize the loops, on the targets without AVX or NEON, e.g., wasm. The lanes are canonical,
+// not in Montgomery form, and the bytes are those of the simd backends, 16 little-endian u32.
+
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::babybear::{BabyBear, BABYBEAR_MOD};
+
+const BABYBEAR_PACK_SIZE: usize = 16;
+
+/// PortableBabyBear packs 16 BabyBear elements in an array
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortableBabyBear {
+    pub v: [BabyBear; BABYBEAR_PACK_SIZE],
+}
+
+impl PortableBabyBear {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: BabyBear) -> PortableBabyBear {
+        PortableBabyBear {
+            v: [x; BABYBEAR_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn map(&self, f: impl Fn(&BabyBear) -> BabyBear) -> PortableBabyBear {
+        PortableBabyBear {
+            v: std::array::from_fn(|i| f(&self.v[i])),
+        }
+    }
+
+    #[inline(always)]
+    fn zip_map(
+        &self,
+        rhs: &Self,
+        f: impl Fn(&BabyBear, &BabyBear) -> BabyBear,
+    ) -> PortableBabyBear {
+        PortableBabyBear {
+            v: std::array::from_fn(|i| f(&self.v[i], &rhs.v[i])),
+        }
+    }
+}
+
+field_common!(PortableBabyBear);
+
+impl FieldSerde for PortableBabyBear {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|lane| lane.serialize_into(&mut writer))
+    }
+
+    /// Fails on non canonical lanes, as `BabyBear` does.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [BabyBear::ZERO; BABYBEAR_PACK_SIZE];
+        for lane in &mut v {
+            *lane = BabyBear::deserialize_from(&mut reader)?;
+        }
+        Ok(PortableBabyBear { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(BabyBear::try_deserialize_from_ecc_format(
+            reader,
+        )?))
+    }
+}
+
+impl Field for PortableBabyBear {
+    const NAME: &'static str = "Portable Packed BabyBear";
+
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = PortableBabyBear {
+        v: [BabyBear::ZERO; BABYBEAR_PACK_SIZE],
+    };
+
+    const ONE: Self = PortableBabyBear {
+        v: [BabyBear::ONE; BABYBEAR_PACK_SIZE],
+    };
+
+    const INV_2: Self = PortableBabyBear {
+        v: [BabyBear::INV_2; BABYBEAR_PACK_SIZE],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|lane| lane.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        PortableBabyBear {
+            v: std::array::from_fn(|_| BabyBear::random_unsafe(&mut rng)),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        PortableBabyBear {
+            v: std::array::from_fn(|_| BabyBear::random_bool(&mut rng)),
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        self.map(|lane| lane.exp(exponent))
+    }
+
+    /// Lane by lane, none if any lane is zero.
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|lane| lane.is_zero()) {
+            return None;
+        }
+        Some(self.map(|lane| lane.inv().unwrap()))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(BabyBear::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for PortableBabyBear {
+    type Scalar = BabyBear;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        BABYBEAR_PACK_SIZE
+    }
+
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert_eq!(base_vec.len(), BABYBEAR_PACK_SIZE);
+        PortableBabyBear {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<BabyBear> for PortableBabyBear {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        Self::pack_full(x)
+    }
+}
+
+impl Mul<&BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+
+    #[inline(always)]
+    fn mul(self, rhs: &BabyBear) -> Self::Output {
+        self * *rhs
+    }
+}
+
+impl Mul<BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+
+    #[inline(always)]
+    fn mul(self, rhs: BabyBear) -> Self::Output {
+        self.map(|lane| *lane * rhs)
+    }
+}
+
+impl Add<BabyBear> for PortableBabyBear {
+    type Output = PortableBabyBear;
+
+    #[inline(always)]
+    fn add(self, rhs: BabyBear) -> Self::Output {
+        self.map(|lane| *lane + rhs)
+    }
+}
+
+impl From<u32> for PortableBabyBear {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        Self::pack_full(BabyBear::from(x))
+    }
+}
+
+impl Neg for PortableBabyBear {
+    type Output = PortableBabyBear;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self.map(|lane| -*lane)
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    // the lanes are below 2^31, so the sums fit in a u32, and the minimum with the sum minus
+    // the modulus, wrapping below zero, is the reduced sum
+    a.zip_map(b, |a, b| {
+        let sum = a.v + b.v;
+        BabyBear {
+            v: sum.min(sum.wrapping_sub(BABYBEAR_MOD)),
+        }
+    })
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    a.zip_map(b, |a, b| {
+        let diff = a.v.wrapping_sub(b.v);
+        BabyBear {
+            v: diff.min(diff.wrapping_add(BABYBEAR_MOD)),
+        }
+    })
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableBabyBear, b: &PortableBabyBear) -> PortableBabyBear {
+    a.zip_map(b, |a, b| *a * b)
+}
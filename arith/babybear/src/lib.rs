@@ -0,0 +1,31 @@
+#![cfg_attr(target_arch = "x86_64", feature(stdarch_x86_avx512))]
+
+/// BabyBear, the field of integers modulo 2^31 - 2^27 + 1
+mod babybear;
+pub use babybear::{BabyBear, BABYBEAR_MOD};
+
+/// SIMDx16 for BabyBear, of each backend of the target arch, that of the build re-exported below
+pub mod babybearx16;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "aarch64")] {
+        pub use babybearx16::babybear_neon::NeonBabyBear as BabyBearx16;
+    } else if #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))] {
+        pub use babybearx16::babybear_avx512::AVXBabyBear as BabyBearx16;
+    } else if #[cfg(target_arch = "x86_64")] {
+        pub use babybearx16::babybear_avx256::AVXBabyBear as BabyBearx16;
+    } else {
+        pub use babybearx16::babybear_portable::PortableBabyBear as BabyBearx16;
+    }
+}
+
+/// Degree 4 extension field for BabyBear
+mod babybear_ext;
+pub use babybear_ext::BabyBearExt4;
+
+/// SIMDx16 for Degree 4 extension field for BabyBear
+mod babybear_ext4x16;
+pub use babybear_ext4x16::BabyBearExt4x16;
+
+#[cfg(test)]
+mod tests;
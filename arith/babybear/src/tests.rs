@@ -0,0 +1,191 @@
+use std::io::Cursor;
+
+use arith::{
+    random_extension_field_tests, random_field_tests, random_inversion_tests,
+    random_simd_field_tests, Field, FieldSerde, SimdBackend, SimdField,
+};
+use ark_std::test_rng;
+
+use crate::{babybearx16, BabyBear, BabyBearExt4, BabyBearExt4x16, BabyBearx16, BABYBEAR_MOD};
+
+#[test]
+fn test_base_field() {
+    random_field_tests::<BabyBear>("BabyBear".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<BabyBear, _>(&mut rng, "BabyBear".to_string());
+}
+
+#[test]
+fn test_simd_field() {
+    random_field_tests::<BabyBearx16>("Vectorized BabyBear".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<BabyBearx16, _>(&mut rng, "Vectorized BabyBear".to_string());
+
+    random_simd_field_tests::<BabyBearx16>("Vectorized BabyBear".to_string());
+}
+
+/// The packed field of the backend, of the values and the bytes of the portable one.
+fn simd_backend_tests<F: SimdField<Scalar = BabyBear> + FieldSerde>(name: &str) {
+    random_field_tests::<F>(name.to_string());
+    random_simd_field_tests::<F>(name.to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<F, _>(&mut rng, name.to_string());
+
+    // the edge values, and random ones
+    let edges = [0, 1, 2, BABYBEAR_MOD - 2, BABYBEAR_MOD - 1].map(BabyBear::from);
+    let mut lanes = |offset: usize| {
+        (0..16)
+            .map(|i| match (i + offset) % 4 {
+                0 => edges[(i + offset) % edges.len()],
+                _ => BabyBear::random_unsafe(&mut rng),
+            })
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (lanes(0), lanes(3));
+    let (x, y) = (F::pack(&a), F::pack(&b));
+    let portable = babybearx16::babybear_portable::PortableBabyBear::pack;
+    assert_eq!(x.unpack(), a);
+    assert_eq!((x * y).unpack(), (portable(&a) * portable(&b)).unpack());
+    assert_eq!((x + y).unpack(), (portable(&a) + portable(&b)).unpack());
+    assert_eq!((x - y).unpack(), (portable(&a) - portable(&b)).unpack());
+    assert_eq!((-x).unpack(), (-portable(&a)).unpack());
+    assert_eq!(x.scale(&b[0]).unpack(), portable(&a).scale(&b[0]).unpack());
+    assert_eq!(F::one().unpack(), vec![BabyBear::ONE; 16]);
+    assert_eq!(F::INV_2.unpack(), vec![BabyBear::INV_2; 16]);
+    assert!(F::pack(&[BabyBear::ZERO; 16]).is_zero());
+
+    let (mut bytes, mut portable_bytes) = (vec![], vec![]);
+    (x * y).serialize_into(&mut bytes).unwrap();
+    (portable(&a) * portable(&b))
+        .serialize_into(&mut portable_bytes)
+        .unwrap();
+    assert_eq!(bytes, portable_bytes);
+    assert_eq!(F::deserialize_from(&bytes[..]).unwrap(), x * y);
+}
+
+#[test]
+fn test_simd_backends() {
+    simd_backend_tests::<babybearx16::babybear_portable::PortableBabyBear>("Portable BabyBear");
+    #[cfg(target_arch = "x86_64")]
+    {
+        if SimdBackend::Avx2.is_available() {
+            simd_backend_tests::<babybearx16::babybear_avx256::AVXBabyBear>("AVX2 BabyBear");
+        }
+        if SimdBackend::Avx512.is_available() {
+            simd_backend_tests::<babybearx16::babybear_avx512::AVXBabyBear>("AVX512 BabyBear");
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    if SimdBackend::Neon.is_available() {
+        simd_backend_tests::<babybearx16::babybear_neon::NeonBabyBear>("Neon BabyBear");
+    }
+}
+
+#[test]
+fn test_ext_field() {
+    random_field_tests::<BabyBearExt4>("BabyBear Ext4".to_string());
+    random_extension_field_tests::<BabyBearExt4>("BabyBear Ext4".to_string());
+    random_field_tests::<BabyBearExt4x16>("Simd BabyBear Ext4".to_string());
+    random_extension_field_tests::<BabyBearExt4x16>("Simd BabyBear Ext4".to_string());
+    random_simd_field_tests::<BabyBearExt4x16>("Simd BabyBear Ext4".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<BabyBearExt4, _>(&mut rng, "BabyBear Ext4".to_string());
+}
+
+/// The lanes of the simd fields are computed as their scalars.
+#[test]
+fn test_simd_lanes() {
+    let mut rng = test_rng();
+    let a = BabyBearExt4x16::random_unsafe(&mut rng);
+    let b = BabyBearExt4x16::random_unsafe(&mut rng);
+    let s = BabyBearExt4::random_unsafe(&mut rng);
+    let lanes = |x: BabyBearExt4x16| x.unpack();
+    for (i, (a_i, b_i)) in lanes(a).into_iter().zip(lanes(b)).enumerate() {
+        assert_eq!(lanes(a * b)[i], a_i * b_i);
+        assert_eq!(lanes(a + b)[i], a_i + b_i);
+        assert_eq!(lanes(a - b)[i], a_i - b_i);
+        assert_eq!(lanes(a.square())[i], a_i.square());
+        assert_eq!(lanes(a * s)[i], a_i * s);
+    }
+}
+
+#[test]
+fn test_reduction() {
+    let minus_one = BabyBear::from(BABYBEAR_MOD - 1);
+    assert_eq!(minus_one, -BabyBear::ONE);
+    assert_eq!(minus_one * minus_one, BabyBear::ONE);
+    assert_eq!(minus_one + minus_one, -BabyBear::from(2u32));
+    assert_eq!(BabyBear::ZERO - BabyBear::ONE, minus_one);
+    assert_eq!(
+        BabyBear::from(3u32).inv().unwrap(),
+        BabyBear::from(1342177281u32)
+    );
+    // 31 is a generator of the multiplicative group, of order 15 * 2^27
+    assert_eq!(
+        BabyBear::from(31u32).exp(BABYBEAR_MOD as u128 - 1),
+        BabyBear::ONE
+    );
+    assert_ne!(
+        BabyBear::from(31u32).exp((BABYBEAR_MOD as u128 - 1) / 2),
+        BabyBear::ONE
+    );
+
+    let minus_ones = BabyBearx16::from(BABYBEAR_MOD - 1);
+    assert_eq!(minus_ones + BabyBearx16::ONE, BabyBearx16::ZERO);
+    assert_eq!(BabyBearx16::ZERO - BabyBearx16::ONE, minus_ones);
+}
+
+#[test]
+fn test_serde() {
+    let a = BabyBearExt4 {
+        v: [
+            BabyBear::from(256u32 + 2),
+            -BabyBear::ONE,
+            BabyBear::ZERO,
+            BabyBear::from(7u32),
+        ],
+    };
+    let mut buffer = vec![];
+    a.serialize_into(&mut buffer).unwrap();
+    assert_eq!(buffer.len(), BabyBearExt4::SERIALIZED_SIZE);
+    let b = BabyBearExt4::deserialize_from(&mut Cursor::new(buffer)).unwrap();
+    assert_eq!(a, b);
+
+    let a = BabyBearx16::from(256 + 2);
+    let mut buffer = vec![];
+    a.serialize_into(&mut buffer).unwrap();
+    assert_eq!(buffer.len(), BabyBearx16::SERIALIZED_SIZE);
+    assert_eq!(BabyBearx16::deserialize_from(&buffer[..]).unwrap(), a);
+
+    // non canonical encodings are rejected
+    let bytes = BABYBEAR_MOD.to_le_bytes();
+    assert!(BabyBear::deserialize_from(&bytes[..]).is_err());
+}
+
+/// Compare to test vectors computed with Python's big integers
+#[test]
+fn test_vectors() {
+    let a = BabyBearExt4 {
+        v: [1u32, 2, 3, 4].map(BabyBear::from),
+    };
+    let b = BabyBearExt4 {
+        v: [5u32, 6, 7, 8].map(BabyBear::from),
+    };
+    let expected_prod = BabyBearExt4 {
+        v: [676u32, 588, 386, 60].map(BabyBear::from),
+    };
+    assert_eq!(expected_prod, a * b);
+
+    let expected_inv = BabyBearExt4 {
+        v: [1587469345u32, 920666518, 1160282443, 647153706].map(BabyBear::from),
+    };
+    assert_eq!(expected_inv, a.inv().unwrap());
+    let a_pow_11 = BabyBearExt4 {
+        v: [374109212u32, 621581642, 269190551, 1925703176].map(BabyBear::from),
+    };
+    assert_eq!(a_pow_11, a.exp(11));
+}
@@ -0,0 +1,101 @@
+// this module benchmarks modular multiplication strategies for the bn254 scalar field
+//
+// `Fr` from halo2curves, which is the bn254 field used across the repo, stores elements in
+// Montgomery form: a multiplication is a Montgomery product, with no conversion but at input and
+// output. The alternatives benchmarked here are:
+// - schoolbook: full 512 bit product, then reduction by long division;
+// - Barrett: full 512 bit product, then reduction with the precomputed floor(2^(2k) / p);
+// - Montgomery: `Fr`, and crypto_bigint's `DynResidue` on the same bigint backend as the two
+//   others, so that the comparison of the reductions is not skewed by the halo2curves assembly.
+//
+// Barrett only pays off over Montgomery when values are converted in and out of Montgomery form
+// at every multiplication, which the prover never does, so `Fr` is kept unless this benchmark
+// shows Barrett on par with `DynResidue`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use crypto_bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
+    Encoding, U256, U512,
+};
+use halo2curves::bn256::Fr;
+use halo2curves::ff::{Field, PrimeField};
+
+const MODULUS: U256 =
+    U256::from_be_hex("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+
+/// Number of bits of the modulus.
+const K: usize = 254;
+
+fn schoolbook_mul(a: &U256, b: &U256) -> U256 {
+    U256::const_rem_wide(a.mul_wide(b), &MODULUS).0
+}
+
+struct Barrett {
+    modulus: U512,
+    /// floor(2^(2k) / p)
+    mu: U512,
+}
+
+impl Barrett {
+    fn new() -> Self {
+        let modulus = U512::from((MODULUS, U256::ZERO));
+        let mu = U512::ONE.shl_vartime(2 * K).wrapping_div(&modulus);
+        Self { modulus, mu }
+    }
+
+    /// The inputs are reduced, so that their product is below 2^(2k) and fits the estimate.
+    fn mul(&self, a: &U256, b: &U256) -> U512 {
+        let x = U512::from(a.mul_wide(b));
+        let q = x
+            .shr_vartime(K - 1)
+            .wrapping_mul(&self.mu)
+            .shr_vartime(K + 1);
+        let mut r = x.wrapping_sub(&q.wrapping_mul(&self.modulus));
+        // the estimate is off by at most 2
+        while r >= self.modulus {
+            r = r.wrapping_sub(&self.modulus);
+        }
+        r
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let a = Fr::random(&mut rng);
+    let b = Fr::random(&mut rng);
+    let a_uint = U256::from_le_bytes(a.to_repr());
+    let b_uint = U256::from_le_bytes(b.to_repr());
+
+    let barrett = Barrett::new();
+    let params = DynResidueParams::new(&MODULUS);
+    let a_residue = DynResidue::new(&a_uint, params);
+    let b_residue = DynResidue::new(&b_uint, params);
+
+    // all the strategies compute the same product
+    let expected = U256::from_le_bytes((a * b).to_repr());
+    assert_eq!(schoolbook_mul(&a_uint, &b_uint), expected);
+    assert_eq!(
+        barrett.mul(&a_uint, &b_uint),
+        U512::from((expected, U256::ZERO))
+    );
+    assert_eq!((a_residue * b_residue).retrieve(), expected);
+
+    let mut group = c.benchmark_group("bn254 modular multiplication");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function(BenchmarkId::new("schoolbook", "long division"), |bencher| {
+        bencher.iter(|| schoolbook_mul(black_box(&a_uint), black_box(&b_uint)))
+    });
+    group.bench_function(BenchmarkId::new("barrett", "crypto_bigint"), |bencher| {
+        bencher.iter(|| barrett.mul(black_box(&a_uint), black_box(&b_uint)))
+    });
+    group.bench_function(BenchmarkId::new("montgomery", "crypto_bigint"), |bencher| {
+        bencher.iter(|| black_box(a_residue) * black_box(b_residue))
+    });
+    group.bench_function(BenchmarkId::new("montgomery", "halo2curves"), |bencher| {
+        bencher.iter(|| black_box(a) * black_box(b))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -7,3 +7,8 @@ pub type GF2_128 = neon::NeonGF2_128;
 mod avx;
 #[cfg(target_arch = "x86_64")]
 pub type GF2_128 = avx::AVXGF2_128;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub(crate) mod portable;
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub type GF2_128 = portable::PortableGF2_128;
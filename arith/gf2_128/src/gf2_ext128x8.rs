@@ -13,3 +13,9 @@ pub type GF2_128x8 = avx512::AVX512GF2_128x8;
 mod avx256;
 #[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
 pub type GF2_128x8 = avx256::AVX256GF2_128x8;
+
+// Portable, for the other targets
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod portable;
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub type GF2_128x8 = portable::PortableGF2_128x8;
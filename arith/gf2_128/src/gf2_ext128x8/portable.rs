@@ -0,0 +1,282 @@
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult, SimdField};
+use gf2::{GF2x8, GF2};
+
+use crate::gf2_ext128::portable::PortableGF2_128;
+
+const GF2_128_PACK_SIZE: usize = 8;
+
+/// Eight PortableGF2_128 lanes, the i-th of which is of the bit 7 - i of a GF2x8, as in
+/// NeonGF2_128x8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortableGF2_128x8 {
+    v: [PortableGF2_128; GF2_128_PACK_SIZE],
+}
+
+field_common!(PortableGF2_128x8);
+
+impl PortableGF2_128x8 {
+    #[inline(always)]
+    fn map(&self, f: impl Fn(&PortableGF2_128) -> PortableGF2_128) -> PortableGF2_128x8 {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| f(&self.v[i])),
+        }
+    }
+
+    #[inline(always)]
+    fn zip_map(
+        &self,
+        rhs: &Self,
+        f: impl Fn(&PortableGF2_128, &PortableGF2_128) -> PortableGF2_128,
+    ) -> PortableGF2_128x8 {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| f(&self.v[i], &rhs.v[i])),
+        }
+    }
+
+    /// The bit of the i-th lane
+    #[inline(always)]
+    fn lane_bit(v: &GF2x8, i: usize) -> GF2 {
+        GF2 {
+            v: (v.v >> (7 - i)) & 1u8,
+        }
+    }
+}
+
+impl FieldSerde for PortableGF2_128x8 {
+    const SERIALIZED_SIZE: usize = 128;
+
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|lane| lane.serialize_into(&mut writer))
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [PortableGF2_128::ZERO; GF2_128_PACK_SIZE];
+        for lane in &mut v {
+            *lane = PortableGF2_128::deserialize_from(&mut reader)?;
+        }
+        Ok(PortableGF2_128x8 { v })
+    }
+
+    #[inline]
+    fn try_deserialize_from_ecc_format<R: std::io::Read>(mut _reader: R) -> FieldSerdeResult<Self>
+    where
+        Self: Sized,
+    {
+        unimplemented!("We don't have a serialization for gf2_128 in ecc yet.")
+    }
+}
+
+impl Field for PortableGF2_128x8 {
+    const NAME: &'static str = "Portable Galios Field 2 128x8";
+
+    const SIZE: usize = 16 * 8;
+
+    const FIELD_SIZE: usize = 128 * 8; // in bits
+
+    const ZERO: Self = PortableGF2_128x8 {
+        v: [PortableGF2_128::ZERO; GF2_128_PACK_SIZE],
+    };
+
+    const ONE: Self = PortableGF2_128x8 {
+        v: [PortableGF2_128::ONE; GF2_128_PACK_SIZE],
+    };
+
+    const INV_2: Self = PortableGF2_128x8 {
+        v: [PortableGF2_128::INV_2; GF2_128_PACK_SIZE],
+    }; // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|lane| lane.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|_| PortableGF2_128::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|_| PortableGF2_128::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn exp(&self, exponent: u128) -> Self {
+        self.map(|lane| lane.exp(exponent))
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|lane| lane.is_zero()) {
+            return None;
+        }
+        Some(self.map(|lane| lane.inv().unwrap()))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self.map(|lane| lane.square())
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        PortableGF2_128::from_uniform_bytes(bytes).into()
+    }
+}
+
+impl SimdField for PortableGF2_128x8 {
+    type Scalar = PortableGF2_128;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        self.map(|lane| lane * challenge)
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GF2_128_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GF2_128_PACK_SIZE);
+        PortableGF2_128x8 {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<PortableGF2_128> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: PortableGF2_128) -> Self {
+        PortableGF2_128x8 {
+            v: [v; GF2_128_PACK_SIZE],
+        }
+    }
+}
+
+impl Neg for PortableGF2_128x8 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        PortableGF2_128::from(v).into()
+    }
+}
+
+impl ExtensionField for PortableGF2_128x8 {
+    const DEGREE: usize = PortableGF2_128::DEGREE;
+
+    const W: u32 = PortableGF2_128::W;
+
+    const X: Self = PortableGF2_128x8 {
+        v: [PortableGF2_128::X; GF2_128_PACK_SIZE],
+    };
+
+    type BaseField = GF2x8;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i].mul_by_base_field(&Self::lane_bit(base, i))),
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i].add_by_base_field(&Self::lane_bit(base, i))),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        self.map(|lane| lane.mul_by_x())
+    }
+}
+
+impl From<GF2x8> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2x8) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| Self::lane_bit(&v, i).into()),
+        }
+    }
+}
+
+impl Mul<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self
+        }
+    }
+}
+
+impl Add<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn add(self, rhs: GF2) -> Self::Output {
+        self.map(|lane| lane.add_by_base_field(&rhs))
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    a.zip_map(b, |a, b| *a + b)
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    add_internal(a, b)
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    a.zip_map(b, |a, b| *a * b)
+}
@@ -127,6 +127,32 @@ fn test_gf_mul_kat() {
     }
 }
 
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+#[test]
+// known answer test, the vectors of the neon one
+fn test_portable_gf_mul_kat() {
+    use crate::gf2_ext128::portable::gfmul;
+
+    let lanes = |v: [u32; 4]| v.iter().rev().fold(0u128, |acc, &x| acc << 32 | x as u128);
+    assert_eq!(gfmul((3u128 << 64) + 5, 0), 0);
+    assert_eq!(gfmul(1, (3u128 << 64) + 5), (3u128 << 64) + 5);
+    assert_eq!(
+        gfmul((3u128 << 64) + 5, (1u128 << 64) + 7),
+        lanes([402, 0, 12, 0])
+    );
+    assert_eq!(
+        gfmul((1u128 << 64) + 7, lanes([1, 1, 1, 1])),
+        lanes([128, 128, 6, 6])
+    );
+    assert_eq!(
+        gfmul(
+            u128::from_le_bytes([7u8; 16]),
+            u128::from_le_bytes([5u8; 16])
+        ),
+        lanes([232394202; 4])
+    );
+}
+
 #[cfg(target_arch = "aarch64")]
 #[test]
 fn test_gf_mul_rnd() {
@@ -0,0 +1,261 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{
+    field_common, Field, FieldForECC, FieldSerde, FieldSerdeError, FieldSerdeResult, SimdField,
+};
+use rand::RngCore;
+
+pub const GOLDILOCKS_MOD: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// 2^64 mod p, i.e., 2^32 - 1
+const EPSILON: u64 = 0xFFFF_FFFF;
+
+/// Reduce a u128 modulo p, using 2^64 = 2^32 - 1 and 2^96 = -1 mod p.
+#[inline(always)]
+pub(crate) fn mod_reduce_u128(x: u128) -> u64 {
+    let x_lo = x as u64;
+    let x_hi = (x >> 64) as u64;
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & EPSILON;
+
+    // x_lo - x_hi_hi, borrowing 2^64 = EPSILON on underflow
+    let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+    if borrow {
+        t0 = t0.wrapping_sub(EPSILON);
+    }
+    // x_hi_lo * 2^64 = x_hi_lo * EPSILON, which fits in a u64
+    let t1 = x_hi_lo * EPSILON;
+    let (t2, carry) = t0.overflowing_add(t1);
+    let t2 = t2.wrapping_add(EPSILON * carry as u64);
+    mod_reduce_u64(t2)
+}
+
+#[inline(always)]
+pub(crate) fn mod_reduce_u64(x: u64) -> u64 {
+    if x >= GOLDILOCKS_MOD {
+        x - GOLDILOCKS_MOD
+    } else {
+        x
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GoldilocksField {
+    /// Always reduced modulo `GOLDILOCKS_MOD`.
+    pub v: u64,
+}
+
+field_common!(GoldilocksField);
+
+impl FieldSerde for GoldilocksField {
+    const SERIALIZED_SIZE: usize = 64 / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(self.v.to_le_bytes().as_ref())?;
+        Ok(())
+    }
+
+    /// Fails on non canonical encodings, i.e., values not smaller than the modulus.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut u = [0u8; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut u)?;
+        let v = u64::from_le_bytes(u);
+        if v >= GOLDILOCKS_MOD {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(GoldilocksField { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buf = [0u8; 32];
+        reader.read_exact(&mut buf)?;
+        if buf.iter().skip(8).any(|&x| x != 0) {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Self::deserialize_from(&buf[..8])
+    }
+}
+
+impl Field for GoldilocksField {
+    const NAME: &'static str = "Goldilocks";
+
+    const SIZE: usize = 64 / 8;
+
+    const FIELD_SIZE: usize = 64;
+
+    const ZERO: Self = GoldilocksField { v: 0 };
+
+    const ONE: Self = GoldilocksField { v: 1 };
+
+    /// (p + 1) / 2
+    const INV_2: Self = GoldilocksField {
+        v: (GOLDILOCKS_MOD >> 1) + 1,
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        GoldilocksField {
+            v: mod_reduce_u64(rng.next_u64()),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        (rng.next_u32() & 1).into()
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// a^(p - 2)
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.exp(GOLDILOCKS_MOD as u128 - 2))
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v as u32
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        GoldilocksField {
+            v: mod_reduce_u64(u64::from_le_bytes(bytes[..8].try_into().unwrap())),
+        }
+    }
+}
+
+impl FieldForECC for GoldilocksField {
+    fn modulus() -> ethnum::U256 {
+        ethnum::U256::from(GOLDILOCKS_MOD)
+    }
+
+    fn from_u256(x: ethnum::U256) -> Self {
+        GoldilocksField {
+            v: (x % ethnum::U256::from(GOLDILOCKS_MOD)).as_u64(),
+        }
+    }
+
+    fn to_u256(&self) -> ethnum::U256 {
+        ethnum::U256::from(self.v)
+    }
+}
+
+/// There is no vectorized Goldilocks yet, the simd field is the field itself.
+impl SimdField for GoldilocksField {
+    type Scalar = Self;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * challenge
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == 1);
+        base_vec[0]
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        vec![*self]
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        1
+    }
+}
+
+impl Neg for GoldilocksField {
+    type Output = GoldilocksField;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        GoldilocksField {
+            v: if self.v == 0 {
+                0
+            } else {
+                GOLDILOCKS_MOD - self.v
+            },
+        }
+    }
+}
+
+impl From<u32> for GoldilocksField {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        GoldilocksField { v: x as u64 }
+    }
+}
+
+impl From<u64> for GoldilocksField {
+    #[inline(always)]
+    fn from(x: u64) -> Self {
+        GoldilocksField {
+            v: mod_reduce_u64(x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &GoldilocksField, b: &GoldilocksField) -> GoldilocksField {
+    let (sum, carry) = a.v.overflowing_add(b.v);
+    // on overflow, sum + 2^64 = sum + EPSILON, and sum < p - EPSILON
+    let v = if carry { sum + EPSILON } else { sum };
+    GoldilocksField {
+        v: mod_reduce_u64(v),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &GoldilocksField, b: &GoldilocksField) -> GoldilocksField {
+    GoldilocksField {
+        v: if a.v >= b.v {
+            a.v - b.v
+        } else {
+            GOLDILOCKS_MOD - (b.v - a.v)
+        },
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &GoldilocksField, b: &GoldilocksField) -> GoldilocksField {
+    GoldilocksField {
+        v: mod_reduce_u128(a.v as u128 * b.v as u128),
+    }
+}
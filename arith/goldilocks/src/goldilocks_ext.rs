@@ -0,0 +1,268 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::goldilocks::{mod_reduce_u64, GoldilocksField};
+
+/// 7 is the smallest quadratic non-residue mod p, so x^2 - 7 is irreducible.
+const NON_RESIDUE: GoldilocksField = GoldilocksField { v: 7 };
+
+/// a0 + a1 * x, modulo x^2 - 7
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct GoldilocksExt2 {
+    pub v: [GoldilocksField; 2],
+}
+
+field_common!(GoldilocksExt2);
+
+impl FieldSerde for GoldilocksExt2 {
+    const SERIALIZED_SIZE: usize = (64 / 8) * 2;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v[0].serialize_into(&mut writer)?;
+        self.v[1].serialize_into(&mut writer)
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        Ok(GoldilocksExt2 {
+            v: [
+                GoldilocksField::deserialize_from(&mut reader)?,
+                GoldilocksField::deserialize_from(&mut reader)?,
+            ],
+        })
+    }
+
+    #[inline]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::from(
+            GoldilocksField::try_deserialize_from_ecc_format(reader)?,
+        ))
+    }
+}
+
+impl Field for GoldilocksExt2 {
+    const NAME: &'static str = "Goldilocks Extension 2";
+
+    const SIZE: usize = 64 / 8 * 2;
+
+    const FIELD_SIZE: usize = 64 * 2;
+
+    const ZERO: Self = GoldilocksExt2 {
+        v: [GoldilocksField::ZERO, GoldilocksField::ZERO],
+    };
+
+    const ONE: Self = GoldilocksExt2 {
+        v: [GoldilocksField::ONE, GoldilocksField::ZERO],
+    };
+
+    const INV_2: Self = GoldilocksExt2 {
+        v: [GoldilocksField::INV_2, GoldilocksField::ZERO],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v[0].is_zero() && self.v[1].is_zero()
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        GoldilocksExt2 {
+            v: [
+                GoldilocksField::random_unsafe(&mut rng),
+                GoldilocksField::random_unsafe(&mut rng),
+            ],
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        GoldilocksExt2 {
+            v: [
+                GoldilocksField::random_bool(&mut rng),
+                GoldilocksField::ZERO,
+            ],
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    /// (a0 - a1 * x) / (a0^2 - 7 * a1^2), the norm being nonzero as 7 is not a square
+    fn inv(&self) -> Option<Self> {
+        let norm = self.v[0].square() - NON_RESIDUE * self.v[1].square();
+        let norm_inv = norm.inv()?;
+        Some(GoldilocksExt2 {
+            v: [self.v[0] * norm_inv, -self.v[1] * norm_inv],
+        })
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        let a = &self.v;
+        GoldilocksExt2 {
+            v: [
+                a[0].square() + NON_RESIDUE * a[1].square(),
+                (a[0] * a[1]).double(),
+            ],
+        }
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v[0].as_u32_unchecked()
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        let v0 = mod_reduce_u64(u64::from_le_bytes(bytes[0..8].try_into().unwrap()));
+        let v1 = mod_reduce_u64(u64::from_le_bytes(bytes[8..16].try_into().unwrap()));
+        GoldilocksExt2 {
+            v: [GoldilocksField { v: v0 }, GoldilocksField { v: v1 }],
+        }
+    }
+}
+
+impl ExtensionField for GoldilocksExt2 {
+    const DEGREE: usize = 2;
+
+    /// Extension Field
+    const W: u32 = 7;
+
+    const X: Self = GoldilocksExt2 {
+        v: [GoldilocksField::ZERO, GoldilocksField::ONE],
+    };
+
+    /// Base field for the extension
+    type BaseField = GoldilocksField;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        GoldilocksExt2 {
+            v: [self.v[0] * base, self.v[1] * base],
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        GoldilocksExt2 {
+            v: [self.v[0] + base, self.v[1]],
+        }
+    }
+
+    /// (a0 + a1 * x) * x = 7 * a1 + a0 * x
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        GoldilocksExt2 {
+            v: [NON_RESIDUE * self.v[1], self.v[0]],
+        }
+    }
+}
+
+/// As for `GoldilocksField`, the simd field is the field itself.
+impl SimdField for GoldilocksExt2 {
+    type Scalar = Self;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * challenge
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == 1);
+        base_vec[0]
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        vec![*self]
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        1
+    }
+}
+
+impl Neg for GoldilocksExt2 {
+    type Output = GoldilocksExt2;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        GoldilocksExt2 {
+            v: [-self.v[0], -self.v[1]],
+        }
+    }
+}
+
+impl From<u32> for GoldilocksExt2 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        GoldilocksExt2 {
+            v: [GoldilocksField::from(x), GoldilocksField::ZERO],
+        }
+    }
+}
+
+impl From<GoldilocksField> for GoldilocksExt2 {
+    #[inline(always)]
+    fn from(x: GoldilocksField) -> Self {
+        GoldilocksExt2 {
+            v: [x, GoldilocksField::ZERO],
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &GoldilocksExt2, b: &GoldilocksExt2) -> GoldilocksExt2 {
+    GoldilocksExt2 {
+        v: [a.v[0] + b.v[0], a.v[1] + b.v[1]],
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &GoldilocksExt2, b: &GoldilocksExt2) -> GoldilocksExt2 {
+    GoldilocksExt2 {
+        v: [a.v[0] - b.v[0], a.v[1] - b.v[1]],
+    }
+}
+
+// (a0 + a1*x) * (b0 + b1*x) mod (x^2 - 7)
+// = a0*b0 + 7*a1*b1 + (a0*b1 + a1*b0)*x
+#[inline(always)]
+fn mul_internal(a: &GoldilocksExt2, b: &GoldilocksExt2) -> GoldilocksExt2 {
+    let a = &a.v;
+    let b = &b.v;
+    GoldilocksExt2 {
+        v: [
+            a[0] * b[0] + NON_RESIDUE * a[1] * b[1],
+            a[0] * b[1] + a[1] * b[0],
+        ],
+    }
+}
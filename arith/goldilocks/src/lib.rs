@@ -1,14 +1,10 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+/// Goldilocks, the field of integers modulo 2^64 - 2^32 + 1
+mod goldilocks;
+pub use goldilocks::{GoldilocksField, GOLDILOCKS_MOD};
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Degree 2 extension field for Goldilocks
+mod goldilocks_ext;
+pub use goldilocks_ext::GoldilocksExt2;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+use std::io::Cursor;
+
+use arith::{
+    random_extension_field_tests, random_field_tests, random_inversion_tests,
+    random_simd_field_tests, Field, FieldSerde,
+};
+use ark_std::test_rng;
+
+use crate::{GoldilocksExt2, GoldilocksField, GOLDILOCKS_MOD};
+
+#[test]
+fn test_base_field() {
+    random_field_tests::<GoldilocksField>("Goldilocks".to_string());
+    random_simd_field_tests::<GoldilocksField>("Goldilocks".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<GoldilocksField, _>(&mut rng, "Goldilocks".to_string());
+}
+
+#[test]
+fn test_ext_field() {
+    random_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
+    random_extension_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
+    random_simd_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<GoldilocksExt2, _>(&mut rng, "Goldilocks Ext2".to_string());
+}
+
+#[test]
+fn test_reduction() {
+    let minus_one = GoldilocksField::from(GOLDILOCKS_MOD - 1);
+    assert_eq!(minus_one, -GoldilocksField::ONE);
+    assert_eq!(minus_one * minus_one, GoldilocksField::ONE);
+    assert_eq!(minus_one + minus_one, -GoldilocksField::from(2u32));
+    assert_eq!(GoldilocksField::ZERO - GoldilocksField::ONE, minus_one);
+    assert_eq!(
+        GoldilocksField::from(3u32).inv().unwrap(),
+        GoldilocksField::from(12297829379609722881u64)
+    );
+    // 2^96 = -1
+    assert_eq!(GoldilocksField::from(2u32).exp(96), minus_one);
+}
+
+#[test]
+fn test_serde() {
+    let a = GoldilocksExt2 {
+        v: [GoldilocksField::from(256u32 + 2), -GoldilocksField::ONE],
+    };
+    let mut buffer = vec![];
+    a.serialize_into(&mut buffer).unwrap();
+    assert_eq!(buffer.len(), GoldilocksExt2::SERIALIZED_SIZE);
+    let b = GoldilocksExt2::deserialize_from(&mut Cursor::new(buffer)).unwrap();
+    assert_eq!(a, b);
+
+    // non canonical encodings are rejected
+    let bytes = GOLDILOCKS_MOD.to_le_bytes();
+    assert!(GoldilocksField::deserialize_from(&bytes[..]).is_err());
+}
+
+/// Compare to test vectors computed with Python's big integers
+#[test]
+fn test_vectors() {
+    let a = GoldilocksExt2 {
+        v: [GoldilocksField::from(1u32), GoldilocksField::from(2u32)],
+    };
+    let b = GoldilocksExt2 {
+        v: [GoldilocksField::from(3u32), GoldilocksField::from(4u32)],
+    };
+    let expected_prod = GoldilocksExt2 {
+        v: [GoldilocksField::from(59u32), GoldilocksField::from(10u32)],
+    };
+    assert_eq!(expected_prod, a * b);
+
+    let expected_inv = GoldilocksExt2 {
+        v: [
+            GoldilocksField::from(4782489203181558898u64),
+            GoldilocksField::from(8881765663051466525u64),
+        ],
+    };
+    assert_eq!(expected_inv, a.inv().unwrap());
+    let a_pow_11 = GoldilocksExt2 {
+        v: [
+            GoldilocksField::from(301134373u32),
+            GoldilocksField::from(117254894u32),
+        ],
+    };
+    assert_eq!(a_pow_11, a.exp(11));
+}
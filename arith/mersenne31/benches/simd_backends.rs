@@ -0,0 +1,54 @@
+//! The M31 packed fields of each backend of the cpu, on the same elements, e.g., of a build for
+//! the baseline of the arch, the wider backends being at least as fast as the narrower ones:
+//! `cargo bench --bench simd_backends`. Only the M31 fields are of a backend picked at runtime.
+
+use arith::{Field, SimdBackend};
+use ark_std::test_rng;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, Criterion,
+};
+use mersenne31::simd::{self, M31SimdBackend};
+
+fn bench_mul<F: Field>(group: &mut BenchmarkGroup<'_, WallTime>, backend: SimdBackend) {
+    if !backend.is_available() {
+        return;
+    }
+    let mut rng = test_rng();
+    let elements = [(); 4].map(|_| F::random_unsafe(&mut rng));
+    group.bench_function(backend.to_string(), |b| {
+        b.iter_batched(
+            || elements,
+            |[mut x, mut y, mut z, mut w]| {
+                for _ in 0..25 {
+                    (x, y, z, w) = (x * y, y * z, z * w, w * x);
+                }
+                (x, y, z, w)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// `bench_mul` of the field `$field` of each backend of the arch, widest first.
+macro_rules! bench_backends {
+    ($c:expr, $name:expr, $field:ident) => {{
+        let mut group = $c.benchmark_group($name);
+        #[cfg(target_arch = "x86_64")]
+        {
+            bench_mul::<<simd::Avx512 as M31SimdBackend>::$field>(&mut group, SimdBackend::Avx512);
+            bench_mul::<<simd::Avx2 as M31SimdBackend>::$field>(&mut group, SimdBackend::Avx2);
+        }
+        #[cfg(target_arch = "aarch64")]
+        bench_mul::<<simd::Neon as M31SimdBackend>::$field>(&mut group, SimdBackend::Neon);
+        bench_mul::<<simd::Scalar as M31SimdBackend>::$field>(&mut group, SimdBackend::Scalar);
+        group.finish();
+    }};
+}
+
+fn simd_backends_benchmark(c: &mut Criterion) {
+    bench_backends!(c, "mul-throughput<M31x16> 100x", M31x16);
+    bench_backends!(c, "mul-throughput<M31Ext3x16> 100x", M31Ext3x16);
+}
+
+criterion_group!(bench, simd_backends_benchmark);
+criterion_main!(bench);
@@ -6,7 +6,6 @@ pub use m31::M31;
 
 /// SIMDx16 for Mersenne-31
 mod m31x16;
-pub use m31x16::M31x16;
 
 /// Degree 3 extension field for Mersenne-31
 mod m31_ext;
@@ -14,7 +13,21 @@ pub use m31_ext::M31Ext3;
 
 /// SIMDx16 for Degree 3 extension field for Mersenne-31
 mod m31_ext3x16;
-pub use m31_ext3x16::M31Ext3x16;
+
+/// The SIMDx16 fields of each backend, those of the build re-exported below
+pub mod simd;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "aarch64")] {
+        pub use simd::neon::{M31Ext3x16, M31x16};
+    } else if #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))] {
+        pub use simd::avx512::{M31Ext3x16, M31x16};
+    } else if #[cfg(target_arch = "x86_64")] {
+        pub use simd::avx2::{M31Ext3x16, M31x16};
+    } else {
+        pub use simd::scalar::{M31Ext3x16, M31x16};
+    }
+}
 
 #[cfg(test)]
 mod tests;
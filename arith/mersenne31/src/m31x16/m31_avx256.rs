@@ -384,31 +384,7 @@ impl Mul<&M31> for AVXM31 {
 
     #[inline(always)]
     fn mul(self, rhs: &M31) -> Self::Output {
-        let rhsv = AVXM31::pack_full(*rhs);
-        unsafe {
-            let mut res: [__m256i; 2] = [_mm256_setzero_si256(); 2];
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..res.len() {
-                let rhs_evn = rhsv.v[i];
-                let lhs_odd_dbl = _mm256_srli_epi64(self.v[i], 31);
-                let lhs_evn_dbl = _mm256_add_epi32(self.v[i], self.v[i]);
-                let rhs_odd = movehdup_epi32(rhsv.v[i]);
-
-                let prod_odd_dbl = _mm256_mul_epu32(lhs_odd_dbl, rhs_odd);
-                let prod_evn_dbl = _mm256_mul_epu32(lhs_evn_dbl, rhs_evn);
-
-                let prod_odd_dup = moveldup_epi32(prod_odd_dbl);
-                let prod_evn_dup = movehdup_epi32(prod_evn_dbl);
-                let prod_lo_dbl = _mm256_blend_epi32(prod_evn_dbl, prod_odd_dup, ODDS);
-                let prod_hi = _mm256_blend_epi32(prod_odd_dbl, prod_evn_dup, EVENS);
-                // Right shift to undo the doubling.
-                let prod_lo = _mm256_srli_epi32::<1>(prod_lo_dbl);
-
-                // Standard addition of two 31-bit values.
-                res[i] = add(prod_lo, prod_hi);
-            }
-            AVXM31 { v: res }
-        }
+        mul_internal(&self, &AVXM31::pack_full(*rhs))
     }
 }
 
@@ -444,8 +420,8 @@ impl Neg for AVXM31 {
         AVXM31 {
             v: unsafe {
                 [
-                    _mm256_xor_epi32(self.v[0], PACKED_MOD),
-                    _mm256_xor_epi32(self.v[1], PACKED_MOD),
+                    _mm256_xor_si256(self.v[0], PACKED_MOD),
+                    _mm256_xor_si256(self.v[1], PACKED_MOD),
                 ]
             },
         }
@@ -454,69 +430,81 @@ impl Neg for AVXM31 {
 
 #[inline(always)]
 fn add_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
-    unsafe {
-        let mut result = [
-            _mm256_add_epi32(a.v[0], b.v[0]),
-            _mm256_add_epi32(a.v[1], b.v[1]),
-        ];
-        let subx = [
-            _mm256_sub_epi32(result[0], PACKED_MOD),
-            _mm256_sub_epi32(result[1], PACKED_MOD),
-        ];
-        result = [
-            _mm256_min_epu32(result[0], subx[0]),
-            _mm256_min_epu32(result[1], subx[1]),
-        ];
-
-        AVXM31 { v: result }
-    }
+    // the backend is only picked on the cpus of avx2, see `SimdBackend::select`
+    unsafe { add_avx2(a, b) }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn add_avx2(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    let mut result = [
+        _mm256_add_epi32(a.v[0], b.v[0]),
+        _mm256_add_epi32(a.v[1], b.v[1]),
+    ];
+    let subx = [
+        _mm256_sub_epi32(result[0], PACKED_MOD),
+        _mm256_sub_epi32(result[1], PACKED_MOD),
+    ];
+    result = [
+        _mm256_min_epu32(result[0], subx[0]),
+        _mm256_min_epu32(result[1], subx[1]),
+    ];
+
+    AVXM31 { v: result }
 }
 
 #[inline(always)]
 fn sub_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    // the backend is only picked on the cpus of avx2, see `SimdBackend::select`
+    unsafe { sub_avx2(a, b) }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn sub_avx2(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    let t = [
+        _mm256_sub_epi32(a.v[0], b.v[0]),
+        _mm256_sub_epi32(a.v[1], b.v[1]),
+    ];
+    let subx = [
+        _mm256_add_epi32(t[0], PACKED_MOD),
+        _mm256_add_epi32(t[1], PACKED_MOD),
+    ];
     AVXM31 {
-        v: unsafe {
-            let t = [
-                _mm256_sub_epi32(a.v[0], b.v[0]),
-                _mm256_sub_epi32(a.v[1], b.v[1]),
-            ];
-            let subx = [
-                _mm256_add_epi32(t[0], PACKED_MOD),
-                _mm256_add_epi32(t[1], PACKED_MOD),
-            ];
-            [
-                _mm256_min_epu32(t[0], subx[0]),
-                _mm256_min_epu32(t[1], subx[1]),
-            ]
-        },
+        v: [
+            _mm256_min_epu32(t[0], subx[0]),
+            _mm256_min_epu32(t[1], subx[1]),
+        ],
     }
 }
 
-#[inline]
+#[inline(always)]
 fn mul_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    // the backend is only picked on the cpus of avx2, see `SimdBackend::select`
+    unsafe { mul_avx2(a, b) }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn mul_avx2(a: &AVXM31, b: &AVXM31) -> AVXM31 {
     // credit: https://github.com/Plonky3/Plonky3/blob/eeb4e37b20127c4daa871b2bad0df30a7c7380db/mersenne-31/src/x86_64_avx2/packing.rs#L154
-    unsafe {
-        let mut res: [__m256i; 2] = [_mm256_setzero_si256(); 2];
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..res.len() {
-            let rhs_evn = b.v[i];
-            let lhs_odd_dbl = _mm256_srli_epi64(a.v[i], 31);
-            let lhs_evn_dbl = _mm256_add_epi32(a.v[i], a.v[i]);
-            let rhs_odd = movehdup_epi32(b.v[i]);
-
-            let prod_odd_dbl = _mm256_mul_epu32(lhs_odd_dbl, rhs_odd);
-            let prod_evn_dbl = _mm256_mul_epu32(lhs_evn_dbl, rhs_evn);
-
-            let prod_odd_dup = moveldup_epi32(prod_odd_dbl);
-            let prod_evn_dup = movehdup_epi32(prod_evn_dbl);
-            let prod_lo_dbl = _mm256_blend_epi32(prod_evn_dbl, prod_odd_dup, ODDS);
-            let prod_hi = _mm256_blend_epi32(prod_odd_dbl, prod_evn_dup, EVENS);
-            // Right shift to undo the doubling.
-            let prod_lo = _mm256_srli_epi32::<1>(prod_lo_dbl);
-
-            // Standard addition of two 31-bit values.
-            res[i] = add(prod_lo, prod_hi);
-        }
-        AVXM31 { v: res }
-    }
+    let mut res: [__m256i; 2] = [_mm256_setzero_si256(); 2];
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..res.len() {
+        let rhs_evn = b.v[i];
+        let lhs_odd_dbl = _mm256_srli_epi64(a.v[i], 31);
+        let lhs_evn_dbl = _mm256_add_epi32(a.v[i], a.v[i]);
+        let rhs_odd = movehdup_epi32(b.v[i]);
+
+        let prod_odd_dbl = _mm256_mul_epu32(lhs_odd_dbl, rhs_odd);
+        let prod_evn_dbl = _mm256_mul_epu32(lhs_evn_dbl, rhs_evn);
+
+        let prod_odd_dup = moveldup_epi32(prod_odd_dbl);
+        let prod_evn_dup = movehdup_epi32(prod_evn_dbl);
+        let prod_lo_dbl = _mm256_blend_epi32(prod_evn_dbl, prod_odd_dup, ODDS);
+        let prod_hi = _mm256_blend_epi32(prod_odd_dbl, prod_evn_dup, EVENS);
+        // Right shift to undo the doubling.
+        let prod_lo = _mm256_srli_epi32::<1>(prod_lo_dbl);
+
+        // Standard addition of two 31-bit values.
+        res[i] = add(prod_lo, prod_hi);
+    }
+    AVXM31 { v: res }
 }
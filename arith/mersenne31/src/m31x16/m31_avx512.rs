@@ -341,25 +341,7 @@ impl Mul<&M31> for AVXM31 {
 
     #[inline(always)]
     fn mul(self, rhs: &M31) -> Self::Output {
-        let rhsv = AVXM31::pack_full(*rhs);
-        unsafe {
-            let rhs_evn = rhsv.v;
-            let lhs_odd_dbl = _mm512_srli_epi64(self.v, 31);
-            let lhs_evn_dbl = _mm512_add_epi32(self.v, self.v);
-            let rhs_odd = movehdup_epi32(rhsv.v);
-
-            let prod_odd_dbl = _mm512_mul_epu32(lhs_odd_dbl, rhs_odd);
-            let prod_evn_dbl = _mm512_mul_epu32(lhs_evn_dbl, rhs_evn);
-
-            let prod_lo_dbl = mask_moveldup_epi32(prod_evn_dbl, ODDS, prod_odd_dbl);
-            let prod_hi = mask_movehdup_epi32(prod_odd_dbl, EVENS, prod_evn_dbl);
-            // Right shift to undo the doubling.
-            let prod_lo = _mm512_srli_epi32::<1>(prod_lo_dbl);
-
-            // Standard addition of two 31-bit values.
-            let res = add(prod_lo, prod_hi);
-            AVXM31 { v: res }
-        }
+        mul_internal(&self, &AVXM31::pack_full(*rhs))
     }
 }
 
@@ -408,45 +390,57 @@ fn movehdup_epi32(x: __m512i) -> __m512i {
 
 #[inline(always)]
 fn add_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
-    unsafe {
-        let mut result = _mm512_add_epi32(a.v, b.v);
-        let subx = _mm512_sub_epi32(result, PACKED_MOD);
-        result = _mm512_min_epu32(result, subx);
+    // the backend is only picked on the cpus of avx512f, see `SimdBackend::select`
+    unsafe { add_avx512(a, b) }
+}
 
-        AVXM31 { v: result }
-    }
+#[target_feature(enable = "avx512f")]
+unsafe fn add_avx512(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    let mut result = _mm512_add_epi32(a.v, b.v);
+    let subx = _mm512_sub_epi32(result, PACKED_MOD);
+    result = _mm512_min_epu32(result, subx);
+
+    AVXM31 { v: result }
 }
 
 #[inline(always)]
 fn sub_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    // the backend is only picked on the cpus of avx512f, see `SimdBackend::select`
+    unsafe { sub_avx512(a, b) }
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn sub_avx512(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    let t = _mm512_sub_epi32(a.v, b.v);
+    let subx = _mm512_add_epi32(t, PACKED_MOD);
     AVXM31 {
-        v: unsafe {
-            let t = _mm512_sub_epi32(a.v, b.v);
-            let subx = _mm512_add_epi32(t, PACKED_MOD);
-            _mm512_min_epu32(t, subx)
-        },
+        v: _mm512_min_epu32(t, subx),
     }
 }
 
-#[inline]
+#[inline(always)]
 fn mul_internal(a: &AVXM31, b: &AVXM31) -> AVXM31 {
-    // credit: https://github.com/Plonky3/Plonky3/blob/eeb4e37b20127c4daa871b2bad0df30a7c7380db/mersenne-31/src/x86_64_avx2/packing.rs#L154
-    unsafe {
-        let rhs_evn = b.v;
-        let lhs_odd_dbl = _mm512_srli_epi64(a.v, 31);
-        let lhs_evn_dbl = _mm512_add_epi32(a.v, a.v);
-        let rhs_odd = movehdup_epi32(b.v);
-
-        let prod_odd_dbl = _mm512_mul_epu32(lhs_odd_dbl, rhs_odd);
-        let prod_evn_dbl = _mm512_mul_epu32(lhs_evn_dbl, rhs_evn);
-
-        let prod_lo_dbl = mask_moveldup_epi32(prod_evn_dbl, ODDS, prod_odd_dbl);
-        let prod_hi = mask_movehdup_epi32(prod_odd_dbl, EVENS, prod_evn_dbl);
-        // Right shift to undo the doubling.
-        let prod_lo = _mm512_srli_epi32::<1>(prod_lo_dbl);
+    // the backend is only picked on the cpus of avx512f, see `SimdBackend::select`
+    unsafe { mul_avx512(a, b) }
+}
 
-        // Standard addition of two 31-bit values.
-        let res = add(prod_lo, prod_hi);
-        AVXM31 { v: res }
-    }
+#[target_feature(enable = "avx512f")]
+unsafe fn mul_avx512(a: &AVXM31, b: &AVXM31) -> AVXM31 {
+    // credit: https://github.com/Plonky3/Plonky3/blob/eeb4e37b20127c4daa871b2bad0df30a7c7380db/mersenne-31/src/x86_64_avx2/packing.rs#L154
+    let rhs_evn = b.v;
+    let lhs_odd_dbl = _mm512_srli_epi64(a.v, 31);
+    let lhs_evn_dbl = _mm512_add_epi32(a.v, a.v);
+    let rhs_odd = movehdup_epi32(b.v);
+
+    let prod_odd_dbl = _mm512_mul_epu32(lhs_odd_dbl, rhs_odd);
+    let prod_evn_dbl = _mm512_mul_epu32(lhs_evn_dbl, rhs_evn);
+
+    let prod_lo_dbl = mask_moveldup_epi32(prod_evn_dbl, ODDS, prod_odd_dbl);
+    let prod_hi = mask_movehdup_epi32(prod_odd_dbl, EVENS, prod_evn_dbl);
+    // Right shift to undo the doubling.
+    let prod_lo = _mm512_srli_epi32::<1>(prod_lo_dbl);
+
+    // Standard addition of two 31-bit values.
+    let res = add(prod_lo, prod_hi);
+    AVXM31 { v: res }
 }
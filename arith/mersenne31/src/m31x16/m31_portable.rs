@@ -0,0 +1,241 @@
+// The lanes are operated on one after the other, as for the portable BabyBearx16, on the targets
+// without AVX or NEON, e.g., wasm. The bytes are those of the simd backends, 16 little-endian u32.
+
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::M31;
+
+const M31_PACK_SIZE: usize = 16;
+
+/// PortableM31 packs 16 M31 elements in an array
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PortableM31 {
+    pub v: [M31; M31_PACK_SIZE],
+}
+
+field_common!(PortableM31);
+
+impl PortableM31 {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: M31) -> PortableM31 {
+        PortableM31 {
+            v: [x; M31_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn map(&self, f: impl Fn(&M31) -> M31) -> PortableM31 {
+        PortableM31 {
+            v: std::array::from_fn(|i| f(&self.v[i])),
+        }
+    }
+
+    #[inline(always)]
+    fn zip_map(&self, rhs: &Self, f: impl Fn(&M31, &M31) -> M31) -> PortableM31 {
+        PortableM31 {
+            v: std::array::from_fn(|i| f(&self.v[i], &rhs.v[i])),
+        }
+    }
+}
+
+impl FieldSerde for PortableM31 {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|lane| lane.serialize_into(&mut writer))
+    }
+
+    /// Reduces the lanes, as `M31` does.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [M31::ZERO; M31_PACK_SIZE];
+        for lane in &mut v {
+            *lane = M31::deserialize_from(&mut reader)?;
+        }
+        Ok(PortableM31 { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buf = [0u8; 32];
+        reader.read_exact(&mut buf)?;
+        assert!(
+            buf.iter().skip(4).all(|&x| x == 0),
+            "non-zero byte found in witness byte"
+        );
+        Ok(Self::pack_full(
+            u32::from_le_bytes(buf[..4].try_into().unwrap()).into(),
+        ))
+    }
+}
+
+impl Field for PortableM31 {
+    const NAME: &'static str = "Portable Packed Mersenne 31";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = PortableM31 {
+        v: [M31::ZERO; M31_PACK_SIZE],
+    };
+
+    const ONE: Self = PortableM31 {
+        v: [M31::ONE; M31_PACK_SIZE],
+    };
+
+    const INV_2: Self = PortableM31 {
+        v: [M31::INV_2; M31_PACK_SIZE],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|lane| lane.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        PortableM31 {
+            v: std::array::from_fn(|_| M31::random_unsafe(&mut rng)),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        PortableM31 {
+            v: std::array::from_fn(|_| M31::random_bool(&mut rng)),
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        self.map(|lane| lane.exp(exponent))
+    }
+
+    /// Lane by lane, none if any lane is zero.
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|lane| lane.is_zero()) {
+            return None;
+        }
+        Some(self.map(|lane| lane.inv().unwrap()))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(M31::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for PortableM31 {
+    type Scalar = M31;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        M31_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == M31_PACK_SIZE);
+        PortableM31 {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<M31> for PortableM31 {
+    #[inline(always)]
+    fn from(x: M31) -> Self {
+        PortableM31::pack_full(x)
+    }
+}
+
+impl Mul<&M31> for PortableM31 {
+    type Output = PortableM31;
+
+    #[inline(always)]
+    fn mul(self, rhs: &M31) -> Self::Output {
+        self * *rhs
+    }
+}
+
+impl Mul<M31> for PortableM31 {
+    type Output = PortableM31;
+
+    #[inline(always)]
+    fn mul(self, rhs: M31) -> Self::Output {
+        self.map(|lane| *lane * rhs)
+    }
+}
+
+impl Add<M31> for PortableM31 {
+    type Output = PortableM31;
+
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        self.map(|lane| *lane + rhs)
+    }
+}
+
+impl From<u32> for PortableM31 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        PortableM31::pack_full(M31::from(x))
+    }
+}
+
+impl Neg for PortableM31 {
+    type Output = PortableM31;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self.map(|lane| -*lane)
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    a.zip_map(b, |a, b| *a + b)
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    a.zip_map(b, |a, b| *a - b)
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    a.zip_map(b, |a, b| *a * b)
+}
@@ -0,0 +1,93 @@
+//! The packed fields of each simd backend of the target arch, to be picked at runtime with
+//! `SimdBackend::select`, through the configs generic over an `M31SimdBackend`.
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Mul},
+};
+
+use arith::{ExtensionField, SimdBackend, SimdField};
+
+use crate::{M31Ext3, M31};
+
+/// The packed fields of a backend, of the same bytes and values whatever the backend.
+pub trait M31SimdBackend:
+    Debug + Clone + Copy + Default + PartialEq + Send + Sync + 'static
+{
+    const BACKEND: SimdBackend;
+
+    type M31x16: SimdField<Scalar = M31> + Send + Sync;
+
+    type M31Ext3x16: ExtensionField<BaseField = Self::M31x16>
+        + SimdField<Scalar = M31Ext3>
+        + Mul<M31, Output = Self::M31Ext3x16>
+        + Add<M31, Output = Self::M31Ext3x16>
+        + Send
+        + Sync;
+}
+
+macro_rules! simd_backend {
+    ($backend:ident, $module:ident) => {
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $backend;
+
+        impl M31SimdBackend for $backend {
+            const BACKEND: SimdBackend = SimdBackend::$backend;
+
+            type M31x16 = $module::M31x16;
+
+            type M31Ext3x16 = $module::M31Ext3x16;
+        }
+    };
+}
+
+/// The lanes one after the other
+pub mod scalar {
+    pub use crate::m31x16::m31_portable::PortableM31 as M31x16;
+
+    crate::m31_ext3x16::m31_ext3x16!("Portable Vectorized Mersenne 31 Extension 3");
+}
+simd_backend!(Scalar, scalar);
+
+#[cfg(target_arch = "x86_64")]
+pub mod avx2 {
+    pub use crate::m31x16::m31_avx256::AVXM31 as M31x16;
+
+    crate::m31_ext3x16::m31_ext3x16!("AVX2 Vectorized Mersenne 31 Extension 3");
+}
+#[cfg(target_arch = "x86_64")]
+simd_backend!(Avx2, avx2);
+
+#[cfg(target_arch = "x86_64")]
+pub mod avx512 {
+    pub use crate::m31x16::m31_avx512::AVXM31 as M31x16;
+
+    crate::m31_ext3x16::m31_ext3x16!("AVX512 Vectorized Mersenne 31 Extension 3");
+}
+#[cfg(target_arch = "x86_64")]
+simd_backend!(Avx512, avx512);
+
+#[cfg(target_arch = "aarch64")]
+pub mod neon {
+    pub use crate::m31x16::m31_neon::NeonM31 as M31x16;
+
+    crate::m31_ext3x16::m31_ext3x16!("Neon Vectorized Mersenne 31 Extension 3");
+}
+#[cfg(target_arch = "aarch64")]
+simd_backend!(Neon, neon);
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "aarch64")] {
+        /// The backend of the target features of the build, see `SimdBackend::compiled`.
+        pub type CompiledBackend = Neon;
+    } else if #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))] {
+        /// The backend of the target features of the build, see `SimdBackend::compiled`.
+        pub type CompiledBackend = Avx512;
+    } else if #[cfg(target_arch = "x86_64")] {
+        /// The backend of the target features of the build, see `SimdBackend::compiled`.
+        pub type CompiledBackend = Avx2;
+    } else {
+        /// The backend of the target features of the build, see `SimdBackend::compiled`.
+        pub type CompiledBackend = Scalar;
+    }
+}
@@ -1,12 +1,13 @@
 use std::io::Cursor;
 
-use arith::Field;
 use arith::{
     random_extension_field_tests, random_field_tests, random_inversion_tests,
     random_simd_field_tests, FieldSerde,
 };
+use arith::{Field, SimdBackend, SimdField};
 use ark_std::test_rng;
 
+use crate::simd::{self, M31SimdBackend};
 use crate::M31Ext3;
 use crate::M31Ext3x16;
 use crate::{M31x16, M31};
@@ -22,6 +23,8 @@ fn get_avx_version() -> &'static str {
         return "AVX2 (256-bit)";
     } else if cfg!(target_arch = "aarch64") {
         return "arm64";
+    } else if cfg!(not(target_arch = "x86_64")) {
+        return "Not x86_64 architecture";
     }
     "Unknown"
 }
@@ -77,6 +80,54 @@ fn test_ext_field() {
     random_simd_field_tests::<M31Ext3x16>("Simd M31 Ext3".to_string());
 }
 
+/// The fields of the backend, of the values and the bytes of the scalar one.
+fn simd_backend_tests<B: M31SimdBackend>() {
+    let name = B::BACKEND.to_string();
+    random_field_tests::<B::M31x16>(name.clone());
+    random_simd_field_tests::<B::M31x16>(name.clone());
+    random_field_tests::<B::M31Ext3x16>(name.clone());
+    random_extension_field_tests::<B::M31Ext3x16>(name.clone());
+    random_simd_field_tests::<B::M31Ext3x16>(name);
+
+    let mut rng = test_rng();
+    let a = (0..16)
+        .map(|_| M31Ext3::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let b = (0..16)
+        .map(|_| M31Ext3::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let product = B::M31Ext3x16::pack(&a) * B::M31Ext3x16::pack(&b);
+    let scalar_product = simd::scalar::M31Ext3x16::pack(&a) * simd::scalar::M31Ext3x16::pack(&b);
+    assert_eq!(product.unpack(), scalar_product.unpack());
+
+    let (mut bytes, mut scalar_bytes) = (vec![], vec![]);
+    product.serialize_into(&mut bytes).unwrap();
+    scalar_product.serialize_into(&mut scalar_bytes).unwrap();
+    assert_eq!(bytes, scalar_bytes);
+}
+
+#[test]
+fn test_simd_backends() {
+    simd_backend_tests::<simd::Scalar>();
+    #[cfg(target_arch = "x86_64")]
+    {
+        if SimdBackend::Avx2.is_available() {
+            simd_backend_tests::<simd::Avx2>();
+        }
+        if SimdBackend::Avx512.is_available() {
+            simd_backend_tests::<simd::Avx512>();
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    if SimdBackend::Neon.is_available() {
+        simd_backend_tests::<simd::Neon>();
+    }
+
+    assert!(SimdBackend::detect().is_available());
+    assert_eq!("AVX2".parse::<SimdBackend>(), Ok(SimdBackend::Avx2));
+    assert!("sse".parse::<SimdBackend>().is_err());
+}
+
 /// Compare to test vectors generated in SageMath
 #[test]
 fn test_vectors() {
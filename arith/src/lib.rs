@@ -9,12 +9,18 @@ pub use extension_field::*;
 mod bn254;
 pub use bn254::*;
 
+mod native_field;
+pub use native_field::*;
+
 mod simd_field;
 pub use simd_field::*;
 
 mod serde;
 pub use serde::*;
 
+mod simd_backend;
+pub use simd_backend::*;
+
 mod macros;
 
 mod benches;
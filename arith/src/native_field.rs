@@ -0,0 +1,404 @@
+//! Prime fields with a user defined modulus.
+//!
+//! `NativeFieldArithmetic<BITS, M>` is the field of integers modulo the prime `M::MODULUS`,
+//! which must fit in `BITS` bits, with `BITS` one of 64, 128, 192 and 256.
+//! The elements are serialized in `BITS / 8` bytes, and the arithmetic is done on
+//! `crypto_bigint::U256` with generic reductions, so it is a lot slower than the dedicated
+//! field implementations. It is meant for experimenting with new fields.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crypto_bigint::{Encoding, U256};
+use rand::RngCore;
+
+use crate::{
+    ExtensionField, Field, FieldForECC, FieldSerde, FieldSerdeError, FieldSerdeResult, SimdField,
+};
+
+/// The modulus of a native field, implemented by a marker type.
+pub trait NativeModulus:
+    'static + Copy + Debug + Default + PartialEq + Eq + Hash + PartialOrd + Ord + Send + Sync
+{
+    const NAME: &'static str;
+
+    /// An odd prime.
+    const MODULUS: U256;
+}
+
+/// Deterministic Miller-Rabin primality test, exact for all u64.
+pub const fn valid_prime(p: u64) -> bool {
+    // these bases are enough for all n < 3.3 * 10^24
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if p < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < BASES.len() {
+        if p == BASES[i] {
+            return true;
+        }
+        if p % BASES[i] == 0 {
+            return false;
+        }
+        i += 1;
+    }
+
+    // p - 1 = d * 2^s with d odd
+    let mut d = p - 1;
+    let mut s = 0;
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+
+    let mut i = 0;
+    'bases: while i < BASES.len() {
+        let mut x = pow_mod_u64(BASES[i], d, p);
+        i += 1;
+        if x == 1 || x == p - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod_u64(x, x, p);
+            if x == p - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+const fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+const fn pow_mod_u64(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut res = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = mul_mod_u64(res, base, m);
+        }
+        base = mul_mod_u64(base, base, m);
+        exp >>= 1;
+    }
+    res
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NativeFieldArithmetic<const BITS: usize, M: NativeModulus> {
+    /// Always reduced modulo `M::MODULUS`.
+    v: U256,
+    _marker: PhantomData<M>,
+}
+
+impl<const BITS: usize, M: NativeModulus> NativeFieldArithmetic<BITS, M> {
+    const WIDTH_CHECK: () = {
+        assert!(
+            BITS == 64 || BITS == 128 || BITS == 192 || BITS == 256,
+            "unsupported native field width"
+        );
+        assert!(
+            M::MODULUS.bits_vartime() <= BITS,
+            "the modulus does not fit in the field width"
+        );
+    };
+
+    #[inline(always)]
+    const fn new_unchecked(v: U256) -> Self {
+        Self {
+            v,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The element `v mod p`.
+    #[inline(always)]
+    pub fn from_uint(v: U256) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::WIDTH_CHECK;
+        Self::new_unchecked(v.const_rem(&M::MODULUS).0)
+    }
+
+    /// The canonical representative of the element, in [0, p).
+    #[inline(always)]
+    pub fn to_uint(&self) -> U256 {
+        self.v
+    }
+
+    fn pow_vartime(&self, exponent: &U256) -> Self {
+        let mut res = Self::ONE;
+        for i in (0..exponent.bits_vartime()).rev() {
+            res = res.square();
+            if exponent.bit_vartime(i) {
+                res *= self;
+            }
+        }
+        res
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> Neg for NativeFieldArithmetic<BITS, M> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new_unchecked(self.v.neg_mod(&M::MODULUS))
+    }
+}
+
+macro_rules! native_field_binop {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:expr) => {
+        impl<const BITS: usize, M: NativeModulus> $trait for NativeFieldArithmetic<BITS, M> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $fn(self, rhs: Self) -> Self {
+                Self::new_unchecked($op(&self.v, &rhs.v, &M::MODULUS))
+            }
+        }
+
+        impl<'a, const BITS: usize, M: NativeModulus> $trait<&'a Self>
+            for NativeFieldArithmetic<BITS, M>
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $fn(self, rhs: &'a Self) -> Self {
+                self.$fn(*rhs)
+            }
+        }
+
+        impl<const BITS: usize, M: NativeModulus> $assign_trait for NativeFieldArithmetic<BITS, M> {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs)
+            }
+        }
+
+        impl<'a, const BITS: usize, M: NativeModulus> $assign_trait<&'a Self>
+            for NativeFieldArithmetic<BITS, M>
+        {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: &'a Self) {
+                *self = (*self).$fn(*rhs)
+            }
+        }
+    };
+}
+
+native_field_binop!(Add, add, AddAssign, add_assign, U256::add_mod);
+native_field_binop!(Sub, sub, SubAssign, sub_assign, U256::sub_mod);
+native_field_binop!(Mul, mul, MulAssign, mul_assign, mul_mod);
+
+#[inline(always)]
+fn mul_mod(a: &U256, b: &U256, p: &U256) -> U256 {
+    U256::const_rem_wide(a.mul_wide(b), p).0
+}
+
+impl<const BITS: usize, M: NativeModulus, T> Sum<T> for NativeFieldArithmetic<BITS, M>
+where
+    T: core::borrow::Borrow<Self>,
+{
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, item| acc + item.borrow())
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus, T> Product<T> for NativeFieldArithmetic<BITS, M>
+where
+    T: core::borrow::Borrow<Self>,
+{
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, item| acc * item.borrow())
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> From<u32> for NativeFieldArithmetic<BITS, M> {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        Self::from_uint(U256::from_u32(x))
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> FieldSerde for NativeFieldArithmetic<BITS, M> {
+    const SERIALIZED_SIZE: usize = BITS / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(&self.v.to_le_bytes()[..Self::SERIALIZED_SIZE])?;
+        Ok(())
+    }
+
+    /// Fails on non canonical encodings, i.e., values not smaller than the modulus.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buffer = [0u8; 32];
+        reader.read_exact(&mut buffer[..Self::SERIALIZED_SIZE])?;
+        let v = U256::from_le_bytes(buffer);
+        if v >= M::MODULUS {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(Self::new_unchecked(v))
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buffer = [0u8; 32];
+        reader.read_exact(&mut buffer)?;
+        let v = U256::from_le_bytes(buffer);
+        if v >= M::MODULUS {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(Self::new_unchecked(v))
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> Field for NativeFieldArithmetic<BITS, M> {
+    const NAME: &'static str = M::NAME;
+
+    /// The in-memory size, of the whole `U256` whatever the width, as the buffers of the mpi
+    /// collectives are of `len * SIZE` bytes; `SERIALIZED_SIZE` is of the width only.
+    const SIZE: usize = std::mem::size_of::<Self>();
+
+    const FIELD_SIZE: usize = BITS;
+
+    const ZERO: Self = Self::new_unchecked(U256::ZERO);
+
+    const ONE: Self = Self::new_unchecked(U256::ONE);
+
+    /// (p + 1) / 2, since p is odd
+    const INV_2: Self = Self::new_unchecked(M::MODULUS.shr_vartime(1).wrapping_add(&U256::ONE));
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == U256::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    /// The reduction of 256 random bits, which is close to uniform when p is much smaller than
+    /// 2^256 or close to it.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self::from_uint(U256::from_le_bytes(bytes))
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        Self::from(rng.next_u32() & 1)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut res = Self::ONE;
+        for i in (0..128 - exponent.leading_zeros()).rev() {
+            res = res.square();
+            if (exponent >> i) & 1 == 1 {
+                res *= self;
+            }
+        }
+        res
+    }
+
+    /// a^(p - 2)
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.pow_vartime(&M::MODULUS.wrapping_sub(&U256::from_u8(2))))
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v.as_words()[0] as u32
+    }
+
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::from_uint(U256::from_le_bytes(*bytes))
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> FieldForECC for NativeFieldArithmetic<BITS, M> {
+    fn modulus() -> ethnum::U256 {
+        ethnum::U256::from_le_bytes(M::MODULUS.to_le_bytes())
+    }
+
+    fn from_u256(x: ethnum::U256) -> Self {
+        Self::from_uint(U256::from_le_bytes(x.to_le_bytes()))
+    }
+
+    fn to_u256(&self) -> ethnum::U256 {
+        ethnum::U256::from_le_bytes(self.v.to_le_bytes())
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> ExtensionField for NativeFieldArithmetic<BITS, M> {
+    const DEGREE: usize = 1;
+
+    /// Extension Field over X-1 which is self
+    const W: u32 = 1;
+
+    // placeholder, doesn't make sense for a prime field
+    const X: Self = Self::ZERO;
+
+    type BaseField = Self;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        *self * base
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        *self + base
+    }
+
+    fn mul_by_x(&self) -> Self {
+        unimplemented!("mul_by_x for a prime field doesn't make sense")
+    }
+}
+
+impl<const BITS: usize, M: NativeModulus> SimdField for NativeFieldArithmetic<BITS, M> {
+    type Scalar = Self;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * challenge
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == 1);
+        base_vec[0]
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        vec![*self]
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        1
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+/// The environment variable to override the simd backend detected, e.g., `EXPANDER_SIMD=avx2`
+pub const SIMD_BACKEND_ENV: &str = "EXPANDER_SIMD";
+
+/// The instruction sets of the packed fields, e.g., of `M31x16`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimdBackend {
+    Avx512,
+    Avx2,
+    Neon,
+    /// The lanes one after the other, on any cpu
+    Scalar,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SimdBackendError {
+    #[error("unknown simd backend {0:?}, expected avx2, avx512, neon or scalar")]
+    Unknown(String),
+
+    #[error("simd backend {0} is not supported by this cpu")]
+    Unsupported(SimdBackend),
+}
+
+impl SimdBackend {
+    /// Widest first
+    pub const ALL: [SimdBackend; 4] = [
+        SimdBackend::Avx512,
+        SimdBackend::Avx2,
+        SimdBackend::Neon,
+        SimdBackend::Scalar,
+    ];
+
+    /// The backend of the target features of the build, that of the fields without a runtime
+    /// selection.
+    pub const fn compiled() -> SimdBackend {
+        if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+            SimdBackend::Avx512
+        } else if cfg!(target_arch = "x86_64") {
+            SimdBackend::Avx2
+        } else if cfg!(target_arch = "aarch64") {
+            SimdBackend::Neon
+        } else {
+            SimdBackend::Scalar
+        }
+    }
+
+    /// Whether the cpu running the binary has the instructions of the backend.
+    pub fn is_available(&self) -> bool {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx512 => is_x86_feature_detected!("avx512f"),
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "aarch64")]
+            SimdBackend::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            SimdBackend::Scalar => true,
+            _ => false,
+        }
+    }
+
+    /// The widest backend of the cpu.
+    pub fn detect() -> SimdBackend {
+        SimdBackend::ALL
+            .into_iter()
+            .find(SimdBackend::is_available)
+            .unwrap_or(SimdBackend::Scalar)
+    }
+
+    /// The backend of `SIMD_BACKEND_ENV` if set, or else the widest of the cpu.
+    pub fn select() -> Result<SimdBackend, SimdBackendError> {
+        match std::env::var(SIMD_BACKEND_ENV) {
+            Ok(name) if !name.is_empty() => {
+                let backend = name.parse::<SimdBackend>()?;
+                if backend.is_available() {
+                    Ok(backend)
+                } else {
+                    Err(SimdBackendError::Unsupported(backend))
+                }
+            }
+            _ => Ok(SimdBackend::detect()),
+        }
+    }
+}
+
+impl fmt::Display for SimdBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SimdBackend::Avx512 => "avx512",
+            SimdBackend::Avx2 => "avx2",
+            SimdBackend::Neon => "neon",
+            SimdBackend::Scalar => "scalar",
+        })
+    }
+}
+
+impl FromStr for SimdBackend {
+    type Err = SimdBackendError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SimdBackend::ALL
+            .into_iter()
+            .find(|backend| backend.to_string() == s.to_ascii_lowercase())
+            .ok_or_else(|| SimdBackendError::Unknown(s.to_string()))
+    }
+}
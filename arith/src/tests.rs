@@ -11,6 +11,9 @@ use crate::{ExtensionField, Field, FieldSerde, SimdField};
 #[cfg(test)]
 mod bn254;
 
+#[cfg(test)]
+mod native_field;
+
 mod field;
 
 #[cfg(target_arch = "x86_64")]
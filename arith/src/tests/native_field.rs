@@ -0,0 +1,88 @@
+use ark_std::test_rng;
+use crypto_bigint::U256;
+use halo2curves::bn256::Fr;
+
+use super::{random_field_tests, random_inversion_tests, random_simd_field_tests};
+use crate::{valid_prime, Field, FieldForECC, NativeFieldArithmetic, NativeModulus};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct GoldilocksModulus;
+
+impl NativeModulus for GoldilocksModulus {
+    const NAME: &'static str = "native goldilocks";
+
+    const MODULUS: U256 = U256::from_u64(0xffff_ffff_0000_0001);
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Mersenne127Modulus;
+
+impl NativeModulus for Mersenne127Modulus {
+    const NAME: &'static str = "native mersenne 127";
+
+    const MODULUS: U256 =
+        U256::from_be_hex("000000000000000000000000000000007fffffffffffffffffffffffffffffff");
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct BN254Modulus;
+
+impl NativeModulus for BN254Modulus {
+    const NAME: &'static str = "native bn254 scalar field";
+
+    const MODULUS: U256 =
+        U256::from_be_hex("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+}
+
+type NativeGoldilocks = NativeFieldArithmetic<64, GoldilocksModulus>;
+type NativeMersenne127 = NativeFieldArithmetic<128, Mersenne127Modulus>;
+type NativeBN254 = NativeFieldArithmetic<256, BN254Modulus>;
+
+#[test]
+fn test_valid_prime() {
+    assert!(!valid_prime(0));
+    assert!(!valid_prime(1));
+    assert!(valid_prime(2));
+    assert!(valid_prime(37));
+    assert!(!valid_prime(561)); // carmichael number
+    assert!(valid_prime((1 << 31) - 1));
+    assert!(valid_prime(0xffff_ffff_0000_0001));
+    assert!(!valid_prime(u64::MAX));
+    // the largest prime below 2^64
+    assert!(valid_prime(u64::MAX - 58));
+}
+
+#[test]
+fn test_field() {
+    random_field_tests::<NativeGoldilocks>("native goldilocks".to_string());
+    random_simd_field_tests::<NativeGoldilocks>("native goldilocks".to_string());
+    random_field_tests::<NativeMersenne127>("native mersenne 127".to_string());
+    random_field_tests::<NativeBN254>("native bn254".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<NativeGoldilocks, _>(&mut rng, "native goldilocks".to_string());
+    random_inversion_tests::<NativeMersenne127, _>(&mut rng, "native mersenne 127".to_string());
+    random_inversion_tests::<NativeBN254, _>(&mut rng, "native bn254".to_string());
+}
+
+#[test]
+fn test_matches_bn254() {
+    assert_eq!(NativeBN254::modulus(), Fr::modulus());
+    assert_eq!(NativeBN254::INV_2 * NativeBN254::from(2), NativeBN254::ONE);
+
+    let mut rng = test_rng();
+    for _ in 0..100 {
+        let a = Fr::random_unsafe(&mut rng);
+        let b = Fr::random_unsafe(&mut rng);
+        let native_a = NativeBN254::from_u256(a.to_u256());
+        let native_b = NativeBN254::from_u256(b.to_u256());
+
+        assert_eq!((native_a * native_b).to_u256(), (a * b).to_u256());
+        assert_eq!((native_a + native_b).to_u256(), (a + b).to_u256());
+        assert_eq!((native_a - native_b).to_u256(), (a - b).to_u256());
+        assert_eq!(
+            native_a.inv().unwrap().to_u256(),
+            a.inv().unwrap().to_u256()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use arith::Field;
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitLayer, CoefType, Gate};
+
+/// A wire of a layered circuit: the wire `index` of the values between two layers.
+/// Layer 0 holds the circuit inputs, and layer `l` the outputs of circuit layer `l - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WireRef {
+    pub layer: usize,
+    pub index: usize,
+}
+
+/// Builds a layered circuit gate by gate.
+/// The gates of a layer read the wires of the layer below, and write to new wires of the
+/// current layer, opened with `next_layer`. Several gates writing the same wire add up.
+/// The layer sizes are padded to a power of two, with at least two wires.
+#[derive(Debug)]
+pub struct GateBuilder<C: GKRConfig> {
+    input_size: usize,
+    layers: Vec<CircuitLayer<C>>,
+    /// Number of wires of each layer, the input layer included.
+    layer_sizes: Vec<usize>,
+}
+
+impl<C: GKRConfig> GateBuilder<C> {
+    pub fn new(input_size: usize) -> Self {
+        Self {
+            input_size,
+            layers: vec![],
+            layer_sizes: vec![input_size],
+        }
+    }
+
+    #[inline]
+    pub fn input(&self, index: usize) -> WireRef {
+        assert!(index < self.input_size, "input {} out of range", index);
+        WireRef { layer: 0, index }
+    }
+
+    pub fn next_layer(&mut self) {
+        self.layers.push(CircuitLayer::default());
+        self.layer_sizes.push(0);
+    }
+
+    pub fn new_wire(&mut self) -> WireRef {
+        assert!(!self.layers.is_empty(), "no layer opened");
+        let size = self.layer_sizes.last_mut().unwrap();
+        *size += 1;
+        WireRef {
+            layer: self.layers.len(),
+            index: *size - 1,
+        }
+    }
+
+    /// `coef * input`, on a new wire.
+    pub fn add(&mut self, input: WireRef, coef: C::CircuitField) -> WireRef {
+        let output = self.new_wire();
+        self.add_into(output, input, coef);
+        output
+    }
+
+    /// `input`, carried to the current layer.
+    #[inline]
+    pub fn relay(&mut self, input: WireRef) -> WireRef {
+        self.add(input, C::CircuitField::ONE)
+    }
+
+    /// `coef * a * b`, on a new wire.
+    pub fn mul(&mut self, a: WireRef, b: WireRef, coef: C::CircuitField) -> WireRef {
+        let output = self.new_wire();
+        self.mul_into(output, a, b, coef);
+        output
+    }
+
+    /// `coef`, on a new wire.
+    pub fn constant(&mut self, coef: C::CircuitField) -> WireRef {
+        let output = self.new_wire();
+        self.constant_into(output, coef);
+        output
+    }
+
+    /// The public input `index`, see `CoefType::PublicInput`, on a new wire.
+    pub fn public_input(&mut self, index: usize) -> WireRef {
+        let output = self.new_wire();
+        self.public_input_into(output, index);
+        output
+    }
+
+    pub fn add_into(&mut self, output: WireRef, input: WireRef, coef: C::CircuitField) {
+        self.check_wires(output, &[input]);
+        self.layers.last_mut().unwrap().add.push(Gate {
+            i_ids: [input.index],
+            o_id: output.index,
+            coef_type: CoefType::Constant,
+            coef,
+            gate_type: 0,
+        });
+    }
+
+    pub fn mul_into(&mut self, output: WireRef, a: WireRef, b: WireRef, coef: C::CircuitField) {
+        self.check_wires(output, &[a, b]);
+        self.layers.last_mut().unwrap().mul.push(Gate {
+            i_ids: [a.index, b.index],
+            o_id: output.index,
+            coef_type: CoefType::Constant,
+            coef,
+            gate_type: 0,
+        });
+    }
+
+    pub fn constant_into(&mut self, output: WireRef, coef: C::CircuitField) {
+        self.check_wires(output, &[]);
+        self.layers.last_mut().unwrap().const_.push(Gate {
+            i_ids: [],
+            o_id: output.index,
+            coef_type: CoefType::Constant,
+            coef,
+            gate_type: 0,
+        });
+    }
+
+    pub fn public_input_into(&mut self, output: WireRef, index: usize) {
+        self.check_wires(output, &[]);
+        self.layers.last_mut().unwrap().const_.push(Gate {
+            i_ids: [],
+            o_id: output.index,
+            coef_type: CoefType::PublicInput(index),
+            coef: C::CircuitField::ONE,
+            gate_type: 0,
+        });
+    }
+
+    fn check_wires(&self, output: WireRef, inputs: &[WireRef]) {
+        let layer = self.layers.len();
+        assert!(
+            layer > 0 && output.layer == layer && output.index < self.layer_sizes[layer],
+            "output wire {:?} is not a wire of the current layer",
+            output
+        );
+        for input in inputs {
+            assert!(
+                input.layer + 1 == layer && input.index < self.layer_sizes[input.layer],
+                "input wire {:?} is not a wire of the previous layer",
+                input
+            );
+        }
+    }
+
+    pub fn build(self) -> Circuit<C> {
+        assert!(!self.layers.is_empty(), "the circuit has no layer");
+        let var_nums = self
+            .layer_sizes
+            .iter()
+            .map(|size| (*size).max(2).next_power_of_two().trailing_zeros() as usize)
+            .collect::<Vec<_>>();
+
+        let mut circuit = Circuit::<C> {
+            layers: self.layers,
+            ..Default::default()
+        };
+        for (i, layer) in circuit.layers.iter_mut().enumerate() {
+            layer.input_var_num = var_nums[i];
+            layer.output_var_num = var_nums[i + 1];
+            layer.identify_structure_info();
+        }
+        circuit
+    }
+}
@@ -0,0 +1,215 @@
+//! Composing a circuit from its wires, rather than layer by layer as with `GateBuilder`.
+//!
+//! Each gate reads two wires and is placed one layer above the higher of them, the inputs
+//! being on layer 0. When the circuit is built, the wires read from further below are relayed
+//! up one layer at a time, and only the gates the public wires depend on are kept. The inputs
+//! are the circuit inputs in the order they were added, and the outputs the public wires in
+//! the order they were marked.
+
+use std::collections::HashMap;
+
+use arith::Field;
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitError, CircuitLayer, CoefType, Gate};
+
+/// A wire of a `CircuitBuilder`, only made by the builder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WireRef {
+    layer_idx: usize,
+    wire_idx: usize,
+}
+
+impl WireRef {
+    /// The layer of the wire, 0 for the inputs.
+    #[inline]
+    pub fn layer(&self) -> usize {
+        self.layer_idx
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    Input,
+    Mul(WireRef, WireRef),
+    Add(WireRef, WireRef),
+}
+
+#[derive(Debug)]
+pub struct CircuitBuilder<C: GKRConfig> {
+    /// The wires of each layer, the inputs first.
+    nodes: Vec<Vec<Node>>,
+    public: Vec<WireRef>,
+    _marker: std::marker::PhantomData<C>,
+}
+
+impl<C: GKRConfig> Default for CircuitBuilder<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: GKRConfig> CircuitBuilder<C> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![vec![]],
+            public: vec![],
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// `n` new circuit inputs.
+    pub fn add_input(&mut self, n: usize) -> Vec<WireRef> {
+        (0..n).map(|_| self.push(0, Node::Input)).collect()
+    }
+
+    /// `a * b`, on a new wire.
+    pub fn add_mul_gate(&mut self, a: WireRef, b: WireRef) -> WireRef {
+        self.push_gate(Node::Mul(a, b))
+    }
+
+    /// `a + b`, on a new wire.
+    pub fn add_add_gate(&mut self, a: WireRef, b: WireRef) -> WireRef {
+        self.push_gate(Node::Add(a, b))
+    }
+
+    /// Make the wire an output of the circuit, if it is not already one.
+    pub fn mark_public(&mut self, w: WireRef) {
+        self.check_wire(w);
+        if !self.public.contains(&w) {
+            self.public.push(w);
+        }
+    }
+
+    /// The layered circuit, checked by `Circuit::validate`. The layer sizes are padded to a
+    /// power of two, with at least two wires, and there is at least one layer of gates.
+    pub fn build(self) -> Result<Circuit<C>, CircuitError> {
+        if self.public.is_empty() {
+            return Err(CircuitError::NoOutput);
+        }
+        let depth = self
+            .public
+            .iter()
+            .map(|w| w.layer_idx)
+            .max()
+            .unwrap()
+            .max(1);
+
+        let mut layout = Layout {
+            nodes: &self.nodes,
+            layers: (0..depth).map(|_| CircuitLayer::default()).collect(),
+            sizes: vec![0; depth + 1],
+            positions: HashMap::new(),
+        };
+        layout.sizes[0] = self.nodes[0].len();
+        for w in &self.public {
+            layout.place(*w, depth);
+        }
+
+        let var_nums = layout
+            .sizes
+            .iter()
+            .map(|size| (*size).max(2).next_power_of_two().trailing_zeros() as usize)
+            .collect::<Vec<_>>();
+        let mut circuit = Circuit::<C> {
+            layers: layout.layers,
+            ..Default::default()
+        };
+        for (i, layer) in circuit.layers.iter_mut().enumerate() {
+            layer.input_var_num = var_nums[i];
+            layer.output_var_num = var_nums[i + 1];
+            layer.identify_structure_info();
+        }
+        circuit.validate()?;
+        Ok(circuit)
+    }
+
+    fn push(&mut self, layer_idx: usize, node: Node) -> WireRef {
+        if self.nodes.len() == layer_idx {
+            self.nodes.push(vec![]);
+        }
+        self.nodes[layer_idx].push(node);
+        WireRef {
+            layer_idx,
+            wire_idx: self.nodes[layer_idx].len() - 1,
+        }
+    }
+
+    /// Each gate has a fan-in of two wires of this builder.
+    fn push_gate(&mut self, node: Node) -> WireRef {
+        let (a, b) = match node {
+            Node::Mul(a, b) | Node::Add(a, b) => (a, b),
+            Node::Input => unreachable!(),
+        };
+        self.check_wire(a);
+        self.check_wire(b);
+        self.push(a.layer_idx.max(b.layer_idx) + 1, node)
+    }
+
+    fn check_wire(&self, w: WireRef) {
+        assert!(
+            self.nodes
+                .get(w.layer_idx)
+                .is_some_and(|layer| w.wire_idx < layer.len()),
+            "wire {:?} is not a wire of this builder",
+            w
+        );
+    }
+}
+
+/// The gates of the circuit, placed from the output layer down.
+struct Layout<'a, C: GKRConfig> {
+    nodes: &'a [Vec<Node>],
+    layers: Vec<CircuitLayer<C>>,
+    /// Number of wires of each layer, the input layer included.
+    sizes: Vec<usize>,
+    /// The position on a layer of a wire of this layer or below.
+    positions: HashMap<(WireRef, usize), usize>,
+}
+
+impl<C: GKRConfig> Layout<'_, C> {
+    /// The position of the wire on the layer, adding its gate or its relay there if needed.
+    fn place(&mut self, w: WireRef, layer: usize) -> usize {
+        if layer == 0 {
+            return w.wire_idx;
+        }
+        if let Some(position) = self.positions.get(&(w, layer)) {
+            return *position;
+        }
+        let position = self.sizes[layer];
+        self.sizes[layer] += 1;
+        self.positions.insert((w, layer), position);
+
+        let inputs = if w.layer_idx < layer {
+            vec![w]
+        } else {
+            match self.nodes[w.layer_idx][w.wire_idx] {
+                Node::Mul(a, b) => {
+                    let i_ids = [self.place(a, layer - 1), self.place(b, layer - 1)];
+                    self.layers[layer - 1].mul.push(gate(i_ids, position));
+                    vec![]
+                }
+                Node::Add(a, b) => vec![a, b],
+                Node::Input => unreachable!("an input above layer 0"),
+            }
+        };
+        for input in inputs {
+            let i_ids = [self.place(input, layer - 1)];
+            self.layers[layer - 1].add.push(gate(i_ids, position));
+        }
+        position
+    }
+}
+
+fn gate<C: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+) -> Gate<C, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    }
+}
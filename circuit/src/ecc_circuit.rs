@@ -1,5 +1,6 @@
-use config::GKRConfig;
-use std::{cmp::max, collections::HashMap, fs, io::Cursor};
+use arith::FieldForECC;
+use config::{GKRConfig, SENTINEL_OFFSET, SENTINEL_SIZE};
+use std::{cmp::max, collections::HashMap, fs, io::Read};
 
 use crate::*;
 
@@ -23,6 +24,36 @@ pub struct Segment<C: GKRConfig> {
 }
 
 impl<C: GKRConfig> Segment<C> {
+    /// Push the gates of the segment at each of the allocations into the layer.
+    pub(crate) fn push_gates(&self, allocs: &[Allocation], layer: &mut CircuitLayer<C>) {
+        for alloc in allocs {
+            for gate in &self.gate_muls {
+                let mut gate = gate.clone();
+                gate.i_ids[0] += alloc.i_offset;
+                gate.i_ids[1] += alloc.i_offset;
+                gate.o_id += alloc.o_offset;
+                layer.mul.push(gate);
+            }
+            for gate in &self.gate_adds {
+                let mut gate = gate.clone();
+                gate.i_ids[0] += alloc.i_offset;
+                gate.o_id += alloc.o_offset;
+                layer.add.push(gate);
+            }
+            for gate in &self.gate_consts {
+                let mut gate = gate.clone();
+                gate.o_id += alloc.o_offset;
+                layer.const_.push(gate);
+            }
+            for gate in &self.gate_uni {
+                let mut gate = gate.clone();
+                gate.i_ids[0] += alloc.i_offset;
+                gate.o_id += alloc.o_offset;
+                layer.uni.push(gate);
+            }
+        }
+    }
+
     #[inline]
     pub fn contain_gates(&self) -> bool {
         !self.gate_muls.is_empty()
@@ -63,6 +94,64 @@ impl<C: GKRConfig> Segment<C> {
         }
         ret
     }
+
+    /// The size of the segment once flattened, of the sizes of its children.
+    fn flattened_size(
+        &self,
+        children: &[Option<SegmentSize>],
+    ) -> std::result::Result<SegmentSize, CircuitError> {
+        let overflow = || CircuitError::MalformedCircuit("the wire offsets overflow".to_string());
+        let mut size = SegmentSize {
+            num_gates: self.gate_muls.len()
+                + self.gate_adds.len()
+                + self.gate_consts.len()
+                + self.gate_uni.len(),
+            depth: 1,
+            max_i_id: (self.gate_muls.iter().flat_map(|g| g.i_ids))
+                .chain(self.gate_adds.iter().flat_map(|g| g.i_ids))
+                .chain(self.gate_uni.iter().flat_map(|g| g.i_ids))
+                .max()
+                .unwrap_or(0),
+            max_o_id: (self.gate_muls.iter().map(|g| g.o_id))
+                .chain(self.gate_adds.iter().map(|g| g.o_id))
+                .chain(self.gate_consts.iter().map(|g| g.o_id))
+                .chain(self.gate_uni.iter().map(|g| g.o_id))
+                .max()
+                .unwrap_or(0),
+        };
+        for (child_seg_id, child_allocs) in &self.child_segs {
+            let child = children[*child_seg_id].expect("the children are sized first");
+            size.num_gates = size
+                .num_gates
+                .saturating_add(child.num_gates.saturating_mul(child_allocs.len()));
+            size.depth = max(size.depth, child.depth + 1);
+            for alloc in child_allocs {
+                let i_id = alloc.i_offset.checked_add(child.max_i_id);
+                let o_id = alloc.o_offset.checked_add(child.max_o_id);
+                size.max_i_id = max(size.max_i_id, i_id.ok_or_else(overflow)?);
+                size.max_o_id = max(size.max_o_id, o_id.ok_or_else(overflow)?);
+            }
+        }
+        Ok(size)
+    }
+}
+
+/// The size of a segment once flattened, with the largest wires of its gates.
+#[derive(Clone, Copy)]
+struct SegmentSize {
+    num_gates: usize,
+    depth: usize,
+    max_i_id: usize,
+    max_o_id: usize,
+}
+
+/// The size of a circuit once flattened, see `RecursiveCircuit::flattened_size`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlattenedSize {
+    /// The gates of all the layers, saturating at `usize::MAX`.
+    pub num_gates: usize,
+    /// The deepest nesting of segments in a layer, a segment without children being of depth 1.
+    pub depth: usize,
 }
 
 #[derive(Default)]
@@ -76,72 +165,138 @@ pub struct RecursiveCircuit<C: GKRConfig> {
 }
 
 impl<C: GKRConfig> RecursiveCircuit<C> {
+    /// The circuit of the file, checked to be of the field of `C` before it is parsed.
     pub fn load(filename: &str) -> std::result::Result<Self, CircuitError> {
         let file_bytes = fs::read(filename)?;
-        let cursor = Cursor::new(file_bytes);
+        Self::check_field(&file_bytes)?;
+        Self::load_from_reader(file_bytes.as_slice())
+    }
 
-        Ok(Self::deserialize_from(cursor))
+    /// Check that the sentinel of the header of the circuit bytes is the modulus of the field
+    /// of `C`, on which the parser would otherwise panic, or misread the gates.
+    pub fn check_field(bytes: &[u8]) -> std::result::Result<(), CircuitError> {
+        let required = SENTINEL_OFFSET + SENTINEL_SIZE;
+        let found: [u8; SENTINEL_SIZE] = bytes
+            .get(SENTINEL_OFFSET..required)
+            .ok_or(CircuitError::TruncatedCircuitHeader {
+                actual: bytes.len(),
+                required,
+            })?
+            .try_into()
+            .unwrap();
+        let expected = <C::CircuitField as FieldForECC>::modulus().to_le_bytes();
+        if found != expected {
+            return Err(CircuitError::WrongCircuitField { expected, found });
+        }
+        Ok(())
     }
 
-    pub fn flatten(&self) -> Circuit<C> {
-        let mut ret = Circuit::<C> {
-            expected_num_output_zeros: self.expected_num_output_zeros,
+    pub fn load_from_reader<R: Read>(reader: R) -> std::result::Result<Self, CircuitError> {
+        Self::deserialize_from(reader)
+    }
+
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// The layer `index` of the flattened circuit, without flattening the other layers.
+    pub fn flatten_layer(&self, index: usize) -> CircuitLayer<C> {
+        let layer_id = self.layers[index];
+        let layer_seg = &self.segments[layer_id];
+        let leaves = layer_seg.scan_leaf_segments(self, layer_id);
+        let mut ret_layer = CircuitLayer {
+            input_var_num: max(layer_seg.i_var_num, 1), // var_num >= 1
+            output_var_num: max(layer_seg.o_var_num, 1), // var_num >= 1
             ..Default::default()
         };
-        // layer-by-layer conversion
-        for layer_id in &self.layers {
-            let layer_seg = &self.segments[*layer_id];
-            let leaves = layer_seg.scan_leaf_segments(self, *layer_id);
-            let mut ret_layer = CircuitLayer {
-                input_var_num: max(layer_seg.i_var_num, 1), // var_num >= 1
-                output_var_num: max(layer_seg.o_var_num, 1), // var_num >= 1
-                ..Default::default()
-            };
-            for (leaf_seg_id, leaf_allocs) in leaves {
-                let leaf_seg = &self.segments[leaf_seg_id];
-                for alloc in leaf_allocs {
-                    for gate in &leaf_seg.gate_muls {
-                        let mut gate = gate.clone();
-                        gate.i_ids[0] += alloc.i_offset;
-                        gate.i_ids[1] += alloc.i_offset;
-                        gate.o_id += alloc.o_offset;
-                        ret_layer.mul.push(gate);
-                    }
-                    for gate in &leaf_seg.gate_adds {
-                        let mut gate = gate.clone();
-                        gate.i_ids[0] += alloc.i_offset;
-                        gate.o_id += alloc.o_offset;
-                        ret_layer.add.push(gate);
+        for (leaf_seg_id, leaf_allocs) in leaves {
+            self.segments[leaf_seg_id].push_gates(&leaf_allocs, &mut ret_layer);
+        }
+        trace_layer(index, &ret_layer);
+        ret_layer
+    }
+
+    /// The size of the circuit of `flatten`, computed from the segments without flattening
+    /// them, as each reuse of a segment multiplies its gates. An error for the segments that
+    /// `flatten` would panic on: a layer or a child out of the segments, a segment among its own
+    /// children, or wires whose offsets overflow.
+    pub fn flattened_size(&self) -> std::result::Result<FlattenedSize, CircuitError> {
+        let num_segments = self.segments.len();
+        let out_of_range = |id| CircuitError::SegmentOutOfRange { id, num_segments };
+        let mut sizes: Vec<Option<SegmentSize>> = vec![None; num_segments];
+        let mut on_stack = vec![false; num_segments];
+        let mut ret = FlattenedSize::default();
+        for &layer_id in &self.layers {
+            if layer_id >= num_segments {
+                return Err(out_of_range(layer_id));
+            }
+            // depth first, each segment sized after its children, without recursing
+            let mut stack = vec![];
+            if sizes[layer_id].is_none() {
+                on_stack[layer_id] = true;
+                stack.push((layer_id, 0));
+            }
+            while let Some(&(id, next_child)) = stack.last() {
+                let segment = &self.segments[id];
+                if let Some(&(child_seg_id, _)) = segment.child_segs.get(next_child) {
+                    stack.last_mut().unwrap().1 += 1;
+                    if child_seg_id >= num_segments {
+                        return Err(out_of_range(child_seg_id));
                     }
-                    for gate in &leaf_seg.gate_consts {
-                        let mut gate = gate.clone();
-                        gate.o_id += alloc.o_offset;
-                        ret_layer.const_.push(gate);
+                    if on_stack[child_seg_id] {
+                        return Err(CircuitError::SegmentCycle { id: child_seg_id });
                     }
-                    for gate in &leaf_seg.gate_uni {
-                        let mut gate = gate.clone();
-                        gate.i_ids[0] += alloc.i_offset;
-                        gate.o_id += alloc.o_offset;
-                        ret_layer.uni.push(gate);
+                    if sizes[child_seg_id].is_none() {
+                        on_stack[child_seg_id] = true;
+                        stack.push((child_seg_id, 0));
                     }
+                    continue;
                 }
+                sizes[id] = Some(segment.flattened_size(&sizes)?);
+                on_stack[id] = false;
+                stack.pop();
             }
-            // debug print layer
-            log::trace!(
-                "layer {} mul: {} add: {} const:{} uni:{} i_var_num: {} o_var_num: {}",
-                ret.layers.len(),
-                ret_layer.mul.len(),
-                ret_layer.add.len(),
-                ret_layer.const_.len(),
-                ret_layer.uni.len(),
-                ret_layer.input_var_num,
-                ret_layer.output_var_num,
-            );
-            ret.layers.push(ret_layer);
+            let size = sizes[layer_id].unwrap();
+            ret.num_gates = ret.num_gates.saturating_add(size.num_gates);
+            ret.depth = max(ret.depth, size.depth);
         }
+        Ok(ret)
+    }
+
+    /// As `flatten`, but returning the errors of `flattened_size` rather than panicking on them.
+    /// The size itself is not bounded, see `FuzzSafeCircuitLoader` for untrusted circuits.
+    pub fn try_flatten(&self) -> std::result::Result<Circuit<C>, CircuitError> {
+        self.flattened_size()?;
+        Ok(self.flatten())
+    }
+
+    pub fn flatten(&self) -> Circuit<C> {
+        let mut ret = Circuit::<C> {
+            expected_num_output_zeros: self.expected_num_output_zeros,
+            ..Default::default()
+        };
+        // layer-by-layer conversion
+        ret.layers = (0..self.num_layers())
+            .map(|i| self.flatten_layer(i))
+            .collect();
 
         ret.identify_rnd_coefs();
         ret.identify_structure_info();
         ret
     }
 }
+
+/// Debug print of the flattened layer.
+pub(crate) fn trace_layer<C: GKRConfig>(index: usize, layer: &CircuitLayer<C>) {
+    log::trace!(
+        "layer {} mul: {} add: {} const:{} uni:{} i_var_num: {} o_var_num: {}",
+        index,
+        layer.mul.len(),
+        layer.add.len(),
+        layer.const_.len(),
+        layer.uni.len(),
+        layer.input_var_num,
+        layer.output_var_num,
+    );
+}
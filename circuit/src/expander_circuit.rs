@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::{any::TypeId, fs};
 
 use arith::{Field, FieldSerde, SimdField};
@@ -8,6 +8,13 @@ use transcript::Transcript;
 
 use crate::*;
 
+/// The number of witnesses, of private and of public inputs per witness, and the modulus, at
+/// the start of the witness payload.
+const WITNESS_PAYLOAD_HEADER_SIZE: usize = 3 * 8 + 32;
+
+/// The number of values of each witness read at once by `try_load_witness_reader`.
+pub const WITNESS_CHUNK_SIZE: usize = 1 << 16;
+
 #[derive(Debug, Clone, Default)]
 pub struct StructureInfo {
     // this var name is a bit misleading -- the power5 gate's max degree is > 1.
@@ -37,24 +44,56 @@ impl<C: GKRConfig> CircuitLayer<C> {
         &self,
         res: &mut Vec<C::SimdCircuitField>,
         public_input: &[C::SimdCircuitField],
+    ) {
+        self.evaluate_with_observer(res, public_input, 0, &mut NoopCircuitObserver);
+    }
+
+    /// Evaluate the layer, reporting every gate evaluation to the observer.
+    #[inline]
+    pub fn evaluate_with_observer<O: CircuitObserver<C> + ?Sized>(
+        &self,
+        res: &mut Vec<C::SimdCircuitField>,
+        public_input: &[C::SimdCircuitField],
+        layer: usize,
+        observer: &mut O,
     ) {
         res.clear();
         res.resize(1 << self.output_var_num, C::SimdCircuitField::zero());
-        for gate in &self.mul {
+        for (index, gate) in self.mul.iter().enumerate() {
             let i0 = &self.input_vals[gate.i_ids[0]];
             let i1 = &self.input_vals[gate.i_ids[1]];
             let o = &mut res[gate.o_id];
             let mul = *i0 * i1;
-            *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &mul);
+            let v = C::circuit_field_mul_simd_circuit_field(&gate.coef, &mul);
+            *o += v;
+            observer.on_gate_evaluated(
+                GateRef {
+                    layer,
+                    kind: GateKind::Mul,
+                    index,
+                },
+                &[*i0, *i1],
+                v,
+            );
         }
 
-        for gate in &self.add {
+        for (index, gate) in self.add.iter().enumerate() {
             let i0 = self.input_vals[gate.i_ids[0]];
             let o = &mut res[gate.o_id];
-            *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0);
+            let v = C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0);
+            *o += v;
+            observer.on_gate_evaluated(
+                GateRef {
+                    layer,
+                    kind: GateKind::Add,
+                    index,
+                },
+                &[i0],
+                v,
+            );
         }
 
-        for gate in &self.const_ {
+        for (index, gate) in self.const_.iter().enumerate() {
             let o = &mut res[gate.o_id];
 
             let coef = match gate.coef_type {
@@ -62,25 +101,44 @@ impl<C: GKRConfig> CircuitLayer<C> {
                 _ => C::circuit_field_to_simd_circuit_field(&gate.coef),
             };
             *o += coef;
+            observer.on_gate_evaluated(
+                GateRef {
+                    layer,
+                    kind: GateKind::Const,
+                    index,
+                },
+                &[],
+                coef,
+            );
         }
 
-        for gate in &self.uni {
+        for (index, gate) in self.uni.iter().enumerate() {
             let i0 = &self.input_vals[gate.i_ids[0]];
             let o = &mut res[gate.o_id];
-            match gate.gate_type {
+            let v = match gate.gate_type {
                 12345 => {
                     // pow5
                     let i0_2 = i0.square();
                     let i0_4 = i0_2.square();
                     let i0_5 = i0_4 * i0;
-                    *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0_5);
+                    C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0_5)
                 }
                 12346 => {
                     // pow1
-                    *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, i0);
+                    C::circuit_field_mul_simd_circuit_field(&gate.coef, i0)
                 }
                 _ => panic!("Unknown gate type: {}", gate.gate_type),
-            }
+            };
+            *o += v;
+            observer.on_gate_evaluated(
+                GateRef {
+                    layer,
+                    kind: GateKind::Uni,
+                    index,
+                },
+                &[*i0],
+                v,
+            );
         }
     }
 
@@ -143,8 +201,31 @@ unsafe impl<C> Send for Circuit<C> where C: GKRConfig {}
 
 impl<C: GKRConfig> Circuit<C> {
     pub fn load_circuit(filename: &str) -> Self {
-        let rc = RecursiveCircuit::<C>::load(filename).unwrap();
-        rc.flatten()
+        Self::try_load_circuit(filename).unwrap()
+    }
+
+    /// As `load_circuit`, but returning the file errors, the circuits of another field than `C`
+    /// and the malformed circuit bytes. The size of the flattened circuit is not bounded, see
+    /// `FuzzSafeCircuitLoader` for untrusted files.
+    #[cfg_attr(feature = "telemetry", tracing::instrument(name = "load_circuit"))]
+    pub fn try_load_circuit(filename: &str) -> Result<Self, CircuitError> {
+        RecursiveCircuit::<C>::load(filename)?.try_flatten()
+    }
+
+    pub fn load_circuit_from_reader<R: Read>(reader: R) -> Self {
+        Self::try_load_circuit_from_reader(reader).unwrap()
+    }
+
+    /// As `load_circuit_from_reader`, but returning the malformed circuit bytes, as
+    /// `try_load_circuit`.
+    pub fn try_load_circuit_from_reader<R: Read>(reader: R) -> Result<Self, CircuitError> {
+        RecursiveCircuit::<C>::load_from_reader(reader)?.try_flatten()
+    }
+
+    /// As `load_circuit`, for the bytes of a circuit file, e.g., fetched from a store or
+    /// embedded in the binary, parsed where they are rather than copied.
+    pub fn load_circuit_from_bytes(bytes: &[u8]) -> Self {
+        Self::load_circuit_from_reader(bytes)
     }
 
     pub fn load_non_simd_witness_file(&mut self, filename: &str) {
@@ -157,66 +238,591 @@ impl<C: GKRConfig> Circuit<C> {
         self.load_witness_bytes(&file_bytes, false);
     }
 
+    /// As `load_witness_file`, but returning the file errors and the witnesses that do not fit
+    /// the circuit.
+    pub fn try_load_witness_file(&mut self, filename: &str) -> Result<(), CircuitError> {
+        self.try_load_witness_reader(fs::File::open(filename)?, false)
+    }
+
+    /// As `try_load_witness_bytes`, for the witness of the reader, e.g., a file, which is read
+    /// `WITNESS_CHUNK_SIZE` values of each simd lane at a time rather than as a whole: besides
+    /// the packed inputs, the memory taken is of a chunk.
+    ///
+    /// The inputs overwrite those of the previous witness in place, allocating only if they are
+    /// more. A witness whose header fits but whose values do not deserialize, e.g., of a value
+    /// over the modulus, leaves the inputs partly overwritten.
+    pub fn try_load_witness_reader<R: Read + Seek>(
+        &mut self,
+        mut reader: R,
+        allow_padding: bool,
+    ) -> Result<(), CircuitError> {
+        Self::read_packed_witness_into(
+            self.log_input_size(),
+            &mut reader,
+            allow_padding,
+            false,
+            &mut self.layers[0].input_vals,
+            &mut self.public_input,
+        )
+    }
+
+    /// As `try_load_witness_file`, with the values of the memory mapped file deserialized by
+    /// `n_threads` threads, each on a contiguous chunk of the values.
+    #[cfg(feature = "parallel")]
+    pub fn load_witness_parallel(
+        &mut self,
+        path: &str,
+        n_threads: usize,
+    ) -> Result<(), CircuitError> {
+        let file = fs::File::open(path)?;
+        // the witness files are not expected to change while being loaded
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let payload = witness_payload::<C::CircuitField>(&mmap)?;
+        let (num_witnesses, _, num_public_inputs_per_witness, bytes) =
+            Self::split_witness_payload(self.log_input_size(), payload, false)?;
+        if num_witnesses < C::get_field_pack_size() {
+            return Err(WitnessError::NotEnoughWitnesses {
+                expected: C::get_field_pack_size(),
+                got: num_witnesses,
+            }
+            .into());
+        }
+        let num_private_inputs_per_witness = 1 << self.log_input_size();
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+        let num_values = bytes.len() / value_size;
+
+        let chunk_len = num_values.div_ceil(n_threads.max(1)).max(1);
+        let mut values = vec![C::CircuitField::ZERO; num_values];
+        let mut results = (0..num_values.div_ceil(chunk_len))
+            .map(|_| Ok(()))
+            .collect::<Vec<arith::FieldSerdeResult<()>>>();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build()
+            .map_err(|e| CircuitError::OtherError(std::io::Error::other(e)))?;
+        pool.scope(|s| {
+            for ((values, bytes), result) in values
+                .chunks_mut(chunk_len)
+                .zip(bytes.chunks(chunk_len * value_size))
+                .zip(results.iter_mut())
+            {
+                s.spawn(move |_| {
+                    *result = values
+                        .iter_mut()
+                        .zip(bytes.chunks_exact(value_size))
+                        .try_for_each(|(value, bytes)| {
+                            *value = C::CircuitField::deserialize_from(bytes)?;
+                            Ok(())
+                        });
+                });
+            }
+        });
+        results
+            .into_iter()
+            .collect::<arith::FieldSerdeResult<()>>()?;
+
+        let (private_input, public_input) = Self::pack_witness_values(
+            self.log_input_size(),
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+            false,
+            |idx| values[idx],
+        );
+        self.layers[0].input_vals = private_input;
+        self.public_input = public_input;
+        Ok(())
+    }
+
+    /// As `load_witness_bytes`, but returning an error if the witness does not fit the circuit,
+    /// e.g., a `WitnessError` for a header of another version or field, or for bytes not of the
+    /// length its header tells of.
+    pub fn try_load_witness_bytes(
+        &mut self,
+        file_bytes: &[u8],
+        allow_padding: bool,
+    ) -> Result<(), CircuitError> {
+        self.try_load_witness_reader(Cursor::new(file_bytes), allow_padding)
+    }
+
+    /// Load the public inputs of the file, leaving the input layer as it is, for a verifier
+    /// without the private inputs. The file is of the witness format, of no private inputs per
+    /// witness, see `public_input_bytes`, or a whole witness, whose private inputs are skipped.
+    pub fn load_public_input_file(&mut self, filename: &str) {
+        self.try_load_public_input_file(filename).unwrap()
+    }
+
+    /// As `load_public_input_file`, but returning the file errors and the public inputs that do
+    /// not fit the circuit.
+    pub fn try_load_public_input_file(&mut self, filename: &str) -> Result<(), CircuitError> {
+        Self::read_packed_witness_into(
+            self.log_input_size(),
+            &mut fs::File::open(filename)?,
+            false,
+            true,
+            &mut vec![],
+            &mut self.public_input,
+        )
+    }
+
+    /// As `try_load_public_input_file`, for the bytes of the file. With `allow_padding`, fewer
+    /// witnesses than the simd lanes are padded as `try_load_witness_bytes` does.
+    pub fn try_load_public_input_bytes(
+        &mut self,
+        file_bytes: &[u8],
+        allow_padding: bool,
+    ) -> Result<(), CircuitError> {
+        Self::read_packed_witness_into(
+            self.log_input_size(),
+            &mut Cursor::new(file_bytes),
+            allow_padding,
+            true,
+            &mut vec![],
+            &mut self.public_input,
+        )
+    }
+
+    /// Split the witness batch bytes, see `split_witness_batch`, into the packed inputs of as
+    /// many prove calls as it takes for `C::get_field_pack_size()` instances each. Each instance
+    /// is a witness file of a single witness, and the lanes of the last pack past the instances
+    /// are zero witnesses.
+    pub fn load_witness_batch_bytes(&self, bytes: &[u8]) -> Result<WitnessBatch<C>, CircuitError> {
+        Self::pack_witness_batch(self.log_input_size(), bytes, false)
+    }
+
+    /// As `load_witness_batch_bytes`, for the public inputs alone, of instances in the format of
+    /// `try_load_public_input_bytes`. The private inputs of each pack are left empty.
+    pub fn load_public_input_batch_bytes(
+        &self,
+        bytes: &[u8],
+    ) -> Result<WitnessBatch<C>, CircuitError> {
+        Self::pack_witness_batch(self.log_input_size(), bytes, true)
+    }
+
+    fn pack_witness_batch(
+        log_input_size: usize,
+        bytes: &[u8],
+        public_only: bool,
+    ) -> Result<WitnessBatch<C>, CircuitError> {
+        let instances = split_witness_batch(bytes)?;
+        if instances.is_empty() {
+            return Err(WitnessError::NoWitnesses.into());
+        }
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+        let mut num_public_inputs = None;
+        let mut values = Vec::with_capacity(instances.len());
+        for (instance, witness_bytes) in instances.iter().enumerate() {
+            let payload = witness_payload::<C::CircuitField>(witness_bytes)?;
+            let (
+                num_witnesses,
+                num_private_inputs_per_witness,
+                num_public_inputs_per_witness,
+                bytes,
+            ) = Self::split_witness_payload(log_input_size, payload, public_only)?;
+            if num_witnesses != 1 {
+                return Err(WitnessError::NotSingleWitness {
+                    instance,
+                    got: num_witnesses,
+                }
+                .into());
+            }
+            let expected = *num_public_inputs.get_or_insert(num_public_inputs_per_witness);
+            if num_public_inputs_per_witness != expected {
+                return Err(WitnessError::PublicInputMismatch {
+                    instance,
+                    expected,
+                    got: num_public_inputs_per_witness,
+                }
+                .into());
+            }
+            let first = if public_only {
+                num_private_inputs_per_witness
+            } else {
+                0
+            };
+            values.push(
+                bytes[first * value_size..]
+                    .chunks_exact(value_size)
+                    .map(C::CircuitField::deserialize_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+
+        let num_private_inputs = if public_only { 0 } else { 1 << log_input_size };
+        let total_size = values[0].len();
+        let pack_size = C::get_field_pack_size();
+        let mut lane_values = Vec::with_capacity(pack_size);
+        let packs = values
+            .chunks(pack_size)
+            .map(|instances| {
+                let mut pack = |offset: usize| {
+                    lane_values.clear();
+                    lane_values.extend(instances.iter().map(|values| values[offset]));
+                    lane_values.resize(pack_size, C::CircuitField::ZERO);
+                    C::SimdCircuitField::pack(&lane_values)
+                };
+                let private_input = (0..num_private_inputs).map(&mut pack).collect();
+                let public_input = (num_private_inputs..total_size).map(&mut pack).collect();
+                (private_input, public_input)
+            })
+            .collect();
+        Ok(WitnessBatch {
+            num_instances: instances.len(),
+            packs,
+        })
+    }
+
+    /// Read the packed private and public inputs of the witness of the reader, from its
+    /// position, into the given buffers, for a circuit of `log_input_size` input variables. The
+    /// values of the simd lanes are read `WITNESS_CHUNK_SIZE` at a time, each chunk packed before
+    /// the next is read. With `public_only`, the private inputs are skipped, and may be left out
+    /// of the witness, see `split_witness_payload`. The buffers are only cleared once the header
+    /// is checked.
+    fn read_packed_witness_into<R: Read + Seek>(
+        log_input_size: usize,
+        reader: &mut R,
+        allow_padding: bool,
+        public_only: bool,
+        private_input: &mut Vec<C::SimdCircuitField>,
+        public_input: &mut Vec<C::SimdCircuitField>,
+    ) -> Result<(), CircuitError> {
+        let start = reader.stream_position()?;
+        let len = reader.seek(SeekFrom::End(0))? - start;
+        reader.seek(SeekFrom::Start(start))?;
+        let mut header = [0u8; WITNESS_HEADER_SIZE + WITNESS_PAYLOAD_HEADER_SIZE];
+        let header_len = header.len().min(len as usize);
+        reader.read_exact(&mut header[..header_len])?;
+        let payload_header = witness_payload::<C::CircuitField>(&header[..header_len])?;
+        let (num_witnesses, num_private_inputs_per_witness, num_public_inputs_per_witness, _) =
+            Self::parse_witness_payload_header(
+                log_input_size,
+                payload_header,
+                (len as usize).saturating_sub(WITNESS_HEADER_SIZE),
+                public_only,
+            )?;
+
+        let pack_size = C::get_field_pack_size();
+        #[allow(clippy::comparison_chain)]
+        if num_witnesses < pack_size {
+            if !allow_padding {
+                return Err(WitnessError::NotEnoughWitnesses {
+                    expected: pack_size,
+                    got: num_witnesses,
+                }
+                .into());
+            }
+            println!(
+                "Warning: padding witnesses, expected {}, got {}",
+                pack_size, num_witnesses
+            );
+        } else if num_witnesses > pack_size {
+            println!(
+                "Warning: dropping additional witnesses, expected {}, got {}",
+                pack_size, num_witnesses
+            );
+        }
+        let total_size = num_private_inputs_per_witness + num_public_inputs_per_witness;
+        let lanes = pack_size.min(num_witnesses);
+        if lanes == 0 && total_size > 0 {
+            return Err(WitnessError::NoWitnesses.into());
+        }
+
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+        let values_start = start + header.len() as u64;
+        let first = if public_only {
+            num_private_inputs_per_witness
+        } else {
+            0
+        };
+        private_input.clear();
+        private_input.reserve(num_private_inputs_per_witness - first);
+        public_input.clear();
+        public_input.reserve(num_public_inputs_per_witness);
+        let mut bytes = vec![];
+        let mut values = vec![];
+        let mut lane_values = Vec::with_capacity(pack_size);
+        for chunk_start in (first..total_size).step_by(WITNESS_CHUNK_SIZE) {
+            let chunk_len = WITNESS_CHUNK_SIZE.min(total_size - chunk_start);
+            bytes.resize(chunk_len * value_size, 0);
+            values.clear();
+            for j in 0..lanes {
+                let offset = (j * total_size + chunk_start) * value_size;
+                reader.seek(SeekFrom::Start(values_start + offset as u64))?;
+                reader.read_exact(&mut bytes)?;
+                for value in bytes.chunks_exact(value_size) {
+                    values.push(C::CircuitField::deserialize_from(value)?);
+                }
+            }
+            for i in 0..chunk_len {
+                lane_values.clear();
+                lane_values.extend((0..lanes).map(|j| values[j * chunk_len + i]));
+                lane_values.resize(pack_size, lane_values[0]);
+                let packed = C::SimdCircuitField::pack(&lane_values);
+                if chunk_start + i < num_private_inputs_per_witness {
+                    private_input.push(packed);
+                } else {
+                    public_input.push(packed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The bytes of the witness file of the circuit, header included, with the private inputs
+    /// left out, to hand to a verifier, see `load_public_input_file`.
+    pub fn public_input_bytes(&self, file_bytes: &[u8]) -> Result<Vec<u8>, CircuitError> {
+        let payload = witness_payload::<C::CircuitField>(file_bytes)?;
+        let (num_witnesses, num_private_inputs_per_witness, num_public_inputs_per_witness, bytes) =
+            Self::split_witness_payload(self.log_input_size(), payload, true)?;
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+        let total_size = num_private_inputs_per_witness + num_public_inputs_per_witness;
+
+        let mut public_bytes = file_bytes[..WITNESS_HEADER_SIZE].to_vec();
+        num_witnesses.serialize_into(&mut public_bytes)?;
+        0usize.serialize_into(&mut public_bytes)?;
+        num_public_inputs_per_witness.serialize_into(&mut public_bytes)?;
+        // the modulus
+        public_bytes.extend_from_slice(&payload[3 * 8..WITNESS_PAYLOAD_HEADER_SIZE]);
+        if total_size > 0 {
+            for witness in bytes.chunks_exact(total_size * value_size) {
+                public_bytes
+                    .extend_from_slice(&witness[num_private_inputs_per_witness * value_size..]);
+            }
+        }
+        Ok(public_bytes)
+    }
+
+    /// The number of witnesses, of private and of public inputs per witness of the witness
+    /// payload, and the bytes of its values, checked to be exactly as many as its header tells
+    /// for a circuit of `log_input_size` input variables, without trusting the header for any
+    /// allocation. With `public_only`, the witnesses may also have no private inputs.
+    fn split_witness_payload(
+        log_input_size: usize,
+        payload: &[u8],
+        public_only: bool,
+    ) -> Result<(usize, usize, usize, &[u8]), CircuitError> {
+        let (num_witnesses, num_private_inputs_per_witness, num_public_inputs_per_witness, len) =
+            Self::parse_witness_payload_header(
+                log_input_size,
+                payload,
+                payload.len(),
+                public_only,
+            )?;
+        Ok((
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+            &payload[WITNESS_PAYLOAD_HEADER_SIZE..WITNESS_PAYLOAD_HEADER_SIZE + len],
+        ))
+    }
+
+    /// As `split_witness_payload`, for the header at the start of a payload of `payload_len`
+    /// bytes, with the length of its values rather than the values.
+    fn parse_witness_payload_header(
+        log_input_size: usize,
+        header: &[u8],
+        payload_len: usize,
+        public_only: bool,
+    ) -> Result<(usize, usize, usize, usize), CircuitError> {
+        if header.len() < WITNESS_PAYLOAD_HEADER_SIZE || payload_len < WITNESS_PAYLOAD_HEADER_SIZE {
+            return Err(WitnessError::Truncated.into());
+        }
+        let mut header = &header[..WITNESS_PAYLOAD_HEADER_SIZE];
+        let num_witnesses = <usize as FieldSerde>::deserialize_from(&mut header)?;
+        let num_private_inputs_per_witness = <usize as FieldSerde>::deserialize_from(&mut header)?;
+        let num_public_inputs_per_witness = <usize as FieldSerde>::deserialize_from(&mut header)?;
+
+        let private_input_size = 1 << log_input_size;
+        if num_private_inputs_per_witness != private_input_size
+            && !(public_only && num_private_inputs_per_witness == 0)
+        {
+            return Err(WitnessError::PrivateInputMismatch {
+                expected: private_input_size,
+                got: num_private_inputs_per_witness,
+            }
+            .into());
+        }
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+        let available = payload_len - WITNESS_PAYLOAD_HEADER_SIZE;
+        let values_len = num_private_inputs_per_witness
+            .checked_add(num_public_inputs_per_witness)
+            .and_then(|num_inputs| num_inputs.checked_mul(num_witnesses))
+            .and_then(|num_values| num_values.checked_mul(value_size));
+        match values_len {
+            Some(values_len) if values_len == available => Ok((
+                num_witnesses,
+                num_private_inputs_per_witness,
+                num_public_inputs_per_witness,
+                values_len,
+            )),
+            _ => Err(WitnessError::LengthMismatch {
+                expected: (WITNESS_HEADER_SIZE + WITNESS_PAYLOAD_HEADER_SIZE)
+                    .saturating_add(values_len.unwrap_or(usize::MAX)),
+                got: WITNESS_HEADER_SIZE + payload_len,
+            }
+            .into()),
+        }
+    }
+
+    /// Load the witness bytes, header included, into the input layer and the public input. The
+    /// values are packed from the bytes where they are, and overwrite the inputs of the previous
+    /// witness in place: a witness of the same size allocates nothing.
     pub fn load_witness_bytes(&mut self, file_bytes: &[u8], allow_padding: bool) {
-        let cursor = Cursor::new(file_bytes);
-        let witness = Witness::<C>::deserialize_from(cursor);
+        let payload = witness_payload::<C::CircuitField>(file_bytes).unwrap();
+        let mut header = payload;
+        let num_witnesses = <usize as FieldSerde>::deserialize_from(&mut header).unwrap();
+        let num_private_inputs_per_witness =
+            <usize as FieldSerde>::deserialize_from(&mut header).unwrap();
+        let num_public_inputs_per_witness =
+            <usize as FieldSerde>::deserialize_from(&mut header).unwrap();
+        let values = &payload[WITNESS_PAYLOAD_HEADER_SIZE..];
+        let value_size = C::CircuitField::SERIALIZED_SIZE;
+
+        Self::pack_witness_values_into(
+            self.log_input_size(),
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+            allow_padding,
+            |idx| C::CircuitField::deserialize_from(&values[idx * value_size..]).unwrap(),
+            &mut self.layers[0].input_vals,
+            &mut self.public_input,
+        );
+    }
+
+    /// Set the input layer values to the witness. The values are copied into the current input
+    /// buffer, which is only allocated if it is smaller than the witness, e.g., on the first call.
+    ///
+    /// Panics if the witness is not of the input layer size.
+    pub fn load_witness_array<const N: usize>(
+        &mut self,
+        witness: WitnessTypedArray<C::SimdCircuitField, N>,
+    ) {
+        assert_eq!(N, 1 << self.log_input_size(), "wrong witness size");
+        let input_vals = &mut self.layers[0].input_vals;
+        input_vals.resize(N, C::SimdCircuitField::ZERO);
+        input_vals.copy_from_slice(witness.as_ref());
+    }
+
+    /// Parse the witness bytes, header included, into the packed private and public inputs of a
+    /// circuit with `log_input_size` input variables, without touching the circuit itself.
+    pub fn parse_witness_bytes(
+        log_input_size: usize,
+        file_bytes: &[u8],
+        allow_padding: bool,
+    ) -> (Vec<C::SimdCircuitField>, Vec<C::SimdCircuitField>) {
+        let payload = witness_payload::<C::CircuitField>(file_bytes).unwrap();
+        let witness = Witness::<C>::deserialize_from(Cursor::new(payload)).unwrap();
+
+        Self::pack_witness_values(
+            log_input_size,
+            witness.num_witnesses,
+            witness.num_private_inputs_per_witness,
+            witness.num_public_inputs_per_witness,
+            allow_padding,
+            |idx| witness.values[idx],
+        )
+    }
+
+    /// As `parse_witness_bytes`, but returning an error if the witness does not fit a circuit of
+    /// `log_input_size` input variables, as `try_load_witness_bytes` does.
+    pub fn try_parse_witness_bytes(
+        log_input_size: usize,
+        file_bytes: &[u8],
+        allow_padding: bool,
+    ) -> Result<(Vec<C::SimdCircuitField>, Vec<C::SimdCircuitField>), CircuitError> {
+        let (mut private_input, mut public_input) = (vec![], vec![]);
+        Self::read_packed_witness_into(
+            log_input_size,
+            &mut Cursor::new(file_bytes),
+            allow_padding,
+            false,
+            &mut private_input,
+            &mut public_input,
+        )?;
+        Ok((private_input, public_input))
+    }
 
-        let private_input_size = 1 << self.log_input_size();
-        let public_input_size = witness.num_public_inputs_per_witness;
+    /// Pack the witness values into the private and public inputs of a circuit with
+    /// `log_input_size` input variables. `get(idx)` returns `values[idx]` of the witness.
+    pub fn pack_witness_values(
+        log_input_size: usize,
+        num_witnesses: usize,
+        num_private_inputs_per_witness: usize,
+        num_public_inputs_per_witness: usize,
+        allow_padding: bool,
+        get: impl Fn(usize) -> C::CircuitField,
+    ) -> (Vec<C::SimdCircuitField>, Vec<C::SimdCircuitField>) {
+        let mut private_input = vec![];
+        let mut public_input = vec![];
+        Self::pack_witness_values_into(
+            log_input_size,
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+            allow_padding,
+            get,
+            &mut private_input,
+            &mut public_input,
+        );
+        (private_input, public_input)
+    }
+
+    /// As `pack_witness_values`, into the given buffers, cleared first and only grown if they
+    /// are smaller than the inputs.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pack_witness_values_into(
+        log_input_size: usize,
+        num_witnesses: usize,
+        num_private_inputs_per_witness: usize,
+        num_public_inputs_per_witness: usize,
+        allow_padding: bool,
+        get: impl Fn(usize) -> C::CircuitField,
+        private_input: &mut Vec<C::SimdCircuitField>,
+        public_input: &mut Vec<C::SimdCircuitField>,
+    ) {
+        let private_input_size = 1 << log_input_size;
+        let public_input_size = num_public_inputs_per_witness;
         let total_size = private_input_size + public_input_size;
 
-        assert_eq!(witness.num_private_inputs_per_witness, private_input_size);
+        assert_eq!(num_private_inputs_per_witness, private_input_size);
         #[allow(clippy::comparison_chain)]
-        if witness.num_witnesses < C::get_field_pack_size() {
+        if num_witnesses < C::get_field_pack_size() {
             if !allow_padding {
                 panic!(
                     "Not enough witness, expected {}, got {}",
                     C::get_field_pack_size(),
-                    witness.num_witnesses
+                    num_witnesses
                 );
             } else {
                 println!(
                     "Warning: padding witnesses, expected {}, got {}",
                     C::get_field_pack_size(),
-                    witness.num_witnesses
+                    num_witnesses
                 );
             }
-        } else if witness.num_witnesses > C::get_field_pack_size() {
+        } else if num_witnesses > C::get_field_pack_size() {
             println!(
                 "Warning: dropping additional witnesses, expected {}, got {}",
                 C::get_field_pack_size(),
-                witness.num_witnesses
+                num_witnesses
             );
         }
 
-        let input = &witness.values;
-        let private_input = &mut self.layers[0].input_vals;
-        let public_input = &mut self.public_input;
+        let pack_size = C::get_field_pack_size();
+        let lanes = pack_size.min(num_witnesses);
+        let mut lane_values = Vec::with_capacity(pack_size);
+        let mut pack = |offset: usize| {
+            lane_values.clear();
+            lane_values.extend((0..lanes).map(|j| get(j * total_size + offset)));
+            lane_values.resize(pack_size, lane_values[0]);
+            C::SimdCircuitField::pack(&lane_values)
+        };
 
         private_input.clear();
+        private_input.extend((0..private_input_size).map(&mut pack));
         public_input.clear();
-
-        for i in 0..private_input_size {
-            let mut private_wit_i = vec![];
-            for j in 0..C::get_field_pack_size().min(witness.num_witnesses) {
-                private_wit_i.push(input[j * total_size + i]);
-            }
-            while private_wit_i.len() < C::get_field_pack_size() {
-                private_wit_i.push(private_wit_i[0]);
-            }
-            private_input.push(C::SimdCircuitField::pack(&private_wit_i));
-        }
-
-        for i in 0..public_input_size {
-            let mut public_wit_i = vec![];
-            for j in 0..C::get_field_pack_size().min(witness.num_witnesses) {
-                public_wit_i.push(input[j * total_size + private_input_size + i]);
-            }
-            while public_wit_i.len() < C::get_field_pack_size() {
-                public_wit_i.push(public_wit_i[0]);
-            }
-            public_input.push(C::SimdCircuitField::pack(&public_wit_i));
-        }
+        public_input.extend((0..public_input_size).map(|i| pack(private_input_size + i)));
     }
 }
 
@@ -250,12 +856,11 @@ impl<C: GKRConfig> Circuit<C> {
                     .collect::<Vec<_>>()
             );
         }
-        let mut output = vec![];
-        self.layers
-            .last()
-            .unwrap()
-            .evaluate(&mut output, &self.public_input);
-        self.layers.last_mut().unwrap().output_vals = output;
+        // the output values of the previous evaluation are overwritten in place
+        let last = self.layers.last_mut().unwrap();
+        let mut output = std::mem::take(&mut last.output_vals);
+        last.evaluate(&mut output, &self.public_input);
+        last.output_vals = output;
 
         log::trace!("output evaluated");
         log::trace!(
@@ -270,6 +875,36 @@ impl<C: GKRConfig> Circuit<C> {
         );
     }
 
+    /// Evaluate the circuit on the given input layer values, reporting every gate evaluation to
+    /// the observer, and return the output values.
+    pub fn evaluate_with_observer(
+        &mut self,
+        witness: &[C::SimdCircuitField],
+        observer: &mut dyn CircuitObserver<C>,
+    ) -> Vec<C::SimdCircuitField> {
+        assert_eq!(witness.len(), 1 << self.log_input_size());
+        self.layers[0].input_vals = witness.to_vec();
+
+        for i in 0..self.layers.len() - 1 {
+            let (layer_p_1, layer_p_2) = self.layers.split_at_mut(i + 1);
+            layer_p_1.last().unwrap().evaluate_with_observer(
+                &mut layer_p_2[0].input_vals,
+                &self.public_input,
+                i,
+                observer,
+            );
+        }
+        let mut output = vec![];
+        self.layers.last().unwrap().evaluate_with_observer(
+            &mut output,
+            &self.public_input,
+            self.layers.len() - 1,
+            observer,
+        );
+        self.layers.last_mut().unwrap().output_vals = output.clone();
+        output
+    }
+
     pub fn identify_rnd_coefs(&mut self) {
         self.rnd_coefs.clear();
         for layer in &mut self.layers {
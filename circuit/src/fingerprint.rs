@@ -0,0 +1,147 @@
+//! A canonical encoding of flattened circuits, and the hash identifying a circuit.
+//!
+//! The encoding only depends on what the circuit computes: the gates of each kind are sorted,
+//! and the coefficients of random gates, which are filled from the transcript, are left out.
+//! It can be decoded back into a circuit with the same hash.
+
+use std::io::Read;
+
+use arith::{Field, FieldSerde, FieldSerdeError, FieldSerdeResult};
+use config::GKRConfig;
+use sha2::{Digest, Sha256};
+
+use crate::{Circuit, CircuitLayer, CoefType, Gate};
+
+impl<C: GKRConfig, const INPUT_NUM: usize> Gate<C, INPUT_NUM> {
+    /// The coefficient is encoded as in the ecc format: a tag, 1 for constant, 2 for random
+    /// and 3 for public input, followed by the value or the public input index.
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.i_ids
+            .iter()
+            .for_each(|id| id.serialize_into(&mut bytes).unwrap());
+        self.o_id.serialize_into(&mut bytes).unwrap();
+        match self.coef_type {
+            CoefType::Constant => {
+                bytes.push(1);
+                self.coef.serialize_into(&mut bytes).unwrap();
+            }
+            CoefType::Random => bytes.push(2),
+            CoefType::PublicInput(idx) => {
+                bytes.push(3);
+                idx.serialize_into(&mut bytes).unwrap();
+            }
+        }
+        self.gate_type.serialize_into(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn from_canonical_bytes<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut i_ids = [0; INPUT_NUM];
+        for id in &mut i_ids {
+            *id = usize::deserialize_from(&mut reader)?;
+        }
+        let o_id = usize::deserialize_from(&mut reader)?;
+        let (coef_type, coef) = match u8::deserialize_from(&mut reader)? {
+            1 => (
+                CoefType::Constant,
+                C::CircuitField::deserialize_from(&mut reader)?,
+            ),
+            2 => (CoefType::Random, C::CircuitField::ZERO),
+            3 => (
+                CoefType::PublicInput(usize::deserialize_from(&mut reader)?),
+                C::CircuitField::ZERO,
+            ),
+            _ => return Err(FieldSerdeError::DeserializeError),
+        };
+        Ok(Self {
+            i_ids,
+            o_id,
+            coef_type,
+            coef,
+            gate_type: usize::deserialize_from(&mut reader)?,
+        })
+    }
+}
+
+fn serialize_gates<C: GKRConfig, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+    bytes: &mut Vec<u8>,
+) {
+    let mut gate_bytes = gates.iter().map(Gate::canonical_bytes).collect::<Vec<_>>();
+    gate_bytes.sort_unstable();
+
+    gate_bytes.len().serialize_into(&mut *bytes).unwrap();
+    gate_bytes.iter().for_each(|b| bytes.extend_from_slice(b));
+}
+
+fn deserialize_gates<C: GKRConfig, const INPUT_NUM: usize, R: Read>(
+    mut reader: R,
+) -> FieldSerdeResult<Vec<Gate<C, INPUT_NUM>>> {
+    let len = usize::deserialize_from(&mut reader)?;
+    (0..len)
+        .map(|_| Gate::from_canonical_bytes(&mut reader))
+        .collect()
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// The canonical encoding of the circuit, prefixed by the name of the circuit field.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        let name = C::CircuitField::NAME.as_bytes();
+        name.len().serialize_into(&mut bytes).unwrap();
+        bytes.extend_from_slice(name);
+
+        self.layers.len().serialize_into(&mut bytes).unwrap();
+        for layer in &self.layers {
+            layer.input_var_num.serialize_into(&mut bytes).unwrap();
+            layer.output_var_num.serialize_into(&mut bytes).unwrap();
+            serialize_gates(&layer.mul, &mut bytes);
+            serialize_gates(&layer.add, &mut bytes);
+            serialize_gates(&layer.const_, &mut bytes);
+            serialize_gates(&layer.uni, &mut bytes);
+        }
+        bytes
+    }
+
+    /// Decode a canonical encoding. The gates come sorted, and the random coefficients are
+    /// zero until filled from a transcript.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> FieldSerdeResult<Self> {
+        let mut reader = bytes;
+        let name_len = usize::deserialize_from(&mut reader)?;
+        let name = C::CircuitField::NAME.as_bytes();
+        if name_len != name.len() || reader.get(..name_len) != Some(name) {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        reader = &reader[name_len..];
+
+        let layer_num = usize::deserialize_from(&mut reader)?;
+        let layers = (0..layer_num)
+            .map(|_| {
+                let mut layer = CircuitLayer::<C> {
+                    input_var_num: usize::deserialize_from(&mut reader)?,
+                    output_var_num: usize::deserialize_from(&mut reader)?,
+                    ..Default::default()
+                };
+                layer.mul = deserialize_gates(&mut reader)?;
+                layer.add = deserialize_gates(&mut reader)?;
+                layer.const_ = deserialize_gates(&mut reader)?;
+                layer.uni = deserialize_gates(&mut reader)?;
+                Ok(layer)
+            })
+            .collect::<FieldSerdeResult<Vec<_>>>()?;
+        if !reader.is_empty() {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+
+        Ok(Self {
+            layers,
+            ..Default::default()
+        })
+    }
+
+    /// Sha256 of the canonical encoding.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.canonical_bytes()).into()
+    }
+}
@@ -21,3 +21,20 @@ pub type GateMul<C> = Gate<C, 2>;
 pub type GateAdd<C> = Gate<C, 1>;
 pub type GateUni<C> = Gate<C, 1>;
 pub type GateConst<C> = Gate<C, 0>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GateKind {
+    Mul,
+    Add,
+    Const,
+    Uni,
+}
+
+/// Reference to a gate of a flattened circuit:
+/// the gate is `layers[layer].mul[index]`, `layers[layer].add[index]`, etc., according to `kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GateRef {
+    pub layer: usize,
+    pub kind: GateKind,
+    pub index: usize,
+}
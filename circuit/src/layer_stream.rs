@@ -0,0 +1,132 @@
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+
+use config::GKRConfig;
+
+#[cfg(feature = "mmap")]
+use crate::MappedCircuit;
+use crate::{Circuit, CircuitError, CircuitLayer, RecursiveCircuit};
+
+/// The compact recursive form of the circuit of a stream, read into memory, or mapped from its
+/// file, see `MappedCircuit`.
+pub enum CircuitStorage<C: GKRConfig> {
+    Owned(RecursiveCircuit<C>),
+    #[cfg(feature = "mmap")]
+    Mapped(MappedCircuit<C>),
+}
+
+impl<C: GKRConfig> CircuitStorage<C> {
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        match self {
+            Self::Owned(rc) => rc.num_layers(),
+            #[cfg(feature = "mmap")]
+            Self::Mapped(mc) => mc.num_layers(),
+        }
+    }
+
+    /// The layer `index` of the flattened circuit, the same for both storages.
+    pub fn flatten_layer(&self, index: usize) -> CircuitLayer<C> {
+        match self {
+            Self::Owned(rc) => rc.flatten_layer(index),
+            #[cfg(feature = "mmap")]
+            Self::Mapped(mc) => mc.flatten_layer(index),
+        }
+    }
+}
+
+/// The layers of a circuit, from the input layer up, flattened from its compact recursive form
+/// `batch_size` layers at a time. At most one batch of flattened layers is held by the stream,
+/// and cloning it only shares the recursive circuit.
+pub struct CircuitLayerStream<C: GKRConfig> {
+    rc: Arc<CircuitStorage<C>>,
+    batch_size: usize,
+    /// The index of the next layer to flatten.
+    next: usize,
+    batch: VecDeque<CircuitLayer<C>>,
+}
+
+impl<C: GKRConfig> Clone for CircuitLayerStream<C> {
+    fn clone(&self) -> Self {
+        Self {
+            rc: self.rc.clone(),
+            batch_size: self.batch_size,
+            next: self.next,
+            batch: self.batch.clone(),
+        }
+    }
+}
+
+impl<C: GKRConfig> CircuitLayerStream<C> {
+    pub fn new(rc: RecursiveCircuit<C>, batch_size: usize) -> Self {
+        Self::with_storage(CircuitStorage::Owned(rc), batch_size)
+    }
+
+    /// The layers of the mapped circuit, flattened from the mapping, which the clones of the
+    /// stream share.
+    #[cfg(feature = "mmap")]
+    pub fn mapped(mc: MappedCircuit<C>, batch_size: usize) -> Self {
+        Self::with_storage(CircuitStorage::Mapped(mc), batch_size)
+    }
+
+    pub fn with_storage(storage: CircuitStorage<C>, batch_size: usize) -> Self {
+        assert!(batch_size > 0);
+        Self {
+            rc: Arc::new(storage),
+            batch_size,
+            next: 0,
+            batch: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// The number of layers of the circuit, whatever was streamed already.
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.rc.num_layers()
+    }
+
+    /// The layers of the range, flattened again.
+    pub fn layers(&self, range: Range<usize>) -> Vec<CircuitLayer<C>> {
+        range.map(|i| self.rc.flatten_layer(i)).collect()
+    }
+}
+
+impl<C: GKRConfig> Iterator for CircuitLayerStream<C> {
+    type Item = CircuitLayer<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batch.is_empty() {
+            let last = (self.next + self.batch_size).min(self.num_layers());
+            self.batch = self.layers(self.next..last).into();
+            self.next = last;
+        }
+        self.batch.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.batch.len() + self.num_layers() - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<C: GKRConfig> ExactSizeIterator for CircuitLayerStream<C> {}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// The layers of the circuit file, flattened `layer_batch_size` layers at a time instead of
+    /// all at once as by `load_circuit`, see `Prover::prove_streaming`. The file is loaded in
+    /// its compact recursive form, so that its errors are returned here rather than by the
+    /// stream.
+    pub fn load_circuit_streaming(
+        path: &str,
+        layer_batch_size: usize,
+    ) -> Result<CircuitLayerStream<C>, CircuitError> {
+        Ok(CircuitLayerStream::new(
+            RecursiveCircuit::<C>::load(path)?,
+            layer_batch_size,
+        ))
+    }
+}
@@ -7,8 +7,40 @@ pub use ecc_circuit::*;
 mod expander_circuit;
 pub use expander_circuit::*;
 
+mod builder;
+pub use builder::*;
+
+pub mod composer;
+
+mod layer_stream;
+pub use layer_stream::*;
+
+#[cfg(feature = "mmap")]
+mod mapped_circuit;
+#[cfg(feature = "mmap")]
+pub use mapped_circuit::*;
+
+mod fingerprint;
+
+mod observer;
+pub use observer::*;
+
 mod witness;
 pub use witness::*;
 
 mod serde;
 pub use serde::*;
+
+mod optimizer;
+pub use optimizer::*;
+
+mod r1cs;
+pub use r1cs::*;
+
+mod stats;
+pub use stats::*;
+
+mod validate;
+
+mod verifier_key;
+pub use verifier_key::*;
@@ -0,0 +1,172 @@
+use std::{cmp::max, collections::HashMap, fs, io::Cursor, marker::PhantomData};
+
+use arith::FieldSerde;
+use config::GKRConfig;
+
+use crate::{
+    ecc_circuit::trace_layer,
+    serde::{deserialize_header, segment_var_num},
+    *,
+};
+
+/// A circuit file mapped into memory, with the offsets of the gates of its segments rather than
+/// the gates themselves, see `Circuit::load_circuit_mmap`. The layers are flattened from the
+/// mapping on demand, e.g., by a `CircuitLayerStream`, so that the processes of a machine that
+/// map the same file share its pages in the page cache instead of each holding its gates.
+pub struct MappedCircuit<C: GKRConfig> {
+    mmap: memmap2::Mmap,
+    pub num_public_inputs: usize,
+    pub num_outputs: usize,
+    pub expected_num_output_zeros: usize,
+
+    segments: Vec<MappedSegment>,
+    layers: Vec<SegmentId>,
+    _marker: PhantomData<C>,
+}
+
+/// A segment of the file, with its child segments, and its gates left in the mapping.
+struct MappedSegment {
+    i_var_num: usize,
+    o_var_num: usize,
+    child_segs: Vec<(SegmentId, Vec<Allocation>)>,
+    /// The offset of the gates in the file, from the number of mul gates.
+    gates_offset: usize,
+    contain_gates: bool,
+}
+
+impl<C: GKRConfig> MappedCircuit<C> {
+    /// Map the circuit file, checked to be of the field of `C`, and index its segments. The
+    /// gates are parsed once to find where each segment ends, as by `RecursiveCircuit::load`,
+    /// returning the malformed bytes as errors.
+    pub fn load(path: &str) -> Result<Self, CircuitError> {
+        let file = fs::File::open(path)?;
+        // the circuit files are not expected to change while being mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        RecursiveCircuit::<C>::check_field(&mmap)?;
+
+        let mut reader = Cursor::new(&mmap[..]);
+        let read_usize = |reader: &mut Cursor<&[u8]>| {
+            <usize as FieldSerde>::deserialize_from(reader).map_err(CircuitError::from)
+        };
+        deserialize_header::<C, _>(&mut reader)?;
+        let num_public_inputs = read_usize(&mut reader)?;
+        let num_outputs = read_usize(&mut reader)?;
+        let expected_num_output_zeros = read_usize(&mut reader)?;
+
+        let num_segments = read_usize(&mut reader)?;
+        // grown with the segments read, as a vector
+        let mut segments = vec![];
+        for _ in 0..num_segments {
+            let i_var_num = segment_var_num(read_usize(&mut reader)?, "input")?;
+            let o_var_num = segment_var_num(read_usize(&mut reader)?, "output")?;
+            let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader)?;
+            let gates_offset = reader.position() as usize;
+            // read and dropped, the gates of a single segment at a time
+            let mut gates = Segment::<C>::default();
+            gates.deserialize_gates_from(&mut reader)?;
+            segments.push(MappedSegment {
+                i_var_num,
+                o_var_num,
+                child_segs,
+                gates_offset,
+                contain_gates: gates.contain_gates(),
+            });
+        }
+        let layers = <Vec<usize> as FromEccSerde>::deserialize_from(&mut reader)?;
+
+        Ok(Self {
+            mmap,
+            num_public_inputs,
+            num_outputs,
+            expected_num_output_zeros,
+            segments,
+            layers,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// The gates of the segment, parsed from the mapping, without its child segments.
+    fn segment_gates(&self, id: SegmentId) -> Segment<C> {
+        let mut segment = Segment::default();
+        segment
+            .deserialize_gates_from(&self.mmap[self.segments[id].gates_offset..])
+            .expect("the gates are parsed by load");
+        segment
+    }
+
+    /// As `Segment::scan_leaf_segments`.
+    fn scan_leaf_segments(&self, cur_id: SegmentId) -> HashMap<SegmentId, Vec<Allocation>> {
+        let mut ret = HashMap::new();
+        let segment = &self.segments[cur_id];
+        if segment.contain_gates {
+            ret.insert(
+                cur_id,
+                vec![Allocation {
+                    i_offset: 0,
+                    o_offset: 0,
+                }],
+            );
+        }
+        for (child_seg_id, child_allocs) in &segment.child_segs {
+            for (leaf_seg_id, leaf_allocs) in self.scan_leaf_segments(*child_seg_id) {
+                let allocs: &mut Vec<Allocation> = ret.entry(leaf_seg_id).or_default();
+                for child_alloc in child_allocs {
+                    for leaf_alloc in &leaf_allocs {
+                        allocs.push(Allocation {
+                            i_offset: child_alloc.i_offset + leaf_alloc.i_offset,
+                            o_offset: child_alloc.o_offset + leaf_alloc.o_offset,
+                        });
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// The layer `index` of the flattened circuit, as `RecursiveCircuit::flatten_layer`, its
+    /// gates parsed from the mapping.
+    pub fn flatten_layer(&self, index: usize) -> CircuitLayer<C> {
+        let layer_id = self.layers[index];
+        let layer_seg = &self.segments[layer_id];
+        let mut ret_layer = CircuitLayer {
+            input_var_num: max(layer_seg.i_var_num, 1), // var_num >= 1
+            output_var_num: max(layer_seg.o_var_num, 1), // var_num >= 1
+            ..Default::default()
+        };
+        for (leaf_seg_id, leaf_allocs) in self.scan_leaf_segments(layer_id) {
+            self.segment_gates(leaf_seg_id)
+                .push_gates(&leaf_allocs, &mut ret_layer);
+        }
+        trace_layer(index, &ret_layer);
+        ret_layer
+    }
+
+    /// The circuit of `Circuit::load_circuit` for the file.
+    pub fn flatten(&self) -> Circuit<C> {
+        let mut ret = Circuit::<C> {
+            expected_num_output_zeros: self.expected_num_output_zeros,
+            ..Default::default()
+        };
+        ret.layers = (0..self.num_layers())
+            .map(|i| self.flatten_layer(i))
+            .collect();
+
+        ret.identify_rnd_coefs();
+        ret.identify_structure_info();
+        ret
+    }
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// The circuit file mapped read-only rather than read, its layers flattened on demand, see
+    /// `MappedCircuit` and `CircuitLayerStream::mapped`. Where the file cannot be mapped, the
+    /// error is that of the mapping, and `load_circuit` reads it as before.
+    pub fn load_circuit_mmap(path: &str) -> Result<MappedCircuit<C>, CircuitError> {
+        MappedCircuit::load(path)
+    }
+}
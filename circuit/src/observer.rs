@@ -0,0 +1,50 @@
+use config::GKRConfig;
+
+use crate::GateRef;
+
+/// Hook called on every gate evaluated by `Circuit::evaluate_with_observer`.
+pub trait CircuitObserver<C: GKRConfig> {
+    /// `output` is the contribution of the gate to its output wire, i.e., the coefficient times
+    /// the gate function of the inputs. A const gate has no input.
+    fn on_gate_evaluated(
+        &mut self,
+        gate: GateRef,
+        inputs: &[C::SimdCircuitField],
+        output: C::SimdCircuitField,
+    );
+}
+
+/// Observer doing nothing, used by the plain evaluation.
+pub struct NoopCircuitObserver;
+
+impl<C: GKRConfig> CircuitObserver<C> for NoopCircuitObserver {
+    #[inline(always)]
+    fn on_gate_evaluated(
+        &mut self,
+        _gate: GateRef,
+        _inputs: &[C::SimdCircuitField],
+        _output: C::SimdCircuitField,
+    ) {
+    }
+}
+
+/// Observer logging every gate evaluation.
+pub struct PrintCircuitObserver;
+
+impl<C: GKRConfig> CircuitObserver<C> for PrintCircuitObserver {
+    fn on_gate_evaluated(
+        &mut self,
+        gate: GateRef,
+        inputs: &[C::SimdCircuitField],
+        output: C::SimdCircuitField,
+    ) {
+        log::info!(
+            "layer {} {:?} gate {}: inputs {:?} -> {:?}",
+            gate.layer,
+            gate.kind,
+            gate.index,
+            inputs,
+            output
+        );
+    }
+}
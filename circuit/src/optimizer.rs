@@ -0,0 +1,223 @@
+//! Constant folding and dead-wire elimination over the gates of a flattened circuit, see
+//! `Circuit::optimize`.
+//!
+//! A wire is known when it is only written by const gates: with a constant value if all of them
+//! have constant coefficients, or as a public input if it is a single public input gate. The
+//! gates of the layer above whose value follows from known inputs become const gates, and the
+//! gates whose value is zero are dropped, so that the known wires propagate up the layers.
+//! The gates are then kept from the output layer down only if the layer above reads their
+//! output wire.
+//!
+//! The gates with random coefficients are neither folded nor removed: the random coefficients
+//! are drawn from the transcript before the output challenges, so their number fixes
+//! `claimed_v` as much as the outputs do.
+
+use arith::Field;
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitLayer, CoefType, Gate, GateConst};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizationReport {
+    pub gates_before: usize,
+    pub gates_after: usize,
+    /// The gates replaced by a const gate, or dropped as always zero.
+    pub constant_folds: usize,
+    /// The gates removed as their output wire is not read.
+    pub dead_wires_removed: usize,
+}
+
+/// The value of a wire known before the witness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KnownWire<F> {
+    Constant(F),
+    PublicInput(usize),
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// Fold the gates of known value into const gates, then remove the gates whose output is not
+    /// needed by the output layer. The numbers of variables of the layers are kept, so the
+    /// optimized circuit takes the same witness, has the same outputs, and proves the same
+    /// `claimed_v`.
+    pub fn optimize(&mut self) -> OptimizationReport {
+        let gates_before = self.num_gates();
+
+        let mut constant_folds = 0;
+        for i in 1..self.layers.len() {
+            let (prev_layers, layers) = self.layers.split_at_mut(i);
+            let known = known_output_wires(prev_layers.last().unwrap());
+            constant_folds += fold_constants(&mut layers[0], &known);
+        }
+
+        let mut dead_wires_removed = 0;
+        let mut live = vec![true; 1 << self.layers.last().unwrap().output_var_num];
+        for layer in self.layers.iter_mut().rev() {
+            dead_wires_removed += remove_dead_gates(layer, &live);
+            live = read_input_wires(layer);
+        }
+
+        if constant_folds + dead_wires_removed > 0 {
+            self.identify_structure_info();
+            // the gates have moved, so the pointers to the random coefficients are stale
+            if self.rnd_coefs_identified {
+                self.identify_rnd_coefs();
+            }
+        }
+
+        OptimizationReport {
+            gates_before,
+            gates_after: self.num_gates(),
+            constant_folds,
+            dead_wires_removed,
+        }
+    }
+
+    fn num_gates(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|layer| layer.mul.len() + layer.add.len() + layer.const_.len() + layer.uni.len())
+            .sum()
+    }
+}
+
+fn known_output_wires<C: GKRConfig>(
+    layer: &CircuitLayer<C>,
+) -> Vec<Option<KnownWire<C::CircuitField>>> {
+    let mut known =
+        vec![Some(KnownWire::Constant(C::CircuitField::ZERO)); 1 << layer.output_var_num];
+    let mut written = vec![false; known.len()];
+    for gate in &layer.const_ {
+        let wire = &mut known[gate.o_id];
+        *wire = match (&gate.coef_type, *wire) {
+            (CoefType::Constant, Some(KnownWire::Constant(v))) => {
+                Some(KnownWire::Constant(v + gate.coef))
+            }
+            (CoefType::PublicInput(idx), _) if !written[gate.o_id] => {
+                Some(KnownWire::PublicInput(*idx))
+            }
+            _ => None,
+        };
+        written[gate.o_id] = true;
+    }
+    let written_wires = layer
+        .mul
+        .iter()
+        .map(|gate| gate.o_id)
+        .chain(layer.add.iter().map(|gate| gate.o_id))
+        .chain(layer.uni.iter().map(|gate| gate.o_id));
+    for o_id in written_wires {
+        known[o_id] = None;
+    }
+    known
+}
+
+/// Replace the gates of known value with const gates, and return the number of folded gates.
+fn fold_constants<C: GKRConfig>(
+    layer: &mut CircuitLayer<C>,
+    known: &[Option<KnownWire<C::CircuitField>>],
+) -> usize {
+    let mut folded = vec![];
+
+    let n_gates = layer.mul.len() + layer.add.len() + layer.uni.len();
+    layer.mul.retain(|gate| {
+        if gate.coef_type != CoefType::Constant {
+            return true;
+        }
+        let value = match (known[gate.i_ids[0]], known[gate.i_ids[1]]) {
+            (Some(KnownWire::Constant(v0)), Some(KnownWire::Constant(v1))) => gate.coef * v0 * v1,
+            (Some(KnownWire::Constant(v)), _) | (_, Some(KnownWire::Constant(v)))
+                if v.is_zero() =>
+            {
+                C::CircuitField::ZERO
+            }
+            _ => return true,
+        };
+        folded.push(constant_gate(gate.o_id, value, gate.gate_type));
+        false
+    });
+    layer.add.retain(|gate| {
+        if gate.coef_type != CoefType::Constant {
+            return true;
+        }
+        match known[gate.i_ids[0]] {
+            Some(KnownWire::Constant(v)) => {
+                folded.push(constant_gate(gate.o_id, gate.coef * v, gate.gate_type))
+            }
+            // the coefficient of a public input gate is one
+            Some(KnownWire::PublicInput(idx)) if gate.coef == C::CircuitField::ONE => {
+                folded.push(Some(GateConst {
+                    i_ids: [],
+                    o_id: gate.o_id,
+                    coef_type: CoefType::PublicInput(idx),
+                    coef: C::CircuitField::ZERO,
+                    gate_type: gate.gate_type,
+                }))
+            }
+            _ => return true,
+        }
+        false
+    });
+    layer.uni.retain(|gate| {
+        if gate.coef_type != CoefType::Constant {
+            return true;
+        }
+        let v = match known[gate.i_ids[0]] {
+            Some(KnownWire::Constant(v)) => v,
+            _ => return true,
+        };
+        let value = match gate.gate_type {
+            // pow5
+            12345 => gate.coef * v.square().square() * v,
+            // pow1
+            12346 => gate.coef * v,
+            _ => return true,
+        };
+        folded.push(constant_gate(gate.o_id, value, 0));
+        false
+    });
+    let n_folded = n_gates - layer.mul.len() - layer.add.len() - layer.uni.len();
+
+    layer.const_.extend(folded.into_iter().flatten());
+    n_folded
+}
+
+/// A const gate writing the value, none if the value is zero.
+fn constant_gate<C: GKRConfig>(
+    o_id: usize,
+    value: C::CircuitField,
+    gate_type: usize,
+) -> Option<GateConst<C>> {
+    (!value.is_zero()).then_some(Gate {
+        i_ids: [],
+        o_id,
+        coef_type: CoefType::Constant,
+        coef: value,
+        gate_type,
+    })
+}
+
+/// Remove the gates whose output wire is not live, and return the number of removed gates.
+fn remove_dead_gates<C: GKRConfig>(layer: &mut CircuitLayer<C>, live: &[bool]) -> usize {
+    let n_gates = layer.mul.len() + layer.add.len() + layer.const_.len() + layer.uni.len();
+    let keep = |coef_type: &CoefType, o_id: usize| *coef_type == CoefType::Random || live[o_id];
+    layer.mul.retain(|gate| keep(&gate.coef_type, gate.o_id));
+    layer.add.retain(|gate| keep(&gate.coef_type, gate.o_id));
+    layer.const_.retain(|gate| keep(&gate.coef_type, gate.o_id));
+    layer.uni.retain(|gate| keep(&gate.coef_type, gate.o_id));
+    n_gates - layer.mul.len() - layer.add.len() - layer.const_.len() - layer.uni.len()
+}
+
+/// Whether each input wire of the layer is read by one of its gates.
+fn read_input_wires<C: GKRConfig>(layer: &CircuitLayer<C>) -> Vec<bool> {
+    let mut read = vec![false; 1 << layer.input_var_num];
+    let i_ids = layer
+        .mul
+        .iter()
+        .flat_map(|gate| gate.i_ids)
+        .chain(layer.add.iter().flat_map(|gate| gate.i_ids))
+        .chain(layer.uni.iter().flat_map(|gate| gate.i_ids));
+    for i_id in i_ids {
+        read[i_id] = true;
+    }
+    read
+}
@@ -0,0 +1,222 @@
+//! Loading the circuits of the binary R1CS format of circom and snarkjs.
+//!
+//! Layout: the magic `r1cs`, the version 1 and the number of sections as little-endian u32,
+//! then the sections, each a u32 type and a u64 size. The header section, of type 1, holds
+//! the size in bytes of the field elements, the prime, the number of wires, of public outputs,
+//! of public inputs and of private inputs, the number of labels as a u64, and the number of
+//! constraints. The constraints section, of type 2, holds for each constraint the linear
+//! combinations A, B and C, each a number of terms followed by the wire and the coefficient of
+//! each term. The other sections are ignored.
+//!
+//! Wire 0 is the constant 1, followed by the public outputs, the public inputs, the private
+//! inputs and the internal wires.
+
+use std::{collections::HashMap, fs, io::Read};
+
+use arith::{Field, FieldForECC};
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitError, GateBuilder, WireRef};
+
+pub const R1CS_MAGIC: [u8; 4] = *b"r1cs";
+
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+
+/// The terms of a linear combination of the wires, as (wire, coefficient).
+pub type LinearCombination<F> = Vec<(usize, F)>;
+
+/// An R1CS, i.e., constraints `A.w * B.w = C.w` over the wires `w`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct R1cs<F> {
+    pub num_wires: usize,
+    pub num_public_outputs: usize,
+    pub num_public_inputs: usize,
+    pub num_private_inputs: usize,
+    pub constraints: Vec<[LinearCombination<F>; 3]>,
+}
+
+fn invalid(message: impl Into<String>) -> CircuitError {
+    CircuitError::InvalidR1cs(message.into())
+}
+
+fn read_u32(r: &mut impl Read) -> Result<u32, CircuitError> {
+    let mut bytes = [0u8; 4];
+    r.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(r: &mut impl Read) -> Result<u64, CircuitError> {
+    let mut bytes = [0u8; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// A little-endian integer of `n8` bytes, at most 32.
+fn read_u256(r: &mut impl Read, n8: usize) -> Result<ethnum::U256, CircuitError> {
+    let mut bytes = [0u8; 32];
+    r.read_exact(&mut bytes[..n8])?;
+    Ok(ethnum::U256::from_le_bytes(bytes))
+}
+
+impl<F: FieldForECC> R1cs<F> {
+    /// Parse the R1CS, failing if its prime is not the modulus of `F`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircuitError> {
+        let mut r = bytes;
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if magic != R1CS_MAGIC {
+            return Err(invalid("not an r1cs file"));
+        }
+        let version = read_u32(&mut r)?;
+        if version != 1 {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+
+        let num_sections = read_u32(&mut r)?;
+        let mut sections = HashMap::new();
+        for _ in 0..num_sections {
+            let section_type = read_u32(&mut r)?;
+            let size = read_u64(&mut r)?;
+            if size > r.len() as u64 {
+                return Err(invalid(format!("truncated section {}", section_type)));
+            }
+            let (section, rest) = r.split_at(size as usize);
+            sections.insert(section_type, section);
+            r = rest;
+        }
+
+        let mut header = *sections
+            .get(&R1CS_HEADER_SECTION)
+            .ok_or_else(|| invalid("no header section"))?;
+        let n8 = read_u32(&mut header)? as usize;
+        if n8 == 0 || n8 > 32 {
+            return Err(invalid(format!("unsupported field size of {} bytes", n8)));
+        }
+        let prime = read_u256(&mut header, n8)?;
+        if prime != F::modulus() {
+            return Err(invalid(format!(
+                "the prime {} is not the modulus of {}",
+                prime,
+                F::NAME
+            )));
+        }
+        let num_wires = read_u32(&mut header)? as usize;
+        let num_public_outputs = read_u32(&mut header)? as usize;
+        let num_public_inputs = read_u32(&mut header)? as usize;
+        let num_private_inputs = read_u32(&mut header)? as usize;
+        let _num_labels = read_u64(&mut header)?;
+        let num_constraints = read_u32(&mut header)? as usize;
+        if 1 + num_public_outputs + num_public_inputs + num_private_inputs > num_wires {
+            return Err(invalid("more inputs and outputs than wires"));
+        }
+
+        let mut r = *sections
+            .get(&R1CS_CONSTRAINTS_SECTION)
+            .ok_or_else(|| invalid("no constraints section"))?;
+        let mut read_linear_combination = || -> Result<LinearCombination<F>, CircuitError> {
+            let num_terms = read_u32(&mut r)?;
+            (0..num_terms)
+                .map(|_| {
+                    let wire = read_u32(&mut r)? as usize;
+                    if wire >= num_wires {
+                        return Err(invalid(format!("wire {} out of range", wire)));
+                    }
+                    let coef = read_u256(&mut r, n8)?;
+                    if coef >= prime {
+                        return Err(invalid(format!("coefficient {} not reduced", coef)));
+                    }
+                    Ok((wire, F::from_u256(coef)))
+                })
+                .collect()
+        };
+        let constraints = (0..num_constraints)
+            .map(|_| {
+                Ok([
+                    read_linear_combination()?,
+                    read_linear_combination()?,
+                    read_linear_combination()?,
+                ])
+            })
+            .collect::<Result<Vec<_>, CircuitError>>()?;
+
+        Ok(Self {
+            num_wires,
+            num_public_outputs,
+            num_public_inputs,
+            num_private_inputs,
+            constraints,
+        })
+    }
+
+    #[inline]
+    pub fn num_public_wires(&self) -> usize {
+        self.num_public_outputs + self.num_public_inputs
+    }
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// The circuit of the R1CS file, see `Circuit::from_r1cs`.
+    pub fn load_from_r1cs(path: &str) -> Result<Self, CircuitError> {
+        let r1cs = R1cs::<C::CircuitField>::from_bytes(&fs::read(path)?)?;
+        Ok(Self::from_r1cs(&r1cs))
+    }
+
+    /// A circuit whose outputs are all zero iff the input layer satisfies the R1CS.
+    ///
+    /// Input `i` is wire `i`, input 0 being unused as wire 0 is the constant 1: the witness
+    /// of circom, padded, is the private input. The public outputs and inputs of the R1CS
+    /// are the public inputs of the circuit, in order, and must match their wires.
+    ///
+    /// The first layer computes `A.w`, `B.w` and `C.w` for each constraint, and the public
+    /// inputs minus the public wires. The second layer outputs `A.w * B.w - C.w` for each
+    /// constraint, then carries the differences of the public wires.
+    pub fn from_r1cs(r1cs: &R1cs<C::CircuitField>) -> Self {
+        let mut builder = GateBuilder::<C>::new(r1cs.num_wires);
+        builder.next_layer();
+        let linear_combination =
+            |builder: &mut GateBuilder<C>, terms: &[(usize, C::CircuitField)]| {
+                let output = builder.new_wire();
+                for &(wire, coef) in terms {
+                    if wire == 0 {
+                        builder.constant_into(output, coef);
+                    } else {
+                        builder.add_into(output, builder.input(wire), coef);
+                    }
+                }
+                output
+            };
+        let products = r1cs
+            .constraints
+            .iter()
+            .map(|[a, b, c]| {
+                [
+                    linear_combination(&mut builder, a),
+                    linear_combination(&mut builder, b),
+                    linear_combination(&mut builder, c),
+                ]
+            })
+            .collect::<Vec<[WireRef; 3]>>();
+        let public_differences = (0..r1cs.num_public_wires())
+            .map(|i| {
+                let difference = builder.public_input(i);
+                builder.add_into(difference, builder.input(i + 1), -C::CircuitField::ONE);
+                difference
+            })
+            .collect::<Vec<_>>();
+
+        builder.next_layer();
+        for [a, b, c] in products {
+            let output = builder.mul(a, b, C::CircuitField::ONE);
+            builder.add_into(output, c, -C::CircuitField::ONE);
+        }
+        for difference in public_differences {
+            builder.relay(difference);
+        }
+
+        let mut circuit = builder.build();
+        circuit.expected_num_output_zeros = r1cs.constraints.len() + r1cs.num_public_wires();
+        circuit.identify_rnd_coefs();
+        circuit
+    }
+}
@@ -1,10 +1,10 @@
 use arith::{Field, FieldForECC, FieldSerde, FieldSerdeError};
-use config::GKRConfig;
+use config::{sentinel_hex, GKRConfig, SENTINEL_SIZE};
 use std::{io::Read, vec};
 use thiserror::Error;
 
-use super::{Allocation, CoefType, Gate, RecursiveCircuit, Segment, Witness};
-use crate::{GateAdd, GateConst, GateMul, SegmentId};
+use super::{Allocation, CoefType, Gate, RecursiveCircuit, Segment, Witness, WitnessError};
+use crate::{GateAdd, GateConst, GateMul, GateRef, SegmentId};
 
 #[derive(Debug, Error)]
 pub enum CircuitError {
@@ -13,75 +13,156 @@ pub enum CircuitError {
 
     #[error("other error: {0:?}")]
     OtherError(#[from] std::io::Error),
+
+    #[error("invalid witness: {0}")]
+    WitnessFormat(#[from] WitnessError),
+
+    #[error("the circuit file has {actual} bytes, fewer than the {required} bytes of its header")]
+    TruncatedCircuitHeader { actual: usize, required: usize },
+
+    /// The circuit is of another field than the config it is loaded for.
+    #[error(
+        "circuit of the field of sentinel {}, expected {} for the config",
+        sentinel_hex(.found),
+        sentinel_hex(.expected)
+    )]
+    WrongCircuitField {
+        expected: [u8; SENTINEL_SIZE],
+        found: [u8; SENTINEL_SIZE],
+    },
+
+    /// The circuit bytes do not follow the circuit format, e.g., of a segment length that is
+    /// not a power of 2.
+    #[error("malformed circuit: {0}")]
+    MalformedCircuit(String),
+
+    #[error("segment {id} is out of the {num_segments} segments")]
+    SegmentOutOfRange { id: usize, num_segments: usize },
+
+    #[error("segment {id} is among its own child segments")]
+    SegmentCycle { id: usize },
+
+    #[error("invalid r1cs: {0}")]
+    InvalidR1cs(String),
+
+    #[error("the circuit has no layer")]
+    NoLayer,
+
+    #[error("the circuit has no output")]
+    NoOutput,
+
+    #[error("layer {layer} has no gate")]
+    EmptyLayer { layer: usize },
+
+    #[error("layer {layer} has {found} input variables, but the layer below has {expected} output variables")]
+    VarNumMismatch {
+        layer: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("gate {gate:?} uses wire {wire}, out of the {size} wires")]
+    WireOutOfRange {
+        gate: GateRef,
+        wire: usize,
+        size: usize,
+    },
+
+    #[error("the witness has {found} input values, but the input layer has {expected} wires")]
+    WitnessSizeMismatch { expected: usize, found: usize },
+
+    #[error("gate {gate:?} reads public input {index}, out of the {size} public inputs")]
+    PublicInputOutOfRange {
+        gate: GateRef,
+        index: usize,
+        size: usize,
+    },
+}
+
+pub trait FromEccSerde: Sized {
+    fn deserialize_from<R: Read>(reader: R) -> Result<Self, CircuitError>;
 }
-pub trait FromEccSerde {
-    fn deserialize_from<R: Read>(reader: R) -> Self;
+
+fn malformed(message: impl Into<String>) -> CircuitError {
+    CircuitError::MalformedCircuit(message.into())
 }
 
 impl<T: FromEccSerde> FromEccSerde for Vec<T> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let vec_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
+    /// The vector grows with the elements read rather than being allocated for its length
+    /// prefix: each element takes at least a byte of the input, so a length past the end of the
+    /// input fails on the first missing element, with no more elements held than bytes read.
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        let vec_len = <usize as FieldSerde>::deserialize_from(&mut reader)?;
         let mut ret = vec![];
         for _ in 0..vec_len {
-            ret.push(T::deserialize_from(&mut reader));
+            ret.push(T::deserialize_from(&mut reader)?);
         }
-        ret
+        Ok(ret)
     }
 }
 
 impl<T1: FromEccSerde, T2: FromEccSerde> FromEccSerde for (T1, T2) {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        (
-            T1::deserialize_from(&mut reader),
-            T2::deserialize_from(&mut reader),
-        )
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        Ok((
+            T1::deserialize_from(&mut reader)?,
+            T2::deserialize_from(&mut reader)?,
+        ))
     }
 }
 
 impl FromEccSerde for usize {
-    fn deserialize_from<R: Read>(reader: R) -> Self {
-        <usize as FieldSerde>::deserialize_from(reader).unwrap()
+    fn deserialize_from<R: Read>(reader: R) -> Result<Self, CircuitError> {
+        Ok(<usize as FieldSerde>::deserialize_from(reader)?)
+    }
+}
+
+/// The coefficient of a gate of `input_num` inputs, from its type byte on.
+fn deserialize_coef<C: GKRConfig, R: Read>(
+    mut reader: R,
+    input_num: usize,
+) -> Result<(CoefType, C::CircuitField), CircuitError> {
+    let coef_type_u8 = u8::deserialize_from(&mut reader)?;
+    match coef_type_u8 {
+        1 => Ok((
+            CoefType::Constant,
+            C::CircuitField::try_deserialize_from_ecc_format(&mut reader)?,
+        )),
+        2 => Ok((CoefType::Random, C::CircuitField::ZERO)),
+        3 => {
+            if input_num > 0 {
+                return Err(malformed(
+                    "Public Input can only be used with constant gates",
+                ));
+            }
+            Ok((
+                CoefType::PublicInput(<usize as FieldSerde>::deserialize_from(&mut reader)?),
+                C::CircuitField::ZERO,
+            ))
+        }
+        _ => Err(malformed(format!(
+            "unknown coefficient type {}",
+            coef_type_u8
+        ))),
     }
 }
 
 impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for Gate<C, INPUT_NUM> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
         let mut i_ids = [0usize; INPUT_NUM];
         for id in &mut i_ids {
-            *id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
+            *id = <usize as FieldSerde>::deserialize_from(&mut reader)?;
         }
 
-        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-
-        let coef_type_u8 = u8::deserialize_from(&mut reader).unwrap();
-        let (coef_type, coef) = match coef_type_u8 {
-            1 => (
-                CoefType::Constant,
-                C::CircuitField::try_deserialize_from_ecc_format(&mut reader).unwrap(),
-            ),
-            2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(
-                        <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-                    ),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
-        };
+        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let (coef_type, coef) = deserialize_coef::<C, _>(&mut reader, INPUT_NUM)?;
 
-        Self {
+        Ok(Self {
             i_ids,
             o_id,
             coef_type,
             coef,
             gate_type: 0,
-        }
+        })
     }
 }
 
@@ -90,37 +171,21 @@ pub struct CustomGateWrapper<C: GKRConfig, const INPUT_NUM: usize> {
 }
 
 impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for CustomGateWrapper<C, INPUT_NUM> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let gate_type = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let i_ids: [usize; INPUT_NUM] = <Vec<usize> as FromEccSerde>::deserialize_from(&mut reader)
-            .try_into()
-            .unwrap();
-
-        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-
-        let coef_type_u8 = u8::deserialize_from(&mut reader).unwrap();
-        let (coef_type, coef) = match coef_type_u8 {
-            1 => (
-                CoefType::Constant,
-                C::CircuitField::try_deserialize_from_ecc_format(&mut reader).unwrap(),
-            ),
-            2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(
-                        <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-                    ),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
-        };
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        let gate_type = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let i_ids = <Vec<usize> as FromEccSerde>::deserialize_from(&mut reader)?;
+        let i_ids: [usize; INPUT_NUM] = i_ids.try_into().map_err(|i_ids: Vec<usize>| {
+            malformed(format!(
+                "custom gate of {} inputs, expected {}",
+                i_ids.len(),
+                INPUT_NUM
+            ))
+        })?;
 
-        Self {
+        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let (coef_type, coef) = deserialize_coef::<C, _>(&mut reader, INPUT_NUM)?;
+
+        Ok(Self {
             custom_gate: Gate::<C, INPUT_NUM> {
                 i_ids,
                 o_id,
@@ -128,94 +193,127 @@ impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for CustomGateWrapper<C,
                 coef,
                 gate_type,
             },
-        }
+        })
     }
 }
 
 impl FromEccSerde for Allocation {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        Self {
-            i_offset: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            o_offset: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-        }
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        Ok(Self {
+            i_offset: <usize as FieldSerde>::deserialize_from(&mut reader)?,
+            o_offset: <usize as FieldSerde>::deserialize_from(&mut reader)?,
+        })
     }
 }
 
+/// The number of variables of a segment of `len` wires, which must be a power of 2.
+pub(crate) fn segment_var_num(len: usize, side: &str) -> Result<usize, CircuitError> {
+    if !len.is_power_of_two() {
+        return Err(malformed(format!(
+            "segment {} length {} is not a power of 2",
+            side, len
+        )));
+    }
+    Ok(len.trailing_zeros() as usize)
+}
+
 impl<C: GKRConfig> FromEccSerde for Segment<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let i_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let o_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        assert!(i_len.is_power_of_two());
-        assert!(o_len.is_power_of_two());
-
-        let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader);
-        let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader);
-        let gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader);
-        let gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader);
-
-        let mut gate_uni = vec![];
-        let len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        for _ in 0..len {
-            let uni = CustomGateWrapper::<C, 1>::deserialize_from(&mut reader).custom_gate;
-            gate_uni.push(uni);
-        }
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        let i_len = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let o_len = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let i_var_num = segment_var_num(i_len, "input")?;
+        let o_var_num = segment_var_num(o_len, "output")?;
 
-        Segment {
-            i_var_num: i_len.trailing_zeros() as usize,
-            o_var_num: o_len.trailing_zeros() as usize,
+        let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader)?;
+        let mut segment = Segment {
+            i_var_num,
+            o_var_num,
             child_segs,
-            gate_muls,
-            gate_adds,
-            gate_consts,
-            gate_uni,
-        }
+            ..Default::default()
+        };
+        segment.deserialize_gates_from(reader)?;
+        Ok(segment)
     }
 }
 
-const VERSION_NUM: usize = 3914834606642317635; // b'CIRCUIT6'
+impl<C: GKRConfig> Segment<C> {
+    /// Read the gates of the segment, which follow its child segments in the circuit file.
+    pub(crate) fn deserialize_gates_from<R: Read>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<(), CircuitError> {
+        self.gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
+        self.gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
+        self.gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
+        self.gate_uni = Vec::<CustomGateWrapper<C, 1>>::deserialize_from(&mut reader)?
+            .into_iter()
+            .map(|wrapper| wrapper.custom_gate)
+            .collect();
+        Ok(())
+    }
+}
+
+pub(crate) const VERSION_NUM: usize = 3914834606642317635; // b'CIRCUIT6'
+
+/// Read the version and the field modulus of the header of a circuit file.
+pub(crate) fn deserialize_header<C: GKRConfig, R: Read>(mut reader: R) -> Result<(), CircuitError> {
+    let version_num = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+    if version_num != VERSION_NUM {
+        return Err(malformed(format!(
+            "circuit version {}, expected {}",
+            version_num, VERSION_NUM
+        )));
+    }
+    let expected_mod = <C::CircuitField as FieldForECC>::modulus();
+    let mut field_mod = [0u8; 32];
+    reader.read_exact(&mut field_mod)?;
+    let read_mod = ethnum::U256::from_le_bytes(field_mod);
+    if expected_mod != read_mod {
+        return Err(malformed(format!(
+            "circuit field modulus {}, expected {}",
+            read_mod, expected_mod
+        )));
+    }
+    Ok(())
+}
 
 impl<C: GKRConfig> FromEccSerde for RecursiveCircuit<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let version_num = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        assert_eq!(version_num, VERSION_NUM);
-        let expected_mod = <C::CircuitField as FieldForECC>::modulus();
-        let mut field_mod = [0u8; 32];
-        reader.read_exact(&mut field_mod).unwrap();
-        let read_mod = ethnum::U256::from_le_bytes(field_mod);
-        assert_eq!(expected_mod, read_mod);
-
-        RecursiveCircuit {
-            num_public_inputs: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            num_outputs: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            expected_num_output_zeros: <usize as FieldSerde>::deserialize_from(&mut reader)
-                .unwrap(),
-
-            segments: Vec::<Segment<C>>::deserialize_from(&mut reader),
-            layers: <Vec<usize> as FromEccSerde>::deserialize_from(&mut reader),
-        }
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        deserialize_header::<C, _>(&mut reader)?;
+
+        Ok(RecursiveCircuit {
+            num_public_inputs: <usize as FieldSerde>::deserialize_from(&mut reader)?,
+            num_outputs: <usize as FieldSerde>::deserialize_from(&mut reader)?,
+            expected_num_output_zeros: <usize as FieldSerde>::deserialize_from(&mut reader)?,
+
+            segments: Vec::<Segment<C>>::deserialize_from(&mut reader)?,
+            layers: <Vec<usize> as FromEccSerde>::deserialize_from(&mut reader)?,
+        })
     }
 }
 
 impl<C: GKRConfig> FromEccSerde for Witness<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let num_witnesses = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let num_private_inputs_per_witness =
-            <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let num_public_inputs_per_witness =
-            <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let _modulus = <[u64; 4]>::deserialize_from(&mut reader).unwrap();
+    fn deserialize_from<R: Read>(mut reader: R) -> Result<Self, CircuitError> {
+        let num_witnesses = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let num_private_inputs_per_witness = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let num_public_inputs_per_witness = <usize as FieldSerde>::deserialize_from(&mut reader)?;
+        let _modulus = <[u64; 4]>::deserialize_from(&mut reader)?;
 
+        let num_values = num_private_inputs_per_witness
+            .checked_add(num_public_inputs_per_witness)
+            .and_then(|per_witness| per_witness.checked_mul(num_witnesses))
+            .ok_or_else(|| malformed("the witness sizes overflow"))?;
+        // grown with the values read, as a vector
         let mut values = vec![];
-        for _ in 0..num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness)
-        {
-            values.push(C::CircuitField::deserialize_from(&mut reader).unwrap());
+        for _ in 0..num_values {
+            values.push(C::CircuitField::deserialize_from(&mut reader)?);
         }
 
-        Self {
+        Ok(Self {
             num_witnesses,
             num_private_inputs_per_witness,
             num_public_inputs_per_witness,
             values,
-        }
+        })
     }
 }
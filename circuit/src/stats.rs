@@ -0,0 +1,164 @@
+use std::{cmp::max, fmt, mem::size_of};
+
+use config::GKRConfig;
+use serde::Serialize;
+
+use crate::{Circuit, CoefType};
+
+/// The size of a circuit, to estimate the prover resources before proving it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CircuitStats {
+    pub num_layers: usize,
+    pub num_mul_gates: usize,
+    pub num_add_gates: usize,
+    pub num_const_gates: usize,
+    pub num_uni_gates: usize,
+    /// The largest number of gates adding up into a single wire.
+    pub max_fan_in: usize,
+    /// The wires of all the layers, the input layer included.
+    pub num_wires: usize,
+    /// The public inputs read by the const gates, i.e., one more than the largest index.
+    pub public_input_size: usize,
+    /// The private inputs of each witness, the wires of the input layer.
+    pub input_size: usize,
+    /// The bytes of the scratch pad of a prover on a single process, as allocated by
+    /// `ProverScratchPad::new` for the largest layer.
+    pub prover_scratchpad_bytes: usize,
+    /// From the input layer to the output layer.
+    pub layers: Vec<LayerStats>,
+}
+
+/// The gates of a layer, by type, the uni gates being the custom ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LayerStats {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    pub num_mul_gates: usize,
+    pub num_add_gates: usize,
+    pub num_const_gates: usize,
+    pub num_uni_gates: usize,
+}
+
+impl fmt::Display for LayerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} vars: {} mul, {} add, {} const, {} uni",
+            self.input_var_num,
+            self.output_var_num,
+            self.num_mul_gates,
+            self.num_add_gates,
+            self.num_const_gates,
+            self.num_uni_gates
+        )
+    }
+}
+
+impl fmt::Display for CircuitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "layers: {}", self.num_layers)?;
+        writeln!(
+            f,
+            "gates: {} mul, {} add, {} const, {} uni",
+            self.num_mul_gates, self.num_add_gates, self.num_const_gates, self.num_uni_gates
+        )?;
+        writeln!(f, "max fan-in: {}", self.max_fan_in)?;
+        writeln!(f, "wires: {}", self.num_wires)?;
+        writeln!(f, "inputs: {}", self.input_size)?;
+        writeln!(f, "public inputs: {}", self.public_input_size)?;
+        write!(
+            f,
+            "prover scratch pad: {} bytes",
+            self.prover_scratchpad_bytes
+        )
+    }
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    pub fn stats(&self) -> CircuitStats {
+        let mut stats = CircuitStats {
+            num_layers: self.layers.len(),
+            num_wires: self
+                .layers
+                .first()
+                .map_or(0, |layer| 1 << layer.input_var_num),
+            input_size: self
+                .layers
+                .first()
+                .map_or(0, |layer| 1 << layer.input_var_num),
+            prover_scratchpad_bytes: Self::prover_scratchpad_bytes(
+                self.layers.iter().map(|layer| layer.input_var_num).max(),
+                self.layers.iter().map(|layer| layer.output_var_num).max(),
+            ),
+            ..Default::default()
+        };
+        // the number of gates writing each output wire of the current layer
+        let mut fan_in = vec![];
+        for layer in &self.layers {
+            stats.num_mul_gates += layer.mul.len();
+            stats.num_add_gates += layer.add.len();
+            stats.num_const_gates += layer.const_.len();
+            stats.num_uni_gates += layer.uni.len();
+            stats.num_wires += 1 << layer.output_var_num;
+            stats.layers.push(LayerStats {
+                input_var_num: layer.input_var_num,
+                output_var_num: layer.output_var_num,
+                num_mul_gates: layer.mul.len(),
+                num_add_gates: layer.add.len(),
+                num_const_gates: layer.const_.len(),
+                num_uni_gates: layer.uni.len(),
+            });
+
+            fan_in.clear();
+            fan_in.resize(1 << layer.output_var_num, 0usize);
+            let o_ids = layer
+                .mul
+                .iter()
+                .map(|g| g.o_id)
+                .chain(layer.add.iter().map(|g| g.o_id))
+                .chain(layer.const_.iter().map(|g| g.o_id))
+                .chain(layer.uni.iter().map(|g| g.o_id));
+            for o_id in o_ids {
+                fan_in[o_id] += 1;
+            }
+            stats.max_fan_in = stats
+                .max_fan_in
+                .max(fan_in.iter().copied().max().unwrap_or(0));
+
+            for gate in &layer.const_ {
+                if let CoefType::PublicInput(idx) = gate.coef_type {
+                    stats.public_input_size = stats.public_input_size.max(idx + 1);
+                }
+            }
+        }
+        stats
+    }
+
+    /// The bytes of the vectors of `ProverScratchPad::new`, the ones of the mpi world left out,
+    /// none for a circuit without layers.
+    fn prover_scratchpad_bytes(
+        max_num_input_var: Option<usize>,
+        max_num_output_var: Option<usize>,
+    ) -> usize {
+        let (Some(max_num_input_var), Some(max_num_output_var)) =
+            (max_num_input_var, max_num_output_var)
+        else {
+            return 0;
+        };
+        let max_input_num = 1usize << max_num_input_var;
+        let max_output_num = 1usize << max_num_output_var;
+        let pack_size = C::get_field_pack_size();
+        // v_evals and hg_evals
+        let field_bytes = 2 * max_input_num * size_of::<C::Field>();
+        // hg_evals_5, hg_evals_1 and eq_evals_at_rx, eq_evals_at_rz0, the simd vectors, and the
+        // eq_evals halves
+        let challenge_bytes = (3 * max_input_num
+            + max_output_num
+            + 3 * pack_size
+            + 2 * max(max_output_num, pack_size))
+            * size_of::<C::ChallengeField>();
+        // gate_exists_5 and gate_exists_1
+        let gate_bytes = 2 * max_input_num * size_of::<bool>();
+        field_bytes + challenge_bytes + gate_bytes
+    }
+}
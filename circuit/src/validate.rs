@@ -0,0 +1,102 @@
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitError, CoefType, GateKind, GateRef};
+
+impl<C: GKRConfig> Circuit<C> {
+    /// Check the structure of the circuit, to report a malformed circuit before proving it
+    /// rather than by a panic in the middle of the proof. The wires of each gate must be in
+    /// range of its layer, and each layer must have a gate and as many input variables as the
+    /// layer below has output variables.
+    ///
+    /// Once a witness is loaded, i.e., the input layer has values, the witness must fill the
+    /// input layer, and the public inputs read by the const gates must be in the witness.
+    pub fn validate(&self) -> Result<(), CircuitError> {
+        if self.layers.is_empty() {
+            return Err(CircuitError::NoLayer);
+        }
+
+        let witness_loaded = !self.layers[0].input_vals.is_empty();
+        if witness_loaded && self.layers[0].input_vals.len() != 1 << self.log_input_size() {
+            return Err(CircuitError::WitnessSizeMismatch {
+                expected: 1 << self.log_input_size(),
+                found: self.layers[0].input_vals.len(),
+            });
+        }
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            if i > 0 && layer.input_var_num != self.layers[i - 1].output_var_num {
+                return Err(CircuitError::VarNumMismatch {
+                    layer: i,
+                    expected: self.layers[i - 1].output_var_num,
+                    found: layer.input_var_num,
+                });
+            }
+
+            let gates = layer
+                .mul
+                .iter()
+                .enumerate()
+                .map(|(j, g)| (GateKind::Mul, j, &g.i_ids[..], g.o_id, &g.coef_type))
+                .chain(
+                    layer
+                        .add
+                        .iter()
+                        .enumerate()
+                        .map(|(j, g)| (GateKind::Add, j, &g.i_ids[..], g.o_id, &g.coef_type)),
+                )
+                .chain(
+                    layer
+                        .const_
+                        .iter()
+                        .enumerate()
+                        .map(|(j, g)| (GateKind::Const, j, &g.i_ids[..], g.o_id, &g.coef_type)),
+                )
+                .chain(
+                    layer
+                        .uni
+                        .iter()
+                        .enumerate()
+                        .map(|(j, g)| (GateKind::Uni, j, &g.i_ids[..], g.o_id, &g.coef_type)),
+                );
+
+            let mut num_gates = 0;
+            for (kind, index, i_ids, o_id, coef_type) in gates {
+                num_gates += 1;
+                let gate = GateRef {
+                    layer: i,
+                    kind,
+                    index,
+                };
+                let input_size = 1 << layer.input_var_num;
+                if let Some(&wire) = i_ids.iter().find(|&&wire| wire >= input_size) {
+                    return Err(CircuitError::WireOutOfRange {
+                        gate,
+                        wire,
+                        size: input_size,
+                    });
+                }
+                let output_size = 1 << layer.output_var_num;
+                if o_id >= output_size {
+                    return Err(CircuitError::WireOutOfRange {
+                        gate,
+                        wire: o_id,
+                        size: output_size,
+                    });
+                }
+                if let CoefType::PublicInput(index) = *coef_type {
+                    if witness_loaded && index >= self.public_input.len() {
+                        return Err(CircuitError::PublicInputOutOfRange {
+                            gate,
+                            index,
+                            size: self.public_input.len(),
+                        });
+                    }
+                }
+            }
+            if num_gates == 0 {
+                return Err(CircuitError::EmptyLayer { layer: i });
+            }
+        }
+        Ok(())
+    }
+}
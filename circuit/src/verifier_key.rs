@@ -0,0 +1,334 @@
+//! What the verifier needs of a circuit, without its evaluation buffers and with its gates
+//! packed, see `Circuit::extract_verifier_key`.
+//!
+//! The gates of each kind are listed as in the circuit, each gate as the varint of the zigzag
+//! difference of its output wire, then of each of its input wires, with the same wire of the
+//! gate before it, then the varint of its gate type and of its coefficient type, followed by
+//! the coefficient if it is not one, or the public input index. Wires and coefficients being
+//! mostly small steps and ones, a gate of two inputs takes about 4 bytes instead of about 56.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use arith::{Field, FieldSerde, FieldSerdeError, FieldSerdeResult};
+use config::GKRConfig;
+
+use crate::{Circuit, CircuitLayer, CoefType, Gate};
+
+const COEF_ONE: u64 = 0;
+const COEF_CONSTANT: u64 = 1;
+const COEF_RANDOM: u64 = 2;
+const COEF_PUBLIC_INPUT: u64 = 3;
+
+#[derive(Debug, Clone)]
+pub struct VerifierKey<C: GKRConfig> {
+    /// The number of input variables of each layer, then of output variables of the last one.
+    var_nums: Vec<usize>,
+    /// One more than the largest public input index read by a gate.
+    num_public_inputs: usize,
+    /// The number of random coefficients of each layer, in the order of `identify_rnd_coefs`.
+    rnd_coef_nums: Vec<usize>,
+    /// The packed gates of each layer.
+    layers: Vec<Vec<u8>>,
+    _marker: PhantomData<C>,
+}
+
+impl<C: GKRConfig> Circuit<C> {
+    /// The verifier key of the circuit, see `Verifier::verify_with_key`. The random
+    /// coefficients are drawn again by the verifier, so their values are not kept.
+    pub fn extract_verifier_key(&self) -> VerifierKey<C> {
+        let mut var_nums = self
+            .layers
+            .iter()
+            .map(|layer| layer.input_var_num)
+            .collect::<Vec<_>>();
+        var_nums.push(self.layers.last().unwrap().output_var_num);
+
+        let mut num_public_inputs = 0;
+        let mut rnd_coef_nums = vec![];
+        let mut layers = vec![];
+        for layer in &self.layers {
+            let mut bytes = vec![];
+            let mut rnd_coef_num = 0;
+            let mut count = |coef_type: &CoefType| match coef_type {
+                CoefType::Random => rnd_coef_num += 1,
+                CoefType::PublicInput(idx) => num_public_inputs = num_public_inputs.max(idx + 1),
+                CoefType::Constant => {}
+            };
+            layer.mul.iter().for_each(|g| count(&g.coef_type));
+            layer.add.iter().for_each(|g| count(&g.coef_type));
+            layer.const_.iter().for_each(|g| count(&g.coef_type));
+            layer.uni.iter().for_each(|g| count(&g.coef_type));
+
+            pack_gates(&layer.mul, &mut bytes);
+            pack_gates(&layer.add, &mut bytes);
+            pack_gates(&layer.const_, &mut bytes);
+            pack_gates(&layer.uni, &mut bytes);
+            bytes.shrink_to_fit();
+            rnd_coef_nums.push(rnd_coef_num);
+            layers.push(bytes);
+        }
+
+        VerifierKey {
+            var_nums,
+            num_public_inputs,
+            rnd_coef_nums,
+            layers,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C: GKRConfig> VerifierKey<C> {
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    #[inline]
+    pub fn log_input_size(&self) -> usize {
+        self.var_nums[0]
+    }
+
+    /// The number of input variables of the layer.
+    #[inline]
+    pub fn input_var_num(&self, i: usize) -> usize {
+        self.var_nums[i]
+    }
+
+    /// Whether the layer has no mul gate, as the `structure_info` of `layer`, without unpacking
+    /// its gates.
+    pub fn max_degree_one(&self, i: usize) -> bool {
+        // the number of mul gates comes first
+        read_varint(&mut self.layers[i].as_slice()) == Some(0)
+    }
+
+    /// The number of output variables of the last layer.
+    #[inline]
+    pub fn log_output_size(&self) -> usize {
+        self.var_nums[self.layers.len()]
+    }
+
+    /// The number of public inputs the gates read, for a single process.
+    #[inline]
+    pub fn num_public_inputs(&self) -> usize {
+        self.num_public_inputs
+    }
+
+    #[inline]
+    pub fn rnd_coef_nums(&self) -> &[usize] {
+        &self.rnd_coef_nums
+    }
+
+    /// The largest number of variables over the inputs and outputs of the layers.
+    pub fn max_num_var(&self) -> usize {
+        *self.var_nums.iter().max().unwrap()
+    }
+
+    /// The gates of the layer, with its structure info, and its random coefficients zero.
+    /// The layer has no input nor output values.
+    pub fn layer(&self, i: usize) -> CircuitLayer<C> {
+        self.unpack_layer(i)
+            .expect("the layers are checked when the key is deserialized")
+    }
+
+    fn unpack_layer(&self, i: usize) -> Option<CircuitLayer<C>> {
+        let mut reader = self.layers[i].as_slice();
+        let mut layer = CircuitLayer::<C> {
+            input_var_num: self.var_nums[i],
+            output_var_num: self.var_nums[i + 1],
+            mul: unpack_gates(&mut reader)?,
+            add: unpack_gates(&mut reader)?,
+            const_: unpack_gates(&mut reader)?,
+            uni: unpack_gates(&mut reader)?,
+            ..Default::default()
+        };
+        if !reader.is_empty() {
+            return None;
+        }
+        layer.identify_structure_info();
+        Some(layer)
+    }
+
+    /// Whether the layer reads and writes its wires only, and has the random coefficients and
+    /// public inputs of the key.
+    fn check_layer(&self, i: usize) -> bool {
+        let layer = match self.unpack_layer(i) {
+            Some(layer) => layer,
+            None => return false,
+        };
+        let input_size = 1usize << layer.input_var_num;
+        let output_size = 1usize << layer.output_var_num;
+        let mul = layer
+            .mul
+            .iter()
+            .map(|g| (&g.i_ids[..], g.o_id, &g.coef_type));
+        let add = layer
+            .add
+            .iter()
+            .map(|g| (&g.i_ids[..], g.o_id, &g.coef_type));
+        let cst = layer
+            .const_
+            .iter()
+            .map(|g| (&g.i_ids[..], g.o_id, &g.coef_type));
+        let uni = layer
+            .uni
+            .iter()
+            .map(|g| (&g.i_ids[..], g.o_id, &g.coef_type));
+        let mut rnd_coef_num = 0;
+        let fits = mul
+            .chain(add)
+            .chain(cst)
+            .chain(uni)
+            .all(|(i_ids, o_id, coef_type)| {
+                match coef_type {
+                    CoefType::Random => rnd_coef_num += 1,
+                    CoefType::PublicInput(idx) if *idx >= self.num_public_inputs => return false,
+                    _ => {}
+                }
+                o_id < output_size && i_ids.iter().all(|i| *i < input_size)
+            });
+        fits && rnd_coef_num == self.rnd_coef_nums[i]
+    }
+}
+
+impl<C: GKRConfig> FieldSerde for VerifierKey<C> {
+    const SERIALIZED_SIZE: usize = unimplemented!();
+
+    /// The variable numbers, the number of public inputs, the numbers of random coefficients,
+    /// and the packed gates of each layer.
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.var_nums.serialize_into(&mut writer)?;
+        self.num_public_inputs.serialize_into(&mut writer)?;
+        self.rnd_coef_nums.serialize_into(&mut writer)?;
+        self.layers.serialize_into(&mut writer)
+    }
+
+    /// Fails on a key whose gates do not fit its layers, each layer being unpacked in turn.
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut key = Self {
+            var_nums: Vec::deserialize_from(&mut reader)?,
+            num_public_inputs: usize::deserialize_from(&mut reader)?,
+            rnd_coef_nums: Vec::deserialize_from(&mut reader)?,
+            layers: Vec::deserialize_from(&mut reader)?,
+            _marker: PhantomData,
+        };
+        key.layers.iter_mut().for_each(Vec::shrink_to_fit);
+        if key.layers.is_empty()
+            || key.var_nums.len() != key.layers.len() + 1
+            || key.rnd_coef_nums.len() != key.layers.len()
+            || key.var_nums.iter().any(|n| *n >= usize::BITS as usize)
+            || !(0..key.layers.len()).all(|i| key.check_layer(i))
+        {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(key)
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("not implemented for VerifierKey")
+    }
+}
+
+fn pack_gates<C: GKRConfig, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+    bytes: &mut Vec<u8>,
+) {
+    write_varint(gates.len() as u64, bytes);
+    let mut o_id = 0;
+    let mut i_ids = [0; INPUT_NUM];
+    for gate in gates {
+        write_varint(zigzag(gate.o_id, o_id), bytes);
+        o_id = gate.o_id;
+        for (i_id, prev) in gate.i_ids.iter().zip(&mut i_ids) {
+            write_varint(zigzag(*i_id, *prev), bytes);
+            *prev = *i_id;
+        }
+        let coef_tag = match gate.coef_type {
+            CoefType::Constant if gate.coef == C::CircuitField::ONE => COEF_ONE,
+            CoefType::Constant => COEF_CONSTANT,
+            CoefType::Random => COEF_RANDOM,
+            CoefType::PublicInput(_) => COEF_PUBLIC_INPUT,
+        };
+        write_varint(((gate.gate_type as u64) << 2) | coef_tag, bytes);
+        match gate.coef_type {
+            CoefType::Constant if coef_tag == COEF_CONSTANT => {
+                gate.coef.serialize_into(&mut *bytes).unwrap()
+            }
+            CoefType::PublicInput(idx) => write_varint(idx as u64, bytes),
+            _ => {}
+        }
+    }
+}
+
+fn unpack_gates<C: GKRConfig, const INPUT_NUM: usize>(
+    reader: &mut &[u8],
+) -> Option<Vec<Gate<C, INPUT_NUM>>> {
+    let len = read_varint(reader)?;
+    let mut gates = vec![];
+    let mut o_id = 0;
+    let mut i_ids = [0; INPUT_NUM];
+    for _ in 0..len {
+        o_id = unzigzag(read_varint(reader)?, o_id)?;
+        for prev in &mut i_ids {
+            *prev = unzigzag(read_varint(reader)?, *prev)?;
+        }
+        let tag = read_varint(reader)?;
+        let (coef_type, coef) = match tag & 3 {
+            COEF_ONE => (CoefType::Constant, C::CircuitField::ONE),
+            COEF_CONSTANT => (
+                CoefType::Constant,
+                C::CircuitField::deserialize_from(&mut *reader).ok()?,
+            ),
+            COEF_RANDOM => (CoefType::Random, C::CircuitField::ZERO),
+            _ => (
+                CoefType::PublicInput(usize::try_from(read_varint(reader)?).ok()?),
+                C::CircuitField::ZERO,
+            ),
+        };
+        gates.push(Gate {
+            i_ids,
+            o_id,
+            coef_type,
+            coef,
+            gate_type: usize::try_from(tag >> 2).ok()?,
+        });
+    }
+    Some(gates)
+}
+
+/// `value - prev`, zigzag encoded.
+#[inline]
+fn zigzag(value: usize, prev: usize) -> u64 {
+    let delta = value as i64 - prev as i64;
+    ((delta << 1) ^ (delta >> 63)) as u64
+}
+
+#[inline]
+fn unzigzag(encoded: u64, prev: usize) -> Option<usize> {
+    let delta = (encoded >> 1) as i64 ^ -((encoded & 1) as i64);
+    usize::try_from((prev as i64).checked_add(delta)?).ok()
+}
+
+fn write_varint(mut value: u64, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(reader: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = reader.split_first()?;
+        *reader = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
@@ -1,4 +1,162 @@
-use config::GKRConfig;
+use arith::FieldForECC;
+use config::{field_sentinel, FieldType, GKRConfig, SENTINEL_SIZE};
+use thiserror::Error;
+
+/// The first bytes of a witness file.
+pub const WITNESS_MAGIC: [u8; 4] = *b"EXPW";
+
+/// The version of the witness format written after the magic, as a little-endian `u32`.
+pub const WITNESS_VERSION: u32 = 1;
+
+/// The magic, the version, and the sentinel of the field, as in the circuit files, before the
+/// serialized `Witness`.
+pub const WITNESS_HEADER_SIZE: usize = WITNESS_MAGIC.len() + 4 + SENTINEL_SIZE;
+
+/// The first bytes of a witness batch, followed by the number of instances as a little-endian
+/// `u64`, and by the witness bytes of each, header included, prefixed by their length.
+pub const WITNESS_BATCH_MAGIC: [u8; 4] = *b"EXPB";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WitnessError {
+    #[error("not a witness, or a witness of the unversioned format, see `migrate_v0_to_v1`")]
+    WrongMagic,
+
+    #[error("witness format version {found}, only version {supported} is supported")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("witness of the field of sentinel {found:?}, expected {expected:?}")]
+    WrongField {
+        expected: [u8; SENTINEL_SIZE],
+        found: [u8; SENTINEL_SIZE],
+    },
+
+    #[error("truncated witness header")]
+    Truncated,
+
+    /// The length of the witness bytes, header included, is not the one of the values its
+    /// header tells of, whether the witness is cut short or followed by other bytes.
+    #[error("witness of {got} bytes, expected {expected} for its number of witnesses and inputs")]
+    LengthMismatch { expected: usize, got: usize },
+
+    #[error("expected {expected} private inputs per witness for the circuit, got {got}")]
+    PrivateInputMismatch { expected: usize, got: usize },
+
+    #[error("not enough witnesses, expected {expected}, got {got}")]
+    NotEnoughWitnesses { expected: usize, got: usize },
+
+    #[error("no witnesses")]
+    NoWitnesses,
+
+    #[error("witness batch cut short in its instance {instance}")]
+    BatchTruncated { instance: usize },
+
+    #[error("{0} bytes after the last instance of the witness batch")]
+    BatchTrailingBytes(usize),
+
+    /// An instance of a batch is a single witness, packed with the other instances.
+    #[error("instance {instance} of the witness batch has {got} witnesses, expected 1")]
+    NotSingleWitness { instance: usize, got: usize },
+
+    #[error(
+        "instance {instance} of the witness batch has {got} public inputs, expected {expected}"
+    )]
+    PublicInputMismatch {
+        instance: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+/// The sentinel of the field of the witness bytes and the serialized `Witness` after their
+/// header, checking the magic and the version.
+pub fn split_witness_header(bytes: &[u8]) -> Result<([u8; SENTINEL_SIZE], &[u8]), WitnessError> {
+    if !bytes.starts_with(&WITNESS_MAGIC) {
+        return Err(WitnessError::WrongMagic);
+    }
+    if bytes.len() < WITNESS_HEADER_SIZE {
+        return Err(WitnessError::Truncated);
+    }
+    let (header, payload) = bytes.split_at(WITNESS_HEADER_SIZE);
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version != WITNESS_VERSION {
+        return Err(WitnessError::UnsupportedVersion {
+            found: version,
+            supported: WITNESS_VERSION,
+        });
+    }
+    Ok((header[8..].try_into().unwrap(), payload))
+}
+
+/// As `split_witness_header`, also checking that the witness is of the field `F`.
+pub fn witness_payload<F: FieldForECC>(bytes: &[u8]) -> Result<&[u8], WitnessError> {
+    let (found, payload) = split_witness_header(bytes)?;
+    let expected = F::modulus().to_le_bytes();
+    if found != expected {
+        return Err(WitnessError::WrongField { expected, found });
+    }
+    Ok(payload)
+}
+
+/// The witness bytes of each instance of the witness batch, see `WITNESS_BATCH_MAGIC`.
+pub fn split_witness_batch(bytes: &[u8]) -> Result<Vec<&[u8]>, WitnessError> {
+    let Some(mut rest) = bytes.strip_prefix(&WITNESS_BATCH_MAGIC[..]) else {
+        return Err(WitnessError::WrongMagic);
+    };
+    fn next_len(rest: &mut &[u8]) -> Option<usize> {
+        let (len, tail) = rest.split_first_chunk::<8>()?;
+        *rest = tail;
+        usize::try_from(u64::from_le_bytes(*len)).ok()
+    }
+    let num_instances = next_len(&mut rest).ok_or(WitnessError::Truncated)?;
+    // not trusting the count for the allocation
+    let mut instances = vec![];
+    for instance in 0..num_instances {
+        let witness = next_len(&mut rest)
+            .and_then(|len| rest.get(..len))
+            .ok_or(WitnessError::BatchTruncated { instance })?;
+        rest = &rest[witness.len()..];
+        instances.push(witness);
+    }
+    if !rest.is_empty() {
+        return Err(WitnessError::BatchTrailingBytes(rest.len()));
+    }
+    Ok(instances)
+}
+
+/// The witness batch of the witness bytes of each instance, see `split_witness_batch`.
+pub fn encode_witness_batch(instances: &[&[u8]]) -> Vec<u8> {
+    let mut bytes = WITNESS_BATCH_MAGIC.to_vec();
+    bytes.extend_from_slice(&(instances.len() as u64).to_le_bytes());
+    for witness in instances {
+        bytes.extend_from_slice(&(witness.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(witness);
+    }
+    bytes
+}
+
+/// The witness bytes of the unversioned format, as written by the compiler before the header,
+/// with the header of the current version prepended. A `FieldType::Native` witness keeps the
+/// modulus it already carries as its sentinel.
+///
+/// Panics if a `FieldType::Native` witness is too short to carry its modulus.
+pub fn migrate_v0_to_v1(bytes: &[u8], field: FieldType) -> Vec<u8> {
+    // after the number of witnesses, of private and of public inputs per witness
+    const MODULUS_OFFSET: usize = 3 * 8;
+
+    let sentinel = field_sentinel(&field).unwrap_or_else(|| {
+        bytes
+            .get(MODULUS_OFFSET..MODULUS_OFFSET + SENTINEL_SIZE)
+            .expect("truncated witness")
+            .try_into()
+            .unwrap()
+    });
+    let mut migrated = Vec::with_capacity(WITNESS_HEADER_SIZE + bytes.len());
+    migrated.extend_from_slice(&WITNESS_MAGIC);
+    migrated.extend_from_slice(&WITNESS_VERSION.to_le_bytes());
+    migrated.extend_from_slice(&sentinel);
+    migrated.extend_from_slice(bytes);
+    migrated
+}
 
 // A direct copy of the witness struct from ecc
 #[derive(Debug, Clone)]
@@ -8,3 +166,39 @@ pub struct Witness<C: GKRConfig> {
     pub num_public_inputs_per_witness: usize,
     pub values: Vec<C::CircuitField>,
 }
+
+/// The instances of a witness batch, packed `C::get_field_pack_size()` at a time into the
+/// simd lanes of the private and public inputs of a prove call, see
+/// `Circuit::load_witness_batch_bytes`. The lanes of the last pack past the instances are
+/// zero witnesses, whose proofs are ignored.
+#[derive(Debug, Clone)]
+pub struct WitnessBatch<C: GKRConfig> {
+    pub num_instances: usize,
+    pub packs: Vec<(Vec<C::SimdCircuitField>, Vec<C::SimdCircuitField>)>,
+}
+
+/// A fixed size witness, held on the stack, for the small circuits where allocating a `Vec`
+/// for a handful of elements dominates. The values are the packed input layer values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WitnessTypedArray<F, const N: usize>(pub [F; N]);
+
+impl<F, const N: usize> From<[F; N]> for WitnessTypedArray<F, N> {
+    #[inline]
+    fn from(values: [F; N]) -> Self {
+        Self(values)
+    }
+}
+
+impl<F, const N: usize> AsRef<[F]> for WitnessTypedArray<F, N> {
+    #[inline]
+    fn as_ref(&self) -> &[F] {
+        &self.0
+    }
+}
+
+impl<F, const N: usize> AsMut<[F]> for WitnessTypedArray<F, N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [F] {
+        &mut self.0
+    }
+}
@@ -1,29 +1,39 @@
+mod babybear_ext_sha2;
 mod bn254_keccak;
 mod bn254_mimc;
 mod bn254_sha2;
 mod gf2_ext_keccak;
 mod gf2_ext_sha2;
+mod goldilocks_ext_sha2;
 mod m31_ext_keccak;
 mod m31_ext_sha2;
+mod native_sha2;
 
 use std::fmt::Debug;
 
-use arith::{ExtensionField, Field, FieldForECC, FieldSerde, SimdField};
+use arith::{ExtensionField, Field, FieldForECC, FieldSerde, SimdBackend, SimdField};
 use ark_std::{end_timer, start_timer};
 
+pub use babybear_ext_sha2::BabyBearExtConfigSha2;
 pub use bn254_keccak::BN254ConfigKeccak;
 pub use bn254_mimc::BN254ConfigMIMC5;
 pub use bn254_sha2::BN254ConfigSha2;
 pub use gf2_ext_keccak::GF2ExtConfigKeccak;
 pub use gf2_ext_sha2::GF2ExtConfigSha2;
-pub use m31_ext_keccak::M31ExtConfigKeccak;
-pub use m31_ext_sha2::M31ExtConfigSha2;
+pub use goldilocks_ext_sha2::GoldilocksExtConfigSha2;
+pub use m31_ext_keccak::{M31ExtConfigKeccak, M31ExtSimdConfigKeccak};
+pub use m31_ext_sha2::{M31ExtConfigSha2, M31ExtSimdConfigSha2};
+pub use native_sha2::NativeFieldConfigSha2;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldType {
     M31,
     BN254,
     GF2,
+    Goldilocks,
+    BabyBear,
+    /// A prime field with a user defined modulus
+    Native,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -34,22 +44,25 @@ pub enum FiatShamirHashType {
     Poseidon,
     Animoe,
     MIMC5, // Note: use MIMC5 for bn254 ONLY
+    /// Only as the hash of `Config::with_hash_function`, no `GKRConfig` has it.
+    Blake3,
 }
 
 pub trait GKRConfig: Default + Debug + Clone + Send + Sync + 'static {
     /// Field type for the circuit, e.g., M31
-    type CircuitField: Field + FieldSerde + FieldForECC + Send;
+    type CircuitField: Field + FieldSerde + FieldForECC + Send + Sync;
 
     /// Field type for the challenge, e.g., M31Ext3
-    type ChallengeField: ExtensionField<BaseField = Self::CircuitField> + Send;
+    type ChallengeField: ExtensionField<BaseField = Self::CircuitField> + Send + Sync;
 
     /// Main field type for the scheme, e.g., M31Ext3x16
     type Field: ExtensionField<BaseField = Self::SimdCircuitField>
         + SimdField<Scalar = Self::ChallengeField>
-        + Send;
+        + Send
+        + Sync;
 
     /// Simd field for circuit, e.g., M31x16
-    type SimdCircuitField: SimdField<Scalar = Self::CircuitField> + FieldSerde + Send;
+    type SimdCircuitField: SimdField<Scalar = Self::CircuitField> + FieldSerde + Send + Sync;
 
     /// Fiat Shamir hash type
     const FIAT_SHAMIR_HASH: FiatShamirHashType;
@@ -57,6 +70,9 @@ pub trait GKRConfig: Default + Debug + Clone + Send + Sync + 'static {
     /// Enum type for Self::Field
     const FIELD_TYPE: FieldType;
 
+    /// The simd backend of the packed fields, by default that of the target features of the build
+    const SIMD_BACKEND: SimdBackend = SimdBackend::compiled();
+
     /// API to allow for multiplications between the challenge and the circuit field
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
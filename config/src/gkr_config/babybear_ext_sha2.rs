@@ -0,0 +1,89 @@
+use arith::ExtensionField;
+use babybear::{BabyBear, BabyBearExt4, BabyBearExt4x16, BabyBearx16};
+
+use super::{FiatShamirHashType, FieldType, GKRConfig};
+
+/// BabyBear in 16 simd lanes, as M31, the challenges are drawn from its degree 4 extension.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BabyBearExtConfigSha2;
+
+impl GKRConfig for BabyBearExtConfigSha2 {
+    type CircuitField = BabyBear;
+
+    type SimdCircuitField = BabyBearx16;
+
+    type ChallengeField = BabyBearExt4;
+
+    type Field = BabyBearExt4x16;
+
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::SHA256;
+
+    const FIELD_TYPE: FieldType = FieldType::BabyBear;
+
+    #[inline(always)]
+    fn challenge_mul_circuit_field(
+        a: &Self::ChallengeField,
+        b: &Self::CircuitField,
+    ) -> Self::ChallengeField {
+        a.mul_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn field_mul_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        // directly multiply BabyBearExt4x16 with BabyBear, skipping the broadcast of BabyBear
+        *a * *b
+    }
+
+    #[inline(always)]
+    fn field_add_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        *a + *b
+    }
+
+    #[inline(always)]
+    fn field_add_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        a.add_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn field_mul_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        a.mul_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn challenge_mul_field(a: &Self::ChallengeField, b: &Self::Field) -> Self::Field {
+        *b * *a
+    }
+
+    #[inline(always)]
+    fn circuit_field_into_field(a: &Self::SimdCircuitField) -> Self::Field {
+        Self::Field::from(*a)
+    }
+
+    #[inline(always)]
+    fn circuit_field_mul_simd_circuit_field(
+        a: &Self::CircuitField,
+        b: &Self::SimdCircuitField,
+    ) -> Self::SimdCircuitField {
+        *b * *a
+    }
+
+    #[inline(always)]
+    fn circuit_field_to_simd_circuit_field(a: &Self::CircuitField) -> Self::SimdCircuitField {
+        Self::SimdCircuitField::from(*a)
+    }
+
+    #[inline(always)]
+    fn simd_circuit_field_into_field(a: &Self::SimdCircuitField) -> Self::Field {
+        Self::Field::from(*a)
+    }
+
+    #[inline(always)]
+    fn simd_circuit_field_mul_challenge_field(
+        a: &Self::SimdCircuitField,
+        b: &Self::ChallengeField,
+    ) -> Self::Field {
+        Self::Field {
+            v: b.v.map(|coef| *a * coef),
+        }
+    }
+}
@@ -1,24 +1,35 @@
-use arith::ExtensionField;
-use mersenne31::{M31Ext3, M31Ext3x16, M31x16, M31};
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, SimdBackend};
+use mersenne31::{
+    simd::{CompiledBackend, M31SimdBackend},
+    M31Ext3, M31,
+};
 
 use super::{FiatShamirHashType, FieldType, GKRConfig};
 
+/// Of the packed fields of the simd backend `B`, e.g., of `SimdBackend::select`.
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct M31ExtConfigSha2;
+pub struct M31ExtSimdConfigSha2<B: M31SimdBackend>(PhantomData<B>);
+
+/// Of the packed fields of the target features of the build.
+pub type M31ExtConfigSha2 = M31ExtSimdConfigSha2<CompiledBackend>;
 
-impl GKRConfig for M31ExtConfigSha2 {
+impl<B: M31SimdBackend> GKRConfig for M31ExtSimdConfigSha2<B> {
     type CircuitField = M31;
 
-    type SimdCircuitField = M31x16;
+    type SimdCircuitField = B::M31x16;
 
     type ChallengeField = M31Ext3;
 
-    type Field = M31Ext3x16;
+    type Field = B::M31Ext3x16;
 
     const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::SHA256;
 
     const FIELD_TYPE: FieldType = FieldType::M31;
 
+    const SIMD_BACKEND: SimdBackend = B::BACKEND;
+
     #[inline(always)]
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
@@ -84,13 +95,6 @@ impl GKRConfig for M31ExtConfigSha2 {
         a: &Self::SimdCircuitField,
         b: &Self::ChallengeField,
     ) -> Self::Field {
-        let b_simd_ext = Self::Field::from(*b);
-        Self::Field {
-            v: [
-                b_simd_ext.v[0] * a,
-                b_simd_ext.v[1] * a,
-                b_simd_ext.v[2] * a,
-            ],
-        }
+        Self::Field::from(*b).mul_by_base_field(a)
     }
 }
@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+
+use arith::{NativeFieldArithmetic, NativeModulus};
+
+use super::{FiatShamirHashType, FieldType, GKRConfig};
+
+/// Config for a prime field with a user defined modulus, see `NativeFieldArithmetic`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NativeFieldConfigSha2<const BITS: usize, M: NativeModulus> {
+    _marker: PhantomData<M>,
+}
+
+impl<const BITS: usize, M: NativeModulus> GKRConfig for NativeFieldConfigSha2<BITS, M> {
+    type CircuitField = NativeFieldArithmetic<BITS, M>;
+
+    type ChallengeField = NativeFieldArithmetic<BITS, M>;
+
+    type Field = NativeFieldArithmetic<BITS, M>;
+
+    type SimdCircuitField = NativeFieldArithmetic<BITS, M>;
+
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::SHA256;
+
+    const FIELD_TYPE: FieldType = FieldType::Native;
+
+    #[inline(always)]
+    fn challenge_mul_circuit_field(
+        a: &Self::ChallengeField,
+        b: &Self::CircuitField,
+    ) -> Self::ChallengeField {
+        *a * b
+    }
+
+    #[inline(always)]
+    fn field_mul_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        *a * b
+    }
+
+    #[inline(always)]
+    fn field_add_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        *a + *b
+    }
+
+    #[inline(always)]
+    fn field_add_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        *a + b
+    }
+
+    #[inline(always)]
+    fn field_mul_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        *a * b
+    }
+
+    #[inline(always)]
+    fn challenge_mul_field(a: &Self::ChallengeField, b: &Self::Field) -> Self::Field {
+        *a * b
+    }
+
+    #[inline(always)]
+    fn circuit_field_into_field(a: &Self::CircuitField) -> Self::Field {
+        *a
+    }
+
+    #[inline(always)]
+    fn circuit_field_mul_simd_circuit_field(
+        a: &Self::CircuitField,
+        b: &Self::SimdCircuitField,
+    ) -> Self::SimdCircuitField {
+        *a * *b
+    }
+
+    #[inline(always)]
+    fn circuit_field_to_simd_circuit_field(a: &Self::CircuitField) -> Self::SimdCircuitField {
+        *a
+    }
+    #[inline(always)]
+    fn simd_circuit_field_into_field(a: &Self::SimdCircuitField) -> Self::Field {
+        *a
+    }
+
+    #[inline(always)]
+    fn simd_circuit_field_mul_challenge_field(
+        a: &Self::SimdCircuitField,
+        b: &Self::ChallengeField,
+    ) -> Self::Field {
+        *a * b
+    }
+}
@@ -0,0 +1,58 @@
+//! The Fiat-Shamir hash of a `Config` chosen at runtime, instead of the hash of its `GKRConfig`.
+
+use thiserror::Error;
+use transcript::{
+    Blake3hasher, FiatShamirBytesHash, HashTranscript, Keccak256hasher, SHA256hasher,
+};
+
+use crate::{FiatShamirHashType, FieldType, GKRScheme, PolynomialCommitmentType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashType {
+    Sha256,
+    Blake3,
+    Keccak256,
+    /// Not available yet as a transcript hash.
+    Poseidon,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ConfigError {
+    #[error("the {hash:?} transcript hash is not available for the {field:?} field")]
+    HashUnavailable { hash: HashType, field: FieldType },
+
+    #[error("{0} mpi processes, but the world size must be a power of 2, e.g., mpiexec -n 4")]
+    WorldSizeNotPowerOfTwo(usize),
+
+    #[error("the {0:?} polynomial commitment is not implemented yet, only Raw and Orion are")]
+    PolynomialCommitmentUnavailable(PolynomialCommitmentType),
+
+    #[error("the {pcs:?} polynomial commitment is not implemented for the {scheme:?} scheme")]
+    PolynomialCommitmentScheme {
+        pcs: PolynomialCommitmentType,
+        scheme: GKRScheme,
+    },
+}
+
+impl From<HashType> for FiatShamirHashType {
+    fn from(hash: HashType) -> Self {
+        match hash {
+            HashType::Sha256 => FiatShamirHashType::SHA256,
+            HashType::Blake3 => FiatShamirHashType::Blake3,
+            HashType::Keccak256 => FiatShamirHashType::Keccak256,
+            HashType::Poseidon => FiatShamirHashType::Poseidon,
+        }
+    }
+}
+
+impl HashType {
+    /// The hasher of the transcript, `None` if the hash has no transcript.
+    pub fn hasher(&self) -> Option<Box<dyn HashTranscript>> {
+        match self {
+            HashType::Sha256 => Some(Box::new(SHA256hasher::new())),
+            HashType::Blake3 => Some(Box::new(Blake3hasher::new())),
+            HashType::Keccak256 => Some(Box::new(Keccak256hasher::new())),
+            HashType::Poseidon => None,
+        }
+    }
+}
@@ -1,12 +1,18 @@
 mod gkr_config;
 pub use gkr_config::*;
 
+mod hash_type;
+pub use hash_type::*;
+
 mod mpi_config;
 pub use mpi_config::*;
 
+mod sentinel;
+pub use sentinel::*;
+
 use arith::Field;
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum PolynomialCommitmentType {
     #[default]
     Raw,
@@ -15,19 +21,23 @@ pub enum PolynomialCommitmentType {
     FRI,
 }
 
-pub const SENTINEL_M31: [u8; 32] = [
-    255, 255, 255, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0,
-];
-
-pub const SENTINEL_BN254: [u8; 32] = [
-    1, 0, 0, 240, 147, 245, 225, 67, 145, 112, 185, 121, 72, 232, 51, 40, 93, 88, 129, 129, 182,
-    69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48,
-];
+impl PolynomialCommitmentType {
+    pub const ALL: [PolynomialCommitmentType; 4] = [
+        PolynomialCommitmentType::Raw,
+        PolynomialCommitmentType::KZG,
+        PolynomialCommitmentType::Orion,
+        PolynomialCommitmentType::FRI,
+    ];
 
-pub const SENTINEL_GF2: [u8; 32] = [
-    2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-];
+    /// Whether the prover commits, and the verifier checks the openings, with the scheme: the
+    /// raw commitment and Orion so far.
+    pub fn is_implemented(&self) -> bool {
+        matches!(
+            self,
+            PolynomialCommitmentType::Raw | PolynomialCommitmentType::Orion
+        )
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum GKRScheme {
@@ -53,6 +63,14 @@ pub struct Config<C: GKRConfig> {
     pub gkr_scheme: GKRScheme,
     // mpi config
     pub mpi_config: MPIConfig,
+    // Seed absorbed by the transcript before anything else, for reproducible challenges
+    pub prover_seed: Option<[u8; 32]>,
+    // Label of the application absorbed by the transcript after the seed, none if empty
+    pub domain: Vec<u8>,
+    // Hash of the transcripts, `C::FIAT_SHAMIR_HASH` if none
+    pub hash_function: Option<HashType>,
+    // Threads of the prover and of the verifier of each process, see `with_num_threads`
+    pub num_threads: usize,
 }
 
 impl<C: GKRConfig> Config<C> {
@@ -66,6 +84,103 @@ impl<C: GKRConfig> Config<C> {
             gkr_config: C::default(),
             gkr_scheme,
             mpi_config,
+            prover_seed: None,
+            domain: vec![],
+            hash_function: None,
+            num_threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// The config of the polynomial commitment, rather than the raw one of `new`, failing for a
+    /// scheme that is not implemented. The verifier must be given the scheme of the prover.
+    pub fn new_with_pcs(
+        gkr_scheme: GKRScheme,
+        polynomial_commitment_type: PolynomialCommitmentType,
+        mpi_config: MPIConfig,
+    ) -> Result<Self, ConfigError> {
+        let config = Config {
+            polynomial_commitment_type,
+            ..Self::new(gkr_scheme, mpi_config)
+        };
+        config.check_polynomial_commitment()?;
+        Ok(config)
+    }
+
+    /// Fails for a polynomial commitment that is not implemented, or not for the GKR scheme:
+    /// Orion opens the claims of the vanilla scheme only.
+    pub fn check_polynomial_commitment(&self) -> Result<(), ConfigError> {
+        if !self.polynomial_commitment_type.is_implemented() {
+            return Err(ConfigError::PolynomialCommitmentUnavailable(
+                self.polynomial_commitment_type.clone(),
+            ));
+        }
+        if self.polynomial_commitment_type == PolynomialCommitmentType::Orion
+            && self.gkr_scheme != GKRScheme::Vanilla
+        {
+            return Err(ConfigError::PolynomialCommitmentScheme {
+                pcs: self.polynomial_commitment_type.clone(),
+                scheme: self.gkr_scheme.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The same config, with the sumcheck of each process over `num_threads` threads rather
+    /// than all the available cores, at least one, and the verification of the proofs of several
+    /// processes over as many. The proofs, and whether they verify, do not depend on the number
+    /// of threads. The processes of a machine share its cores, which they divide with this.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads.max(1);
+        self
+    }
+
+    /// The same config, with the transcripts of the prover and of the verifier seeded.
+    /// Proofs of the same circuit and witness with the same seed are identical. The verifier
+    /// of a proof package reads the seed of the prover from it, a seed given being checked.
+    pub fn with_prover_seed(mut self, seed: [u8; 32]) -> Self {
+        self.prover_seed = Some(seed);
+        self
+    }
+
+    /// As `with_prover_seed`, of the little-endian bytes of the seed padded with zeros. The
+    /// prover draws no other randomness, the raw commitment and the sumcheck being of the
+    /// transcript alone, so that the proofs of a seed are the same bytes from run to run.
+    pub fn with_rng_seed(self, seed: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        self.with_prover_seed(bytes)
+    }
+
+    /// The same config, with the transcripts of the prover and of the verifier bound to the
+    /// domain, e.g., the name of the application, for its proofs not to verify in another
+    /// domain. The empty domain is no domain, of the same proofs as without.
+    pub fn with_domain(mut self, domain: &[u8]) -> Self {
+        self.domain = domain.to_vec();
+        self
+    }
+
+    /// The hash of the transcripts, of `with_hash_function` if any, else `C::FIAT_SHAMIR_HASH`.
+    pub fn fiat_shamir_hash(&self) -> FiatShamirHashType {
+        match self.hash_function {
+            Some(hash) => hash.into(),
+            None => C::FIAT_SHAMIR_HASH,
+        }
+    }
+
+    /// The same config, with the transcripts of the prover and of the verifier hashed by `hash`
+    /// rather than by `C::FIAT_SHAMIR_HASH`, see `Prover::prove_silent`. The verifier must be
+    /// given the hash of the prover. The challenges of the fields larger than the digest of the
+    /// hash could not be drawn, and Poseidon is not available as a transcript hash yet.
+    pub fn with_hash_function(mut self, hash: HashType) -> Result<Self, ConfigError> {
+        match hash.hasher() {
+            Some(hasher) if C::ChallengeField::SIZE <= hasher.digest_size() => {
+                self.hash_function = Some(hash);
+                Ok(self)
+            }
+            _ => Err(ConfigError::HashUnavailable {
+                hash,
+                field: C::FIELD_TYPE,
+            }),
         }
     }
 }
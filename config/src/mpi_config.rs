@@ -1,14 +1,27 @@
-use std::{cmp, fmt::Debug};
+//! The processes of a proof. Without the `mpi` feature, e.g., for wasm, a config is of a single
+//! process, and of several only for the verifiers of their proofs, see `new_for_verifier`.
+
+#[cfg(feature = "mpi")]
+use std::{
+    cmp,
+    fmt::Debug,
+    mem, thread,
+    time::{Duration, Instant},
+};
 
 use arith::{Field, FieldSerde};
+#[cfg(feature = "mpi")]
 use mpi::{
     environment::Universe,
     ffi,
+    request::{Request, StaticScope},
     topology::{Process, SimpleCommunicator},
     traits::*,
 };
 use transcript::Transcript;
 
+use crate::ConfigError;
+
 #[macro_export]
 macro_rules! root_println {
     ($config: expr, $($arg:tt)*) => {
@@ -18,12 +31,17 @@ macro_rules! root_println {
     };
 }
 
+#[cfg(feature = "mpi")]
 static mut UNIVERSE: Option<Universe> = None;
+#[cfg(feature = "mpi")]
 static mut WORLD: Option<SimpleCommunicator> = None;
 
 #[derive(Clone)]
+#[cfg_attr(not(feature = "mpi"), derive(Debug))]
 pub struct MPIConfig {
+    #[cfg(feature = "mpi")]
     pub universe: Option<&'static mpi::environment::Universe>,
+    #[cfg(feature = "mpi")]
     pub world: Option<&'static SimpleCommunicator>,
     pub world_size: i32,
     pub world_rank: i32,
@@ -32,7 +50,9 @@ pub struct MPIConfig {
 impl Default for MPIConfig {
     fn default() -> Self {
         Self {
+            #[cfg(feature = "mpi")]
             universe: None,
+            #[cfg(feature = "mpi")]
             world: None,
             world_size: 1,
             world_rank: 0,
@@ -40,6 +60,20 @@ impl Default for MPIConfig {
     }
 }
 
+impl MPIConfig {
+    /// Err for a world size other than a power of 2: the sumcheck runs over the bits of the
+    /// ranks, and the proofs of other sizes would not verify.
+    pub fn check_world_size(&self) -> Result<(), ConfigError> {
+        let world_size = self.world_size as usize;
+        if world_size.is_power_of_two() {
+            Ok(())
+        } else {
+            Err(ConfigError::WorldSizeNotPowerOfTwo(world_size))
+        }
+    }
+}
+
+#[cfg(feature = "mpi")]
 impl Debug for MPIConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let universe_fmt = if self.universe.is_none() {
@@ -75,9 +109,11 @@ impl MPIConfig {
     const ROOT_RANK: i32 = 0;
 
     /// The communication limit for MPI is 2^30. Save 10 bits for #parties here.
+    #[cfg(feature = "mpi")]
     const CHUNK_SIZE: usize = 1usize << 20;
 
     // OK if already initialized, mpi::initialize() will return None
+    #[cfg(feature = "mpi")]
     #[allow(static_mut_refs)]
     pub fn init() {
         unsafe {
@@ -89,11 +125,13 @@ impl MPIConfig {
         }
     }
 
+    #[cfg(feature = "mpi")]
     #[inline]
     pub fn finalize() {
         unsafe { ffi::MPI_Finalize() };
     }
 
+    #[cfg(feature = "mpi")]
     #[allow(static_mut_refs)]
     pub fn new() -> Self {
         Self::init();
@@ -120,7 +158,9 @@ impl MPIConfig {
     #[inline]
     pub fn new_for_verifier(world_size: i32) -> Self {
         Self {
+            #[cfg(feature = "mpi")]
             universe: None,
+            #[cfg(feature = "mpi")]
             world: None,
             world_size,
             world_rank: 0,
@@ -128,12 +168,14 @@ impl MPIConfig {
     }
 
     /// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
+    #[cfg(feature = "mpi")]
     #[inline]
     unsafe fn elem_to_u8_bytes<V: Sized>(elem: &V, byte_size: usize) -> Vec<u8> {
         Vec::<u8>::from_raw_parts((elem as *const V) as *mut u8, byte_size, byte_size)
     }
 
     /// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
+    #[cfg(feature = "mpi")]
     #[inline]
     unsafe fn vec_to_u8_bytes<F: Field>(vec: &Vec<F>) -> Vec<u8> {
         Vec::<u8>::from_raw_parts(
@@ -143,6 +185,7 @@ impl MPIConfig {
         )
     }
 
+    #[cfg(feature = "mpi")]
     #[allow(clippy::collapsible_else_if)]
     pub fn gather_vec<F: Field>(&self, local_vec: &Vec<F>, global_vec: &mut Vec<F>) {
         unsafe {
@@ -201,7 +244,69 @@ impl MPIConfig {
         }
     }
 
+    /// Root process splits the global vector into world_size equal parts,
+    /// and sends the i-th part to the i-th process. The inverse of gather_vec.
+    /// local_vec must be allocated with the size of a part on all processes,
+    /// world size 1 included.
+    #[cfg(feature = "mpi")]
+    #[allow(clippy::collapsible_else_if)]
+    pub fn scatter_vec<F: Field>(&self, global_vec: &[F], local_vec: &mut Vec<F>) {
+        assert!(!self.is_root() || global_vec.len() == local_vec.len() * self.world_size());
+        unsafe {
+            if self.world_size == 1 {
+                *local_vec = global_vec.to_vec()
+            } else {
+                let global_vec_u8 = std::slice::from_raw_parts(
+                    global_vec.as_ptr() as *const u8,
+                    global_vec.len() * F::SIZE,
+                );
+                let mut local_vec_u8 = Self::vec_to_u8_bytes(local_vec);
+                let local_n_bytes = local_vec_u8.len();
+                let n_chunks = (local_n_bytes + Self::CHUNK_SIZE - 1) / Self::CHUNK_SIZE;
+                if n_chunks == 1 {
+                    if self.world_rank == Self::ROOT_RANK {
+                        self.root_process()
+                            .scatter_into_root(global_vec_u8, &mut local_vec_u8);
+                    } else {
+                        self.root_process().scatter_into(&mut local_vec_u8);
+                    }
+                } else {
+                    if self.world_rank == Self::ROOT_RANK {
+                        let mut chunk_buffer_u8 = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
+                        for i in 0..n_chunks {
+                            let local_start = i * Self::CHUNK_SIZE;
+                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
+
+                            // collect the i-th chunk of every part into the buffer
+                            let actual_chunk_size = local_end - local_start;
+                            for j in 0..self.world_size() {
+                                let global_start = j * local_n_bytes + local_start;
+                                let global_end = global_start + actual_chunk_size;
+                                chunk_buffer_u8[j * actual_chunk_size..(j + 1) * actual_chunk_size]
+                                    .copy_from_slice(&global_vec_u8[global_start..global_end]);
+                            }
+
+                            self.root_process().scatter_into_root(
+                                &chunk_buffer_u8[..actual_chunk_size * self.world_size()],
+                                &mut local_vec_u8[local_start..local_end],
+                            );
+                        }
+                    } else {
+                        for i in 0..n_chunks {
+                            let local_start = i * Self::CHUNK_SIZE;
+                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
+                            self.root_process()
+                                .scatter_into(&mut local_vec_u8[local_start..local_end]);
+                        }
+                    }
+                }
+                local_vec_u8.leak(); // discard control of the memory
+            }
+        }
+    }
+
     /// Root process broadcase a value f into all the processes
+    #[cfg(feature = "mpi")]
     #[inline]
     pub fn root_broadcast<F: Field>(&self, f: &mut F) {
         unsafe {
@@ -273,17 +378,40 @@ impl MPIConfig {
         self.world_rank == Self::ROOT_RANK
     }
 
+    #[cfg(feature = "mpi")]
     #[inline(always)]
     pub fn root_process(&self) -> Process {
         self.world.unwrap().process_at_rank(Self::ROOT_RANK)
     }
 
+    #[cfg(feature = "mpi")]
     #[inline(always)]
     pub fn barrier(&self) {
         self.world.unwrap().barrier();
     }
 
+    /// Root process broadcasts the bytes into those of the others, without blocking.
+    #[cfg(feature = "mpi")]
+    pub fn immediate_root_broadcast_bytes(&self, bytes: Vec<u8>) -> PendingBytes {
+        PendingBytes::start(bytes, |buffer| {
+            self.root_process()
+                .immediate_broadcast_into(StaticScope, buffer)
+        })
+    }
+
+    /// Receives the `len` bytes the rank sends with the tag, without blocking.
+    #[cfg(feature = "mpi")]
+    pub fn immediate_receive_bytes(&self, rank: usize, len: usize, tag: i32) -> PendingBytes {
+        PendingBytes::start(vec![0u8; len], |buffer| {
+            self.world
+                .unwrap()
+                .process_at_rank(rank as i32)
+                .immediate_receive_into_with_tag(StaticScope, buffer, tag)
+        })
+    }
+
     /// broadcast root transcript state. incurs an additional hash if self.world_size > 1
+    #[cfg(feature = "mpi")]
     pub fn transcript_sync_up<F, T>(&self, transcript: &mut T)
     where
         F: Field + FieldSerde,
@@ -314,4 +442,106 @@ impl MPIConfig {
     }
 }
 
+/// A non-blocking broadcast or receive of bytes, which owns its buffer until the request
+/// completes and then gives it back. The buffer of a request dropped before completing is leaked
+/// with the request, as MPI may still write to it.
+#[cfg(feature = "mpi")]
+pub struct PendingBytes {
+    request: Option<Request<'static, [u8]>>,
+    buffer: *mut [u8],
+}
+
+#[cfg(feature = "mpi")]
+impl PendingBytes {
+    fn start(
+        bytes: Vec<u8>,
+        start: impl FnOnce(&'static mut [u8]) -> Request<'static, [u8]>,
+    ) -> Self {
+        let buffer = Box::into_raw(bytes.into_boxed_slice());
+        // the request is the only user of the buffer until it completes
+        let request = start(unsafe { &mut *buffer });
+        Self {
+            request: Some(request),
+            buffer,
+        }
+    }
+
+    /// The bytes, the first time the request is found complete.
+    pub fn test(&mut self) -> Option<Vec<u8>> {
+        match self.request.take()?.test() {
+            // the completed request no longer borrows the buffer
+            Ok(_) => Some(unsafe { Box::from_raw(self.buffer) }.into_vec()),
+            Err(request) => {
+                self.request = Some(request);
+                None
+            }
+        }
+    }
+
+    /// Polls the request until it completes, or else until the deadline passes.
+    pub fn wait_until(mut self, deadline: Instant, poll_interval: Duration) -> Option<Vec<u8>> {
+        loop {
+            if let Some(bytes) = self.test() {
+                return Some(bytes);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            thread::sleep(poll_interval);
+        }
+    }
+}
+
+#[cfg(feature = "mpi")]
+impl Drop for PendingBytes {
+    fn drop(&mut self) {
+        // a request dropped before completing would panic
+        if let Some(request) = self.request.take() {
+            mem::forget(request);
+        }
+    }
+}
+
+/// A single process, the root, which is the whole world but for the verifiers of several.
+#[cfg(not(feature = "mpi"))]
+impl MPIConfig {
+    #[inline]
+    pub fn init() {}
+
+    #[inline]
+    pub fn finalize() {}
+
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::ptr_arg)]
+    pub fn gather_vec<F: Field>(&self, local_vec: &Vec<F>, global_vec: &mut Vec<F>) {
+        assert_eq!(self.world_size, 1, "no mpi to gather with");
+        *global_vec = local_vec.clone()
+    }
+
+    pub fn scatter_vec<F: Field>(&self, global_vec: &[F], local_vec: &mut Vec<F>) {
+        assert_eq!(self.world_size, 1, "no mpi to scatter with");
+        assert_eq!(global_vec.len(), local_vec.len());
+        *local_vec = global_vec.to_vec()
+    }
+
+    /// The root is the only process, and has the value already.
+    #[inline]
+    pub fn root_broadcast<F: Field>(&self, _f: &mut F) {}
+
+    #[inline(always)]
+    pub fn barrier(&self) {}
+
+    #[inline]
+    pub fn transcript_sync_up<F, T>(&self, _transcript: &mut T)
+    where
+        F: Field + FieldSerde,
+        T: Transcript<F>,
+    {
+    }
+}
+
 unsafe impl Send for MPIConfig {}
@@ -0,0 +1,60 @@
+//! The sentinels of the circuit files: the modulus of the circuit field, as 32 little-endian
+//! bytes, written by the compiler after the 8 bytes of the format version. Bytes 8 to 39 of a
+//! circuit file thus tell its field before anything else is parsed.
+
+use crate::FieldType;
+
+/// Where the sentinel is in a circuit file.
+pub const SENTINEL_OFFSET: usize = 8;
+
+pub const SENTINEL_SIZE: usize = 32;
+
+/// 2^31 - 1
+pub const SENTINEL_M31: [u8; 32] = [
+    255, 255, 255, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0,
+];
+
+/// The order of the BN254 scalar field
+pub const SENTINEL_BN254: [u8; 32] = [
+    1, 0, 0, 240, 147, 245, 225, 67, 145, 112, 185, 121, 72, 232, 51, 40, 93, 88, 129, 129, 182,
+    69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48,
+];
+
+/// 2
+pub const SENTINEL_GF2: [u8; 32] = [
+    2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// 2^64 - 2^32 + 1
+pub const SENTINEL_GOLDILOCKS: [u8; 32] = [
+    1, 0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0,
+];
+
+/// 2^31 - 2^27 + 1
+pub const SENTINEL_BABYBEAR: [u8; 32] = [
+    1, 0, 0, 120, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0,
+];
+
+/// The bytes of the sentinel in hex, in the order of the file, for the error messages.
+pub fn sentinel_hex(sentinel: &[u8; SENTINEL_SIZE]) -> String {
+    sentinel
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The sentinel of the field, `None` for `FieldType::Native` whose modulus is only known to
+/// its config.
+pub fn field_sentinel(field: &FieldType) -> Option<[u8; SENTINEL_SIZE]> {
+    match field {
+        FieldType::M31 => Some(SENTINEL_M31),
+        FieldType::BN254 => Some(SENTINEL_BN254),
+        FieldType::GF2 => Some(SENTINEL_GF2),
+        FieldType::Goldilocks => Some(SENTINEL_GOLDILOCKS),
+        FieldType::BabyBear => Some(SENTINEL_BABYBEAR),
+        FieldType::Native => None,
+    }
+}
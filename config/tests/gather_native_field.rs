@@ -0,0 +1,45 @@
+use arith::{Field, NativeFieldArithmetic, NativeModulus};
+use config::MPIConfig;
+use crypto_bigint::U256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct GoldilocksModulus;
+
+impl NativeModulus for GoldilocksModulus {
+    const NAME: &'static str = "native goldilocks";
+
+    const MODULUS: U256 = U256::from_u64(0xffff_ffff_0000_0001);
+}
+
+/// A width below 256 bits, whose elements are still of a whole `U256` in memory.
+type NativeGoldilocks = NativeFieldArithmetic<64, GoldilocksModulus>;
+
+#[test]
+fn test_gather_native_field() {
+    const TEST_SIZE: usize = (1 << 10) + 1;
+
+    assert_eq!(
+        <NativeGoldilocks as Field>::SIZE,
+        std::mem::size_of::<NativeGoldilocks>()
+    );
+
+    let mpi_config = MPIConfig::new();
+    let local_vec = (0..TEST_SIZE)
+        .map(|i| NativeGoldilocks::from((mpi_config.world_rank() * TEST_SIZE + i) as u32))
+        .collect::<Vec<_>>();
+
+    let mut global_vec = if mpi_config.is_root() {
+        vec![NativeGoldilocks::ZERO; TEST_SIZE * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+
+    mpi_config.gather_vec(&local_vec, &mut global_vec);
+    if mpi_config.is_root() {
+        for (i, v) in global_vec.iter().enumerate() {
+            assert_eq!(NativeGoldilocks::from(i as u32), *v);
+        }
+    }
+
+    MPIConfig::finalize();
+}
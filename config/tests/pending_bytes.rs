@@ -0,0 +1,30 @@
+#![cfg(feature = "mpi")]
+
+use std::time::{Duration, Instant};
+
+use config::MPIConfig;
+
+#[test]
+fn test_pending_bytes() {
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+    let deadline = Instant::now() + Duration::from_secs(10);
+
+    let mpi_config = MPIConfig::new();
+    let bytes = if mpi_config.is_root() {
+        (0..=255).collect()
+    } else {
+        vec![0u8; 256]
+    };
+    let broadcast = mpi_config
+        .immediate_root_broadcast_bytes(bytes)
+        .wait_until(deadline, POLL_INTERVAL);
+    assert_eq!(broadcast, Some((0..=255).collect::<Vec<u8>>()));
+
+    // nothing is sent with the tag, the receive is dropped, and its buffer leaked, unanswered
+    let receive = mpi_config
+        .immediate_receive_bytes(mpi_config.world_rank(), 8, 0x5442)
+        .wait_until(Instant::now(), POLL_INTERVAL);
+    assert_eq!(receive, None);
+
+    MPIConfig::finalize();
+}
@@ -0,0 +1,51 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use arith::Field;
+use config::MPIConfig;
+use mersenne31::M31;
+
+#[test]
+fn test_scatter_vec() {
+    const TEST_SIZE: usize = (1 << 10) + 1;
+
+    let mpi_config = MPIConfig::new();
+    let global_vec = if mpi_config.is_root() {
+        (0..TEST_SIZE * mpi_config.world_size())
+            .map(|i| M31::from(i as u32))
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    // each process gets its part, and gathering the parts gives back the global vector
+    let mut local_vec = vec![M31::ZERO; TEST_SIZE];
+    mpi_config.scatter_vec(&global_vec, &mut local_vec);
+    for (i, v) in local_vec.iter().enumerate() {
+        assert_eq!(
+            M31::from((mpi_config.world_rank() * TEST_SIZE + i) as u32),
+            *v
+        );
+    }
+
+    let mut gathered = if mpi_config.is_root() {
+        vec![M31::ZERO; TEST_SIZE * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&local_vec, &mut gathered);
+    if mpi_config.is_root() {
+        assert_eq!(gathered, global_vec);
+    }
+
+    // a part not of the size of those of the global vector is refused by the root, before any
+    // communication, so only a world of 1 process checks it without hanging the others
+    if mpi_config.world_size() == 1 {
+        let mut short_vec = vec![M31::ZERO; TEST_SIZE - 1];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            mpi_config.scatter_vec(&global_vec, &mut short_vec)
+        }));
+        assert!(result.is_err());
+    }
+
+    MPIConfig::finalize();
+}
@@ -0,0 +1,22 @@
+use config::{ConfigError, MPIConfig};
+
+#[test]
+fn test_check_world_size() {
+    for world_size in [1, 2, 4] {
+        assert_eq!(
+            MPIConfig::new_for_verifier(world_size).check_world_size(),
+            Ok(())
+        );
+    }
+    assert_eq!(
+        MPIConfig::new_for_verifier(3).check_world_size(),
+        Err(ConfigError::WorldSizeNotPowerOfTwo(3))
+    );
+    assert_eq!(
+        MPIConfig::new_for_verifier(6)
+            .check_world_size()
+            .unwrap_err()
+            .to_string(),
+        "6 mpi processes, but the world size must be a power of 2, e.g., mpiexec -n 4"
+    );
+}
@@ -0,0 +1,108 @@
+#ifndef EXPANDER_H
+#define EXPANDER_H
+
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a call, 0 for success.
+ */
+typedef enum ExpanderStatus {
+  EXPANDER_STATUS_OK = 0,
+  /**
+   * A null pointer for a handle, an output or bytes of a non-zero length.
+   */
+  EXPANDER_STATUS_NULL_ARGUMENT = 1,
+  /**
+   * The witness, or the public inputs, do not fit the circuit of the handle.
+   */
+  EXPANDER_STATUS_INVALID_WITNESS = 2,
+  /**
+   * The proof bytes are not a proof package of the field of the circuit.
+   */
+  EXPANDER_STATUS_INVALID_PROOF = 3,
+  /**
+   * The proof is read, but does not verify.
+   */
+  EXPANDER_STATUS_REJECTED = 4,
+  /**
+   * The proof could not be serialized.
+   */
+  EXPANDER_STATUS_INTERNAL = 5,
+  EXPANDER_STATUS_PANIC = 6,
+} ExpanderStatus;
+
+/**
+ * A circuit loaded by `expander_load_circuit`, opaque to the callers.
+ */
+typedef struct ExpanderHandle ExpanderHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Load the circuit of the bytes of a circuit file, of the field of its sentinel. Null for bytes
+ * that are not a valid circuit, to be freed by `expander_free` otherwise.
+ *
+ * # Safety
+ * `bytes` is valid for reads of `len` bytes.
+ */
+struct ExpanderHandle *expander_load_circuit(const uint8_t *bytes, size_t len);
+
+/**
+ * Prove the circuit of the handle on the witness bytes, in the format of the witness files.
+ * On success, `*out_proof` and `*out_len` are the bytes of the proof package, as written by
+ * `expander-exec prove`, to be freed by `expander_free_proof`.
+ *
+ * # Safety
+ * `handle` is null or of `expander_load_circuit`, `witness` is valid for reads of `len` bytes,
+ * and `out_proof` and `out_len` are null or valid for writes.
+ */
+enum ExpanderStatus expander_prove(struct ExpanderHandle *handle,
+                                   const uint8_t *witness,
+                                   size_t len,
+                                   uint8_t **out_proof,
+                                   size_t *out_len);
+
+/**
+ * Verify the proof package bytes for the circuit of the handle on the public inputs, in the
+ * witness format without the private inputs, or on the whole witness. `Ok` for a proof that
+ * verifies, and `Rejected` for one that is read but does not.
+ *
+ * # Safety
+ * `handle` is null or of `expander_load_circuit`, and `public_input` and `proof` are valid for
+ * reads of `public_input_len` and `proof_len` bytes.
+ */
+enum ExpanderStatus expander_verify(struct ExpanderHandle *handle,
+                                    const uint8_t *public_input,
+                                    size_t public_input_len,
+                                    const uint8_t *proof,
+                                    size_t proof_len);
+
+/**
+ * Free the handle of `expander_load_circuit`. A null handle is ignored.
+ *
+ * # Safety
+ * `handle` is null or of `expander_load_circuit`, and not used after.
+ */
+void expander_free(struct ExpanderHandle *handle);
+
+/**
+ * Free the proof of `expander_prove`, of its length. A null proof is ignored.
+ *
+ * # Safety
+ * `proof` and `len` are null or of `expander_prove`, and the proof is not used after.
+ */
+void expander_free_proof(uint8_t *proof, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* EXPANDER_H */
@@ -0,0 +1,249 @@
+//! A C ABI over the prover and the verifier, for the callers in other languages to prove and
+//! verify without a process per proof, see `include/expander.h`.
+//!
+//! A handle owns a circuit, loaded once, and the prover of its config with its scratch pad, so
+//! that the proofs after the first allocate no more than the proof itself. The config is the
+//! one of `expander-exec` for the field of the circuit, e.g., SHA-256 for M31 and MiMC for
+//! BN254, and the scheme the one fit for its gates, see `SchemeSelector`.
+//!
+//! No panic crosses the boundary: the calls that panic return `ExpanderStatus::Panic`, or a
+//! null handle. A handle is used by a single thread at a time.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use circuit::Circuit;
+use config::{
+    BN254ConfigMIMC5, BabyBearExtConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig,
+    GoldilocksExtConfigSha2, M31ExtConfigSha2, MPIConfig,
+};
+use gkr::{
+    detect_field_type,
+    executor::{self, ExpanderError},
+    CompressionCodec, FuzzSafeCircuitLoader, ProofLayout, ProofPackage, Prover, SchemeSelector,
+};
+
+/// The outcome of a call, 0 for success.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpanderStatus {
+    Ok = 0,
+    /// A null pointer for a handle, an output or bytes of a non-zero length.
+    NullArgument = 1,
+    /// The witness, or the public inputs, do not fit the circuit of the handle.
+    InvalidWitness = 2,
+    /// The proof bytes are not a proof package of the field of the circuit.
+    InvalidProof = 3,
+    /// The proof is read, but does not verify.
+    Rejected = 4,
+    /// The proof could not be serialized.
+    Internal = 5,
+    Panic = 6,
+}
+
+/// The circuit and the prover of a handle, of the config of their field.
+trait Session {
+    fn prove(&mut self, witness: &[u8]) -> Result<Vec<u8>, ExpanderStatus>;
+
+    fn verify(&mut self, public_input: &[u8], proof: &[u8]) -> ExpanderStatus;
+}
+
+struct CircuitSession<C: GKRConfig> {
+    config: Config<C>,
+    circuit: Circuit<C>,
+    prover: Prover<C>,
+}
+
+impl<C: GKRConfig> CircuitSession<C> {
+    fn load(bytes: &[u8]) -> Option<Self> {
+        let circuit = FuzzSafeCircuitLoader::<C>::load(bytes).ok()?;
+        circuit.validate().ok()?;
+        let config = Config::<C>::new(
+            SchemeSelector::select(&circuit),
+            MPIConfig::new_for_verifier(1),
+        );
+        executor::check_scheme(&circuit, &config.gkr_scheme).ok()?;
+        let mut prover = Prover::new(&config).ok()?;
+        prover.prepare_mem(&circuit);
+        Some(CircuitSession {
+            config,
+            circuit,
+            prover,
+        })
+    }
+}
+
+impl<C: GKRConfig> Session for CircuitSession<C> {
+    fn prove(&mut self, witness: &[u8]) -> Result<Vec<u8>, ExpanderStatus> {
+        self.circuit
+            .try_load_witness_bytes(witness, false)
+            .map_err(|_| ExpanderStatus::InvalidWitness)?;
+        let (claimed_v, proof) = self.prover.prove_silent(&mut self.circuit);
+        // committing to the public inputs, for `verify` to refuse the proof on others
+        executor::package_proof_with_input_commitment(
+            &self.config,
+            &self.circuit,
+            &proof,
+            &claimed_v,
+            CompressionCodec::None,
+        )
+        .map_err(|_| ExpanderStatus::Internal)
+    }
+
+    fn verify(&mut self, public_input: &[u8], proof: &[u8]) -> ExpanderStatus {
+        let max_proof_size = ProofLayout::new(&self.circuit, &self.config).proof_size();
+        let Ok(package) =
+            ProofPackage::deserialize_with_max_proof_size(&mut &proof[..], max_proof_size)
+        else {
+            return ExpanderStatus::InvalidProof;
+        };
+        if self
+            .circuit
+            .try_load_public_input_bytes(public_input, false)
+            .is_err()
+        {
+            return ExpanderStatus::InvalidWitness;
+        }
+        match executor::try_verify_package_on(&self.config, &mut self.circuit, &package) {
+            Ok(Ok(())) => ExpanderStatus::Ok,
+            Ok(Err(_)) => ExpanderStatus::Rejected,
+            Err(ExpanderError::WitnessLoad(_)) => ExpanderStatus::InvalidWitness,
+            Err(_) => ExpanderStatus::InvalidProof,
+        }
+    }
+}
+
+/// A circuit loaded by `expander_load_circuit`, opaque to the callers.
+pub struct ExpanderHandle {
+    session: Box<dyn Session>,
+}
+
+/// The bytes of the pointer, empty for a zero length whatever the pointer, and none for a null
+/// pointer of a non-zero length.
+///
+/// # Safety
+/// A non-null pointer is valid for reads of `len` bytes.
+unsafe fn bytes_of<'a>(bytes: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if bytes.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(bytes, len))
+    }
+}
+
+fn catch_status(f: impl FnOnce() -> ExpanderStatus) -> ExpanderStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(ExpanderStatus::Panic)
+}
+
+/// Load the circuit of the bytes of a circuit file, of the field of its sentinel. Null for bytes
+/// that are not a valid circuit, to be freed by `expander_free` otherwise.
+///
+/// # Safety
+/// `bytes` is valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn expander_load_circuit(
+    bytes: *const u8,
+    len: usize,
+) -> *mut ExpanderHandle {
+    let Some(bytes) = bytes_of(bytes, len) else {
+        return ptr::null_mut();
+    };
+    let session = panic::catch_unwind(|| -> Option<Box<dyn Session>> {
+        Some(match detect_field_type(bytes).ok()? {
+            FieldType::M31 => Box::new(CircuitSession::<M31ExtConfigSha2>::load(bytes)?),
+            FieldType::BN254 => Box::new(CircuitSession::<BN254ConfigMIMC5>::load(bytes)?),
+            FieldType::GF2 => Box::new(CircuitSession::<GF2ExtConfigSha2>::load(bytes)?),
+            FieldType::Goldilocks => {
+                Box::new(CircuitSession::<GoldilocksExtConfigSha2>::load(bytes)?)
+            }
+            FieldType::BabyBear => Box::new(CircuitSession::<BabyBearExtConfigSha2>::load(bytes)?),
+        })
+    });
+    match session {
+        Ok(Some(session)) => Box::into_raw(Box::new(ExpanderHandle { session })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Prove the circuit of the handle on the witness bytes, in the format of the witness files.
+/// On success, `*out_proof` and `*out_len` are the bytes of the proof package, as written by
+/// `expander-exec prove`, to be freed by `expander_free_proof`.
+///
+/// # Safety
+/// `handle` is null or of `expander_load_circuit`, `witness` is valid for reads of `len` bytes,
+/// and `out_proof` and `out_len` are null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn expander_prove(
+    handle: *mut ExpanderHandle,
+    witness: *const u8,
+    len: usize,
+    out_proof: *mut *mut u8,
+    out_len: *mut usize,
+) -> ExpanderStatus {
+    let (Some(handle), Some(witness)) = (handle.as_mut(), bytes_of(witness, len)) else {
+        return ExpanderStatus::NullArgument;
+    };
+    if out_proof.is_null() || out_len.is_null() {
+        return ExpanderStatus::NullArgument;
+    }
+    catch_status(|| match handle.session.prove(witness) {
+        Ok(proof) => {
+            let proof = proof.into_boxed_slice();
+            *out_len = proof.len();
+            *out_proof = Box::into_raw(proof) as *mut u8;
+            ExpanderStatus::Ok
+        }
+        Err(status) => status,
+    })
+}
+
+/// Verify the proof package bytes for the circuit of the handle on the public inputs, in the
+/// witness format without the private inputs, or on the whole witness. `Ok` for a proof that
+/// verifies, and `Rejected` for one that is read but does not.
+///
+/// # Safety
+/// `handle` is null or of `expander_load_circuit`, and `public_input` and `proof` are valid for
+/// reads of `public_input_len` and `proof_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn expander_verify(
+    handle: *mut ExpanderHandle,
+    public_input: *const u8,
+    public_input_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+) -> ExpanderStatus {
+    let (Some(handle), Some(public_input), Some(proof)) = (
+        handle.as_mut(),
+        bytes_of(public_input, public_input_len),
+        bytes_of(proof, proof_len),
+    ) else {
+        return ExpanderStatus::NullArgument;
+    };
+    catch_status(|| handle.session.verify(public_input, proof))
+}
+
+/// Free the handle of `expander_load_circuit`. A null handle is ignored.
+///
+/// # Safety
+/// `handle` is null or of `expander_load_circuit`, and not used after.
+#[no_mangle]
+pub unsafe extern "C" fn expander_free(handle: *mut ExpanderHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Free the proof of `expander_prove`, of its length. A null proof is ignored.
+///
+/// # Safety
+/// `proof` and `len` are null or of `expander_prove`, and the proof is not used after.
+#[no_mangle]
+pub unsafe extern "C" fn expander_free_proof(proof: *mut u8, len: usize) {
+    if !proof.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(proof, len)));
+    }
+}
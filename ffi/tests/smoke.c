@@ -0,0 +1,76 @@
+/* Prove and verify through the C ABI, see tests/smoke.rs for how it is built and run:
+ *   smoke <circuit_file> <witness_file>
+ */
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "expander.h"
+
+static uint8_t *read_file(const char *path, size_t *len) {
+  FILE *file = fopen(path, "rb");
+  if (file == NULL) {
+    return NULL;
+  }
+  fseek(file, 0, SEEK_END);
+  *len = (size_t)ftell(file);
+  fseek(file, 0, SEEK_SET);
+  uint8_t *bytes = malloc(*len);
+  if (bytes != NULL && fread(bytes, 1, *len, file) != *len) {
+    free(bytes);
+    bytes = NULL;
+  }
+  fclose(file);
+  return bytes;
+}
+
+#define CHECK(condition)                                          \
+  do {                                                            \
+    if (!(condition)) {                                           \
+      fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #condition); \
+      return 1;                                                   \
+    }                                                             \
+  } while (0)
+
+int main(int argc, char **argv) {
+  CHECK(argc == 3);
+  size_t circuit_len, witness_len;
+  uint8_t *circuit = read_file(argv[1], &circuit_len);
+  uint8_t *witness = read_file(argv[2], &witness_len);
+  CHECK(circuit != NULL && witness != NULL);
+
+  CHECK(expander_load_circuit(circuit, 16) == NULL);
+  ExpanderHandle *handle = expander_load_circuit(circuit, circuit_len);
+  CHECK(handle != NULL);
+
+  /* twice, the second reusing the scratch pad of the first */
+  uint8_t *proof = NULL;
+  size_t proof_len = 0;
+  for (int i = 0; i < 2; i++) {
+    expander_free_proof(proof, proof_len);
+    CHECK(expander_prove(handle, witness, witness_len, &proof, &proof_len) ==
+          EXPANDER_STATUS_OK);
+    CHECK(proof != NULL && proof_len > 0);
+  }
+  CHECK(expander_verify(handle, witness, witness_len, proof, proof_len) ==
+        EXPANDER_STATUS_OK);
+
+  CHECK(expander_verify(handle, witness, witness_len, proof, proof_len - 1) ==
+        EXPANDER_STATUS_INVALID_PROOF);
+  proof[proof_len - 1] ^= 1;
+  CHECK(expander_verify(handle, witness, witness_len, proof, proof_len) !=
+        EXPANDER_STATUS_OK);
+  CHECK(expander_prove(handle, witness, witness_len - 1, &proof, &proof_len) ==
+        EXPANDER_STATUS_INVALID_WITNESS);
+  CHECK(expander_prove(NULL, witness, witness_len, &proof, &proof_len) ==
+        EXPANDER_STATUS_NULL_ARGUMENT);
+  CHECK(expander_prove(handle, NULL, witness_len, &proof, &proof_len) ==
+        EXPANDER_STATUS_NULL_ARGUMENT);
+
+  expander_free_proof(proof, proof_len);
+  expander_free(handle);
+  expander_free(NULL);
+  free(circuit);
+  free(witness);
+  printf("success\n");
+  return 0;
+}
@@ -0,0 +1,122 @@
+use std::{path::PathBuf, process::Command, ptr};
+
+use expander_ffi::*;
+use gkr::{
+    utils::{KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS},
+    ProofPackage,
+};
+
+fn data_file(path: &str) -> String {
+    "../".to_owned() + path
+}
+
+#[test]
+fn test_prove_and_verify() {
+    let circuit = std::fs::read(data_file(KECCAK_M31_CIRCUIT)).unwrap();
+    let witness = std::fs::read(data_file(KECCAK_M31_WITNESS)).unwrap();
+    unsafe {
+        assert!(expander_load_circuit(circuit.as_ptr(), 16).is_null());
+        assert!(expander_load_circuit(ptr::null(), circuit.len()).is_null());
+        let handle = expander_load_circuit(circuit.as_ptr(), circuit.len());
+        assert!(!handle.is_null());
+
+        let mut proof = ptr::null_mut();
+        let mut proof_len = 0;
+        let prove = |witness: &[u8], proof: &mut *mut u8, proof_len: &mut usize| {
+            expander_prove(handle, witness.as_ptr(), witness.len(), proof, proof_len)
+        };
+        assert_eq!(
+            prove(&witness, &mut proof, &mut proof_len),
+            ExpanderStatus::Ok
+        );
+        let first = std::slice::from_raw_parts(proof, proof_len).to_vec();
+        expander_free_proof(proof, proof_len);
+        // the package commits to the public inputs it is verified on
+        let package = ProofPackage::deserialize_from(&mut &first[..]).unwrap();
+        assert!(package.public_input_commitment.is_some());
+        // the same proof from the prepared prover
+        assert_eq!(
+            prove(&witness, &mut proof, &mut proof_len),
+            ExpanderStatus::Ok
+        );
+        assert_eq!(std::slice::from_raw_parts(proof, proof_len), &first[..]);
+
+        let verify = |public_input: &[u8], proof: &[u8]| {
+            expander_verify(
+                handle,
+                public_input.as_ptr(),
+                public_input.len(),
+                proof.as_ptr(),
+                proof.len(),
+            )
+        };
+        assert_eq!(verify(&witness, &first), ExpanderStatus::Ok);
+        assert_eq!(
+            verify(&witness, &first[..first.len() - 1]),
+            ExpanderStatus::InvalidProof
+        );
+        assert_eq!(
+            verify(&witness[..witness.len() - 1], &first),
+            ExpanderStatus::InvalidWitness
+        );
+        assert_eq!(
+            prove(&witness[..witness.len() - 1], &mut proof, &mut proof_len),
+            ExpanderStatus::InvalidWitness
+        );
+        assert_eq!(
+            expander_prove(
+                handle,
+                witness.as_ptr(),
+                witness.len(),
+                ptr::null_mut(),
+                &mut proof_len
+            ),
+            ExpanderStatus::NullArgument
+        );
+
+        expander_free_proof(proof, proof_len);
+        expander_free(handle);
+        expander_free(ptr::null_mut());
+    }
+}
+
+/// The C smoke test, compiled against the header and the cdylib of the crate by the C compiler
+/// of the system, `cc`, failing without one: run by the CI, with
+/// `cargo test -p expander-ffi --test smoke -- --ignored`.
+#[test]
+#[ignore]
+fn test_c_smoke() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // target/<profile>/deps/<test> to target/<profile>, where the cdylib is
+    let lib_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+    let binary = lib_dir.join("expander_ffi_smoke");
+    let compiled = Command::new("cc")
+        .arg(manifest_dir.join("tests/smoke.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lexpander_ffi")
+        .arg("-o")
+        .arg(&binary)
+        .status();
+    let status = compiled.unwrap_or_else(|e| panic!("no C compiler: {}", e));
+    assert!(status.success(), "unable to compile tests/smoke.c");
+    let output = Command::new(&binary)
+        .arg(data_file(KECCAK_M31_CIRCUIT))
+        .arg(data_file(KECCAK_M31_WITNESS))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "success\n");
+}
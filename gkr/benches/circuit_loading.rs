@@ -0,0 +1,85 @@
+use std::time::Instant;
+
+use arith::FieldForECC;
+use circuit::Circuit;
+use config::{GKRConfig, M31ExtConfigSha2, SENTINEL_M31};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+type C = M31ExtConfigSha2;
+
+const LOG_NUM_WIRES: usize = 18;
+const DEPTH: usize = 4;
+
+fn put_usizes(bytes: &mut Vec<u8>, values: &[usize]) {
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// `DEPTH` layers over 2^18 wires, each of a single segment of its mul gates, the i-th wire the
+/// product of the i-th and the next one of the layer below.
+fn write_circuit(path: &str) {
+    let num_wires = 1 << LOG_NUM_WIRES;
+    let one = <C as GKRConfig>::CircuitField::from(1u32)
+        .to_u256()
+        .to_le_bytes();
+    let mut bytes = b"CIRCUIT6".to_vec();
+    bytes.extend_from_slice(&SENTINEL_M31);
+    put_usizes(&mut bytes, &[0, num_wires, 0, 1]);
+    put_usizes(&mut bytes, &[num_wires, num_wires, 0, num_wires]);
+    for i in 0..num_wires {
+        put_usizes(&mut bytes, &[i, (i + 1) % num_wires, i]);
+        bytes.push(1);
+        bytes.extend_from_slice(&one);
+    }
+    put_usizes(&mut bytes, &[0, 0, 0]);
+    put_usizes(&mut bytes, &[DEPTH]);
+    put_usizes(&mut bytes, &[0; DEPTH]);
+    std::fs::write(path, bytes).unwrap();
+}
+
+/// The private resident memory of the process, in kB, of `/proc/self/status` where there is one:
+/// the pages of a mapped file are shared with the other processes that map it.
+fn rss_anon_kb() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("RssAnon:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// The time to load the circuit, and the private memory it holds once loaded.
+fn measure<T>(name: &str, load: impl FnOnce() -> T) {
+    let before = rss_anon_kb();
+    let start = Instant::now();
+    let loaded = load();
+    let elapsed = start.elapsed();
+    match (before, rss_anon_kb()) {
+        (Some(before), Some(after)) => eprintln!(
+            "{}: loaded in {:?}, {} kB more private memory",
+            name,
+            elapsed,
+            after.saturating_sub(before)
+        ),
+        _ => eprintln!("{}: loaded in {:?}", name, elapsed),
+    }
+    drop(loaded);
+}
+
+fn criterion_circuit_loading(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("expander_bench_circuit_m31.txt");
+    let path = path.to_str().unwrap();
+    write_circuit(path);
+    measure("mmap", || Circuit::<C>::load_circuit_mmap(path).unwrap());
+    measure("read", || Circuit::<C>::load_circuit(path));
+
+    let mut group = c.benchmark_group(format!("load m31, {} layers of 2^18 gates", DEPTH));
+    group.sample_size(10);
+    group.bench_function("read", |b| b.iter(|| Circuit::<C>::load_circuit(path)));
+    group.bench_function("mmap", |b| {
+        b.iter(|| Circuit::<C>::load_circuit_mmap(path).unwrap())
+    });
+    group.finish();
+    std::fs::remove_file(path).unwrap();
+}
+
+criterion_group!(benches, criterion_circuit_loading);
+criterion_main!(benches);
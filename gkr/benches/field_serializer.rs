@@ -0,0 +1,45 @@
+use arith::{Field, FieldSerde};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gkr::VecFieldSerializer;
+use halo2curves::bn256::Fr;
+use mersenne31::M31;
+use std::hint::black_box;
+
+const WITNESS_SIZE: usize = 1 << 20;
+
+fn bench_field<F: Field + FieldSerde + Sync>(c: &mut Criterion, name: &str) {
+    let mut rng = rand::thread_rng();
+    let witness = (0..WITNESS_SIZE)
+        .map(|_| F::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group(format!("serialize 2^20 {} elements", name));
+    group.throughput(Throughput::Bytes(
+        (WITNESS_SIZE * F::SERIALIZED_SIZE) as u64,
+    ));
+    group.bench_function(BenchmarkId::new("element by element", name), |b| {
+        b.iter(|| {
+            let mut bytes = vec![];
+            for e in &witness {
+                e.serialize_into(&mut bytes).unwrap();
+            }
+            black_box(bytes)
+        })
+    });
+    group.bench_function(BenchmarkId::new("batch", name), |b| {
+        b.iter(|| {
+            let mut bytes = vec![];
+            VecFieldSerializer::serialize_batch(&witness, &mut bytes).unwrap();
+            black_box(bytes)
+        })
+    });
+    group.finish();
+}
+
+fn criterion_field_serializer(c: &mut Criterion) {
+    bench_field::<M31>(c, "M31");
+    bench_field::<Fr>(c, "BN254");
+}
+
+criterion_group!(benches, criterion_field_serializer);
+criterion_main!(benches);
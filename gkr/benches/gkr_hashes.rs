@@ -11,7 +11,7 @@ use gkr::{
 use std::hint::black_box;
 
 fn prover_run<C: GKRConfig>(config: &Config<C>, circuit: &mut Circuit<C>) {
-    let mut prover = Prover::new(config);
+    let mut prover = Prover::new(config).unwrap();
     prover.prepare_mem(circuit);
     prover.prove(circuit);
 }
@@ -0,0 +1,35 @@
+use arith::{Field, FieldSerde};
+use config::{Config, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gkr::MultithreadedHasher;
+use mersenne31::M31Ext3;
+use sha2::{Digest, Sha256};
+use std::hint::black_box;
+
+fn criterion_multithreaded_hasher(c: &mut Criterion) {
+    let config =
+        Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut rng = rand::thread_rng();
+
+    let mut group = c.benchmark_group("hash W M31Ext3 elements");
+    for width in [1024, 4096, 16384] {
+        let elements = (0..width)
+            .map(|_| M31Ext3::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        group.throughput(Throughput::Elements(width as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", width), &elements, |b, e| {
+            b.iter(|| {
+                let mut bytes = vec![];
+                e.iter().for_each(|f| f.serialize_into(&mut bytes).unwrap());
+                black_box(Sha256::digest(bytes))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", width), &elements, |b, e| {
+            b.iter(|| black_box(MultithreadedHasher::hash_parallel(e, &config)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_multithreaded_hasher);
+criterion_main!(benches);
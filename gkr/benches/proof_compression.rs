@@ -0,0 +1,68 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{
+    BN254ConfigSha2, Config, GF2ExtConfigSha2, GKRConfig, GKRScheme, GoldilocksExtConfigSha2,
+    M31ExtConfigSha2, MPIConfig,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use gkr::{CompressionCodec, ProofPackage, Prover};
+use std::hint::black_box;
+
+const WIDTH: usize = 16;
+const DEPTH: usize = 64;
+
+/// `DEPTH` layers over `WIDTH` wires, each wire the product of two wires of the layer below.
+fn circuit<C: GKRConfig>() -> Circuit<C> {
+    let mut builder = GateBuilder::<C>::new(WIDTH);
+    let mut wires = (0..WIDTH).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..DEPTH {
+        builder.next_layer();
+        wires = (0..WIDTH)
+            .map(|i| builder.mul(wires[i], wires[(i + 1) % WIDTH], C::CircuitField::ONE))
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit.layers[0].input_vals = (0..WIDTH as u32)
+        .map(|v| C::circuit_field_to_simd_circuit_field(&C::CircuitField::from(v + 2)))
+        .collect();
+    circuit
+}
+
+fn bench_field<C: GKRConfig>(c: &mut Criterion, name: &str) {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = circuit::<C>();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let package = ProofPackage::new(&config, &proof, &claimed_v).unwrap();
+
+    for codec in [CompressionCodec::None, CompressionCodec::Zstd { level: 3 }] {
+        let package = package.clone().with_compression(codec);
+        let bytes = package.to_bytes();
+        println!(
+            "{}, {:?}: {} bytes, ratio {:.3}",
+            name,
+            codec,
+            bytes.len(),
+            bytes.len() as f64 / proof.bytes.len() as f64
+        );
+
+        c.bench_function(&format!("serialize {}, {:?}", name, codec), |b| {
+            b.iter(|| black_box(black_box(&package).to_bytes()))
+        });
+        c.bench_function(&format!("deserialize {}, {:?}", name, codec), |b| {
+            b.iter(|| black_box(ProofPackage::deserialize_from(&mut black_box(&bytes[..]))))
+        });
+    }
+}
+
+fn criterion_proof_compression(c: &mut Criterion) {
+    bench_field::<M31ExtConfigSha2>(c, "m31");
+    bench_field::<BN254ConfigSha2>(c, "bn254");
+    bench_field::<GF2ExtConfigSha2>(c, "gf2");
+    bench_field::<GoldilocksExtConfigSha2>(c, "goldilocks");
+}
+
+criterion_group!(benches, criterion_proof_compression);
+criterion_main!(benches);
@@ -0,0 +1,92 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig, PolynomialCommitmentType};
+use criterion::{criterion_group, criterion_main, Criterion};
+use gkr::{ProofLayout, ProofSegment, Prover, Verifier};
+use std::hint::black_box;
+
+type C = M31ExtConfigSha2;
+
+const DEPTH: usize = 4;
+
+/// `DEPTH` layers over `1 << log_width` wires, each wire the product of two wires of the layer
+/// below.
+fn circuit(log_width: usize) -> Circuit<C> {
+    let width = 1 << log_width;
+    let mut builder = GateBuilder::<C>::new(width);
+    let mut wires = (0..width).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..DEPTH {
+        builder.next_layer();
+        wires = (0..width)
+            .map(|i| {
+                builder.mul(
+                    wires[i],
+                    wires[(i + 1) % width],
+                    <C as GKRConfig>::CircuitField::ONE,
+                )
+            })
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit.layers[0].input_vals = (0..width as u32)
+        .map(|v| {
+            C::circuit_field_to_simd_circuit_field(&<C as GKRConfig>::CircuitField::from(v + 2))
+        })
+        .collect();
+    circuit
+}
+
+fn criterion_proof_sizes(c: &mut Criterion) {
+    for log_width in [6, 10, 14] {
+        let mut circuit = circuit(log_width);
+        for pcs in [
+            PolynomialCommitmentType::Raw,
+            PolynomialCommitmentType::Orion,
+        ] {
+            let config = Config::<C>::new_with_pcs(
+                GKRScheme::Vanilla,
+                pcs.clone(),
+                MPIConfig::new_for_verifier(1),
+            )
+            .unwrap();
+            let mut prover = Prover::new(&config).unwrap();
+            prover.prepare_mem(&circuit);
+            let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+            let layout = ProofLayout::new(&circuit, &config);
+            assert_eq!(proof.bytes.len(), layout.proof_size());
+            let commitment_bytes: usize = layout
+                .segments
+                .iter()
+                .filter(|s| matches!(s, ProofSegment::Commitment(_) | ProofSegment::Opening(_)))
+                .map(|s| layout.segment_size(s))
+                .sum();
+            println!(
+                "2^{} inputs, {:?}: {} proof bytes, {} of the commitment and its openings",
+                log_width,
+                pcs,
+                proof.bytes.len(),
+                commitment_bytes
+            );
+
+            c.bench_function(&format!("prove 2^{} inputs, {:?}", log_width, pcs), |b| {
+                b.iter(|| black_box(prover.prove_silent(black_box(&mut circuit))))
+            });
+            let verifier = Verifier::new(&config);
+            let public_input = circuit.public_input.clone();
+            c.bench_function(&format!("verify 2^{} inputs, {:?}", log_width, pcs), |b| {
+                b.iter(|| {
+                    black_box(verifier.verify(
+                        black_box(&mut circuit),
+                        &public_input,
+                        &claimed_v,
+                        &proof,
+                    ))
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(benches, criterion_proof_sizes);
+criterion_main!(benches);
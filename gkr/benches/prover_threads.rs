@@ -0,0 +1,53 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use criterion::{criterion_group, criterion_main, Criterion};
+use gkr::Prover;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+const LOG_NUM_WIRES: usize = 20;
+const DEPTH: usize = 4;
+
+/// `DEPTH` layers over 2^20 wires, the i-th wire of each the product of the i-th and the next
+/// one of the layer below.
+fn circuit() -> Circuit<C> {
+    let num_wires = 1 << LOG_NUM_WIRES;
+    let mut builder = GateBuilder::<C>::new(num_wires);
+    let mut wires = (0..num_wires).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..DEPTH {
+        builder.next_layer();
+        wires = (0..num_wires)
+            .map(|i| builder.mul(wires[i], wires[(i + 1) % num_wires], F::ONE))
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+fn criterion_prover_threads(c: &mut Criterion) {
+    let mut circuit = circuit();
+    let mut rng = rand::thread_rng();
+    circuit.layers[0].input_vals = (0..1 << LOG_NUM_WIRES)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    let mut group = c.benchmark_group(format!("prove m31, {} layers of 2^20 wires", DEPTH));
+    group.sample_size(10);
+    for num_threads in [1, n_threads] {
+        let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1))
+            .with_num_threads(num_threads);
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        group.bench_function(format!("{} threads", num_threads), |b| {
+            b.iter(|| prover.prove_silent(&mut circuit))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_prover_threads);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use criterion::{criterion_group, criterion_main, Criterion};
+use gkr::{Prover, SumcheckRoundCompressor};
+use std::hint::black_box;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+const DEPTH: usize = 1000;
+
+/// `DEPTH` identical layers over 4 wires: `(a * b, b, c + d, d)`.
+fn uniform_circuit() -> Circuit<C> {
+    let mut builder = GateBuilder::<C>::new(4);
+    let mut wires = (0..4).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..DEPTH {
+        builder.next_layer();
+        let ab = builder.mul(wires[0], wires[1], F::ONE);
+        let b = builder.relay(wires[1]);
+        let cd = builder.add(wires[2], F::ONE);
+        builder.add_into(cd, wires[3], F::ONE);
+        let d = builder.relay(wires[3]);
+        wires = vec![ab, b, cd, d];
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+fn criterion_round_compressor(c: &mut Criterion) {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+
+    for (name, input) in [("zero", [0u32; 4]), ("nonzero", [3, 5, 7, 11])] {
+        let mut circuit = uniform_circuit();
+        circuit.layers[0].input_vals = input
+            .iter()
+            .map(|v| C::circuit_field_to_simd_circuit_field(&F::from(*v)))
+            .collect();
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (_, proof) = prover.prove_silent(&mut circuit);
+
+        let compressed = SumcheckRoundCompressor::compress(&circuit, &config, &proof);
+        println!(
+            "{} layers, {} input: {} bytes, {} compressed, ratio {:.3}",
+            DEPTH,
+            name,
+            proof.bytes.len(),
+            compressed.size(),
+            compressed.size() as f64 / proof.bytes.len() as f64
+        );
+
+        c.bench_function(&format!("compress {} layers, {} input", DEPTH, name), |b| {
+            b.iter(|| {
+                black_box(SumcheckRoundCompressor::compress(
+                    black_box(&circuit),
+                    &config,
+                    black_box(&proof),
+                ))
+            })
+        });
+    }
+}
+
+criterion_group!(benches, criterion_round_compressor);
+criterion_main!(benches);
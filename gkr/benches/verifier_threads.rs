@@ -0,0 +1,61 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use criterion::{criterion_group, criterion_main, Criterion};
+use gkr::{ProofLayout, Verifier};
+use transcript::Proof;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+const LOG_NUM_WIRES: usize = 14;
+const DEPTH: usize = 2;
+
+/// `DEPTH` layers over 2^14 wires of each process, the i-th wire of each the product of the i-th
+/// and the next one of the layer below.
+fn circuit() -> Circuit<C> {
+    let num_wires = 1 << LOG_NUM_WIRES;
+    let mut builder = GateBuilder::<C>::new(num_wires);
+    let mut wires = (0..num_wires).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..DEPTH {
+        builder.next_layer();
+        wires = (0..num_wires)
+            .map(|i| builder.mul(wires[i], wires[(i + 1) % num_wires], F::ONE))
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit.layers[0].input_vals = vec![Default::default(); num_wires];
+    circuit
+}
+
+/// The proofs of several processes are not made without mpi, so the verifier is given a proof
+/// of zeros, which it checks through as it does a valid one, before rejecting it.
+fn criterion_verifier_threads(c: &mut Criterion) {
+    let mut circuit = circuit();
+    let mut group = c.benchmark_group(format!("verify m31, {} layers of 2^14 wires", DEPTH));
+    group.sample_size(10);
+    for world_size in [1, 8, 32] {
+        for num_threads in [1, 8] {
+            let config =
+                Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(world_size))
+                    .with_num_threads(num_threads);
+            let proof = Proof {
+                bytes: vec![0; ProofLayout::new(&circuit, &config).proof_size()],
+            };
+            let num_claimed = (1 << LOG_NUM_WIRES)
+                * <C as GKRConfig>::get_field_pack_size()
+                * world_size as usize;
+            let claimed_v = vec![<C as GKRConfig>::ChallengeField::ZERO; num_claimed];
+            let verifier = Verifier::new(&config);
+            group.bench_function(
+                format!("world size {}, {} threads", world_size, num_threads),
+                |b| b.iter(|| verifier.verify(&mut circuit, &[], &claimed_v, &proof)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_verifier_threads);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use arith::FieldSerde;
+use circuit::{migrate_v0_to_v1, Circuit, CircuitLayer};
+use config::{FieldType, GKRConfig, M31ExtConfigSha2};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+type C = M31ExtConfigSha2;
+
+const LOG_INPUT_SIZE: usize = 19;
+/// With 16 witnesses of 2^19 private inputs, 10M values in all.
+const NUM_PUBLIC_INPUTS: usize = 10_000_000 / 16 - (1 << LOG_INPUT_SIZE);
+
+fn write_witness(path: &str) {
+    let num_witnesses = C::get_field_pack_size();
+    let num_private = 1 << LOG_INPUT_SIZE;
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    num_private.serialize_into(&mut bytes).unwrap();
+    NUM_PUBLIC_INPUTS.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for i in 0..num_witnesses * (num_private + NUM_PUBLIC_INPUTS) {
+        <C as GKRConfig>::CircuitField::from(i as u32)
+            .serialize_into(&mut bytes)
+            .unwrap();
+    }
+    std::fs::write(path, migrate_v0_to_v1(&bytes, FieldType::M31)).unwrap();
+}
+
+fn criterion_witness_loading(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("expander_bench_witness_m31.txt");
+    let path = path.to_str().unwrap();
+    write_witness(path);
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: LOG_INPUT_SIZE,
+            output_var_num: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let n_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    let mut group = c.benchmark_group("load 10M m31 witness values");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| b.iter(|| circuit.load_witness_file(path)));
+    group.bench_function(format!("parallel, {} threads", n_threads), |b| {
+        b.iter(|| circuit.load_witness_parallel(path, n_threads).unwrap())
+    });
+    group.finish();
+    std::fs::remove_file(path).unwrap();
+}
+
+criterion_group!(benches, criterion_witness_loading);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use std::{path::Path, process::Command};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // the grpc server and client of expander-exec, see grpc.rs
+    if std::env::var_os("CARGO_FEATURE_SERVER").is_some() {
+        tonic_build::compile_protos("proto/expander.proto")?;
+    }
+
+    // the commit of `/info`, of the environment, e.g., for the builds outside of a checkout, or
+    // else of git, if any
+    println!("cargo:rerun-if-env-changed=EXPANDER_GIT_COMMIT");
+    if Path::new("../.git/HEAD").exists() {
+        println!("cargo:rerun-if-changed=../.git/HEAD");
+    }
+    let commit = std::env::var("EXPANDER_GIT_COMMIT").ok().or_else(|| {
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+    if let Some(commit) = commit.filter(|commit| !commit.is_empty()) {
+        println!("cargo:rustc-env=EXPANDER_GIT_COMMIT={}", commit);
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Prove and verify a witness with a running `expander-exec serve --transport grpc`:
+//!
+//! cargo run --example grpc_client -- http://127.0.0.1:3030 ./data/witness_m31.txt
+
+use gkr::proto::{expander_client::ExpanderClient, ProveRequest, ReadyRequest, VerifyRequest};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 3 {
+        println!("Usage: grpc_client <input:server_url> <input:witness_file>");
+        return Ok(());
+    }
+    let witness = std::fs::read(&args[2])?;
+    let mut client = ExpanderClient::connect(args[1].clone()).await?;
+
+    let ready = client.ready(ReadyRequest {}).await?.into_inner();
+    println!(
+        "Ready since {}, {} of {} workers busy, simd backend {}",
+        ready.ready_since, ready.busy_workers, ready.workers, ready.simd_backend
+    );
+
+    let proven = client
+        .prove(ProveRequest {
+            witness: witness.clone(),
+        })
+        .await?
+        .into_inner();
+    println!(
+        "Proof of {} bytes, {} claimed values",
+        proven.proof.len(),
+        proven.claimed_v.len()
+    );
+
+    let verified = client
+        .verify(VerifyRequest {
+            witness: witness.clone(),
+            proof: proven.proof,
+        })
+        .await?
+        .into_inner();
+    if verified.verified {
+        println!("success");
+    } else {
+        println!("failure: {}", verified.error);
+    }
+
+    let witnesses = (0..2).map(move |_| ProveRequest {
+        witness: witness.clone(),
+    });
+    let mut proofs = client
+        .prove_stream(tonic::codegen::tokio_stream::iter(witnesses))
+        .await?
+        .into_inner();
+    while let Some(proven) = proofs.message().await? {
+        println!("Streamed proof of {} bytes", proven.proof.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Metadata attached to the gates of a flattened circuit, e.g., the source location a compiler
+//! front-end emitted a gate from.
+
+use std::collections::HashMap;
+
+use circuit::GateRef;
+
+#[derive(Debug, Clone, Default)]
+pub struct CircuitAnnotationSystem {
+    annotations: HashMap<(GateRef, String), String>,
+}
+
+impl CircuitAnnotationSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the annotation `key` of the gate, replacing any previous value.
+    pub fn annotate(&mut self, gate_ref: GateRef, key: &str, value: &str) {
+        self.annotations
+            .insert((gate_ref, key.to_string()), value.to_string());
+    }
+
+    pub fn get(&self, gate_ref: GateRef, key: &str) -> Option<&str> {
+        self.annotations
+            .get(&(gate_ref, key.to_string()))
+            .map(|v| v.as_str())
+    }
+
+    /// All the annotations of the gate, sorted by key.
+    pub fn annotations_of(&self, gate_ref: GateRef) -> Vec<(&str, &str)> {
+        let mut annotations = self
+            .annotations
+            .iter()
+            .filter(|((gate, _), _)| *gate == gate_ref)
+            .map(|((_, key), value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        annotations.sort();
+        annotations
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+}
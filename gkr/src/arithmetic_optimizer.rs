@@ -0,0 +1,186 @@
+//! Rewriting the field operations of compiled circuits into their cheaper equivalents.
+//!
+//! The circuits have no dedicated squaring or doubling gates, so the rewrites target the
+//! existing gates:
+//! - a squaring `a * a` compiled as the product of two copies of `a`, i.e., a mul gate reading
+//!   two wires written by the same gates from the same inputs, reads one of the copies twice,
+//!   which leaves the other copy to be dropped if unused;
+//! - a doubling `a + a`, i.e., several add gates between the same wires, becomes a single add
+//!   gate with the coefficients summed, and so do repeated squarings of the same wire.
+//!
+//! Only the gates with constant coefficients are merged.
+
+use std::collections::HashMap;
+
+use arith::FieldSerde;
+use circuit::{Circuit, CircuitLayer, CoefType, Gate, GateKind};
+use config::GKRConfig;
+
+use crate::SumcheckDegreeOptimizer;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizationReport {
+    pub squarings_introduced: usize,
+    pub doublings_introduced: usize,
+}
+
+pub struct FieldArithmeticOptimizer;
+
+/// The gates writing a wire, comparable across wires: kind, gate type, sorted inputs, and
+/// coefficient bytes.
+type WireSignature = Vec<(GateKind, usize, Vec<usize>, Vec<u8>)>;
+
+impl FieldArithmeticOptimizer {
+    /// Panics if the evaluations of the optimized circuit do not match the original ones
+    /// on a random input.
+    pub fn optimize<C: GKRConfig>(circuit: &mut Circuit<C>) -> OptimizationReport {
+        let original = circuit.clone();
+
+        let mut report = OptimizationReport::default();
+        for i in 0..circuit.layers.len() {
+            let (prev_layers, layers) = circuit.layers.split_at_mut(i);
+            let layer = &mut layers[0];
+            if let Some(prev_layer) = prev_layers.last() {
+                let signatures = Self::wire_signatures(prev_layer);
+                for g in &mut layer.mul {
+                    let [a, b] = g.i_ids;
+                    if a != b
+                        && signatures
+                            .get(&a)
+                            .is_some_and(|s| Some(s) == signatures.get(&b))
+                    {
+                        g.i_ids = [a.min(b); 2];
+                        report.squarings_introduced += 1;
+                    }
+                }
+            }
+            // merging the repeated squarings does not introduce new ones
+            Self::merge_duplicates(&mut layer.mul);
+            report.doublings_introduced += Self::merge_duplicates(&mut layer.add);
+        }
+
+        if report != OptimizationReport::default() {
+            // the gates have moved, so the pointers to the random coefficients are stale
+            if circuit.rnd_coefs_identified {
+                circuit.identify_rnd_coefs();
+            }
+            assert!(
+                SumcheckDegreeOptimizer::evaluations_match(&original, circuit),
+                "the optimized circuit is not equivalent to the original one"
+            );
+        }
+        report
+    }
+
+    /// The signatures of the output wires of the layer that only depend on the inputs of the
+    /// layer, i.e., whose gates have no random coefficient.
+    fn wire_signatures<C: GKRConfig>(layer: &CircuitLayer<C>) -> HashMap<usize, WireSignature> {
+        let mut signatures = HashMap::<usize, WireSignature>::new();
+        let mut random = vec![];
+        let mut push = |kind,
+                        gate_type,
+                        i_ids: &[usize],
+                        coef_type: &CoefType,
+                        coef: &C::CircuitField,
+                        o_id| {
+            let coef_bytes = match coef_type {
+                CoefType::Constant => {
+                    let mut bytes = vec![0];
+                    coef.serialize_into(&mut bytes).unwrap();
+                    bytes
+                }
+                CoefType::PublicInput(idx) => {
+                    let mut bytes = vec![1];
+                    bytes.extend_from_slice(&idx.to_le_bytes());
+                    bytes
+                }
+                CoefType::Random => {
+                    random.push(o_id);
+                    return;
+                }
+            };
+            let mut i_ids = i_ids.to_vec();
+            i_ids.sort();
+            signatures
+                .entry(o_id)
+                .or_default()
+                .push((kind, gate_type, i_ids, coef_bytes));
+        };
+        for g in &layer.mul {
+            push(
+                GateKind::Mul,
+                g.gate_type,
+                &g.i_ids,
+                g.coef_type,
+                &g.coef,
+                g.o_id,
+            );
+        }
+        for g in &layer.add {
+            push(
+                GateKind::Add,
+                g.gate_type,
+                &g.i_ids,
+                g.coef_type,
+                &g.coef,
+                g.o_id,
+            );
+        }
+        for g in &layer.const_ {
+            push(
+                GateKind::Const,
+                g.gate_type,
+                &g.i_ids,
+                g.coef_type,
+                &g.coef,
+                g.o_id,
+            );
+        }
+        for g in &layer.uni {
+            push(
+                GateKind::Uni,
+                g.gate_type,
+                &g.i_ids,
+                g.coef_type,
+                &g.coef,
+                g.o_id,
+            );
+        }
+
+        for o_id in random {
+            signatures.remove(&o_id);
+        }
+        signatures
+            .values_mut()
+            .for_each(|signature| signature.sort());
+        signatures
+    }
+
+    /// Merge the gates with constant coefficients between the same wires, and return the
+    /// number of removed gates.
+    fn merge_duplicates<C: GKRConfig, const INPUT_NUM: usize>(
+        gates: &mut Vec<Gate<C, INPUT_NUM>>,
+    ) -> usize {
+        let len = gates.len();
+        let mut first = HashMap::<(Vec<usize>, usize, usize), usize>::new();
+        let mut merged: Vec<Gate<C, INPUT_NUM>> = Vec::with_capacity(len);
+        for g in gates.drain(..) {
+            if g.coef_type != CoefType::Constant {
+                merged.push(g);
+                continue;
+            }
+            let mut i_ids = g.i_ids.to_vec();
+            i_ids.sort();
+            let key = (i_ids, g.o_id, g.gate_type);
+            match first.get(&key) {
+                Some(&k) => merged[k].coef += g.coef,
+                None => {
+                    first.insert(key, merged.len());
+                    merged.push(g);
+                }
+            }
+        }
+        *gates = merged;
+        len - gates.len()
+    }
+}
@@ -0,0 +1,157 @@
+//! The throughput of the prover on a circuit and a witness, as reported by
+//! `expander-exec benchmark` and `expander-exec bench`.
+
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+
+/// The latencies of the measured proofs, in milliseconds, and the memory of the process.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchmarkReport {
+    pub iterations: usize,
+    pub prove_min_ms: f64,
+    pub prove_max_ms: f64,
+    pub prove_mean_ms: f64,
+    pub prove_p50_ms: f64,
+    pub prove_p95_ms: f64,
+    pub prove_p99_ms: f64,
+    /// The verifications measured, none for `bench` without `--verify`.
+    pub verify_iterations: usize,
+    pub verify_mean_ms: f64,
+    /// Over the time spent proving, the verifications excluded.
+    pub proofs_per_second: f64,
+    pub proof_bytes: usize,
+    /// The peak resident set size once the witness is loaded, which reads the witness file a
+    /// chunk at a time, see `Circuit::try_load_witness_reader`.
+    pub peak_rss_after_witness_load_bytes: Option<u64>,
+    /// The peak resident set size after `Prover::prepare_mem`, see `peak_rss_bytes`.
+    pub peak_rss_after_prepare_bytes: Option<u64>,
+    pub peak_rss_after_first_prove_bytes: Option<u64>,
+}
+
+impl BenchmarkReport {
+    /// The report of the prove and verify latencies of the measured iterations.
+    ///
+    /// Panics without any prove latency.
+    pub fn new(
+        prove_latencies: &[Duration],
+        verify_latencies: &[Duration],
+        proof_bytes: usize,
+        peak_rss_after_witness_load_bytes: Option<u64>,
+        peak_rss_after_prepare_bytes: Option<u64>,
+        peak_rss_after_first_prove_bytes: Option<u64>,
+    ) -> Self {
+        assert!(!prove_latencies.is_empty(), "no proof to report");
+        let mut sorted = prove_latencies.to_vec();
+        sorted.sort();
+        let total = sorted.iter().sum::<Duration>();
+        Self {
+            iterations: sorted.len(),
+            prove_min_ms: millis(sorted[0]),
+            prove_max_ms: millis(sorted[sorted.len() - 1]),
+            prove_mean_ms: millis(total) / sorted.len() as f64,
+            prove_p50_ms: millis(percentile(&sorted, 50)),
+            prove_p95_ms: millis(percentile(&sorted, 95)),
+            prove_p99_ms: millis(percentile(&sorted, 99)),
+            verify_iterations: verify_latencies.len(),
+            verify_mean_ms: match verify_latencies.len() {
+                0 => 0.0,
+                n => millis(verify_latencies.iter().sum()) / n as f64,
+            },
+            proofs_per_second: sorted.len() as f64 / total.as_secs_f64(),
+            proof_bytes,
+            peak_rss_after_witness_load_bytes,
+            peak_rss_after_prepare_bytes,
+            peak_rss_after_first_prove_bytes,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "iterations: {}", self.iterations)?;
+        writeln!(
+            f,
+            "prove latency (ms): min {:.3}, max {:.3}, mean {:.3}, p50 {:.3}, p95 {:.3}, p99 {:.3}",
+            self.prove_min_ms,
+            self.prove_max_ms,
+            self.prove_mean_ms,
+            self.prove_p50_ms,
+            self.prove_p95_ms,
+            self.prove_p99_ms
+        )?;
+        match self.verify_iterations {
+            0 => writeln!(f, "verify latency (ms): not measured")?,
+            _ => writeln!(f, "verify latency (ms): mean {:.3}", self.verify_mean_ms)?,
+        }
+        writeln!(f, "proofs per second: {:.3}", self.proofs_per_second)?;
+        writeln!(f, "proof size: {} bytes", self.proof_bytes)?;
+        for (name, rss) in [
+            (
+                "after the witness load",
+                self.peak_rss_after_witness_load_bytes,
+            ),
+            ("after prepare_mem", self.peak_rss_after_prepare_bytes),
+            (
+                "after the first proof",
+                self.peak_rss_after_first_prove_bytes,
+            ),
+        ] {
+            match rss {
+                Some(rss) => writeln!(f, "peak RSS {}: {} bytes", name, rss)?,
+                None => writeln!(f, "peak RSS {}: unavailable", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// The nearest-rank percentile of the sorted latencies.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// The peak resident set size of the process so far, `VmHWM` of `/proc/self/status`.
+#[cfg(target_os = "linux")]
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// The peak resident set size of the process so far, from `getrusage`.
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn peak_rss_bytes() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let max_rss = u64::try_from(unsafe { usage.assume_init() }.ru_maxrss).ok()?;
+    // in bytes on macOS, in kilobytes elsewhere
+    if cfg!(target_os = "macos") {
+        Some(max_rss)
+    } else {
+        Some(max_rss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+pub fn peak_rss_bytes() -> Option<u64> {
+    None
+}
@@ -0,0 +1,91 @@
+//! Circuits signed off by a known party.
+//!
+//! The signer signs the verification key of the circuit, which includes the circuit hash, so a
+//! certificate is only valid for the exact circuit and proof system parameters it was issued for.
+//! Whether the signer is trusted is up to the caller.
+
+use std::io::{Read, Write};
+
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use circuit::Circuit;
+use config::GKRConfig;
+use ed25519_dalek::Signer;
+
+use crate::VerificationKey;
+
+pub type Ed25519Signature = ed25519_dalek::Signature;
+pub type Ed25519PublicKey = ed25519_dalek::VerifyingKey;
+pub type Ed25519KeyPair = ed25519_dalek::SigningKey;
+
+/// Prepended to the signed message, so that the signature cannot be replayed in another context.
+const CERTIFICATE_DOMAIN: &[u8] = b"expander circuit certificate";
+
+pub struct CertifiedCircuit<C: GKRConfig> {
+    pub circuit: Circuit<C>,
+    pub vk: VerificationKey<C>,
+    pub certificate: Ed25519Signature,
+    pub signer_pk: Ed25519PublicKey,
+}
+
+impl<C: GKRConfig> CertifiedCircuit<C> {
+    fn message(vk: &VerificationKey<C>) -> Vec<u8> {
+        let mut message = CERTIFICATE_DOMAIN.to_vec();
+        vk.serialize_into(&mut message).unwrap();
+        message
+    }
+
+    /// Panics if the verification key is not the one of the circuit.
+    pub fn sign(circuit: &Circuit<C>, vk: &VerificationKey<C>, keypair: &Ed25519KeyPair) -> Self {
+        assert_eq!(
+            vk.circuit_hash,
+            circuit.hash(),
+            "the verification key is for another circuit"
+        );
+        Self {
+            circuit: circuit.clone(),
+            vk: vk.clone(),
+            certificate: keypair.sign(&Self::message(vk)),
+            signer_pk: keypair.verifying_key(),
+        }
+    }
+
+    /// Whether the certificate is a valid signature of the verification key by the signer,
+    /// and the verification key is the one of the circuit.
+    pub fn verify_certificate(&self) -> bool {
+        self.vk.circuit_hash == self.circuit.hash()
+            && self
+                .signer_pk
+                .verify_strict(&Self::message(&self.vk), &self.certificate)
+                .is_ok()
+    }
+
+    /// Serialize the verification key, the certificate and the signer public key.
+    /// The circuit is distributed separately.
+    pub fn serialize_certificate_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.vk.serialize_into(&mut writer)?;
+        writer.write_all(&self.certificate.to_bytes())?;
+        writer.write_all(self.signer_pk.as_bytes())?;
+        Ok(())
+    }
+
+    /// Pair the circuit with a certificate serialized by `serialize_certificate_into`.
+    /// The certificate is not verified.
+    pub fn deserialize_certificate_from<R: Read>(
+        circuit: Circuit<C>,
+        mut reader: R,
+    ) -> FieldSerdeResult<Self> {
+        let vk = VerificationKey::<C>::deserialize_from(&mut reader)?;
+        let mut certificate = [0u8; 64];
+        reader.read_exact(&mut certificate)?;
+        let mut signer_pk = [0u8; 32];
+        reader.read_exact(&mut signer_pk)?;
+
+        Ok(Self {
+            circuit,
+            vk,
+            certificate: Ed25519Signature::from_bytes(&certificate),
+            signer_pk: Ed25519PublicKey::from_bytes(&signer_pk)
+                .map_err(|_| FieldSerdeError::DeserializeError)?,
+        })
+    }
+}
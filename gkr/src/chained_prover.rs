@@ -0,0 +1,248 @@
+//! Proving two circuits A and B, where the input layer of B starts with the output layer of A.
+//!
+//! B is proven first, on the output of A followed by its own witness. A is then proven with a
+//! transcript that has absorbed the proof of B, so that its challenges are random with respect
+//! to the input of B. The consistency proof is the beginning of the input layer of B, laid out
+//! as the claimed values: the verifier checks it against the claimed values of A, and against
+//! the input values of B, which the raw commitment reveals. With a hiding commitment, it would
+//! be an opening proof for B instead.
+//!
+//! The output of A is computed before its transcript exists, so A cannot use random gate
+//! coefficients.
+
+use circuit::{Circuit, CoefType};
+use config::{Config, FiatShamirHashType, GKRConfig, PolynomialCommitmentType};
+use sha2::{Digest, Sha256};
+use transcript::{
+    BytesHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher, Proof, SHA256hasher,
+    Transcript,
+};
+
+use crate::{gather_claimed_values, verifier::SumcheckMessages, Prover, RawCommitment, Verifier};
+
+#[derive(Debug, Clone)]
+pub struct ChainedProof<C: GKRConfig> {
+    pub claimed_v_a: Vec<C::ChallengeField>,
+    pub proof_a: Proof,
+    pub proof_b: Proof,
+    /// The output of A, as read by B, laid out as by `gather_claimed_values`.
+    pub consistency: Vec<C::ChallengeField>,
+}
+
+pub struct ChainedProver<C: GKRConfig> {
+    circuit_a: Circuit<C>,
+    circuit_b: Circuit<C>,
+    config: Config<C>,
+}
+
+impl<C: GKRConfig> ChainedProver<C> {
+    /// Panics if A uses random coefficients, if the output layer of A does not fit in the input
+    /// layer of B, or for a config other than a single process with the raw commitment.
+    pub fn new(circuit_a: Circuit<C>, circuit_b: Circuit<C>, config: &Config<C>) -> Self {
+        check_chain(&circuit_a, &circuit_b, config);
+        Self {
+            circuit_a,
+            circuit_b,
+            config: config.clone(),
+        }
+    }
+
+    /// Prove A on `witness_a`, and B on the output of A followed by `witness_b`. Returns the
+    /// proofs and the claimed values of B.
+    ///
+    /// Panics unless the witnesses fill the input layers.
+    pub fn prove_chain(
+        &self,
+        witness_a: &[C::SimdCircuitField],
+        witness_b: &[C::SimdCircuitField],
+    ) -> (ChainedProof<C>, Vec<C::ChallengeField>) {
+        let mut circuit_a = self.circuit_a.clone();
+        assert_eq!(
+            witness_a.len(),
+            1 << circuit_a.layers[0].input_var_num,
+            "the witness of A must fill its input layer"
+        );
+        circuit_a.layers[0].input_vals = witness_a.to_vec();
+        circuit_a.evaluate();
+        let output_a = &circuit_a.layers.last().unwrap().output_vals;
+
+        let mut circuit_b = self.circuit_b.clone();
+        assert_eq!(
+            output_a.len() + witness_b.len(),
+            1 << circuit_b.layers[0].input_var_num,
+            "the output of A and the witness of B must fill the input layer of B"
+        );
+        circuit_b.layers[0].input_vals = output_a.iter().chain(witness_b).copied().collect();
+        let mut prover = Prover::new(&self.config).expect("checked by check_chain");
+        prover.prepare_mem(&circuit_b);
+        let (claimed_v_b, proof_b) = prover.prove_silent(&mut circuit_b);
+
+        let mut prover = Prover::new(&self.config).expect("checked by check_chain");
+        prover.prepare_mem(&circuit_a);
+        let (claimed_v_a, proof_a) = match C::FIAT_SHAMIR_HASH {
+            FiatShamirHashType::Keccak256 => prove_seeded(
+                &mut prover,
+                &mut circuit_a,
+                &proof_b,
+                BytesHashTranscript::<C::ChallengeField, Keccak256hasher>::new(),
+            ),
+            FiatShamirHashType::SHA256 => prove_seeded(
+                &mut prover,
+                &mut circuit_a,
+                &proof_b,
+                BytesHashTranscript::<C::ChallengeField, SHA256hasher>::new(),
+            ),
+            FiatShamirHashType::MIMC5 => prove_seeded(
+                &mut prover,
+                &mut circuit_a,
+                &proof_b,
+                FieldHashTranscript::<C::ChallengeField, MIMCHasher<C::ChallengeField>>::new(),
+            ),
+            _ => unreachable!(),
+        };
+
+        // the claimed values of A are the output of A, as read by B
+        let proof = ChainedProof {
+            consistency: claimed_v_a.clone(),
+            claimed_v_a,
+            proof_a,
+            proof_b,
+        };
+        (proof, claimed_v_b)
+    }
+}
+
+pub struct ChainedVerifier<C: GKRConfig> {
+    circuit_a: Circuit<C>,
+    circuit_b: Circuit<C>,
+    config: Config<C>,
+}
+
+impl<C: GKRConfig> ChainedVerifier<C> {
+    /// Panics under the conditions of `ChainedProver::new`.
+    pub fn new(circuit_a: Circuit<C>, circuit_b: Circuit<C>, config: &Config<C>) -> Self {
+        check_chain(&circuit_a, &circuit_b, config);
+        Self {
+            circuit_a,
+            circuit_b,
+            config: config.clone(),
+        }
+    }
+
+    /// Check both proofs, and that the input of B starts with the output of A.
+    pub fn verify_chain(&self, proof: &ChainedProof<C>, claimed_v_b: &[C::ChallengeField]) -> bool {
+        let verifier = Verifier::new(&self.config);
+
+        let mut circuit_b = self.circuit_b.clone();
+        let public_input_b = circuit_b.public_input.clone();
+        if !verifier.verify(&mut circuit_b, &public_input_b, claimed_v_b, &proof.proof_b) {
+            return false;
+        }
+        // the proof of B verifies, so it starts with the input values of B
+        let input_b = RawCommitment::<C>::deserialize_from(
+            proof.proof_b.bytes.as_slice(),
+            1 << self.circuit_b.layers[0].input_var_num,
+        )
+        .expect("the proof of B is verified")
+        .poly_vals;
+
+        let mut circuit_a = self.circuit_a.clone();
+        let public_input_a = circuit_a.public_input.clone();
+        let passed = match C::FIAT_SHAMIR_HASH {
+            FiatShamirHashType::Keccak256 => verify_seeded(
+                &verifier,
+                &mut circuit_a,
+                &public_input_a,
+                proof,
+                BytesHashTranscript::<C::ChallengeField, Keccak256hasher>::new(),
+            ),
+            FiatShamirHashType::SHA256 => verify_seeded(
+                &verifier,
+                &mut circuit_a,
+                &public_input_a,
+                proof,
+                BytesHashTranscript::<C::ChallengeField, SHA256hasher>::new(),
+            ),
+            FiatShamirHashType::MIMC5 => verify_seeded(
+                &verifier,
+                &mut circuit_a,
+                &public_input_a,
+                proof,
+                FieldHashTranscript::<C::ChallengeField, MIMCHasher<C::ChallengeField>>::new(),
+            ),
+            _ => unreachable!(),
+        };
+        if !passed || proof.consistency != proof.claimed_v_a {
+            return false;
+        }
+
+        let output_var_num = self.circuit_a.layers.last().unwrap().output_var_num;
+        proof.consistency
+            == gather_claimed_values::<C>(&self.config.mpi_config, &input_b[..1 << output_var_num])
+    }
+}
+
+fn check_chain<C: GKRConfig>(circuit_a: &Circuit<C>, circuit_b: &Circuit<C>, config: &Config<C>) {
+    assert!(
+        config.polynomial_commitment_type == PolynomialCommitmentType::Raw
+            && config.mpi_config.world_size() == 1,
+        "chains are proven by a single process with the raw commitment"
+    );
+    assert!(
+        circuit_a.layers.iter().all(|layer| {
+            let mul = layer.mul.iter().map(|g| &g.coef_type);
+            let add = layer.add.iter().map(|g| &g.coef_type);
+            let cst = layer.const_.iter().map(|g| &g.coef_type);
+            let uni = layer.uni.iter().map(|g| &g.coef_type);
+            mul.chain(add)
+                .chain(cst)
+                .chain(uni)
+                .all(|coef_type| *coef_type != CoefType::Random)
+        }),
+        "the output of A must not depend on random coefficients"
+    );
+    assert!(
+        circuit_a.layers.last().unwrap().output_var_num <= circuit_b.layers[0].input_var_num,
+        "the output layer of A does not fit in the input layer of B"
+    );
+}
+
+/// Absorb the proof of B into the transcript of A, without adding it to the proof of A.
+fn absorb_proof_b<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    transcript: &mut T,
+    proof_b: &Proof,
+) {
+    transcript.lock_proof();
+    transcript.append_u8_slice(&Sha256::digest(&proof_b.bytes));
+    transcript.unlock_proof();
+}
+
+fn prove_seeded<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    prover: &mut Prover<C>,
+    circuit_a: &mut Circuit<C>,
+    proof_b: &Proof,
+    mut transcript: T,
+) -> (Vec<C::ChallengeField>, Proof) {
+    absorb_proof_b::<C, T>(&mut transcript, proof_b);
+    prover.prove_with_transcript(circuit_a, &mut transcript)
+}
+
+fn verify_seeded<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    verifier: &Verifier<C>,
+    circuit_a: &mut Circuit<C>,
+    public_input_a: &[C::SimdCircuitField],
+    proof: &ChainedProof<C>,
+    mut transcript: T,
+) -> bool {
+    absorb_proof_b::<C, T>(&mut transcript, &proof.proof_b);
+    verifier
+        .verify_with_transcript(
+            circuit_a,
+            public_input_a,
+            &proof.claimed_v_a,
+            &proof.proof_a,
+            &mut transcript,
+            &mut SumcheckMessages::default(),
+        )
+        .passed()
+}
@@ -0,0 +1,285 @@
+//! Proving circuits whose flattened layers do not fit in memory at once.
+//!
+//! The circuit is kept in its compact recursive form, see `RecursiveCircuit`, and flattened
+//! `chunk_size` layers at a time by a `CircuitLayerStream`. A first pass evaluates the chunks
+//! from the input layer up, keeping only the input values of each chunk. The layers are then
+//! proven from the output down, as by `gkr_prove`: each chunk is flattened and evaluated again
+//! from its input values, its layers are proven, and it is dropped before the chunk below.
+//! Besides the recursive circuit, the memory holds the layers of one chunk, and the input
+//! values of the chunks.
+//!
+//! The proof is the same as the proof of `Prover::prove` for the flattened circuit.
+
+use std::{fs, marker::PhantomData};
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CircuitLayerStream, CoefType};
+use config::{Config, FiatShamirHashType, GKRConfig, GKRScheme, PolynomialCommitmentType};
+use sumcheck::{sumcheck_prove_gkr_layer, ProverScratchPad};
+use transcript::{
+    BytesHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher, Proof, SHA256hasher,
+    Transcript,
+};
+
+#[cfg(feature = "grinding")]
+use crate::grind;
+use crate::{
+    absorb_claimed_values, absorb_public_input, absorb_seed_and_domain, gather_claimed_values,
+    gather_public_input, RawCommitment,
+};
+
+pub struct ChunkedMPIProver<C: GKRConfig> {
+    _marker: PhantomData<C>,
+}
+
+/// What the prover keeps of the whole circuit.
+struct ChunkedCircuit<C: GKRConfig> {
+    /// Flattening a chunk at a time.
+    layers: CircuitLayerStream<C>,
+    public_input: Vec<C::SimdCircuitField>,
+    /// The values of the random coefficients of each layer, in the order of
+    /// `CircuitLayer::identify_rnd_coefs`.
+    rnd_coefs: Vec<Vec<C::CircuitField>>,
+    /// The input values of each chunk.
+    chunk_inputs: Vec<Vec<C::SimdCircuitField>>,
+}
+
+impl<C: GKRConfig> ChunkedCircuit<C> {
+    /// The layers of the chunk from `first` as a circuit, evaluated from `input_vals`.
+    fn evaluate_chunk(&self, first: usize, input_vals: Vec<C::SimdCircuitField>) -> Circuit<C> {
+        let last = (first + self.layers.batch_size()).min(self.layers.num_layers());
+        let mut chunk = Circuit::<C> {
+            layers: self
+                .layers
+                .layers(first..last)
+                .into_iter()
+                .zip(&self.rnd_coefs[first..last])
+                .map(|(mut layer, rnd_coefs)| {
+                    set_rnd_coefs(&mut layer, rnd_coefs);
+                    layer.identify_structure_info();
+                    layer
+                })
+                .collect(),
+            public_input: self.public_input.clone(),
+            ..Default::default()
+        };
+        chunk.layers[0].input_vals = input_vals;
+        chunk.evaluate();
+        chunk
+    }
+}
+
+impl<C: GKRConfig> ChunkedMPIProver<C> {
+    /// Prove the circuit of the file on the witness of the file, see `Circuit::load_circuit`
+    /// and `Circuit::load_witness_file`, flattening `chunk_size` layers at a time.
+    /// Only for vanilla GKR with the raw commitment.
+    pub fn prove_chunked(
+        circuit_file: &str,
+        witness_file: &str,
+        chunk_size: usize,
+        config: &Config<C>,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        let layers = Circuit::<C>::load_circuit_streaming(circuit_file, chunk_size).unwrap();
+        let log_input_size = layers.layers(0..1)[0].input_var_num;
+        let (input_vals, public_input) = Circuit::<C>::parse_witness_bytes(
+            log_input_size,
+            &fs::read(witness_file).unwrap(),
+            false,
+        );
+        Self::prove_stream(layers, input_vals, public_input, config)
+    }
+
+    /// Prove the circuit of the stream on the packed inputs, flattening a batch of the stream
+    /// at a time. Only for vanilla GKR with the raw commitment.
+    pub fn prove_stream(
+        layers: CircuitLayerStream<C>,
+        input_vals: Vec<C::SimdCircuitField>,
+        public_input: Vec<C::SimdCircuitField>,
+        config: &Config<C>,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        assert_eq!(config.gkr_scheme, GKRScheme::Vanilla);
+        assert_eq!(
+            config.polynomial_commitment_type,
+            PolynomialCommitmentType::Raw
+        );
+
+        match C::FIAT_SHAMIR_HASH {
+            FiatShamirHashType::Keccak256 => {
+                let mut transcript =
+                    BytesHashTranscript::<C::ChallengeField, Keccak256hasher>::new();
+                Self::prove_internal(layers, input_vals, public_input, config, &mut transcript)
+            }
+            FiatShamirHashType::SHA256 => {
+                let mut transcript = BytesHashTranscript::<C::ChallengeField, SHA256hasher>::new();
+                Self::prove_internal(layers, input_vals, public_input, config, &mut transcript)
+            }
+            FiatShamirHashType::MIMC5 => {
+                let mut transcript =
+                    FieldHashTranscript::<C::ChallengeField, MIMCHasher<C::ChallengeField>>::new();
+                Self::prove_internal(layers, input_vals, public_input, config, &mut transcript)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn prove_internal<T: Transcript<C::ChallengeField>>(
+        layers: CircuitLayerStream<C>,
+        input_vals: Vec<C::SimdCircuitField>,
+        public_input: Vec<C::SimdCircuitField>,
+        config: &Config<C>,
+        transcript: &mut T,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        let mpi_config = &config.mpi_config;
+        let layer_num = layers.num_layers();
+        let chunk_size = layers.batch_size();
+
+        // the sizes of the scratch pad and the number of random coefficients of each layer,
+        // one layer at a time
+        let mut max_num_input_var = 0;
+        let mut max_num_output_var = 0;
+        let mut output_var_num = 0;
+        let mut rnd_coef_nums = vec![];
+        for mut layer in layers.clone() {
+            max_num_input_var = max_num_input_var.max(layer.input_var_num);
+            max_num_output_var = max_num_output_var.max(layer.output_var_num);
+            output_var_num = layer.output_var_num;
+            let mut rnd_coefs = vec![];
+            layer.identify_rnd_coefs(&mut rnd_coefs);
+            rnd_coef_nums.push(rnd_coefs.len());
+        }
+        let mut sp = ProverScratchPad::<C>::new(
+            max_num_input_var,
+            max_num_output_var,
+            mpi_config.world_size(),
+        );
+
+        absorb_seed_and_domain::<C, T>(transcript, config);
+
+        // PC commit
+        let commitment = RawCommitment::<C>::mpi_new(&input_vals, mpi_config);
+        let mut buffer = vec![];
+        commitment.serialize_into(&mut buffer).unwrap();
+        transcript.append_u8_slice(&buffer);
+        let gathered = gather_public_input::<C>(mpi_config, &public_input);
+        absorb_public_input::<C, T>(transcript, &gathered, mpi_config.world_size());
+
+        mpi_config.transcript_sync_up(transcript);
+
+        #[cfg(feature = "grinding")]
+        grind::<C, T>(transcript, config);
+
+        let rnd_coefs = draw_rnd_coefs::<C, T>(&rnd_coef_nums, transcript);
+
+        // evaluate, keeping the input values of each chunk
+        let mut circuit = ChunkedCircuit {
+            layers,
+            public_input,
+            rnd_coefs,
+            chunk_inputs: vec![],
+        };
+        let mut vals = input_vals;
+        for first in (0..layer_num).step_by(chunk_size) {
+            circuit.chunk_inputs.push(vals.clone());
+            let mut chunk = circuit.evaluate_chunk(first, vals);
+            vals = std::mem::take(&mut chunk.layers.last_mut().unwrap().output_vals);
+        }
+        let claimed_v = gather_claimed_values::<C>(mpi_config, &vals);
+        drop(vals);
+        absorb_claimed_values::<C, T>(transcript, &claimed_v);
+        mpi_config.transcript_sync_up(transcript);
+
+        // as in gkr_prove
+        let mut rz0 = vec![];
+        let mut rz1 = None;
+        let mut r_simd = vec![];
+        let mut r_mpi = vec![];
+        for _ in 0..output_var_num {
+            rz0.push(transcript.generate_challenge_field_element());
+        }
+        for _ in 0..C::get_field_pack_size().trailing_zeros() {
+            r_simd.push(transcript.generate_challenge_field_element());
+        }
+        for _ in 0..mpi_config.world_size().trailing_zeros() {
+            r_mpi.push(transcript.generate_challenge_field_element());
+        }
+
+        let mut alpha = None;
+        let chunk_num = circuit.chunk_inputs.len();
+        for c in (0..chunk_num).rev() {
+            let first = c * chunk_size;
+            let input_vals = std::mem::take(&mut circuit.chunk_inputs[c]);
+            let chunk = circuit.evaluate_chunk(first, input_vals);
+            for (j, layer) in chunk.layers.iter().enumerate().rev() {
+                (rz0, rz1, r_simd, r_mpi) = sumcheck_prove_gkr_layer(
+                    layer,
+                    &rz0,
+                    &rz1,
+                    &r_simd,
+                    &r_mpi,
+                    alpha,
+                    transcript,
+                    &mut sp,
+                    mpi_config,
+                    first + j == layer_num - 1,
+                );
+
+                if rz1.is_some() {
+                    let mut tmp = transcript.generate_challenge_field_element();
+                    mpi_config.root_broadcast(&mut tmp);
+                    alpha = Some(tmp)
+                } else {
+                    alpha = None;
+                }
+            }
+        }
+
+        (claimed_v, transcript.finalize_and_get_proof())
+    }
+}
+
+/// Draw the random coefficients of all the layers from the transcript, exactly as
+/// `Circuit::fill_rnd_coefs` would for the flattened circuit.
+pub(crate) fn draw_rnd_coefs<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    rnd_coef_nums: &[usize],
+    transcript: &mut T,
+) -> Vec<Vec<C::CircuitField>> {
+    let total = rnd_coef_nums.iter().sum();
+    let mut holder = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            const_: vec![
+                circuit::Gate {
+                    i_ids: [],
+                    o_id: 0,
+                    coef_type: CoefType::Random,
+                    coef: C::CircuitField::ZERO,
+                    gate_type: 0,
+                };
+                total
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    holder.identify_rnd_coefs();
+    holder.fill_rnd_coefs(transcript);
+
+    let mut values = holder.layers[0].const_.iter().map(|g| g.coef);
+    rnd_coef_nums
+        .iter()
+        .map(|&n| values.by_ref().take(n).collect())
+        .collect()
+}
+
+/// Set the random coefficients of the layer, in the order of `identify_rnd_coefs`.
+pub(crate) fn set_rnd_coefs<C: GKRConfig>(layer: &mut CircuitLayer<C>, values: &[C::CircuitField]) {
+    let mul = layer.mul.iter_mut().map(|g| (&g.coef_type, &mut g.coef));
+    let add = layer.add.iter_mut().map(|g| (&g.coef_type, &mut g.coef));
+    let cst = layer.const_.iter_mut().map(|g| (&g.coef_type, &mut g.coef));
+    let uni = layer.uni.iter_mut().map(|g| (&g.coef_type, &mut g.coef));
+    mul.chain(add)
+        .chain(cst)
+        .chain(uni)
+        .filter(|(coef_type, _)| **coef_type == CoefType::Random)
+        .zip(values)
+        .for_each(|((_, coef), value)| *coef = *value);
+}
@@ -0,0 +1,107 @@
+//! `gkr_circuit!`, declaring small layered circuits, e.g., for tests.
+//!
+//! ```ignore
+//! let circuit: Circuit<M31ExtConfigSha2> = gkr_circuit! {
+//!     inputs: [a, b, c];
+//!     layer 0 { x = a + b; y = b * c };
+//!     layer 1 { out = x * y };
+//!     output: [out]
+//! };
+//! ```
+//!
+//! Each statement defines a new wire of the layer as a sum of wires and products of two wires
+//! of the layer below, all with coefficient one. It expands to `GateBuilder` calls, the wires
+//! being `WireRef` variables: using an undeclared wire is a compile error, and so is a layer
+//! numbered out of order. Using a wire of another layer than the one below panics when the
+//! circuit is built. The output wires have to be wires of the last layer, which holds the wires
+//! in the order of the statements.
+
+use arith::Field;
+use circuit::{GateBuilder, WireRef};
+use config::GKRConfig;
+
+#[doc(hidden)]
+pub fn __new_builder<C: GKRConfig>(input_size: usize) -> GateBuilder<C> {
+    GateBuilder::new(input_size)
+}
+
+#[doc(hidden)]
+pub fn __mul<C: GKRConfig>(builder: &mut GateBuilder<C>, a: WireRef, b: WireRef) -> WireRef {
+    builder.mul(a, b, C::CircuitField::ONE)
+}
+
+#[doc(hidden)]
+pub fn __add_into<C: GKRConfig>(builder: &mut GateBuilder<C>, output: WireRef, a: WireRef) {
+    builder.add_into(output, a, C::CircuitField::ONE)
+}
+
+#[doc(hidden)]
+pub fn __mul_into<C: GKRConfig>(
+    builder: &mut GateBuilder<C>,
+    output: WireRef,
+    a: WireRef,
+    b: WireRef,
+) {
+    builder.mul_into(output, a, b, C::CircuitField::ONE)
+}
+
+#[doc(hidden)]
+pub const fn __check_layer_numbers(layers: &[usize]) {
+    let mut i = 0;
+    while i < layers.len() {
+        assert!(
+            layers[i] == i,
+            "the layers must be numbered 0, 1, 2, ... in order"
+        );
+        i += 1;
+    }
+}
+
+#[macro_export]
+macro_rules! gkr_circuit {
+    (
+        inputs: [$($input:ident),+ $(,)?];
+        $(
+            layer $layer:literal {
+                $($out:ident = $t0:ident $(* $t0b:ident)? $(+ $t:ident $(* $tb:ident)?)*);+ $(;)?
+            } $(;)?
+        )+
+        output: [$($output:ident),+ $(,)?] $(;)?
+    ) => {{
+        const _: () = $crate::circuit_dsl::__check_layer_numbers(&[$($layer),+]);
+
+        let mut builder = $crate::circuit_dsl::__new_builder([$(stringify!($input)),+].len());
+        let [$($input),+] = ::std::array::from_fn(|i| builder.input(i));
+        $(
+            builder.next_layer();
+            $(
+                let $out = $crate::gkr_circuit!(@new builder, $t0 $(, $t0b)?);
+                $($crate::gkr_circuit!(@into builder, $out, $t $(, $tb)?);)*
+            )+
+        )+
+
+        let last_layer = [$($layer),+].len();
+        $(
+            assert_eq!(
+                $output.layer,
+                last_layer,
+                "output {} is not a wire of the last layer",
+                stringify!($output)
+            );
+        )+
+        builder.build()
+    }};
+
+    (@new $builder:ident, $a:ident) => {
+        $builder.relay($a)
+    };
+    (@new $builder:ident, $a:ident, $b:ident) => {
+        $crate::circuit_dsl::__mul(&mut $builder, $a, $b)
+    };
+    (@into $builder:ident, $out:ident, $a:ident) => {
+        $crate::circuit_dsl::__add_into(&mut $builder, $out, $a)
+    };
+    (@into $builder:ident, $out:ident, $a:ident, $b:ident) => {
+        $crate::circuit_dsl::__mul_into(&mut $builder, $out, $a, $b)
+    };
+}
@@ -0,0 +1,431 @@
+//! The circuit and witness files decoded for reading, to compare what a frontend writes with
+//! what the parser reads.
+//!
+//! On malformed circuit bytes, the bytes the parser read before its error are the offset of the
+//! error.
+
+use std::{cell::Cell, fmt, io::Read};
+
+use arith::{Field, FieldSerde, SimdField};
+use circuit::{
+    witness_payload, Circuit, CircuitError, CoefType, Gate, RecursiveCircuit, WitnessError,
+    WITNESS_HEADER_SIZE,
+};
+use config::{sentinel_hex, GKRConfig, SENTINEL_OFFSET, SENTINEL_SIZE};
+use serde::Serialize;
+use thiserror::Error;
+use transcript::encode_hex;
+
+/// The number of witnesses, of private and of public inputs, and the modulus, after the header.
+const WITNESS_PAYLOAD_HEADER_SIZE: usize = 3 * 8 + 32;
+
+#[derive(Debug, Error)]
+pub enum DumpError {
+    #[error("failed to parse the circuit at byte {offset}: {message}")]
+    CircuitParse { offset: usize, message: String },
+
+    #[error("failed to parse the witness at byte {offset}: {message}")]
+    WitnessParse { offset: usize, message: String },
+
+    #[error("layer {layer} out of the {num_layers} layers")]
+    LayerOutOfRange { layer: usize, num_layers: usize },
+
+    #[error(transparent)]
+    Circuit(#[from] CircuitError),
+}
+
+/// Which gates and values are dumped, all of them by default.
+#[derive(Debug, Clone, Default)]
+pub struct DumpOptions {
+    /// Only this layer of the circuit, 0 being the input layer.
+    pub layer: Option<usize>,
+    /// At most this many gates of each layer, and values of each witness.
+    pub limit: Option<usize>,
+}
+
+impl DumpOptions {
+    fn take<T>(&self, items: impl Iterator<Item = T>) -> Vec<T> {
+        items.take(self.limit.unwrap_or(usize::MAX)).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CircuitDump {
+    /// The `Field::NAME` of the circuit field.
+    pub field: String,
+    pub sentinel: String,
+    pub num_public_inputs: usize,
+    pub num_outputs: usize,
+    pub expected_num_output_zeros: usize,
+    /// From the input layer to the output layer.
+    pub layers: Vec<LayerDump>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerDump {
+    pub index: usize,
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    /// All the gates of the layer, of which `gates` may be the first ones only.
+    pub num_gates: usize,
+    /// The mul gates, then the add, the const and the uni ones.
+    pub gates: Vec<GateDump>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateDump {
+    /// `mul`, `add`, `const` or `uni`.
+    pub kind: &'static str,
+    pub inputs: Vec<usize>,
+    pub output: usize,
+    /// The hex of the canonical bytes of the constant coefficient, `random` or `public_input`.
+    pub coef: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_input: Option<usize>,
+    /// The custom gate type of the uni gates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gate_type: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WitnessDump {
+    pub field: String,
+    pub num_witnesses: usize,
+    pub num_private_inputs_per_witness: usize,
+    pub num_public_inputs_per_witness: usize,
+    pub witnesses: Vec<WitnessValuesDump>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WitnessValuesDump {
+    pub index: usize,
+    pub private_inputs: Vec<ValueDump>,
+    pub public_inputs: Vec<ValueDump>,
+    /// The outputs of the circuit evaluated on the witness, the random coefficients being zero.
+    /// None for the witnesses past the simd lanes, which are dropped by the prover.
+    pub outputs: Option<Vec<ValueDump>>,
+}
+
+/// A value and its index among the inputs or the outputs, the value in the hex of its canonical
+/// bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueDump {
+    pub index: usize,
+    pub value: String,
+}
+
+fn field_hex<F: FieldSerde>(value: &F) -> String {
+    let mut bytes = vec![];
+    value.serialize_into(&mut bytes).unwrap();
+    encode_hex(&bytes)
+}
+
+impl GateDump {
+    fn new<C: GKRConfig, const INPUT_NUM: usize>(
+        kind: &'static str,
+        gate: &Gate<C, INPUT_NUM>,
+    ) -> Self {
+        let (coef, public_input) = match gate.coef_type {
+            CoefType::Constant => (field_hex(&gate.coef), None),
+            CoefType::Random => ("random".to_string(), None),
+            CoefType::PublicInput(index) => ("public_input".to_string(), Some(index)),
+        };
+        Self {
+            kind,
+            inputs: gate.i_ids.to_vec(),
+            output: gate.o_id,
+            coef,
+            public_input,
+            gate_type: (kind == "uni").then_some(gate.gate_type),
+        }
+    }
+}
+
+/// Counts the bytes read, in a cell left to read after a panic.
+struct OffsetReader<'a, R> {
+    inner: R,
+    offset: &'a Cell<usize>,
+}
+
+impl<R: Read> Read for OffsetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset.set(self.offset.get() + n);
+        Ok(n)
+    }
+}
+
+/// The circuit of the bytes, with its number of public inputs and of outputs.
+fn parse_circuit<C: GKRConfig>(bytes: &[u8]) -> Result<(Circuit<C>, usize, usize), DumpError> {
+    RecursiveCircuit::<C>::check_field(bytes)?;
+    let offset = Cell::new(0);
+    let reader = OffsetReader {
+        inner: bytes,
+        offset: &offset,
+    };
+    let parse = || -> Result<_, CircuitError> {
+        let rc = RecursiveCircuit::<C>::load_from_reader(reader)?;
+        Ok((rc.try_flatten()?, rc.num_public_inputs, rc.num_outputs))
+    };
+    parse().map_err(|e| DumpError::CircuitParse {
+        offset: offset.get(),
+        message: e.to_string(),
+    })
+}
+
+/// The header and the gates of the circuit bytes.
+pub fn dump_circuit<C: GKRConfig>(
+    bytes: &[u8],
+    options: &DumpOptions,
+) -> Result<CircuitDump, DumpError> {
+    let (circuit, num_public_inputs, num_outputs) = parse_circuit::<C>(bytes)?;
+    let layers = match options.layer {
+        Some(layer) if layer >= circuit.layers.len() => {
+            return Err(DumpError::LayerOutOfRange {
+                layer,
+                num_layers: circuit.layers.len(),
+            })
+        }
+        Some(layer) => layer..layer + 1,
+        None => 0..circuit.layers.len(),
+    };
+    let layers = layers
+        .map(|index| {
+            let layer = &circuit.layers[index];
+            let gates = layer
+                .mul
+                .iter()
+                .map(|gate| GateDump::new("mul", gate))
+                .chain(layer.add.iter().map(|gate| GateDump::new("add", gate)))
+                .chain(layer.const_.iter().map(|gate| GateDump::new("const", gate)))
+                .chain(layer.uni.iter().map(|gate| GateDump::new("uni", gate)));
+            LayerDump {
+                index,
+                input_var_num: layer.input_var_num,
+                output_var_num: layer.output_var_num,
+                num_gates: layer.mul.len() + layer.add.len() + layer.const_.len() + layer.uni.len(),
+                gates: options.take(gates),
+            }
+        })
+        .collect();
+    Ok(CircuitDump {
+        field: C::CircuitField::NAME.to_string(),
+        sentinel: sentinel_hex(
+            bytes[SENTINEL_OFFSET..SENTINEL_OFFSET + SENTINEL_SIZE]
+                .try_into()
+                .unwrap(),
+        ),
+        num_public_inputs,
+        num_outputs,
+        expected_num_output_zeros: circuit.expected_num_output_zeros,
+        layers,
+    })
+}
+
+/// The values of the witness bytes, header included, and the outputs of the circuit on them.
+pub fn dump_witness<C: GKRConfig>(
+    circuit_bytes: &[u8],
+    witness_bytes: &[u8],
+    options: &DumpOptions,
+) -> Result<WitnessDump, DumpError> {
+    let (mut circuit, _, _) = parse_circuit::<C>(circuit_bytes)?;
+    let payload =
+        witness_payload::<C::CircuitField>(witness_bytes).map_err(|e| DumpError::WitnessParse {
+            offset: 0,
+            message: e.to_string(),
+        })?;
+    if payload.len() < WITNESS_PAYLOAD_HEADER_SIZE {
+        return Err(DumpError::WitnessParse {
+            offset: witness_bytes.len(),
+            message: "truncated witness header".to_string(),
+        });
+    }
+    let header: Vec<usize> = payload[..3 * 8]
+        .chunks(8)
+        .map(|chunk| usize::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    let (num_witnesses, num_private_inputs, num_public_inputs) = (header[0], header[1], header[2]);
+
+    // without trusting the header for any allocation
+    let value_size = C::CircuitField::SERIALIZED_SIZE;
+    let values_offset = WITNESS_HEADER_SIZE + WITNESS_PAYLOAD_HEADER_SIZE;
+    let available = (witness_bytes.len() - values_offset) / value_size;
+    let num_values = num_private_inputs
+        .checked_add(num_public_inputs)
+        .and_then(|num_inputs| num_inputs.checked_mul(num_witnesses));
+    if num_values.map_or(true, |num_values| num_values > available) {
+        return Err(DumpError::WitnessParse {
+            offset: values_offset + available * value_size,
+            message: format!(
+                "the header tells of {} witnesses of {} private and {} public inputs, the file \
+                 ends after {} values",
+                num_witnesses, num_private_inputs, num_public_inputs, available
+            ),
+        });
+    }
+    let num_values = num_values.unwrap();
+    let values_end = values_offset + num_values * value_size;
+    if values_end != witness_bytes.len() {
+        return Err(DumpError::WitnessParse {
+            offset: values_end,
+            message: format!(
+                "{} bytes after the last value",
+                witness_bytes.len() - values_end
+            ),
+        });
+    }
+    let values = (0..num_values)
+        .map(|i| {
+            let offset = values_offset + i * value_size;
+            C::CircuitField::deserialize_from(&witness_bytes[offset..offset + value_size]).map_err(
+                |e| DumpError::WitnessParse {
+                    offset,
+                    message: e.to_string(),
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let outputs = evaluate_outputs(
+        &mut circuit,
+        &values,
+        num_witnesses,
+        num_private_inputs,
+        num_public_inputs,
+    )?;
+    let value_dumps = |values: &[C::CircuitField]| {
+        options.take(values.iter().enumerate().map(|(index, value)| ValueDump {
+            index,
+            value: field_hex(value),
+        }))
+    };
+    let witnesses = values
+        .chunks(num_private_inputs + num_public_inputs)
+        .take(num_witnesses)
+        .enumerate()
+        .map(|(index, values)| WitnessValuesDump {
+            index,
+            private_inputs: value_dumps(&values[..num_private_inputs]),
+            public_inputs: value_dumps(&values[num_private_inputs..]),
+            outputs: outputs.get(index).map(|outputs| value_dumps(outputs)),
+        })
+        .collect();
+    Ok(WitnessDump {
+        field: C::CircuitField::NAME.to_string(),
+        num_witnesses,
+        num_private_inputs_per_witness: num_private_inputs,
+        num_public_inputs_per_witness: num_public_inputs,
+        witnesses,
+    })
+}
+
+/// The outputs of each witness of a simd lane, the circuit being evaluated on the lanes as
+/// loaded for a proof, the missing lanes padded with the first witness.
+fn evaluate_outputs<C: GKRConfig>(
+    circuit: &mut Circuit<C>,
+    values: &[C::CircuitField],
+    num_witnesses: usize,
+    num_private_inputs: usize,
+    num_public_inputs: usize,
+) -> Result<Vec<Vec<C::CircuitField>>, DumpError> {
+    if circuit.layers.is_empty() {
+        return Err(CircuitError::NoLayer.into());
+    }
+    if num_private_inputs != 1 << circuit.log_input_size() {
+        return Err(CircuitError::from(WitnessError::PrivateInputMismatch {
+            expected: 1 << circuit.log_input_size(),
+            got: num_private_inputs,
+        })
+        .into());
+    }
+    if num_witnesses == 0 {
+        return Ok(vec![]);
+    }
+    let total_size = num_private_inputs + num_public_inputs;
+    let lanes = C::get_field_pack_size().min(num_witnesses);
+    let pack = |offset: usize| {
+        let mut lane_values = (0..lanes)
+            .map(|j| values[j * total_size + offset])
+            .collect::<Vec<_>>();
+        lane_values.resize(C::get_field_pack_size(), lane_values[0]);
+        C::SimdCircuitField::pack(&lane_values)
+    };
+    circuit.layers[0].input_vals = (0..num_private_inputs).map(pack).collect();
+    circuit.public_input = (num_private_inputs..total_size).map(pack).collect();
+    circuit.validate()?;
+
+    circuit.evaluate();
+    let outputs = &circuit.layers.last().unwrap().output_vals;
+    let outputs = outputs.iter().map(|v| v.unpack()).collect::<Vec<_>>();
+    Ok((0..lanes)
+        .map(|j| outputs.iter().map(|lanes| lanes[j]).collect())
+        .collect())
+}
+
+impl fmt::Display for GateDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(gate_type) = self.gate_type {
+            write!(f, " {}", gate_type)?;
+        }
+        write!(f, " {:?} -> {}, ", self.inputs, self.output)?;
+        match self.public_input {
+            Some(index) => write!(f, "public input {}", index),
+            None => write!(f, "coef {}", self.coef),
+        }
+    }
+}
+
+impl fmt::Display for CircuitDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "field: {}, sentinel {}", self.field, self.sentinel)?;
+        write!(
+            f,
+            "public inputs: {}, outputs: {}, expected zeros: {}",
+            self.num_public_inputs, self.num_outputs, self.expected_num_output_zeros
+        )?;
+        for layer in &self.layers {
+            write!(
+                f,
+                "\nlayer {}: {} -> {} vars, {} gates",
+                layer.index, layer.input_var_num, layer.output_var_num, layer.num_gates
+            )?;
+            for gate in &layer.gates {
+                write!(f, "\n  {}", gate)?;
+            }
+            if layer.gates.len() < layer.num_gates {
+                write!(f, "\n  ... {} more", layer.num_gates - layer.gates.len())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WitnessDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field: {}, {} witnesses of {} private and {} public inputs",
+            self.field,
+            self.num_witnesses,
+            self.num_private_inputs_per_witness,
+            self.num_public_inputs_per_witness
+        )?;
+        let values = |f: &mut fmt::Formatter<'_>, name: &str, values: &[ValueDump]| {
+            for value in values {
+                write!(f, "\n  {}[{}] = {}", name, value.index, value.value)?;
+            }
+            Ok(())
+        };
+        for witness in &self.witnesses {
+            write!(f, "\nwitness {}:", witness.index)?;
+            values(f, "private", &witness.private_inputs)?;
+            values(f, "public", &witness.public_inputs)?;
+            match &witness.outputs {
+                Some(outputs) => values(f, "output", outputs)?,
+                None => write!(f, "\n  not evaluated, past the simd lanes")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+//! A registry of known circuits, identified by their hash, see `Circuit::hash`.
+//!
+//! The index is stored as a JSON object from the hex encoded hash to the metadata:
+//! `{ "<hash>": { "name": .., "version": .., "description": .., "field_type": ..,
+//! "input_size": .., "output_size": .., "expected_depth": .. } }`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+
+use circuit::Circuit;
+use config::GKRConfig;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexError {
+    #[error("io error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    #[error("json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("invalid circuit hash: {0}")]
+    InvalidHash(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitMetadata {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub field_type: String,
+    /// Number of private inputs of the circuit, i.e., the size of its input layer.
+    pub input_size: usize,
+    /// Size of the output layer of the circuit.
+    pub output_size: usize,
+    /// The optimal multiplicative depth for what the circuit computes, if known.
+    /// See `CircuitComplexityLowerBound`.
+    #[serde(default)]
+    pub expected_depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitLibraryIndex {
+    circuits: HashMap<[u8; 32], CircuitMetadata>,
+}
+
+impl CircuitLibraryIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &str) -> Result<Self, IndexError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), IndexError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, IndexError> {
+        let entries: BTreeMap<String, CircuitMetadata> = serde_json::from_str(json)?;
+        let circuits = entries
+            .into_iter()
+            .map(|(hash, metadata)| Ok((decode_hash(&hash)?, metadata)))
+            .collect::<Result<_, IndexError>>()?;
+        Ok(Self { circuits })
+    }
+
+    /// The circuits are sorted by hash, so that the file does not change between saves.
+    pub fn to_json(&self) -> Result<String, IndexError> {
+        let entries = self
+            .circuits
+            .iter()
+            .map(|(hash, metadata)| (encode_hash(hash), metadata))
+            .collect::<BTreeMap<_, _>>();
+        Ok(serde_json::to_string_pretty(&entries)?)
+    }
+
+    pub fn lookup(&self, hash: &[u8; 32]) -> Option<&CircuitMetadata> {
+        self.circuits.get(hash)
+    }
+
+    pub fn lookup_circuit<C: GKRConfig>(&self, circuit: &Circuit<C>) -> Option<&CircuitMetadata> {
+        self.lookup(&circuit.hash())
+    }
+
+    /// Register the circuit, replacing any previous entry for it, and return its hash.
+    pub fn register<C: GKRConfig>(
+        &mut self,
+        circuit: &Circuit<C>,
+        name: &str,
+        version: &str,
+    ) -> [u8; 32] {
+        let hash = circuit.hash();
+        let metadata = CircuitMetadata {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            field_type: format!("{:?}", C::FIELD_TYPE),
+            input_size: 1 << circuit.layers.first().unwrap().input_var_num,
+            output_size: 1 << circuit.layers.last().unwrap().output_var_num,
+            expected_depth: None,
+        };
+        self.circuits.insert(hash, metadata);
+        hash
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.circuits.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.circuits.is_empty()
+    }
+}
+
+pub(crate) fn encode_hash(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub(crate) fn decode_hash(hex: &str) -> Result<[u8; 32], IndexError> {
+    let invalid = || IndexError::InvalidHash(hex.to_string());
+
+    if hex.len() != 64 {
+        return Err(invalid());
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2).ok_or_else(invalid)?, 16)
+            .map_err(|_| invalid())?;
+    }
+    Ok(hash)
+}
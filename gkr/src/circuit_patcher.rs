@@ -0,0 +1,168 @@
+//! Delta patches between circuits, to deploy a recompiled circuit without shipping it whole.
+//!
+//! The patch is a binary diff of the canonical encodings of the circuits, see
+//! `Circuit::canonical_bytes`: a sequence of copies of ranges of the old encoding and of
+//! inserted bytes. Copies are found by indexing the old encoding by blocks, then extending the
+//! matches byte by byte, as in rsync. As the gates are sorted in the encoding, a changed layer
+//! mostly leaves the encodings of the other layers untouched.
+
+use std::collections::HashMap;
+
+use circuit::Circuit;
+use config::GKRConfig;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Size of the blocks of the old encoding that copies are searched from.
+const BLOCK_SIZE: usize = 32;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PatchError {
+    #[error("the patch does not apply to this circuit")]
+    BaseMismatch,
+
+    #[error("the patch copies {len} bytes at offset {offset}, out of the base circuit")]
+    InvalidCopy { offset: usize, len: usize },
+
+    #[error("the patched bytes do not encode a circuit")]
+    InvalidCircuit,
+
+    #[error("the patched circuit is not the target circuit")]
+    TargetMismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatchOp {
+    /// Copy `len` bytes of the old encoding, from `offset`.
+    Copy {
+        offset: usize,
+        len: usize,
+    },
+    Insert(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitPatch {
+    /// `Circuit::hash` of the circuit the patch applies to.
+    pub base_hash: [u8; 32],
+    /// `Circuit::hash` of the patched circuit.
+    pub target_hash: [u8; 32],
+    pub ops: Vec<PatchOp>,
+}
+
+impl CircuitPatch {
+    /// Number of bytes inserted by the patch, i.e., not copied from the old circuit.
+    pub fn inserted_len(&self) -> usize {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                PatchOp::Insert(bytes) => bytes.len(),
+                PatchOp::Copy { .. } => 0,
+            })
+            .sum()
+    }
+}
+
+pub struct CircuitBinaryPatcher;
+
+impl CircuitBinaryPatcher {
+    pub fn generate_patch<C: GKRConfig>(old: &Circuit<C>, new: &Circuit<C>) -> CircuitPatch {
+        let old_bytes = old.canonical_bytes();
+        let new_bytes = new.canonical_bytes();
+
+        let mut blocks = HashMap::new();
+        for (i, block) in old_bytes.chunks_exact(BLOCK_SIZE).enumerate() {
+            blocks.entry(block).or_insert(i * BLOCK_SIZE);
+        }
+
+        let mut ops = vec![];
+        let mut pending = vec![];
+        let mut i = 0;
+        while i < new_bytes.len() {
+            let found = new_bytes
+                .get(i..i + BLOCK_SIZE)
+                .and_then(|block| blocks.get(block));
+            let Some(&offset) = found else {
+                pending.push(new_bytes[i]);
+                i += 1;
+                continue;
+            };
+
+            // extend the match backwards over the pending bytes, then forwards
+            let mut start = offset;
+            while start > 0 && pending.last() == Some(&old_bytes[start - 1]) {
+                pending.pop();
+                start -= 1;
+            }
+            let mut len = offset - start + BLOCK_SIZE;
+            i += BLOCK_SIZE;
+            while i < new_bytes.len()
+                && start + len < old_bytes.len()
+                && new_bytes[i] == old_bytes[start + len]
+            {
+                len += 1;
+                i += 1;
+            }
+
+            if !pending.is_empty() {
+                ops.push(PatchOp::Insert(std::mem::take(&mut pending)));
+            }
+            ops.push(PatchOp::Copy { offset: start, len });
+        }
+        if !pending.is_empty() {
+            ops.push(PatchOp::Insert(pending));
+        }
+
+        CircuitPatch {
+            base_hash: old.hash(),
+            target_hash: new.hash(),
+            ops,
+        }
+    }
+
+    /// Patch the layers of the circuit in place. On error, the circuit is left unchanged.
+    ///
+    /// The gates of the patched circuit come in the canonical order, and the random
+    /// coefficients have to be filled again.
+    pub fn apply_patch<C: GKRConfig>(
+        circuit: &mut Circuit<C>,
+        patch: &CircuitPatch,
+    ) -> Result<(), PatchError> {
+        let old_bytes = circuit.canonical_bytes();
+        if circuit.hash() != patch.base_hash {
+            return Err(PatchError::BaseMismatch);
+        }
+
+        let mut new_bytes = vec![];
+        for op in &patch.ops {
+            match op {
+                PatchOp::Copy { offset, len } => {
+                    let range = old_bytes.get(*offset..offset.saturating_add(*len)).ok_or(
+                        PatchError::InvalidCopy {
+                            offset: *offset,
+                            len: *len,
+                        },
+                    )?;
+                    new_bytes.extend_from_slice(range);
+                }
+                PatchOp::Insert(bytes) => new_bytes.extend_from_slice(bytes),
+            }
+        }
+
+        let patched = Circuit::<C>::from_canonical_bytes(&new_bytes)
+            .map_err(|_| PatchError::InvalidCircuit)?;
+        if patched.hash() != patch.target_hash {
+            return Err(PatchError::TargetMismatch);
+        }
+
+        circuit.layers = patched.layers;
+        circuit
+            .layers
+            .iter_mut()
+            .for_each(|layer| layer.identify_structure_info());
+        if circuit.rnd_coefs_identified {
+            circuit.identify_rnd_coefs();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,598 @@
+//! Client SDK for the HTTP API exposed by `expander-exec serve`.
+//!
+//! The client speaks a minimal subset of HTTP/1.1 directly over a `TcpStream`, without an http
+//! client dependency, reqwest being only a dev-dependency of the tests of the server: one request
+//! per connection, with `Connection: close` and a `Content-Length` framed body. A response body
+//! is framed by its `Content-Length`, by its chunks with `Transfer-Encoding: chunked`, or else
+//! by the end of the connection, and is refused past `ProverClient::max_response_bytes`.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("io error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    #[error("invalid server address: {0}")]
+    InvalidAddress(String),
+
+    #[error("malformed http response from {0}")]
+    MalformedResponse(String),
+
+    #[error("server {server} responded with status {status}")]
+    BadStatus { server: String, status: u16 },
+
+    #[error("the response of {server} is over the {max} bytes of the client")]
+    ResponseTooLarge { server: String, max: usize },
+
+    #[error("no server available, last error: {0}")]
+    NoServerAvailable(Box<ClientError>),
+
+    #[error("batch request failed: {0}")]
+    BatchFailed(String),
+
+    #[error("witness {index} of the batch failed: {message}")]
+    BatchItemFailed { index: usize, message: String },
+}
+
+/// Connection and read/write timeout of a single request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Timeout for health checks, which should not wait for a busy prover.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The largest response body a client reads unless given another limit, see
+/// `ProverClient::with_max_response_bytes`, e.g., of a batch of large proofs.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 1 << 30;
+
+/// The largest status line and headers of a response, as for the trailers of a chunked one.
+const MAX_RESPONSE_HEADER_BYTES: usize = 1 << 16;
+
+/// The blobs concatenated, each prefixed by its length as a little-endian u64. This is the
+/// body of the `/prove_batch` requests, and the framing of their responses.
+pub fn encode_length_prefixed(blobs: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(blobs.iter().map(|blob| 8 + blob.len()).sum());
+    for blob in blobs {
+        bytes.extend_from_slice(&(blob.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(blob);
+    }
+    bytes
+}
+
+/// The blobs of `encode_length_prefixed`, or `None` if the bytes are truncated.
+pub fn decode_length_prefixed(mut bytes: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let mut blobs = vec![];
+    while !bytes.is_empty() {
+        let len = u64::from_le_bytes(bytes.get(..8)?.try_into().unwrap()) as usize;
+        let blob = bytes[8..].get(..len)?;
+        blobs.push(blob.to_vec());
+        bytes = &bytes[8 + len..];
+    }
+    Some(blobs)
+}
+
+/// The body of a `/prove_batch` response: for each witness, in order, 0 followed by its proof,
+/// or 1 followed by the description of the failure to prove it, as `encode_length_prefixed`
+/// blobs.
+pub fn encode_batch_results(results: &[Result<Vec<u8>, String>]) -> Vec<u8> {
+    let items = results
+        .iter()
+        .map(|result| match result {
+            Ok(proof) => [&[0u8][..], proof].concat(),
+            Err(message) => [&[1u8][..], message.as_bytes()].concat(),
+        })
+        .collect::<Vec<_>>();
+    encode_length_prefixed(&items)
+}
+
+/// The results of `encode_batch_results`, or `None` if the bytes are malformed.
+pub fn decode_batch_results(bytes: &[u8]) -> Option<Vec<Result<Vec<u8>, String>>> {
+    decode_length_prefixed(bytes)?
+        .into_iter()
+        .map(|item| match item.split_first()? {
+            (0, proof) => Some(Ok(proof.to_vec())),
+            (1, message) => Some(Err(String::from_utf8_lossy(message).to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Client of a single `expander-exec serve` instance.
+#[derive(Debug, Clone)]
+pub struct ProverClient {
+    /// Server address as `host:port`, an optional `http://` prefix is stripped.
+    pub server: String,
+
+    /// The largest response body read, a larger one being a `ResponseTooLarge`.
+    pub max_response_bytes: usize,
+}
+
+impl ProverClient {
+    pub fn new(server: &str) -> Self {
+        Self {
+            server: server
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .to_string(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+
+    /// The same client, reading response bodies of at most `max_response_bytes` bytes.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Ping the `/ready` endpoint.
+    pub fn ready(&self) -> Result<String, ClientError> {
+        let body = self.request("GET", "/ready", &[], HEALTH_CHECK_TIMEOUT)?;
+        Ok(String::from_utf8_lossy(&body).to_string())
+    }
+
+    /// Post the witness bytes to `/prove`, and return the serialized proof.
+    pub fn prove(&self, witness: &[u8]) -> Result<Vec<u8>, ClientError> {
+        self.request("POST", "/prove", witness, REQUEST_TIMEOUT)
+    }
+
+    /// Post the witnesses to `/prove_batch`, see `encode_length_prefixed`, and return the
+    /// serialized proofs, in the same order. A witness the server fails to prove, e.g., of
+    /// another field, is a `BatchItemFailed` of its own, the others being proved regardless.
+    pub fn prove_batch(
+        &self,
+        witnesses: &[Vec<u8>],
+    ) -> Result<Vec<Result<Vec<u8>, ClientError>>, ClientError> {
+        let body = encode_length_prefixed(witnesses);
+        let response = self.request("POST", "/prove_batch", &body, REQUEST_TIMEOUT)?;
+        let results = decode_batch_results(&response)
+            .ok_or_else(|| ClientError::MalformedResponse(self.server.clone()))?;
+        if results.len() != witnesses.len() {
+            return Err(ClientError::MalformedResponse(self.server.clone()));
+        }
+        Ok(results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| {
+                result.map_err(|message| ClientError::BatchItemFailed { index, message })
+            })
+            .collect())
+    }
+
+    /// Post the public inputs and the proof to `/verify`, using the same framing as the server:
+    /// two little-endian u64 lengths followed by the public input and the proof bytes. The
+    /// public inputs are of the witness format, see `Circuit::public_input_bytes`, or the whole
+    /// witness. Public inputs or a proof the server cannot read are a `BadStatus`, a 400 or a
+    /// 422.
+    pub fn verify(&self, witness: &[u8], proof: &[u8]) -> Result<bool, ClientError> {
+        let mut body = Vec::with_capacity(16 + witness.len() + proof.len());
+        body.extend_from_slice(&(witness.len() as u64).to_le_bytes());
+        body.extend_from_slice(&(proof.len() as u64).to_le_bytes());
+        body.extend_from_slice(witness);
+        body.extend_from_slice(proof);
+
+        let response = self.request("POST", "/verify", &body, REQUEST_TIMEOUT)?;
+        serde_json::from_slice::<serde_json::Value>(&response)
+            .ok()
+            .and_then(|response| response["ok"].as_bool())
+            .ok_or_else(|| ClientError::MalformedResponse(self.server.clone()))
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<Vec<u8>, ClientError> {
+        let addr = self
+            .server
+            .to_socket_addrs()
+            .map_err(|_| ClientError::InvalidAddress(self.server.clone()))?
+            .next()
+            .ok_or_else(|| ClientError::InvalidAddress(self.server.clone()))?;
+
+        let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        let header = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            method,
+            path,
+            self.server,
+            body.len()
+        );
+        stream.write_all(header.as_bytes())?;
+        stream.write_all(body)?;
+        stream.flush()?;
+
+        self.read_response(&mut BufReader::new(stream))
+    }
+
+    /// The body of a 2xx response, or a `BadStatus` without reading the body of another.
+    fn read_response(&self, reader: &mut impl BufRead) -> Result<Vec<u8>, ClientError> {
+        let malformed = || ClientError::MalformedResponse(self.server.clone());
+        let too_large = || ClientError::ResponseTooLarge {
+            server: self.server.clone(),
+            max: self.max_response_bytes,
+        };
+
+        let lines = self.read_header_lines(reader)?;
+        let status = lines
+            .first()
+            .and_then(|status_line| status_line.split_whitespace().nth(1))
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(malformed)?;
+        if !(200..300).contains(&status) {
+            return Err(ClientError::BadStatus {
+                server: self.server.clone(),
+                status,
+            });
+        }
+
+        let header = |name: &str| {
+            lines
+                .iter()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim())
+        };
+        let chunked = header("transfer-encoding").is_some_and(|encodings| {
+            encodings
+                .split(',')
+                .any(|encoding| encoding.trim().eq_ignore_ascii_case("chunked"))
+        });
+        let content_length = header("content-length")
+            .map(|len| len.parse::<usize>().map_err(|_| malformed()))
+            .transpose()?;
+
+        let mut body = vec![];
+        if chunked {
+            self.read_chunks(reader, &mut body)?;
+        } else if let Some(len) = content_length {
+            if len > self.max_response_bytes {
+                return Err(too_large());
+            }
+            reader.by_ref().take(len as u64).read_to_end(&mut body)?;
+            if body.len() < len {
+                return Err(malformed());
+            }
+        } else {
+            // framed by the end of the connection
+            reader
+                .by_ref()
+                .take(self.max_response_bytes as u64 + 1)
+                .read_to_end(&mut body)?;
+            if body.len() > self.max_response_bytes {
+                return Err(too_large());
+            }
+        }
+        Ok(body)
+    }
+
+    /// The chunks of a chunked body, each after its size in hex, up to the last chunk, of size
+    /// 0, and the trailers after it.
+    fn read_chunks(
+        &self,
+        reader: &mut impl BufRead,
+        body: &mut Vec<u8>,
+    ) -> Result<(), ClientError> {
+        let malformed = || ClientError::MalformedResponse(self.server.clone());
+        loop {
+            let line = read_line(reader, MAX_RESPONSE_HEADER_BYTES)?.ok_or_else(malformed)?;
+            // the size, without the chunk extensions
+            let size = std::str::from_utf8(&line)
+                .ok()
+                .and_then(|line| line.split(';').next())
+                .and_then(|size| usize::from_str_radix(size.trim(), 16).ok())
+                .ok_or_else(malformed)?;
+            if size == 0 {
+                break;
+            }
+            if size > self.max_response_bytes - body.len() {
+                return Err(ClientError::ResponseTooLarge {
+                    server: self.server.clone(),
+                    max: self.max_response_bytes,
+                });
+            }
+            let start = body.len();
+            reader.by_ref().take(size as u64).read_to_end(body)?;
+            let mut crlf = [0u8; 2];
+            if body.len() - start < size
+                || reader.read_exact(&mut crlf).is_err()
+                || crlf != *b"\r\n"
+            {
+                return Err(malformed());
+            }
+        }
+        self.read_header_lines(reader)?;
+        Ok(())
+    }
+
+    /// The lines up to an empty one, without their line endings, of the status line and the
+    /// headers of a response, or of the trailers of a chunked body.
+    fn read_header_lines(&self, reader: &mut impl BufRead) -> Result<Vec<String>, ClientError> {
+        let malformed = || ClientError::MalformedResponse(self.server.clone());
+        let mut lines = vec![];
+        let mut remaining = MAX_RESPONSE_HEADER_BYTES;
+        loop {
+            let line = read_line(reader, remaining)?.ok_or_else(malformed)?;
+            remaining -= line.len();
+            let line = String::from_utf8(line).map_err(|_| malformed())?;
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                return Ok(lines);
+            }
+            lines.push(line.to_string());
+        }
+    }
+}
+
+/// The next line, with its line feed, or `None` at the end of the stream or past `max` bytes.
+fn read_line(reader: &mut impl BufRead, max: usize) -> std::io::Result<Option<Vec<u8>>> {
+    let mut line = vec![];
+    reader
+        .by_ref()
+        .take(max as u64)
+        .read_until(b'\n', &mut line)?;
+    Ok(line.ends_with(b"\n").then_some(line))
+}
+
+/// Initial back off of a server after a failure, doubled on every consecutive failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound of the back off.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default)]
+struct ServerHealth {
+    consecutive_failures: u32,
+    retry_after: Option<Instant>,
+}
+
+/// Client distributing requests round-robin over several `expander-exec serve` instances
+/// that serve the same circuit.
+/// A failed server is skipped for an exponentially growing back off period,
+/// and the request is retried on the next server.
+#[derive(Debug)]
+pub struct ProverClusterClient {
+    clients: Vec<ProverClient>,
+    next: AtomicUsize,
+    health: Mutex<Vec<ServerHealth>>,
+}
+
+impl ProverClusterClient {
+    pub fn new(servers: Vec<String>) -> Self {
+        assert!(!servers.is_empty(), "at least one server is required");
+        let clients = servers
+            .iter()
+            .map(|s| ProverClient::new(s))
+            .collect::<Vec<_>>();
+        let health = Mutex::new(vec![ServerHealth::default(); clients.len()]);
+        Self {
+            clients,
+            next: AtomicUsize::new(0),
+            health,
+        }
+    }
+
+    /// Ping the `/ready` endpoint of all servers concurrently.
+    /// A successful ping also clears the back off of the server.
+    pub fn health_check_all(&self) -> Vec<(String, bool)> {
+        let results = thread::scope(|s| {
+            self.clients
+                .iter()
+                .map(|client| s.spawn(move || client.ready().is_ok()))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(false))
+                .collect::<Vec<_>>()
+        });
+
+        results
+            .iter()
+            .enumerate()
+            .for_each(|(i, &ok)| self.report(i, ok));
+
+        self.clients
+            .iter()
+            .zip(results)
+            .map(|(client, ok)| (client.server.clone(), ok))
+            .collect()
+    }
+
+    pub fn prove(&self, witness: &[u8]) -> Result<Vec<u8>, ClientError> {
+        self.with_retry(|client| client.prove(witness))
+    }
+
+    pub fn verify(&self, witness: &[u8], proof: &[u8]) -> Result<bool, ClientError> {
+        self.with_retry(|client| client.verify(witness, proof))
+    }
+
+    /// Try the request on each server at most once, starting from the next server in the
+    /// round-robin order that is not backing off.
+    fn with_retry<T>(
+        &self,
+        f: impl Fn(&ProverClient) -> Result<T, ClientError>,
+    ) -> Result<T, ClientError> {
+        let mut last_error = None;
+        for _ in 0..self.clients.len() {
+            let i = self.pick_server();
+            match f(&self.clients[i]) {
+                Ok(res) => {
+                    self.report(i, true);
+                    return Ok(res);
+                }
+                // the server is reachable but rejected the request, retrying won't help
+                Err(e @ ClientError::BadStatus { .. }) => {
+                    self.report(i, true);
+                    return Err(e);
+                }
+                Err(e) => {
+                    log::warn!("request to {} failed: {}", self.clients[i].server, e);
+                    self.report(i, false);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(ClientError::NoServerAvailable(Box::new(
+            last_error.unwrap(),
+        )))
+    }
+
+    /// Next server in round-robin order that is not backing off.
+    /// If all servers are backing off, the one that becomes available first is picked.
+    fn pick_server(&self) -> usize {
+        let n = self.clients.len();
+        let health = self.health.lock().unwrap();
+        let now = Instant::now();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        (0..n)
+            .map(|offset| (start + offset) % n)
+            .find(|&i| health[i].retry_after.map_or(true, |t| t <= now))
+            .unwrap_or_else(|| {
+                (0..n)
+                    .min_by_key(|&i| health[i].retry_after.unwrap_or(now))
+                    .unwrap()
+            })
+    }
+
+    fn report(&self, i: usize, ok: bool) {
+        let mut health = self.health.lock().unwrap();
+        let server = &mut health[i];
+        if ok {
+            *server = ServerHealth::default();
+        } else {
+            server.consecutive_failures += 1;
+            let backoff = INITIAL_BACKOFF
+                .saturating_mul(1u32 << (server.consecutive_failures - 1).min(16))
+                .min(MAX_BACKOFF);
+            server.retry_after = Some(Instant::now() + backoff);
+        }
+    }
+}
+
+type ProofSender = mpsc::Sender<Result<Vec<u8>, ClientError>>;
+
+#[derive(Default)]
+struct BatchQueue {
+    witnesses: Vec<Vec<u8>>,
+    senders: Vec<ProofSender>,
+    /// Arrival time of the oldest pending request.
+    opened_at: Option<Instant>,
+    closed: bool,
+}
+
+/// Groups the prove requests of concurrent callers into `/prove_batch` calls.
+/// A batch is sent once it holds `max_batch_size` witnesses, or `max_latency_ms` milliseconds
+/// after its first witness arrived, whichever comes first.
+pub struct TransactionBatcher {
+    queue: Arc<(Mutex<BatchQueue>, Condvar)>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl TransactionBatcher {
+    pub fn new(client: ProverClient, max_batch_size: usize, max_latency_ms: u64) -> Self {
+        assert!(max_batch_size > 0, "the batch size must be positive");
+        let queue = Arc::new((Mutex::new(BatchQueue::default()), Condvar::new()));
+        let max_latency = Duration::from_millis(max_latency_ms);
+
+        let worker_queue = queue.clone();
+        let worker = thread::spawn(move || {
+            while let Some((witnesses, senders)) =
+                Self::next_batch(&worker_queue, max_batch_size, max_latency)
+            {
+                match client.prove_batch(&witnesses) {
+                    Ok(proofs) => senders.into_iter().zip(proofs).for_each(|(sender, proof)| {
+                        let _ = sender.send(proof);
+                    }),
+                    Err(e) => {
+                        log::warn!("batch of {} witnesses failed: {}", witnesses.len(), e);
+                        senders.into_iter().for_each(|sender| {
+                            let _ = sender.send(Err(ClientError::BatchFailed(e.to_string())));
+                        })
+                    }
+                }
+            }
+        });
+
+        Self {
+            queue,
+            worker: Some(worker),
+        }
+    }
+
+    /// Queue the witness, and block until the proof of its batch is back.
+    pub fn prove(&self, witness: &[u8]) -> Result<Vec<u8>, ClientError> {
+        let (sender, receiver) = mpsc::channel();
+        {
+            let (queue, condvar) = &*self.queue;
+            let mut queue = queue.lock().unwrap();
+            queue.witnesses.push(witness.to_vec());
+            queue.senders.push(sender);
+            queue.opened_at.get_or_insert_with(Instant::now);
+            condvar.notify_one();
+        }
+        receiver.recv().unwrap()
+    }
+
+    /// Wait for the next batch to be due, or `None` once the batcher is dropped and drained.
+    fn next_batch(
+        queue: &(Mutex<BatchQueue>, Condvar),
+        max_batch_size: usize,
+        max_latency: Duration,
+    ) -> Option<(Vec<Vec<u8>>, Vec<ProofSender>)> {
+        let (queue, condvar) = queue;
+        let mut queue = queue.lock().unwrap();
+        loop {
+            let due = match queue.opened_at {
+                None if queue.closed => return None,
+                None => None,
+                Some(_) if queue.closed || queue.witnesses.len() >= max_batch_size => {
+                    Some(Duration::ZERO)
+                }
+                Some(opened_at) => Some(max_latency.saturating_sub(opened_at.elapsed())),
+            };
+            queue = match due {
+                None => condvar.wait(queue).unwrap(),
+                Some(Duration::ZERO) => break,
+                Some(timeout) => condvar.wait_timeout(queue, timeout).unwrap().0,
+            };
+        }
+
+        let n = queue.witnesses.len().min(max_batch_size);
+        let witnesses = queue.witnesses.drain(..n).collect();
+        let senders = queue.senders.drain(..n).collect();
+        queue.opened_at = if queue.witnesses.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+        Some((witnesses, senders))
+    }
+}
+
+impl Drop for TransactionBatcher {
+    /// The pending requests are still sent before the worker exits.
+    fn drop(&mut self) {
+        let (queue, condvar) = &*self.queue;
+        queue.lock().unwrap().closed = true;
+        condvar.notify_one();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Comparing the multiplicative depth of a circuit against a declared lower bound.
+//!
+//! The depth of a wire is the number of multiplications on its longest path from the input layer:
+//! add and identity gates keep the depth of their input, mul gates add one to the deepest of their
+//! inputs, pow5 gates add three (two squarings and a multiplication), and constants are of depth
+//! zero. Circuits compiled without care for their critical path end up deeper than needed, which
+//! costs layers, and so proving time. The lower bound for a circuit is declared in its entry of the
+//! `CircuitLibraryIndex`, as `expected_depth`.
+
+use circuit::{Circuit, CircuitLayer};
+use config::GKRConfig;
+
+use crate::CircuitLibraryIndex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexityCheck {
+    /// No lower bound is declared for the circuit.
+    Undeclared { actual_depth: usize },
+    Optimal {
+        actual_depth: usize,
+        lower_bound: usize,
+    },
+    Suboptimal {
+        actual_depth: usize,
+        lower_bound: usize,
+    },
+}
+
+impl ComplexityCheck {
+    #[inline]
+    pub fn is_suboptimal(&self) -> bool {
+        matches!(self, ComplexityCheck::Suboptimal { .. })
+    }
+}
+
+pub struct CircuitComplexityLowerBound;
+
+impl CircuitComplexityLowerBound {
+    /// Check the depth of the circuit against the expected depth, and log a warning if it is
+    /// above.
+    pub fn check<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        expected_depth: Option<usize>,
+    ) -> ComplexityCheck {
+        let actual_depth = Self::multiplicative_depth(circuit);
+        match expected_depth {
+            None => ComplexityCheck::Undeclared { actual_depth },
+            Some(lower_bound) if actual_depth > lower_bound => {
+                log::warn!(
+                    "circuit of multiplicative depth {}, while {} is achievable",
+                    actual_depth,
+                    lower_bound
+                );
+                ComplexityCheck::Suboptimal {
+                    actual_depth,
+                    lower_bound,
+                }
+            }
+            Some(lower_bound) => ComplexityCheck::Optimal {
+                actual_depth,
+                lower_bound,
+            },
+        }
+    }
+
+    /// Check the circuit against the expected depth of its entry in the index.
+    pub fn check_indexed<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        index: &CircuitLibraryIndex,
+    ) -> ComplexityCheck {
+        let expected_depth = index
+            .lookup_circuit(circuit)
+            .and_then(|metadata| metadata.expected_depth);
+        Self::check(circuit, expected_depth)
+    }
+
+    /// The maximum multiplicative depth of the output wires.
+    pub fn multiplicative_depth<C: GKRConfig>(circuit: &Circuit<C>) -> usize {
+        let input_size = circuit
+            .layers
+            .first()
+            .map_or(0, |layer| 1 << layer.input_var_num);
+        circuit
+            .layers
+            .iter()
+            .fold(vec![0; input_size], |depths, layer| {
+                Self::layer_depths(layer, &depths)
+            })
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The depths of the output wires of the layer, from the depths of its input wires.
+    fn layer_depths<C: GKRConfig>(layer: &CircuitLayer<C>, input_depths: &[usize]) -> Vec<usize> {
+        let mut depths = vec![0; 1 << layer.output_var_num];
+        for gate in &layer.mul {
+            let d = input_depths[gate.i_ids[0]].max(input_depths[gate.i_ids[1]]) + 1;
+            depths[gate.o_id] = depths[gate.o_id].max(d);
+        }
+        for gate in &layer.add {
+            depths[gate.o_id] = depths[gate.o_id].max(input_depths[gate.i_ids[0]]);
+        }
+        for gate in &layer.uni {
+            let d = match gate.gate_type {
+                // pow5
+                12345 => input_depths[gate.i_ids[0]] + 3,
+                _ => input_depths[gate.i_ids[0]],
+            };
+            depths[gate.o_id] = depths[gate.o_id].max(d);
+        }
+        depths
+    }
+}
@@ -0,0 +1,127 @@
+//! Locating the gates behind a witness that does not satisfy the circuit.
+//!
+//! A satisfying witness evaluates to an all-zero output. For each non zero output wire, the
+//! debugger lists the gates of the output layer writing it, with their annotations.
+
+use std::fmt;
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, GateKind, GateRef};
+use config::GKRConfig;
+
+use crate::CircuitAnnotationSystem;
+
+#[derive(Debug, Clone)]
+pub struct ConstraintViolation<C: GKRConfig> {
+    /// Index of the output wire.
+    pub wire: usize,
+    pub value: C::SimdCircuitField,
+    /// The gates of the output layer writing the wire.
+    pub gates: Vec<GateRef>,
+    /// The annotations of each gate, in the order of `gates`.
+    pub annotations: Vec<Vec<(String, String)>>,
+}
+
+impl<C: GKRConfig> fmt::Display for ConstraintViolation<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "output wire {} is {:?}, not zero", self.wire, self.value)?;
+        for (gate, annotations) in self.gates.iter().zip(&self.annotations) {
+            write!(
+                f,
+                "  layer {} {:?} gate {}",
+                gate.layer, gate.kind, gate.index
+            )?;
+            if !annotations.is_empty() {
+                let annotations = annotations
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                write!(f, " ({})", annotations)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ConstraintViolationDebugger<'a> {
+    annotations: &'a CircuitAnnotationSystem,
+}
+
+impl<'a> ConstraintViolationDebugger<'a> {
+    pub fn new(annotations: &'a CircuitAnnotationSystem) -> Self {
+        Self { annotations }
+    }
+
+    /// Evaluate the circuit on its current input, and return the violated output wires.
+    pub fn debug<C: GKRConfig>(&self, circuit: &mut Circuit<C>) -> Vec<ConstraintViolation<C>> {
+        circuit.evaluate();
+
+        let layer_index = circuit.layers.len() - 1;
+        let layer = &circuit.layers[layer_index];
+        layer
+            .output_vals
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(wire, value)| {
+                let gates = writers(layer_index, layer, wire);
+                let annotations = gates
+                    .iter()
+                    .map(|gate| {
+                        self.annotations
+                            .annotations_of(*gate)
+                            .into_iter()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect()
+                    })
+                    .collect();
+                ConstraintViolation {
+                    wire,
+                    value: *value,
+                    gates,
+                    annotations,
+                }
+            })
+            .collect()
+    }
+
+    /// The violations as a readable report, empty if the witness satisfies the circuit.
+    pub fn report<C: GKRConfig>(&self, circuit: &mut Circuit<C>) -> String {
+        self.debug(circuit)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect()
+    }
+}
+
+/// The gates of the layer writing the output wire.
+fn writers<C: GKRConfig>(layer_index: usize, layer: &CircuitLayer<C>, wire: usize) -> Vec<GateRef> {
+    let o_ids = [
+        (
+            GateKind::Mul,
+            layer.mul.iter().map(|g| g.o_id).collect::<Vec<_>>(),
+        ),
+        (GateKind::Add, layer.add.iter().map(|g| g.o_id).collect()),
+        (
+            GateKind::Const,
+            layer.const_.iter().map(|g| g.o_id).collect(),
+        ),
+        (GateKind::Uni, layer.uni.iter().map(|g| g.o_id).collect()),
+    ];
+    o_ids
+        .into_iter()
+        .flat_map(|(kind, o_ids)| {
+            o_ids
+                .into_iter()
+                .enumerate()
+                .filter(move |(_, o_id)| *o_id == wire)
+                .map(move |(index, _)| GateRef {
+                    layer: layer_index,
+                    kind,
+                    index,
+                })
+        })
+        .collect()
+}
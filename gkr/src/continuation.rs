@@ -0,0 +1,172 @@
+//! Proving a step circuit over an unbounded number of steps, one state to the next.
+//!
+//! The input layer of the step circuit is the current state followed by the step witness, which
+//! starts with the next state: the circuit outputs zero iff the transition between the two is
+//! valid. A state is committed to by the hash of its values.
+//!
+//! Without recursion, the accumulated proof is the sequence of the step proofs, which the
+//! verifier checks one by one against the chain of state commitments, reading the states from
+//! the raw commitments of the proofs. Folding them into a proof of constant size requires the
+//! verifier as a circuit, see `NativeVerificationGadget`.
+
+use arith::{Field, FieldSerde};
+use circuit::Circuit;
+use config::{Config, GKRConfig, PolynomialCommitmentType};
+use sha2::{Digest, Sha256};
+use transcript::Proof;
+
+use crate::{Prover, Verifier, WitnessReconstructor};
+
+#[derive(Debug, Clone)]
+pub struct StepProof<C: GKRConfig> {
+    pub claimed_v: Vec<C::ChallengeField>,
+    pub proof: Proof,
+}
+
+#[derive(Debug, Clone)]
+pub struct IncrementalProof<C: GKRConfig> {
+    /// The proofs of the steps, in order.
+    pub steps: Vec<StepProof<C>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinuationState<C: GKRConfig> {
+    /// Number of steps proven so far.
+    pub step: u64,
+    pub state: Vec<C::SimdCircuitField>,
+    pub state_commitment: C::CircuitField,
+    /// Commitment to the state before the first step.
+    pub initial_commitment: C::CircuitField,
+    /// `None` before the first step.
+    pub accumulated_proof: Option<IncrementalProof<C>>,
+}
+
+/// Hash of the state values, as a field element.
+pub fn commit_state<C: GKRConfig>(state: &[C::SimdCircuitField]) -> C::CircuitField {
+    let mut bytes = vec![];
+    state
+        .iter()
+        .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+    let digest: [u8; 32] = Sha256::digest(&bytes).into();
+    C::CircuitField::from_uniform_bytes(&digest)
+}
+
+impl<C: GKRConfig> ContinuationState<C> {
+    pub fn new(initial_state: Vec<C::SimdCircuitField>) -> Self {
+        let commitment = commit_state::<C>(&initial_state);
+        Self {
+            step: 0,
+            state: initial_state,
+            state_commitment: commitment,
+            initial_commitment: commitment,
+            accumulated_proof: None,
+        }
+    }
+
+    /// Check every step proof, and that the states chain from the initial commitment to the
+    /// current state.
+    pub fn verify(&self, step_circuit: &Circuit<C>, config: &Config<C>) -> bool {
+        if commit_state::<C>(&self.state) != self.state_commitment {
+            return false;
+        }
+        let steps = match &self.accumulated_proof {
+            None => return self.step == 0 && self.state_commitment == self.initial_commitment,
+            Some(proof) => &proof.steps,
+        };
+        if steps.len() as u64 != self.step {
+            return false;
+        }
+
+        let state_size = self.state.len();
+        let public_input = step_circuit.public_input.clone();
+        let verifier = Verifier::new(config);
+        let mut commitment = self.initial_commitment;
+        for step in steps {
+            // the transition is valid iff the output is zero
+            if !step.claimed_v.iter().all(|v| v.is_zero()) {
+                return false;
+            }
+            let witness = match WitnessReconstructor::reconstruct_with_config(
+                config,
+                step_circuit,
+                &step.proof,
+                &public_input,
+            ) {
+                Ok(witness) => witness,
+                Err(_) => return false,
+            };
+            if commit_state::<C>(&witness[..state_size]) != commitment {
+                return false;
+            }
+
+            let mut circuit = step_circuit.clone();
+            if !verifier.verify(&mut circuit, &public_input, &step.claimed_v, &step.proof) {
+                return false;
+            }
+            commitment = commit_state::<C>(&witness[state_size..2 * state_size]);
+        }
+        commitment == self.state_commitment
+    }
+}
+
+pub struct ContinuationStep;
+
+impl ContinuationStep {
+    /// Prove one step from the state, and return the next state.
+    ///
+    /// Panics unless the state and the witness fill the input layer of the step circuit and
+    /// satisfy it, or for a config other than a single process with the raw commitment.
+    pub fn prove<C: GKRConfig>(
+        state: &ContinuationState<C>,
+        step_circuit: &Circuit<C>,
+        step_witness: &[C::SimdCircuitField],
+        config: &Config<C>,
+    ) -> ContinuationState<C> {
+        assert!(
+            config.polynomial_commitment_type == PolynomialCommitmentType::Raw
+                && config.mpi_config.world_size() == 1,
+            "continuations are proven by a single process with the raw commitment"
+        );
+        let state_size = state.state.len();
+        assert!(
+            step_witness.len() >= state_size,
+            "the step witness must start with the next state"
+        );
+        assert_eq!(
+            state_size + step_witness.len(),
+            1 << step_circuit.layers[0].input_var_num,
+            "the state and the step witness must fill the input layer"
+        );
+
+        let mut circuit = step_circuit.clone();
+        circuit.layers[0].input_vals = state.state.iter().chain(step_witness).copied().collect();
+        let mut prover = Prover::new(config).expect("checked above");
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+        assert!(
+            circuit
+                .layers
+                .last()
+                .unwrap()
+                .output_vals
+                .iter()
+                .all(|v| v.is_zero()),
+            "the step witness does not satisfy the step circuit"
+        );
+
+        let mut steps = state
+            .accumulated_proof
+            .as_ref()
+            .map_or(vec![], |proof| proof.steps.clone());
+        steps.push(StepProof { claimed_v, proof });
+
+        let next_state = step_witness[..state_size].to_vec();
+        ContinuationState {
+            step: state.step + 1,
+            state_commitment: commit_state::<C>(&next_state),
+            state: next_state,
+            initial_commitment: state.initial_commitment,
+            accumulated_proof: Some(IncrementalProof { steps }),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Lowering the degree of the layer sumchecks through gate substitutions.
+//!
+//! A mul gate `coef * a * b` is degree two and forces the second (`y`) phase of the layer
+//! sumcheck. When one of its inputs is a wire whose value is fixed by the circuit, i.e., a wire of
+//! the previous layer that is only written by constant gates, the mul gate is equivalent to the
+//! degree one add gate `(coef * b) * a`, and to a const gate if both inputs are fixed.
+//! Once all the mul gates of a layer are substituted, the layer no longer needs the second phase.
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst};
+use config::GKRConfig;
+
+pub struct SumcheckDegreeOptimizer;
+
+impl SumcheckDegreeOptimizer {
+    /// Substitute the mul gates with a fixed input, and return the number of substituted gates.
+    ///
+    /// Panics if the evaluations of the optimized circuit do not match the original ones
+    /// on a random input.
+    pub fn optimize<C: GKRConfig>(circuit: &mut Circuit<C>) -> usize {
+        let original = circuit.clone();
+
+        let mut n_optimized = 0;
+        for i in 1..circuit.layers.len() {
+            let (prev_layers, layers) = circuit.layers.split_at_mut(i);
+            let fixed_wires = Self::fixed_output_wires(prev_layers.last().unwrap());
+            n_optimized += Self::substitute_mul_gates(&mut layers[0], &fixed_wires);
+        }
+
+        if n_optimized > 0 {
+            circuit
+                .layers
+                .iter_mut()
+                .for_each(|layer| layer.identify_structure_info());
+            // the gates have moved, so the pointers to the random coefficients are stale
+            if circuit.rnd_coefs_identified {
+                circuit.identify_rnd_coefs();
+            }
+
+            assert!(
+                Self::evaluations_match(&original, circuit),
+                "the optimized circuit is not equivalent to the original one"
+            );
+        }
+        n_optimized
+    }
+
+    /// Evaluate both circuits on the same random input, and compare their outputs.
+    pub fn evaluations_match<C: GKRConfig>(a: &Circuit<C>, b: &Circuit<C>) -> bool {
+        let mut rng = rand::thread_rng();
+        let mut a = a.clone();
+        let mut b = b.clone();
+
+        a.set_random_input_for_test();
+        b.layers[0].input_vals.clone_from(&a.layers[0].input_vals);
+
+        let public_input_size = a
+            .layers
+            .iter()
+            .flat_map(|layer| layer.const_.iter())
+            .filter_map(|gate| match gate.coef_type {
+                CoefType::PublicInput(idx) => Some(idx + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        a.public_input = (0..public_input_size)
+            .map(|_| C::SimdCircuitField::random_unsafe(&mut rng))
+            .collect();
+        b.public_input.clone_from(&a.public_input);
+
+        a.evaluate();
+        b.evaluate();
+        a.layers.last().unwrap().output_vals == b.layers.last().unwrap().output_vals
+    }
+
+    /// The output wires of a layer that are only written by constant gates, with their values.
+    pub(crate) fn fixed_output_wires<C: GKRConfig>(
+        layer: &CircuitLayer<C>,
+    ) -> Vec<Option<C::CircuitField>> {
+        let mut fixed = vec![Some(C::CircuitField::ZERO); 1 << layer.output_var_num];
+
+        for gate in &layer.const_ {
+            match gate.coef_type {
+                CoefType::Constant => {
+                    if let Some(v) = fixed[gate.o_id].as_mut() {
+                        *v += gate.coef;
+                    }
+                }
+                _ => fixed[gate.o_id] = None,
+            }
+        }
+        let written_wires = layer
+            .mul
+            .iter()
+            .map(|gate| gate.o_id)
+            .chain(layer.add.iter().map(|gate| gate.o_id))
+            .chain(layer.uni.iter().map(|gate| gate.o_id));
+        for o_id in written_wires {
+            fixed[o_id] = None;
+        }
+
+        fixed
+    }
+
+    fn substitute_mul_gates<C: GKRConfig>(
+        layer: &mut CircuitLayer<C>,
+        fixed_wires: &[Option<C::CircuitField>],
+    ) -> usize {
+        let n_mul = layer.mul.len();
+        let mut remaining = Vec::with_capacity(n_mul);
+
+        for gate in layer.mul.drain(..) {
+            // a random coefficient is only known after the transcript is set up
+            if gate.coef_type != CoefType::Constant {
+                remaining.push(gate);
+                continue;
+            }
+
+            match (fixed_wires[gate.i_ids[0]], fixed_wires[gate.i_ids[1]]) {
+                (Some(v0), Some(v1)) => layer.const_.push(GateConst {
+                    i_ids: [],
+                    o_id: gate.o_id,
+                    coef_type: CoefType::Constant,
+                    coef: gate.coef * v0 * v1,
+                    gate_type: gate.gate_type,
+                }),
+                (Some(v), None) | (None, Some(v)) => {
+                    let free_input = if fixed_wires[gate.i_ids[0]].is_some() {
+                        gate.i_ids[1]
+                    } else {
+                        gate.i_ids[0]
+                    };
+                    layer.add.push(GateAdd {
+                        i_ids: [free_input],
+                        o_id: gate.o_id,
+                        coef_type: CoefType::Constant,
+                        coef: gate.coef * v,
+                        gate_type: gate.gate_type,
+                    })
+                }
+                (None, None) => remaining.push(gate),
+            }
+        }
+
+        layer.mul = remaining;
+        n_mul - layer.mul.len()
+    }
+}
@@ -0,0 +1,148 @@
+//! Deployment of a Solidity verifier contract with Foundry.
+//!
+//! Foundry's `cast` only deploys compiled bytecode, so the source is compiled and deployed in one
+//! step with `forge create`, which prints the address of the deployed contract.
+//! Both `forge` and an RPC endpoint (e.g., a local `anvil`) are required at runtime.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DeployError {
+    #[error("io error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    #[error("no contract definition found in the solidity source")]
+    NoContract,
+
+    #[error("forge create failed: {0}")]
+    CommandFailed(String),
+
+    #[error("deployed address not found in forge output: {0}")]
+    AddressNotFound(String),
+
+    #[error("invalid ethereum address: {0}")]
+    InvalidAddress(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EthereumAddress(pub [u8; 20]);
+
+impl FromStr for EthereumAddress {
+    type Err = DeployError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DeployError::InvalidAddress(s.to_string());
+
+        let hex = s.strip_prefix("0x").ok_or_else(invalid)?;
+        if hex.len() != 40 {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; 20];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2).ok_or_else(invalid)?, 16)
+                .map_err(|_| invalid())?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for EthereumAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+pub struct EthereumVerifierDeployer;
+
+impl EthereumVerifierDeployer {
+    /// Compile and deploy the last contract defined in `solidity_source`,
+    /// and return the address it is deployed to.
+    pub fn deploy(
+        solidity_source: &str,
+        rpc_url: &str,
+        private_key: &str,
+    ) -> Result<EthereumAddress, DeployError> {
+        let contract_name = Self::contract_name(solidity_source).ok_or(DeployError::NoContract)?;
+
+        // forge create expects the source inside a project directory
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let project_dir: PathBuf = std::env::temp_dir().join(format!(
+            "expander-verifier-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(project_dir.join("src"))?;
+        fs::write(project_dir.join("src/Verifier.sol"), solidity_source)?;
+
+        let output =
+            Self::forge_create_command(&project_dir, &contract_name, rpc_url, private_key).output();
+        let _ = fs::remove_dir_all(&project_dir);
+        let output = output?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if !output.status.success() {
+            return Err(DeployError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+        Self::parse_deployed_address(&stdout)
+    }
+
+    /// The `forge create` command of the contract of the project. The private key is passed in
+    /// the `ETH_PRIVATE_KEY` environment variable of the child, not in its arguments, which any
+    /// user of the machine can list, e.g., with `ps`; the command is not to be logged either.
+    pub(crate) fn forge_create_command(
+        project_dir: &Path,
+        contract_name: &str,
+        rpc_url: &str,
+        private_key: &str,
+    ) -> Command {
+        let mut command = Command::new("forge");
+        command
+            .current_dir(project_dir)
+            .env("ETH_PRIVATE_KEY", private_key)
+            .arg("create")
+            .arg(format!("src/Verifier.sol:{}", contract_name))
+            .args(["--rpc-url", rpc_url])
+            .arg("--broadcast");
+        command
+    }
+
+    /// Name of the last contract of the source, usually the one depending on all the others.
+    fn contract_name(solidity_source: &str) -> Option<String> {
+        solidity_source
+            .lines()
+            .filter_map(|line| {
+                let mut tokens = line.split_whitespace();
+                if tokens.next()? != "contract" {
+                    return None;
+                }
+                tokens
+                    .next()
+                    .map(|name| name.trim_end_matches('{').to_string())
+            })
+            .last()
+    }
+
+    /// Parse the `Deployed to: 0x...` line printed by `forge create`.
+    pub fn parse_deployed_address(forge_output: &str) -> Result<EthereumAddress, DeployError> {
+        forge_output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Deployed to:"))
+            .ok_or_else(|| DeployError::AddressNotFound(forge_output.to_string()))?
+            .trim()
+            .parse()
+    }
+}
@@ -0,0 +1,191 @@
+//! Human readable descriptions of the proof system, for audits.
+
+use std::fmt::Write;
+
+use circuit::{Circuit, CoefType};
+use config::{Config, GKRConfig};
+
+/// The verifier algorithm specialized to a circuit, as LaTeX `algpseudocode`.
+/// It follows `Verifier::verify` step by step, for vanilla GKR with the raw commitment.
+pub struct GKRVerifierPseudocode;
+
+impl GKRVerifierPseudocode {
+    pub fn generate<C: GKRConfig>(circuit: &Circuit<C>, config: &Config<C>) -> String {
+        let mut out = String::new();
+        Self::write(&mut out, circuit, config).unwrap();
+        out
+    }
+
+    fn write<C: GKRConfig>(
+        out: &mut String,
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+    ) -> std::fmt::Result {
+        let layer_num = circuit.layers.len();
+        let world_size = config.mpi_config.world_size();
+        let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+        let mpi_var_num = world_size.trailing_zeros() as usize;
+        let input_var_num = circuit.layers[0].input_var_num;
+        let n_rnd_coefs = circuit
+            .layers
+            .iter()
+            .flat_map(|layer| {
+                let mul = layer.mul.iter().map(|g| &g.coef_type);
+                let add = layer.add.iter().map(|g| &g.coef_type);
+                let cst = layer.const_.iter().map(|g| &g.coef_type);
+                let uni = layer.uni.iter().map(|g| &g.coef_type);
+                mul.chain(add).chain(cst).chain(uni)
+            })
+            .filter(|coef_type| **coef_type == CoefType::Random)
+            .count();
+
+        writeln!(
+            out,
+            "% GKR verifier for a circuit of {} layers over {:?}, {} simd lanes, {} processes, \
+             Fiat-Shamir with {:?}",
+            layer_num,
+            C::FIELD_TYPE,
+            C::get_field_pack_size(),
+            world_size,
+            C::FIAT_SHAMIR_HASH
+        )?;
+        writeln!(out, "\\begin{{algorithmic}}[1]")?;
+        writeln!(
+            out,
+            "\\Require claimed output values $y$, proof $\\pi$, public input $\\mathit{{pub}}$"
+        )?;
+        writeln!(
+            out,
+            "\\State read the commitment $C$, the ${} \\cdot 2^{{{}}}$ input values, from $\\pi$",
+            world_size, input_var_num
+        )?;
+        writeln!(out, "\\State absorb $C$ into the transcript")?;
+        if world_size > 1 {
+            writeln!(out, "\\State hash the transcript state")?;
+        }
+        if n_rnd_coefs > 0 {
+            writeln!(
+                out,
+                "\\State draw the {} random gate coefficients",
+                n_rnd_coefs
+            )?;
+        }
+        writeln!(
+            out,
+            "\\State absorb the {} claimed values $y$, one per output of each lane and process",
+            (world_size * C::get_field_pack_size()) << circuit.layers[layer_num - 1].output_var_num
+        )?;
+        if world_size > 1 {
+            writeln!(out, "\\State hash the transcript state")?;
+        }
+        writeln!(
+            out,
+            "\\State draw $r_z \\in \\mathbb{{F}}^{{{}}}$, $r_{{simd}} \\in \\mathbb{{F}}^{{{}}}$, \
+             $r_{{mpi}} \\in \\mathbb{{F}}^{{{}}}$",
+            circuit.layers[layer_num - 1].output_var_num,
+            simd_var_num,
+            mpi_var_num
+        )?;
+        writeln!(
+            out,
+            "\\State $v \\gets \\tilde{{y}}(r_z, r_{{simd}}, r_{{mpi}})$, the multilinear \\
+             extension of $y$"
+        )?;
+
+        let mut has_second_claim = false;
+        for i in (0..layer_num).rev() {
+            let layer = &circuit.layers[i];
+            writeln!(out, "\\Statex \\textbf{{Layer {}}}", i)?;
+            if has_second_claim {
+                writeln!(
+                    out,
+                    "\\State $S \\gets v + \\alpha v' - \\mathrm{{cst}}_{{{}}}(r_z, r_z', \\alpha, \
+                     \\mathit{{pub}})$",
+                    i
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "\\State $S \\gets v - \\mathrm{{cst}}_{{{}}}(r_z, \\mathit{{pub}})$",
+                    i
+                )?;
+            }
+
+            Self::write_rounds(out, "x", layer.input_var_num, 2)?;
+            Self::write_rounds(out, "simd", simd_var_num, 3)?;
+            Self::write_rounds(out, "mpi", mpi_var_num, 3)?;
+            writeln!(
+                out,
+                "\\State read $v_x$ from $\\pi$, absorb it, \
+                 $S \\gets S - v_x \\cdot \\mathrm{{add}}_{{{}}}(r_z, r_x)$",
+                i
+            )?;
+
+            if layer.structure_info.max_degree_one {
+                writeln!(out, "\\State \\textbf{{check}} $S = 0$")?;
+                writeln!(out, "\\State $(r_z, v) \\gets (r_x, v_x)$")?;
+                has_second_claim = false;
+            } else {
+                Self::write_rounds(out, "y", layer.input_var_num, 2)?;
+                writeln!(out, "\\State read $v_y$ from $\\pi$, absorb it")?;
+                writeln!(
+                    out,
+                    "\\State \\textbf{{check}} $S = v_x v_y \\cdot \\mathrm{{mul}}_{{{}}}(r_z, r_x, r_y)$",
+                    i
+                )?;
+                writeln!(
+                    out,
+                    "\\State $(r_z, v, r_z', v') \\gets (r_x, v_x, r_y, v_y)$, draw $\\alpha$"
+                )?;
+                has_second_claim = true;
+            }
+            writeln!(
+                out,
+                "\\State $(r_{{simd}}, r_{{mpi}}) \\gets (r_{{simd}}', r_{{mpi}}')$"
+            )?;
+        }
+
+        writeln!(out, "\\Statex \\textbf{{Input layer}}")?;
+        writeln!(
+            out,
+            "\\State \\textbf{{check}} $\\tilde{{C}}(r_z, r_{{simd}}, r_{{mpi}}) = v$"
+        )?;
+        if has_second_claim {
+            writeln!(
+                out,
+                "\\State \\textbf{{check}} $\\tilde{{C}}(r_z', r_{{simd}}, r_{{mpi}}) = v'$"
+            )?;
+        }
+        writeln!(out, "\\end{{algorithmic}}")
+    }
+
+    /// The sumcheck rounds over `var_num` variables named `name`, with round polynomials of
+    /// the given degree. Rounds over the simd or mpi variables draw the primed challenges.
+    fn write_rounds(
+        out: &mut String,
+        name: &str,
+        var_num: usize,
+        degree: usize,
+    ) -> std::fmt::Result {
+        if var_num == 0 {
+            return Ok(());
+        }
+        let challenge = match name {
+            "x" | "y" => format!("r_{}", name),
+            _ => format!("r_{{{}}}'", name),
+        };
+        let evals = (0..=degree)
+            .map(|k| format!("p({})", k))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(out, "\\For{{$j = 0, \\ldots, {}$}}", var_num - 1)?;
+        writeln!(out, "\\State read ${}$ from $\\pi$, absorb them", evals)?;
+        writeln!(out, "\\State \\textbf{{check}} $p(0) + p(1) = S$")?;
+        writeln!(
+            out,
+            "\\State draw ${}[j]$, $S \\gets p({}[j])$",
+            challenge, challenge
+        )?;
+        writeln!(out, "\\EndFor")
+    }
+}
@@ -0,0 +1,193 @@
+//! Circuits assembled layer by layer at runtime, e.g., one loop iteration at a time once the
+//! bound is known, with the wiring checked as each layer is pushed.
+
+use std::collections::HashSet;
+
+use circuit::{Circuit, CircuitLayer, GateAdd, GateConst, GateKind, GateMul, GateRef, GateUni};
+use config::{Config, GKRConfig};
+use thiserror::Error;
+
+use crate::ProofLayout;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConnectivityError {
+    #[error("layer {layer} has {found} input variables, but the layer below has {expected} output variables")]
+    VarNumMismatch {
+        layer: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("gate {gate:?} reads wire {wire}, out of the {size} input wires")]
+    InputOutOfRange {
+        gate: GateRef,
+        wire: usize,
+        size: usize,
+    },
+
+    #[error("gate {gate:?} writes wire {wire}, out of the {size} output wires")]
+    OutputOutOfRange {
+        gate: GateRef,
+        wire: usize,
+        size: usize,
+    },
+
+    #[error("gate {gate:?} reads wire {wire}, which no gate of the layer below writes")]
+    UnwrittenInput { gate: GateRef, wire: usize },
+
+    #[error("gate {gate:?} has the unknown type {gate_type}")]
+    UnknownGateType { gate: GateRef, gate_type: usize },
+}
+
+/// The gates of a layer, and its sizes.
+#[derive(Debug, Clone, Default)]
+pub struct LayerSpec<C: GKRConfig> {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    pub mul: Vec<GateMul<C>>,
+    pub add: Vec<GateAdd<C>>,
+    pub const_: Vec<GateConst<C>>,
+    pub uni: Vec<GateUni<C>>,
+}
+
+pub struct DynamicCircuit<C: GKRConfig> {
+    config: Config<C>,
+    layers: Vec<CircuitLayer<C>>,
+    /// The output wires written by a gate of the last layer.
+    written: HashSet<usize>,
+}
+
+impl<C: GKRConfig> DynamicCircuit<C> {
+    pub fn new(config: &Config<C>) -> Self {
+        Self {
+            config: config.clone(),
+            layers: vec![],
+            written: HashSet::new(),
+        }
+    }
+
+    /// Append a layer on top of the current ones. On error, the circuit is left unchanged.
+    pub fn push_layer(&mut self, layer: LayerSpec<C>) -> Result<&mut Self, ConnectivityError> {
+        let index = self.layers.len();
+        if let Some(below) = self.layers.last() {
+            if below.output_var_num != layer.input_var_num {
+                return Err(ConnectivityError::VarNumMismatch {
+                    layer: index,
+                    expected: below.output_var_num,
+                    found: layer.input_var_num,
+                });
+            }
+        }
+
+        let gate_ref = |kind, i| GateRef {
+            layer: index,
+            kind,
+            index: i,
+        };
+        let inputs = layer
+            .mul
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (gate_ref(GateKind::Mul, i), g.i_ids.to_vec(), g.o_id))
+            .chain(
+                layer
+                    .add
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Add, i), g.i_ids.to_vec(), g.o_id)),
+            )
+            .chain(
+                layer
+                    .const_
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Const, i), vec![], g.o_id)),
+            )
+            .chain(
+                layer
+                    .uni
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Uni, i), g.i_ids.to_vec(), g.o_id)),
+            )
+            .collect::<Vec<_>>();
+
+        let input_size = 1 << layer.input_var_num;
+        let output_size = 1 << layer.output_var_num;
+        for (gate, i_ids, o_id) in &inputs {
+            for &wire in i_ids {
+                if wire >= input_size {
+                    return Err(ConnectivityError::InputOutOfRange {
+                        gate: *gate,
+                        wire,
+                        size: input_size,
+                    });
+                }
+                // the input layer of the circuit is written by the witness
+                if index > 0 && !self.written.contains(&wire) {
+                    return Err(ConnectivityError::UnwrittenInput { gate: *gate, wire });
+                }
+            }
+            if *o_id >= output_size {
+                return Err(ConnectivityError::OutputOutOfRange {
+                    gate: *gate,
+                    wire: *o_id,
+                    size: output_size,
+                });
+            }
+        }
+        for (i, g) in layer.uni.iter().enumerate() {
+            // pow5 and identity, see `CircuitLayer::evaluate`
+            if g.gate_type != 12345 && g.gate_type != 12346 {
+                return Err(ConnectivityError::UnknownGateType {
+                    gate: gate_ref(GateKind::Uni, i),
+                    gate_type: g.gate_type,
+                });
+            }
+        }
+
+        self.written = inputs.iter().map(|(_, _, o_id)| *o_id).collect();
+        self.layers.push(CircuitLayer {
+            input_var_num: layer.input_var_num,
+            output_var_num: layer.output_var_num,
+            mul: layer.mul,
+            add: layer.add,
+            const_: layer.const_,
+            uni: layer.uni,
+            ..Default::default()
+        });
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Size in bytes of the proofs of the circuit as it is now.
+    pub fn proof_size(&self) -> usize {
+        ProofLayout::new(&self.build(), &self.config).proof_size()
+    }
+
+    /// The circuit, ready to be proven.
+    ///
+    /// Panics if no layer was pushed.
+    pub fn finalize(self) -> Circuit<C> {
+        let mut circuit = self.build();
+        circuit.identify_rnd_coefs();
+        circuit
+    }
+
+    fn build(&self) -> Circuit<C> {
+        assert!(!self.layers.is_empty(), "no layer pushed");
+        let mut circuit = Circuit::<C> {
+            layers: self.layers.clone(),
+            ..Default::default()
+        };
+        circuit
+            .layers
+            .iter_mut()
+            .for_each(|layer| layer.identify_structure_info());
+        circuit
+    }
+}
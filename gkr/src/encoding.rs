@@ -0,0 +1,8 @@
+//! Encodings of proofs for other languages and tools.
+//!
+//! A proof document is a map with the keys `field`, the `Field::NAME` of the claimed values,
+//! e.g., `"Mersenne 31 Extension 3"`, `proof`, the proof bytes, and `claimed_v`, the claimed
+//! values of the outputs, serialized one after the other. Text encodings represent the byte
+//! strings in hex.
+
+pub mod cbor;
@@ -0,0 +1,93 @@
+//! Proof documents in CBOR, RFC 8949, with the keys in the order of the module documentation
+//! and the byte strings as CBOR byte strings.
+
+use arith::{Field, FieldSerde, FieldSerdeError};
+use ciborium::Value;
+use thiserror::Error;
+use transcript::Proof;
+
+#[derive(Debug, Error)]
+pub enum CborDecodeError {
+    #[error("invalid cbor: {0}")]
+    CborError(String),
+
+    #[error("the proof document is not a map")]
+    NotAMap,
+
+    #[error("missing or ill typed key {0}")]
+    InvalidKey(&'static str),
+
+    #[error("proof for the field {found}, expected {expected}")]
+    FieldMismatch {
+        expected: &'static str,
+        found: String,
+    },
+
+    #[error("invalid claimed values: {0}")]
+    ClaimedValueError(#[from] FieldSerdeError),
+}
+
+pub fn encode_proof<F: Field + FieldSerde>(proof: &Proof, claimed_v: &[F]) -> Vec<u8> {
+    let mut claimed_v_bytes = vec![];
+    claimed_v
+        .iter()
+        .for_each(|v| v.serialize_into(&mut claimed_v_bytes).unwrap());
+    let document = Value::Map(vec![
+        (Value::from("field"), Value::from(F::NAME)),
+        (Value::from("proof"), Value::Bytes(proof.bytes.clone())),
+        (Value::from("claimed_v"), Value::Bytes(claimed_v_bytes)),
+    ]);
+
+    let mut bytes = vec![];
+    ciborium::into_writer(&document, &mut bytes).unwrap();
+    bytes
+}
+
+/// Fails on a document for another field than `F`. Unknown keys are ignored.
+pub fn decode_proof<F: Field + FieldSerde>(
+    bytes: &[u8],
+) -> Result<(Proof, Vec<F>), CborDecodeError> {
+    let document: Value =
+        ciborium::from_reader(bytes).map_err(|e| CborDecodeError::CborError(e.to_string()))?;
+    let entries = match document {
+        Value::Map(entries) => entries,
+        _ => return Err(CborDecodeError::NotAMap),
+    };
+    let get = |key: &'static str| {
+        entries
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(key))
+            .map(|(_, v)| v)
+            .ok_or(CborDecodeError::InvalidKey(key))
+    };
+
+    let field = get("field")?
+        .as_text()
+        .ok_or(CborDecodeError::InvalidKey("field"))?;
+    if field != F::NAME {
+        return Err(CborDecodeError::FieldMismatch {
+            expected: F::NAME,
+            found: field.to_string(),
+        });
+    }
+    let proof_bytes = get("proof")?
+        .as_bytes()
+        .ok_or(CborDecodeError::InvalidKey("proof"))?;
+    let claimed_v_bytes = get("claimed_v")?
+        .as_bytes()
+        .ok_or(CborDecodeError::InvalidKey("claimed_v"))?;
+    if claimed_v_bytes.len() % F::SERIALIZED_SIZE != 0 {
+        return Err(FieldSerdeError::DeserializeError.into());
+    }
+
+    let claimed_v = claimed_v_bytes
+        .chunks(F::SERIALIZED_SIZE)
+        .map(F::deserialize_from)
+        .collect::<Result<_, _>>()?;
+    Ok((
+        Proof {
+            bytes: proof_bytes.clone(),
+        },
+        claimed_v,
+    ))
+}
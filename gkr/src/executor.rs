@@ -0,0 +1,1179 @@
+//! Proving and verifying circuit files as `expander-exec` does, returning the failures instead
+//! of exiting, so that a malformed file does not take down the process embedding the prover.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{ErrorKind, Read, Write},
+};
+
+use arith::{FieldSerde, FieldSerdeError, SimdField};
+use circuit::{
+    encode_witness_batch, split_witness_batch, Circuit, CircuitError, WITNESS_BATCH_MAGIC,
+};
+use config::{
+    Config, ConfigError, FieldType, GKRConfig, GKRScheme, MPIConfig, PolynomialCommitmentType,
+};
+use log::{info, warn};
+use thiserror::Error;
+use transcript::{decode_hex, encode_hex, Proof};
+
+use crate::{
+    decode_length_prefixed, encode_length_prefixed, gather_public_input, input_commitment,
+    CircuitLoadError, CompressionCodec, FieldProofError, FieldProofManifest, ProofFieldElements,
+    ProofLayout, ProofPackage, ProofPackageError, Prover, ProverStats, SchemeSelector,
+    VecFieldSerializer, VerificationError, Verifier,
+};
+
+#[derive(Debug, Error)]
+pub enum ExpanderError {
+    #[error("unable to load the circuit: {0}")]
+    CircuitLoad(CircuitError),
+
+    /// Also when the witness does not fit the circuit, and for the public inputs of a verifier.
+    #[error("unable to load the witness: {0}")]
+    WitnessLoad(CircuitError),
+
+    #[error("invalid circuit: {0}")]
+    InvalidCircuit(CircuitError),
+
+    /// Also when the sentinel of the circuit file is of no known field.
+    #[error("unable to detect the field of the circuit: {0}")]
+    FieldTypeDetection(#[from] CircuitLoadError),
+
+    #[error("unable to serialize the proof: {0}")]
+    ProofSerialize(#[from] FieldSerdeError),
+
+    #[error("unable to deserialize the proof: {0}")]
+    ProofDeserialize(#[from] ProofPackageError),
+
+    #[error("invalid field proof: {0}")]
+    FieldProof(#[from] FieldProofError),
+
+    #[error("the {0:?} scheme does not support the gates of the circuit")]
+    UnsupportedScheme(GKRScheme),
+
+    #[error("invalid config: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("the circuit is over {circuit:?}, but the proof over {proof:?}")]
+    FieldTypeMismatch {
+        circuit: FieldType,
+        proof: FieldType,
+    },
+
+    #[error("unable to write the proof: {0}")]
+    ProofWrite(std::io::Error),
+
+    #[error("unable to read the proof: {0}")]
+    ProofRead(std::io::Error),
+
+    #[error("unable to write the public inputs: {0}")]
+    PublicInputWrite(std::io::Error),
+
+    #[error("unable to read the public values: {0}")]
+    PublicValuesRead(std::io::Error),
+
+    #[error("invalid public values: {0}")]
+    InvalidPublicValues(String),
+
+    #[error("{found} public values, but the circuit reads {expected} public inputs")]
+    PublicValuesCount { expected: usize, found: usize },
+
+    #[error("unable to read stdin: {0}")]
+    StdinRead(std::io::Error),
+
+    /// The witness and proof piped to `verify` are not framed as the bodies of `/verify`.
+    #[error("invalid witness and proof on stdin: {0}")]
+    StdinFraming(String),
+
+    #[error("unable to read the manifest: {0}")]
+    ManifestRead(std::io::Error),
+
+    #[error("line {line} of the manifest is not a witness file and a proof file")]
+    InvalidManifestLine { line: usize },
+
+    #[error("the witness batch has {witness} instances, but the proofs are of {proof}")]
+    ProofBatchInstances { witness: usize, proof: usize },
+
+    #[error("{found} proofs for the instances of the batch, expected {expected}")]
+    ProofBatchCount { expected: usize, found: usize },
+
+    #[error(
+        "the proof is of {0} processes, verify it on a batch of the public inputs of each rank"
+    )]
+    RankPublicInputsMissing(usize),
+
+    #[error("the public inputs of {found} processes, but the proof is of {expected}")]
+    RankPublicInputCount { expected: usize, found: usize },
+}
+
+/// The first bytes of the proofs of a witness batch, see `prove_batch_with_compression`,
+/// followed by the number of instances as a little-endian `u64`, and by the proof package of
+/// each prove call, as `encode_length_prefixed` blobs.
+pub const PROOF_BATCH_MAGIC: [u8; 4] = *b"EXPN";
+
+/// The proofs of a witness batch, in order, each of as many instances as the simd lanes but the
+/// last, of the instances left.
+#[derive(Debug, Clone)]
+pub struct ProofBatch {
+    pub num_instances: usize,
+    pub packages: Vec<ProofPackage>,
+}
+
+/// The proofs of a proof file, of a witness or of a witness batch.
+#[derive(Debug, Clone)]
+pub enum ProofFile {
+    Package(ProofPackage),
+    Batch(ProofBatch),
+}
+
+impl ProofFile {
+    /// The package telling the field, the scheme and the config of the proofs of the file.
+    pub fn first_package(&self) -> &ProofPackage {
+        match self {
+            ProofFile::Package(package) => package,
+            ProofFile::Batch(batch) => &batch.packages[0],
+        }
+    }
+}
+
+/// The proof package of the circuit file on the witness file, uncompressed.
+pub fn prove<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    witness_path: &str,
+) -> Result<Vec<u8>, ExpanderError> {
+    prove_with_compression(
+        config,
+        circuit_path,
+        witness_path,
+        CompressionCodec::None,
+        false,
+    )
+    .map(|(bytes, _)| bytes)
+}
+
+/// The proof package of the circuit file on the witness file, with its proof compressed by the
+/// codec. With mpi, the package of the root has the public inputs of all the processes, for the
+/// proof to be verified without as many processes, and the ones of the others have none. The
+/// package commits to the public inputs of the process, and with `commit_witness` to its
+/// private inputs too, see `ProofPackage::with_input_commitment`. The stats are the ones of the
+/// prover of the process, see `Prover::prove_with_stats`.
+pub fn prove_with_compression<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    witness_path: &str,
+    compression: CompressionCodec,
+    commit_witness: bool,
+) -> Result<(Vec<u8>, ProverStats), ExpanderError> {
+    let circuit = load_circuit_and_witness::<C>(circuit_path, witness_path)?;
+    prove_loaded(config, circuit, compression, commit_witness)
+}
+
+/// As `prove_with_compression`, with the public inputs of the public values file, see
+/// `read_public_values`, in place of those of the witness, which needs only its private inputs.
+pub fn prove_with_public_values<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    witness_path: &str,
+    public_values_path: &str,
+    compression: CompressionCodec,
+    commit_witness: bool,
+) -> Result<(Vec<u8>, ProverStats), ExpanderError> {
+    let mut circuit = load_circuit_and_witness::<C>(circuit_path, witness_path)?;
+    load_public_values(&mut circuit, &read_public_values::<C>(public_values_path)?)?;
+    prove_loaded(config, circuit, compression, commit_witness)
+}
+
+fn prove_loaded<C: GKRConfig>(
+    config: &Config<C>,
+    mut circuit: Circuit<C>,
+    compression: CompressionCodec,
+    commit_witness: bool,
+) -> Result<(Vec<u8>, ProverStats), ExpanderError> {
+    circuit.validate().map_err(ExpanderError::InvalidCircuit)?;
+    check_scheme(&circuit, &config.gkr_scheme)?;
+    let mut prover = Prover::new(config)?;
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof, stats) = prover.prove_with_stats(&mut circuit);
+    let bytes = package_proof_with_public_input(
+        config,
+        &circuit,
+        &proof,
+        &claimed_v,
+        compression,
+        commit_witness,
+    )?;
+    Ok((bytes, stats))
+}
+
+/// As `prove_with_compression`, for the witness batch file, see
+/// `Circuit::load_witness_batch_bytes`: the circuit is loaded and the prover prepared once, for
+/// the prove calls of all the packs of instances. The bytes are the proof batch, see
+/// `PROOF_BATCH_MAGIC`, and the stats those of each prove call.
+pub fn prove_batch_with_compression<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    witness_path: &str,
+    compression: CompressionCodec,
+    commit_witness: bool,
+) -> Result<(Vec<u8>, Vec<ProverStats>), ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    circuit.validate().map_err(ExpanderError::InvalidCircuit)?;
+    check_scheme(&circuit, &config.gkr_scheme)?;
+    let witness_bytes = fs::read(witness_path).map_err(|e| ExpanderError::WitnessLoad(e.into()))?;
+    let batch = circuit
+        .load_witness_batch_bytes(&witness_bytes)
+        .map_err(ExpanderError::WitnessLoad)?;
+    let mut prover = Prover::new(config)?;
+    prover.prepare_mem(&circuit);
+    let mut packages = Vec::with_capacity(batch.packs.len());
+    let mut stats = Vec::with_capacity(batch.packs.len());
+    for (private_input, public_input) in batch.packs {
+        circuit.layers[0].input_vals = private_input;
+        circuit.public_input = public_input;
+        let (claimed_v, proof, pack_stats) = prover.prove_with_stats(&mut circuit);
+        packages.push(package_proof_with_public_input(
+            config,
+            &circuit,
+            &proof,
+            &claimed_v,
+            compression,
+            commit_witness,
+        )?);
+        stats.push(pack_stats);
+    }
+    Ok((proof_batch_bytes(batch.num_instances, &packages), stats))
+}
+
+/// Whether the witness file, not stdin, is a witness batch, see `WITNESS_BATCH_MAGIC`.
+pub fn is_witness_batch(witness_path: &str) -> Result<bool, ExpanderError> {
+    if witness_path == "-" {
+        return Ok(false);
+    }
+    let mut magic = vec![];
+    fs::File::open(witness_path)
+        .and_then(|file| {
+            file.take(WITNESS_BATCH_MAGIC.len() as u64)
+                .read_to_end(&mut magic)
+        })
+        .map_err(|e| ExpanderError::WitnessLoad(e.into()))?;
+    Ok(magic == WITNESS_BATCH_MAGIC)
+}
+
+/// The proof batch of the proof packages of a witness batch of `num_instances` instances.
+pub fn proof_batch_bytes(num_instances: usize, packages: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = PROOF_BATCH_MAGIC.to_vec();
+    bytes.extend_from_slice(&(num_instances as u64).to_le_bytes());
+    bytes.extend_from_slice(&encode_length_prefixed(packages));
+    bytes
+}
+
+/// Whether the proofs of the proof batch are valid for the instances of the witness batch file,
+/// or of stdin for `-`, of whole witnesses or of public inputs alone, see
+/// `Circuit::load_public_input_batch_bytes`. The result of each instance is the one of the
+/// proof of its pack, the zero witnesses padding the last pack being left out.
+pub fn verify_proof_batch<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_path: &str,
+    batch: &ProofBatch,
+) -> Result<Vec<Result<(), VerificationError>>, ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    let bytes = if public_input_path == "-" {
+        read_stdin()?
+    } else {
+        fs::read(public_input_path).map_err(|e| ExpanderError::WitnessLoad(e.into()))?
+    };
+    let inputs = circuit
+        .load_public_input_batch_bytes(&bytes)
+        .map_err(ExpanderError::WitnessLoad)?;
+    if inputs.num_instances != batch.num_instances {
+        return Err(ExpanderError::ProofBatchInstances {
+            witness: inputs.num_instances,
+            proof: batch.num_instances,
+        });
+    }
+    if batch.packages.len() != inputs.packs.len() {
+        return Err(ExpanderError::ProofBatchCount {
+            expected: inputs.packs.len(),
+            found: batch.packages.len(),
+        });
+    }
+
+    let pack_size = C::get_field_pack_size();
+    let mut results = Vec::with_capacity(batch.num_instances);
+    for ((_, public_input), package) in inputs.packs.into_iter().zip(&batch.packages) {
+        circuit.public_input = public_input;
+        let result = try_verify_package_on(config, &mut circuit, package)?;
+        let lanes = pack_size.min(batch.num_instances - results.len());
+        results.extend(std::iter::repeat(result).take(lanes));
+    }
+    Ok(results)
+}
+
+/// Whether the proof package bytes are a valid proof of the circuit file on the public inputs
+/// of the file, see `Circuit::load_public_input_file`, which may also be the whole witness.
+/// A proof that does not verify is `Ok(false)`, only the files that cannot be read are errors.
+pub fn verify<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_path: &str,
+    proof_bytes: &[u8],
+) -> Result<bool, ExpanderError> {
+    let package = ProofPackage::deserialize_from(&mut &proof_bytes[..])?;
+    verify_package(config, circuit_path, public_input_path, &package)
+}
+
+/// As `verify`, for a deserialized proof package. A package with the public inputs of the
+/// processes of the prover is verified on each process alone, see
+/// `Verifier::try_verify_gathered`: on the public inputs of the file for a single process,
+/// and for several, on those of each rank, the file being a witness batch of the public input
+/// files of the ranks, see `load_rank_public_inputs`. Without, as for the packages before
+/// version 5, each process reads the public inputs of its shard, and the root verifies for all.
+pub fn verify_package<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_path: &str,
+    package: &ProofPackage,
+) -> Result<bool, ExpanderError> {
+    Ok(try_verify_package(config, circuit_path, public_input_path, package)?.is_ok())
+}
+
+/// As `verify_package`, with why a proof that does not verify is rejected, see
+/// `Verifier::try_mpi_verify`. The public inputs are read from stdin for `-`.
+pub fn try_verify_package<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_path: &str,
+    package: &ProofPackage,
+) -> Result<Result<(), VerificationError>, ExpanderError> {
+    check_field_type::<C>(package)?;
+    if public_input_path == "-" {
+        return try_verify_package_bytes(config, circuit_path, &read_stdin()?, package);
+    }
+    if is_witness_batch(public_input_path)? {
+        let bytes =
+            fs::read(public_input_path).map_err(|e| ExpanderError::WitnessLoad(e.into()))?;
+        return try_verify_package_bytes(config, circuit_path, &bytes, package);
+    }
+    let mut circuit = load_circuit_and_public_input::<C>(circuit_path, public_input_path)?;
+    if let Err(e) = check_private_input_commitment_on(config, &mut circuit, package, |circuit| {
+        circuit.try_load_witness_file(public_input_path)
+    }) {
+        return Ok(Err(e));
+    }
+    try_verify_package_on(config, &mut circuit, package)
+}
+
+/// As `try_verify_package`, with the public inputs of the public values file in place of those
+/// of the public input file, see `prove_with_public_values`.
+pub fn try_verify_package_with_public_values<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_path: &str,
+    public_values_path: &str,
+    package: &ProofPackage,
+) -> Result<Result<(), VerificationError>, ExpanderError> {
+    check_field_type::<C>(package)?;
+    let mut circuit = load_circuit_and_public_input::<C>(circuit_path, public_input_path)?;
+    if let Err(e) = check_private_input_commitment_on(config, &mut circuit, package, |circuit| {
+        circuit.try_load_witness_file(public_input_path)
+    }) {
+        return Ok(Err(e));
+    }
+    load_public_values(&mut circuit, &read_public_values::<C>(public_values_path)?)?;
+    try_verify_package_on(config, &mut circuit, package)
+}
+
+/// As `try_verify_package`, on the public input bytes, or the witness bytes, rather than a file.
+pub fn try_verify_package_bytes<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    public_input_bytes: &[u8],
+    package: &ProofPackage,
+) -> Result<Result<(), VerificationError>, ExpanderError> {
+    check_field_type::<C>(package)?;
+    if public_input_bytes.starts_with(&WITNESS_BATCH_MAGIC) {
+        let mut circuit = load_circuit::<C>(circuit_path)?;
+        let rank_public_inputs = load_rank_public_inputs(&mut circuit, public_input_bytes)?;
+        return try_verify_package_of_ranks(
+            config,
+            &mut circuit,
+            Some(&rank_public_inputs),
+            package,
+        );
+    }
+    let mut circuit = load_circuit_and_public_input_bytes::<C>(circuit_path, public_input_bytes)?;
+    if let Err(e) = check_private_input_commitment_on(config, &mut circuit, package, |circuit| {
+        circuit.try_load_witness_bytes(public_input_bytes, false)
+    }) {
+        return Ok(Err(e));
+    }
+    try_verify_package_on(config, &mut circuit, package)
+}
+
+fn check_field_type<C: GKRConfig>(package: &ProofPackage) -> Result<(), ExpanderError> {
+    if package.field_type != C::FIELD_TYPE {
+        return Err(ExpanderError::FieldTypeMismatch {
+            circuit: C::FIELD_TYPE,
+            proof: package.field_type.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Fails with `VerificationError::InputCommitmentMismatch` if the package commits to other
+/// public inputs than the ones of the circuit, before verifying it. A package without the
+/// commitment, e.g., of a version before 9, passes with a warning.
+pub fn check_public_input_commitment<C: GKRConfig>(
+    circuit: &Circuit<C>,
+    package: &ProofPackage,
+) -> Result<(), VerificationError> {
+    match package.public_input_commitment {
+        None => {
+            warn!("The proof does not commit to its public inputs, which are left unchecked.");
+            Ok(())
+        }
+        Some(commitment) if commitment != input_commitment::<C>(&circuit.public_input) => {
+            Err(VerificationError::InputCommitmentMismatch("public inputs"))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// As `check_public_input_commitment`, for the private inputs a package committing to the whole
+/// witness commits to, loaded into the circuit by `load_witness`, e.g., from the file of the
+/// public inputs. The commitment is left unchecked if the private inputs cannot be loaded, as
+/// of a public input file.
+pub fn check_private_input_commitment<C: GKRConfig>(
+    circuit: &mut Circuit<C>,
+    package: &ProofPackage,
+    load_witness: impl FnOnce(&mut Circuit<C>) -> Result<(), CircuitError>,
+) -> Result<(), VerificationError> {
+    let Some(commitment) = package.private_input_commitment else {
+        return Ok(());
+    };
+    if let Err(e) = load_witness(circuit) {
+        info!(
+            "The commitment of the private inputs is left unchecked: {}",
+            e
+        );
+        return Ok(());
+    }
+    if commitment != input_commitment::<C>(&circuit.layers[0].input_vals) {
+        return Err(VerificationError::InputCommitmentMismatch("private inputs"));
+    }
+    Ok(())
+}
+
+/// Whether the package is verified by each process alone, on the public inputs of the root of
+/// the prover, rather than by the processes of an mpi verifier each on the public inputs of its
+/// shard, which the commitments of the package are not of.
+fn is_verified_alone<C: GKRConfig>(config: &Config<C>, package: &ProofPackage) -> bool {
+    package.public_input.is_some() || config.mpi_config.world_size() == 1
+}
+
+/// `check_private_input_commitment`, unless by the processes of an mpi verifier.
+fn check_private_input_commitment_on<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &mut Circuit<C>,
+    package: &ProofPackage,
+    load_witness: impl FnOnce(&mut Circuit<C>) -> Result<(), CircuitError>,
+) -> Result<(), VerificationError> {
+    if !is_verified_alone(config, package) {
+        return Ok(());
+    }
+    check_private_input_commitment(circuit, package, load_witness)
+}
+
+/// The public inputs of each process of an mpi prover, in the order of the ranks, of a witness
+/// batch of their public input files, or witness files, see `split_witness_batch`. The circuit
+/// is left with those of the root.
+pub fn load_rank_public_inputs<C: GKRConfig>(
+    circuit: &mut Circuit<C>,
+    batch_bytes: &[u8],
+) -> Result<Vec<Vec<C::SimdCircuitField>>, ExpanderError> {
+    let ranks =
+        split_witness_batch(batch_bytes).map_err(|e| ExpanderError::WitnessLoad(e.into()))?;
+    let mut rank_public_inputs = ranks
+        .iter()
+        .rev()
+        .map(|rank_bytes| {
+            circuit
+                .try_load_public_input_bytes(rank_bytes, false)
+                .map_err(ExpanderError::WitnessLoad)?;
+            Ok(circuit.public_input.clone())
+        })
+        .collect::<Result<Vec<_>, ExpanderError>>()?;
+    rank_public_inputs.reverse();
+    Ok(rank_public_inputs)
+}
+
+/// As `try_verify_package`, on a circuit already loaded with its public inputs, e.g., kept
+/// across the proofs to verify. The commitment of the public inputs of the package is checked
+/// first, see `check_public_input_commitment`, unless by the processes of an mpi verifier.
+/// A package of several processes needs the public inputs of each, see
+/// `try_verify_package_of_ranks`.
+pub fn try_verify_package_on<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &mut Circuit<C>,
+    package: &ProofPackage,
+) -> Result<Result<(), VerificationError>, ExpanderError> {
+    try_verify_package_of_ranks(config, circuit, None, package)
+}
+
+/// As `try_verify_package_on`, given the public inputs of each process of the prover, e.g., of
+/// `load_rank_public_inputs`, the first being those of the circuit. A package of the public
+/// inputs of several processes is verified on those given, and fails if it records others: the
+/// public inputs of the ranks but the root are not taken from the prover.
+pub fn try_verify_package_of_ranks<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &mut Circuit<C>,
+    rank_public_inputs: Option<&[Vec<C::SimdCircuitField>]>,
+    package: &ProofPackage,
+) -> Result<Result<(), VerificationError>, ExpanderError> {
+    check_field_type::<C>(package)?;
+    if is_verified_alone(config, package) {
+        if let Err(e) = check_public_input_commitment(circuit, package) {
+            return Ok(Err(e));
+        }
+    }
+    let config = &with_package_seed(config, package)?;
+    if let (Some(world_size), Some(public_input)) =
+        (package.mpi_world_size, package.public_input::<C>()?)
+    {
+        if !world_size.is_power_of_two() {
+            return Err(ProofPackageError::InvalidMPIWorldSize(world_size).into());
+        }
+        let rank_public_input = match rank_public_inputs {
+            Some(rank_public_inputs) if rank_public_inputs.len() == world_size => {
+                rank_public_inputs.concat()
+            }
+            Some(rank_public_inputs) => {
+                return Err(ExpanderError::RankPublicInputCount {
+                    expected: world_size,
+                    found: rank_public_inputs.len(),
+                })
+            }
+            // those of a single process are the ones of the circuit
+            None if world_size == 1 => circuit.public_input.clone(),
+            None => return Err(ExpanderError::RankPublicInputsMissing(world_size)),
+        };
+        if public_input != rank_public_input {
+            return Ok(Err(VerificationError::PublicInputMismatch));
+        }
+        let config = Config {
+            mpi_config: MPIConfig::new_for_verifier(world_size as i32),
+            ..config.clone()
+        };
+        let (proof, claimed_v) = unpack_proof(&config, package)?;
+        check_scheme(circuit, &config.gkr_scheme)?;
+        return Ok(Verifier::new(&config).try_verify_gathered(
+            circuit,
+            &rank_public_input,
+            &claimed_v,
+            &proof,
+        ));
+    }
+    let (proof, claimed_v) = unpack_proof(config, package)?;
+    check_scheme(circuit, &config.gkr_scheme)?;
+    Ok(Verifier::new(config).try_mpi_verify(circuit, &claimed_v, &proof))
+}
+
+/// The public input, or witness, and proof file pairs of the batch manifest, one pair per line
+/// separated by whitespace. The empty lines and the lines starting with `#` are skipped.
+pub fn read_batch_manifest(manifest_path: &str) -> Result<Vec<(String, String)>, ExpanderError> {
+    let manifest = fs::read_to_string(manifest_path).map_err(ExpanderError::ManifestRead)?;
+    manifest
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(
+            |(line, pair)| match pair.split_whitespace().collect::<Vec<_>>()[..] {
+                [witness_path, proof_path] => {
+                    Ok((witness_path.to_string(), proof_path.to_string()))
+                }
+                _ => Err(ExpanderError::InvalidManifestLine { line }),
+            },
+        )
+        .collect()
+}
+
+/// Whether each proof file of the pairs is a valid proof of the circuit file on its public
+/// input file, see `Verifier::verify_batch`. The circuit is loaded once, and a pair whose public
+/// inputs or proof cannot be read is an error of its own, the rest of the batch being verified
+/// regardless.
+pub fn verify_batch<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    pairs: &[(String, String)],
+    hex: bool,
+) -> Result<Vec<Result<bool, ExpanderError>>, ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    check_scheme(&circuit, &config.gkr_scheme)?;
+
+    let mut results = vec![];
+    // the proofs of each seed are verified together, with the config of the seed
+    let mut inputs_by_seed = BTreeMap::<_, (Vec<usize>, Vec<_>)>::new();
+    for (public_input_path, proof_path) in pairs {
+        let input = circuit
+            .try_load_public_input_file(public_input_path)
+            .map_err(ExpanderError::WitnessLoad)
+            .and_then(|_| {
+                let package = read_proof_package(proof_path, hex)?;
+                if package.field_type != C::FIELD_TYPE {
+                    return Err(ExpanderError::FieldTypeMismatch {
+                        circuit: C::FIELD_TYPE,
+                        proof: package.field_type,
+                    });
+                }
+                let (proof, claimed_v) = unpack_proof(config, &package)?;
+                let seed = package.prover_seed.or(config.prover_seed);
+                Ok((seed, (circuit.public_input.clone(), claimed_v, proof)))
+            });
+        match input {
+            Ok((seed, input)) => {
+                // filled in by the verification of the batch
+                let (indices, inputs) = inputs_by_seed.entry(seed).or_default();
+                indices.push(results.len());
+                inputs.push(input);
+                results.push(Ok(false));
+            }
+            Err(e) => results.push(Err(e)),
+        }
+    }
+
+    for (seed, (indices, inputs)) in inputs_by_seed {
+        let config = Config {
+            prover_seed: seed,
+            ..config.clone()
+        };
+        let verified = Verifier::new(&config).verify_batch(&circuit, &inputs);
+        for (i, verified) in indices.into_iter().zip(verified) {
+            results[i] = Ok(verified);
+        }
+    }
+    Ok(results)
+}
+
+/// The config of the seed the package records, if any, the verifier absorbing the seed of the
+/// prover, see `ProofPackage::prover_seed`. That of the config otherwise, e.g., for the packages
+/// before version 10. Fails if the config is of another seed.
+fn with_package_seed<C: GKRConfig>(
+    config: &Config<C>,
+    package: &ProofPackage,
+) -> Result<Config<C>, ProofPackageError> {
+    check_prover_seed(config, package)?;
+    Ok(Config {
+        prover_seed: package.prover_seed.or(config.prover_seed),
+        ..config.clone()
+    })
+}
+
+/// A seed given to the verifier is checked against the one of the package, if any.
+fn check_prover_seed<C: GKRConfig>(
+    config: &Config<C>,
+    package: &ProofPackage,
+) -> Result<(), ProofPackageError> {
+    match (&config.prover_seed, &package.prover_seed) {
+        (Some(seed), Some(package_seed)) if seed != package_seed => {
+            Err(ProofPackageError::ProverSeedMismatch)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fails unless the scheme proves all the gates of the circuit: GKR^2 only the univariate
+/// gates, see `SchemeSelector`, and vanilla GKR all the others.
+pub fn check_scheme<C: GKRConfig>(
+    circuit: &Circuit<C>,
+    gkr_scheme: &GKRScheme,
+) -> Result<(), ExpanderError> {
+    let supported = match gkr_scheme {
+        GKRScheme::Vanilla => circuit.layers.iter().all(|layer| layer.uni.is_empty()),
+        GKRScheme::GkrSquare => SchemeSelector::select(circuit) == GKRScheme::GkrSquare,
+    };
+    if supported {
+        Ok(())
+    } else {
+        Err(ExpanderError::UnsupportedScheme(gkr_scheme.clone()))
+    }
+}
+
+/// The circuit of the file, with its stats logged.
+pub fn load_circuit<C: GKRConfig>(circuit_path: &str) -> Result<Circuit<C>, ExpanderError> {
+    let circuit =
+        Circuit::<C>::try_load_circuit(circuit_path).map_err(ExpanderError::CircuitLoad)?;
+    info!("Circuit stats:\n{}", circuit.stats());
+    Ok(circuit)
+}
+
+/// The circuit of the bytes of a circuit file, with its stats logged. It panics on malformed
+/// bytes, see `FuzzSafeCircuitLoader` for untrusted ones.
+pub fn load_circuit_from_bytes<C: GKRConfig>(bytes: &[u8]) -> Circuit<C> {
+    let circuit = Circuit::<C>::load_circuit_from_bytes(bytes);
+    info!("Circuit stats:\n{}", circuit.stats());
+    circuit
+}
+
+/// The circuit of the file, with the witness of the file, or of stdin for `-`, loaded.
+pub fn load_circuit_and_witness<C: GKRConfig>(
+    circuit_path: &str,
+    witness_path: &str,
+) -> Result<Circuit<C>, ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    if witness_path == "-" {
+        circuit
+            .try_load_witness_bytes(&read_stdin()?, false)
+            .map_err(ExpanderError::WitnessLoad)?;
+    } else {
+        circuit
+            .try_load_witness_file(witness_path)
+            .map_err(ExpanderError::WitnessLoad)?;
+    }
+    Ok(circuit)
+}
+
+/// The circuit of the file, with the public inputs of the file loaded, see
+/// `Circuit::load_public_input_file`.
+pub fn load_circuit_and_public_input<C: GKRConfig>(
+    circuit_path: &str,
+    public_input_path: &str,
+) -> Result<Circuit<C>, ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    circuit
+        .try_load_public_input_file(public_input_path)
+        .map_err(ExpanderError::WitnessLoad)?;
+    Ok(circuit)
+}
+
+/// As `load_circuit_and_public_input`, with the public inputs of the bytes.
+pub fn load_circuit_and_public_input_bytes<C: GKRConfig>(
+    circuit_path: &str,
+    public_input_bytes: &[u8],
+) -> Result<Circuit<C>, ExpanderError> {
+    let mut circuit = load_circuit::<C>(circuit_path)?;
+    circuit
+        .try_load_public_input_bytes(public_input_bytes, false)
+        .map_err(ExpanderError::WitnessLoad)?;
+    Ok(circuit)
+}
+
+/// Write the public inputs of the witness file of the circuit file to the output file, in the
+/// witness format without the private inputs, see `Circuit::public_input_bytes`. Those of a
+/// witness batch are a batch of the public inputs of each instance.
+pub fn write_public_input<C: GKRConfig>(
+    circuit_path: &str,
+    witness_path: &str,
+    output_path: &str,
+) -> Result<(), ExpanderError> {
+    let circuit = load_circuit::<C>(circuit_path)?;
+    let witness_bytes = fs::read(witness_path).map_err(|e| ExpanderError::WitnessLoad(e.into()))?;
+    let bytes = if witness_bytes.starts_with(&WITNESS_BATCH_MAGIC) {
+        let instances = split_witness_batch(&witness_bytes)
+            .map_err(|e| ExpanderError::WitnessLoad(e.into()))?
+            .into_iter()
+            .map(|witness_bytes| circuit.public_input_bytes(witness_bytes))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ExpanderError::WitnessLoad)?;
+        encode_witness_batch(&instances.iter().map(Vec::as_slice).collect::<Vec<_>>())
+    } else {
+        circuit
+            .public_input_bytes(&witness_bytes)
+            .map_err(ExpanderError::WitnessLoad)?
+    };
+    fs::write(output_path, bytes).map_err(ExpanderError::PublicInputWrite)
+}
+
+/// The public inputs of the bytes of a public values file: their number as 8 bytes little
+/// endian, then each value in its canonical bytes, those of every witness.
+pub fn parse_public_values<C: GKRConfig>(
+    bytes: &[u8],
+) -> Result<Vec<C::CircuitField>, ExpanderError> {
+    let mut reader = bytes;
+    let values = Vec::<C::CircuitField>::deserialize_from(&mut reader)
+        .map_err(|e| ExpanderError::InvalidPublicValues(e.to_string()))?;
+    if !reader.is_empty() {
+        return Err(ExpanderError::InvalidPublicValues(format!(
+            "{} bytes after the last value",
+            reader.len()
+        )));
+    }
+    Ok(values)
+}
+
+/// The public inputs of the public values file, see `parse_public_values`.
+pub fn read_public_values<C: GKRConfig>(
+    public_values_path: &str,
+) -> Result<Vec<C::CircuitField>, ExpanderError> {
+    let bytes = fs::read(public_values_path).map_err(ExpanderError::PublicValuesRead)?;
+    parse_public_values::<C>(&bytes)
+}
+
+/// Set the public inputs of the circuit to the values, in every simd lane, checked to be as
+/// many as the const gates of the circuit read.
+pub fn load_public_values<C: GKRConfig>(
+    circuit: &mut Circuit<C>,
+    values: &[C::CircuitField],
+) -> Result<(), ExpanderError> {
+    let expected = circuit.stats().public_input_size;
+    if values.len() != expected {
+        return Err(ExpanderError::PublicValuesCount {
+            expected,
+            found: values.len(),
+        });
+    }
+    let pack_size = C::get_field_pack_size();
+    circuit.public_input = values
+        .iter()
+        .map(|value| C::SimdCircuitField::pack(&vec![*value; pack_size]))
+        .collect();
+    Ok(())
+}
+
+/// The proof packaged as by `prove_with_compression`, with the public inputs of the circuit,
+/// gathered from all the processes with mpi.
+fn package_proof_with_public_input<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &Circuit<C>,
+    proof: &Proof,
+    claimed_v: &[C::ChallengeField],
+    compression: CompressionCodec,
+    commit_witness: bool,
+) -> Result<Vec<u8>, FieldSerdeError> {
+    let public_input = gather_public_input::<C>(&config.mpi_config, &circuit.public_input);
+    let mut bytes = Vec::new();
+    ProofPackage::new(config, proof, claimed_v)?
+        .with_compression(compression)
+        .with_public_input::<C>(&public_input)?
+        .with_input_commitment::<C>(
+            commit_witness.then_some(&circuit.layers[0].input_vals[..]),
+            &circuit.public_input,
+        )
+        .serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// As `package_proof`, committing to the public inputs of the circuit, see
+/// `ProofPackage::with_input_commitment`.
+pub fn package_proof_with_input_commitment<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &Circuit<C>,
+    proof: &Proof,
+    claimed_v: &[C::ChallengeField],
+    compression: CompressionCodec,
+) -> Result<Vec<u8>, FieldSerdeError> {
+    let mut bytes = Vec::new();
+    ProofPackage::new(config, proof, claimed_v)?
+        .with_compression(compression)
+        .with_input_commitment::<C>(None, &circuit.public_input)
+        .serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The proof packaged with the claimed values and the config.
+pub fn package_proof<C: GKRConfig>(
+    config: &Config<C>,
+    proof: &Proof,
+    claimed_v: &[C::ChallengeField],
+    compression: CompressionCodec,
+) -> Result<Vec<u8>, FieldSerdeError> {
+    let mut bytes = Vec::new();
+    ProofPackage::new(config, proof, claimed_v)?
+        .with_compression(compression)
+        .serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The proof and claimed values of the package, if it was generated with the scheme, the
+/// transcript hash, the polynomial commitment and the mpi world size of the config.
+pub fn unpack_proof<C: GKRConfig>(
+    config: &Config<C>,
+    package: &ProofPackage,
+) -> Result<(Proof, Vec<C::ChallengeField>), ProofPackageError> {
+    if package.gkr_scheme != config.gkr_scheme {
+        return Err(ProofPackageError::GKRSchemeMismatch {
+            expected: config.gkr_scheme.clone(),
+            found: package.gkr_scheme.clone(),
+        });
+    }
+    if package.fiat_shamir_hash != config.fiat_shamir_hash() {
+        return Err(ProofPackageError::FiatShamirHashMismatch {
+            expected: config.fiat_shamir_hash(),
+            found: package.fiat_shamir_hash.clone(),
+        });
+    }
+    if package.polynomial_commitment_type != config.polynomial_commitment_type {
+        return Err(ProofPackageError::PolynomialCommitmentMismatch {
+            expected: config.polynomial_commitment_type.clone(),
+            found: package.polynomial_commitment_type.clone(),
+        });
+    }
+    check_prover_seed(config, package)?;
+    if let Some(world_size) = package.mpi_world_size {
+        if world_size != config.mpi_config.world_size() {
+            return Err(ProofPackageError::MPIWorldSizeMismatch {
+                expected: config.mpi_config.world_size(),
+                found: world_size,
+            });
+        }
+    }
+    Ok((package.proof.clone(), package.claimed_v::<C>()?))
+}
+
+/// The proof package of the file, or of stdin for `-`, as hex with `hex`.
+pub fn read_proof_package(proof_path: &str, hex: bool) -> Result<ProofPackage, ExpanderError> {
+    let bytes = if proof_path == "-" {
+        read_stdin()?
+    } else {
+        fs::read(proof_path).map_err(ExpanderError::ProofRead)?
+    };
+    parse_proof_package(bytes, hex)
+}
+
+/// The proof package of the file, or of stdin for `-`, or the proof batch, see
+/// `PROOF_BATCH_MAGIC`, as hex with `hex`.
+pub fn read_proof_file(proof_path: &str, hex: bool) -> Result<ProofFile, ExpanderError> {
+    let bytes = if proof_path == "-" {
+        read_stdin()?
+    } else {
+        fs::read(proof_path).map_err(ExpanderError::ProofRead)?
+    };
+    let decoded;
+    let batch_bytes = if hex {
+        decoded = decode_hex(hex_str(&bytes)?.trim()).map_err(ProofPackageError::Hex)?;
+        &decoded
+    } else {
+        &bytes
+    };
+    let Some(batch_bytes) = batch_bytes.strip_prefix(&PROOF_BATCH_MAGIC[..]) else {
+        return parse_proof_package(bytes, hex).map(ProofFile::Package);
+    };
+    let (num_instances, packages) = batch_bytes
+        .split_first_chunk::<8>()
+        .and_then(|(num_instances, packages)| {
+            Some((
+                usize::try_from(u64::from_le_bytes(*num_instances)).ok()?,
+                decode_length_prefixed(packages)?,
+            ))
+        })
+        .ok_or(ProofPackageError::Truncated)?;
+    if packages.is_empty() {
+        return Err(ProofPackageError::Truncated.into());
+    }
+    let packages = packages
+        .iter()
+        .map(|package| ProofPackage::deserialize_from(&mut package.as_slice()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ProofFile::Batch(ProofBatch {
+        num_instances,
+        packages,
+    }))
+}
+
+fn hex_str(bytes: &[u8]) -> Result<&str, ExpanderError> {
+    std::str::from_utf8(bytes)
+        .map_err(|e| ExpanderError::ProofRead(std::io::Error::new(ErrorKind::InvalidData, e)))
+}
+
+fn parse_proof_package(bytes: Vec<u8>, hex: bool) -> Result<ProofPackage, ExpanderError> {
+    if hex {
+        Ok(ProofPackage::from_hex_string(hex_str(&bytes)?.trim())?)
+    } else {
+        Ok(ProofPackage::deserialize_from(&mut bytes.as_slice())?)
+    }
+}
+
+/// The witness and proof package of stdin, framed as the bodies of `/verify`, see
+/// `split_witness_and_proof`, with the proof as hex with `hex`.
+pub fn read_piped_witness_and_proof(hex: bool) -> Result<(Vec<u8>, ProofPackage), ExpanderError> {
+    let bytes = read_stdin()?;
+    let (witness_bytes, proof_bytes) =
+        split_witness_and_proof(&bytes).map_err(ExpanderError::StdinFraming)?;
+    let package = parse_proof_package(proof_bytes.to_vec(), hex)?;
+    Ok((witness_bytes.to_vec(), package))
+}
+
+/// All of stdin, as bytes whatever the platform. An empty stdin, e.g., of a writer that closed
+/// it before writing, is an error rather than an empty witness or proof.
+fn read_stdin() -> Result<Vec<u8>, ExpanderError> {
+    let mut bytes = vec![];
+    std::io::stdin()
+        .lock()
+        .read_to_end(&mut bytes)
+        .map_err(ExpanderError::StdinRead)?;
+    if bytes.is_empty() {
+        return Err(ExpanderError::StdinRead(std::io::Error::new(
+            ErrorKind::UnexpectedEof,
+            "nothing to read",
+        )));
+    }
+    Ok(bytes)
+}
+
+/// The witness and proof bytes of a verify request, or of the stdin of `verify - -`, each
+/// prefixed by its length, or the description of the framing error of a body too short for
+/// them.
+pub fn split_witness_and_proof(bytes: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    split_framed_pair(bytes, ["witness", "proof"])
+}
+
+/// The witness and public values bytes of a prove request with an `X-Public-Values: framed`
+/// header, framed as by `split_witness_and_proof`.
+pub fn split_witness_and_public_values(bytes: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    split_framed_pair(bytes, ["witness", "public values"])
+}
+
+/// The two parts of the bytes, after their lengths, as little-endian `u64`s.
+fn split_framed_pair<'a>(
+    bytes: &'a [u8],
+    names: [&str; 2],
+) -> Result<(&'a [u8], &'a [u8]), String> {
+    let Some(lengths) = bytes.get(..16) else {
+        return Err(format!(
+            "body of {} bytes, short of the 16 bytes of the {} and {} lengths",
+            bytes.len(),
+            names[0],
+            names[1]
+        ));
+    };
+    let length =
+        |offset: usize| u64::from_le_bytes(lengths[offset..offset + 8].try_into().unwrap());
+    let (first_len, second_len) = (length(0), length(8));
+    let too_long = || {
+        format!(
+            "{} of {} bytes and {} of {} bytes, past the end of the body of {} bytes",
+            names[0],
+            first_len,
+            names[1],
+            second_len,
+            bytes.len()
+        )
+    };
+    let first_end = usize::try_from(first_len)
+        .ok()
+        .and_then(|len| len.checked_add(16))
+        .ok_or_else(too_long)?;
+    let second_end = usize::try_from(second_len)
+        .ok()
+        .and_then(|len| len.checked_add(first_end))
+        .ok_or_else(too_long)?;
+    match (bytes.get(16..first_end), bytes.get(first_end..second_end)) {
+        (Some(first), Some(second)) => Ok((first, second)),
+        _ => Err(too_long()),
+    }
+}
+
+/// The file of the manifest of the field proof of the file, see `write_field_proof`.
+pub fn field_proof_manifest_path(proof_path: &str) -> String {
+    format!("{}.manifest.json", proof_path)
+}
+
+fn check_field_proof<C: GKRConfig>(config: &Config<C>) -> Result<(), FieldProofError> {
+    if config.gkr_scheme != GKRScheme::Vanilla
+        || config.polynomial_commitment_type != PolynomialCommitmentType::Raw
+    {
+        return Err(FieldProofError::UnsupportedProof);
+    }
+    Ok(())
+}
+
+/// The proof and the claimed values of the package as the elements of a field proof of the
+/// circuit, with their manifest, see `ProofFieldElements`.
+pub fn to_field_proof<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &Circuit<C>,
+    package: &ProofPackage,
+) -> Result<(FieldProofManifest, Vec<C::ChallengeField>), ExpanderError> {
+    check_field_type::<C>(package)?;
+    check_field_proof(config)?;
+    let (proof, claimed_v) = unpack_proof(config, package)?;
+    let layout = ProofLayout::new(circuit, config);
+    let manifest = FieldProofManifest::new::<C>(&layout, claimed_v.len());
+    let mut elements = proof.to_field_elements::<C>(&layout)?;
+    elements.extend_from_slice(&claimed_v);
+    Ok((manifest, elements))
+}
+
+/// The proof package of the config of the field proof of the circuit, the inverse of
+/// `to_field_proof`.
+pub fn from_field_proof<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &Circuit<C>,
+    manifest: &FieldProofManifest,
+    elements: &[C::ChallengeField],
+) -> Result<ProofPackage, ExpanderError> {
+    check_field_proof(config)?;
+    let layout = ProofLayout::new(circuit, config);
+    if *manifest != FieldProofManifest::new::<C>(&layout, manifest.claimed_v) {
+        return Err(FieldProofError::ManifestMismatch.into());
+    }
+    if elements.len() != manifest.num_elements() {
+        return Err(FieldProofError::ElementCountMismatch {
+            expected: manifest.num_elements(),
+            found: elements.len(),
+        }
+        .into());
+    }
+    let (proof, claimed_v) = elements.split_at(elements.len() - manifest.claimed_v);
+    let proof = Proof::from_field_elements::<C>(proof, &layout)?;
+    Ok(ProofPackage::new(config, &proof, claimed_v)?)
+}
+
+/// Write the proof package bytes of the circuit file as a field proof, the elements to the file
+/// and the manifest as JSON to `field_proof_manifest_path`.
+pub fn write_field_proof<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    package_bytes: &[u8],
+    output_path: &str,
+) -> Result<(), ExpanderError> {
+    let package = ProofPackage::deserialize_from(&mut &package_bytes[..])?;
+    let circuit = load_circuit::<C>(circuit_path)?;
+    let (manifest, elements) = to_field_proof(config, &circuit, &package)?;
+    let mut bytes = vec![];
+    VecFieldSerializer::serialize_batch(&elements, &mut bytes)?;
+    fs::write(output_path, bytes).map_err(ExpanderError::ProofWrite)?;
+    let manifest = serde_json::to_string_pretty(&manifest).map_err(FieldProofError::from)?;
+    fs::write(field_proof_manifest_path(output_path), manifest).map_err(ExpanderError::ProofWrite)
+}
+
+/// The proof package of the field proof of `write_field_proof`.
+pub fn read_field_proof<C: GKRConfig>(
+    config: &Config<C>,
+    circuit_path: &str,
+    proof_path: &str,
+) -> Result<ProofPackage, ExpanderError> {
+    let bytes = fs::read(proof_path).map_err(ExpanderError::ProofRead)?;
+    let manifest =
+        fs::read(field_proof_manifest_path(proof_path)).map_err(ExpanderError::ProofRead)?;
+    let manifest: FieldProofManifest =
+        serde_json::from_slice(&manifest).map_err(FieldProofError::from)?;
+    let elements = VecFieldSerializer::<C::ChallengeField>::deserialize_batch(&bytes)
+        .map_err(FieldProofError::from)?;
+    let circuit = load_circuit::<C>(circuit_path)?;
+    from_field_proof(config, &circuit, &manifest, &elements)
+}
+
+/// Write the proof package bytes to the file, or to stdout for `-`, as hex with `hex`.
+pub fn write_proof_package(
+    output_path: &str,
+    bytes: Vec<u8>,
+    hex: bool,
+) -> Result<(), ExpanderError> {
+    let bytes = if hex {
+        (encode_hex(&bytes) + "\n").into_bytes()
+    } else {
+        bytes
+    };
+    if output_path == "-" {
+        std::io::stdout()
+            .write_all(&bytes)
+            .map_err(ExpanderError::ProofWrite)
+    } else {
+        fs::write(output_path, bytes).map_err(ExpanderError::ProofWrite)
+    }
+}
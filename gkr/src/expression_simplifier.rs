@@ -0,0 +1,226 @@
+//! Removing the algebraically redundant gates of compiled circuits.
+//!
+//! Each identity is a `SimplificationRule` rewriting the gates of one layer, knowing which
+//! output wires of the layer below hold fixed values. The rules are applied to all the layers
+//! until none of them applies anymore. Only the gates with constant coefficients are rewritten:
+//! the random coefficients are only known once the transcript is set up.
+
+use std::collections::HashMap;
+
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd};
+use config::GKRConfig;
+
+use crate::SumcheckDegreeOptimizer;
+
+pub trait SimplificationRule<C: GKRConfig> {
+    fn name(&self) -> &str;
+
+    /// Rewrite the gates of the layer, and return the number of simplified gates.
+    /// `fixed_inputs[i]` is the value of the input wire `i` if it is fixed by the circuit, and
+    /// is empty for the input layer.
+    fn apply(&self, layer: &mut CircuitLayer<C>, fixed_inputs: &[Option<C::CircuitField>])
+        -> usize;
+}
+
+/// `a + 0 = a`: the add and const gates with a zero coefficient are removed.
+pub struct AddZeroRule;
+
+impl<C: GKRConfig> SimplificationRule<C> for AddZeroRule {
+    fn name(&self) -> &str {
+        "a + 0 = a"
+    }
+
+    fn apply(&self, layer: &mut CircuitLayer<C>, _: &[Option<C::CircuitField>]) -> usize {
+        let is_zero = |coef_type: &CoefType, coef: &C::CircuitField| {
+            *coef_type == CoefType::Constant && coef.is_zero()
+        };
+        let n = layer.add.len() + layer.const_.len();
+        layer.add.retain(|g| !is_zero(&g.coef_type, &g.coef));
+        layer.const_.retain(|g| !is_zero(&g.coef_type, &g.coef));
+        n - layer.add.len() - layer.const_.len()
+    }
+}
+
+/// `a * 0 = 0`: the mul gates with a zero coefficient or a zero input are removed.
+pub struct MulZeroRule;
+
+impl<C: GKRConfig> SimplificationRule<C> for MulZeroRule {
+    fn name(&self) -> &str {
+        "a * 0 = 0"
+    }
+
+    fn apply(
+        &self,
+        layer: &mut CircuitLayer<C>,
+        fixed_inputs: &[Option<C::CircuitField>],
+    ) -> usize {
+        let is_zero_wire = |i: usize| {
+            fixed_inputs
+                .get(i)
+                .is_some_and(|v| *v == Some(C::CircuitField::ZERO))
+        };
+        let n = layer.mul.len();
+        layer.mul.retain(|g| {
+            g.coef_type != CoefType::Constant
+                || !(g.coef.is_zero() || is_zero_wire(g.i_ids[0]) || is_zero_wire(g.i_ids[1]))
+        });
+        n - layer.mul.len()
+    }
+}
+
+/// `a * 1 = a`: the mul gates with an input fixed to one become add gates.
+pub struct MulOneRule;
+
+impl<C: GKRConfig> SimplificationRule<C> for MulOneRule {
+    fn name(&self) -> &str {
+        "a * 1 = a"
+    }
+
+    fn apply(
+        &self,
+        layer: &mut CircuitLayer<C>,
+        fixed_inputs: &[Option<C::CircuitField>],
+    ) -> usize {
+        let is_one_wire = |i: usize| {
+            fixed_inputs
+                .get(i)
+                .is_some_and(|v| *v == Some(C::CircuitField::ONE))
+        };
+        let n = layer.mul.len();
+        let mut remaining = Vec::with_capacity(n);
+        for g in layer.mul.drain(..) {
+            let other = if is_one_wire(g.i_ids[0]) {
+                g.i_ids[1]
+            } else if is_one_wire(g.i_ids[1]) {
+                g.i_ids[0]
+            } else {
+                remaining.push(g);
+                continue;
+            };
+            if g.coef_type != CoefType::Constant {
+                remaining.push(g);
+                continue;
+            }
+            layer.add.push(GateAdd {
+                i_ids: [other],
+                o_id: g.o_id,
+                coef_type: CoefType::Constant,
+                coef: g.coef,
+                gate_type: g.gate_type,
+            });
+        }
+        layer.mul = remaining;
+        n - layer.mul.len()
+    }
+}
+
+/// `a - a = 0`: the add gates between the same wires whose coefficients sum to zero are
+/// removed.
+pub struct CancellationRule;
+
+impl<C: GKRConfig> SimplificationRule<C> for CancellationRule {
+    fn name(&self) -> &str {
+        "a - a = 0"
+    }
+
+    fn apply(&self, layer: &mut CircuitLayer<C>, _: &[Option<C::CircuitField>]) -> usize {
+        let mut sums = HashMap::<(usize, usize), C::CircuitField>::new();
+        for g in layer
+            .add
+            .iter()
+            .filter(|g| g.coef_type == CoefType::Constant)
+        {
+            *sums
+                .entry((g.i_ids[0], g.o_id))
+                .or_insert(C::CircuitField::ZERO) += g.coef;
+        }
+        let n = layer.add.len();
+        layer.add.retain(|g| {
+            g.coef_type != CoefType::Constant || !sums[&(g.i_ids[0], g.o_id)].is_zero()
+        });
+        n - layer.add.len()
+    }
+}
+
+pub struct CircuitExpressionSimplifier<C: GKRConfig> {
+    rules: Vec<Box<dyn SimplificationRule<C>>>,
+}
+
+impl<C: GKRConfig> Default for CircuitExpressionSimplifier<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: GKRConfig> CircuitExpressionSimplifier<C> {
+    /// A simplifier with the rules of this module.
+    pub fn new() -> Self {
+        Self {
+            rules: vec![
+                Box::new(MulZeroRule),
+                Box::new(MulOneRule),
+                Box::new(CancellationRule),
+                Box::new(AddZeroRule),
+            ],
+        }
+    }
+
+    /// A simplifier without any rule.
+    pub fn empty() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Apply the rule after the current ones.
+    pub fn add_rule(&mut self, rule: Box<dyn SimplificationRule<C>>) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    /// Apply the rules until none applies, and return the number of simplified gates.
+    ///
+    /// Panics if the evaluations of the simplified circuit do not match the original ones
+    /// on a random input.
+    pub fn simplify(&self, circuit: &mut Circuit<C>) -> usize {
+        let original = circuit.clone();
+
+        let mut n_simplified = 0;
+        loop {
+            let mut n_pass = 0;
+            for i in 0..circuit.layers.len() {
+                let (prev_layers, layers) = circuit.layers.split_at_mut(i);
+                let fixed_inputs = prev_layers
+                    .last()
+                    .map(SumcheckDegreeOptimizer::fixed_output_wires)
+                    .unwrap_or_default();
+                for rule in &self.rules {
+                    n_pass += rule.apply(&mut layers[0], &fixed_inputs);
+                }
+            }
+            if n_pass == 0 {
+                break;
+            }
+            n_simplified += n_pass;
+        }
+
+        if n_simplified > 0 {
+            circuit
+                .layers
+                .iter_mut()
+                .for_each(|layer| layer.identify_structure_info());
+            // the gates have moved, so the pointers to the random coefficients are stale
+            if circuit.rnd_coefs_identified {
+                circuit.identify_rnd_coefs();
+            }
+            assert!(
+                SumcheckDegreeOptimizer::evaluations_match(&original, circuit),
+                "the simplified circuit is not equivalent to the original one"
+            );
+        }
+        n_simplified
+    }
+}
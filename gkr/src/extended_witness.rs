@@ -0,0 +1,103 @@
+//! Witness polynomials with fewer values than their number of variables allows.
+//!
+//! A witness of `n` values is the multilinear polynomial whose evaluations over the hypercube
+//! are the values followed by zeros. The variables are in the order of the GKR layers: the
+//! first variable is the lowest bit of the index.
+
+use arith::Field;
+use polynomials::MultiLinearPoly;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumcheckExtendedWitness<F: Field> {
+    /// Number of leading evaluations that may be non zero, the others are padding.
+    pub num_values: usize,
+    pub evals: Vec<F>,
+}
+
+impl<F: Field> SumcheckExtendedWitness<F> {
+    /// Pad the witness with zeros to `2^target_num_vars` evaluations.
+    ///
+    /// Panics if the witness has more than `2^target_num_vars` values.
+    pub fn from_witness(w: &[F], target_num_vars: usize) -> Self {
+        assert!(
+            w.len() <= 1 << target_num_vars,
+            "witness of {} values does not fit {} variables",
+            w.len(),
+            target_num_vars
+        );
+        let mut evals = w.to_vec();
+        evals.resize(1 << target_num_vars, F::ZERO);
+        Self {
+            num_values: w.len(),
+            evals,
+        }
+    }
+
+    #[inline]
+    pub fn num_vars(&self) -> usize {
+        self.evals.len().trailing_zeros() as usize
+    }
+
+    #[inline]
+    pub fn into_evals(self) -> Vec<F> {
+        self.evals
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        let mut scratch = vec![F::ZERO; self.evals.len()];
+        MultiLinearPoly::evaluate_with_buffer(&self.evals, point, &mut scratch)
+    }
+
+    /// Same as `evaluate`, in `O(num_values * num_vars)` rather than `O(2^num_vars)`, so that
+    /// a short witness over many variables is cheap.
+    pub fn evaluate_sparse(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_vars());
+        self.evals[..self.num_values]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let eq = point.iter().enumerate().fold(F::ONE, |acc, (j, r)| {
+                    if (i >> j) & 1 == 1 {
+                        acc * *r
+                    } else {
+                        acc * (F::ONE - *r)
+                    }
+                });
+                *v * eq
+            })
+            .sum()
+    }
+
+    /// Sum over the hypercube, i.e., the claim of a sumcheck over the witness polynomial.
+    #[inline]
+    pub fn sum(&self) -> F {
+        self.evals[..self.num_values].iter().copied().sum()
+    }
+
+    /// Evaluations at 0 and 1 of the round polynomial of a sumcheck over the first variable.
+    pub fn round_evals(&self) -> (F, F) {
+        assert!(self.num_vars() > 0, "no variable left");
+        self.evals[..self.num_values.next_multiple_of(2)]
+            .chunks(2)
+            .fold((F::ZERO, F::ZERO), |(p0, p1), pair| {
+                (p0 + pair[0], p1 + pair[1])
+            })
+    }
+
+    /// Fix the first variable to `r`, as the sumcheck prover does at the end of a round.
+    /// The padding stays zero, so only the non zero prefix is folded.
+    pub fn fix_first_variable(&mut self, r: &F) {
+        assert!(self.num_vars() > 0, "no variable left");
+        let half = self.evals.len() / 2;
+        let n = self.num_values.div_ceil(2);
+        for i in 0..n {
+            self.evals[i] = self.evals[2 * i] + (self.evals[2 * i + 1] - self.evals[2 * i]) * *r;
+        }
+        // the folded values beyond the new prefix
+        self.evals[n..self.num_values.min(half)]
+            .iter_mut()
+            .for_each(|v| *v = F::ZERO);
+        self.evals.truncate(half);
+        self.num_values = n;
+    }
+}
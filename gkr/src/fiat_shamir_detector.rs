@@ -0,0 +1,102 @@
+//! Checking the Fiat-Shamir transform of a protocol run on its transcript calls, as recorded by
+//! an `AuditedTranscript`.
+//!
+//! A round is the run of messages absorbed between two challenges. The checks mirror the usual
+//! weak Fiat-Shamir pitfalls: a challenge that depends on no message, messages that never reach
+//! the hash, rounds that can be replayed, and a transcript that is not bound to the statement,
+//! which lets a prover choose the circuit after seeing the challenges.
+
+use std::collections::HashMap;
+
+use transcript::{TranscriptAuditLog, TranscriptEvent};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeakFSWarning {
+    /// The challenge of the event is drawn before any message is absorbed.
+    ChallengeWithoutContext { event: usize },
+    /// The state is set at the event while some absorbed messages are not hashed yet, so they
+    /// do not reach the later challenges.
+    DiscardedMessages { event: usize },
+    /// The rounds starting at the events `first` and `second` absorb the same message.
+    RepeatedMessage { first: usize, second: usize },
+    /// The first absorbed message does not contain the statement of the log, or the log has
+    /// no statement.
+    MissingCircuitCommitment,
+}
+
+pub struct WeakFiatShamirDetector;
+
+impl WeakFiatShamirDetector {
+    /// The warnings in the order of the events, the missing commitment first.
+    pub fn analyze(transcript_log: &TranscriptAuditLog) -> Vec<WeakFSWarning> {
+        let mut warnings = vec![];
+
+        let first_message = transcript_log.events.iter().find_map(|e| match e {
+            TranscriptEvent::Absorb(bytes) => Some(bytes),
+            _ => None,
+        });
+        let committed = match (&transcript_log.statement, first_message) {
+            (Some(statement), Some(message)) => {
+                statement.is_empty() || message.windows(statement.len()).any(|w| w == statement)
+            }
+            _ => false,
+        };
+        if !committed {
+            warnings.push(WeakFSWarning::MissingCircuitCommitment);
+        }
+
+        let mut absorbed_any = false;
+        // whether some absorbed bytes are not hashed yet
+        let mut pending = false;
+        // the current round as (first event, message)
+        let mut round: Option<(usize, Vec<u8>)> = None;
+        let mut rounds = HashMap::<Vec<u8>, usize>::new();
+        let mut end_round = |round: &mut Option<(usize, Vec<u8>)>, warnings: &mut Vec<_>| {
+            if let Some((start, message)) = round.take() {
+                match rounds.get(&message) {
+                    Some(&first) => warnings.push(WeakFSWarning::RepeatedMessage {
+                        first,
+                        second: start,
+                    }),
+                    None => {
+                        rounds.insert(message, start);
+                    }
+                }
+            }
+        };
+
+        for (i, event) in transcript_log.events.iter().enumerate() {
+            match event {
+                TranscriptEvent::Absorb(bytes) => {
+                    absorbed_any = true;
+                    pending = true;
+                    round
+                        .get_or_insert_with(|| (i, vec![]))
+                        .1
+                        .extend_from_slice(bytes);
+                }
+                TranscriptEvent::Challenge { .. } => {
+                    if !absorbed_any {
+                        warnings.push(WeakFSWarning::ChallengeWithoutContext { event: i });
+                    }
+                    pending = false;
+                    end_round(&mut round, &mut warnings);
+                }
+                TranscriptEvent::HashState | TranscriptEvent::UnlockProof => pending = false,
+                TranscriptEvent::SetState(_) => {
+                    if pending {
+                        warnings.push(WeakFSWarning::DiscardedMessages { event: i });
+                    }
+                    // the state carries the context of the messages hashed into it
+                    absorbed_any = true;
+                    pending = false;
+                    end_round(&mut round, &mut warnings);
+                }
+                TranscriptEvent::LockProof => {}
+            }
+        }
+        end_round(&mut round, &mut warnings);
+
+        warnings
+    }
+}
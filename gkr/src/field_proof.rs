@@ -0,0 +1,210 @@
+//! Proofs as elements of the challenge field, for the verifiers in a circuit, e.g., of a
+//! recursive composition.
+//!
+//! A field proof is the elements of the proof in the order of its `ProofLayout`, followed by the
+//! claimed values, each in the canonical bytes of `FieldSerde`. The raw commitment is of circuit
+//! field elements, each lifted to the challenge field, one simd element after the other in the
+//! order of their lanes. The other segments are of challenge field elements already. The number
+//! of elements of each section is that of the `FieldProofManifest`.
+
+use arith::{Field, FieldSerde, FieldSerdeError, SimdField};
+use config::GKRConfig;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use transcript::Proof;
+
+use crate::{ProofLayout, ProofSegment};
+
+#[derive(Debug, Error)]
+pub enum FieldProofError {
+    #[error("the proof has {found} bytes, but the proofs of the circuit {expected}")]
+    ProofSizeMismatch { expected: usize, found: usize },
+
+    #[error("{found} field elements, but the proofs of the circuit have {expected}")]
+    ElementCountMismatch { expected: usize, found: usize },
+
+    #[error("the commitment element {0} is not in the circuit field")]
+    NotInCircuitField(usize),
+
+    #[error("the manifest is not that of the proofs of the circuit")]
+    ManifestMismatch,
+
+    #[error("only the vanilla proofs with the raw commitment are encoded as field elements")]
+    UnsupportedProof,
+
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    FieldSerde(#[from] FieldSerdeError),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerManifest {
+    /// The evaluations of the round polynomials of the sumchecks of the layer.
+    pub sumcheck: usize,
+    /// vx, and vy unless the layer is of degree one.
+    pub claims: usize,
+}
+
+/// The number of elements of each section of a field proof, in their order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldProofManifest {
+    /// The `Field::NAME` of the elements, e.g., `"Mersenne 31 Extension 3"`.
+    pub field: String,
+    pub commitment: usize,
+    /// From the output layer down.
+    pub layers: Vec<LayerManifest>,
+    /// Zero for the raw commitment, evaluated by the verifier itself.
+    pub opening: usize,
+    pub claimed_v: usize,
+}
+
+impl FieldProofManifest {
+    pub fn new<C: GKRConfig>(layout: &ProofLayout, num_claimed: usize) -> Self {
+        let mut segments = layout.segments.iter();
+        let commitment = segments
+            .next()
+            .map_or(0, |segment| num_elements_of::<C>(layout, segment));
+        let layers = layout
+            .layer_segments
+            .iter()
+            .map(|&n| {
+                let mut layer = LayerManifest::default();
+                for segment in segments.by_ref().take(n) {
+                    match segment {
+                        ProofSegment::Claim => layer.claims += 1,
+                        _ => layer.sumcheck += num_elements_of::<C>(layout, segment),
+                    }
+                }
+                layer
+            })
+            .collect();
+        Self {
+            field: C::ChallengeField::NAME.to_string(),
+            commitment,
+            layers,
+            opening: 0,
+            claimed_v: num_claimed,
+        }
+    }
+
+    pub fn num_elements(&self) -> usize {
+        let layers: usize = self.layers.iter().map(|l| l.sumcheck + l.claims).sum();
+        self.commitment + layers + self.opening + self.claimed_v
+    }
+}
+
+/// The number of challenge field elements of the segment.
+fn num_elements_of<C: GKRConfig>(layout: &ProofLayout, segment: &ProofSegment) -> usize {
+    match segment {
+        ProofSegment::Commitment(size) => {
+            size / C::SimdCircuitField::SERIALIZED_SIZE * C::SimdCircuitField::pack_size()
+        }
+        _ => layout.segment_size(segment) / layout.element_size,
+    }
+}
+
+/// The proof as the elements of a field proof, without the claimed values, and back.
+pub trait ProofFieldElements: Sized {
+    fn to_field_elements<C: GKRConfig>(
+        &self,
+        layout: &ProofLayout,
+    ) -> Result<Vec<C::ChallengeField>, FieldProofError>;
+
+    fn from_field_elements<C: GKRConfig>(
+        elements: &[C::ChallengeField],
+        layout: &ProofLayout,
+    ) -> Result<Self, FieldProofError>;
+}
+
+impl ProofFieldElements for Proof {
+    fn to_field_elements<C: GKRConfig>(
+        &self,
+        layout: &ProofLayout,
+    ) -> Result<Vec<C::ChallengeField>, FieldProofError> {
+        if self.bytes.len() != layout.proof_size() {
+            return Err(FieldProofError::ProofSizeMismatch {
+                expected: layout.proof_size(),
+                found: self.bytes.len(),
+            });
+        }
+
+        let mut elements = vec![];
+        let mut offset = 0;
+        for segment in &layout.segments {
+            let size = layout.segment_size(segment);
+            let bytes = &self.bytes[offset..offset + size];
+            match segment {
+                ProofSegment::Commitment(_) => {
+                    for chunk in bytes.chunks(C::SimdCircuitField::SERIALIZED_SIZE) {
+                        let packed = C::SimdCircuitField::deserialize_from(chunk)?;
+                        elements.extend(packed.unpack().into_iter().map(C::ChallengeField::from));
+                    }
+                }
+                _ => {
+                    for chunk in bytes.chunks(layout.element_size) {
+                        elements.push(C::ChallengeField::deserialize_from(chunk)?);
+                    }
+                }
+            }
+            offset += size;
+        }
+        Ok(elements)
+    }
+
+    fn from_field_elements<C: GKRConfig>(
+        elements: &[C::ChallengeField],
+        layout: &ProofLayout,
+    ) -> Result<Self, FieldProofError> {
+        let expected = layout
+            .segments
+            .iter()
+            .map(|segment| num_elements_of::<C>(layout, segment))
+            .sum();
+        if elements.len() != expected {
+            return Err(FieldProofError::ElementCountMismatch {
+                expected,
+                found: elements.len(),
+            });
+        }
+
+        let mut bytes = Vec::with_capacity(layout.proof_size());
+        let mut offset = 0;
+        for segment in &layout.segments {
+            let n = num_elements_of::<C>(layout, segment);
+            let segment_elements = &elements[offset..offset + n];
+            match segment {
+                ProofSegment::Commitment(_) => {
+                    let lanes = segment_elements
+                        .iter()
+                        .enumerate()
+                        .map(|(i, element)| {
+                            to_circuit_field::<C>(element)
+                                .ok_or(FieldProofError::NotInCircuitField(offset + i))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for lanes in lanes.chunks(C::SimdCircuitField::pack_size()) {
+                        C::SimdCircuitField::pack(lanes).serialize_into(&mut bytes)?;
+                    }
+                }
+                _ => {
+                    for element in segment_elements {
+                        element.serialize_into(&mut bytes)?;
+                    }
+                }
+            }
+            offset += n;
+        }
+        Ok(Proof { bytes })
+    }
+}
+
+/// The circuit field element lifted to the element, if any. Its bytes are the first of those
+/// of the element, the coefficient of degree zero, checked by lifting it again.
+fn to_circuit_field<C: GKRConfig>(element: &C::ChallengeField) -> Option<C::CircuitField> {
+    let mut bytes = vec![];
+    element.serialize_into(&mut bytes).ok()?;
+    let base = C::CircuitField::deserialize_from(&bytes[..]).ok()?;
+    (C::ChallengeField::from(base) == *element).then_some(base)
+}
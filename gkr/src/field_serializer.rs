@@ -0,0 +1,55 @@
+//! Batch serialization of field elements, for witnesses and proofs.
+
+use std::{io::Write, marker::PhantomData};
+
+use arith::{FieldSerde, FieldSerdeError};
+use rayon::prelude::*;
+
+/// Under this number of elements, the batch is serialized on the current thread.
+const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// Serializes slices of field elements, tightly packed, with a single write.
+///
+/// The elements are serialized in parallel into disjoint chunks of one pre-allocated buffer,
+/// instead of one `write_all` per element into the writer. The per-element encoding is the one
+/// of `FieldSerde::serialize_into`, so the output is the same.
+pub struct VecFieldSerializer<F: FieldSerde> {
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldSerde + Sync> VecFieldSerializer<F> {
+    pub fn serialize_batch(elements: &[F], mut writer: impl Write) -> Result<(), FieldSerdeError> {
+        let mut buffer = vec![0u8; elements.len() * F::SERIALIZED_SIZE];
+        if elements.len() < PARALLEL_THRESHOLD {
+            Self::serialize_chunk(elements, &mut buffer)?;
+        } else {
+            let chunk_len = elements.len().div_ceil(rayon::current_num_threads());
+            elements
+                .par_chunks(chunk_len)
+                .zip(buffer.par_chunks_mut(chunk_len * F::SERIALIZED_SIZE))
+                .try_for_each(|(elements, buffer)| Self::serialize_chunk(elements, buffer))?;
+        }
+        writer.write_all(&buffer)?;
+        Ok(())
+    }
+
+    /// The inverse of `serialize_batch`, for `bytes` holding exactly the serialized elements.
+    pub fn deserialize_batch(bytes: &[u8]) -> Result<Vec<F>, FieldSerdeError>
+    where
+        F: Send,
+    {
+        if bytes.len() % F::SERIALIZED_SIZE != 0 {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        bytes
+            .par_chunks(F::SERIALIZED_SIZE)
+            .map(F::deserialize_from)
+            .collect()
+    }
+
+    fn serialize_chunk(elements: &[F], mut buffer: &mut [u8]) -> Result<(), FieldSerdeError> {
+        elements
+            .iter()
+            .try_for_each(|element| element.serialize_into(&mut buffer))
+    }
+}
@@ -0,0 +1,113 @@
+//! Detecting the duplicate circuits of a deployment by their fingerprints, the Sha3-256 of their
+//! canonical encodings, see `Circuit::canonical_bytes`. Circuits only differing in their
+//! witness or random coefficient values have the same fingerprint.
+
+use std::collections::HashMap;
+
+use circuit::Circuit;
+use config::GKRConfig;
+use tiny_keccak::{Hasher, Sha3};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitFingerprintRegistry {
+    paths: HashMap<[u8; 32], String>,
+}
+
+impl CircuitFingerprintRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fingerprint<C: GKRConfig>(circuit: &Circuit<C>) -> [u8; 32] {
+        let mut hasher = Sha3::v256();
+        hasher.update(&circuit.canonical_bytes());
+        let mut fingerprint = [0u8; 32];
+        hasher.finalize(&mut fingerprint);
+        fingerprint
+    }
+
+    /// The path of the circuit registered before with the same fingerprint, if any and if it is
+    /// not `path` itself. Otherwise, the circuit is registered at `path`.
+    pub fn check_duplicate<C: GKRConfig>(
+        &mut self,
+        circuit: &Circuit<C>,
+        path: &str,
+    ) -> Option<&str> {
+        let fingerprint = Self::fingerprint(circuit);
+        let existing = self
+            .paths
+            .entry(fingerprint)
+            .or_insert_with(|| path.to_string());
+        if *existing == path {
+            None
+        } else {
+            Some(existing.as_str())
+        }
+    }
+
+    pub fn get(&self, fingerprint: &[u8; 32]) -> Option<&str> {
+        self.paths.get(fingerprint).map(String::as_str)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl CircuitFingerprintRegistry {
+    /// Load the registry from the `circuit_fingerprints` table of the database, created if
+    /// missing.
+    pub fn open_sqlite(db_path: &str) -> rusqlite::Result<Self> {
+        let db = Self::connect(db_path)?;
+        let mut statement = db.prepare("SELECT fingerprint, path FROM circuit_fingerprints")?;
+        let rows = statement.query_map([], |row| {
+            let fingerprint: Vec<u8> = row.get(0)?;
+            let path: String = row.get(1)?;
+            Ok((fingerprint, path))
+        })?;
+
+        let mut registry = Self::new();
+        for row in rows {
+            let (fingerprint, path) = row?;
+            // skip the rows of other tools sharing the table
+            if let Ok(fingerprint) = fingerprint.try_into() {
+                registry.paths.insert(fingerprint, path);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Write the registry to the database, keeping the rows of the other fingerprints.
+    pub fn save_sqlite(&self, db_path: &str) -> rusqlite::Result<()> {
+        let mut db = Self::connect(db_path)?;
+        let tx = db.transaction()?;
+        {
+            let mut statement = tx.prepare(
+                "INSERT OR REPLACE INTO circuit_fingerprints (fingerprint, path) VALUES (?1, ?2)",
+            )?;
+            for (fingerprint, path) in &self.paths {
+                statement.execute(rusqlite::params![fingerprint.as_slice(), path])?;
+            }
+        }
+        tx.commit()
+    }
+
+    fn connect(db_path: &str) -> rusqlite::Result<rusqlite::Connection> {
+        let db = rusqlite::Connection::open(db_path)?;
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS circuit_fingerprints (
+                fingerprint BLOB PRIMARY KEY,
+                path TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(db)
+    }
+}
@@ -0,0 +1,184 @@
+//! Loading untrusted circuit bytes without crashing the process.
+//!
+//! The circuit parser returns the malformed bytes as errors, and its vectors grow with the
+//! elements read rather than by their length prefixes, so the memory taken by parsing is bounded
+//! by the input. A segment reused by its parents multiplies its gates in the flattened circuit
+//! though, so the size of the flattened circuit is computed from the segments and bounded, see
+//! `MAX_FLATTENED_GATES`, before it is flattened. The flattened circuit is then checked for out of
+//! range wires, which would otherwise panic later during evaluation or proving, and for layers
+//! larger than the prover and the verifier can allocate, see `MAX_VAR_NUM`.
+
+use std::{fs, io::Read, marker::PhantomData};
+
+use circuit::{Circuit, CircuitLayer, RecursiveCircuit};
+use config::{
+    sentinel_hex, FieldType, GKRConfig, SENTINEL_BABYBEAR, SENTINEL_BN254, SENTINEL_GF2,
+    SENTINEL_GOLDILOCKS, SENTINEL_M31, SENTINEL_OFFSET, SENTINEL_SIZE,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CircuitLoadError {
+    #[error("failed to parse the circuit: {0}")]
+    ParseError(String),
+
+    #[error("invalid circuit: {0}")]
+    InvalidCircuit(String),
+
+    #[error("the circuit file has {actual} bytes, fewer than the {required} bytes of its header")]
+    FileTooShort { actual: usize, required: usize },
+
+    #[error("unknown field sentinel {}", sentinel_hex(.bytes))]
+    UnknownFieldSentinel { bytes: [u8; SENTINEL_SIZE] },
+
+    #[error("the flattened circuit has {num_gates} gates, more than the {max} of the loader")]
+    TooManyGates { num_gates: usize, max: usize },
+
+    #[error("the segments are nested {depth} deep, more than the {max} of the loader")]
+    SegmentsTooDeep { depth: usize, max: usize },
+
+    #[error("unable to read the circuit file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// The field of the circuit file, told by the sentinel of its header, see `SENTINEL_OFFSET`.
+pub fn detect_field_type_from_circuit_file(path: &str) -> Result<FieldType, CircuitLoadError> {
+    let mut header = vec![];
+    fs::File::open(path)?
+        .take((SENTINEL_OFFSET + SENTINEL_SIZE) as u64)
+        .read_to_end(&mut header)?;
+    detect_field_type(&header)
+}
+
+/// The field of the circuit bytes, of which only the header is read.
+pub fn detect_field_type(bytes: &[u8]) -> Result<FieldType, CircuitLoadError> {
+    let required = SENTINEL_OFFSET + SENTINEL_SIZE;
+    let sentinel: [u8; SENTINEL_SIZE] = bytes
+        .get(SENTINEL_OFFSET..required)
+        .ok_or(CircuitLoadError::FileTooShort {
+            actual: bytes.len(),
+            required,
+        })?
+        .try_into()
+        .unwrap();
+    match sentinel {
+        SENTINEL_M31 => Ok(FieldType::M31),
+        SENTINEL_BN254 => Ok(FieldType::BN254),
+        SENTINEL_GF2 => Ok(FieldType::GF2),
+        SENTINEL_GOLDILOCKS => Ok(FieldType::Goldilocks),
+        SENTINEL_BABYBEAR => Ok(FieldType::BabyBear),
+        bytes => Err(CircuitLoadError::UnknownFieldSentinel { bytes }),
+    }
+}
+
+/// The gates of the flattened circuit that `FuzzSafeCircuitLoader::load` accepts, of some
+/// gigabytes once flattened, see `FuzzSafeCircuitLoader::load_with_max_gates` for larger ones.
+/// Fewer on 32-bit targets, whose memory is of 4 gigabytes at most.
+pub const MAX_FLATTENED_GATES: usize = if usize::BITS > 32 { 1 << 26 } else { 1 << 22 };
+
+/// The nesting of segments that the loader accepts, as they are flattened recursively.
+pub const MAX_SEGMENT_DEPTH: usize = 1 << 10;
+
+/// The variables of a layer that the loader accepts, as the prover and the verifier allocate
+/// the evaluations of each layer. Fewer on 32-bit targets, e.g., wasm, for the bytes of the
+/// values of a layer to be counted in a `usize`.
+pub const MAX_VAR_NUM: usize = if usize::BITS > 32 { 30 } else { 24 };
+
+pub struct FuzzSafeCircuitLoader<C: GKRConfig> {
+    phantom: PhantomData<C>,
+}
+
+impl<C: GKRConfig> FuzzSafeCircuitLoader<C> {
+    pub fn load(reader: impl Read) -> Result<Circuit<C>, CircuitLoadError> {
+        Self::load_with_max_gates(reader, MAX_FLATTENED_GATES)
+    }
+
+    /// As `load`, for a flattened circuit of at most `max_gates` gates.
+    pub fn load_with_max_gates(
+        reader: impl Read,
+        max_gates: usize,
+    ) -> Result<Circuit<C>, CircuitLoadError> {
+        let rc = RecursiveCircuit::<C>::load_from_reader(reader)
+            .map_err(|e| CircuitLoadError::ParseError(e.to_string()))?;
+        let size = rc
+            .flattened_size()
+            .map_err(|e| CircuitLoadError::InvalidCircuit(e.to_string()))?;
+        if size.num_gates > max_gates {
+            return Err(CircuitLoadError::TooManyGates {
+                num_gates: size.num_gates,
+                max: max_gates,
+            });
+        }
+        if size.depth > MAX_SEGMENT_DEPTH {
+            return Err(CircuitLoadError::SegmentsTooDeep {
+                depth: size.depth,
+                max: MAX_SEGMENT_DEPTH,
+            });
+        }
+
+        let circuit = rc.flatten();
+        Self::validate(&circuit)?;
+        Ok(circuit)
+    }
+
+    /// Check that evaluating the circuit cannot index out of bounds.
+    pub fn validate(circuit: &Circuit<C>) -> Result<(), CircuitLoadError> {
+        let invalid = |msg: String| Err(CircuitLoadError::InvalidCircuit(msg));
+
+        if circuit.layers.is_empty() {
+            return invalid("the circuit has no layer".to_string());
+        }
+        for (i, layer) in circuit.layers.iter().enumerate() {
+            if let Some(next_layer) = circuit.layers.get(i + 1) {
+                if layer.output_var_num != next_layer.input_var_num {
+                    return invalid(format!(
+                        "layer {} has {} output vars, but layer {} has {} input vars",
+                        i,
+                        layer.output_var_num,
+                        i + 1,
+                        next_layer.input_var_num
+                    ));
+                }
+            }
+            Self::validate_layer(layer).or_else(|msg| invalid(format!("layer {}: {}", i, msg)))?;
+        }
+        Ok(())
+    }
+
+    fn validate_layer(layer: &CircuitLayer<C>) -> Result<(), String> {
+        let var_num = layer.input_var_num.max(layer.output_var_num);
+        if var_num > MAX_VAR_NUM {
+            return Err(format!(
+                "{} variables, more than the {} of the loader",
+                var_num, MAX_VAR_NUM
+            ));
+        }
+        let n_inputs = 1usize << layer.input_var_num;
+        let n_outputs = 1usize << layer.output_var_num;
+
+        let wires = layer
+            .mul
+            .iter()
+            .map(|g| (g.i_ids.to_vec(), g.o_id))
+            .chain(layer.add.iter().map(|g| (g.i_ids.to_vec(), g.o_id)))
+            .chain(layer.const_.iter().map(|g| (vec![], g.o_id)))
+            .chain(layer.uni.iter().map(|g| (g.i_ids.to_vec(), g.o_id)));
+        for (i_ids, o_id) in wires {
+            if let Some(i_id) = i_ids.iter().find(|&&i_id| i_id >= n_inputs) {
+                return Err(format!("input wire {} out of range {}", i_id, n_inputs));
+            }
+            if o_id >= n_outputs {
+                return Err(format!("output wire {} out of range {}", o_id, n_outputs));
+            }
+        }
+
+        if let Some(gate) = layer
+            .uni
+            .iter()
+            .find(|g| g.gate_type != 12345 && g.gate_type != 12346)
+        {
+            return Err(format!("unknown gate type {}", gate.gate_type));
+        }
+        Ok(())
+    }
+}
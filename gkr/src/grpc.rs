@@ -0,0 +1,156 @@
+//! The gRPC interface of the prover and the verifier, see `proto/expander.proto`, as an
+//! alternative to the HTTP one of `expander-exec serve`.
+//!
+//! The service only decodes the requests: the proving and the verification are left to an
+//! `ExpanderBackend`, so that both interfaces share the same workers.
+
+use std::{future::Future, net::SocketAddr, sync::Arc};
+
+use tokio::sync::mpsc;
+#[cfg(feature = "tls")]
+use tonic::transport::{Identity, ServerTlsConfig};
+use tonic::{
+    codegen::tokio_stream::wrappers::ReceiverStream, metadata::MetadataMap, transport::Server,
+    Request, Response, Status, Streaming,
+};
+use warp::http::HeaderMap;
+
+/// The generated messages, client and server.
+pub mod proto {
+    tonic::include_proto!("expander");
+}
+
+use proto::{
+    expander_server::{Expander, ExpanderServer},
+    ProveRequest, ProveResponse, ReadyRequest, ReadyResponse, VerifyRequest, VerifyResponse,
+};
+
+/// The proofs of `ProveStream` answered ahead of the client reading them, the next witness
+/// waiting for the client otherwise.
+const PROVE_STREAM_BUFFER: usize = 4;
+
+#[tonic::async_trait]
+pub trait ExpanderBackend: Send + Sync + 'static {
+    /// The proof package bytes of the witness bytes, with the bytes of its claimed values.
+    async fn prove(&self, witness: Vec<u8>, headers: HeaderMap) -> Result<ProveResponse, Status>;
+
+    /// Whether the proof package bytes are a valid proof for the witness bytes, and why not.
+    /// Witnesses the circuit cannot load are not valid, rather than errors, as proofs that do
+    /// not verify, but proofs that cannot be read are invalid arguments.
+    async fn verify(
+        &self,
+        witness: Vec<u8>,
+        proof: Vec<u8>,
+        headers: HeaderMap,
+    ) -> Result<VerifyResponse, Status>;
+
+    fn ready(&self) -> ReadyResponse;
+}
+
+pub struct ExpanderGrpcService<B: ExpanderBackend> {
+    backend: Arc<B>,
+}
+
+impl<B: ExpanderBackend> ExpanderGrpcService<B> {
+    pub fn new(backend: Arc<B>) -> Self {
+        Self { backend }
+    }
+}
+
+/// The metadata of the request as HTTP headers, e.g., for the trace context.
+fn headers(metadata: &MetadataMap) -> HeaderMap {
+    metadata.clone().into_headers()
+}
+
+#[tonic::async_trait]
+impl<B: ExpanderBackend> Expander for ExpanderGrpcService<B> {
+    type ProveStreamStream = ReceiverStream<Result<ProveResponse, Status>>;
+
+    async fn prove(
+        &self,
+        request: Request<ProveRequest>,
+    ) -> Result<Response<ProveResponse>, Status> {
+        let headers = headers(request.metadata());
+        let proven = self
+            .backend
+            .prove(request.into_inner().witness, headers)
+            .await?;
+        Ok(Response::new(proven))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<VerifyRequest>,
+    ) -> Result<Response<VerifyResponse>, Status> {
+        let headers = headers(request.metadata());
+        let VerifyRequest { witness, proof } = request.into_inner();
+        let verified = self.backend.verify(witness, proof, headers).await?;
+        Ok(Response::new(verified))
+    }
+
+    /// The witnesses are proven one after the other, on the workers of the other requests, with
+    /// the metadata of the stream.
+    async fn prove_stream(
+        &self,
+        request: Request<Streaming<ProveRequest>>,
+    ) -> Result<Response<Self::ProveStreamStream>, Status> {
+        let headers = headers(request.metadata());
+        let mut witnesses = request.into_inner();
+        let backend = self.backend.clone();
+        let (sender, receiver) = mpsc::channel(PROVE_STREAM_BUFFER);
+        tokio::spawn(async move {
+            loop {
+                let proven = match witnesses.message().await {
+                    Ok(Some(request)) => backend.prove(request.witness, headers.clone()).await,
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let failed = proven.is_err();
+                // the client is gone, or the stream ends with the failure
+                if sender.send(proven).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+
+    async fn ready(
+        &self,
+        _request: Request<ReadyRequest>,
+    ) -> Result<Response<ReadyResponse>, Status> {
+        Ok(Response::new(self.backend.ready()))
+    }
+}
+
+/// Serve the backend until the server fails or `shutdown` resolves, rejecting the messages over
+/// `max_message_bytes`, over TLS with the PEM certificate chain and key if given. On shutdown,
+/// the requests under way are answered before returning.
+pub async fn serve_grpc<B: ExpanderBackend>(
+    backend: Arc<B>,
+    addr: SocketAddr,
+    max_message_bytes: usize,
+    tls: Option<(Vec<u8>, Vec<u8>)>,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    let service = ExpanderServer::new(ExpanderGrpcService::new(backend))
+        .max_decoding_message_size(max_message_bytes);
+    #[cfg(feature = "tls")]
+    let mut server = match tls {
+        Some((cert, key)) => Server::builder()
+            .tls_config(ServerTlsConfig::new().identity(Identity::from_pem(cert, key)))?,
+        None => Server::builder(),
+    };
+    #[cfg(not(feature = "tls"))]
+    let mut server = {
+        assert!(
+            tls.is_none(),
+            "serving over TLS requires building with the tls feature"
+        );
+        Server::builder()
+    };
+    server
+        .add_service(service)
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
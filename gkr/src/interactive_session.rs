@@ -0,0 +1,315 @@
+//! The GKR protocol run interactively: the challenges are sent by the verifier, one at a time,
+//! instead of being derived from the transcript by Fiat-Shamir.
+//!
+//! The prover runs on its own thread, on a transcript which hands out the prover messages
+//! and blocks until the verifier sends the next challenge. For circuits without random
+//! coefficients, drawing each challenge as the Fiat-Shamir transcript would, over the same
+//! messages, the public inputs after the commitment and the claimed values after them, gives
+//! back the proof of `Prover::prove` without seed nor grinding.
+
+use std::{
+    panic,
+    sync::mpsc::{channel, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+use arith::{Field, FieldSerde};
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme, PolynomialCommitmentType};
+use sumcheck::ProverScratchPad;
+use thiserror::Error;
+use transcript::{Proof, Transcript, TranscriptState};
+
+use crate::{gather_claimed_values, gkr_prove, RawCommitment};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// The prover sent its commitment, see `InteractiveProverSession::initial_message`, and
+    /// waits for the first challenge.
+    WaitingForChallenge,
+    /// The prover received `n` challenges and waits for the next one.
+    ProvingRound(usize),
+    Done,
+}
+
+/// A config the interactive session cannot prove with, see `InteractiveProverSession::new`.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SessionError {
+    #[error("the interactive session proves vanilla GKR only, not {0:?}")]
+    UnsupportedScheme(GKRScheme),
+
+    #[error("the interactive session runs on a single process, not on {0}")]
+    UnsupportedWorldSize(usize),
+
+    #[error("the interactive session commits with the Raw polynomial commitment only, not {0:?}")]
+    UnsupportedPolynomialCommitment(PolynomialCommitmentType),
+}
+
+/// What the prover sends between two challenges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProverMessage<F> {
+    pub bytes: Vec<u8>,
+    /// The claimed values of the outputs, see `gather_claimed_values`, on the message before
+    /// the first challenge of the sumchecks only, for the verifier to draw it after them.
+    pub claimed_v: Option<Vec<F>>,
+}
+
+enum Event<F> {
+    Message(Vec<u8>, Option<Vec<F>>),
+    Done { bytes: Vec<u8>, proof: Proof },
+}
+
+/// Unwinds the prover thread of a session dropped before its end.
+struct SessionDropped;
+
+/// The transcript of the prover thread: the challenges come from the verifier.
+struct InteractiveTranscript<F> {
+    proof: Proof,
+    /// The bytes appended since the last challenge.
+    pending: Vec<u8>,
+    /// The claimed values, once the circuit is evaluated, sent with the next message.
+    claimed_v: Option<Vec<F>>,
+    proof_locked: bool,
+    events: Sender<Event<F>>,
+    challenges: Receiver<F>,
+}
+
+impl<F: Field + FieldSerde> Transcript<F> for InteractiveTranscript<F> {
+    fn new() -> Self {
+        unimplemented!("the interactive transcript is created by its session")
+    }
+
+    fn append_field_element(&mut self, f: &F) {
+        let mut buf = vec![];
+        f.serialize_into(&mut buf).unwrap();
+        self.append_u8_slice(&buf);
+    }
+
+    /// The bytes appended while the proof is locked are only meant to be hashed. Without a
+    /// hash, they are dropped.
+    fn append_u8_slice(&mut self, buffer: &[u8]) {
+        if !self.proof_locked {
+            self.proof.bytes.extend_from_slice(buffer);
+            self.pending.extend_from_slice(buffer);
+        }
+    }
+
+    fn generate_challenge_field_element(&mut self) -> F {
+        let message = Event::Message(std::mem::take(&mut self.pending), self.claimed_v.take());
+        match self
+            .events
+            .send(message)
+            .ok()
+            .and(self.challenges.recv().ok())
+        {
+            Some(challenge) => challenge,
+            None => panic::resume_unwind(Box::new(SessionDropped)),
+        }
+    }
+
+    fn generate_challenge_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
+        let mut ret = vec![];
+        while ret.len() < n_bytes {
+            self.generate_challenge_field_element()
+                .serialize_into(&mut ret)
+                .unwrap();
+        }
+        ret.truncate(n_bytes);
+        ret
+    }
+
+    fn finalize_and_get_proof(&self) -> Proof {
+        self.proof.clone()
+    }
+
+    /// Only called to sync the transcripts of several processes, which a session refuses.
+    fn hash_and_return_state(&mut self) -> Vec<u8> {
+        unimplemented!("the interactive transcript has no hash state")
+    }
+
+    fn set_state(&mut self, _state: &[u8]) {
+        unimplemented!("the interactive transcript has no hash state")
+    }
+
+    fn lock_proof(&mut self) {
+        assert!(!self.proof_locked);
+        self.proof_locked = true;
+    }
+
+    fn unlock_proof(&mut self) {
+        assert!(self.proof_locked);
+        self.proof_locked = false;
+    }
+
+    fn capture_state(&self) -> TranscriptState {
+        unimplemented!("the interactive transcript has no hash state")
+    }
+}
+
+/// The prover side of an interactive GKR proof. Only for vanilla GKR on a single process.
+pub struct InteractiveProverSession<C: GKRConfig> {
+    state: SessionState,
+    initial_message: ProverMessage<C::ChallengeField>,
+    proof: Option<Proof>,
+    events: Receiver<Event<C::ChallengeField>>,
+    challenges: Sender<C::ChallengeField>,
+    prover: Option<JoinHandle<()>>,
+}
+
+impl<C: GKRConfig> InteractiveProverSession<C> {
+    /// Start proving the circuit, with its witness loaded, up to the first challenge.
+    ///
+    /// Fails for a config of GKR^2, of several processes, whose transcripts are synced by their
+    /// hash states, or of another polynomial commitment than Raw, which the session commits with.
+    pub fn new(circuit: Circuit<C>, config: &Config<C>) -> Result<Self, SessionError> {
+        if config.gkr_scheme != GKRScheme::Vanilla {
+            return Err(SessionError::UnsupportedScheme(config.gkr_scheme.clone()));
+        }
+        if config.mpi_config.world_size() != 1 {
+            return Err(SessionError::UnsupportedWorldSize(
+                config.mpi_config.world_size(),
+            ));
+        }
+        if config.polynomial_commitment_type != PolynomialCommitmentType::Raw {
+            return Err(SessionError::UnsupportedPolynomialCommitment(
+                config.polynomial_commitment_type.clone(),
+            ));
+        }
+
+        let (events_sender, events) = channel();
+        let (challenges, challenges_receiver) = channel();
+        let config = config.clone();
+        let prover = thread::spawn(move || {
+            let mut transcript = InteractiveTranscript {
+                proof: Proof::default(),
+                pending: vec![],
+                claimed_v: None,
+                proof_locked: false,
+                events: events_sender,
+                challenges: challenges_receiver,
+            };
+            let proof = prove_interactive(circuit, &config, &mut transcript);
+            // the verifier may have dropped the session by now
+            let _ = transcript.events.send(Event::Done {
+                bytes: transcript.pending,
+                proof,
+            });
+        });
+
+        let mut session = Self {
+            state: SessionState::WaitingForChallenge,
+            initial_message: ProverMessage {
+                bytes: vec![],
+                claimed_v: None,
+            },
+            proof: None,
+            events,
+            challenges,
+            prover: Some(prover),
+        };
+        session.initial_message = session.next_message();
+        Ok(session)
+    }
+
+    /// The commitment to the witness, sent before any challenge, with the claimed values for
+    /// the circuits without random coefficients.
+    pub fn initial_message(&self) -> &ProverMessage<C::ChallengeField> {
+        &self.initial_message
+    }
+
+    #[inline]
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Send the next challenge, returning the prover messages up to the following one.
+    ///
+    /// Panics if the session is done.
+    pub fn receive_challenge(
+        &mut self,
+        challenge: C::ChallengeField,
+    ) -> ProverMessage<C::ChallengeField> {
+        let round = match self.state {
+            SessionState::WaitingForChallenge => 1,
+            SessionState::ProvingRound(n) => n + 1,
+            SessionState::Done => panic!("the session is done"),
+        };
+        // on a failure, the prover thread is gone and its panic is rethrown below
+        let _ = self.challenges.send(challenge);
+        let message = self.next_message();
+        if self.state != SessionState::Done {
+            self.state = SessionState::ProvingRound(round);
+        }
+        message
+    }
+
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.state == SessionState::Done
+    }
+
+    /// All the prover messages, once the session is done. The proof is taken only once.
+    pub fn take_proof(&mut self) -> Option<Proof> {
+        self.proof.take()
+    }
+
+    /// Wait for the prover thread to send its next message, rethrowing its panic if any.
+    fn next_message(&mut self) -> ProverMessage<C::ChallengeField> {
+        match self.events.recv() {
+            Ok(Event::Message(bytes, claimed_v)) => ProverMessage { bytes, claimed_v },
+            Ok(Event::Done { bytes, proof }) => {
+                self.state = SessionState::Done;
+                self.proof = Some(proof);
+                self.prover.take().unwrap().join().unwrap();
+                ProverMessage {
+                    bytes,
+                    claimed_v: None,
+                }
+            }
+            Err(_) => match self.prover.take().unwrap().join() {
+                Err(e) => panic::resume_unwind(e),
+                Ok(()) => unreachable!("the prover thread ended without its proof"),
+            },
+        }
+    }
+}
+
+/// As `Prover::prove`, without the seed nor the grinding, which only make sense with
+/// Fiat-Shamir.
+fn prove_interactive<C: GKRConfig>(
+    mut circuit: Circuit<C>,
+    config: &Config<C>,
+    transcript: &mut InteractiveTranscript<C::ChallengeField>,
+) -> Proof {
+    let max_num_input_var = circuit
+        .layers
+        .iter()
+        .map(|layer| layer.input_var_num)
+        .max()
+        .unwrap();
+    let max_num_output_var = circuit
+        .layers
+        .iter()
+        .map(|layer| layer.output_var_num)
+        .max()
+        .unwrap();
+    let mut sp = ProverScratchPad::<C>::new(max_num_input_var, max_num_output_var, 1);
+
+    let commitment = RawCommitment::<C>::mpi_new(&circuit.layers[0].input_vals, &config.mpi_config);
+    let mut buffer = vec![];
+    commitment.serialize_into(&mut buffer).unwrap();
+    transcript.append_u8_slice(&buffer);
+
+    if !circuit.rnd_coefs_identified {
+        circuit.identify_rnd_coefs();
+    }
+    circuit.fill_rnd_coefs(transcript);
+    circuit.evaluate();
+    transcript.claimed_v = Some(gather_claimed_values::<C>(
+        &config.mpi_config,
+        &circuit.layers.last().unwrap().output_vals,
+    ));
+
+    gkr_prove(&circuit, &mut sp, transcript, &config.mpi_config);
+    transcript.finalize_and_get_proof()
+}
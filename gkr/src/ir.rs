@@ -0,0 +1,398 @@
+//! A stable intermediate representation of the circuits, for import and export tooling.
+//!
+//! Unlike `Circuit`, the IR does not depend on the config: the coefficients are decimal
+//! strings of their canonical value, and a gate is an operation with its input wires rather
+//! than an entry of one of the per kind gate lists. The IR is exchanged as JSON or CBOR, with
+//! the same serde data model.
+
+use arith::{Field, FieldForECC};
+use circuit::{Circuit, CircuitLayer, CoefType, Gate, GateUni};
+use config::{FieldType, GKRConfig};
+use ethnum::U256;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The version of the IR written by `to_ir`. Readers accept this version and the older ones.
+pub const GKR_CIRCUIT_IR_VERSION: u16 = 1;
+
+/// The uni gates known to `CircuitLayer::evaluate`.
+const POW5_GATE_TYPE: usize = 12345;
+const IDENTITY_GATE_TYPE: usize = 12346;
+
+#[derive(Debug, Error)]
+pub enum IRError {
+    #[error("unsupported ir version {0}")]
+    UnsupportedVersion(u16),
+
+    #[error("ir for the field {found:?}, expected {expected:?}")]
+    FieldTypeMismatch {
+        expected: FieldType,
+        found: FieldType,
+    },
+
+    #[error("the circuit has no layer")]
+    EmptyCircuit,
+
+    #[error("layer {layer} has {found} input variables, but the layer below has {expected} output variables")]
+    VarNumMismatch {
+        layer: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("gate {gate} of layer {layer} has {found} inputs, expected {expected}")]
+    InputCountMismatch {
+        layer: usize,
+        gate: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("gate {gate} of layer {layer} reads a wire of layer {wire_layer}")]
+    NonAdjacentWire {
+        layer: usize,
+        gate: usize,
+        wire_layer: usize,
+    },
+
+    #[error("gate {gate} of layer {layer} uses wire {wire}, out of the {size} wires")]
+    WireOutOfRange {
+        layer: usize,
+        gate: usize,
+        wire: usize,
+        size: usize,
+    },
+
+    #[error("gate {gate} of layer {layer} has the unknown type {gate_type}")]
+    UnknownGateType {
+        layer: usize,
+        gate: usize,
+        gate_type: usize,
+    },
+
+    #[error("invalid coefficient {0}")]
+    InvalidCoef(String),
+
+    #[error("json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("cbor error: {0}")]
+    CborError(String),
+}
+
+/// A wire of the circuit values: layer 0 is the input of the circuit, and layer `l + 1` the
+/// output of the circuit layer `l`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IRWireRef {
+    pub layer: usize,
+    pub wire: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateOpIR {
+    /// coef * in_0 * in_1
+    Mul,
+    /// coef * in_0
+    Add,
+    /// coef, with no input
+    Const,
+    /// coef * f(in_0), with f the pow5 gate for the type 12345, and the identity for 12346
+    Uni { gate_type: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IRCoef {
+    /// The canonical value of the field element, in decimal.
+    Constant(String),
+    /// Drawn from the transcript when proving.
+    Random,
+    /// The public input of the given index, for const gates only.
+    PublicInput(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IRGate {
+    pub op: GateOpIR,
+    pub inputs: Vec<IRWireRef>,
+    /// The wire of the output of the layer the gate adds its value to.
+    pub output: usize,
+    pub coef: IRCoef,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IRLayer {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    pub gates: Vec<IRGate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GKRCircuitIR {
+    pub version: u16,
+    #[serde(with = "field_type_name")]
+    pub field_type: FieldType,
+    pub layers: Vec<IRLayer>,
+    /// The number of leading outputs that must be zero, see `Circuit::expected_num_output_zeros`.
+    #[serde(default)]
+    pub expected_num_output_zeros: usize,
+}
+
+impl GKRCircuitIR {
+    pub fn to_json(&self) -> Result<String, IRError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, IRError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_cbor(&self) -> Result<Vec<u8>, IRError> {
+        let mut bytes = vec![];
+        ciborium::into_writer(self, &mut bytes).map_err(|e| IRError::CborError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, IRError> {
+        ciborium::from_reader(bytes).map_err(|e| IRError::CborError(e.to_string()))
+    }
+}
+
+/// Conversions between `Circuit` and its IR.
+pub trait IRConvertible: Sized {
+    fn to_ir(&self) -> GKRCircuitIR;
+
+    /// The circuit, with its structure info and random coefficients identified, ready to be
+    /// proven. Fails unless the IR is well formed and for the field of the config.
+    fn from_ir(ir: GKRCircuitIR) -> Result<Self, IRError>;
+}
+
+impl<C: GKRConfig> IRConvertible for Circuit<C> {
+    fn to_ir(&self) -> GKRCircuitIR {
+        let layers = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(l, layer)| {
+                let wires = |i_ids: &[usize]| {
+                    i_ids
+                        .iter()
+                        .map(|&wire| IRWireRef { layer: l, wire })
+                        .collect::<Vec<_>>()
+                };
+                let mul = layer
+                    .mul
+                    .iter()
+                    .map(|g| ir_gate(GateOpIR::Mul, wires(&g.i_ids), g));
+                let add = layer
+                    .add
+                    .iter()
+                    .map(|g| ir_gate(GateOpIR::Add, wires(&g.i_ids), g));
+                let cst = layer
+                    .const_
+                    .iter()
+                    .map(|g| ir_gate(GateOpIR::Const, vec![], g));
+                let uni = layer.uni.iter().map(|g| {
+                    let op = GateOpIR::Uni {
+                        gate_type: g.gate_type,
+                    };
+                    ir_gate(op, wires(&g.i_ids), g)
+                });
+                IRLayer {
+                    input_var_num: layer.input_var_num,
+                    output_var_num: layer.output_var_num,
+                    gates: mul.chain(add).chain(cst).chain(uni).collect(),
+                }
+            })
+            .collect();
+
+        GKRCircuitIR {
+            version: GKR_CIRCUIT_IR_VERSION,
+            field_type: C::FIELD_TYPE,
+            layers,
+            expected_num_output_zeros: self.expected_num_output_zeros,
+        }
+    }
+
+    fn from_ir(ir: GKRCircuitIR) -> Result<Self, IRError> {
+        if ir.version > GKR_CIRCUIT_IR_VERSION {
+            return Err(IRError::UnsupportedVersion(ir.version));
+        }
+        if ir.field_type != C::FIELD_TYPE {
+            return Err(IRError::FieldTypeMismatch {
+                expected: C::FIELD_TYPE,
+                found: ir.field_type,
+            });
+        }
+        if ir.layers.is_empty() {
+            return Err(IRError::EmptyCircuit);
+        }
+
+        let mut layers = vec![];
+        for (l, ir_layer) in ir.layers.iter().enumerate() {
+            if l > 0 && ir.layers[l - 1].output_var_num != ir_layer.input_var_num {
+                return Err(IRError::VarNumMismatch {
+                    layer: l,
+                    expected: ir.layers[l - 1].output_var_num,
+                    found: ir_layer.input_var_num,
+                });
+            }
+            let mut layer = CircuitLayer::<C> {
+                input_var_num: ir_layer.input_var_num,
+                output_var_num: ir_layer.output_var_num,
+                ..Default::default()
+            };
+            for (i, ir_gate) in ir_layer.gates.iter().enumerate() {
+                match ir_gate.op {
+                    GateOpIR::Mul => layer.mul.push(circuit_gate(l, i, ir_layer, ir_gate)?),
+                    GateOpIR::Add => layer.add.push(circuit_gate(l, i, ir_layer, ir_gate)?),
+                    GateOpIR::Const => layer.const_.push(circuit_gate(l, i, ir_layer, ir_gate)?),
+                    GateOpIR::Uni { gate_type } => {
+                        if gate_type != POW5_GATE_TYPE && gate_type != IDENTITY_GATE_TYPE {
+                            return Err(IRError::UnknownGateType {
+                                layer: l,
+                                gate: i,
+                                gate_type,
+                            });
+                        }
+                        let mut gate: GateUni<C> = circuit_gate(l, i, ir_layer, ir_gate)?;
+                        gate.gate_type = gate_type;
+                        layer.uni.push(gate);
+                    }
+                }
+            }
+            layer.identify_structure_info();
+            layers.push(layer);
+        }
+
+        let mut circuit = Circuit::<C> {
+            layers,
+            expected_num_output_zeros: ir.expected_num_output_zeros,
+            ..Default::default()
+        };
+        circuit.identify_rnd_coefs();
+        Ok(circuit)
+    }
+}
+
+fn ir_gate<C: GKRConfig, const INPUT_NUM: usize>(
+    op: GateOpIR,
+    inputs: Vec<IRWireRef>,
+    gate: &Gate<C, INPUT_NUM>,
+) -> IRGate {
+    let coef = match gate.coef_type {
+        CoefType::Constant => IRCoef::Constant(gate.coef.to_u256().to_string()),
+        CoefType::Random => IRCoef::Random,
+        CoefType::PublicInput(idx) => IRCoef::PublicInput(idx),
+    };
+    IRGate {
+        op,
+        inputs,
+        output: gate.o_id,
+        coef,
+    }
+}
+
+/// The gate `gate` of the layer `layer`, checked against the sizes of the layer.
+fn circuit_gate<C: GKRConfig, const INPUT_NUM: usize>(
+    layer: usize,
+    gate: usize,
+    ir_layer: &IRLayer,
+    ir_gate: &IRGate,
+) -> Result<Gate<C, INPUT_NUM>, IRError> {
+    if ir_gate.inputs.len() != INPUT_NUM {
+        return Err(IRError::InputCountMismatch {
+            layer,
+            gate,
+            expected: INPUT_NUM,
+            found: ir_gate.inputs.len(),
+        });
+    }
+    let input_size = 1 << ir_layer.input_var_num;
+    let mut i_ids = [0; INPUT_NUM];
+    for (id, input) in i_ids.iter_mut().zip(&ir_gate.inputs) {
+        if input.layer != layer {
+            return Err(IRError::NonAdjacentWire {
+                layer,
+                gate,
+                wire_layer: input.layer,
+            });
+        }
+        if input.wire >= input_size {
+            return Err(IRError::WireOutOfRange {
+                layer,
+                gate,
+                wire: input.wire,
+                size: input_size,
+            });
+        }
+        *id = input.wire;
+    }
+    let output_size = 1 << ir_layer.output_var_num;
+    if ir_gate.output >= output_size {
+        return Err(IRError::WireOutOfRange {
+            layer,
+            gate,
+            wire: ir_gate.output,
+            size: output_size,
+        });
+    }
+
+    let (coef_type, coef) = match &ir_gate.coef {
+        IRCoef::Constant(value) => (CoefType::Constant, parse_coef::<C>(value)?),
+        IRCoef::Random => (CoefType::Random, C::CircuitField::ZERO),
+        IRCoef::PublicInput(idx) if INPUT_NUM == 0 => {
+            (CoefType::PublicInput(*idx), C::CircuitField::ZERO)
+        }
+        IRCoef::PublicInput(_) => {
+            return Err(IRError::InvalidCoef(
+                "public inputs are for const gates only".to_string(),
+            ))
+        }
+    };
+    Ok(Gate {
+        i_ids,
+        o_id: ir_gate.output,
+        coef_type,
+        coef,
+        gate_type: 0,
+    })
+}
+
+fn parse_coef<C: GKRConfig>(value: &str) -> Result<C::CircuitField, IRError> {
+    let v = U256::from_str_radix(value, 10).map_err(|_| IRError::InvalidCoef(value.to_string()))?;
+    if v >= C::CircuitField::modulus() {
+        return Err(IRError::InvalidCoef(value.to_string()));
+    }
+    Ok(C::CircuitField::from_u256(v))
+}
+
+/// `FieldType` as its variant name, the config crate having no serde support.
+mod field_type_name {
+    use config::FieldType;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    const FIELD_TYPES: [(FieldType, &str); 6] = [
+        (FieldType::M31, "M31"),
+        (FieldType::BN254, "BN254"),
+        (FieldType::GF2, "GF2"),
+        (FieldType::Goldilocks, "Goldilocks"),
+        (FieldType::BabyBear, "BabyBear"),
+        (FieldType::Native, "Native"),
+    ];
+
+    pub fn serialize<S: Serializer>(field_type: &FieldType, s: S) -> Result<S::Ok, S::Error> {
+        let (_, name) = FIELD_TYPES.iter().find(|(t, _)| t == field_type).unwrap();
+        s.serialize_str(name)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<FieldType, D::Error> {
+        let name = String::deserialize(d)?;
+        FIELD_TYPES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(t, _)| t.clone())
+            .ok_or_else(|| D::Error::custom(format!("unknown field type {}", name)))
+    }
+}
@@ -0,0 +1,147 @@
+//! The jobs of the requests answered before they are done, e.g., the proofs of `/prove_async`.
+//!
+//! A job is pending until a worker starts it, running until it finishes, then kept with its
+//! result for the time to live of the store. The finished jobs past their time to live are
+//! evicted on every access, so that the results no one fetches do not accumulate, and the jobs
+//! not finished yet are at most a maximum, so that the witnesses waiting for a worker do not
+//! either.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobState<T> {
+    Pending,
+    Running,
+    Finished(T),
+}
+
+struct Job<T> {
+    state: JobState<T>,
+    finished_at: Option<Instant>,
+}
+
+pub struct JobStore<T> {
+    jobs: Mutex<HashMap<String, Job<T>>>,
+    ttl: Duration,
+    max_unfinished: usize,
+}
+
+impl<T: Clone> JobStore<T> {
+    pub fn new(ttl: Duration, max_unfinished: usize) -> Self {
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+            ttl,
+            max_unfinished,
+        }
+    }
+
+    /// How long a finished job is kept.
+    #[inline]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// How many jobs may be pending or running at once.
+    #[inline]
+    pub fn max_unfinished(&self) -> usize {
+        self.max_unfinished
+    }
+
+    /// A new pending job, or `None` if the jobs pending or running are already at the maximum.
+    /// Its id is 128 random bits as hex, so that the results of a client cannot be fetched by
+    /// another one guessing its id.
+    pub fn submit(&self) -> Option<String> {
+        let mut jobs = self.lock_evicted();
+        if Self::count_unfinished(&jobs) >= self.max_unfinished {
+            return None;
+        }
+        let id = loop {
+            let id = format!("{:032x}", rand::thread_rng().gen::<u128>());
+            if !jobs.contains_key(&id) {
+                break id;
+            }
+        };
+        jobs.insert(
+            id.clone(),
+            Job {
+                state: JobState::Pending,
+                finished_at: None,
+            },
+        );
+        Some(id)
+    }
+
+    /// Mark the job as running, unless it was evicted.
+    pub fn start(&self, id: &str) {
+        if let Some(job) = self.lock_evicted().get_mut(id) {
+            job.state = JobState::Running;
+        }
+    }
+
+    /// Record the result of the job, kept from now on for the time to live of the store.
+    pub fn finish(&self, id: &str, result: T) {
+        if let Some(job) = self.lock_evicted().get_mut(id) {
+            job.state = JobState::Finished(result);
+            job.finished_at = Some(Instant::now());
+        }
+    }
+
+    /// The state of the job, or `None` if there is no such job, e.g., if it was evicted.
+    pub fn get(&self, id: &str) -> Option<JobState<T>> {
+        self.inspect(id, JobState::clone)
+    }
+
+    /// `f` of the state of the job, without cloning its result, or `None` if there is no such
+    /// job. The store is locked while `f` runs.
+    pub fn inspect<R>(&self, id: &str, f: impl FnOnce(&JobState<T>) -> R) -> Option<R> {
+        self.lock_evicted().get(id).map(|job| f(&job.state))
+    }
+
+    /// The number of jobs kept, finished or not.
+    pub fn len(&self) -> usize {
+        self.lock_evicted().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of jobs pending or running.
+    pub fn unfinished(&self) -> usize {
+        Self::count_unfinished(&self.lock_evicted())
+    }
+
+    /// Evict the jobs finished more than the time to live before `now`, and return how many.
+    pub fn evict_expired(&self, now: Instant) -> usize {
+        let mut jobs = self.jobs.lock().unwrap();
+        Self::evict(&mut jobs, self.ttl, now)
+    }
+
+    fn lock_evicted(&self) -> MutexGuard<'_, HashMap<String, Job<T>>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        Self::evict(&mut jobs, self.ttl, Instant::now());
+        jobs
+    }
+
+    fn count_unfinished(jobs: &HashMap<String, Job<T>>) -> usize {
+        jobs.values()
+            .filter(|job| !matches!(job.state, JobState::Finished(_)))
+            .count()
+    }
+
+    fn evict(jobs: &mut HashMap<String, Job<T>>, ttl: Duration, now: Instant) -> usize {
+        let len = jobs.len();
+        jobs.retain(|_, job| match job.finished_at {
+            Some(finished_at) => now.saturating_duration_since(finished_at) <= ttl,
+            None => true,
+        });
+        len - jobs.len()
+    }
+}
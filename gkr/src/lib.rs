@@ -1,15 +1,223 @@
 #![cfg_attr(target_arch = "x86_64", feature(stdarch_x86_avx512))]
 
+pub mod annotation;
+pub use annotation::*;
+
+pub mod arithmetic_optimizer;
+pub use arithmetic_optimizer::*;
+
+pub mod benchmark;
+pub use benchmark::*;
+
+pub mod certified_circuit;
+pub use certified_circuit::*;
+
+pub mod chained_prover;
+pub use chained_prover::*;
+
+pub mod chunked_prover;
+pub use chunked_prover::*;
+
+pub mod circuit_dsl;
+
+pub mod circuit_dump;
+pub use circuit_dump::*;
+
+pub mod circuit_library;
+pub use circuit_library::*;
+
+pub mod circuit_patcher;
+pub use circuit_patcher::*;
+
+pub mod client;
+pub use client::*;
+
+pub mod complexity;
+pub use complexity::*;
+
+pub mod constraint_debugger;
+pub use constraint_debugger::*;
+
+pub mod continuation;
+
+pub mod degree_optimizer;
+pub use degree_optimizer::*;
+
+pub mod deployer;
+pub use deployer::*;
+
+pub mod docs;
+
+pub mod dynamic_circuit;
+pub use dynamic_circuit::*;
+
+pub mod encoding;
+
+pub mod executor;
+
+pub mod expression_simplifier;
+pub use expression_simplifier::*;
+
+pub mod extended_witness;
+pub use extended_witness::*;
+
+pub mod fiat_shamir_detector;
+pub use fiat_shamir_detector::*;
+
+pub mod field_proof;
+pub use field_proof::*;
+
+pub mod field_serializer;
+pub use field_serializer::*;
+
+pub mod fingerprint_registry;
+pub use fingerprint_registry::*;
+
+pub mod fuzz_safe_loader;
+pub use fuzz_safe_loader::*;
+
+#[cfg(feature = "server")]
+pub mod grpc;
+#[cfg(feature = "server")]
+pub use grpc::*;
+
+pub mod interactive_session;
+pub use interactive_session::*;
+
+pub mod ir;
+pub use ir::*;
+
+pub mod job_store;
+pub use job_store::*;
+
+pub mod maxflow;
+pub use maxflow::*;
+
+#[cfg(feature = "mpi")]
+pub mod mpi_broadcast;
+#[cfg(feature = "mpi")]
+pub use mpi_broadcast::*;
+
+#[cfg(feature = "mpi")]
+pub mod mpi_group_prover;
+#[cfg(feature = "mpi")]
+pub use mpi_group_prover::*;
+
+#[cfg(feature = "mpi")]
+pub mod mpi_health_check;
+#[cfg(feature = "mpi")]
+pub use mpi_health_check::*;
+
+#[cfg(feature = "mpi")]
+pub mod mpi_latency_profiler;
+#[cfg(feature = "mpi")]
+pub use mpi_latency_profiler::*;
+
+pub mod mpi_orchestrator;
+pub use mpi_orchestrator::*;
+
+pub mod multithreaded_hasher;
+pub use multithreaded_hasher::*;
+
 pub mod poly_commit;
 pub use poly_commit::*;
 
+pub mod privacy_analyzer;
+pub use privacy_analyzer::*;
+
+pub mod profiler;
+pub use profiler::*;
+
+pub mod proof_layout;
+pub use proof_layout::*;
+
+pub mod proof_package;
+pub use proof_package::*;
+
+pub mod proof_pruner;
+pub use proof_pruner::*;
+
+pub mod proof_size_budget;
+pub use proof_size_budget::*;
+
+pub mod protocol_constants;
+pub use protocol_constants::*;
+
+pub mod prove_metrics;
+pub use prove_metrics::*;
+
 pub mod prover;
 pub use prover::*;
 
+pub mod reconstruction;
+pub use reconstruction::*;
+
+#[cfg(feature = "repl")]
+pub mod repl;
+#[cfg(feature = "repl")]
+pub use repl::*;
+
+pub mod round_compressor;
+pub use round_compressor::*;
+
+pub mod scheme_selector;
+pub use scheme_selector::*;
+
+pub mod security;
+pub use security::*;
+
+#[cfg(feature = "server")]
+pub mod telemetry;
+#[cfg(feature = "server")]
+pub use telemetry::*;
+
+pub mod tournament;
+pub use tournament::*;
+
+pub mod verification_gadget;
+pub use verification_gadget::*;
+
+pub mod verification_key;
+pub use verification_key::*;
+
 pub mod verifier;
 pub use verifier::*;
 
+#[cfg(feature = "server")]
+pub mod verifier_bridge;
+#[cfg(feature = "server")]
+pub use verifier_bridge::*;
+
 pub mod utils;
 
+pub mod vk_registry;
+pub use vk_registry::*;
+
+pub mod witness_distributor;
+pub use witness_distributor::*;
+
+pub mod witness_encryption;
+pub use witness_encryption::*;
+
+pub mod witness_finder;
+pub use witness_finder::*;
+
+pub mod witness_json;
+pub use witness_json::*;
+
+pub mod witness_loader;
+pub use witness_loader::*;
+
+pub mod witness_oracle;
+pub use witness_oracle::*;
+
+pub mod witness_schema;
+pub use witness_schema::*;
+
+#[cfg(feature = "server")]
+pub mod worker_pool;
+#[cfg(feature = "server")]
+pub use worker_pool::*;
+
 #[cfg(test)]
 mod tests;
@@ -96,6 +96,7 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
             FieldType::GF2 => Circuit::<C>::load_circuit(KECCAK_GF2_CIRCUIT),
             FieldType::M31 => Circuit::<C>::load_circuit(KECCAK_M31_CIRCUIT),
             FieldType::BN254 => Circuit::<C>::load_circuit(KECCAK_BN254_CIRCUIT),
+            _ => unreachable!("not supported"),
         },
         "poseidon" => match C::FIELD_TYPE {
             FieldType::M31 => Circuit::<C>::load_circuit(POSEIDON_M31_CIRCUIT),
@@ -110,6 +111,7 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
             FieldType::GF2 => KECCAK_GF2_WITNESS,
             FieldType::M31 => KECCAK_M31_WITNESS,
             FieldType::BN254 => KECCAK_BN254_WITNESS,
+            _ => unreachable!("not supported"),
         },
         "poseidon" => match C::FIELD_TYPE {
             FieldType::M31 => POSEIDON_M31_WITNESS,
@@ -156,7 +158,7 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
             thread::spawn(move || {
                 loop {
                     // bench func
-                    let mut prover = Prover::new(&local_config);
+                    let mut prover = Prover::new(&local_config).unwrap();
                     prover.prepare_mem(&c);
                     prover.prove(&mut c);
                     // update cnt
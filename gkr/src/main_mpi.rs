@@ -1,15 +1,15 @@
 use circuit::Circuit;
 use clap::Parser;
 use config::{
-    BN254ConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigSha2,
-    MPIConfig,
+    root_println, BN254ConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig, GKRScheme,
+    M31ExtConfigSha2, MPIConfig,
 };
 use gkr::{
     utils::{
         KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS,
         KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS, POSEIDON_M31_CIRCUIT, POSEIDON_M31_WITNESS,
     },
-    Prover,
+    MPIBroadcastCircuit, MPIHealthCheck, MPILatencyProfiler, Prover,
 };
 
 /// ...
@@ -27,6 +27,14 @@ struct Args {
     /// number of repeat
     #[arg(short, long, default_value_t = 1)]
     repeats: usize,
+
+    /// load the circuit on the root only, and broadcast it to the other ranks
+    #[arg(long, default_value_t = false)]
+    broadcast_circuit: bool,
+
+    /// warn if the round-trip latency between two ranks exceeds this many microseconds
+    #[arg(long, default_value_t = 1000)]
+    max_latency_us: u64,
 }
 
 fn main() {
@@ -34,6 +42,28 @@ fn main() {
     print_info(&args);
 
     let mpi_config = MPIConfig::new();
+    if let Err(e) = mpi_config.check_world_size() {
+        root_println!(mpi_config, "Error: {}", e);
+        std::process::exit(1);
+    }
+    if let Err(e) = MPIHealthCheck::verify_all_ranks_ready(&mpi_config) {
+        println!("Rank {}: {}", mpi_config.world_rank(), e);
+        std::process::exit(1);
+    }
+    if mpi_config.world_size() > 1 {
+        let latency = MPILatencyProfiler::ping_all_ranks(&mpi_config);
+        if latency.max_latency_us() > args.max_latency_us {
+            let (a, b) = latency.worst_pair().unwrap();
+            root_println!(
+                mpi_config,
+                "Warning: round-trip latency of {} us between ranks {} and {}, above {} us",
+                latency.max_latency_us(),
+                a,
+                b,
+                args.max_latency_us
+            );
+        }
+    }
 
     match args.field.as_str() {
         "m31ext3" => match args.scheme.as_str() {
@@ -79,24 +109,35 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
     let pack_size = C::get_field_pack_size();
 
     // load circuit
-    let mut circuit = match args.scheme.as_str() {
+    let circuit_path = match args.scheme.as_str() {
         "keccak" => match C::FIELD_TYPE {
-            FieldType::GF2 => Circuit::<C>::load_circuit(KECCAK_GF2_CIRCUIT),
-            FieldType::M31 => Circuit::<C>::load_circuit(KECCAK_M31_CIRCUIT),
-            FieldType::BN254 => Circuit::<C>::load_circuit(KECCAK_BN254_CIRCUIT),
+            FieldType::GF2 => KECCAK_GF2_CIRCUIT,
+            FieldType::M31 => KECCAK_M31_CIRCUIT,
+            FieldType::BN254 => KECCAK_BN254_CIRCUIT,
+            _ => unreachable!("not supported"),
         },
         "poseidon" => match C::FIELD_TYPE {
-            FieldType::M31 => Circuit::<C>::load_circuit(POSEIDON_M31_CIRCUIT),
+            FieldType::M31 => POSEIDON_M31_CIRCUIT,
             _ => unreachable!(),
         },
         _ => unreachable!(),
     };
+    let mut circuit = if args.broadcast_circuit {
+        let circuit = config
+            .mpi_config
+            .is_root()
+            .then(|| Circuit::<C>::load_circuit(circuit_path));
+        MPIBroadcastCircuit::broadcast(circuit, &config.mpi_config)
+    } else {
+        Circuit::<C>::load_circuit(circuit_path)
+    };
 
     let witness_path = match args.scheme.as_str() {
         "keccak" => match C::FIELD_TYPE {
             FieldType::GF2 => KECCAK_GF2_WITNESS,
             FieldType::M31 => KECCAK_M31_WITNESS,
             FieldType::BN254 => KECCAK_BN254_WITNESS,
+            _ => unreachable!("not supported"),
         },
         "poseidon" => match C::FIELD_TYPE {
             FieldType::M31 => POSEIDON_M31_WITNESS,
@@ -130,7 +171,7 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
         config.mpi_config.barrier(); // wait until everyone is here
         let start_time = std::time::Instant::now();
         for _j in 0..N_PROOF {
-            let mut prover = Prover::new(&config);
+            let mut prover = Prover::new(&config).unwrap();
             prover.prepare_mem(&circuit);
             prover.prove(&mut circuit);
         }
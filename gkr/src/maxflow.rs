@@ -0,0 +1,190 @@
+//! Checking that the outputs of a circuit are computed from its inputs, rather than hardcoded.
+//!
+//! The circuit is seen as a flow network: a node per wire of each layer, an edge of capacity 1
+//! from each input wire of a gate to its output wire, a source feeding the input layer and the
+//! public inputs, and a sink fed by the output layer.
+
+use std::collections::VecDeque;
+
+use circuit::{Circuit, CoefType, GateKind, GateRef};
+use config::GKRConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaxflowResult {
+    /// Every output is reachable from the inputs, and `flow` wire-disjoint paths lead from the
+    /// inputs to the outputs.
+    Connected { flow: usize },
+    /// The output wire of the gate depends on no input, e.g., it is only written by constant
+    /// gates.
+    Disconnected { output_gate: GateRef },
+}
+
+pub struct CircuitMaxflow;
+
+struct Edge {
+    to: usize,
+    cap: usize,
+}
+
+/// A flow network, with each edge followed by its reverse edge.
+struct Network {
+    edges: Vec<Edge>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Network {
+    fn new(node_num: usize) -> Self {
+        Self {
+            edges: vec![],
+            adjacency: vec![vec![]; node_num],
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize) {
+        self.adjacency[from].push(self.edges.len());
+        self.edges.push(Edge { to, cap: 1 });
+        self.adjacency[to].push(self.edges.len());
+        self.edges.push(Edge { to: from, cap: 0 });
+    }
+
+    /// The nodes reachable from the source through edges with remaining capacity,
+    /// as BFS levels.
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.adjacency.len()];
+        levels[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &e in &self.adjacency[node] {
+                let edge = &self.edges[e];
+                if edge.cap > 0 && levels[edge.to].is_none() {
+                    levels[edge.to] = Some(levels[node].unwrap() + 1);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        levels
+    }
+
+    /// Dinic's algorithm, with an iterative search of the blocking flows as the networks of
+    /// deep circuits are deep.
+    fn max_flow(&mut self, source: usize, sink: usize) -> usize {
+        let mut flow = 0;
+        loop {
+            let levels = self.levels(source);
+            if levels[sink].is_none() {
+                return flow;
+            }
+            let mut next = vec![0; self.adjacency.len()];
+            loop {
+                // the edges of the current path
+                let mut path: Vec<usize> = vec![];
+                let mut node = source;
+                while node != sink {
+                    let edge = self.adjacency[node][next[node]..]
+                        .iter()
+                        .position(|&e| {
+                            let edge = &self.edges[e];
+                            edge.cap > 0 && levels[edge.to] == levels[node].map(|l| l + 1)
+                        })
+                        .map(|k| next[node] + k);
+                    match edge {
+                        Some(k) => {
+                            next[node] = k;
+                            let e = self.adjacency[node][k];
+                            path.push(e);
+                            node = self.edges[e].to;
+                        }
+                        None => {
+                            // dead end
+                            next[node] = self.adjacency[node].len();
+                            match path.pop() {
+                                Some(e) => {
+                                    node = self.edges[e ^ 1].to;
+                                    next[node] += 1;
+                                }
+                                None => break,
+                            }
+                        }
+                    }
+                }
+                if node != sink {
+                    break;
+                }
+                for e in path {
+                    self.edges[e].cap -= 1;
+                    self.edges[e ^ 1].cap += 1;
+                }
+                flow += 1;
+            }
+        }
+    }
+}
+
+impl CircuitMaxflow {
+    pub fn compute<C: GKRConfig>(circuit: &Circuit<C>) -> MaxflowResult {
+        // the first node of the wires of each layer, the input layer first
+        let mut offsets = vec![0];
+        let mut node_num = 1 << circuit.layers[0].input_var_num;
+        for layer in &circuit.layers {
+            offsets.push(node_num);
+            node_num += 1 << layer.output_var_num;
+        }
+        let (source, sink) = (node_num, node_num + 1);
+        let mut network = Network::new(node_num + 2);
+
+        for wire in 0..1 << circuit.layers[0].input_var_num {
+            network.add_edge(source, wire);
+        }
+        for (l, layer) in circuit.layers.iter().enumerate() {
+            let (input, output) = (offsets[l], offsets[l + 1]);
+            for g in &layer.mul {
+                network.add_edge(input + g.i_ids[0], output + g.o_id);
+                network.add_edge(input + g.i_ids[1], output + g.o_id);
+            }
+            for g in &layer.add {
+                network.add_edge(input + g.i_ids[0], output + g.o_id);
+            }
+            for g in &layer.uni {
+                network.add_edge(input + g.i_ids[0], output + g.o_id);
+            }
+            for g in &layer.const_ {
+                if let CoefType::PublicInput(_) = g.coef_type {
+                    network.add_edge(source, output + g.o_id);
+                }
+            }
+        }
+
+        let last = circuit.layers.len() - 1;
+        let output = offsets[last + 1];
+        let levels = network.levels(source);
+        if let Some(output_gate) = output_gates(circuit, last)
+            .into_iter()
+            .find(|(_, o_id)| levels[output + o_id].is_none())
+            .map(|(gate, _)| gate)
+        {
+            return MaxflowResult::Disconnected { output_gate };
+        }
+
+        for wire in 0..1 << circuit.layers[last].output_var_num {
+            network.add_edge(output + wire, sink);
+        }
+        MaxflowResult::Connected {
+            flow: network.max_flow(source, sink),
+        }
+    }
+}
+
+/// The gates of the layer with their output wires, in the order of `GateKind`.
+fn output_gates<C: GKRConfig>(circuit: &Circuit<C>, layer: usize) -> Vec<(GateRef, usize)> {
+    let gate_ref = |kind, index| GateRef { layer, kind, index };
+    let l = &circuit.layers[layer];
+    let mul = l.mul.iter().map(|g| g.o_id).enumerate();
+    let add = l.add.iter().map(|g| g.o_id).enumerate();
+    let cst = l.const_.iter().map(|g| g.o_id).enumerate();
+    let uni = l.uni.iter().map(|g| g.o_id).enumerate();
+    mul.map(|(i, o)| (gate_ref(GateKind::Mul, i), o))
+        .chain(add.map(|(i, o)| (gate_ref(GateKind::Add, i), o)))
+        .chain(cst.map(|(i, o)| (gate_ref(GateKind::Const, i), o)))
+        .chain(uni.map(|(i, o)| (gate_ref(GateKind::Uni, i), o)))
+        .collect()
+}
@@ -0,0 +1,54 @@
+//! Distributing the circuit from the root to the other ranks, for environments without shared
+//! storage. Only the circuit is sent, in its canonical encoding: each rank still provides its
+//! own witness.
+
+use std::marker::PhantomData;
+
+use circuit::Circuit;
+use config::{GKRConfig, MPIConfig};
+use mpi::traits::*;
+
+/// The largest message of a single broadcast, below the 2^31 elements MPI counts are limited to.
+const MAX_MESSAGE_SIZE: usize = 1 << 30;
+
+pub struct MPIBroadcastCircuit<C: GKRConfig> {
+    _marker: PhantomData<C>,
+}
+
+impl<C: GKRConfig> MPIBroadcastCircuit<C> {
+    /// Must be called by all the ranks, the root with the circuit and the others with `None`.
+    /// The root gets its circuit back untouched, the other ranks a copy without input values.
+    pub fn broadcast(circuit: Option<Circuit<C>>, mpi_config: &MPIConfig) -> Circuit<C> {
+        assert_eq!(
+            circuit.is_some(),
+            mpi_config.is_root(),
+            "the circuit must be given by the root, and only by the root"
+        );
+        if mpi_config.world_size() == 1 {
+            return circuit.unwrap();
+        }
+
+        let root = mpi_config.root_process();
+        let mut bytes = match &circuit {
+            Some(circuit) => circuit.canonical_bytes(),
+            None => vec![],
+        };
+        let mut len = bytes.len() as u64;
+        root.broadcast_into(&mut len);
+        bytes.resize(len as usize, 0);
+        for chunk in bytes.chunks_mut(MAX_MESSAGE_SIZE) {
+            root.broadcast_into(chunk);
+        }
+
+        match circuit {
+            Some(circuit) => circuit,
+            None => {
+                let mut circuit = Circuit::<C>::from_canonical_bytes(&bytes)
+                    .expect("invalid circuit broadcast by the root");
+                circuit.identify_rnd_coefs();
+                circuit.identify_structure_info();
+                circuit
+            }
+        }
+    }
+}
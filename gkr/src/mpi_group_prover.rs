@@ -0,0 +1,143 @@
+//! Splitting the MPI world into groups proving independent circuits.
+//!
+//! Rank `r` joins group `r % num_groups`, and the prover of each group only talks to the ranks
+//! of its group, as if they were the whole world. The root of a group is its lowest world rank.
+
+use std::ptr::NonNull;
+
+use circuit::Circuit;
+use config::{Config, ConfigError, GKRConfig, MPIConfig};
+use mpi::{
+    topology::{Color, SimpleCommunicator},
+    traits::*,
+};
+use transcript::Proof;
+
+use crate::Prover;
+
+/// The communicator of a group, split from the world, and freed, with `MPI_Comm_free`, when
+/// dropped: by all the ranks of the group, as it is collective.
+#[derive(Debug)]
+struct GroupCommunicator {
+    comm: NonNull<SimpleCommunicator>,
+}
+
+impl GroupCommunicator {
+    fn new(comm: SimpleCommunicator) -> Self {
+        Self {
+            comm: NonNull::from(Box::leak(Box::new(comm))),
+        }
+    }
+
+    /// The communicator, for the `MPIConfig` of the group, which must not outlive `self`.
+    unsafe fn get(&self) -> &'static SimpleCommunicator {
+        &*self.comm.as_ptr()
+    }
+}
+
+impl Drop for GroupCommunicator {
+    fn drop(&mut self) {
+        // the communicator frees itself when dropped
+        drop(unsafe { Box::from_raw(self.comm.as_ptr()) });
+    }
+}
+
+/// The configs of the group, e.g., those of `group_config` and their clones, borrow the
+/// communicator of the group, which lives as long as the prover: they must not outlive it.
+#[derive(Debug)]
+pub struct MPIGroupProver {
+    num_groups: usize,
+    group_id: usize,
+    // dropped before the communicator it borrows, which is only held to be freed
+    group_config: MPIConfig,
+    _group_comm: Option<GroupCommunicator>,
+}
+
+impl MPIGroupProver {
+    /// Must be called by all the ranks of the world.
+    pub fn new(config: &MPIConfig, num_groups: usize) -> Self {
+        assert!(num_groups > 0);
+        assert_eq!(
+            config.world_size() % num_groups,
+            0,
+            "world size {} is not a multiple of the number of groups {}",
+            config.world_size(),
+            num_groups
+        );
+        let group_size = config.world_size() / num_groups;
+        assert!(
+            group_size.is_power_of_two(),
+            "group size {} is not a power of 2",
+            group_size
+        );
+
+        let group_id = config.world_rank() % num_groups;
+        let (group_config, group_comm) = if config.world_size() == 1 {
+            (config.clone(), None)
+        } else {
+            let group_comm = GroupCommunicator::new(
+                config
+                    .world
+                    .unwrap()
+                    .split_by_color(Color::with_value(group_id as i32))
+                    .unwrap(),
+            );
+            // the config does not outlive the communicator, both being of the prover
+            let group = unsafe { group_comm.get() };
+            let group_config = MPIConfig {
+                universe: config.universe,
+                world_size: group.size(),
+                world_rank: group.rank(),
+                world: Some(group),
+            };
+            (group_config, Some(group_comm))
+        };
+
+        Self {
+            num_groups,
+            group_id,
+            group_config,
+            _group_comm: group_comm,
+        }
+    }
+
+    #[inline]
+    pub fn num_groups(&self) -> usize {
+        self.num_groups
+    }
+
+    /// The group of the current rank, i.e., which circuit it takes part in proving.
+    #[inline]
+    pub fn group_id(&self) -> usize {
+        self.group_id
+    }
+
+    /// The MPI config of the group, whose world is the group.
+    #[inline]
+    pub fn group_config(&self) -> &MPIConfig {
+        &self.group_config
+    }
+
+    #[inline]
+    pub fn is_group_root(&self) -> bool {
+        self.group_config.is_root()
+    }
+
+    /// Prove the circuit of the group, with the local witness of the rank loaded.
+    /// The result is returned on the root of the group only. Fails as `Prover::new` for the
+    /// config.
+    pub fn prove<C: GKRConfig>(
+        &self,
+        config: &Config<C>,
+        circuit: &mut Circuit<C>,
+    ) -> Result<Option<(Vec<C::ChallengeField>, Proof)>, ConfigError> {
+        let mut group_config = config.clone();
+        group_config.mpi_config = self.group_config.clone();
+
+        let mut prover = Prover::new(&group_config)?;
+        prover.prepare_mem(circuit);
+        let result = prover.prove_silent(circuit);
+
+        Ok(self.is_group_root().then_some(result))
+    }
+}
@@ -0,0 +1,156 @@
+//! Checking that all the MPI processes are alive and run the same version before proving.
+//!
+//! Blocking collectives hang forever if a process is gone, so the check only uses non-blocking
+//! operations polled until a deadline:
+//! 1. the root broadcasts its version hash;
+//! 2. every other process compares it with its own, and reports to the root;
+//! 3. the root broadcasts the outcome, so that all the processes return the same result;
+//! 4. on success, all the processes meet at a barrier.
+//!
+//! A request that does not complete in time cannot be cancelled safely, so its buffer is leaked
+//! with it, see `PendingBytes`. The processes are not expected to keep proving after a failure.
+
+use std::time::{Duration, Instant};
+
+use config::{MPIConfig, PendingBytes};
+use mpi::traits::*;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MPISyncError {
+    #[error("rank {0} did not respond in time")]
+    RankTimeout(usize),
+
+    #[error("rank {0} runs a different version than the root")]
+    VersionMismatch(usize),
+}
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+const ACK_TAG: i32 = 0x4850; // "HP"
+
+/// Outcome broadcast by the root: a status byte followed by the rank as a little-endian u64.
+const OUTCOME_SIZE: usize = 9;
+const STATUS_OK: u8 = 0;
+const STATUS_TIMEOUT: u8 = 1;
+const STATUS_MISMATCH: u8 = 2;
+
+pub struct MPIHealthCheck;
+
+impl MPIHealthCheck {
+    /// Hash identifying the build, compared across the processes.
+    pub fn version_hash() -> [u8; 32] {
+        Sha256::new()
+            .chain_update(env!("CARGO_PKG_NAME"))
+            .chain_update(env!("CARGO_PKG_VERSION"))
+            .finalize()
+            .into()
+    }
+
+    pub fn verify_all_ranks_ready(config: &MPIConfig) -> Result<(), MPISyncError> {
+        Self::verify_all_ranks_ready_with_timeout(config, DEFAULT_TIMEOUT)
+    }
+
+    pub fn verify_all_ranks_ready_with_timeout(
+        config: &MPIConfig,
+        timeout: Duration,
+    ) -> Result<(), MPISyncError> {
+        if config.world_size() == 1 {
+            return Ok(());
+        }
+        let deadline = Instant::now() + timeout;
+
+        // 1. broadcast the version hash of the root
+        let broadcast = config.immediate_root_broadcast_bytes(Self::version_hash().to_vec());
+
+        let outcome = if config.is_root() {
+            // 2. collect the reports, the root does not need its broadcast to complete for that
+            let outcome = Self::collect_reports(config, deadline);
+            broadcast.wait_until(deadline, POLL_INTERVAL);
+
+            // 3. share the outcome
+            let mut outcome_bytes = vec![0u8; OUTCOME_SIZE];
+            match outcome {
+                Ok(()) => outcome_bytes[0] = STATUS_OK,
+                Err(MPISyncError::RankTimeout(rank)) => {
+                    outcome_bytes[0] = STATUS_TIMEOUT;
+                    outcome_bytes[1..].copy_from_slice(&(rank as u64).to_le_bytes());
+                }
+                Err(MPISyncError::VersionMismatch(rank)) => {
+                    outcome_bytes[0] = STATUS_MISMATCH;
+                    outcome_bytes[1..].copy_from_slice(&(rank as u64).to_le_bytes());
+                }
+            }
+            config
+                .immediate_root_broadcast_bytes(outcome_bytes)
+                .wait_until(deadline + timeout, POLL_INTERVAL);
+            outcome
+        } else {
+            let Some(root_version) = broadcast.wait_until(deadline, POLL_INTERVAL) else {
+                return Err(MPISyncError::RankTimeout(0));
+            };
+            let ack = [u8::from(root_version == Self::version_hash())];
+            config.root_process().send_with_tag(&ack[..], ACK_TAG);
+
+            // 3. receive the outcome, the root may have spent the whole timeout collecting
+            let Some(outcome_bytes) = config
+                .immediate_root_broadcast_bytes(vec![0u8; OUTCOME_SIZE])
+                .wait_until(deadline + timeout, POLL_INTERVAL)
+            else {
+                return Err(MPISyncError::RankTimeout(0));
+            };
+            let rank = u64::from_le_bytes(outcome_bytes[1..].try_into().unwrap()) as usize;
+            match outcome_bytes[0] {
+                STATUS_OK => Ok(()),
+                STATUS_TIMEOUT => Err(MPISyncError::RankTimeout(rank)),
+                _ => Err(MPISyncError::VersionMismatch(rank)),
+            }
+        };
+
+        // 4. everyone is alive, a blocking barrier is safe
+        if outcome.is_ok() {
+            config.barrier();
+        }
+        outcome
+    }
+
+    /// Wait for the report of every non-root process, and return the first failure.
+    fn collect_reports(config: &MPIConfig, deadline: Instant) -> Result<(), MPISyncError> {
+        let mut pending = (1..config.world_size())
+            .map(|rank| (rank, Some(config.immediate_receive_bytes(rank, 1, ACK_TAG))))
+            .collect::<Vec<(usize, Option<PendingBytes>)>>();
+
+        let mut mismatch = None;
+        loop {
+            for (rank, receive) in pending.iter_mut() {
+                if let Some(ack) = receive.as_mut().and_then(PendingBytes::test) {
+                    *receive = None;
+                    if ack[0] == 0 && mismatch.is_none() {
+                        mismatch = Some(*rank);
+                    }
+                }
+            }
+
+            // the receives still pending are dropped, and their buffers leaked, on a timeout
+            let missing = pending
+                .iter()
+                .find(|(_, receive)| receive.is_some())
+                .map(|(rank, _)| *rank);
+            match missing {
+                None => break,
+                Some(rank) if Instant::now() >= deadline => {
+                    return Err(MPISyncError::RankTimeout(rank))
+                }
+                Some(_) => std::thread::sleep(POLL_INTERVAL),
+            }
+        }
+
+        match mismatch {
+            Some(rank) => Err(MPISyncError::VersionMismatch(rank)),
+            None => Ok(()),
+        }
+    }
+}
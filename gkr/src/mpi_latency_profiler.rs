@@ -0,0 +1,112 @@
+//! Measuring the round-trip latency between the MPI processes before a distributed prove,
+//! whose collectives are as slow as the slowest link.
+//!
+//! The pairs of ranks play ping-pong one after the other, in lexicographic order, so that each
+//! blocking exchange only waits on the two ranks of the pair. The lower rank of a pair times the
+//! round trips, and the measures are then summed across the processes so that all of them get
+//! the whole matrix.
+
+use std::time::Instant;
+
+use config::MPIConfig;
+use mpi::{collective::SystemOperation, topology::Process, traits::*};
+
+const PING_TAG: i32 = 0x4c50; // "LP"
+
+/// Timed round trips per pair, after one untimed round trip setting up the connection.
+const PING_ROUNDS: usize = 16;
+
+/// The round-trip latencies between the ranks, in microseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyMatrix {
+    world_size: usize,
+    latencies_us: Vec<u64>,
+}
+
+impl LatencyMatrix {
+    #[inline]
+    pub fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline]
+    pub fn latency_us(&self, a: usize, b: usize) -> u64 {
+        self.latencies_us[a * self.world_size + b]
+    }
+
+    /// The latency of the slowest pair of ranks, 0 for a single process.
+    pub fn max_latency_us(&self) -> u64 {
+        self.latencies_us.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The slowest pair of ranks, with the lower rank first.
+    pub fn worst_pair(&self) -> Option<(usize, usize)> {
+        (0..self.world_size)
+            .flat_map(|a| (a + 1..self.world_size).map(move |b| (a, b)))
+            .max_by_key(|&(a, b)| self.latency_us(a, b))
+    }
+}
+
+pub struct MPILatencyProfiler;
+
+impl MPILatencyProfiler {
+    /// Must be called by all the ranks, which all get the same matrix.
+    pub fn ping_all_ranks(config: &MPIConfig) -> LatencyMatrix {
+        let n = config.world_size();
+        let mut local = vec![0u64; n * n];
+        if n > 1 {
+            let world = config.world.unwrap();
+            let rank = config.world_rank();
+            for a in 0..n {
+                for b in a + 1..n {
+                    if rank == a {
+                        local[a * n + b] = Self::ping(world.process_at_rank(b as i32));
+                    } else if rank == b {
+                        Self::pong(world.process_at_rank(a as i32));
+                    }
+                }
+            }
+        }
+
+        // each latency was measured by a single rank, the others contribute zeros
+        let mut latencies_us = vec![0u64; n * n];
+        match config.world {
+            Some(world) => {
+                world.all_reduce_into(&local[..], &mut latencies_us[..], SystemOperation::sum())
+            }
+            None => latencies_us.copy_from_slice(&local),
+        }
+        for a in 0..n {
+            for b in 0..a {
+                latencies_us[a * n + b] = latencies_us[b * n + a];
+            }
+        }
+        LatencyMatrix {
+            world_size: n,
+            latencies_us,
+        }
+    }
+
+    /// The mean round-trip time to the peer, in microseconds.
+    fn ping(peer: Process) -> u64 {
+        let message = [0u8];
+        let mut reply = [0u8];
+        let mut round_trip = || {
+            peer.send_with_tag(&message[..], PING_TAG);
+            peer.receive_into_with_tag(&mut reply[..], PING_TAG);
+        };
+
+        round_trip();
+        let start = Instant::now();
+        (0..PING_ROUNDS).for_each(|_| round_trip());
+        (start.elapsed().as_micros() / PING_ROUNDS as u128) as u64
+    }
+
+    fn pong(peer: Process) {
+        let mut message = [0u8];
+        for _ in 0..PING_ROUNDS + 1 {
+            peer.receive_into_with_tag(&mut message[..], PING_TAG);
+            peer.send_with_tag(&message[..], PING_TAG);
+        }
+    }
+}
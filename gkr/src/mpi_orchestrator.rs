@@ -0,0 +1,111 @@
+//! Planning which rank works on which layer, for clusters whose ranks are not alike.
+
+use std::mem::size_of;
+
+use circuit::{Circuit, CircuitLayer, GateAdd, GateMul};
+use config::GKRConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankCapability {
+    pub rank: usize,
+    pub cores: usize,
+    pub memory_gb: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerAssignment {
+    pub layer: usize,
+    pub rank: usize,
+    /// Estimated cost of the layer, proportional to its number of wires and gates.
+    pub cost: usize,
+}
+
+pub struct MPIProveOrchestrator;
+
+impl MPIProveOrchestrator {
+    /// Assign each layer of the circuit to a rank, the assignments sorted by layer.
+    ///
+    /// The layers are taken from the widest to the narrowest, and each goes to the rank that
+    /// would finish it the earliest given the cores and the work it already has, among the ranks
+    /// with enough memory for it. So the wider layers go to the ranks with more cores. If no rank
+    /// has enough memory for a layer, it goes to the rank with the most memory.
+    pub fn assign_work<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        rank_capabilities: &[RankCapability],
+    ) -> Vec<LayerAssignment> {
+        assert!(!rank_capabilities.is_empty(), "no rank to assign work to");
+        assert!(
+            rank_capabilities.iter().all(|r| r.cores > 0),
+            "a rank has no core"
+        );
+
+        let mut layers = circuit
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| (i, Self::layer_cost(layer), Self::layer_memory(layer)))
+            .collect::<Vec<_>>();
+        // stable, so that equally wide layers are taken in order
+        layers.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut loads = vec![0; rank_capabilities.len()];
+        let mut assignments = layers
+            .into_iter()
+            .map(|(layer, cost, memory)| {
+                let fits = |r: &RankCapability| r.memory_gb << 30 >= memory;
+                let chosen = if rank_capabilities.iter().any(fits) {
+                    (0..rank_capabilities.len())
+                        .filter(|&i| fits(&rank_capabilities[i]))
+                        .min_by(|&i, &j| {
+                            // compare (load_i + cost) / cores_i with (load_j + cost) / cores_j
+                            let finish_i = (loads[i] + cost) * rank_capabilities[j].cores;
+                            let finish_j = (loads[j] + cost) * rank_capabilities[i].cores;
+                            finish_i.cmp(&finish_j)
+                        })
+                        .unwrap()
+                } else {
+                    (0..rank_capabilities.len())
+                        .max_by_key(|&i| rank_capabilities[i].memory_gb)
+                        .unwrap()
+                };
+                loads[chosen] += cost;
+                LayerAssignment {
+                    layer,
+                    rank: rank_capabilities[chosen].rank,
+                    cost,
+                }
+            })
+            .collect::<Vec<_>>();
+        assignments.sort_by_key(|a| a.layer);
+        assignments
+    }
+
+    /// The layers assigned to the rank, in order. This is the rank's part of the circuit.
+    pub fn layers_of(assignments: &[LayerAssignment], rank: usize) -> Vec<usize> {
+        assignments
+            .iter()
+            .filter(|a| a.rank == rank)
+            .map(|a| a.layer)
+            .collect()
+    }
+
+    fn layer_cost<C: GKRConfig>(layer: &CircuitLayer<C>) -> usize {
+        (1 << layer.input_var_num)
+            + (1 << layer.output_var_num)
+            + layer.mul.len()
+            + layer.add.len()
+            + layer.const_.len()
+            + layer.uni.len()
+    }
+
+    /// Bytes held while proving the layer: its values, the sumcheck buffers over its inputs,
+    /// and its gates.
+    fn layer_memory<C: GKRConfig>(layer: &CircuitLayer<C>) -> usize {
+        let values = ((1 << layer.input_var_num) * 3 + (1 << layer.output_var_num))
+            * size_of::<C::SimdCircuitField>();
+        let gates = layer.mul.len() * size_of::<GateMul<C>>()
+            + (layer.add.len() + layer.uni.len()) * size_of::<GateAdd<C>>();
+        values + gates
+    }
+}
@@ -0,0 +1,79 @@
+//! Hashing wide vectors of field elements with all the cores.
+//!
+//! The serialized elements are split into chunks of `CHUNK_LEN` elements, each chunk is hashed
+//! by a rayon task, and the chunk digests are combined by a binary tree of hashes. The leaves
+//! and the inner nodes are domain separated, and a node without a sibling is carried up as is.
+//! The chunks do not depend on the number of threads, so neither does the digest, but it
+//! differs from the hash of the concatenated elements: both sides have to use this hasher.
+
+use std::marker::PhantomData;
+
+use arith::FieldSerde;
+use config::{Config, FiatShamirHashType, GKRConfig};
+use rayon::prelude::*;
+use transcript::{FiatShamirBytesHash, Keccak256hasher, SHA256hasher};
+
+/// Number of elements hashed by each task.
+pub const CHUNK_LEN: usize = 256;
+
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+
+pub struct MultithreadedHasher<F: FieldSerde> {
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldSerde + Sync> MultithreadedHasher<F> {
+    /// Hash with the Fiat-Shamir hash of the config.
+    ///
+    /// Panics for the hashes over fields, e.g., MiMC, which do not hash bytes.
+    pub fn hash_parallel<C: GKRConfig>(elements: &[F], _config: &Config<C>) -> [u8; 32] {
+        match C::FIAT_SHAMIR_HASH {
+            FiatShamirHashType::Keccak256 => Self::tree_hash::<Keccak256hasher>(elements),
+            FiatShamirHashType::SHA256 => Self::tree_hash::<SHA256hasher>(elements),
+            hash => panic!("{:?} is not a bytes hash", hash),
+        }
+    }
+
+    pub fn tree_hash<H: FiatShamirBytesHash>(elements: &[F]) -> [u8; 32] {
+        assert_eq!(H::DIGEST_SIZE, 32);
+        let mut level = elements
+            .par_chunks(CHUNK_LEN)
+            .map(|chunk| {
+                let mut bytes = Vec::with_capacity(1 + chunk.len() * F::SERIALIZED_SIZE);
+                bytes.push(LEAF_TAG);
+                chunk
+                    .iter()
+                    .for_each(|f| f.serialize_into(&mut bytes).unwrap());
+                hash::<H>(&bytes)
+            })
+            .collect::<Vec<_>>();
+        if level.is_empty() {
+            return hash::<H>(&[LEAF_TAG]);
+        }
+
+        while level.len() > 1 {
+            level = level
+                .par_chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut bytes = [0u8; 65];
+                        bytes[0] = NODE_TAG;
+                        bytes[1..33].copy_from_slice(left);
+                        bytes[33..].copy_from_slice(right);
+                        hash::<H>(&bytes)
+                    }
+                    _ => pair[0],
+                })
+                .collect();
+        }
+        level[0]
+    }
+}
+
+#[inline]
+fn hash<H: FiatShamirBytesHash>(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    H::hash(&mut output, input);
+    output
+}
@@ -3,4 +3,7 @@
 pub mod raw;
 pub use self::raw::*;
 
+pub mod orion;
+pub use self::orion::*;
+
 pub trait PolynomialCommitment {}
@@ -0,0 +1,416 @@
+//! The Orion commitment, without the code switching of its paper: the input values of all the
+//! processes are the rows of a matrix, each row encoded by a public linear code of rate 1/4, and
+//! the commitment is the Merkle root of the columns of the encoded rows. The opening at a point
+//! is the combination of the rows by the eq of the high variables of the point, checked against
+//! the same combination of the columns at the queried positions, along with a random combination
+//! of the rows, for the rows to be close to codewords.
+//!
+//! The commitment is a hash and the opening of the order of the square root of the number of
+//! values, each query a column and its Merkle path; the queries, of the security bits of the
+//! config, make it larger than the raw commitment for small inputs, see the `proof-sizes`
+//! benchmark.
+//!
+//! The code is systematic, a row followed by three times as many combinations of its values, of
+//! coefficients of the circuit field derived by Sha256 from their position, so that the simd
+//! lanes of the values are encoded alike. The random code is assumed of a relative distance of at
+//! least 1/2 over a circuit field of at least 31 bits, and of 1/5 over a smaller one.
+
+use std::io::Read;
+
+use arith::{Field, FieldSerde, SimdField};
+use config::{GKRConfig, MPIConfig};
+use polynomials::{EqPolynomial, MultiLinearPoly};
+use sha2::{Digest, Sha256};
+use transcript::Transcript;
+
+use super::RawCommitment;
+
+/// The log2 of the length of a codeword over that of its row.
+const LOG_INV_RATE: usize = 2;
+
+const HASH_SIZE: usize = 32;
+
+const CODE_DOMAIN: &[u8] = b"expander orion code";
+
+const LEAF_TAG: u8 = 0;
+
+const NODE_TAG: u8 = 1;
+
+/// The shape of the committed matrix and the number of queried columns of an opening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrionParams {
+    /// The variables of the polynomial, those of the inputs of a process then of the processes.
+    pub num_vars: usize,
+    /// The low variables of the polynomial, of the position in a row.
+    pub row_len_log: usize,
+    pub num_queries: usize,
+}
+
+impl OrionParams {
+    /// The rows are as long as there are rows, or half as long.
+    pub fn new<C: GKRConfig>(num_vars: usize, security_bits: usize) -> Self {
+        let relative_distance = if C::CircuitField::FIELD_SIZE >= 31 {
+            0.5
+        } else {
+            0.2
+        };
+        // a row far from the code passes a query with probability at most 1 - distance / 3
+        let bits_per_query = -(1.0 - relative_distance / 3.0f64).log2();
+        Self {
+            num_vars,
+            row_len_log: num_vars / 2,
+            num_queries: (security_bits as f64 / bits_per_query).ceil() as usize,
+        }
+    }
+
+    #[inline]
+    pub fn row_len(&self) -> usize {
+        1 << self.row_len_log
+    }
+
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        1 << (self.num_vars - self.row_len_log)
+    }
+
+    #[inline]
+    pub fn codeword_len(&self) -> usize {
+        self.row_len() << LOG_INV_RATE
+    }
+
+    /// Size in bytes of the commitment, the Merkle root.
+    #[inline]
+    pub fn commitment_size(&self) -> usize {
+        HASH_SIZE
+    }
+
+    /// Size in bytes of an opening: the two combinations of the rows, then the queried columns,
+    /// each followed by its Merkle path.
+    pub fn opening_size<C: GKRConfig>(&self) -> usize {
+        2 * self.row_len() * C::Field::SERIALIZED_SIZE + self.num_queries * self.query_size::<C>()
+    }
+
+    #[inline]
+    fn query_size<C: GKRConfig>(&self) -> usize {
+        self.num_rows() * C::SimdCircuitField::SERIALIZED_SIZE
+            + (self.row_len_log + LOG_INV_RATE) * HASH_SIZE
+    }
+
+    /// Whether the opening read from the reader, whose bytes are appended to the transcript as
+    /// by `OrionCommitment::open`, shows that the committed polynomial is `y` at `x`, followed
+    /// by `x_simd`. The bytes that are not of an opening, or of invalid field elements, are not.
+    pub fn verify<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+        &self,
+        root: &[u8; HASH_SIZE],
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        y: C::ChallengeField,
+        transcript: &mut T,
+        mut reader: impl Read,
+    ) -> bool {
+        if x.len() != self.num_vars {
+            return false;
+        }
+        let gamma = transcript.generate_challenge_field_elements(self.num_rows());
+        let eq_rows = eq_evals(&x[self.row_len_log..]);
+
+        let row_len = self.row_len();
+        let rows = match read_message(
+            &mut reader,
+            2 * row_len * C::Field::SERIALIZED_SIZE,
+            transcript,
+        )
+        .and_then(|bytes| deserialize_all::<C::Field>(&bytes))
+        {
+            Some(rows) => rows,
+            None => return false,
+        };
+        let (proximity_row, eval_row) = rows.split_at(row_len);
+
+        // the lanes of the combined row at the low variables, then at the simd variables
+        let y_simd = eq_evals(&x[..self.row_len_log])
+            .iter()
+            .zip(eval_row)
+            .fold(C::Field::zero(), |acc, (eq, v)| {
+                acc + C::challenge_mul_field(eq, v)
+            });
+        let lanes = y_simd.unpack();
+        let mut scratch = vec![C::ChallengeField::default(); lanes.len()];
+        if MultiLinearPoly::evaluate_with_buffer(&lanes, x_simd, &mut scratch) != y {
+            return false;
+        }
+
+        let queries = draw_queries(transcript, self);
+        let query_size = self.query_size::<C>();
+        let column_size = self.num_rows() * C::SimdCircuitField::SERIALIZED_SIZE;
+        let bytes = match read_message(&mut reader, queries.len() * query_size, transcript) {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        queries
+            .iter()
+            .zip(bytes.chunks(query_size))
+            .all(|(&j, query)| {
+                let (column, path) = query.split_at(column_size);
+                let column = match deserialize_all::<C::SimdCircuitField>(column) {
+                    Some(column) => column,
+                    None => return false,
+                };
+                let path = path
+                    .chunks(HASH_SIZE)
+                    .map(|node| node.try_into().unwrap())
+                    .collect::<Vec<[u8; HASH_SIZE]>>();
+                verify_path(root, j, hash_leaf::<C>(column.iter()), &path)
+                    && encoded_at::<C>(self.row_len_log, proximity_row, j)
+                        == combine_column::<C>(&column, &gamma)
+                    && encoded_at::<C>(self.row_len_log, eval_row, j)
+                        == combine_column::<C>(&column, &eq_rows)
+            })
+    }
+}
+
+pub struct OrionCommitment<C: GKRConfig> {
+    pub params: OrionParams,
+    /// The codewords of the rows, one after the other.
+    codewords: Vec<C::SimdCircuitField>,
+    tree: MerkleTree,
+}
+
+impl<C: GKRConfig> OrionCommitment<C> {
+    /// Panics if the number of values is not a power of 2.
+    pub fn new(poly_vals: &[C::SimdCircuitField], security_bits: usize) -> Self {
+        assert!(poly_vals.len().is_power_of_two());
+        let params =
+            OrionParams::new::<C>(poly_vals.len().trailing_zeros() as usize, security_bits);
+        let row_len = params.row_len();
+        let codeword_len = params.codeword_len();
+
+        let parity_coefs = (0..codeword_len - row_len)
+            .flat_map(|j| (0..row_len).map(move |k| code_coef::<C>(params.row_len_log, j, k)))
+            .collect::<Vec<_>>();
+        let mut codewords = Vec::with_capacity(params.num_rows() * codeword_len);
+        for row in poly_vals.chunks(row_len) {
+            codewords.extend_from_slice(row);
+            codewords.extend(parity_coefs.chunks(row_len).map(|coefs| {
+                coefs
+                    .iter()
+                    .zip(row)
+                    .fold(C::SimdCircuitField::zero(), |acc, (coef, v)| {
+                        acc + C::circuit_field_mul_simd_circuit_field(coef, v)
+                    })
+            }));
+        }
+
+        let leaves = (0..codeword_len)
+            .map(|j| {
+                hash_leaf::<C>((0..params.num_rows()).map(|r| &codewords[r * codeword_len + j]))
+            })
+            .collect();
+        Self {
+            params,
+            codewords,
+            tree: MerkleTree::new(leaves),
+        }
+    }
+
+    /// Create a commitment collectively, as `RawCommitment::mpi_new`, only the root having it.
+    pub fn mpi_new(
+        local_poly_vals: &Vec<C::SimdCircuitField>,
+        mpi_config: &MPIConfig,
+        security_bits: usize,
+    ) -> Option<Self> {
+        let poly_vals = RawCommitment::<C>::mpi_new(local_poly_vals, mpi_config).poly_vals;
+        mpi_config
+            .is_root()
+            .then(|| Self::new(&poly_vals, security_bits))
+    }
+
+    #[inline]
+    pub fn root(&self) -> [u8; HASH_SIZE] {
+        self.tree.root()
+    }
+
+    /// Append the opening at `x`, that of the values, then of the processes, to the transcript,
+    /// see `OrionParams::verify`.
+    pub fn open<T: Transcript<C::ChallengeField>>(
+        &self,
+        x: &[C::ChallengeField],
+        transcript: &mut T,
+    ) {
+        let params = &self.params;
+        assert_eq!(x.len(), params.num_vars);
+        let gamma = transcript.generate_challenge_field_elements(params.num_rows());
+        let eq_rows = eq_evals(&x[params.row_len_log..]);
+
+        let mut message = vec![];
+        for coefs in [&gamma, &eq_rows] {
+            for k in 0..params.row_len() {
+                combine_column::<C>(&self.column(k), coefs)
+                    .serialize_into(&mut message)
+                    .unwrap();
+            }
+        }
+        transcript.append_u8_slice(&message);
+
+        let mut message = vec![];
+        for j in draw_queries(transcript, params) {
+            for v in self.column(j) {
+                v.serialize_into(&mut message).unwrap();
+            }
+            for node in self.tree.path(j) {
+                message.extend_from_slice(&node);
+            }
+        }
+        transcript.append_u8_slice(&message);
+    }
+
+    fn column(&self, j: usize) -> Vec<C::SimdCircuitField> {
+        let codeword_len = self.params.codeword_len();
+        (0..self.params.num_rows())
+            .map(|r| self.codewords[r * codeword_len + j])
+            .collect()
+    }
+}
+
+/// The coefficient of value `k` of a row of `1 << row_len_log` values in its parity value `j`.
+fn code_coef<C: GKRConfig>(row_len_log: usize, j: usize, k: usize) -> C::CircuitField {
+    let mut hasher = Sha256::new();
+    hasher.update(CODE_DOMAIN);
+    hasher.update((row_len_log as u64).to_le_bytes());
+    hasher.update((j as u64).to_le_bytes());
+    hasher.update((k as u64).to_le_bytes());
+    let bytes: [u8; HASH_SIZE] = hasher.finalize().into();
+    C::CircuitField::from_uniform_bytes(&bytes)
+}
+
+/// Value `j` of the codeword of the row.
+fn encoded_at<C: GKRConfig>(row_len_log: usize, row: &[C::Field], j: usize) -> C::Field {
+    if j < row.len() {
+        return row[j];
+    }
+    row.iter()
+        .enumerate()
+        .fold(C::Field::zero(), |acc, (k, v)| {
+            acc + C::field_mul_circuit_field(v, &code_coef::<C>(row_len_log, j - row.len(), k))
+        })
+}
+
+fn combine_column<C: GKRConfig>(
+    column: &[C::SimdCircuitField],
+    coefs: &[C::ChallengeField],
+) -> C::Field {
+    column
+        .iter()
+        .zip(coefs)
+        .fold(C::Field::zero(), |acc, (v, coef)| {
+            acc + C::simd_circuit_field_mul_challenge_field(v, coef)
+        })
+}
+
+fn eq_evals<F: Field>(r: &[F]) -> Vec<F> {
+    let mut evals = vec![F::zero(); 1 << r.len()];
+    EqPolynomial::build_eq_x_r_with_buf(r, &F::one(), &mut evals);
+    evals
+}
+
+/// The positions of the queried columns, drawn from the transcript.
+fn draw_queries<F: Field + FieldSerde, T: Transcript<F>>(
+    transcript: &mut T,
+    params: &OrionParams,
+) -> Vec<usize> {
+    transcript
+        .generate_challenge_u8_slice(8 * params.num_queries)
+        .chunks(8)
+        .map(|bytes| {
+            u64::from_le_bytes(bytes.try_into().unwrap()) as usize & (params.codeword_len() - 1)
+        })
+        .collect()
+}
+
+/// The next `size` bytes of the reader, appended to the transcript, if as many.
+fn read_message<F: Field + FieldSerde, T: Transcript<F>>(
+    reader: &mut impl Read,
+    size: usize,
+    transcript: &mut T,
+) -> Option<Vec<u8>> {
+    let mut bytes = vec![0u8; size];
+    reader.read_exact(&mut bytes).ok()?;
+    transcript.append_u8_slice(&bytes);
+    Some(bytes)
+}
+
+fn deserialize_all<F: FieldSerde>(bytes: &[u8]) -> Option<Vec<F>> {
+    bytes
+        .chunks(F::SERIALIZED_SIZE)
+        .map(|chunk| F::deserialize_from(chunk).ok())
+        .collect()
+}
+
+fn hash_leaf<'a, C: GKRConfig>(
+    column: impl Iterator<Item = &'a C::SimdCircuitField>,
+) -> [u8; HASH_SIZE] {
+    let mut bytes = vec![LEAF_TAG];
+    for v in column {
+        v.serialize_into(&mut bytes).unwrap();
+    }
+    Sha256::digest(&bytes).into()
+}
+
+fn hash_node(left: &[u8; HASH_SIZE], right: &[u8; HASH_SIZE]) -> [u8; HASH_SIZE] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn verify_path(
+    root: &[u8; HASH_SIZE],
+    mut index: usize,
+    leaf: [u8; HASH_SIZE],
+    path: &[[u8; HASH_SIZE]],
+) -> bool {
+    let node = path.iter().fold(leaf, |node, sibling| {
+        let parent = if index & 1 == 0 {
+            hash_node(&node, sibling)
+        } else {
+            hash_node(sibling, &node)
+        };
+        index >>= 1;
+        parent
+    });
+    node == *root
+}
+
+/// A Merkle tree over a power of 2 leaves: node 1 is the root, and the children of node `i` are
+/// `2 * i` and `2 * i + 1`, the leaves the last half of the nodes.
+struct MerkleTree {
+    nodes: Vec<[u8; HASH_SIZE]>,
+}
+
+impl MerkleTree {
+    fn new(leaves: Vec<[u8; HASH_SIZE]>) -> Self {
+        let num_leaves = leaves.len();
+        let mut nodes = vec![[0u8; HASH_SIZE]; num_leaves];
+        nodes.extend(leaves);
+        for i in (1..num_leaves).rev() {
+            nodes[i] = hash_node(&nodes[2 * i], &nodes[2 * i + 1]);
+        }
+        Self { nodes }
+    }
+
+    fn root(&self) -> [u8; HASH_SIZE] {
+        self.nodes[1]
+    }
+
+    /// The siblings of the nodes from the leaf up to the root, excluded.
+    fn path(&self, leaf: usize) -> Vec<[u8; HASH_SIZE]> {
+        let mut path = vec![];
+        let mut i = self.nodes.len() / 2 + leaf;
+        while i > 1 {
+            path.push(self.nodes[i ^ 1]);
+            i >>= 1;
+        }
+        path
+    }
+}
@@ -6,6 +6,7 @@ use std::io::{Read, Write};
 use arith::{Field, FieldSerde, FieldSerdeResult, SimdField};
 use config::{GKRConfig, MPIConfig};
 use polynomials::MultiLinearPoly;
+use rayon::{prelude::*, ThreadPool};
 
 #[derive(Default)]
 pub struct RawOpening {}
@@ -17,7 +18,7 @@ pub struct RawCommitment<C: GKRConfig> {
 impl<C: GKRConfig> RawCommitment<C> {
     #[inline]
     pub fn size(&self) -> usize {
-        self.poly_vals.len() * C::SimdCircuitField::SIZE
+        self.poly_vals.len() * C::SimdCircuitField::SERIALIZED_SIZE
     }
 
     #[inline]
@@ -27,13 +28,14 @@ impl<C: GKRConfig> RawCommitment<C> {
             .try_for_each(|v| v.serialize_into(&mut writer))
     }
 
+    /// The commitment of `poly_size` values, or the error of the first one that cannot be read.
     #[inline]
-    pub fn deserialize_from<R: Read>(mut reader: R, poly_size: usize) -> Self {
+    pub fn deserialize_from<R: Read>(mut reader: R, poly_size: usize) -> FieldSerdeResult<Self> {
         let poly_vals = (0..poly_size)
-            .map(|_| C::SimdCircuitField::deserialize_from(&mut reader).unwrap()) // TODO: error propagation
-            .collect();
+            .map(|_| C::SimdCircuitField::deserialize_from(&mut reader))
+            .collect::<FieldSerdeResult<_>>()?;
 
-        RawCommitment { poly_vals }
+        Ok(RawCommitment { poly_vals })
     }
 }
 
@@ -63,6 +65,22 @@ impl<C: GKRConfig> RawCommitment<C> {
         }
     }
 
+    /// As `mpi_new(..).serialize_into(writer)`, without copying the values of a single process.
+    #[inline]
+    pub fn mpi_serialize_into<W: Write>(
+        local_poly_vals: &Vec<C::SimdCircuitField>,
+        mpi_config: &MPIConfig,
+        mut writer: W,
+    ) -> FieldSerdeResult<()> {
+        if mpi_config.world_size() == 1 {
+            local_poly_vals
+                .iter()
+                .try_for_each(|v| v.serialize_into(&mut writer))
+        } else {
+            Self::mpi_new(local_poly_vals, mpi_config).serialize_into(writer)
+        }
+    }
+
     #[inline(always)]
     fn eval_local(
         v: &[C::SimdCircuitField],
@@ -90,6 +108,7 @@ impl<C: GKRConfig> RawCommitment<C> {
     /// Note: verifier should not have access to the MPIConfig
     /// The mpi size is implicitly specified by the length of x_mpi,
     /// So make sure it is correct
+    /// The values of the processes are evaluated over the threads of the pool, if any
     #[inline]
     pub fn mpi_verify(
         &self,
@@ -97,13 +116,25 @@ impl<C: GKRConfig> RawCommitment<C> {
         x_simd: &[C::ChallengeField],
         x_mpi: &[C::ChallengeField],
         y: C::ChallengeField,
+        pool: Option<&ThreadPool>,
     ) -> bool {
         let local_poly_size = self.poly_vals.len() >> x_mpi.len();
-        let local_evals = self
-            .poly_vals
-            .chunks(local_poly_size)
-            .map(|local_vals| Self::eval_local(local_vals, x, x_simd))
-            .collect::<Vec<C::ChallengeField>>();
+        let eval_local =
+            |local_vals: &[C::SimdCircuitField]| Self::eval_local(local_vals, x, x_simd);
+        // collected in the order of the ranks, whatever the threads
+        let local_evals = match pool {
+            Some(pool) => pool.install(|| {
+                self.poly_vals
+                    .par_chunks(local_poly_size)
+                    .map(eval_local)
+                    .collect::<Vec<C::ChallengeField>>()
+            }),
+            None => self
+                .poly_vals
+                .chunks(local_poly_size)
+                .map(eval_local)
+                .collect::<Vec<C::ChallengeField>>(),
+        };
 
         let mut scratch = vec![C::ChallengeField::default(); local_evals.len()];
         y == MultiLinearPoly::evaluate_with_buffer(&local_evals, x_mpi, &mut scratch)
@@ -0,0 +1,111 @@
+//! Which outputs of a circuit depend on the witness.
+//!
+//! The inputs of a circuit are the private inputs of the witness, the public inputs only reach
+//! the circuit as coefficients of const gates. An output gate leaks information about the
+//! witness when its backward slice, the input wires its value is computed from, is not empty.
+
+use std::collections::BTreeSet;
+
+use circuit::{Circuit, GateKind, GateRef, WireRef};
+use config::GKRConfig;
+
+/// The input wires each output gate depends on, sorted, in the order of the output gates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrivacyAnalysisReport {
+    pub outputs: Vec<(GateRef, Vec<WireRef>)>,
+}
+
+impl PrivacyAnalysisReport {
+    /// The output gates that depend on a private input, with the inputs they depend on.
+    pub fn leaking_outputs(&self) -> Vec<(GateRef, Vec<WireRef>)> {
+        self.outputs
+            .iter()
+            .filter(|(_, inputs)| !inputs.is_empty())
+            .cloned()
+            .collect()
+    }
+}
+
+pub struct WitnessPrivacyAnalyzer;
+
+impl WitnessPrivacyAnalyzer {
+    pub fn analyze<C: GKRConfig>(circuit: &Circuit<C>) -> PrivacyAnalysisReport {
+        let layer_num = circuit.layers.len();
+        if layer_num == 0 {
+            return PrivacyAnalysisReport::default();
+        }
+
+        // writers[l][o]: the inputs, wires of the layer l, of the gates of the layer l writing o
+        let writers = circuit
+            .layers
+            .iter()
+            .map(|layer| {
+                let mut writers = vec![vec![]; 1 << layer.output_var_num];
+                let gates = layer
+                    .mul
+                    .iter()
+                    .map(|g| (g.o_id, &g.i_ids[..]))
+                    .chain(layer.add.iter().map(|g| (g.o_id, &g.i_ids[..])))
+                    .chain(layer.uni.iter().map(|g| (g.o_id, &g.i_ids[..])));
+                for (o_id, i_ids) in gates {
+                    writers[o_id].extend_from_slice(i_ids);
+                }
+                writers
+            })
+            .collect::<Vec<Vec<Vec<usize>>>>();
+
+        let last = layer_num - 1;
+        let output_layer = &circuit.layers[last];
+        let gate_ref = |kind, index| GateRef {
+            layer: last,
+            kind,
+            index,
+        };
+        let output_gates = output_layer
+            .mul
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (gate_ref(GateKind::Mul, i), &g.i_ids[..]))
+            .chain(
+                output_layer
+                    .add
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Add, i), &g.i_ids[..])),
+            )
+            .chain(
+                output_layer
+                    .const_
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Const, i), &g.i_ids[..])),
+            )
+            .chain(
+                output_layer
+                    .uni
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (gate_ref(GateKind::Uni, i), &g.i_ids[..])),
+            );
+
+        let outputs = output_gates
+            .map(|(gate, i_ids)| {
+                // the wires of the layer l the gate depends on, from the layer below it down
+                let mut slice = i_ids.iter().copied().collect::<BTreeSet<usize>>();
+                for l in (1..=last).rev() {
+                    slice = slice
+                        .iter()
+                        .flat_map(|&wire| writers[l - 1][wire].iter().copied())
+                        .collect();
+                }
+                let inputs = slice
+                    .into_iter()
+                    .map(|index| WireRef { layer: 0, index })
+                    .collect();
+                (gate, inputs)
+            })
+            .collect();
+
+        PrivacyAnalysisReport { outputs }
+    }
+}
@@ -0,0 +1,115 @@
+//! Analytic estimation of how much each gate contributes to the proof size.
+//!
+//! The sumcheck messages of a layer are determined by the number of variables, not by the
+//! number of gates, so a layer's share of the proof is split among the gates that require it:
+//! - phase one (over `x`, the simd and the mpi variables, plus the `vx` claim) is shared by
+//!   all the gates of the layer;
+//! - phase two (over `y`, plus the `vy` claim) only exists because of the mul gates, and is
+//!   shared by them.
+//!
+//! The input commitment is not attributed to any gate.
+
+use arith::FieldSerde;
+use circuit::{Circuit, CircuitLayer, GateKind, GateRef};
+use config::{Config, GKRConfig, GKRScheme};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateProofSizeContribution {
+    pub gate_ref: GateRef,
+    pub layer: usize,
+    pub estimated_proof_contribution_bytes: usize,
+}
+
+pub struct GKRCircuitProfiler;
+
+impl GKRCircuitProfiler {
+    /// Estimated proof size contribution of every gate, largest first.
+    pub fn profile<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+    ) -> Vec<GateProofSizeContribution> {
+        let mut contributions = vec![];
+        for (i, layer) in circuit.layers.iter().enumerate() {
+            let (phase_one_bytes, phase_two_bytes) = Self::layer_proof_bytes(layer, config);
+
+            let n_gates = layer.mul.len() + layer.add.len() + layer.const_.len() + layer.uni.len();
+            let gates = [
+                (GateKind::Mul, layer.mul.len()),
+                (GateKind::Add, layer.add.len()),
+                (GateKind::Const, layer.const_.len()),
+                (GateKind::Uni, layer.uni.len()),
+            ]
+            .into_iter()
+            .flat_map(|(kind, len)| (0..len).map(move |index| (kind, index)));
+
+            for (rank, (kind, index)) in gates.enumerate() {
+                let mut bytes = split_evenly(phase_one_bytes, n_gates, rank);
+                if kind == GateKind::Mul {
+                    bytes += split_evenly(phase_two_bytes, layer.mul.len(), index);
+                }
+                contributions.push(GateProofSizeContribution {
+                    gate_ref: GateRef {
+                        layer: i,
+                        kind,
+                        index,
+                    },
+                    layer: i,
+                    estimated_proof_contribution_bytes: bytes,
+                });
+            }
+        }
+
+        contributions.sort_by(|a, b| {
+            b.estimated_proof_contribution_bytes
+                .cmp(&a.estimated_proof_contribution_bytes)
+                .then(a.gate_ref.cmp(&b.gate_ref))
+        });
+        contributions
+    }
+
+    /// The `n` gates contributing the most to the proof size.
+    pub fn top_contributors<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+        n: usize,
+    ) -> Vec<GateProofSizeContribution> {
+        let mut contributions = Self::profile(circuit, config);
+        contributions.truncate(n);
+        contributions
+    }
+
+    /// Proof bytes of the sumcheck of a layer, split into phase one and phase two.
+    fn layer_proof_bytes<C: GKRConfig>(
+        layer: &CircuitLayer<C>,
+        config: &Config<C>,
+    ) -> (usize, usize) {
+        match config.gkr_scheme {
+            GKRScheme::Vanilla => {
+                let elem_size = C::ChallengeField::SERIALIZED_SIZE;
+                let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+                let mpi_var_num = config.mpi_config.world_size().trailing_zeros() as usize;
+
+                // degree 2 polynomials over x, degree 3 over the simd and mpi variables, and vx
+                let phase_one =
+                    (layer.input_var_num * 3 + (simd_var_num + mpi_var_num) * 4 + 1) * elem_size;
+                // degree 2 polynomials over y, and vy
+                let phase_two = if layer.mul.is_empty() {
+                    0
+                } else {
+                    (layer.input_var_num * 3 + 1) * elem_size
+                };
+                (phase_one, phase_two)
+            }
+            GKRScheme::GkrSquare => {
+                // degree 6 polynomials over x, and vx written twice
+                ((layer.input_var_num * 7 + 2) * C::Field::SERIALIZED_SIZE, 0)
+            }
+        }
+    }
+}
+
+/// Share of the `rank`-th of `n` parties when splitting `total` as evenly as possible.
+#[inline]
+fn split_evenly(total: usize, n: usize, rank: usize) -> usize {
+    total / n + usize::from(rank < total % n)
+}
@@ -0,0 +1,128 @@
+//! The order and sizes of the parts of a vanilla GKR proof.
+
+use arith::FieldSerde;
+use circuit::{Circuit, VerifierKey};
+use config::{Config, GKRConfig, PolynomialCommitmentType};
+
+use crate::OrionParams;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofSegment {
+    /// The commitment, of the given size in bytes: the input values for the raw commitment.
+    Commitment(usize),
+    /// A sumcheck round: the evaluations of the round polynomial at 0, 1, ..., degree.
+    SumcheckRound { degree: usize },
+    /// The claimed value of the input layer polynomial at the sumcheck point, i.e., vx or vy.
+    Claim,
+    /// The opening of the commitment at the claim vx or vy of the input layer, of the given size
+    /// in bytes. The raw commitment has none, its values being evaluated by the verifier.
+    Opening(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofLayout {
+    /// Size in bytes of a challenge field element.
+    pub element_size: usize,
+    pub segments: Vec<ProofSegment>,
+    /// The number of segments of each layer, from the output layer down, after the commitment
+    /// and before the openings.
+    pub layer_segments: Vec<usize>,
+}
+
+impl ProofLayout {
+    /// The layers are proven from the output layer down. For each layer: the rounds of the
+    /// first phase over the input variables, the simd variables and the mpi variables, then vx,
+    /// and, unless the layer is of degree one, the rounds of the second phase and vy. Then the
+    /// openings at vx and vy of the input layer, if the commitment has any. The witness need not
+    /// be loaded.
+    pub fn new<C: GKRConfig>(circuit: &Circuit<C>, config: &Config<C>) -> Self {
+        let layers = circuit
+            .layers
+            .iter()
+            .map(|layer| (layer.input_var_num, layer.structure_info.max_degree_one))
+            .collect::<Vec<_>>();
+        Self::of_layers(&layers, config)
+    }
+
+    /// The layout of the proofs of the circuit of the verifier key.
+    pub fn for_key<C: GKRConfig>(vk: &VerifierKey<C>, config: &Config<C>) -> Self {
+        let layers = (0..vk.num_layers())
+            .map(|i| (vk.input_var_num(i), vk.max_degree_one(i)))
+            .collect::<Vec<_>>();
+        Self::of_layers(&layers, config)
+    }
+
+    /// The layout of the proofs of a circuit of the layers, from the input layer, each of its
+    /// number of input variables and whether it is of degree one.
+    pub(crate) fn of_layers<C: GKRConfig>(layers: &[(usize, bool)], config: &Config<C>) -> Self {
+        let world_size = config.mpi_config.world_size();
+        let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+        let mpi_var_num = world_size.trailing_zeros() as usize;
+        let log_input_size = layers
+            .first()
+            .map_or(0, |&(input_var_num, _)| input_var_num);
+        let orion = OrionParams::new::<C>(log_input_size + mpi_var_num, config.security_bits);
+
+        let mut segments = vec![ProofSegment::Commitment(
+            match config.polynomial_commitment_type {
+                PolynomialCommitmentType::Orion => orion.commitment_size(),
+                _ => (1 << log_input_size) * world_size * C::SimdCircuitField::SERIALIZED_SIZE,
+            },
+        )];
+        let mut layer_segments = vec![];
+        for &(input_var_num, max_degree_one) in layers.iter().rev() {
+            let first_segment = segments.len();
+            let x_rounds = (0..input_var_num).map(|_| ProofSegment::SumcheckRound { degree: 2 });
+            let simd_mpi_rounds =
+                (0..simd_var_num + mpi_var_num).map(|_| ProofSegment::SumcheckRound { degree: 3 });
+            segments.extend(x_rounds.chain(simd_mpi_rounds));
+            segments.push(ProofSegment::Claim);
+
+            if !max_degree_one {
+                segments
+                    .extend((0..input_var_num).map(|_| ProofSegment::SumcheckRound { degree: 2 }));
+                segments.push(ProofSegment::Claim);
+            }
+            layer_segments.push(segments.len() - first_segment);
+        }
+        if config.polynomial_commitment_type == PolynomialCommitmentType::Orion {
+            let num_claims = match layers.first() {
+                Some((_, false)) => 2,
+                _ => 1,
+            };
+            segments
+                .extend((0..num_claims).map(|_| ProofSegment::Opening(orion.opening_size::<C>())));
+        }
+
+        Self {
+            element_size: C::ChallengeField::SERIALIZED_SIZE,
+            segments,
+            layer_segments,
+        }
+    }
+
+    #[inline]
+    pub fn segment_size(&self, segment: &ProofSegment) -> usize {
+        match segment {
+            ProofSegment::Commitment(size) | ProofSegment::Opening(size) => *size,
+            ProofSegment::SumcheckRound { degree } => (degree + 1) * self.element_size,
+            ProofSegment::Claim => self.element_size,
+        }
+    }
+
+    /// Size in bytes of a valid proof.
+    pub fn proof_size(&self) -> usize {
+        self.segments.iter().map(|s| self.segment_size(s)).sum()
+    }
+
+    /// Size in bytes of a valid proof pruned by `ProofPruner`, i.e., with one element less
+    /// per sumcheck round.
+    pub fn pruned_proof_size(&self) -> usize {
+        let n_rounds = self
+            .segments
+            .iter()
+            .filter(|s| matches!(s, ProofSegment::SumcheckRound { .. }))
+            .count();
+        self.proof_size() - n_rounds * self.element_size
+    }
+}
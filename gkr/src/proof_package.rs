@@ -0,0 +1,569 @@
+//! A self-describing container for the proofs, so that they can be verified without knowing
+//! out of band how they were generated.
+//!
+//! Layout: the magic `EXPP`, the format version, the field type tag, the GKR scheme tag, the
+//! compression codec tag, followed for zstd by the compression level as a little-endian i32,
+//! then the proof, compressed by the codec, and the claimed values, their count as a
+//! little-endian u64 followed by their serialization, each prefixed by its length as a
+//! little-endian u64, the tag of the Fiat-Shamir hash of the transcript, and last
+//! the mpi world size of the prover as a little-endian u64, 0 if unknown, then 1 followed by
+//! the length prefixed public inputs of all the processes of the prover, or 0 without them, the
+//! tag of the polynomial commitment, and last 1 followed by the commitment of the public inputs
+//! of the root of the prover, see `input_commitment`, or 0 without it, then the same for the
+//! commitment of its private inputs, and for the seed of the transcripts of the prover.
+//! The packages of version 1 have no codec tag, their proof being uncompressed, the packages
+//! before version 3 have no hash tag, their hash being the one `expander-exec` proved the field
+//! with, see `legacy_fiat_shamir_hash`, the packages before version 4 have no world size, the
+//! packages before version 5 no public inputs, the packages before version 7 a single
+//! claimed value without its count, the packages before version 8 no commitment tag, their
+//! commitment being raw, the packages before version 9 no commitment of their inputs, and the
+//! packages before version 10 no seed, which the verifier had to be given.
+
+use std::io::{ErrorKind, Read, Write};
+
+use arith::{FieldSerde, FieldSerdeError};
+use config::{
+    Config, FiatShamirHashType, FieldType, GKRConfig, GKRScheme, PolynomialCommitmentType,
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use transcript::{decode_hex, encode_hex, Proof, ProofDecodeError};
+
+use crate::verification_key::{fiat_shamir_hash_tag, polynomial_commitment_tag};
+
+pub const PROOF_PACKAGE_MAGIC: [u8; 4] = *b"EXPP";
+
+/// The latest version of the format. The transcripts of the proofs of version 6 absorb the
+/// public inputs, and those of version 7 the claimed values, so that the proofs of the earlier
+/// versions, read still, no longer verify.
+pub const PROOF_PACKAGE_VERSION: u8 = 10;
+
+/// The most bytes a compressed proof is decompressed to by `ProofPackage::deserialize_from`,
+/// for the packages of proofs of unknown circuits, see `deserialize_with_max_proof_size`.
+pub const MAX_PROOF_SIZE: usize = 1 << 28;
+
+#[derive(Debug, Error)]
+pub enum ProofPackageError {
+    #[error("not a proof package")]
+    InvalidMagic,
+
+    #[error(
+        "unsupported proof package version {0}, the latest supported is {PROOF_PACKAGE_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+
+    #[error("unknown field type tag {0}")]
+    UnknownFieldType(u8),
+
+    #[error("unknown gkr scheme tag {0}")]
+    UnknownGKRScheme(u8),
+
+    #[error("unknown fiat shamir hash tag {0}")]
+    UnknownFiatShamirHash(u8),
+
+    #[error("unknown polynomial commitment tag {0}")]
+    UnknownPolynomialCommitment(u8),
+
+    #[error("unknown compression codec tag {0}")]
+    UnknownCompressionCodec(u8),
+
+    #[error("unable to decompress the proof: {0}")]
+    Decompression(std::io::Error),
+
+    #[error("the proof decompresses to more than {max_size} bytes")]
+    TooLarge { max_size: usize },
+
+    #[error("truncated proof package")]
+    Truncated,
+
+    #[error("{0} bytes after the proof package")]
+    TrailingBytes(usize),
+
+    #[error("invalid hex: {0}")]
+    Hex(ProofDecodeError),
+
+    #[error("the proof is over {found:?}, not {expected:?}")]
+    FieldTypeMismatch {
+        expected: FieldType,
+        found: FieldType,
+    },
+
+    #[error("the proof is for {found:?}, not {expected:?}")]
+    GKRSchemeMismatch {
+        expected: GKRScheme,
+        found: GKRScheme,
+    },
+
+    #[error("the proof transcript is hashed with {found:?}, not {expected:?}")]
+    FiatShamirHashMismatch {
+        expected: FiatShamirHashType,
+        found: FiatShamirHashType,
+    },
+
+    #[error("the proof is committed with {found:?}, not {expected:?}, see --pcs")]
+    PolynomialCommitmentMismatch {
+        expected: PolynomialCommitmentType,
+        found: PolynomialCommitmentType,
+    },
+
+    #[error("the proof is of another seed than the one given, see --seed")]
+    ProverSeedMismatch,
+
+    #[error("the proof was generated by {found} mpi processes, not {expected}")]
+    MPIWorldSizeMismatch { expected: usize, found: usize },
+
+    #[error("the proof claims {0} mpi processes, which is not a power of 2")]
+    InvalidMPIWorldSize(usize),
+
+    #[error("the public inputs of {0} bytes are not a whole number of field elements")]
+    InvalidPublicInput(usize),
+
+    #[error("the claimed values of {0} bytes are not as many field elements as their count")]
+    InvalidClaimedValues(usize),
+
+    #[error("field serde error: {0:?}")]
+    FieldSerdeError(FieldSerdeError),
+}
+
+impl From<FieldSerdeError> for ProofPackageError {
+    fn from(e: FieldSerdeError) -> Self {
+        match e {
+            FieldSerdeError::IOError(e) if e.kind() == ErrorKind::UnexpectedEof => Self::Truncated,
+            e => Self::FieldSerdeError(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for ProofPackageError {
+    fn from(e: std::io::Error) -> Self {
+        FieldSerdeError::from(e).into()
+    }
+}
+
+/// How the proof is compressed in the package. The proof of the package itself is never
+/// compressed, only its serialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionCodec {
+    #[default]
+    None,
+    Zstd {
+        level: i32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofPackage {
+    pub field_type: FieldType,
+    pub gkr_scheme: GKRScheme,
+    pub fiat_shamir_hash: FiatShamirHashType,
+    /// Raw for the packages before version 8.
+    pub polynomial_commitment_type: PolynomialCommitmentType,
+    pub compression: CompressionCodec,
+    pub proof: Proof,
+    /// The count of the claimed values as a little-endian u64, followed by the values serialized
+    /// in the challenge field of the config.
+    pub claimed_v: Vec<u8>,
+    /// The number of mpi processes of the prover, unknown for the packages before version 4.
+    pub mpi_world_size: Option<usize>,
+    /// The public inputs of the mpi processes of the prover in the order of their ranks,
+    /// serialized in the simd circuit field of the config, gathered by the root when proving.
+    pub public_input: Option<Vec<u8>>,
+    /// The commitment of the public inputs of the root of the prover, for a verifier to tell
+    /// the proofs of other inputs before verifying them, none for the packages before version 9.
+    pub public_input_commitment: Option<[u8; 32]>,
+    /// The commitment of the private inputs of the root of the prover, if it committed to its
+    /// whole witness.
+    pub private_input_commitment: Option<[u8; 32]>,
+    /// The seed of the transcripts of the prover, see `Config::with_prover_seed`, which the
+    /// verifier absorbs in place of its own, none for the packages before version 10.
+    pub prover_seed: Option<[u8; 32]>,
+}
+
+/// The SHA-256 of the packed inputs of a prove call, as loaded in the circuit, e.g., its
+/// `public_input`, serialized in the simd circuit field after their count as a little-endian
+/// u64.
+pub fn input_commitment<C: GKRConfig>(inputs: &[C::SimdCircuitField]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update((inputs.len() as u64).to_le_bytes());
+    let mut bytes = Vec::with_capacity(C::SimdCircuitField::SERIALIZED_SIZE);
+    for input in inputs {
+        bytes.clear();
+        // into a vec, which does not fail
+        input.serialize_into(&mut bytes).unwrap();
+        hasher.update(&bytes);
+    }
+    hasher.finalize().into()
+}
+
+fn field_type_tag(field_type: &FieldType) -> u8 {
+    match field_type {
+        FieldType::M31 => 0,
+        FieldType::BN254 => 1,
+        FieldType::GF2 => 2,
+        FieldType::Native => 3,
+        FieldType::Goldilocks => 4,
+        FieldType::BabyBear => 5,
+    }
+}
+
+/// The hash of the configs of the field `expander-exec` proved with before the packages recorded
+/// the hash.
+fn legacy_fiat_shamir_hash(field_type: &FieldType) -> FiatShamirHashType {
+    match field_type {
+        FieldType::BN254 => FiatShamirHashType::MIMC5,
+        _ => FiatShamirHashType::SHA256,
+    }
+}
+
+impl ProofPackage {
+    pub fn new<C: GKRConfig>(
+        config: &Config<C>,
+        proof: &Proof,
+        claimed_v: &[C::ChallengeField],
+    ) -> Result<Self, FieldSerdeError> {
+        let mut claimed_v_bytes = (claimed_v.len() as u64).to_le_bytes().to_vec();
+        claimed_v
+            .iter()
+            .try_for_each(|v| v.serialize_into(&mut claimed_v_bytes))?;
+        Ok(Self {
+            field_type: C::FIELD_TYPE,
+            gkr_scheme: config.gkr_scheme.clone(),
+            fiat_shamir_hash: config.fiat_shamir_hash(),
+            polynomial_commitment_type: config.polynomial_commitment_type.clone(),
+            compression: CompressionCodec::None,
+            proof: proof.clone(),
+            claimed_v: claimed_v_bytes,
+            mpi_world_size: Some(config.mpi_config.world_size()),
+            public_input: None,
+            public_input_commitment: None,
+            private_input_commitment: None,
+            prover_seed: config.prover_seed,
+        })
+    }
+
+    /// The same package, with the commitment of the public inputs of the root of the prover,
+    /// and of its private inputs, if any, see `input_commitment`.
+    pub fn with_input_commitment<C: GKRConfig>(
+        mut self,
+        private_input: Option<&[C::SimdCircuitField]>,
+        public_input: &[C::SimdCircuitField],
+    ) -> Self {
+        self.public_input_commitment = Some(input_commitment::<C>(public_input));
+        self.private_input_commitment = private_input.map(input_commitment::<C>);
+        self
+    }
+
+    /// The same package, with the public inputs of all the processes of the prover.
+    pub fn with_public_input<C: GKRConfig>(
+        mut self,
+        public_input: &[C::SimdCircuitField],
+    ) -> Result<Self, FieldSerdeError> {
+        let mut bytes = vec![];
+        public_input
+            .iter()
+            .try_for_each(|input| input.serialize_into(&mut bytes))?;
+        self.public_input = Some(bytes);
+        Ok(self)
+    }
+
+    /// The public inputs of all the processes of the prover, if the package has them, failing
+    /// if the package is over another field than the one of `C`.
+    pub fn public_input<C: GKRConfig>(
+        &self,
+    ) -> Result<Option<Vec<C::SimdCircuitField>>, ProofPackageError> {
+        if self.field_type != C::FIELD_TYPE {
+            return Err(ProofPackageError::FieldTypeMismatch {
+                expected: C::FIELD_TYPE,
+                found: self.field_type.clone(),
+            });
+        }
+        let Some(bytes) = &self.public_input else {
+            return Ok(None);
+        };
+        if bytes.len() % C::SimdCircuitField::SERIALIZED_SIZE != 0 {
+            return Err(ProofPackageError::InvalidPublicInput(bytes.len()));
+        }
+        bytes
+            .chunks_exact(C::SimdCircuitField::SERIALIZED_SIZE)
+            .map(|input| Ok(C::SimdCircuitField::deserialize_from(input)?))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    /// The same package, with its proof serialized by the codec.
+    pub fn with_compression(mut self, compression: CompressionCodec) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// The claimed values, failing if the package is over another field than the one of `C`.
+    pub fn claimed_v<C: GKRConfig>(&self) -> Result<Vec<C::ChallengeField>, ProofPackageError> {
+        if self.field_type != C::FIELD_TYPE {
+            return Err(ProofPackageError::FieldTypeMismatch {
+                expected: C::FIELD_TYPE,
+                found: self.field_type.clone(),
+            });
+        }
+        let invalid = || ProofPackageError::InvalidClaimedValues(self.claimed_v.len());
+        let (count, values) = self
+            .claimed_v
+            .split_first_chunk::<8>()
+            .ok_or_else(invalid)?;
+        let size = C::ChallengeField::SERIALIZED_SIZE;
+        if u64::from_le_bytes(*count).checked_mul(size as u64) != Some(values.len() as u64) {
+            return Err(invalid());
+        }
+        values
+            .chunks_exact(size)
+            .map(|v| Ok(C::ChallengeField::deserialize_from(v)?))
+            .collect()
+    }
+
+    pub fn serialize_into(&self, w: &mut impl Write) -> Result<(), FieldSerdeError> {
+        let gkr_scheme_tag = match self.gkr_scheme {
+            GKRScheme::Vanilla => 0u8,
+            GKRScheme::GkrSquare => 1,
+        };
+        w.write_all(&PROOF_PACKAGE_MAGIC)?;
+        w.write_all(&[
+            PROOF_PACKAGE_VERSION,
+            field_type_tag(&self.field_type),
+            gkr_scheme_tag,
+        ])?;
+        match self.compression {
+            CompressionCodec::None => {
+                w.write_all(&[0])?;
+                self.proof.serialize_into(&mut *w)?;
+            }
+            CompressionCodec::Zstd { level } => {
+                w.write_all(&[1])?;
+                w.write_all(&level.to_le_bytes())?;
+                Proof {
+                    bytes: zstd::encode_all(self.proof.bytes.as_slice(), level)?,
+                }
+                .serialize_into(&mut *w)?;
+            }
+        }
+        (self.claimed_v.len() as u64).serialize_into(&mut *w)?;
+        w.write_all(&self.claimed_v)?;
+        w.write_all(&[fiat_shamir_hash_tag(&self.fiat_shamir_hash)])?;
+        (self.mpi_world_size.unwrap_or(0) as u64).serialize_into(&mut *w)?;
+        match &self.public_input {
+            None => w.write_all(&[0])?,
+            Some(public_input) => {
+                w.write_all(&[1])?;
+                (public_input.len() as u64).serialize_into(&mut *w)?;
+                w.write_all(public_input)?;
+            }
+        }
+        w.write_all(&[polynomial_commitment_tag(&self.polynomial_commitment_type)])?;
+        for commitment in [
+            &self.public_input_commitment,
+            &self.private_input_commitment,
+            &self.prover_seed,
+        ] {
+            match commitment {
+                None => w.write_all(&[0])?,
+                Some(commitment) => {
+                    w.write_all(&[1])?;
+                    w.write_all(commitment)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The package of the bytes, its proof decompressed to at most `MAX_PROOF_SIZE` bytes.
+    pub fn deserialize_from(r: &mut impl Read) -> Result<Self, ProofPackageError> {
+        Self::deserialize_with_max_proof_size(r, MAX_PROOF_SIZE)
+    }
+
+    /// As `deserialize_from`, with a compressed proof refused if it decompresses to more than
+    /// `max_proof_size` bytes, e.g., the `ProofLayout` size of the proofs of the circuit.
+    pub fn deserialize_with_max_proof_size(
+        r: &mut impl Read,
+        max_proof_size: usize,
+    ) -> Result<Self, ProofPackageError> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if magic != PROOF_PACKAGE_MAGIC {
+            return Err(ProofPackageError::InvalidMagic);
+        }
+        let mut tags = [0u8; 3];
+        r.read_exact(&mut tags)?;
+        if tags[0] > PROOF_PACKAGE_VERSION {
+            return Err(ProofPackageError::UnsupportedVersion(tags[0]));
+        }
+        let field_type = [
+            FieldType::M31,
+            FieldType::BN254,
+            FieldType::GF2,
+            FieldType::Native,
+            FieldType::Goldilocks,
+            FieldType::BabyBear,
+        ]
+        .into_iter()
+        .find(|field_type| field_type_tag(field_type) == tags[1])
+        .ok_or(ProofPackageError::UnknownFieldType(tags[1]))?;
+        let gkr_scheme = match tags[2] {
+            0 => GKRScheme::Vanilla,
+            1 => GKRScheme::GkrSquare,
+            tag => return Err(ProofPackageError::UnknownGKRScheme(tag)),
+        };
+        let compression = if tags[0] < 2 {
+            CompressionCodec::None
+        } else {
+            let mut tag = [0u8; 1];
+            r.read_exact(&mut tag)?;
+            match tag[0] {
+                0 => CompressionCodec::None,
+                1 => {
+                    let mut level = [0u8; 4];
+                    r.read_exact(&mut level)?;
+                    CompressionCodec::Zstd {
+                        level: i32::from_le_bytes(level),
+                    }
+                }
+                tag => return Err(ProofPackageError::UnknownCompressionCodec(tag)),
+            }
+        };
+        let proof_bytes = read_length_prefixed(r)?;
+        let proof = Proof {
+            bytes: match compression {
+                CompressionCodec::None => proof_bytes,
+                CompressionCodec::Zstd { .. } => decompress(&proof_bytes, max_proof_size)?,
+            },
+        };
+        let mut claimed_v = read_length_prefixed(r)?;
+        if tags[0] < 7 {
+            claimed_v.splice(0..0, 1u64.to_le_bytes());
+        }
+        let fiat_shamir_hash = if tags[0] < 3 {
+            legacy_fiat_shamir_hash(&field_type)
+        } else {
+            let mut tag = [0u8; 1];
+            r.read_exact(&mut tag)?;
+            [
+                FiatShamirHashType::SHA256,
+                FiatShamirHashType::Keccak256,
+                FiatShamirHashType::Poseidon,
+                FiatShamirHashType::Animoe,
+                FiatShamirHashType::MIMC5,
+                FiatShamirHashType::Blake3,
+            ]
+            .into_iter()
+            .find(|hash| fiat_shamir_hash_tag(hash) == tag[0])
+            .ok_or(ProofPackageError::UnknownFiatShamirHash(tag[0]))?
+        };
+        let mpi_world_size = if tags[0] < 4 {
+            None
+        } else {
+            match u64::deserialize_from(&mut *r)? {
+                0 => None,
+                world_size if world_size.is_power_of_two() => Some(world_size as usize),
+                world_size => {
+                    return Err(ProofPackageError::InvalidMPIWorldSize(world_size as usize))
+                }
+            }
+        };
+        let public_input = if tags[0] < 5 {
+            None
+        } else {
+            let mut tag = [0u8; 1];
+            r.read_exact(&mut tag)?;
+            match tag[0] {
+                0 => None,
+                _ => Some(read_length_prefixed(r)?),
+            }
+        };
+        let polynomial_commitment_type = if tags[0] < 8 {
+            PolynomialCommitmentType::Raw
+        } else {
+            let mut tag = [0u8; 1];
+            r.read_exact(&mut tag)?;
+            PolynomialCommitmentType::ALL
+                .into_iter()
+                .find(|pcs| polynomial_commitment_tag(pcs) == tag[0])
+                .ok_or(ProofPackageError::UnknownPolynomialCommitment(tag[0]))?
+        };
+        let mut read_commitment = |since: u8| -> Result<_, ProofPackageError> {
+            if tags[0] < since {
+                return Ok(None);
+            }
+            let mut tag = [0u8; 1];
+            r.read_exact(&mut tag)?;
+            match tag[0] {
+                0 => Ok(None),
+                _ => {
+                    let mut commitment = [0u8; 32];
+                    r.read_exact(&mut commitment)?;
+                    Ok(Some(commitment))
+                }
+            }
+        };
+        let public_input_commitment = read_commitment(9)?;
+        let private_input_commitment = read_commitment(9)?;
+        let prover_seed = read_commitment(10)?;
+
+        Ok(Self {
+            field_type,
+            gkr_scheme,
+            fiat_shamir_hash,
+            polynomial_commitment_type,
+            compression,
+            proof,
+            claimed_v,
+            mpi_world_size,
+            public_input,
+            public_input_commitment,
+            private_input_commitment,
+            prover_seed,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.serialize_into(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// The bytes of the package as lowercase hex.
+    pub fn to_hex_string(&self) -> String {
+        encode_hex(&self.to_bytes())
+    }
+
+    /// The package of `to_hex_string`, with nothing after it.
+    pub fn from_hex_string(s: &str) -> Result<Self, ProofPackageError> {
+        let bytes = decode_hex(s).map_err(ProofPackageError::Hex)?;
+        let mut reader = bytes.as_slice();
+        let package = Self::deserialize_from(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(ProofPackageError::TrailingBytes(reader.len()));
+        }
+        Ok(package)
+    }
+}
+
+/// The zstd frame decompressed, a byte past `max_size` at most, so that a small frame of a
+/// large proof is refused before it is allocated.
+fn decompress(frame: &[u8], max_size: usize) -> Result<Vec<u8>, ProofPackageError> {
+    let mut bytes = vec![];
+    zstd::Decoder::new(frame)
+        .and_then(|decoder| decoder.take(max_size as u64 + 1).read_to_end(&mut bytes))
+        .map_err(ProofPackageError::Decompression)?;
+    if bytes.len() > max_size {
+        return Err(ProofPackageError::TooLarge { max_size });
+    }
+    Ok(bytes)
+}
+
+/// The bytes after their length, without trusting the length for the allocation.
+fn read_length_prefixed(r: &mut impl Read) -> Result<Vec<u8>, ProofPackageError> {
+    let len = u64::deserialize_from(&mut *r)?;
+    let mut bytes = vec![];
+    r.by_ref().take(len).read_to_end(&mut bytes)?;
+    if (bytes.len() as u64) < len {
+        return Err(ProofPackageError::Truncated);
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,139 @@
+//! Removing the derivable sumcheck messages from a proof.
+//!
+//! In each sumcheck round, the verifier checks `p(0) + p(1) == claimed_sum` on the round
+//! polynomial `p`, so `p(1)` carries no information: a verifier tracking the claimed sum
+//! recomputes it from `p(0)`. A pruned proof leaves it out, which saves one field element out of
+//! `d + 1` per round of degree `d`, and verifies with `Verifier::verify_pruned`.
+//! Going back to a full proof takes a verifier pass, as the claimed sums depend on the challenges.
+
+use arith::FieldSerde;
+use circuit::Circuit;
+use config::{Config, GKRConfig};
+use transcript::Proof;
+
+use crate::{verifier::SumcheckMessages, ProofLayout, ProofSegment, Verifier};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrunedProof {
+    pub bytes: Vec<u8>,
+}
+
+pub struct ProofPruner;
+
+impl ProofPruner {
+    /// Panics if the proof does not have the size of a proof of the circuit.
+    pub fn prune<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+        proof: &Proof,
+    ) -> PrunedProof {
+        let layout = ProofLayout::new(circuit, config);
+        assert_eq!(
+            proof.bytes.len(),
+            layout.proof_size(),
+            "the proof is not a proof of the circuit"
+        );
+
+        let element_size = layout.element_size;
+        let mut bytes = Vec::with_capacity(layout.pruned_proof_size());
+        let mut offset = 0;
+        for segment in &layout.segments {
+            let size = layout.segment_size(segment);
+            let segment_bytes = &proof.bytes[offset..offset + size];
+            match segment {
+                ProofSegment::SumcheckRound { .. } => {
+                    bytes.extend_from_slice(&segment_bytes[..element_size]);
+                    bytes.extend_from_slice(&segment_bytes[2 * element_size..]);
+                }
+                _ => bytes.extend_from_slice(segment_bytes),
+            }
+            offset += size;
+        }
+
+        PrunedProof { bytes }
+    }
+}
+
+impl PrunedProof {
+    /// Restore the full proof. The evaluations at 1 are recomputed by running the verifier,
+    /// so the result is only a valid proof if the pruned proof verifies.
+    ///
+    /// Panics if the pruned proof does not have the size of a pruned proof of the circuit.
+    pub fn expand<C: GKRConfig>(
+        &self,
+        circuit: &mut Circuit<C>,
+        config: &Config<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+    ) -> Proof {
+        let layout = ProofLayout::new(circuit, config);
+        assert_eq!(
+            self.bytes.len(),
+            layout.pruned_proof_size(),
+            "the pruned proof is not a pruned proof of the circuit"
+        );
+
+        let mut messages = SumcheckMessages {
+            pruned: true,
+            ..Default::default()
+        };
+        Verifier::new(config).verify_sumcheck_messages(
+            circuit,
+            public_input,
+            claimed_v,
+            &self.bytes,
+            &mut messages,
+        );
+
+        let element_size = layout.element_size;
+        let mut evals_at_one = messages.evals_at_one.iter();
+        let mut bytes = Vec::with_capacity(layout.proof_size());
+        let mut offset = 0;
+        for segment in &layout.segments {
+            let size = match segment {
+                ProofSegment::SumcheckRound { .. } => {
+                    let size = layout.segment_size(segment) - element_size;
+                    bytes.extend_from_slice(&self.bytes[offset..offset + element_size]);
+                    evals_at_one
+                        .next()
+                        .unwrap()
+                        .serialize_into(&mut bytes)
+                        .unwrap();
+                    bytes.extend_from_slice(&self.bytes[offset + element_size..offset + size]);
+                    size
+                }
+                _ => {
+                    let size = layout.segment_size(segment);
+                    bytes.extend_from_slice(&self.bytes[offset..offset + size]);
+                    size
+                }
+            };
+            offset += size;
+        }
+
+        Proof { bytes }
+    }
+}
+
+impl<C: GKRConfig> Verifier<C> {
+    pub fn verify_pruned(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &PrunedProof,
+    ) -> bool {
+        let mut messages = SumcheckMessages {
+            pruned: true,
+            ..Default::default()
+        };
+        self.verify_sumcheck_messages(
+            circuit,
+            public_input,
+            claimed_v,
+            &proof.bytes,
+            &mut messages,
+        )
+        .passed()
+    }
+}
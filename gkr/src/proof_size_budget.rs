@@ -0,0 +1,49 @@
+//! Refusing to prove circuits whose proofs would not fit a size limit, e.g., the calldata
+//! limit of a chain verifying them.
+//!
+//! The size of a vanilla GKR proof with the raw commitment only depends on the circuit and the
+//! config, see `ProofLayout`: the estimate is exact, and known before the first sumcheck round,
+//! so the prover aborts before spending any time on a proof that would be over the budget.
+//! The size of a GKR^2 proof is not given by a layout, so those are refused.
+
+use config::GKRScheme;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the proof would be {estimated_final_bytes} bytes, over the budget of {max_bytes} bytes")]
+pub struct ProofSizeBudgetExceeded {
+    pub estimated_final_bytes: usize,
+    pub max_bytes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ProofSizeBudgetError {
+    #[error(transparent)]
+    Exceeded(#[from] ProofSizeBudgetExceeded),
+
+    #[error("the size of the {0:?} proofs is not known before proving, only of vanilla GKR ones")]
+    UnsupportedScheme(GKRScheme),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSizeBudget {
+    pub max_bytes: usize,
+}
+
+impl ProofSizeBudget {
+    #[inline]
+    pub fn new(max_bytes: usize) -> Self {
+        Self { max_bytes }
+    }
+
+    /// Whether a proof of `proof_size` bytes fits the budget.
+    pub fn check(&self, proof_size: usize) -> Result<(), ProofSizeBudgetExceeded> {
+        if proof_size > self.max_bytes {
+            return Err(ProofSizeBudgetExceeded {
+                estimated_final_bytes: proof_size,
+                max_bytes: self.max_bytes,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! The parameters the correctness and soundness of a proof depend on, in one place.
+
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme};
+use transcript::Proof;
+
+use crate::{ProofLayout, ProofSegment, ProtocolSecurityEstimator};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GKRProtocolConstants {
+    /// The number of sumcheck rounds of each layer, the input layer first.
+    pub rounds_per_layer: Vec<usize>,
+    /// The largest degree of a round polynomial.
+    pub max_sumcheck_degree: usize,
+    pub total_rounds: usize,
+    /// `max_sumcheck_degree / |F|`, the probability that a round accepts a wrong claim,
+    /// by Schwartz-Zippel.
+    pub soundness_error_per_round: f64,
+}
+
+impl GKRProtocolConstants {
+    pub fn compute<C: GKRConfig>(circuit: &Circuit<C>, config: &Config<C>) -> Self {
+        let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+        let mpi_var_num = config.mpi_config.world_size().trailing_zeros() as usize;
+
+        let (rounds_per_layer, max_sumcheck_degree) = match config.gkr_scheme {
+            // phase one over x, simd and mpi, of degree 3 over simd and mpi, and phase two over
+            // y for the layers with mul gates
+            GKRScheme::Vanilla => {
+                let rounds = circuit
+                    .layers
+                    .iter()
+                    .map(|layer| {
+                        let phase_two = if layer.structure_info.max_degree_one {
+                            0
+                        } else {
+                            layer.input_var_num
+                        };
+                        layer.input_var_num + simd_var_num + mpi_var_num + phase_two
+                    })
+                    .collect();
+                let degree = if simd_var_num + mpi_var_num > 0 { 3 } else { 2 };
+                (rounds, degree)
+            }
+            // a single phase over x, of degree 6 for the pow5 gates
+            GKRScheme::GkrSquare => (
+                circuit
+                    .layers
+                    .iter()
+                    .map(|layer| layer.input_var_num)
+                    .collect(),
+                6,
+            ),
+        };
+        let total_rounds = rounds_per_layer.iter().sum();
+        let field_bits = ProtocolSecurityEstimator::challenge_field_bits::<C>();
+
+        Self {
+            rounds_per_layer,
+            max_sumcheck_degree,
+            total_rounds,
+            soundness_error_per_round: max_sumcheck_degree as f64 / field_bits.exp2(),
+        }
+    }
+
+    /// Panics unless the proof has the size of a vanilla GKR proof with the raw commitment
+    /// following these constants, see `ProofLayout`.
+    pub fn assert_matches<C: GKRConfig>(
+        &self,
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+        proof: &Proof,
+    ) {
+        let layout = ProofLayout::new(circuit, config);
+        let degrees = layout
+            .segments
+            .iter()
+            .filter_map(|s| match s {
+                ProofSegment::SumcheckRound { degree } => Some(*degree),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(degrees.len(), self.total_rounds, "wrong number of rounds");
+        assert!(
+            degrees.iter().all(|d| *d <= self.max_sumcheck_degree),
+            "a sumcheck round exceeds the degree bound {}",
+            self.max_sumcheck_degree
+        );
+        assert_eq!(
+            proof.bytes.len(),
+            layout.proof_size(),
+            "the proof does not follow the protocol constants"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! The requests, latencies and sizes of the proofs of the serve mode, in the Prometheus text
+//! format. The metrics are labelled with the field and the scheme of the circuit served, for the
+//! instances of several circuits to be scraped together.
+
+use std::{
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use config::{FieldType, GKRScheme};
+
+use crate::ProofStats;
+
+/// The upper bounds of the latency buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 10] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// The upper bounds of the witness loading buckets, in seconds.
+const WITNESS_LOAD_BUCKETS: [f64; 8] = [0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5];
+
+/// The upper bounds of the size buckets, in bytes: 1 KiB to 64 MiB, by factors of 4.
+const PROOF_BYTES_BUCKETS: [f64; 9] = [
+    1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0, 67108864.0,
+];
+
+struct Histogram {
+    bounds: &'static [f64],
+    /// The number of observations in each bucket, not cumulated.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// `labels` are the labels of every sample, e.g., `field="M31",`, each with its comma.
+    fn render(&self, out: &mut String, name: &str, help: &str, labels: &str) {
+        writeln!(out, "# HELP {} {}", name, help).unwrap();
+        writeln!(out, "# TYPE {} histogram", name).unwrap();
+        let mut cumulated = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulated += count;
+            writeln!(
+                out,
+                "{}_bucket{{{}le=\"{}\"}} {}",
+                name, labels, bound, cumulated
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "{}_bucket{{{}le=\"+Inf\"}} {}",
+            name, labels, self.count
+        )
+        .unwrap();
+        writeln!(out, "{}_sum{} {}", name, braced(labels), self.sum).unwrap();
+        writeln!(out, "{}_count{} {}", name, braced(labels), self.count).unwrap();
+    }
+}
+
+/// The labels without their last comma in braces, or nothing if there is no label.
+fn braced(labels: &str) -> String {
+    match labels.strip_suffix(',') {
+        Some(labels) => format!("{{{}}}", labels),
+        None => String::new(),
+    }
+}
+
+/// The requests counted by `ProveMetrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsRequest {
+    Prove,
+    Verify,
+}
+
+impl MetricsRequest {
+    fn name(&self) -> &'static str {
+        match self {
+            MetricsRequest::Prove => "prove",
+            MetricsRequest::Verify => "verify",
+        }
+    }
+}
+
+/// The successes and the failures of a kind of request.
+#[derive(Default)]
+struct RequestCounter {
+    successes: AtomicU64,
+    failures: AtomicU64,
+}
+
+pub struct ProveMetrics {
+    /// The labels of every sample, each followed by a comma.
+    labels: String,
+    prove_requests: RequestCounter,
+    verify_requests: RequestCounter,
+    latency: Mutex<Histogram>,
+    proof_bytes: Mutex<Histogram>,
+    witness_load: Mutex<Histogram>,
+}
+
+impl Default for ProveMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProveMetrics {
+    /// Metrics without labels.
+    pub fn new() -> Self {
+        Self::with_labels(String::new())
+    }
+
+    /// Metrics labelled with the field and the scheme of the circuit, as in
+    /// `field="M31",scheme="vanilla"`.
+    pub fn for_circuit(field_type: &FieldType, scheme: &GKRScheme) -> Self {
+        let scheme = match scheme {
+            GKRScheme::Vanilla => "vanilla",
+            GKRScheme::GkrSquare => "gkr_square",
+        };
+        Self::with_labels(format!("field=\"{:?}\",scheme=\"{}\",", field_type, scheme))
+    }
+
+    fn with_labels(labels: String) -> Self {
+        Self {
+            labels,
+            prove_requests: RequestCounter::default(),
+            verify_requests: RequestCounter::default(),
+            latency: Mutex::new(Histogram::new(&LATENCY_BUCKETS)),
+            proof_bytes: Mutex::new(Histogram::new(&PROOF_BYTES_BUCKETS)),
+            witness_load: Mutex::new(Histogram::new(&WITNESS_LOAD_BUCKETS)),
+        }
+    }
+
+    /// Count a request, answered with a proof or a valid verification, or not.
+    pub fn record_request(&self, request: MetricsRequest, success: bool) {
+        let counter = self.counter(request);
+        let count = if success {
+            &counter.successes
+        } else {
+            &counter.failures
+        };
+        count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn counter(&self, request: MetricsRequest) -> &RequestCounter {
+        match request {
+            MetricsRequest::Prove => &self.prove_requests,
+            MetricsRequest::Verify => &self.verify_requests,
+        }
+    }
+
+    /// The time to deserialize a witness into the circuit, loaded or rejected.
+    pub fn record_witness_load(&self, duration: Duration) {
+        self.witness_load
+            .lock()
+            .unwrap()
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record(&self, stats: &ProofStats) {
+        self.latency
+            .lock()
+            .unwrap()
+            .observe(stats.prove_duration.as_secs_f64());
+        self.proof_bytes
+            .lock()
+            .unwrap()
+            .observe(stats.proof_bytes as f64);
+    }
+
+    /// The counters of the requests and the histograms of the recorded proofs, as served on
+    /// `/metrics`.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "# HELP expander_requests_total Prove and verify requests."
+        )
+        .unwrap();
+        writeln!(out, "# TYPE expander_requests_total counter").unwrap();
+        for request in [MetricsRequest::Prove, MetricsRequest::Verify] {
+            let counter = self.counter(request);
+            for (result, count) in [
+                ("success", &counter.successes),
+                ("failure", &counter.failures),
+            ] {
+                writeln!(
+                    out,
+                    "expander_requests_total{{{}request=\"{}\",result=\"{}\"}} {}",
+                    self.labels,
+                    request.name(),
+                    result,
+                    count.load(Ordering::Relaxed)
+                )
+                .unwrap();
+            }
+        }
+        self.latency.lock().unwrap().render(
+            &mut out,
+            "expander_prove_duration_seconds",
+            "Wall-clock proving time.",
+            &self.labels,
+        );
+        self.proof_bytes.lock().unwrap().render(
+            &mut out,
+            "expander_proof_bytes",
+            "Size of the proofs, before packaging.",
+            &self.labels,
+        );
+        self.witness_load.lock().unwrap().render(
+            &mut out,
+            "expander_witness_load_duration_seconds",
+            "Time to deserialize the witnesses into the circuit.",
+            &self.labels,
+        );
+        out
+    }
+}
@@ -1,5 +1,7 @@
 //! This module implements the core GKR IOP.
 
+use std::time::{Duration, Instant};
+
 use arith::{Field, SimdField};
 use ark_std::{end_timer, start_timer};
 use circuit::Circuit;
@@ -21,6 +23,25 @@ pub fn gkr_prove<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     Option<Vec<C::ChallengeField>>,
     Vec<C::ChallengeField>,
     Vec<C::ChallengeField>,
+) {
+    gkr_prove_timed(circuit, sp, transcript, mpi_config, &mut vec![])
+}
+
+/// As `gkr_prove`, pushing the time taken by the sumcheck of each layer to the durations, from
+/// the output layer to the input layer.
+#[allow(clippy::type_complexity)]
+pub(crate) fn gkr_prove_timed<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    circuit: &Circuit<C>,
+    sp: &mut ProverScratchPad<C>,
+    transcript: &mut T,
+    mpi_config: &MPIConfig,
+    layer_durations: &mut Vec<Duration>,
+) -> (
+    C::ChallengeField,
+    Vec<C::ChallengeField>,
+    Option<Vec<C::ChallengeField>>,
+    Vec<C::ChallengeField>,
+    Vec<C::ChallengeField>,
 ) {
     let timer = start_timer!(|| "gkr prove");
     let layer_num = circuit.layers.len();
@@ -67,6 +88,10 @@ pub fn gkr_prove<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     };
 
     for i in (0..layer_num).rev() {
+        // the layer is a field of the span, whose name must be static
+        #[cfg(feature = "telemetry")]
+        let _span = tracing::debug_span!("sumcheck_layer", layer = i).entered();
+        let layer_start = Instant::now();
         (rz0, rz1, r_simd, r_mpi) = sumcheck_prove_gkr_layer(
             &circuit.layers[i],
             &rz0,
@@ -88,6 +113,7 @@ pub fn gkr_prove<C: GKRConfig, T: Transcript<C::ChallengeField>>(
         } else {
             alpha = None;
         }
+        layer_durations.push(layer_start.elapsed());
     }
 
     end_timer!(timer);
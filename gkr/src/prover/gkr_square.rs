@@ -1,6 +1,8 @@
 // an implementation of the GKR^2 protocol
 //! This module implements the core GKR^2 IOP.
 
+use std::time::{Duration, Instant};
+
 use ark_std::{end_timer, start_timer};
 use circuit::Circuit;
 use config::GKRConfig;
@@ -11,6 +13,16 @@ pub fn gkr_square_prove<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     circuit: &Circuit<C>,
     sp: &mut ProverScratchPad<C>,
     transcript: &mut T,
+) -> (C::Field, Vec<C::ChallengeField>) {
+    gkr_square_prove_timed(circuit, sp, transcript, &mut vec![])
+}
+
+/// As `gkr_square_prove`, with the time of the sumcheck of each layer, see `gkr_prove_timed`.
+pub(crate) fn gkr_square_prove_timed<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    circuit: &Circuit<C>,
+    sp: &mut ProverScratchPad<C>,
+    transcript: &mut T,
+    layer_durations: &mut Vec<Duration>,
 ) -> (C::Field, Vec<C::ChallengeField>) {
     let timer = start_timer!(|| "gkr^2 prove");
     let layer_num = circuit.layers.len();
@@ -24,7 +36,11 @@ pub fn gkr_square_prove<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     let claimed_v = C::eval_circuit_vals_at_challenge(circuit_output, &rz0, &mut sp.hg_evals);
 
     for i in (0..layer_num).rev() {
+        #[cfg(feature = "telemetry")]
+        let _span = tracing::debug_span!("sumcheck_layer", layer = i).entered();
+        let layer_start = Instant::now();
         rz0 = sumcheck_prove_gkr_square_layer(&circuit.layers[i], &rz0, transcript, sp);
+        layer_durations.push(layer_start.elapsed());
 
         log::trace!("Layer {} proved", i);
         log::trace!("rz0.0: {:?}", rz0[0]);
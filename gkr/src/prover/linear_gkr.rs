@@ -1,15 +1,28 @@
 //! This module implements the whole GKR prover, including the IOP and PCS.
 
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use arith::{Field, FieldSerde, SimdField};
 use ark_std::{end_timer, start_timer};
-use circuit::Circuit;
-use config::{Config, FiatShamirHashType, GKRConfig, GKRScheme, PolynomialCommitmentType};
+use circuit::{Circuit, CircuitLayerStream};
+use config::{
+    Config, ConfigError, FiatShamirHashType, GKRConfig, GKRScheme, MPIConfig,
+    PolynomialCommitmentType,
+};
 use sumcheck::ProverScratchPad;
 use transcript::{
-    BytesHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher, Proof, SHA256hasher,
-    Transcript,
+    BytesHashTranscript, DynHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher,
+    Proof, SHA256hasher, Transcript,
 };
 
-use crate::{gkr_prove, gkr_square_prove, RawCommitment};
+use crate::{
+    gather_public_input, gkr_prove_timed, gkr_square_prove_timed, ChunkedMPIProver,
+    GKRProtocolConstants, OrionCommitment, ProofLayout, ProofSizeBudget, ProofSizeBudgetError,
+    RawCommitment,
+};
 
 #[cfg(feature = "grinding")]
 pub(crate) fn grind<C: GKRConfig, T: Transcript<C::ChallengeField>>(
@@ -43,23 +56,160 @@ pub(crate) fn grind<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     end_timer!(timer);
 }
 
+const SEED_TAG: u8 = 0;
+const DOMAIN_TAG: u8 = 1;
+
+/// Absorb the seed and the domain of the config, if any, without adding them to the proof: the
+/// verifier absorbs the same ones from its config. Each is prefixed by its tag and its length,
+/// for a seed not to be absorbed as a domain, nor a domain as a seed.
+pub(crate) fn absorb_seed_and_domain<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    transcript: &mut T,
+    config: &Config<C>,
+) {
+    let mut absorb = |tag: u8, bytes: &[u8]| {
+        transcript.lock_proof();
+        transcript.append_u8_slice(&[tag]);
+        transcript.append_u8_slice(&(bytes.len() as u64).to_le_bytes());
+        transcript.append_u8_slice(bytes);
+        transcript.unlock_proof();
+    };
+    if let Some(seed) = &config.prover_seed {
+        absorb(SEED_TAG, seed);
+    }
+    if !config.domain.is_empty() {
+        absorb(DOMAIN_TAG, &config.domain);
+    }
+}
+
+/// Absorb the public inputs of all the processes, in the order of their ranks as the root
+/// gathers them, prefixed by the world size and their number, without adding them to the proof:
+/// the verifier absorbs the ones it is given, for the proof to verify on those of its witness
+/// alone. With mpi, only the root has them, whose transcript the others are synced up to.
+pub(crate) fn absorb_public_input<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    transcript: &mut T,
+    public_input: &[C::SimdCircuitField],
+    world_size: usize,
+) {
+    let mut bytes = Vec::with_capacity(16 + public_input.len() * C::SimdCircuitField::SIZE);
+    bytes.extend_from_slice(&(world_size as u64).to_le_bytes());
+    bytes.extend_from_slice(&(public_input.len() as u64).to_le_bytes());
+    public_input
+        .iter()
+        .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+    transcript.lock_proof();
+    transcript.append_u8_slice(&bytes);
+    transcript.unlock_proof();
+}
+
+/// The values of the output layer of all the processes, as the challenge field: the claims of
+/// the proof, see `Verifier::verify`. Each process lays out its outputs lane after lane of the
+/// simd field, and the root gathers them in the order of the ranks; the others have none.
+pub fn gather_claimed_values<C: GKRConfig>(
+    mpi_config: &MPIConfig,
+    output_vals: &[C::SimdCircuitField],
+) -> Vec<C::ChallengeField> {
+    let lanes = output_vals.iter().map(|v| v.unpack()).collect::<Vec<_>>();
+    let local = (0..C::get_field_pack_size())
+        .flat_map(|lane| lanes.iter().map(move |v| C::ChallengeField::from(v[lane])))
+        .collect::<Vec<_>>();
+    let mut gathered = if mpi_config.is_root() {
+        vec![C::ChallengeField::ZERO; local.len() * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&local, &mut gathered);
+    gathered
+}
+
+/// Absorb the claimed values, prefixed by their number, without adding them to the proof, so
+/// that the output challenges come after them.
+pub(crate) fn absorb_claimed_values<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    transcript: &mut T,
+    claimed_v: &[C::ChallengeField],
+) {
+    let mut bytes = Vec::with_capacity(8 + claimed_v.len() * C::ChallengeField::SIZE);
+    bytes.extend_from_slice(&(claimed_v.len() as u64).to_le_bytes());
+    claimed_v
+        .iter()
+        .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+    transcript.lock_proof();
+    transcript.append_u8_slice(&bytes);
+    transcript.unlock_proof();
+}
+
+/// What it took to prove a circuit, see `Prover::prove`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProofStats {
+    pub proof_bytes: usize,
+    pub num_sumcheck_rounds: usize,
+    /// The wall-clock time of the proving, from the commitment to the proof.
+    pub prove_duration: Duration,
+}
+
+/// Where the time of a proof went, see `Prover::prove_with_stats`. The phases are timed once
+/// each, and the sumcheck once per layer, from the output layer to the input layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProverStats {
+    pub proof: ProofStats,
+    /// The evaluation of the circuit on the witness, with the random coefficients.
+    pub witness_eval_duration: Duration,
+    /// The sumcheck of each layer, the first for the output layer.
+    pub sumcheck_layer_durations: Vec<Duration>,
+    /// The commitment to the inputs, and its absorption by the transcript.
+    pub commit_duration: Duration,
+    pub open_duration: Duration,
+    /// The bytes appended to the proof by the sumchecks, i.e., without the commitment.
+    pub transcript_bytes: usize,
+}
+
+impl ProverStats {
+    pub fn sumcheck_duration(&self) -> Duration {
+        self.sumcheck_layer_durations.iter().sum()
+    }
+}
+
+impl std::fmt::Display for ProverStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "witness eval {:.1} ms, sumcheck {:.1} ms over {} layers, commit {:.1} ms, \
+             open {:.1} ms, {} transcript bytes, {} proof bytes",
+            ms(self.witness_eval_duration),
+            ms(self.sumcheck_duration()),
+            self.sumcheck_layer_durations.len(),
+            ms(self.commit_duration),
+            ms(self.open_duration),
+            self.transcript_bytes,
+            self.proof.proof_bytes,
+        )
+    }
+}
+
 #[derive(Default)]
 pub struct Prover<C: GKRConfig> {
     config: Config<C>,
     sp: ProverScratchPad<C>,
+    /// The serialized commitment of the last proof, overwritten by the next one.
+    commitment_bytes: Vec<u8>,
+    /// The threads of `config.num_threads`, built by the first `prepare_mem`, and none for one
+    /// thread.
+    pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl<C: GKRConfig> Prover<C> {
-    pub fn new(config: &Config<C>) -> Self {
+    /// Fails for a polynomial commitment that is not implemented, see
+    /// `Config::check_polynomial_commitment`, or for an mpi world size that is not a power of 2.
+    pub fn new(config: &Config<C>) -> Result<Self, ConfigError> {
         // assert_eq!(config.fs_hash, crate::config::FiatShamirHashType::SHA256);
-        assert_eq!(
-            config.polynomial_commitment_type,
-            PolynomialCommitmentType::Raw
-        );
-        Prover {
+        config.check_polynomial_commitment()?;
+        config.mpi_config.check_world_size()?;
+        Ok(Prover {
             config: config.clone(),
             sp: ProverScratchPad::default(),
-        }
+            commitment_bytes: vec![],
+            pool: None,
+        })
     }
     pub fn prepare_mem(&mut self, c: &Circuit<C>) {
         let max_num_input_var = c
@@ -79,77 +229,276 @@ impl<C: GKRConfig> Prover<C> {
             max_num_output_var,
             self.config.mpi_config.world_size(),
         );
+        self.commitment_bytes = Vec::with_capacity(
+            (C::SimdCircuitField::SIZE << c.log_input_size()) * self.config.mpi_config.world_size(),
+        );
+        if self.pool.is_none() && self.config.num_threads > 1 {
+            // without the threads, e.g., on wasm, the sumcheck runs on the calling thread
+            self.pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(self.config.num_threads)
+                .build()
+                .ok()
+                .map(Arc::new);
+        }
+        self.sp.pool = self.pool.clone();
     }
 
     fn prove_internal<T>(
         &mut self,
         c: &mut Circuit<C>,
         transcript: &mut T,
-    ) -> (C::ChallengeField, Proof)
+        stats: &mut ProverStats,
+    ) -> (Vec<C::ChallengeField>, Proof)
     where
         T: Transcript<C::ChallengeField>,
     {
         let timer = start_timer!(|| "prove");
 
-        // PC commit
-        let commitment =
-            RawCommitment::<C>::mpi_new(&c.layers[0].input_vals, &self.config.mpi_config);
+        absorb_seed_and_domain::<C, T>(transcript, &self.config);
 
-        let mut buffer = vec![];
-        commitment.serialize_into(&mut buffer).unwrap(); // TODO: error propagation
-        transcript.append_u8_slice(&buffer);
+        // PC commit
+        let commit_start = Instant::now();
+        self.commitment_bytes.clear();
+        // only the root has the orion commitment, and opens it
+        let mut orion = None;
+        match self.config.polynomial_commitment_type {
+            PolynomialCommitmentType::Raw => RawCommitment::<C>::mpi_serialize_into(
+                &c.layers[0].input_vals,
+                &self.config.mpi_config,
+                &mut self.commitment_bytes,
+            )
+            .unwrap(), // TODO: error propagation
+            PolynomialCommitmentType::Orion => {
+                orion = OrionCommitment::<C>::mpi_new(
+                    &c.layers[0].input_vals,
+                    &self.config.mpi_config,
+                    self.config.security_bits,
+                );
+                if let Some(commitment) = &orion {
+                    self.commitment_bytes.extend_from_slice(&commitment.root());
+                }
+            }
+            _ => unreachable!("refused by Prover::new"),
+        }
+        transcript.append_u8_slice(&self.commitment_bytes);
+        let public_input = gather_public_input::<C>(&self.config.mpi_config, &c.public_input);
+        absorb_public_input::<C, T>(
+            transcript,
+            &public_input,
+            self.config.mpi_config.world_size(),
+        );
 
         self.config.mpi_config.transcript_sync_up(transcript);
+        stats.commit_duration = commit_start.elapsed();
 
         #[cfg(feature = "grinding")]
         grind::<C, T>(transcript, &self.config);
 
+        let eval_start = Instant::now();
         c.fill_rnd_coefs(transcript);
         c.evaluate();
+        stats.witness_eval_duration = eval_start.elapsed();
 
-        let mut claimed_v = C::ChallengeField::default();
-        let mut _rx = vec![];
-        let mut _ry = None;
-        let mut _rsimd = vec![];
-        let mut _rmpi = vec![];
+        let claimed_v = gather_claimed_values::<C>(
+            &self.config.mpi_config,
+            &c.layers.last().unwrap().output_vals,
+        );
+        absorb_claimed_values::<C, T>(transcript, &claimed_v);
+        self.config.mpi_config.transcript_sync_up(transcript);
 
-        if self.config.gkr_scheme == GKRScheme::GkrSquare {
-            (_, _rx) = gkr_square_prove(c, &mut self.sp, transcript);
+        stats.sumcheck_layer_durations.clear();
+        let (rx, ry, rmpi) = if self.config.gkr_scheme == GKRScheme::GkrSquare {
+            let (_, rx) = gkr_square_prove_timed(
+                c,
+                &mut self.sp,
+                transcript,
+                &mut stats.sumcheck_layer_durations,
+            );
+            (rx, None, vec![])
         } else {
-            (claimed_v, _rx, _ry, _rsimd, _rmpi) =
-                gkr_prove(c, &mut self.sp, transcript, &self.config.mpi_config);
-        }
+            let (_, rx, ry, _rsimd, rmpi) = gkr_prove_timed(
+                c,
+                &mut self.sp,
+                transcript,
+                &self.config.mpi_config,
+                &mut stats.sumcheck_layer_durations,
+            );
+            (rx, ry, rmpi)
+        };
 
         // open
+        let open_start = Instant::now();
         match self.config.polynomial_commitment_type {
             PolynomialCommitmentType::Raw => {
                 // no need to update transcript
             }
-            _ => todo!(),
+            PolynomialCommitmentType::Orion => {
+                // at vx then vy, of the inputs of the process then of the processes
+                if let Some(commitment) = &orion {
+                    for r in std::iter::once(&rx).chain(&ry) {
+                        commitment.open(&[&r[..], &rmpi[..]].concat(), transcript);
+                    }
+                }
+            }
+            _ => unreachable!("refused by Prover::new"),
         }
+        stats.open_duration = open_start.elapsed();
 
         end_timer!(timer);
 
-        (claimed_v, transcript.finalize_and_get_proof())
+        let proof = transcript.finalize_and_get_proof();
+        stats.transcript_bytes = proof
+            .bytes
+            .len()
+            .saturating_sub(self.commitment_bytes.len());
+        if cfg!(debug_assertions)
+            && self.config.gkr_scheme == GKRScheme::Vanilla
+            && self.config.polynomial_commitment_type == PolynomialCommitmentType::Raw
+            && self.config.mpi_config.is_root()
+        {
+            GKRProtocolConstants::compute(c, &self.config).assert_matches(c, &self.config, &proof);
+        }
+
+        (claimed_v, proof)
+    }
+
+    /// Prove the circuit, with the stats of the proof.
+    pub fn prove(&mut self, c: &mut Circuit<C>) -> (Vec<C::ChallengeField>, Proof, ProofStats) {
+        let (claimed_v, proof, stats) = self.prove_with_stats(c);
+        (claimed_v, proof, stats.proof)
+    }
+
+    /// As `prove`, with the time taken by each phase of the proof.
+    #[cfg_attr(
+        feature = "telemetry",
+        tracing::instrument(name = "prove", skip_all, fields(num_layers = c.layers.len()))
+    )]
+    pub fn prove_with_stats(
+        &mut self,
+        c: &mut Circuit<C>,
+    ) -> (Vec<C::ChallengeField>, Proof, ProverStats) {
+        let start = Instant::now();
+        let mut stats = ProverStats::default();
+        let (claimed_v, proof) = self.prove_timed(c, &mut stats);
+        stats.proof = ProofStats {
+            proof_bytes: proof.bytes.len(),
+            num_sumcheck_rounds: GKRProtocolConstants::compute(c, &self.config).total_rounds,
+            prove_duration: start.elapsed(),
+        };
+        (claimed_v, proof, stats)
     }
 
-    pub fn prove(&mut self, c: &mut Circuit<C>) -> (C::ChallengeField, Proof) {
+    /// As `prove`, without the stats. The transcript is hashed by the hash function of the
+    /// config if any, see `Config::with_hash_function`, else by `C::FIAT_SHAMIR_HASH`.
+    pub fn prove_silent(&mut self, c: &mut Circuit<C>) -> (Vec<C::ChallengeField>, Proof) {
+        self.prove_timed(c, &mut ProverStats::default())
+    }
+
+    fn prove_timed(
+        &mut self,
+        c: &mut Circuit<C>,
+        stats: &mut ProverStats,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        if let Some(hash) = self.config.hash_function {
+            let mut transcript = DynHashTranscript::<C::ChallengeField>::with_hasher(
+                hash.hasher().expect("no transcript for the hash function"),
+            );
+            return self.prove_internal(c, &mut transcript, stats);
+        }
         match C::FIAT_SHAMIR_HASH {
             FiatShamirHashType::Keccak256 => {
                 let mut transcript =
                     BytesHashTranscript::<C::ChallengeField, Keccak256hasher>::new();
-                self.prove_internal(c, &mut transcript)
+                self.prove_internal(c, &mut transcript, stats)
             }
             FiatShamirHashType::SHA256 => {
                 let mut transcript = BytesHashTranscript::<C::ChallengeField, SHA256hasher>::new();
-                self.prove_internal(c, &mut transcript)
+                self.prove_internal(c, &mut transcript, stats)
             }
             FiatShamirHashType::MIMC5 => {
                 let mut transcript: FieldHashTranscript<<C as GKRConfig>::ChallengeField, _> =
                     FieldHashTranscript::<C::ChallengeField, MIMCHasher<C::ChallengeField>>::new();
-                self.prove_internal(c, &mut transcript)
+                self.prove_internal(c, &mut transcript, stats)
             }
             _ => unreachable!(),
         }
     }
+
+    /// Prove with the transcript seeded, whatever the seed of the config. The proof verifies
+    /// with the config `with_prover_seed(seed)`.
+    pub fn prove_with_seed(
+        &mut self,
+        c: &mut Circuit<C>,
+        seed: [u8; 32],
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        let config_seed = self.config.prover_seed.replace(seed);
+        let result = self.prove_silent(c);
+        self.config.prover_seed = config_seed;
+        result
+    }
+
+    /// Bind the next proofs to the domain, in place of the domain of the config, see
+    /// `Config::with_domain`.
+    pub fn set_domain(&mut self, domain: &[u8]) {
+        self.config.domain.clear();
+        self.config.domain.extend_from_slice(domain);
+    }
+
+    /// Prove unless the proof would be over the budget, in which case nothing is proven.
+    /// Only for vanilla GKR, the size of the proofs being given by their `ProofLayout`: a config
+    /// of GKR^2 fails with `ProofSizeBudgetError::UnsupportedScheme`.
+    pub fn prove_with_budget(
+        &mut self,
+        c: &mut Circuit<C>,
+        budget: &ProofSizeBudget,
+    ) -> Result<(Vec<C::ChallengeField>, Proof), ProofSizeBudgetError> {
+        if self.config.gkr_scheme != GKRScheme::Vanilla {
+            return Err(ProofSizeBudgetError::UnsupportedScheme(
+                self.config.gkr_scheme.clone(),
+            ));
+        }
+        budget.check(ProofLayout::new(c, &self.config).proof_size())?;
+        Ok(self.prove_silent(c))
+    }
+
+    /// Prove the circuit of the stream on the packed inputs, see `Circuit::parse_witness_bytes`,
+    /// with a single batch of flattened layers in memory at a time, see `ChunkedMPIProver`. The
+    /// proof is the proof of `prove_silent` for the flattened circuit. The scratch pad of the
+    /// prover is not used, the stream allocating its own for its largest layer.
+    pub fn prove_streaming(
+        &self,
+        layers: CircuitLayerStream<C>,
+        input_vals: Vec<C::SimdCircuitField>,
+        public_input: Vec<C::SimdCircuitField>,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        ChunkedMPIProver::<C>::prove_stream(layers, input_vals, public_input, &self.config)
+    }
+
+    /// Prove the circuit on each of the witnesses in turn, see `Circuit::load_witness_bytes`.
+    /// The scratch pad is allocated once for the whole batch, and each witness overwrites the
+    /// input values and the public input left by the previous one.
+    pub fn prove_batch(
+        &mut self,
+        c: &mut Circuit<C>,
+        witnesses: &[Vec<u8>],
+    ) -> Vec<(Vec<C::ChallengeField>, Proof)> {
+        self.prepare_mem(c);
+        witnesses
+            .iter()
+            .map(|witness| {
+                c.load_witness_bytes(witness, true);
+                self.prove_silent(c)
+            })
+            .collect()
+    }
+
+    /// Prove with the given transcript rather than the one of `C::FIAT_SHAMIR_HASH`, e.g., to
+    /// record the calls made to it with an `AuditedTranscript`.
+    pub fn prove_with_transcript<T: Transcript<C::ChallengeField>>(
+        &mut self,
+        c: &mut Circuit<C>,
+        transcript: &mut T,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        self.prove_internal(c, transcript, &mut ProverStats::default())
+    }
 }
@@ -0,0 +1,102 @@
+//! Recovering the private witness from a proof.
+//!
+//! Whether this is possible depends on the polynomial commitment scheme only:
+//! - with the raw commitment, the proof starts with the whole input layer of every process, so
+//!   whoever holds the proof can read the witness. Raw proofs are only zero-knowledge towards
+//!   parties that never see them, i.e., they fit designated-verifier settings where the proof is
+//!   sent over a private channel to the verifier, who is trusted with the witness.
+//! - with a hiding commitment (KZG, Orion, FRI), the proof only carries a commitment and
+//!   openings at random points, a standard GKR proof, from which the witness cannot be recovered.
+//!
+//! The reconstructed witness is checked against the circuit: with the public input, it must
+//! evaluate to an all-zero output, as any witness the prover accepts.
+
+use std::io::Cursor;
+
+use arith::{Field, FieldSerde};
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme, MPIConfig, PolynomialCommitmentType};
+use thiserror::Error;
+use transcript::Proof;
+
+#[derive(Debug, Error)]
+pub enum ReconstructionError {
+    #[error("the {0:?} polynomial commitment does not reveal the witness")]
+    HidingCommitment(PolynomialCommitmentType),
+
+    #[error("the proof is too short to contain the witness")]
+    TruncatedProof,
+
+    #[error("the witness in the proof is not a valid encoding")]
+    MalformedWitness,
+
+    #[error("the witness in the proof does not satisfy the circuit")]
+    UnsatisfiedCircuit,
+}
+
+pub struct WitnessReconstructor<C: GKRConfig> {
+    _marker: std::marker::PhantomData<C>,
+}
+
+impl<C: GKRConfig> WitnessReconstructor<C> {
+    /// Reconstruct the witness of a proof generated by a single process with the raw commitment.
+    pub fn reconstruct(
+        circuit: &Circuit<C>,
+        proof: &Proof,
+        public_input: &[C::SimdCircuitField],
+    ) -> Result<Vec<C::SimdCircuitField>, ReconstructionError> {
+        let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+        Self::reconstruct_with_config(&config, circuit, proof, public_input)
+    }
+
+    /// Reconstruct the witness of a proof generated with `config`. For a proof generated by
+    /// several processes, the local witnesses are concatenated in rank order, and the public
+    /// input is the gathered one, as given to the verifier.
+    pub fn reconstruct_with_config(
+        config: &Config<C>,
+        circuit: &Circuit<C>,
+        proof: &Proof,
+        public_input: &[C::SimdCircuitField],
+    ) -> Result<Vec<C::SimdCircuitField>, ReconstructionError> {
+        if config.polynomial_commitment_type != PolynomialCommitmentType::Raw {
+            return Err(ReconstructionError::HidingCommitment(
+                config.polynomial_commitment_type.clone(),
+            ));
+        }
+
+        let world_size = config.mpi_config.world_size();
+        let local_size = 1 << circuit.layers[0].input_var_num;
+        if proof.bytes.len() < local_size * world_size * C::SimdCircuitField::SERIALIZED_SIZE {
+            return Err(ReconstructionError::TruncatedProof);
+        }
+
+        let mut cursor = Cursor::new(&proof.bytes);
+        let witness = (0..local_size * world_size)
+            .map(|_| C::SimdCircuitField::deserialize_from(&mut cursor))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ReconstructionError::MalformedWitness)?;
+
+        let local_public_size = public_input.len() / world_size;
+        let mut circuit = circuit.clone();
+        for rank in 0..world_size {
+            circuit.layers[0].input_vals =
+                witness[rank * local_size..(rank + 1) * local_size].to_vec();
+            circuit.public_input =
+                public_input[rank * local_public_size..(rank + 1) * local_public_size].to_vec();
+            circuit.evaluate();
+
+            if !circuit
+                .layers
+                .last()
+                .unwrap()
+                .output_vals
+                .iter()
+                .all(|v| v.is_zero())
+            {
+                return Err(ReconstructionError::UnsatisfiedCircuit);
+            }
+        }
+
+        Ok(witness)
+    }
+}
@@ -0,0 +1,207 @@
+//! An interactive debugger to explore how the witness flows through a circuit.
+//!
+//! Commands:
+//! - `set <wire_id> <value>`: set an input wire, the value is broadcast to all the simd lanes;
+//! - `eval`: evaluate the circuit and print the output layer;
+//! - `print <layer>:<mul|add|const|uni>:<index>`: print the inputs and the output of a gate
+//!   in the last evaluation;
+//! - `prove`: prove the circuit on the current witness with a single process;
+//! - `help`, `quit`.
+
+use std::collections::HashMap;
+
+use circuit::{Circuit, CircuitObserver, GateKind, GateRef};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+use rustyline::{error::ReadlineError, DefaultEditor};
+use thiserror::Error;
+
+use crate::Prover;
+
+#[derive(Debug, Error)]
+pub enum ReplError {
+    #[error("unknown command: {0}, type help for the list of commands")]
+    UnknownCommand(String),
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("wire {0} is not an input wire")]
+    WireOutOfRange(usize),
+
+    #[error("gate {0:?} was not evaluated, run eval first")]
+    GateNotEvaluated(GateRef),
+}
+
+const HELP: &str = "\
+set <wire_id> <value>     set an input wire
+eval                      evaluate the circuit and print the output layer
+print <layer>:<kind>:<i>  print a gate of the last evaluation, kind is mul, add, const or uni
+prove                     prove the circuit on the current witness
+quit                      exit";
+
+/// Observer recording the inputs and the output of every gate.
+struct RecordingObserver<C: GKRConfig> {
+    gates: HashMap<GateRef, (Vec<C::SimdCircuitField>, C::SimdCircuitField)>,
+}
+
+impl<C: GKRConfig> CircuitObserver<C> for RecordingObserver<C> {
+    fn on_gate_evaluated(
+        &mut self,
+        gate: GateRef,
+        inputs: &[C::SimdCircuitField],
+        output: C::SimdCircuitField,
+    ) {
+        self.gates.insert(gate, (inputs.to_vec(), output));
+    }
+}
+
+pub struct LiveWitnessDebugger<C: GKRConfig> {
+    circuit: Circuit<C>,
+    witness: Vec<C::SimdCircuitField>,
+    evaluated_gates: HashMap<GateRef, (Vec<C::SimdCircuitField>, C::SimdCircuitField)>,
+}
+
+impl<C: GKRConfig> LiveWitnessDebugger<C> {
+    /// The witness starts at zero.
+    pub fn new(mut circuit: Circuit<C>) -> Self {
+        if !circuit.rnd_coefs_identified {
+            circuit.identify_rnd_coefs();
+        }
+        let witness = vec![C::SimdCircuitField::default(); 1 << circuit.log_input_size()];
+        Self {
+            circuit,
+            witness,
+            evaluated_gates: HashMap::new(),
+        }
+    }
+
+    /// Run the REPL on stdin until `quit` or end of input.
+    pub fn start(circuit: Circuit<C>) -> rustyline::Result<()> {
+        let mut debugger = Self::new(circuit);
+        let mut editor = DefaultEditor::new()?;
+
+        loop {
+            let line = match editor.readline("> ") {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(e),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            editor.add_history_entry(line)?;
+            if line == "quit" || line == "exit" {
+                break;
+            }
+
+            match debugger.execute(line) {
+                Ok(output) => println!("{}", output),
+                Err(e) => println!("error: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute a command, and return what it prints.
+    pub fn execute(&mut self, line: &str) -> Result<String, ReplError> {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        match tokens.as_slice() {
+            ["set", wire_id, value] => {
+                let wire_id = parse_number::<usize>(wire_id)?;
+                let value = parse_number::<u32>(value)?;
+                self.set(wire_id, C::CircuitField::from(value))?;
+                Ok(format!("in[{}] = {}", wire_id, value))
+            }
+            ["eval"] => Ok(Self::format_values("out", &self.eval())),
+            ["print", gate_ref] => self.print_gate(parse_gate_ref(gate_ref)?),
+            ["prove"] => {
+                let (claimed_v, proof) = self.prove();
+                Ok(format!(
+                    "claimed values {:?}, proof size {} bytes",
+                    claimed_v,
+                    proof.bytes.len()
+                ))
+            }
+            ["help"] => Ok(HELP.to_string()),
+            _ => Err(ReplError::UnknownCommand(line.to_string())),
+        }
+    }
+
+    pub fn set(&mut self, wire_id: usize, value: C::CircuitField) -> Result<(), ReplError> {
+        let wire = self
+            .witness
+            .get_mut(wire_id)
+            .ok_or(ReplError::WireOutOfRange(wire_id))?;
+        *wire = C::circuit_field_to_simd_circuit_field(&value);
+        Ok(())
+    }
+
+    /// Evaluate the circuit on the current witness, and return the output layer.
+    pub fn eval(&mut self) -> Vec<C::SimdCircuitField> {
+        let mut observer = RecordingObserver::<C> {
+            gates: HashMap::new(),
+        };
+        let output = self
+            .circuit
+            .evaluate_with_observer(&self.witness, &mut observer);
+        self.evaluated_gates = observer.gates;
+        output
+    }
+
+    pub fn prove(&mut self) -> (Vec<C::ChallengeField>, transcript::Proof) {
+        let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+        let mut circuit = self.circuit.clone();
+        circuit.layers[0].input_vals.clone_from(&self.witness);
+
+        let mut prover = Prover::new(&config).expect("the raw commitment of a single process");
+        prover.prepare_mem(&circuit);
+        prover.prove_silent(&mut circuit)
+    }
+
+    fn print_gate(&self, gate: GateRef) -> Result<String, ReplError> {
+        let (inputs, output) = self
+            .evaluated_gates
+            .get(&gate)
+            .ok_or(ReplError::GateNotEvaluated(gate))?;
+        Ok(format!(
+            "{}:{:?}:{} inputs {:?} -> {:?}",
+            gate.layer, gate.kind, gate.index, inputs, output
+        ))
+    }
+
+    fn format_values(name: &str, values: &[C::SimdCircuitField]) -> String {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| format!("{}[{}] = {:?}", name, i, v))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, ReplError> {
+    s.parse()
+        .map_err(|_| ReplError::InvalidArgument(s.to_string()))
+}
+
+fn parse_gate_ref(s: &str) -> Result<GateRef, ReplError> {
+    let invalid = || ReplError::InvalidArgument(s.to_string());
+
+    let parts = s.split(':').collect::<Vec<_>>();
+    let [layer, kind, index] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    let kind = match *kind {
+        "mul" => GateKind::Mul,
+        "add" => GateKind::Add,
+        "const" => GateKind::Const,
+        "uni" => GateKind::Uni,
+        _ => return Err(invalid()),
+    };
+    Ok(GateRef {
+        layer: parse_number(layer)?,
+        kind,
+        index: parse_number(index)?,
+    })
+}
@@ -0,0 +1,204 @@
+//! Run-length encoding of the sumcheck messages of a proof.
+//!
+//! When the layers of a circuit are alike and its values are too, e.g., zero padding, the round
+//! polynomials of consecutive sumcheck rounds can be equal. A compressed proof stores a run of
+//! equal consecutive round messages once, with its length, and the other parts as is. It is
+//! self-contained: decompressing it does not take the circuit, but the size it may decompress
+//! to, e.g., that of the proofs of the circuit.
+
+use std::io::{Read, Write};
+
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use circuit::Circuit;
+use config::{Config, GKRConfig};
+use thiserror::Error;
+use transcript::Proof;
+
+use crate::{ProofLayout, ProofSegment};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompressedRun {
+    /// Bytes of the proof, as is.
+    Raw(Vec<u8>),
+    /// `count` consecutive copies of the round message.
+    Repeated { message: Vec<u8>, count: usize },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompressedProof {
+    pub runs: Vec<CompressedRun>,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DecompressError {
+    #[error("the compressed proof decompresses to more than {max_size} bytes")]
+    TooLarge { max_size: usize },
+}
+
+pub struct SumcheckRoundCompressor;
+
+impl SumcheckRoundCompressor {
+    /// Panics if the proof does not have the size of a proof of the circuit.
+    pub fn compress<C: GKRConfig>(
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+        proof: &Proof,
+    ) -> CompressedProof {
+        let layout = ProofLayout::new(circuit, config);
+        assert_eq!(
+            proof.bytes.len(),
+            layout.proof_size(),
+            "the proof is not a proof of the circuit"
+        );
+
+        let mut runs = vec![];
+        let mut offset = 0;
+        for segment in &layout.segments {
+            let size = layout.segment_size(segment);
+            let bytes = &proof.bytes[offset..offset + size];
+            offset += size;
+
+            match (segment, runs.last_mut()) {
+                (
+                    ProofSegment::SumcheckRound { .. },
+                    Some(CompressedRun::Repeated { message, count }),
+                ) if message.as_slice() == bytes => *count += 1,
+                (ProofSegment::SumcheckRound { .. }, _) => runs.push(CompressedRun::Repeated {
+                    message: bytes.to_vec(),
+                    count: 1,
+                }),
+                (_, Some(CompressedRun::Raw(raw))) => raw.extend_from_slice(bytes),
+                _ => runs.push(CompressedRun::Raw(bytes.to_vec())),
+            }
+        }
+
+        // a message seen once takes less room raw
+        let mut merged: Vec<CompressedRun> = vec![];
+        for run in runs {
+            let run = match run {
+                CompressedRun::Repeated { message, count: 1 } => CompressedRun::Raw(message),
+                run => run,
+            };
+            match (merged.last_mut(), run) {
+                (Some(CompressedRun::Raw(raw)), CompressedRun::Raw(bytes)) => {
+                    raw.extend_from_slice(&bytes)
+                }
+                (_, run) => merged.push(run),
+            }
+        }
+        CompressedProof { runs: merged }
+    }
+}
+
+impl CompressedProof {
+    /// The proof, if of `max_size` bytes at most, e.g., the `ProofLayout::proof_size` of the
+    /// circuit, checked before allocating: the counts of the runs are of the compressed bytes.
+    pub fn decompress(&self, max_size: usize) -> Result<Proof, DecompressError> {
+        let size = self
+            .decompressed_size()
+            .filter(|size| *size <= max_size)
+            .ok_or(DecompressError::TooLarge { max_size })?;
+        let mut bytes = Vec::with_capacity(size);
+        for run in &self.runs {
+            match run {
+                CompressedRun::Raw(raw) => bytes.extend_from_slice(raw),
+                CompressedRun::Repeated { message, count } => {
+                    (0..*count).for_each(|_| bytes.extend_from_slice(message))
+                }
+            }
+        }
+        Ok(Proof { bytes })
+    }
+
+    /// As `decompress`, of the size of the proofs of the circuit.
+    pub fn decompress_for<C: GKRConfig>(
+        &self,
+        circuit: &Circuit<C>,
+        config: &Config<C>,
+    ) -> Result<Proof, DecompressError> {
+        self.decompress(ProofLayout::new(circuit, config).proof_size())
+    }
+
+    /// Size in bytes of the decompressed proof, if it fits a `usize`.
+    pub fn decompressed_size(&self) -> Option<usize> {
+        self.runs.iter().try_fold(0usize, |size, run| match run {
+            CompressedRun::Raw(raw) => size.checked_add(raw.len()),
+            CompressedRun::Repeated { message, count } => {
+                size.checked_add(message.len().checked_mul(*count)?)
+            }
+        })
+    }
+
+    /// Size in bytes of the serialized compressed proof.
+    pub fn size(&self) -> usize {
+        self.runs
+            .iter()
+            .map(|run| match run {
+                CompressedRun::Raw(raw) => 1 + 8 + raw.len(),
+                CompressedRun::Repeated { message, .. } => 1 + 8 + 8 + message.len(),
+            })
+            .sum::<usize>()
+            + 8
+    }
+}
+
+impl FieldSerde for CompressedProof {
+    const SERIALIZED_SIZE: usize = unimplemented!();
+
+    /// The number of runs, then each run: tag 0 for raw bytes, followed by their length and the
+    /// bytes, or tag 1 for a repeated message, followed by the count, the length and the message.
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.runs.len().serialize_into(&mut writer)?;
+        for run in &self.runs {
+            match run {
+                CompressedRun::Raw(raw) => {
+                    writer.write_all(&[0])?;
+                    raw.len().serialize_into(&mut writer)?;
+                    writer.write_all(raw)?;
+                }
+                CompressedRun::Repeated { message, count } => {
+                    writer.write_all(&[1])?;
+                    count.serialize_into(&mut writer)?;
+                    message.len().serialize_into(&mut writer)?;
+                    writer.write_all(message)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The runs are of the bytes read, but not their counts, see `decompress`.
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let read_bytes = |reader: &mut R| -> FieldSerdeResult<Vec<u8>> {
+            let len = usize::deserialize_from(&mut *reader)?;
+            let mut bytes = vec![];
+            Read::take(&mut *reader, len as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != len {
+                return Err(FieldSerdeError::DeserializeError);
+            }
+            Ok(bytes)
+        };
+
+        let run_num = usize::deserialize_from(&mut reader)?;
+        let mut runs = vec![];
+        for _ in 0..run_num {
+            let run = match u8::deserialize_from(&mut reader)? {
+                0 => CompressedRun::Raw(read_bytes(&mut reader)?),
+                1 => {
+                    let count = usize::deserialize_from(&mut reader)?;
+                    CompressedRun::Repeated {
+                        message: read_bytes(&mut reader)?,
+                        count,
+                    }
+                }
+                _ => return Err(FieldSerdeError::DeserializeError),
+            };
+            runs.push(run);
+        }
+        Ok(Self { runs })
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("not implemented for CompressedProof")
+    }
+}
@@ -0,0 +1,42 @@
+//! Choosing the GKR scheme of a circuit, rather than leaving it to the user.
+//!
+//! The schemes are those of `GKRScheme`. GKR^2 proves a layer in a single sumcheck over its
+//! input variables, instead of two for vanilla GKR, but only supports the univariate gates:
+//! it is chosen whenever the circuit has no other gate.
+
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+pub struct SchemeSelector;
+
+impl SchemeSelector {
+    pub fn select<C: GKRConfig>(circuit: &Circuit<C>) -> GKRScheme {
+        let gkr_square_layer = |layer: &CircuitLayer<C>| {
+            layer.mul.is_empty()
+                && layer.add.is_empty()
+                && layer.const_.is_empty()
+                // pow5 and identity, see `SumcheckGkrSquareHelper`
+                && layer
+                    .uni
+                    .iter()
+                    .all(|g| g.gate_type == 12345 || g.gate_type == 12346)
+        };
+        if !circuit.layers.is_empty() && circuit.layers.iter().all(gkr_square_layer) {
+            GKRScheme::GkrSquare
+        } else {
+            GKRScheme::Vanilla
+        }
+    }
+}
+
+/// `Config::auto_select`, the config crate knowing nothing of circuits.
+pub trait AutoSelectConfig<C: GKRConfig> {
+    /// The default config with the scheme of `SchemeSelector::select`.
+    fn auto_select(circuit: &Circuit<C>, mpi_config: MPIConfig) -> Self;
+}
+
+impl<C: GKRConfig> AutoSelectConfig<C> for Config<C> {
+    fn auto_select(circuit: &Circuit<C>, mpi_config: MPIConfig) -> Self {
+        Config::new(SchemeSelector::select(circuit), mpi_config)
+    }
+}
@@ -0,0 +1,127 @@
+//! Estimating the security level of a configuration, for a given circuit.
+//!
+//! Each sumcheck round over a univariate polynomial of degree `d` fails to catch a cheating
+//! prover with probability at most `d / |F|` (Schwartz-Zippel), `F` being the challenge field.
+//! The soundness error of GKR is bounded by the sum over all the rounds of all the layers, plus
+//! one `1 / |F|` per random combination of two claims and per random gate coefficient, plus
+//! the initial check of the output over the random point `(r_z, r_simd, r_mpi)`. So it grows
+//! with the depth of the circuit and the logarithm of its width.
+
+use arith::{ExtensionField, FieldForECC};
+use circuit::{Circuit, CoefType};
+use config::{Config, FiatShamirHashType, GKRConfig, GKRScheme};
+use serde::{Deserialize, Serialize};
+
+use crate::{ProofLayout, ProofSegment};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SecurityEstimate {
+    /// `-log2` of the soundness error of the interactive protocol.
+    pub soundness_bits: f64,
+    /// Security of the non-interactive protocol against an adversary pulling out a proof for a
+    /// false statement: the round-by-round soundness under Fiat-Shamir, capped by the collision
+    /// resistance of the transcript hash.
+    pub knowledge_soundness_bits: f64,
+    /// `None`: the proofs are not zero knowledge, the raw commitment reveals the witness.
+    pub zero_knowledge_bits: Option<f64>,
+}
+
+impl SecurityEstimate {
+    #[inline]
+    pub fn is_secure(&self, target_bits: f64) -> bool {
+        self.soundness_bits >= target_bits && self.knowledge_soundness_bits >= target_bits
+    }
+}
+
+pub struct ProtocolSecurityEstimator;
+
+impl ProtocolSecurityEstimator {
+    pub fn estimate<C: GKRConfig>(config: &Config<C>, circuit: &Circuit<C>) -> SecurityEstimate {
+        let field_bits = Self::challenge_field_bits::<C>();
+        let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+        let mpi_var_num = config.mpi_config.world_size().trailing_zeros() as usize;
+
+        // the degrees of the sumcheck rounds, and the number of other degree one checks
+        let (degrees, linear_checks) = match config.gkr_scheme {
+            GKRScheme::Vanilla => {
+                let layout = ProofLayout::new(circuit, config);
+                let degrees = layout
+                    .segments
+                    .iter()
+                    .filter_map(|s| match s {
+                        ProofSegment::SumcheckRound { degree } => Some(*degree),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let n_alphas = circuit
+                    .layers
+                    .iter()
+                    .filter(|layer| !layer.structure_info.max_degree_one)
+                    .count();
+                (degrees, n_alphas)
+            }
+            // one phase of degree 6 per layer, over the input variables
+            GKRScheme::GkrSquare => {
+                let degrees = circuit
+                    .layers
+                    .iter()
+                    .flat_map(|layer| std::iter::repeat(6).take(layer.input_var_num))
+                    .collect();
+                (degrees, 0)
+            }
+        };
+        let n_rnd_coefs = circuit
+            .layers
+            .iter()
+            .flat_map(|layer| {
+                let mul = layer.mul.iter().map(|g| &g.coef_type);
+                let add = layer.add.iter().map(|g| &g.coef_type);
+                let cst = layer.const_.iter().map(|g| &g.coef_type);
+                let uni = layer.uni.iter().map(|g| &g.coef_type);
+                mul.chain(add).chain(cst).chain(uni)
+            })
+            .filter(|coef_type| **coef_type == CoefType::Random)
+            .count();
+        let output_vars =
+            circuit.layers.last().unwrap().output_var_num + simd_var_num + mpi_var_num;
+
+        let error_numerator =
+            degrees.iter().sum::<usize>() + linear_checks + n_rnd_coefs + output_vars;
+        let soundness_bits = field_bits - (error_numerator.max(1) as f64).log2();
+
+        let max_degree = degrees.iter().copied().max().unwrap_or(1);
+        #[allow(unused_mut)]
+        let mut round_by_round_bits = field_bits - (max_degree as f64).log2();
+        #[cfg(feature = "grinding")]
+        {
+            round_by_round_bits += config.grinding_bits as f64;
+        }
+        let knowledge_soundness_bits = round_by_round_bits.min(Self::hash_bits::<C>(field_bits));
+
+        SecurityEstimate {
+            soundness_bits,
+            knowledge_soundness_bits,
+            zero_knowledge_bits: None,
+        }
+    }
+
+    /// `log2 |F|` for the challenge field, an extension of degree `DEGREE` of the circuit field.
+    pub(crate) fn challenge_field_bits<C: GKRConfig>() -> f64 {
+        let (high, low) = C::CircuitField::modulus().into_words();
+        let modulus = high as f64 * 2f64.powi(128) + low as f64;
+        modulus.log2() * <C::ChallengeField as ExtensionField>::DEGREE as f64
+    }
+
+    /// Collision resistance of the transcript hash: half of its output size in bits.
+    fn hash_bits<C: GKRConfig>(field_bits: f64) -> f64 {
+        match C::FIAT_SHAMIR_HASH {
+            FiatShamirHashType::SHA256
+            | FiatShamirHashType::Keccak256
+            | FiatShamirHashType::Blake3 => 128.0,
+            // algebraic hashes output one challenge field element
+            FiatShamirHashType::Poseidon
+            | FiatShamirHashType::Animoe
+            | FiatShamirHashType::MIMC5 => field_bits / 2.0,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Distributed tracing of the serve mode requests.
+//!
+//! Each request is a span, exported to a Jaeger agent. A request with a `traceparent` header,
+//! see the W3C trace context, continues the trace of the caller; otherwise it starts a new
+//! trace.
+//!
+//! With the `telemetry` feature, the `tracing` spans of the prover, the verifier and the
+//! circuit loading are exported too, as children of the span of the request they serve.
+
+use std::time::Instant;
+
+use config::FieldType;
+use opentelemetry::{
+    propagation::{Extractor, TextMapPropagator},
+    trace::{Span, TraceError, Tracer},
+    KeyValue,
+};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace as sdktrace};
+use warp::http::HeaderMap;
+
+use crate::encode_hash;
+
+pub struct TelemetryMiddleware {
+    tracer: sdktrace::Tracer,
+    propagator: TraceContextPropagator,
+}
+
+impl TelemetryMiddleware {
+    /// Export the spans to the Jaeger agent at `jaeger_endpoint`, e.g., `127.0.0.1:6831`,
+    /// as the service `service_name`.
+    pub fn new(jaeger_endpoint: &str, service_name: &str) -> Result<Self, TraceError> {
+        let tracer = opentelemetry_jaeger::new_agent_pipeline()
+            .with_endpoint(jaeger_endpoint)
+            .with_service_name(service_name)
+            .install_simple()?;
+        Ok(Self {
+            tracer,
+            propagator: TraceContextPropagator::new(),
+        })
+    }
+
+    /// Export the `tracing` spans along with the request spans, installing the global
+    /// subscriber.
+    #[cfg(feature = "telemetry")]
+    pub fn install_tracing_subscriber(&self) -> Result<(), tracing_subscriber::util::TryInitError> {
+        use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(self.tracer.clone()))
+            .try_init()
+    }
+
+    /// A span named `name`, child of the span of the `traceparent` header if any.
+    pub fn start_span(&self, name: &'static str, headers: &HeaderMap) -> RequestSpan {
+        let parent = self.propagator.extract(&HeaderExtractor(headers));
+        RequestSpan {
+            span: Some(self.tracer.start_with_context(name, &parent)),
+            start: Instant::now(),
+        }
+    }
+}
+
+/// The span of a request, ended with its `duration_ms` when dropped.
+pub struct RequestSpan {
+    span: Option<sdktrace::Span>,
+    start: Instant,
+}
+
+impl RequestSpan {
+    /// The span of the request if the telemetry is enabled, otherwise a span recording
+    /// nothing.
+    pub fn start(
+        telemetry: Option<&TelemetryMiddleware>,
+        name: &'static str,
+        headers: &HeaderMap,
+    ) -> Self {
+        match telemetry {
+            Some(telemetry) => telemetry.start_span(name, headers),
+            None => Self {
+                span: None,
+                start: Instant::now(),
+            },
+        }
+    }
+
+    pub fn record_circuit(&mut self, field_type: &FieldType, circuit_hash: &[u8; 32]) {
+        self.set_attribute(KeyValue::new("field_type", format!("{:?}", field_type)));
+        self.set_attribute(KeyValue::new("circuit_hash", encode_hash(circuit_hash)));
+    }
+
+    pub fn record_proof_size(&mut self, proof_size_bytes: usize) {
+        self.set_attribute(KeyValue::new("proof_size_bytes", proof_size_bytes as i64));
+    }
+
+    #[inline]
+    pub fn span(&self) -> Option<&sdktrace::Span> {
+        self.span.as_ref()
+    }
+
+    fn set_attribute(&mut self, attribute: KeyValue) {
+        if let Some(span) = &mut self.span {
+            span.set_attribute(attribute);
+        }
+    }
+}
+
+impl Drop for RequestSpan {
+    fn drop(&mut self) {
+        let duration_ms = self.start.elapsed().as_millis() as i64;
+        self.set_attribute(KeyValue::new("duration_ms", duration_ms));
+        if let Some(span) = &mut self.span {
+            span.end();
+        }
+    }
+}
+
+/// The `tracing` span of the request `name`, child of the span of the `traceparent` header if
+/// any. The spans entered within it, e.g., of `Prover::prove`, are its children.
+#[cfg(feature = "telemetry")]
+pub fn request_tracing_span(name: &'static str, headers: &HeaderMap) -> tracing::Span {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let span = tracing::info_span!("request", name);
+    span.set_parent(TraceContextPropagator::new().extract(&HeaderExtractor(headers)));
+    span
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
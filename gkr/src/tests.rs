@@ -1,2 +1,82 @@
+mod annotation;
+mod arithmetic_optimizer;
+mod benchmark;
+mod certified_circuit;
+mod chained_prover;
+mod chunked_prover;
+mod circuit_composer;
+mod circuit_dsl;
+mod circuit_dump;
+mod circuit_library;
+mod circuit_observer;
+mod circuit_optimizer;
+mod circuit_patcher;
+mod circuit_stats;
+mod circuit_validate;
+mod client;
+mod common;
+mod complexity;
+mod continuation;
+mod degree_optimizer;
+mod deployer;
+mod docs;
+mod dynamic_circuit;
+mod encoding;
+mod executor;
+mod expression_simplifier;
+mod extended_witness;
+mod fiat_shamir_detector;
+mod field_proof;
+mod field_serializer;
+mod fingerprint_registry;
+mod fuzz_safe_loader;
 mod gkr_correctness;
+mod interactive_session;
+mod ir;
+mod job_store;
+mod mapped_circuit;
+mod maxflow;
+#[cfg(feature = "mpi")]
+mod mpi_group_prover;
+#[cfg(feature = "mpi")]
+mod mpi_health_check;
+mod mpi_orchestrator;
+mod multithreaded_hasher;
+mod orion;
+mod privacy_analyzer;
+mod profiler;
+mod proof_package;
+mod proof_pruner;
+mod proof_size_budget;
+mod protocol_constants;
+mod prove_metrics;
+mod prover_threads;
+mod r1cs;
+mod reconstruction;
+#[cfg(feature = "repl")]
+mod repl;
+mod round_compressor;
+mod scheme_selector;
+mod security;
+mod streaming_prover;
 mod system;
+#[cfg(feature = "server")]
+mod telemetry;
+mod tournament;
+mod verification_gadget;
+#[cfg(feature = "server")]
+mod verifier_bridge;
+mod verifier_key;
+mod verifier_threads;
+mod vk_registry;
+mod witness_encryption;
+mod witness_finder;
+mod witness_format;
+mod witness_json;
+mod witness_loader;
+mod witness_oracle;
+mod witness_parallel;
+mod witness_schema;
+mod witness_typed_array;
+#[cfg(feature = "server")]
+mod worker_pool;
@@ -0,0 +1,51 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, GateKind, GateRef};
+use config::GKRConfig;
+
+use super::common::{field_gate, C};
+use crate::{CircuitAnnotationSystem, ConstraintViolationDebugger};
+
+type F = <C as GKRConfig>::SimdCircuitField;
+
+#[test]
+fn test_annotations_in_violations() {
+    let one = <C as GKRConfig>::CircuitField::ONE;
+    // out0 = x0 * x1 - x2, out1 = x3 - 1
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![field_gate([0, 1], 0, one)],
+            add: vec![field_gate([2], 0, -one), field_gate([3], 1, one)],
+            const_: vec![field_gate([], 1, -one)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let mul = GateRef {
+        layer: 0,
+        kind: GateKind::Mul,
+        index: 0,
+    };
+    let mut annotations = CircuitAnnotationSystem::new();
+    annotations.annotate(mul, "file", "main.zk");
+    annotations.annotate(mul, "line", "3");
+    annotations.annotate(mul, "line", "4");
+    assert_eq!(annotations.get(mul, "line"), Some("4"));
+    assert_eq!(annotations.get(mul, "column"), None);
+    assert_eq!(annotations.len(), 2);
+
+    let debugger = ConstraintViolationDebugger::new(&annotations);
+    circuit.layers[0].input_vals = vec![F::from(2), F::from(3), F::from(6), F::ONE];
+    assert!(debugger.debug(&mut circuit).is_empty());
+
+    circuit.layers[0].input_vals[2] = F::from(7);
+    let violations = debugger.debug(&mut circuit);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].wire, 0);
+    assert_eq!(violations[0].gates.len(), 2);
+    let report = debugger.report(&mut circuit);
+    assert!(report.contains("layer 0 Mul gate 0 (file=main.zk line=4)"));
+    assert!(report.contains("layer 0 Add gate 0\n"));
+}
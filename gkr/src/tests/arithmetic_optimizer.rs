@@ -0,0 +1,74 @@
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{finalize, gate, C};
+use crate::{FieldArithmeticOptimizer, OptimizationReport, Prover, Verifier};
+
+fn test_circuit() -> Circuit<C> {
+    finalize(Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                // wires 0 and 1 are copies of 3 times input 0
+                add: vec![
+                    gate([0], 0, 3),
+                    gate([0], 1, 3),
+                    gate([1], 2, 1),
+                    gate([2], 3, 1),
+                ],
+                // wire 2 also gets input 2 * input 3, not a copy of any other wire
+                mul: vec![gate([2, 3], 2, 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![gate([0, 1], 0, 1), gate([1, 0], 0, 2), gate([2, 3], 1, 1)],
+                // a doubling of wire 3
+                add: vec![gate([3], 1, 1), gate([3], 1, 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_field_arithmetic_optimizer() {
+    let mut circuit = test_circuit();
+    let report = FieldArithmeticOptimizer::optimize(&mut circuit);
+    assert_eq!(
+        report,
+        OptimizationReport {
+            squarings_introduced: 2,
+            doublings_introduced: 1,
+        }
+    );
+
+    let layer = &circuit.layers[1];
+    let muls = layer
+        .mul
+        .iter()
+        .map(|g| (g.i_ids, g.o_id))
+        .collect::<Vec<_>>();
+    assert_eq!(muls, vec![([0, 0], 0), ([2, 3], 1)]);
+    assert_eq!(layer.mul[0].coef, <C as GKRConfig>::CircuitField::from(3));
+    assert_eq!(layer.add.len(), 1);
+    assert_eq!(layer.add[0].coef, <C as GKRConfig>::CircuitField::from(2));
+
+    // nothing left to optimize
+    assert_eq!(
+        FieldArithmeticOptimizer::optimize(&mut circuit),
+        OptimizationReport::default()
+    );
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    circuit.set_random_input_for_test();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use crate::BenchmarkReport;
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn test_benchmark_report() {
+    // 1 to 100 ms, shuffled
+    let prove_latencies = (1..=100u64)
+        .map(|i| Duration::from_millis(i * 37 % 101))
+        .collect::<Vec<_>>();
+    let verify_latencies = [Duration::from_millis(2), Duration::from_millis(4)];
+    let report = BenchmarkReport::new(
+        &prove_latencies,
+        &verify_latencies,
+        1234,
+        Some(1 << 19),
+        Some(1 << 20),
+        None,
+    );
+
+    assert_eq!(report.iterations, 100);
+    assert_close(report.prove_min_ms, 1.0);
+    assert_close(report.prove_max_ms, 100.0);
+    assert_close(report.prove_mean_ms, 50.5);
+    assert_close(report.prove_p50_ms, 50.0);
+    assert_close(report.prove_p95_ms, 95.0);
+    assert_close(report.prove_p99_ms, 99.0);
+    assert_close(report.verify_mean_ms, 3.0);
+    assert_close(report.proofs_per_second, 100.0 / 5.05);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["proof_bytes"], 1234);
+    assert_eq!(json["peak_rss_after_witness_load_bytes"], 1 << 19);
+    assert_eq!(json["peak_rss_after_prepare_bytes"], 1 << 20);
+    assert!(json["peak_rss_after_first_prove_bytes"].is_null());
+
+    let text = report.to_string();
+    assert!(text.contains("p95 95.000, p99 99.000"));
+    assert!(text.contains("verify latency (ms): mean 3.000"));
+    assert!(text.contains("peak RSS after the witness load: 524288 bytes"));
+    assert!(text.contains("peak RSS after the first proof: unavailable"));
+}
+
+#[test]
+fn test_benchmark_report_single_proof() {
+    let report = BenchmarkReport::new(&[Duration::from_millis(8)], &[], 0, None, None, None);
+    assert_close(report.prove_p50_ms, 8.0);
+    assert_close(report.prove_p99_ms, 8.0);
+    assert_eq!(report.verify_iterations, 0);
+    assert_close(report.verify_mean_ms, 0.0);
+    assert!(report
+        .to_string()
+        .contains("verify latency (ms): not measured"));
+    assert_close(report.proofs_per_second, 125.0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_peak_rss_bytes() {
+    assert!(crate::peak_rss_bytes().is_some_and(|rss| rss > 0));
+}
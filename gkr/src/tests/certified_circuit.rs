@@ -0,0 +1,58 @@
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::C;
+use crate::{CertifiedCircuit, Ed25519KeyPair, VerificationKey};
+
+fn test_circuit(coef: u32) -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            mul: vec![Gate {
+                i_ids: [0, 1],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: <C as GKRConfig>::CircuitField::from(coef),
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_certified_circuit() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit = test_circuit(1);
+    let vk = VerificationKey::new(&circuit, &config);
+    assert!(vk.matches(&circuit, &config));
+
+    let keypair = Ed25519KeyPair::from_bytes(&[7u8; 32]);
+    let certified = CertifiedCircuit::sign(&circuit, &vk, &keypair);
+    assert!(certified.verify_certificate());
+
+    let mut bytes = vec![];
+    certified.serialize_certificate_into(&mut bytes).unwrap();
+    let loaded =
+        CertifiedCircuit::<C>::deserialize_certificate_from(circuit.clone(), bytes.as_slice())
+            .unwrap();
+    assert!(loaded.verify_certificate());
+    assert_eq!(loaded.vk, vk);
+
+    // the certificate does not carry over to another circuit
+    let other =
+        CertifiedCircuit::<C>::deserialize_certificate_from(test_circuit(2), bytes.as_slice())
+            .unwrap();
+    assert!(!other.verify_certificate());
+
+    // nor to another signer
+    let mut forged = CertifiedCircuit::sign(&circuit, &vk, &keypair);
+    forged.signer_pk = Ed25519KeyPair::from_bytes(&[8u8; 32]).verifying_key();
+    assert!(!forged.verify_certificate());
+
+    let mut other_config = config.clone();
+    other_config.gkr_scheme = GKRScheme::GkrSquare;
+    assert!(!vk.matches(&circuit, &other_config));
+}
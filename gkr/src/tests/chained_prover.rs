@@ -0,0 +1,91 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{field_gate, finalize, C};
+use crate::{ChainedProver, ChainedVerifier, Prover};
+
+type F = <C as GKRConfig>::SimdCircuitField;
+
+/// `[x, y] -> [x * y, x]`.
+fn circuit_a() -> Circuit<C> {
+    let one = <C as GKRConfig>::CircuitField::ONE;
+    finalize(Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            mul: vec![field_gate([0, 1], 0, one)],
+            add: vec![field_gate([0], 1, one)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+/// `[a, b, c, d] -> [a - c, b - d]`: zero iff the witness repeats the output of A.
+fn circuit_b() -> Circuit<C> {
+    let one = <C as GKRConfig>::CircuitField::ONE;
+    finalize(Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            add: vec![
+                field_gate([0], 0, one),
+                field_gate([2], 0, -one),
+                field_gate([1], 1, one),
+                field_gate([3], 1, -one),
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+fn config() -> Config<C> {
+    Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1))
+}
+
+#[test]
+fn test_chained_prover() {
+    let config = config();
+    let prover = ChainedProver::new(circuit_a(), circuit_b(), &config);
+    let verifier = ChainedVerifier::new(circuit_a(), circuit_b(), &config);
+
+    let (proof, claimed_v) =
+        prover.prove_chain(&[F::from(3), F::from(5)], &[F::from(15), F::from(3)]);
+    assert!(claimed_v.iter().all(|v| v.is_zero()));
+    assert!(verifier.verify_chain(&proof, &claimed_v));
+
+    let mut forged = proof.clone();
+    forged.consistency[0] += <C as GKRConfig>::ChallengeField::ONE;
+    assert!(!verifier.verify_chain(&forged, &claimed_v));
+
+    let mut wrong_claim = claimed_v.clone();
+    wrong_claim[0] += <C as GKRConfig>::ChallengeField::ONE;
+    assert!(!verifier.verify_chain(&proof, &wrong_claim));
+}
+
+#[test]
+fn test_chained_prover_rejects_inconsistent_input() {
+    let config = config();
+    let prover = ChainedProver::new(circuit_a(), circuit_b(), &config);
+    let verifier = ChainedVerifier::new(circuit_a(), circuit_b(), &config);
+    let (mut proof, _) = prover.prove_chain(&[F::from(3), F::from(5)], &[F::from(15), F::from(3)]);
+
+    // a valid proof of B on an input that does not start with the output of A
+    let mut circuit = circuit_b();
+    circuit.layers[0].input_vals = vec![F::from(1), F::from(2), F::from(1), F::from(2)];
+    let mut b_prover = Prover::new(&config).unwrap();
+    b_prover.prepare_mem(&circuit);
+    let (claimed_v, proof_b) = b_prover.prove_silent(&mut circuit);
+    proof.proof_b = proof_b;
+    assert!(!verifier.verify_chain(&proof, &claimed_v));
+}
+
+#[test]
+#[should_panic(expected = "the output of A must not depend on random coefficients")]
+fn test_chained_prover_random_coefs() {
+    let mut circuit = circuit_a();
+    circuit.layers[0].add[0].coef_type = CoefType::Random;
+    ChainedProver::new(circuit, circuit_b(), &config());
+}
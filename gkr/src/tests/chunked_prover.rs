@@ -0,0 +1,31 @@
+use circuit::Circuit;
+use config::{Config, GKRScheme, MPIConfig};
+
+use super::common::C;
+use crate::{utils::*, ChunkedMPIProver, Prover, Verifier};
+
+#[test]
+fn test_chunked_prover() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let mut circuit = Circuit::<C>::load_circuit(&circuit_path);
+    circuit.load_witness_file(&witness_path);
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    // chunks dividing the layers or not, and a single chunk
+    let layer_num = circuit.layers.len();
+    for chunk_size in [1, 3, layer_num] {
+        let (chunked_v, chunked_proof) =
+            ChunkedMPIProver::<C>::prove_chunked(&circuit_path, &witness_path, chunk_size, &config);
+        assert_eq!(chunked_v, claimed_v);
+        assert_eq!(chunked_proof.bytes, proof.bytes);
+    }
+
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
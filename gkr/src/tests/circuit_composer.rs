@@ -0,0 +1,161 @@
+use arith::{Field, SimdField};
+use circuit::{
+    composer::{CircuitBuilder, WireRef},
+    CircuitError,
+};
+use config::{Config, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+
+use crate::{Prover, Verifier};
+
+type C = GF2ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+/// The bits of an 8-bit word, the least significant first.
+type Word = [WireRef; 8];
+
+fn xor(b: &mut CircuitBuilder<C>, x: &Word, y: &Word) -> Word {
+    std::array::from_fn(|i| b.add_add_gate(x[i], y[i]))
+}
+
+fn and(b: &mut CircuitBuilder<C>, x: &Word, y: &Word) -> Word {
+    std::array::from_fn(|i| b.add_mul_gate(x[i], y[i]))
+}
+
+fn rotr(x: &Word, n: usize) -> Word {
+    std::array::from_fn(|i| x[(i + n) % 8])
+}
+
+fn sigma(b: &mut CircuitBuilder<C>, x: &Word, [r0, r1, r2]: [usize; 3]) -> Word {
+    let t = xor(b, &rotr(x, r0), &rotr(x, r1));
+    xor(b, &t, &rotr(x, r2))
+}
+
+/// One round of a SHA-256 like compression on 8-bit words, with xor in place of the additions.
+fn round_circuit(b: &mut CircuitBuilder<C>) -> (Word, Word) {
+    let one = b.add_input(1)[0];
+    let [a, bb, c, d, e, f, g, h]: [Word; 8] =
+        std::array::from_fn(|_| b.add_input(8).try_into().unwrap());
+
+    // ch = (e & f) ^ (!e & g)
+    let ones = [one; 8];
+    let not_e = xor(b, &e, &ones);
+    let ef = and(b, &e, &f);
+    let not_eg = and(b, &not_e, &g);
+    let ch = xor(b, &ef, &not_eg);
+    // maj = (a & b) ^ (a & c) ^ (b & c)
+    let ab = and(b, &a, &bb);
+    let ac = and(b, &a, &c);
+    let bc = and(b, &bb, &c);
+    let maj = xor(b, &ab, &ac);
+    let maj = xor(b, &maj, &bc);
+
+    let s1 = sigma(b, &e, [1, 4, 6]);
+    let t1 = xor(b, &h, &s1);
+    let t1 = xor(b, &t1, &ch);
+    let s0 = sigma(b, &a, [2, 3, 5]);
+    let t2 = xor(b, &s0, &maj);
+    (xor(b, &t1, &t2), xor(b, &d, &t1))
+}
+
+fn round(w: [u8; 8]) -> (u8, u8) {
+    let [a, b, c, d, e, f, g, h] = w;
+    let ch = (e & f) ^ (!e & g);
+    let maj = (a & b) ^ (a & c) ^ (b & c);
+    let t1 = h ^ e.rotate_right(1) ^ e.rotate_right(4) ^ e.rotate_right(6) ^ ch;
+    let t2 = a.rotate_right(2) ^ a.rotate_right(3) ^ a.rotate_right(5) ^ maj;
+    (t1 ^ t2, d ^ t1)
+}
+
+#[test]
+fn test_circuit_builder_round() {
+    let mut builder = CircuitBuilder::<C>::new();
+    let (new_a, new_e) = round_circuit(&mut builder);
+    new_a
+        .iter()
+        .chain(&new_e)
+        .for_each(|w| builder.mark_public(*w));
+    let mut circuit = builder.build().unwrap();
+    // 65 inputs and 16 outputs
+    assert_eq!(circuit.layers[0].input_var_num, 7);
+    assert_eq!(circuit.layers.last().unwrap().output_var_num, 4);
+
+    let words = [0x6a, 0xbb, 0x3c, 0xa5, 0x51, 0x9b, 0x1f, 0x5b];
+    let bits = std::iter::once(1)
+        .chain(words.iter().flat_map(|w| (0..8).map(move |i| (w >> i) & 1)))
+        .map(|bit| C::circuit_field_to_simd_circuit_field(&F::from(bit as u32)));
+    let mut input_vals = bits.collect::<Vec<_>>();
+    input_vals.resize(1 << 7, <C as GKRConfig>::SimdCircuitField::ZERO);
+    circuit.layers[0].input_vals = input_vals;
+    circuit.evaluate();
+
+    let (expected_a, expected_e) = round(words);
+    let output = &circuit.layers.last().unwrap().output_vals;
+    for i in 0..8 {
+        assert_eq!(
+            output[i].unpack()[0],
+            F::from(((expected_a >> i) & 1) as u32)
+        );
+        assert_eq!(
+            output[8 + i].unpack()[0],
+            F::from(((expected_e >> i) & 1) as u32)
+        );
+    }
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    circuit.identify_rnd_coefs();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &proof));
+}
+
+#[test]
+fn test_circuit_builder_relay() {
+    type C = M31ExtConfigSha2;
+    type F = <C as GKRConfig>::CircuitField;
+
+    // x * y on layer 1, and x relayed to layer 2 for (x * y) + x
+    let mut builder = CircuitBuilder::<C>::new();
+    let [x, y] = builder.add_input(2).try_into().unwrap();
+    let xy = builder.add_mul_gate(x, y);
+    let out = builder.add_add_gate(xy, x);
+    assert_eq!(out.layer(), 2);
+    builder.mark_public(out);
+    builder.mark_public(x);
+    builder.mark_public(out);
+
+    let mut circuit = builder.build().unwrap();
+    assert_eq!(circuit.layers.len(), 2);
+    assert_eq!(circuit.layers[0].mul.len(), 1);
+    assert_eq!(circuit.layers[0].add.len(), 1);
+    assert_eq!(circuit.layers[1].add.len(), 3);
+
+    circuit.layers[0].input_vals = [3, 4]
+        .iter()
+        .map(|v| C::circuit_field_to_simd_circuit_field(&F::from(*v)))
+        .collect();
+    circuit.evaluate();
+    let output = &circuit.layers[1].output_vals;
+    assert_eq!(output[0].unpack()[0], F::from(15));
+    assert_eq!(output[1].unpack()[0], F::from(3));
+}
+
+#[test]
+fn test_circuit_builder_no_output() {
+    let mut builder = CircuitBuilder::<C>::new();
+    let [x, y] = builder.add_input(2).try_into().unwrap();
+    builder.add_mul_gate(x, y);
+    assert!(matches!(builder.build(), Err(CircuitError::NoOutput)));
+}
+
+#[test]
+#[should_panic(expected = "is not a wire of this builder")]
+fn test_circuit_builder_foreign_wire() {
+    let mut other = CircuitBuilder::<C>::new();
+    let [x, y] = other.add_input(2).try_into().unwrap();
+    let xy = other.add_mul_gate(x, y);
+
+    let mut builder = CircuitBuilder::<C>::new();
+    let z = builder.add_input(1)[0];
+    builder.add_add_gate(z, xy);
+}
@@ -0,0 +1,48 @@
+use arith::SimdField;
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{C, F};
+use crate::{gkr_circuit, Prover, Verifier};
+
+#[test]
+fn test_gkr_circuit_macro() {
+    let mut circuit: Circuit<C> = gkr_circuit! {
+        inputs: [a, b, c];
+        layer 0 { x = a + b; y = b * c; z = a * a + c };
+        layer 1 { out = x * y; out2 = z + x * x };
+        output: [out, out2]
+    };
+    assert_eq!(circuit.layers.len(), 2);
+    assert_eq!(circuit.layers[0].input_var_num, 2);
+    assert_eq!(circuit.layers[0].mul.len(), 2);
+    assert_eq!(circuit.layers[0].add.len(), 3);
+
+    circuit.layers[0].input_vals = [2, 3, 4, 0]
+        .iter()
+        .map(|v| C::circuit_field_to_simd_circuit_field(&F::from(*v)))
+        .collect();
+    circuit.evaluate();
+    let output = &circuit.layers[1].output_vals;
+    // x = 5, y = 12, z = 8
+    assert_eq!(output[0].unpack()[0], F::from(60));
+    assert_eq!(output[1].unpack()[0], F::from(33));
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    circuit.identify_rnd_coefs();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &proof));
+}
+
+#[test]
+#[should_panic(expected = "output x is not a wire of the last layer")]
+fn test_gkr_circuit_macro_output_layer() {
+    let _: Circuit<C> = gkr_circuit! {
+        inputs: [a, b];
+        layer 0 { x = a * b };
+        layer 1 { y = x };
+        output: [x, y]
+    };
+}
@@ -0,0 +1,176 @@
+use arith::FieldSerde;
+use circuit::migrate_v0_to_v1;
+use config::{FieldType, SENTINEL_M31};
+use mersenne31::M31;
+
+use super::common::C;
+use crate::{dump_circuit, dump_witness, DumpError, DumpOptions};
+
+fn put_usizes(bytes: &mut Vec<u8>, values: &[usize]) {
+    for value in values {
+        value.serialize_into(&mut *bytes).unwrap();
+    }
+}
+
+/// One layer of 4 inputs and 2 outputs, x0 * x1 and x2 + x3.
+pub(super) fn circuit_bytes() -> Vec<u8> {
+    let one = |bytes: &mut Vec<u8>| {
+        bytes.push(1);
+        bytes.extend_from_slice(&1usize.to_le_bytes());
+        bytes.extend_from_slice(&[0; 24]);
+    };
+    let mut bytes = b"CIRCUIT6".to_vec();
+    bytes.extend_from_slice(&SENTINEL_M31);
+    put_usizes(&mut bytes, &[0, 2, 0, 1]);
+    put_usizes(&mut bytes, &[4, 2, 0]);
+    put_usizes(&mut bytes, &[1, 0, 1, 0]);
+    one(&mut bytes);
+    put_usizes(&mut bytes, &[2, 2, 1]);
+    one(&mut bytes);
+    put_usizes(&mut bytes, &[3, 1]);
+    one(&mut bytes);
+    put_usizes(&mut bytes, &[0, 0, 1, 0]);
+    bytes
+}
+
+/// The witnesses of the inputs w, w + 1, w + 2, w + 3.
+pub(super) fn witness_bytes(num_witnesses: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    put_usizes(&mut bytes, &[num_witnesses, 4, 0]);
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for w in 0..num_witnesses as u32 {
+        for i in 0..4 {
+            M31::from(w + i).serialize_into(&mut bytes).unwrap();
+        }
+    }
+    migrate_v0_to_v1(&bytes, FieldType::M31)
+}
+
+fn hex(value: u32) -> String {
+    let mut bytes = vec![];
+    M31::from(value).serialize_into(&mut bytes).unwrap();
+    transcript::encode_hex(&bytes)
+}
+
+#[test]
+fn test_dump_circuit() {
+    let dump = dump_circuit::<C>(&circuit_bytes(), &DumpOptions::default()).unwrap();
+    assert_eq!(dump.field, "Mersenne 31");
+    assert_eq!(dump.num_outputs, 2);
+    assert_eq!(dump.layers.len(), 1);
+    let layer = &dump.layers[0];
+    assert_eq!((layer.input_var_num, layer.output_var_num), (2, 1));
+    assert_eq!(layer.num_gates, 3);
+    let kinds = layer.gates.iter().map(|g| g.kind).collect::<Vec<_>>();
+    assert_eq!(kinds, ["mul", "add", "add"]);
+    assert_eq!(layer.gates[0].inputs, [0, 1]);
+    assert_eq!(layer.gates[2].output, 1);
+    assert_eq!(layer.gates[0].coef, hex(1));
+    assert!(dump.to_string().contains("mul [0, 1] -> 0"));
+
+    let options = DumpOptions {
+        layer: Some(0),
+        limit: Some(1),
+    };
+    let dump = dump_circuit::<C>(&circuit_bytes(), &options).unwrap();
+    assert_eq!(dump.layers[0].gates.len(), 1);
+    assert_eq!(dump.layers[0].num_gates, 3);
+    assert!(dump.to_string().contains("... 2 more"));
+
+    let options = DumpOptions {
+        layer: Some(1),
+        limit: None,
+    };
+    assert!(matches!(
+        dump_circuit::<C>(&circuit_bytes(), &options),
+        Err(DumpError::LayerOutOfRange {
+            layer: 1,
+            num_layers: 1
+        })
+    ));
+}
+
+#[test]
+fn test_dump_circuit_parse_errors() {
+    let bytes = circuit_bytes();
+    let truncated = &bytes[..bytes.len() - 5];
+    match dump_circuit::<C>(truncated, &DumpOptions::default()) {
+        Err(DumpError::CircuitParse { offset, .. }) => assert_eq!(offset, truncated.len()),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    // the coefficient type of the mul gate
+    let mut bad_coef = bytes.clone();
+    let coef_offset = 8 + 32 + 4 * 8 + 3 * 8 + 4 * 8;
+    bad_coef[coef_offset] = 7;
+    match dump_circuit::<C>(&bad_coef, &DumpOptions::default()) {
+        Err(DumpError::CircuitParse { offset, .. }) => assert_eq!(offset, coef_offset + 1),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    assert!(matches!(
+        dump_circuit::<C>(&bytes[..16], &DumpOptions::default()),
+        Err(DumpError::Circuit(_))
+    ));
+}
+
+#[test]
+fn test_dump_witness() {
+    let dump = dump_witness::<C>(
+        &circuit_bytes(),
+        &witness_bytes(16),
+        &DumpOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(dump.num_witnesses, 16);
+    assert_eq!(dump.num_private_inputs_per_witness, 4);
+    assert_eq!(dump.witnesses.len(), 16);
+    for (w, witness) in dump.witnesses.iter().enumerate() {
+        let w = w as u32;
+        assert_eq!(witness.private_inputs[1].index, 1);
+        assert_eq!(witness.private_inputs[1].value, hex(w + 1));
+        assert!(witness.public_inputs.is_empty());
+        let outputs = witness.outputs.as_ref().unwrap();
+        assert_eq!(outputs[0].value, hex(w * (w + 1)));
+        assert_eq!(outputs[1].value, hex(2 * w + 5));
+    }
+
+    // the witnesses past the simd lanes are not evaluated
+    let options = DumpOptions {
+        layer: None,
+        limit: Some(1),
+    };
+    let dump = dump_witness::<C>(&circuit_bytes(), &witness_bytes(17), &options).unwrap();
+    assert_eq!(dump.witnesses[0].private_inputs.len(), 1);
+    assert_eq!(dump.witnesses[0].outputs.as_ref().unwrap().len(), 1);
+    assert!(dump.witnesses[16].outputs.is_none());
+}
+
+#[test]
+fn test_dump_witness_parse_errors() {
+    let circuit = circuit_bytes();
+    let witness = witness_bytes(16);
+    let values_offset = witness.len() - 16 * 4 * 4;
+
+    let truncated = &witness[..witness.len() - 6];
+    match dump_witness::<C>(&circuit, truncated, &DumpOptions::default()) {
+        Err(DumpError::WitnessParse { offset, .. }) => assert_eq!(offset, witness.len() - 8),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    let mut trailing = witness.clone();
+    trailing.push(0);
+    match dump_witness::<C>(&circuit, &trailing, &DumpOptions::default()) {
+        Err(DumpError::WitnessParse { offset, .. }) => assert_eq!(offset, witness.len()),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    match dump_witness::<C>(
+        &circuit,
+        &witness[..values_offset - 1],
+        &DumpOptions::default(),
+    ) {
+        Err(DumpError::WitnessParse { offset, .. }) => assert_eq!(offset, values_offset - 1),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
@@ -0,0 +1,62 @@
+use circuit::{Circuit, CircuitLayer};
+use config::GKRConfig;
+
+use super::common::{gate, C};
+use crate::{CircuitLibraryIndex, IndexError};
+
+fn test_circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![gate([0, 1], 0, 1), gate([2, 3], 1, 1)],
+            add: vec![gate([2], 0, 5)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_circuit_hash() {
+    let circuit = test_circuit();
+
+    // the order of the gates does not matter
+    let mut reordered = test_circuit();
+    reordered.layers[0].mul.reverse();
+    assert_eq!(circuit.hash(), reordered.hash());
+
+    let mut modified = test_circuit();
+    modified.layers[0].add[0].coef = <C as GKRConfig>::CircuitField::from(6u32);
+    assert_ne!(circuit.hash(), modified.hash());
+}
+
+#[test]
+fn test_circuit_library_index() {
+    let circuit = test_circuit();
+    let mut index = CircuitLibraryIndex::new();
+    let hash = index.register(&circuit, "product", "1.0.0");
+
+    let metadata = index.lookup(&hash).unwrap();
+    assert_eq!(metadata.name, "product");
+    assert_eq!(metadata.version, "1.0.0");
+    assert_eq!(metadata.field_type, "M31");
+    assert_eq!(metadata.input_size, 4);
+    assert_eq!(metadata.output_size, 2);
+    assert!(index.lookup(&[0u8; 32]).is_none());
+
+    let path = std::env::temp_dir().join(format!("circuit_index_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    index.save(path).unwrap();
+    let loaded = CircuitLibraryIndex::load(path).unwrap();
+    assert_eq!(loaded, index);
+    assert_eq!(loaded.lookup_circuit(&circuit), Some(metadata));
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        CircuitLibraryIndex::from_json(
+            r#"{"00": {"name": "", "version": "", "field_type": "", "input_size": 0, "output_size": 0}}"#
+        ),
+        Err(IndexError::InvalidHash(_))
+    ));
+}
@@ -0,0 +1,87 @@
+use arith::Field;
+use circuit::{CircuitObserver, GateKind, GateRef};
+use config::GKRConfig;
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::common::{finalize, gate, gate_of_type, two_layer_circuit, C};
+
+type SimdF = <C as GKRConfig>::SimdCircuitField;
+
+/// The gate evaluations reported, in order.
+#[derive(Default)]
+struct RecordingObserver {
+    gates: Vec<(GateRef, Vec<SimdF>, SimdF)>,
+}
+
+impl CircuitObserver<C> for RecordingObserver {
+    fn on_gate_evaluated(&mut self, gate: GateRef, inputs: &[SimdF], output: SimdF) {
+        self.gates.push((gate, inputs.to_vec(), output));
+    }
+}
+
+#[test]
+fn test_evaluate_with_observer() {
+    let mut circuit = two_layer_circuit::<C>();
+    // a gate of every kind
+    circuit.layers[0].const_.push(gate([], 1, 7));
+    circuit.layers[1].uni.push(gate_of_type([3], 0, 12345));
+    let mut circuit = finalize(circuit);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let witness = (0..1 << circuit.log_input_size())
+        .map(|_| SimdF::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let mut observer = RecordingObserver::default();
+    let output = circuit.evaluate_with_observer(&witness, &mut observer);
+    let observed_inputs = circuit
+        .layers
+        .iter()
+        .map(|layer| layer.input_vals.clone())
+        .collect::<Vec<_>>();
+
+    // the plain evaluation, of the same values layer by layer
+    circuit.layers[0].input_vals = witness;
+    circuit.evaluate();
+    for (layer, input_vals) in circuit.layers.iter().zip(&observed_inputs) {
+        assert_eq!(layer.input_vals, *input_vals);
+    }
+    assert_eq!(output, circuit.layers.last().unwrap().output_vals);
+
+    // the outputs of the gates of each layer sum up to the values of the next layer
+    let mut sums = circuit
+        .layers
+        .iter()
+        .map(|layer| vec![SimdF::ZERO; 1 << layer.output_var_num])
+        .collect::<Vec<_>>();
+    for (gate, inputs, v) in &observer.gates {
+        let layer = &circuit.layers[gate.layer];
+        let (o_id, i_ids) = match gate.kind {
+            GateKind::Mul => (
+                layer.mul[gate.index].o_id,
+                layer.mul[gate.index].i_ids.to_vec(),
+            ),
+            GateKind::Add => (
+                layer.add[gate.index].o_id,
+                layer.add[gate.index].i_ids.to_vec(),
+            ),
+            GateKind::Const => (layer.const_[gate.index].o_id, vec![]),
+            GateKind::Uni => (
+                layer.uni[gate.index].o_id,
+                layer.uni[gate.index].i_ids.to_vec(),
+            ),
+        };
+        let expected_inputs = i_ids
+            .iter()
+            .map(|&i| layer.input_vals[i])
+            .collect::<Vec<_>>();
+        assert_eq!(*inputs, expected_inputs);
+        sums[gate.layer][o_id] += v;
+    }
+    let num_gates = |i: usize| {
+        let layer = &circuit.layers[i];
+        layer.mul.len() + layer.add.len() + layer.const_.len() + layer.uni.len()
+    };
+    assert_eq!(observer.gates.len(), num_gates(0) + num_gates(1));
+    assert_eq!(sums[0], circuit.layers[1].input_vals);
+    assert_eq!(sums[1], circuit.layers[1].output_vals);
+}
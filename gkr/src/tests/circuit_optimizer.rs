@@ -0,0 +1,126 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, OptimizationReport};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{typed_gate, C, F};
+use crate::{Prover, Verifier};
+
+fn test_circuit() -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 3,
+                // wires 0, 1 and 7 are the constants 3, 4 and 0, wire 2 is the public input
+                const_: vec![
+                    typed_gate([], 0, CoefType::Constant, 3),
+                    typed_gate([], 1, CoefType::Constant, 4),
+                    typed_gate([], 2, CoefType::PublicInput(0), 0),
+                    typed_gate([], 7, CoefType::Constant, 0),
+                ],
+                mul: vec![typed_gate([1, 2], 4, CoefType::Constant, 1)],
+                add: vec![
+                    typed_gate([0], 3, CoefType::Constant, 1),
+                    // wires 5 and 6 are not read
+                    typed_gate([3], 5, CoefType::Constant, 2),
+                    typed_gate([2], 6, CoefType::Random, 0),
+                ],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 3,
+                output_var_num: 2,
+                mul: vec![
+                    // 2 * 3 * 4
+                    typed_gate([0, 1], 0, CoefType::Constant, 2),
+                    // times zero
+                    typed_gate([3, 7], 1, CoefType::Constant, 1),
+                    typed_gate([3, 4], 3, CoefType::Constant, 1),
+                ],
+                add: vec![
+                    typed_gate([4], 1, CoefType::Constant, 1),
+                    // a copy of the public input
+                    typed_gate([2], 2, CoefType::Constant, 1),
+                    typed_gate([0], 3, CoefType::Constant, 5),
+                ],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                // wire 3 is not read
+                mul: vec![typed_gate([0, 2], 0, CoefType::Constant, 1)],
+                add: vec![
+                    typed_gate([1], 0, CoefType::Constant, 1),
+                    typed_gate([1], 1, CoefType::Constant, 3),
+                ],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit.identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_circuit_optimize() {
+    let original = test_circuit();
+    let mut optimized = test_circuit();
+    let report = optimized.optimize();
+    assert_eq!(
+        report,
+        OptimizationReport {
+            gates_before: 17,
+            gates_after: 8,
+            constant_folds: 4,
+            dead_wires_removed: 8,
+        }
+    );
+
+    let layer = &optimized.layers[1];
+    assert!(layer.mul.is_empty());
+    assert!(layer.structure_info.max_degree_one);
+    let consts = layer
+        .const_
+        .iter()
+        .map(|g| (g.o_id, g.coef_type.clone(), g.coef))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        consts,
+        vec![
+            (0, CoefType::Constant, F::from(24)),
+            (2, CoefType::PublicInput(0), F::ZERO)
+        ]
+    );
+    // the gate with a random coefficient is kept, its output unread
+    assert_eq!(optimized.layers[0].add.len(), 1);
+    assert_eq!(optimized.rnd_coefs.len(), original.rnd_coefs.len());
+
+    // nothing left to optimize
+    let mut again = optimized.clone();
+    let report = again.optimize();
+    assert_eq!(report.constant_folds + report.dead_wires_removed, 0);
+    assert_eq!(report.gates_before, report.gates_after);
+
+    let mut rng = rand::thread_rng();
+    let input_vals = (0..4)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let public_input = vec![<C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng)];
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let verifier = Verifier::new(&config);
+
+    let mut claimed_vs = vec![];
+    for mut circuit in [original, optimized] {
+        circuit.layers[0].input_vals.clone_from(&input_vals);
+        circuit.public_input.clone_from(&public_input);
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+        assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+        claimed_vs.push(claimed_v);
+    }
+    assert_eq!(claimed_vs[0], claimed_vs[1]);
+}
@@ -0,0 +1,83 @@
+use circuit::{Circuit, CircuitLayer};
+
+use super::common::{gate, C};
+use crate::{CircuitBinaryPatcher, PatchError, PatchOp};
+
+/// `depth` layers of 8 wires, the gates of layer `i` scaled by `coefs[i]`.
+fn test_circuit(coefs: &[u32]) -> Circuit<C> {
+    let layers = coefs
+        .iter()
+        .map(|&c| CircuitLayer {
+            input_var_num: 3,
+            output_var_num: 3,
+            mul: (0..8).map(|i| gate([i, (i + 1) % 8], i, c)).collect(),
+            add: (0..8).map(|i| gate([i], (i + 3) % 8, c + 1)).collect(),
+            ..Default::default()
+        })
+        .collect();
+    let mut circuit = Circuit::<C> {
+        layers,
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit
+}
+
+#[test]
+fn test_canonical_bytes_round_trip() {
+    let circuit = test_circuit(&[1, 2, 3]);
+    let decoded = Circuit::<C>::from_canonical_bytes(&circuit.canonical_bytes()).unwrap();
+    assert_eq!(decoded.hash(), circuit.hash());
+
+    let bytes = circuit.canonical_bytes();
+    assert!(Circuit::<C>::from_canonical_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn test_circuit_binary_patcher() {
+    let old = test_circuit(&[1, 2, 3, 4]);
+    let new = test_circuit(&[1, 2, 7, 4]);
+    let patch = CircuitBinaryPatcher::generate_patch(&old, &new);
+    // only the coefficients of one layer change
+    assert!(patch.inserted_len() < new.canonical_bytes().len() / 2);
+
+    let mut circuit = old.clone();
+    CircuitBinaryPatcher::apply_patch(&mut circuit, &patch).unwrap();
+    assert_eq!(circuit.hash(), new.hash());
+    assert_eq!(circuit.layers.len(), 4);
+
+    // the patch only applies to the old circuit
+    assert_eq!(
+        CircuitBinaryPatcher::apply_patch(&mut circuit, &patch),
+        Err(PatchError::BaseMismatch)
+    );
+
+    let mut tampered = patch.clone();
+    tampered.ops.push(PatchOp::Insert(vec![0]));
+    let mut circuit = old.clone();
+    assert_eq!(
+        CircuitBinaryPatcher::apply_patch(&mut circuit, &tampered),
+        Err(PatchError::InvalidCircuit)
+    );
+    assert_eq!(circuit.hash(), old.hash());
+
+    tampered.ops = vec![PatchOp::Copy {
+        offset: 0,
+        len: usize::MAX,
+    }];
+    assert!(matches!(
+        CircuitBinaryPatcher::apply_patch(&mut circuit, &tampered),
+        Err(PatchError::InvalidCopy { .. })
+    ));
+
+    // a valid circuit, but not the target
+    let mut other = CircuitBinaryPatcher::generate_patch(&old, &test_circuit(&[5, 2, 7, 4]));
+    other.target_hash = patch.target_hash;
+    assert_eq!(
+        CircuitBinaryPatcher::apply_patch(&mut circuit, &other),
+        Err(PatchError::TargetMismatch)
+    );
+}
@@ -0,0 +1,110 @@
+use std::mem::size_of;
+
+use circuit::{Circuit, CircuitLayer, CircuitStats, CoefType, Gate, LayerStats};
+use config::GKRConfig;
+use sumcheck::ProverScratchPad;
+
+use super::common::{typed_gate, C};
+
+#[test]
+fn test_circuit_stats() {
+    let circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![typed_gate([0, 1], 0, CoefType::Constant, 1)],
+                add: vec![
+                    typed_gate([2], 1, CoefType::Constant, 1),
+                    typed_gate([3], 1, CoefType::Constant, 1),
+                ],
+                const_: vec![typed_gate([], 1, CoefType::PublicInput(2), 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                add: vec![typed_gate([0], 0, CoefType::Constant, 1)],
+                uni: vec![Gate {
+                    gate_type: 12345,
+                    ..gate([1], 1, CoefType::Constant)
+                }],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let stats = circuit.stats();
+    let prover_scratchpad_bytes = stats.prover_scratchpad_bytes;
+    assert_eq!(
+        stats,
+        CircuitStats {
+            num_layers: 2,
+            num_mul_gates: 1,
+            num_add_gates: 3,
+            num_const_gates: 1,
+            num_uni_gates: 1,
+            max_fan_in: 3,
+            num_wires: 8,
+            public_input_size: 3,
+            input_size: 4,
+            prover_scratchpad_bytes,
+            layers: vec![
+                LayerStats {
+                    input_var_num: 2,
+                    output_var_num: 1,
+                    num_mul_gates: 1,
+                    num_add_gates: 2,
+                    num_const_gates: 1,
+                    num_uni_gates: 0,
+                },
+                LayerStats {
+                    input_var_num: 1,
+                    output_var_num: 1,
+                    num_mul_gates: 0,
+                    num_add_gates: 1,
+                    num_const_gates: 0,
+                    num_uni_gates: 1,
+                },
+            ],
+        }
+    );
+    assert_eq!(
+        stats.to_string(),
+        format!(
+            "layers: 2\ngates: 1 mul, 3 add, 1 const, 1 uni\nmax fan-in: 3\nwires: 8\ninputs: 4\n\
+             public inputs: 3\nprover scratch pad: {} bytes",
+            prover_scratchpad_bytes
+        )
+    );
+    assert_eq!(
+        stats.layers[0].to_string(),
+        "2 -> 1 vars: 1 mul, 2 add, 1 const, 0 uni"
+    );
+
+    // the vectors of the scratch pad of a single process, for the largest layer
+    let sp = ProverScratchPad::<C>::new(2, 1, 1);
+    let field_bytes = (sp.v_evals.len() + sp.hg_evals.len()) * size_of::<<C as GKRConfig>::Field>();
+    let challenge_bytes = [
+        &sp.hg_evals_5,
+        &sp.hg_evals_1,
+        &sp.simd_var_v_evals,
+        &sp.simd_var_hg_evals,
+        &sp.eq_evals_at_rx,
+        &sp.eq_evals_at_rz0,
+        &sp.eq_evals_at_r_simd0,
+        &sp.eq_evals_first_half,
+        &sp.eq_evals_second_half,
+    ]
+    .iter()
+    .map(|evals| evals.len())
+    .sum::<usize>()
+        * size_of::<<C as GKRConfig>::ChallengeField>();
+    let gate_bytes = sp.gate_exists_5.len() + sp.gate_exists_1.len();
+    assert_eq!(
+        prover_scratchpad_bytes,
+        field_bytes + challenge_bytes + gate_bytes
+    );
+    assert_eq!(Circuit::<C>::default().stats(), CircuitStats::default());
+}
@@ -0,0 +1,129 @@
+use arith::Field;
+use circuit::{Circuit, CircuitError, CircuitLayer, CoefType, GateKind, GateRef};
+use config::GKRConfig;
+
+use super::common::{typed_gate, C};
+
+type SimdF = <C as GKRConfig>::SimdCircuitField;
+
+/// 4 inputs, then 2 wires, then 1 wire, reading public input 0.
+fn sample_circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![typed_gate([0, 1], 0, CoefType::Constant, 1)],
+                add: vec![typed_gate([2], 1, CoefType::Constant, 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 0,
+                add: vec![typed_gate([1], 0, CoefType::Constant, 1)],
+                const_: vec![typed_gate([], 0, CoefType::PublicInput(0), 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_validate_valid_circuit() {
+    let mut circuit = sample_circuit();
+    assert!(circuit.validate().is_ok());
+
+    circuit.layers[0].input_vals = vec![SimdF::ZERO; 4];
+    circuit.public_input = vec![SimdF::ZERO];
+    assert!(circuit.validate().is_ok());
+}
+
+#[test]
+fn test_validate_layers() {
+    assert!(matches!(
+        Circuit::<C>::default().validate(),
+        Err(CircuitError::NoLayer)
+    ));
+
+    let mut circuit = sample_circuit();
+    circuit.layers[1].add.clear();
+    circuit.layers[1].const_.clear();
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::EmptyLayer { layer: 1 })
+    ));
+
+    let mut circuit = sample_circuit();
+    circuit.layers[1].input_var_num = 2;
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::VarNumMismatch {
+            layer: 1,
+            expected: 1,
+            found: 2
+        })
+    ));
+}
+
+#[test]
+fn test_validate_wires() {
+    let mut circuit = sample_circuit();
+    circuit.layers[0].add[0].i_ids = [4];
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::WireOutOfRange {
+            gate: GateRef {
+                layer: 0,
+                kind: GateKind::Add,
+                index: 0
+            },
+            wire: 4,
+            size: 4
+        })
+    ));
+
+    let mut circuit = sample_circuit();
+    circuit.layers[1].const_[0].o_id = 1;
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::WireOutOfRange {
+            gate: GateRef {
+                layer: 1,
+                kind: GateKind::Const,
+                index: 0
+            },
+            wire: 1,
+            size: 1
+        })
+    ));
+}
+
+#[test]
+fn test_validate_witness() {
+    let mut circuit = sample_circuit();
+    circuit.layers[0].input_vals = vec![SimdF::ZERO; 2];
+    circuit.public_input = vec![SimdF::ZERO];
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::WitnessSizeMismatch {
+            expected: 4,
+            found: 2
+        })
+    ));
+
+    circuit.layers[0].input_vals = vec![SimdF::ZERO; 4];
+    circuit.public_input = vec![];
+    assert!(matches!(
+        circuit.validate(),
+        Err(CircuitError::PublicInputOutOfRange {
+            gate: GateRef {
+                layer: 1,
+                kind: GateKind::Const,
+                index: 0
+            },
+            index: 0,
+            size: 0
+        })
+    ));
+}
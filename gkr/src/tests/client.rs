@@ -0,0 +1,245 @@
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use crate::{
+    decode_batch_results, decode_length_prefixed, encode_batch_results, encode_length_prefixed,
+    ClientError, ProverClient, ProverClusterClient, TransactionBatcher,
+};
+
+/// A mock server answering every request with a fixed body, for `n_requests` requests.
+fn spawn_mock_server(body: &'static str, n_requests: usize) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        for stream in listener.incoming().take(n_requests) {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    addr
+}
+
+/// An address nobody is listening on.
+fn dead_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+#[test]
+fn test_cluster_health_check() {
+    let live = spawn_mock_server("ready", 1);
+    let dead = dead_server();
+    let cluster = ProverClusterClient::new(vec![live.clone(), dead.clone()]);
+
+    let health = cluster.health_check_all();
+    assert_eq!(health, vec![(live, true), (dead, false)]);
+}
+
+#[test]
+fn test_cluster_fails_over() {
+    let live = spawn_mock_server("proof", 2);
+    let cluster = ProverClusterClient::new(vec![dead_server(), live, dead_server()]);
+
+    // whichever server is picked first, the request eventually lands on the live one
+    assert_eq!(cluster.prove(b"witness").unwrap(), b"proof");
+    assert_eq!(cluster.prove(b"witness").unwrap(), b"proof");
+}
+
+/// A mock `/prove_batch` server, "proving" each witness by reversing it and failing on the
+/// empty ones, for `n_requests` requests. Returns the address and the counter of served batches.
+fn spawn_mock_batch_server(n_requests: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let n_batches = Arc::new(AtomicUsize::new(0));
+    let counter = n_batches.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().take(n_requests) {
+            let mut stream = stream.unwrap();
+            let mut request = vec![];
+            let mut buf = [0u8; 1024];
+            let body_start = loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(i) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break i + 4;
+                }
+            };
+            let content_length = String::from_utf8_lossy(&request[..body_start])
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .unwrap()
+                .parse::<usize>()
+                .unwrap();
+            while request.len() < body_start + content_length {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            let witnesses = decode_length_prefixed(&request[body_start..]).unwrap();
+            let results = witnesses
+                .into_iter()
+                .map(|w| {
+                    if w.is_empty() {
+                        Err("empty witness".to_string())
+                    } else {
+                        Ok(w.into_iter().rev().collect::<Vec<_>>())
+                    }
+                })
+                .collect::<Vec<_>>();
+            let body = encode_batch_results(&results);
+            counter.fetch_add(1, Ordering::SeqCst);
+            let header = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+            stream.write_all(header.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+    (addr, n_batches)
+}
+
+#[test]
+fn test_transaction_batcher() {
+    let (server, n_batches) = spawn_mock_batch_server(2);
+    // the latency is long enough for the four requests to fill a batch
+    let batcher = TransactionBatcher::new(ProverClient::new(&server), 4, 60_000);
+
+    thread::scope(|s| {
+        let handles = (0..4u8)
+            .map(|i| {
+                let batcher = &batcher;
+                s.spawn(move || (i, batcher.prove(&[i, 0, 1]).unwrap()))
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let (i, proof) = handle.join().unwrap();
+            assert_eq!(proof, vec![1, 0, i]);
+        }
+    });
+    assert_eq!(n_batches.load(Ordering::SeqCst), 1);
+
+    // a lone request is sent once the latency is over
+    let batcher = TransactionBatcher::new(ProverClient::new(&server), 4, 10);
+    assert_eq!(batcher.prove(&[7, 8]).unwrap(), vec![8, 7]);
+    assert_eq!(n_batches.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_prove_batch_item_failure() {
+    let (server, _) = spawn_mock_batch_server(1);
+    let results = ProverClient::new(&server)
+        .prove_batch(&[vec![1, 2], vec![], vec![3]])
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &vec![2, 1]);
+    assert!(matches!(
+        &results[1],
+        Err(ClientError::BatchItemFailed { index: 1, message }) if message == "empty witness"
+    ));
+    assert_eq!(results[2].as_ref().unwrap(), &vec![3]);
+}
+
+#[test]
+fn test_batch_results_round_trip() {
+    let results = vec![
+        Ok(b"proof".to_vec()),
+        Err("invalid witness".to_string()),
+        Ok(vec![]),
+    ];
+    let bytes = encode_batch_results(&results);
+    assert_eq!(decode_batch_results(&bytes).unwrap(), results);
+
+    // an item without its tag, and an unknown tag
+    assert!(decode_batch_results(&encode_length_prefixed(&[vec![]])).is_none());
+    assert!(decode_batch_results(&encode_length_prefixed(&[vec![2, 0]])).is_none());
+}
+
+#[test]
+fn test_length_prefixed_round_trip() {
+    let blobs = vec![b"witness".to_vec(), vec![], vec![0u8; 300]];
+    let bytes = encode_length_prefixed(&blobs);
+    assert_eq!(decode_length_prefixed(&bytes).unwrap(), blobs);
+    assert_eq!(decode_length_prefixed(&[]).unwrap(), Vec::<Vec<u8>>::new());
+
+    // a truncated length, and a truncated blob
+    assert!(decode_length_prefixed(&bytes[..4]).is_none());
+    assert!(decode_length_prefixed(&bytes[..bytes.len() - 1]).is_none());
+}
+
+/// A mock server answering a single request with a raw response, status line and headers
+/// included.
+fn spawn_raw_server(response: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let mut stream = listener.incoming().next().unwrap().unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf).unwrap();
+        // the client may hang up before the whole response
+        let _ = stream.write_all(response.as_bytes());
+    });
+    addr
+}
+
+#[test]
+fn test_client_response_framing() {
+    let prove = |response, max| {
+        ProverClient::new(&spawn_raw_server(response))
+            .with_max_response_bytes(max)
+            .prove(b"witness")
+    };
+
+    // chunked, with a chunk extension and a trailer, the header names in any case
+    let chunked = "HTTP/1.1 200 OK\r\ntransfer-encoding: Chunked\r\n\r\n\
+                   5\r\nproof\r\n3;ext=1\r\n123\r\n0\r\nX-Trailer: 1\r\n\r\n";
+    assert_eq!(prove(chunked, 8).unwrap(), b"proof123");
+    assert!(matches!(
+        prove(chunked, 7),
+        Err(ClientError::ResponseTooLarge { max: 7, .. })
+    ));
+
+    // read up to the content length, refused past the limit before reading it
+    let framed = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nproof and more";
+    assert_eq!(prove(framed, 5).unwrap(), b"proof");
+    assert!(matches!(
+        prove(framed, 4),
+        Err(ClientError::ResponseTooLarge { max: 4, .. })
+    ));
+
+    // framed by the end of the connection
+    let unframed = "HTTP/1.1 200 OK\r\n\r\nproof";
+    assert_eq!(prove(unframed, 5).unwrap(), b"proof");
+    assert!(matches!(
+        prove(unframed, 4),
+        Err(ClientError::ResponseTooLarge { max: 4, .. })
+    ));
+
+    // a chunk size which is not hex, a chunk without its line ending, a truncated body
+    for malformed in [
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nproof\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nproof0\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\nproof",
+    ] {
+        assert!(matches!(
+            prove(malformed, 1 << 10),
+            Err(ClientError::MalformedResponse(_))
+        ));
+    }
+
+    assert!(matches!(
+        prove("HTTP/1.1 503 Service Unavailable\r\n\r\nbusy", 1 << 10),
+        Err(ClientError::BadStatus { status: 503, .. })
+    ));
+}
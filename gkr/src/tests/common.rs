@@ -0,0 +1,113 @@
+//! The config, the gates and the small circuits of the tests.
+
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use config::{GKRConfig, M31ExtConfigSha2};
+
+pub(crate) type C = M31ExtConfigSha2;
+pub(crate) type F = <C as GKRConfig>::CircuitField;
+
+/// A gate of type 0, of the constant coefficient, of the config of the circuit it is in.
+pub(crate) fn gate<Cfg: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef: u32,
+) -> Gate<Cfg, INPUT_NUM> {
+    typed_gate(i_ids, o_id, CoefType::Constant, coef)
+}
+
+/// As `gate`, of a coefficient of the field, e.g., a negative one.
+pub(crate) fn field_gate<Cfg: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef: Cfg::CircuitField,
+) -> Gate<Cfg, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type: CoefType::Constant,
+        coef,
+        gate_type: 0,
+    }
+}
+
+/// As `gate`, of the coefficient one.
+pub(crate) fn unit_gate<Cfg: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+) -> Gate<Cfg, INPUT_NUM> {
+    gate_of_type(i_ids, o_id, 0)
+}
+
+/// As `unit_gate`, of the gate type.
+pub(crate) fn gate_of_type<Cfg: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    gate_type: usize,
+) -> Gate<Cfg, INPUT_NUM> {
+    Gate {
+        gate_type,
+        ..gate(i_ids, o_id, 1)
+    }
+}
+
+/// A gate of type 0, of the coefficient type, and of the coefficient if it is a constant.
+pub(crate) fn typed_gate<Cfg: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef_type: CoefType,
+    coef: u32,
+) -> Gate<Cfg, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef: Cfg::CircuitField::from(coef),
+        gate_type: 0,
+    }
+}
+
+/// The circuit with the structure of its layers and its random coefficients identified, for
+/// the prover and the verifier.
+pub(crate) fn finalize<Cfg: GKRConfig>(mut circuit: Circuit<Cfg>) -> Circuit<Cfg> {
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+/// A single layer of the sizes, without gates.
+pub(crate) fn single_layer_circuit(input_var_num: usize, output_var_num: usize) -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num,
+            output_var_num,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Two layers over 4 inputs `(a, b, c, d)`: `(a * b, 3 * c * d, 2 * b, d)`, then
+/// `(a * b + 3 * c * d, 10 * b)`, to be finalized, see `finalize`.
+pub(crate) fn two_layer_circuit<Cfg: GKRConfig>() -> Circuit<Cfg> {
+    Circuit::<Cfg> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![gate([0, 1], 0, 1), gate([2, 3], 1, 3)],
+                add: vec![gate([1], 2, 2), gate([3], 3, 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                add: vec![gate([0], 0, 1), gate([1], 0, 1), gate([2], 1, 5)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
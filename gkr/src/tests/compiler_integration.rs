@@ -25,7 +25,7 @@
 
 //     let mut prover = Prover::new(&config);
 //     prover.prepare_mem(&circuit);
-//     let (claimed_v, proof) = prover.prove(&mut circuit);
+//     let (claimed_v, proof) = prover.prove_silent(&mut circuit);
 //     println!("Proof generated. Size: {} bytes", proof.bytes.len());
 //     // write proof to file
 //     fs::write(FILENAME_PROOF, &proof.bytes).expect("Unable to write proof to file.");
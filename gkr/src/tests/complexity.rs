@@ -0,0 +1,81 @@
+use circuit::{Circuit, CircuitLayer};
+
+use super::common::{gate_of_type, C};
+use crate::{CircuitComplexityLowerBound, CircuitLibraryIndex, ComplexityCheck};
+
+/// Computes `x0^2 * x1^5`, on a critical path of two mul gates and a pow5 gate.
+fn test_circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                mul: vec![gate_of_type([0, 0], 0, 0)],
+                add: vec![gate_of_type([1], 1, 0)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                add: vec![gate_of_type([0], 0, 0)],
+                uni: vec![gate_of_type([1], 1, 12345)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 0,
+                mul: vec![gate_of_type([0, 1], 0, 0)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_multiplicative_depth() {
+    let circuit = test_circuit();
+    assert_eq!(
+        CircuitComplexityLowerBound::multiplicative_depth(&circuit),
+        4
+    );
+
+    assert_eq!(
+        CircuitComplexityLowerBound::check(&circuit, None),
+        ComplexityCheck::Undeclared { actual_depth: 4 }
+    );
+    assert_eq!(
+        CircuitComplexityLowerBound::check(&circuit, Some(4)),
+        ComplexityCheck::Optimal {
+            actual_depth: 4,
+            lower_bound: 4
+        }
+    );
+    // x0^2 * x1^5 = (x0 * x1)^2 * x1^3, of depth 3
+    assert!(CircuitComplexityLowerBound::check(&circuit, Some(3)).is_suboptimal());
+}
+
+#[test]
+fn test_check_indexed() {
+    let circuit = test_circuit();
+    let mut index = CircuitLibraryIndex::new();
+    assert_eq!(
+        CircuitComplexityLowerBound::check_indexed(&circuit, &index),
+        ComplexityCheck::Undeclared { actual_depth: 4 }
+    );
+
+    let hash = index.register(&circuit, "x0^2 * x1^5", "1.0.0");
+    let mut json: serde_json::Value = serde_json::from_str(&index.to_json().unwrap()).unwrap();
+    let entry = json.as_object_mut().unwrap().values_mut().next().unwrap();
+    entry["expected_depth"] = 3.into();
+    let index = CircuitLibraryIndex::from_json(&json.to_string()).unwrap();
+    assert_eq!(index.lookup(&hash).unwrap().expected_depth, Some(3));
+
+    assert_eq!(
+        CircuitComplexityLowerBound::check_indexed(&circuit, &index),
+        ComplexityCheck::Suboptimal {
+            actual_depth: 4,
+            lower_bound: 3
+        }
+    );
+}
@@ -0,0 +1,70 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{field_gate, C};
+use crate::continuation::{ContinuationState, ContinuationStep};
+
+type F = <C as GKRConfig>::SimdCircuitField;
+
+/// The step `x -> x^2 + 1`, on the input layer `[x, x']`.
+fn step_circuit() -> Circuit<C> {
+    let one = <C as GKRConfig>::CircuitField::ONE;
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            mul: vec![field_gate([0, 0], 0, -one)],
+            add: vec![field_gate([1], 0, one)],
+            const_: vec![field_gate([], 0, -one)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_continuation() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit = step_circuit();
+
+    let mut state = ContinuationState::<C>::new(vec![F::from(2)]);
+    assert!(state.verify(&circuit, &config));
+    for _ in 0..3 {
+        let x = state.state[0];
+        state = ContinuationStep::prove(&state, &circuit, &[x * x + F::ONE], &config);
+    }
+    assert_eq!(state.step, 3);
+    // 2 -> 5 -> 26 -> 677
+    assert_eq!(state.state, vec![F::from(677)]);
+    assert!(state.verify(&circuit, &config));
+
+    // a state that does not follow from the proofs
+    let mut forged = state.clone();
+    forged.state = vec![F::from(678)];
+    assert!(!forged.verify(&circuit, &config));
+    forged.state_commitment = crate::continuation::commit_state::<C>(&forged.state);
+    assert!(!forged.verify(&circuit, &config));
+
+    // a proof dropped from the chain
+    let mut truncated = state.clone();
+    truncated
+        .accumulated_proof
+        .as_mut()
+        .unwrap()
+        .steps
+        .remove(1);
+    truncated.step = 2;
+    assert!(!truncated.verify(&circuit, &config));
+}
+
+#[test]
+#[should_panic(expected = "does not satisfy")]
+fn test_continuation_invalid_step() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let state = ContinuationState::<C>::new(vec![F::from(2)]);
+    ContinuationStep::prove(&state, &step_circuit(), &[F::from(6)], &config);
+}
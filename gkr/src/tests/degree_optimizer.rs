@@ -0,0 +1,72 @@
+use circuit::{Circuit, CircuitLayer};
+use config::GKRConfig;
+
+use super::common::{gate, C};
+use crate::SumcheckDegreeOptimizer;
+
+/// Layer 0 outputs the constant 3 on wire 0 and relays the inputs 0 and 1 on wires 1 and 2.
+/// Layer 1 multiplies wire 0 by wire 1, wire 0 by itself, and wire 1 by wire 2.
+fn test_circuit() -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                add: vec![gate([0], 1, 1), gate([1], 2, 1)],
+                const_: vec![gate([], 0, 3)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![gate([0, 1], 0, 2), gate([0, 0], 1, 1), gate([1, 2], 2, 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit
+}
+
+#[test]
+fn test_degree_optimizer() {
+    let original = test_circuit();
+    let mut circuit = original.clone();
+
+    assert_eq!(SumcheckDegreeOptimizer::optimize(&mut circuit), 2);
+    assert!(SumcheckDegreeOptimizer::evaluations_match(
+        &original, &circuit
+    ));
+
+    let layer = &circuit.layers[1];
+    assert_eq!(layer.mul.len(), 1);
+    assert_eq!(layer.add.len(), 1);
+    assert_eq!(layer.const_.len(), 1);
+    assert_eq!(layer.add[0].i_ids, [1]);
+    assert_eq!(
+        layer.add[0].coef,
+        <C as GKRConfig>::CircuitField::from(6u32)
+    );
+    assert_eq!(
+        layer.const_[0].coef,
+        <C as GKRConfig>::CircuitField::from(9u32)
+    );
+    assert!(!layer.structure_info.max_degree_one);
+
+    // nothing left to substitute
+    assert_eq!(SumcheckDegreeOptimizer::optimize(&mut circuit), 0);
+}
+
+#[test]
+fn test_degree_optimizer_removes_second_phase() {
+    let mut circuit = test_circuit();
+    circuit.layers[1].mul.pop();
+
+    assert_eq!(SumcheckDegreeOptimizer::optimize(&mut circuit), 2);
+    assert!(circuit.layers[1].mul.is_empty());
+    assert!(circuit.layers[1].structure_info.max_degree_one);
+}
@@ -0,0 +1,65 @@
+use std::{ffi::OsStr, path::Path};
+
+use crate::{EthereumAddress, EthereumVerifierDeployer};
+
+#[test]
+fn test_parse_deployed_address() {
+    let forge_output = "\
+Deployer: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+Deployed to: 0x5FbDB2315678afecb367f032d93F642f64180aa3
+Transaction hash: 0x0000000000000000000000000000000000000000000000000000000000000000
+";
+    let address = EthereumVerifierDeployer::parse_deployed_address(forge_output).unwrap();
+    assert_eq!(
+        address.to_string(),
+        "0x5fbdb2315678afecb367f032d93f642f64180aa3"
+    );
+    assert_eq!(
+        address.to_string().parse::<EthereumAddress>().unwrap(),
+        address
+    );
+
+    assert!(EthereumVerifierDeployer::parse_deployed_address("Error: reverted").is_err());
+    assert!("0x1234".parse::<EthereumAddress>().is_err());
+}
+
+#[test]
+fn test_forge_create_command_private_key() {
+    const PRIVATE_KEY: &str = "0x0123456789abcdef";
+    let command = EthereumVerifierDeployer::forge_create_command(
+        Path::new("/tmp"),
+        "Verifier",
+        "http://127.0.0.1:8545",
+        PRIVATE_KEY,
+    );
+
+    // the key is in the environment of the child, and nowhere in its arguments
+    assert!(command
+        .get_envs()
+        .any(|(key, value)| key == "ETH_PRIVATE_KEY" && value == Some(OsStr::new(PRIVATE_KEY))));
+    assert!(command
+        .get_args()
+        .all(|arg| !arg.to_string_lossy().contains(PRIVATE_KEY) && arg != "--private-key"));
+}
+
+/// Requires `forge` and a local testnet started with `anvil`.
+#[test]
+#[ignore]
+fn test_deploy_to_anvil() {
+    const SOURCE: &str = "\
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Verifier {
+    function verify() public pure returns (bool) {
+        return true;
+    }
+}
+";
+    // the first of the default anvil accounts
+    const PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    let address =
+        EthereumVerifierDeployer::deploy(SOURCE, "http://127.0.0.1:8545", PRIVATE_KEY).unwrap();
+    assert_ne!(address, EthereumAddress([0u8; 20]));
+}
@@ -0,0 +1,53 @@
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRScheme, MPIConfig};
+
+use super::common::{unit_gate, C};
+use crate::docs::GKRVerifierPseudocode;
+
+#[test]
+fn test_verifier_pseudocode() {
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![unit_gate([0, 1], 0)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                add: vec![unit_gate([0], 0), unit_gate([1], 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+
+    let pseudocode = GKRVerifierPseudocode::generate(&circuit, &config);
+    assert!(pseudocode.starts_with("% GKR verifier for a circuit of 2 layers"));
+    assert!(pseudocode.contains("\\begin{algorithmic}[1]"));
+    assert!(pseudocode.trim_end().ends_with("\\end{algorithmic}"));
+
+    // the layers in verification order
+    let layer_1 = pseudocode.find("\\textbf{Layer 1}").unwrap();
+    let layer_0 = pseudocode.find("\\textbf{Layer 0}").unwrap();
+    assert!(layer_1 < layer_0);
+
+    // x and simd rounds for both layers, y rounds for the mul layer only
+    assert_eq!(pseudocode.matches("\\For{").count(), 5);
+    assert_eq!(
+        pseudocode.matches("\\For{").count(),
+        pseudocode.matches("\\EndFor").count()
+    );
+    // a claimed value per output of each of the 16 lanes
+    assert!(pseudocode.contains("absorb the 32 claimed values"));
+    assert!(pseudocode.contains("\\mathrm{mul}_{0}(r_z, r_x, r_y)"));
+    // the mul layer leaves two claims on the input layer
+    assert!(pseudocode.contains("\\tilde{C}(r_z', r_{simd}, r_{mpi}) = v'"));
+}
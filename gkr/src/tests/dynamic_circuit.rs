@@ -0,0 +1,104 @@
+use arith::SimdField;
+use circuit::{Gate, GateKind, GateRef};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{unit_gate, C, F};
+use crate::{ConnectivityError, DynamicCircuit, LayerSpec, ProofLayout, Prover, Verifier};
+
+/// `out0 = x0 * x1`, `out1 = x0 + x1`.
+fn step() -> LayerSpec<C> {
+    LayerSpec {
+        input_var_num: 1,
+        output_var_num: 1,
+        mul: vec![unit_gate([0, 1], 0)],
+        add: vec![unit_gate([0], 1), unit_gate([1], 1)],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_dynamic_circuit() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut dynamic = DynamicCircuit::<C>::new(&config);
+    for _ in 0..3 {
+        dynamic.push_layer(step()).unwrap();
+    }
+    assert_eq!(dynamic.num_layers(), 3);
+    let proof_size = dynamic.proof_size();
+
+    let mut circuit = dynamic.finalize();
+    assert_eq!(proof_size, ProofLayout::new(&circuit, &config).proof_size());
+
+    circuit.layers[0].input_vals = [1, 2]
+        .iter()
+        .map(|v| C::circuit_field_to_simd_circuit_field(&F::from(*v)))
+        .collect();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    // (1, 2) -> (2, 3) -> (6, 5) -> (30, 11)
+    assert_eq!(circuit.layers[2].output_vals[0].unpack()[0], F::from(30));
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &proof));
+}
+
+#[test]
+fn test_dynamic_circuit_connectivity_errors() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut dynamic = DynamicCircuit::<C>::new(&config);
+    dynamic.push_layer(step()).unwrap();
+
+    let mut wider = step();
+    wider.input_var_num = 2;
+    assert_eq!(
+        dynamic.push_layer(wider).err(),
+        Some(ConnectivityError::VarNumMismatch {
+            layer: 1,
+            expected: 1,
+            found: 2
+        })
+    );
+
+    let mut out_of_range = step();
+    out_of_range.add.push(unit_gate([2], 0));
+    assert_eq!(
+        dynamic.push_layer(out_of_range).err(),
+        Some(ConnectivityError::InputOutOfRange {
+            gate: GateRef {
+                layer: 1,
+                kind: GateKind::Add,
+                index: 2
+            },
+            wire: 2,
+            size: 2
+        })
+    );
+
+    // only out0 is written
+    let mut sparse = step();
+    sparse.add.clear();
+    dynamic.push_layer(sparse).unwrap();
+    assert_eq!(
+        dynamic.push_layer(step()).err(),
+        Some(ConnectivityError::UnwrittenInput {
+            gate: GateRef {
+                layer: 2,
+                kind: GateKind::Mul,
+                index: 0
+            },
+            wire: 1
+        })
+    );
+
+    let mut unknown = step();
+    unknown.mul.clear();
+    unknown.add.clear();
+    unknown.uni.push(Gate {
+        gate_type: 7,
+        ..gate([0], 0)
+    });
+    assert!(matches!(
+        dynamic.push_layer(unknown),
+        Err(ConnectivityError::UnknownGateType { gate_type: 7, .. })
+    ));
+    assert_eq!(dynamic.num_layers(), 2);
+}
@@ -0,0 +1,84 @@
+use arith::{Field, FieldSerde};
+use ciborium::Value;
+use mersenne31::{M31Ext3, M31};
+use rand::thread_rng;
+use transcript::Proof;
+
+use crate::encoding::cbor::{decode_proof, encode_proof, CborDecodeError};
+
+#[test]
+fn test_cbor_proof_roundtrip() {
+    let mut rng = thread_rng();
+    let proof = Proof {
+        bytes: (0..1000).map(|i| i as u8).collect(),
+    };
+    let claimed_v = (0..2)
+        .map(|_| M31Ext3::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let bytes = encode_proof(&proof, &claimed_v);
+    // the proof bytes are a single byte string
+    assert!(bytes.len() < proof.bytes.len() + 64);
+    assert_eq!(decode_proof::<M31Ext3>(&bytes).unwrap(), (proof, claimed_v));
+
+    assert!(matches!(
+        decode_proof::<M31>(&bytes),
+        Err(CborDecodeError::FieldMismatch { .. })
+    ));
+    assert!(matches!(
+        decode_proof::<M31Ext3>(&bytes[..bytes.len() - 1]),
+        Err(CborDecodeError::CborError(_))
+    ));
+}
+
+#[test]
+fn test_cbor_proof_document() {
+    let claimed_v = vec![M31::from(7)];
+    let bytes = encode_proof(&Proof { bytes: vec![1, 2] }, &claimed_v);
+    let document: Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+    let mut claimed_v_bytes = vec![];
+    claimed_v[0].serialize_into(&mut claimed_v_bytes).unwrap();
+    assert_eq!(
+        document,
+        Value::Map(vec![
+            (Value::from("field"), Value::from("Mersenne 31")),
+            (Value::from("proof"), Value::Bytes(vec![1, 2])),
+            (Value::from("claimed_v"), Value::Bytes(claimed_v_bytes)),
+        ])
+    );
+
+    // keys in any order, unknown keys ignored
+    let document = Value::Map(vec![
+        (Value::from("version"), Value::from(1)),
+        (Value::from("claimed_v"), Value::Bytes(vec![7, 0, 0, 0])),
+        (Value::from("proof"), Value::Bytes(vec![])),
+        (Value::from("field"), Value::from("Mersenne 31")),
+    ]);
+    let mut bytes = vec![];
+    ciborium::into_writer(&document, &mut bytes).unwrap();
+    assert_eq!(
+        decode_proof::<M31>(&bytes).unwrap(),
+        (Proof::default(), claimed_v)
+    );
+
+    // the claimed values are whole field elements
+    let document = Value::Map(vec![
+        (Value::from("field"), Value::from("Mersenne 31")),
+        (Value::from("proof"), Value::Bytes(vec![])),
+        (Value::from("claimed_v"), Value::Bytes(vec![7, 0, 0, 0, 1])),
+    ]);
+    let mut bytes = vec![];
+    ciborium::into_writer(&document, &mut bytes).unwrap();
+    assert!(matches!(
+        decode_proof::<M31>(&bytes),
+        Err(CborDecodeError::ClaimedValueError(_))
+    ));
+
+    let document = Value::Map(vec![(Value::from("field"), Value::from("Mersenne 31"))]);
+    let mut bytes = vec![];
+    ciborium::into_writer(&document, &mut bytes).unwrap();
+    assert!(matches!(
+        decode_proof::<M31>(&bytes),
+        Err(CborDecodeError::InvalidKey("proof"))
+    ));
+}
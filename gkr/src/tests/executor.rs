@@ -0,0 +1,790 @@
+use arith::{Field, FieldSerde, SimdBackend, SimdField};
+use circuit::{encode_witness_batch, WITNESS_HEADER_SIZE};
+use config::{
+    Config, ConfigError, FiatShamirHashType, GKRConfig, GKRScheme, M31ExtConfigKeccak,
+    M31ExtSimdConfigSha2, MPIConfig, PolynomialCommitmentType,
+};
+use mersenne31::simd::{self, M31SimdBackend};
+
+use super::common::C;
+use crate::{
+    executor::{self, ExpanderError, ProofFile},
+    utils::*,
+    CompressionCodec, ProofPackage, ProofPackageError, Prover, VerificationError, Verifier,
+};
+
+#[test]
+fn test_executor_prove_and_verify() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    assert!(executor::verify(&config, &circuit_path, &witness_path, &proof_bytes).unwrap());
+
+    // a proof that does not verify is not an error
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    let mut claimed_v = package.claimed_v::<C>().unwrap();
+    claimed_v[0] += <C as GKRConfig>::ChallengeField::ONE;
+    let wrong_bytes =
+        executor::package_proof(&config, &package.proof, &claimed_v, CompressionCodec::None)
+            .unwrap();
+    assert!(!executor::verify(&config, &circuit_path, &witness_path, &wrong_bytes).unwrap());
+
+    assert!(matches!(
+        executor::verify(
+            &config,
+            &circuit_path,
+            &witness_path,
+            &proof_bytes[..proof_bytes.len() - 1]
+        ),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::Truncated
+        ))
+    ));
+    assert!(matches!(
+        executor::prove(&config, "../data/no_such_circuit.txt", &witness_path),
+        Err(ExpanderError::CircuitLoad(_))
+    ));
+    assert!(matches!(
+        executor::prove(&config, &circuit_path, "../data/no_such_witness.txt"),
+        Err(ExpanderError::WitnessLoad(_))
+    ));
+}
+
+#[test]
+fn test_executor_schemes() {
+    let vanilla = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let square = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    let keccak_circuit = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let keccak_witness = "../".to_owned() + KECCAK_M31_WITNESS;
+    let poseidon_circuit = "../".to_owned() + POSEIDON_M31_CIRCUIT;
+    let poseidon_witness = "../".to_owned() + POSEIDON_M31_WITNESS;
+
+    // each scheme proves the gates the other one does not
+    let proof_bytes = executor::prove(&square, &poseidon_circuit, &poseidon_witness).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(package.gkr_scheme, GKRScheme::GkrSquare);
+    assert!(executor::verify(&square, &poseidon_circuit, &poseidon_witness, &proof_bytes).unwrap());
+    assert!(matches!(
+        executor::verify(&vanilla, &poseidon_circuit, &poseidon_witness, &proof_bytes),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::GKRSchemeMismatch { .. }
+        ))
+    ));
+    assert!(matches!(
+        executor::prove(&vanilla, &poseidon_circuit, &poseidon_witness),
+        Err(ExpanderError::UnsupportedScheme(GKRScheme::Vanilla))
+    ));
+
+    let proof_bytes = executor::prove(&vanilla, &keccak_circuit, &keccak_witness).unwrap();
+    assert!(matches!(
+        executor::verify(&square, &keccak_circuit, &keccak_witness, &proof_bytes),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::GKRSchemeMismatch { .. }
+        ))
+    ));
+    assert!(matches!(
+        executor::prove(&square, &keccak_circuit, &keccak_witness),
+        Err(ExpanderError::UnsupportedScheme(GKRScheme::GkrSquare))
+    ));
+}
+
+#[test]
+fn test_executor_hashes() {
+    let sha2 = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let keccak =
+        Config::<M31ExtConfigKeccak>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&keccak, &circuit_path, &witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(package.fiat_shamir_hash, FiatShamirHashType::Keccak256);
+    assert!(executor::verify(&keccak, &circuit_path, &witness_path, &proof_bytes).unwrap());
+    assert!(matches!(
+        executor::verify(&sha2, &circuit_path, &witness_path, &proof_bytes),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::FiatShamirHashMismatch {
+                expected: FiatShamirHashType::SHA256,
+                found: FiatShamirHashType::Keccak256,
+            }
+        ))
+    ));
+
+    // relabeled, the proof is checked against the challenges of the other hash
+    let relabeled = ProofPackage {
+        fiat_shamir_hash: FiatShamirHashType::SHA256,
+        ..package
+    };
+    assert!(!executor::verify_package(&sha2, &circuit_path, &witness_path, &relabeled).unwrap());
+}
+
+#[test]
+fn test_executor_rng_seed() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let seeded = config.clone().with_rng_seed(7);
+    let other_seed = config.clone().with_rng_seed(8);
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&seeded, &circuit_path, &witness_path).unwrap();
+    let same_seed_bytes = executor::prove(&seeded, &circuit_path, &witness_path).unwrap();
+    let other_seed_bytes = executor::prove(&other_seed, &circuit_path, &witness_path).unwrap();
+    assert_eq!(proof_bytes, same_seed_bytes);
+    assert_ne!(proof_bytes, other_seed_bytes);
+
+    // the verifier reads the seed from the package, and rejects another one given
+    assert!(executor::verify(&seeded, &circuit_path, &witness_path, &proof_bytes).unwrap());
+    assert!(executor::verify(&config, &circuit_path, &witness_path, &proof_bytes).unwrap());
+    assert!(matches!(
+        executor::verify(&other_seed, &circuit_path, &witness_path, &proof_bytes),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::ProverSeedMismatch
+        ))
+    ));
+}
+
+#[test]
+fn test_executor_polynomial_commitment() {
+    let raw = Config::<C>::new_with_pcs(
+        GKRScheme::Vanilla,
+        PolynomialCommitmentType::Raw,
+        MPIConfig::new_for_verifier(1),
+    )
+    .unwrap();
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&raw, &circuit_path, &witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(
+        package.polynomial_commitment_type,
+        PolynomialCommitmentType::Raw
+    );
+    assert!(executor::verify(&raw, &circuit_path, &witness_path, &proof_bytes).unwrap());
+
+    let orion = Config::<C>::new_with_pcs(
+        GKRScheme::Vanilla,
+        PolynomialCommitmentType::Orion,
+        MPIConfig::new_for_verifier(1),
+    )
+    .unwrap();
+    let orion_bytes = executor::prove(&orion, &circuit_path, &witness_path).unwrap();
+    let orion_package = ProofPackage::deserialize_from(&mut orion_bytes.as_slice()).unwrap();
+    assert_eq!(
+        orion_package.polynomial_commitment_type,
+        PolynomialCommitmentType::Orion
+    );
+    assert!(executor::verify(&orion, &circuit_path, &witness_path, &orion_bytes).unwrap());
+
+    for pcs in PolynomialCommitmentType::ALL {
+        if !pcs.is_implemented() {
+            assert!(matches!(
+                Config::<C>::new_with_pcs(GKRScheme::Vanilla, pcs, MPIConfig::new_for_verifier(1)),
+                Err(ConfigError::PolynomialCommitmentUnavailable(found)) if found == pcs
+            ));
+        }
+    }
+
+    let relabeled = ProofPackage {
+        polynomial_commitment_type: PolynomialCommitmentType::Orion,
+        ..package
+    };
+    assert!(matches!(
+        executor::verify_package(&raw, &circuit_path, &witness_path, &relabeled),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::PolynomialCommitmentMismatch {
+                expected: PolynomialCommitmentType::Raw,
+                found: PolynomialCommitmentType::Orion,
+            }
+        ))
+    ));
+}
+
+/// The proof of the packed fields of the backend `B`, of the keccak circuit.
+fn simd_backend_proof<B: M31SimdBackend>() -> Vec<u8> {
+    let config =
+        Config::<M31ExtSimdConfigSha2<B>>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    executor::prove(&config, &circuit_path, &witness_path).unwrap()
+}
+
+/// Of the same bytes as that of the scalar backend, if the cpu has the backend.
+fn assert_simd_backend_proof<B: M31SimdBackend>(scalar_proof: &[u8]) {
+    if B::BACKEND.is_available() {
+        assert_eq!(
+            simd_backend_proof::<B>(),
+            scalar_proof,
+            "{} proof",
+            B::BACKEND
+        );
+    }
+}
+
+#[test]
+fn test_executor_simd_backends() {
+    let scalar_proof = simd_backend_proof::<simd::Scalar>();
+    #[cfg(target_arch = "x86_64")]
+    {
+        assert_simd_backend_proof::<simd::Avx2>(&scalar_proof);
+        assert_simd_backend_proof::<simd::Avx512>(&scalar_proof);
+    }
+    #[cfg(target_arch = "aarch64")]
+    assert_simd_backend_proof::<simd::Neon>(&scalar_proof);
+
+    // and verified by the backend of the build
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    assert!(executor::verify(&config, &circuit_path, &witness_path, &scalar_proof).unwrap());
+    assert_eq!(C::SIMD_BACKEND, SimdBackend::compiled());
+}
+
+#[test]
+fn test_executor_mpi_world_size() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(package.mpi_world_size, Some(1));
+    assert!(package.public_input.is_some());
+
+    // without the public inputs of the prover, verified on as many processes
+    let distributed = ProofPackage {
+        mpi_world_size: Some(2),
+        public_input: None,
+        ..package.clone()
+    };
+    assert!(matches!(
+        executor::verify_package(&config, &circuit_path, &witness_path, &distributed),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::MPIWorldSizeMismatch {
+                expected: 1,
+                found: 2,
+            }
+        ))
+    ));
+
+    // with them, verified on a single process as the prover of the world size of the package,
+    // given the public inputs of each rank
+    let distributed = ProofPackage {
+        mpi_world_size: Some(2),
+        ..package.clone()
+    };
+    assert!(matches!(
+        executor::verify_package(&config, &circuit_path, &witness_path, &distributed),
+        Err(ExpanderError::RankPublicInputsMissing(2))
+    ));
+    let witness = std::fs::read(&witness_path).unwrap();
+    let ranks_path = |ranks: usize| {
+        let path = std::env::temp_dir()
+            .join(format!(
+                "{}_{}_rank_public_inputs.txt",
+                std::process::id(),
+                ranks
+            ))
+            .to_str()
+            .unwrap()
+            .to_string();
+        std::fs::write(&path, encode_witness_batch(&vec![&witness[..]; ranks])).unwrap();
+        path
+    };
+    assert!(
+        !executor::verify_package(&config, &circuit_path, &ranks_path(2), &distributed).unwrap()
+    );
+    assert!(matches!(
+        executor::verify_package(&config, &circuit_path, &ranks_path(3), &distributed),
+        Err(ExpanderError::RankPublicInputCount {
+            expected: 2,
+            found: 3,
+        })
+    ));
+
+    // the public inputs the package records for the other rank are not taken from the prover
+    let mut circuit = executor::load_circuit::<C>(&circuit_path).unwrap();
+    let rank_public_inputs =
+        executor::load_rank_public_inputs(&mut circuit, &std::fs::read(ranks_path(2)).unwrap())
+            .unwrap();
+    let mut forged = rank_public_inputs.concat();
+    forged.push(<C as GKRConfig>::SimdCircuitField::ONE);
+    let forged = distributed.clone().with_public_input::<C>(&forged).unwrap();
+    assert_eq!(
+        executor::try_verify_package_of_ranks(
+            &config,
+            &mut circuit,
+            Some(&rank_public_inputs),
+            &forged
+        )
+        .unwrap(),
+        Err(VerificationError::PublicInputMismatch)
+    );
+
+    // no prover runs on a world size other than a power of 2
+    let distributed = ProofPackage {
+        mpi_world_size: Some(3),
+        ..package.clone()
+    };
+    assert!(matches!(
+        executor::verify_package(&config, &circuit_path, &witness_path, &distributed),
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::InvalidMPIWorldSize(3)
+        ))
+    ));
+
+    // the packages before the world size was recorded are verified on any number of processes
+    let legacy = ProofPackage {
+        mpi_world_size: None,
+        public_input: None,
+        ..package
+    };
+    assert!(executor::verify_package(&config, &circuit_path, &witness_path, &legacy).unwrap());
+}
+
+#[test]
+fn test_prover_world_size_not_power_of_two() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(3));
+    assert!(matches!(
+        Prover::new(&config),
+        Err(ConfigError::WorldSizeNotPowerOfTwo(3))
+    ));
+}
+
+#[test]
+fn test_prover_polynomial_commitment_not_implemented() {
+    // past Config::new_with_pcs, the prover refuses the commitment itself
+    let config = Config::<C> {
+        polynomial_commitment_type: PolynomialCommitmentType::KZG,
+        ..Config::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1))
+    };
+    assert!(matches!(
+        Prover::new(&config),
+        Err(ConfigError::PolynomialCommitmentUnavailable(
+            PolynomialCommitmentType::KZG
+        ))
+    ));
+}
+
+#[test]
+fn test_executor_gathered_public_input() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    let mut circuit =
+        executor::load_circuit_and_witness::<C>(&circuit_path, &witness_path).unwrap();
+    assert_eq!(
+        package.public_input::<C>().unwrap(),
+        Some(circuit.public_input.clone())
+    );
+    assert_eq!(
+        executor::try_verify_package(&config, &circuit_path, &witness_path, &package).unwrap(),
+        Ok(())
+    );
+
+    // the public inputs of the package must be the ones of the witness
+    let (claimed_v, proof) = executor::unpack_proof(&config, &package).unwrap();
+    let mut public_input = circuit.public_input.clone();
+    public_input.push(<C as GKRConfig>::SimdCircuitField::ONE);
+    assert_eq!(
+        Verifier::new(&config).try_verify_gathered(&mut circuit, &public_input, &claimed_v, &proof),
+        Err(VerificationError::PublicInputMismatch)
+    );
+}
+
+#[test]
+fn test_executor_verify_public_input() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let public_input_path = std::env::temp_dir()
+        .join(format!("{}_public_input.txt", std::process::id()))
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    executor::write_public_input::<C>(&circuit_path, &witness_path, &public_input_path).unwrap();
+    assert!(
+        std::fs::metadata(&public_input_path).unwrap().len()
+            < std::fs::metadata(&witness_path).unwrap().len()
+    );
+    assert!(executor::verify(&config, &circuit_path, &public_input_path, &proof_bytes).unwrap());
+    // not a witness to prove with
+    assert!(matches!(
+        executor::prove(&config, &circuit_path, &public_input_path),
+        Err(ExpanderError::WitnessLoad(_))
+    ));
+    std::fs::remove_file(&public_input_path).unwrap();
+}
+
+#[test]
+fn test_executor_input_commitment() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let file = |name: &str| {
+        std::env::temp_dir()
+            .join(format!("{}_{}", std::process::id(), name))
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+
+    // the witness of another first private input, of the same public inputs
+    let mut other_witness = std::fs::read(&witness_path).unwrap();
+    let offset = WITNESS_HEADER_SIZE + 3 * 8 + 32;
+    let size = <C as GKRConfig>::CircuitField::SERIALIZED_SIZE;
+    let value =
+        <C as GKRConfig>::CircuitField::deserialize_from(&other_witness[offset..offset + size])
+            .unwrap();
+    let mut value_bytes = vec![];
+    (value + <C as GKRConfig>::CircuitField::ONE)
+        .serialize_into(&mut value_bytes)
+        .unwrap();
+    other_witness[offset..offset + size].copy_from_slice(&value_bytes);
+    let other_path = file("other_witness.txt");
+    std::fs::write(&other_path, &other_witness).unwrap();
+
+    let prove = |witness_path: &str| {
+        let (bytes, _) = executor::prove_with_compression(
+            &config,
+            &circuit_path,
+            witness_path,
+            CompressionCodec::None,
+            true,
+        )
+        .unwrap();
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap()
+    };
+    let package = prove(&witness_path);
+    let other_package = prove(&other_path);
+    assert_eq!(
+        package.public_input_commitment,
+        other_package.public_input_commitment
+    );
+    assert!(package.private_input_commitment.is_some());
+    assert_ne!(
+        package.private_input_commitment,
+        other_package.private_input_commitment
+    );
+    for (path, package) in [(&witness_path, &package), (&other_path, &other_package)] {
+        assert_eq!(
+            executor::try_verify_package(&config, &circuit_path, path, package).unwrap(),
+            Ok(())
+        );
+    }
+
+    // swapped, the proofs fail before the sumchecks
+    let mismatch = Err(VerificationError::InputCommitmentMismatch("private inputs"));
+    for (path, package) in [(&witness_path, &other_package), (&other_path, &package)] {
+        assert_eq!(
+            executor::try_verify_package(&config, &circuit_path, path, package).unwrap(),
+            mismatch
+        );
+        let witness = std::fs::read(path).unwrap();
+        assert_eq!(
+            executor::try_verify_package_bytes(&config, &circuit_path, &witness, package).unwrap(),
+            mismatch
+        );
+    }
+    // which the sumchecks, of the public inputs alone, do not tell
+    let legacy = ProofPackage {
+        public_input_commitment: None,
+        private_input_commitment: None,
+        ..other_package
+    };
+    assert_eq!(
+        executor::try_verify_package(&config, &circuit_path, &witness_path, &legacy).unwrap(),
+        Ok(())
+    );
+
+    // the commitment of the private inputs is left unchecked without them
+    let public_input_path = file("committed_public_input.txt");
+    executor::write_public_input::<C>(&circuit_path, &other_path, &public_input_path).unwrap();
+    assert_eq!(
+        executor::try_verify_package(&config, &circuit_path, &public_input_path, &package).unwrap(),
+        Ok(())
+    );
+
+    // but not the one of the public inputs
+    let mut circuit =
+        executor::load_circuit_and_witness::<C>(&circuit_path, &witness_path).unwrap();
+    assert_eq!(
+        executor::check_public_input_commitment(&circuit, &package),
+        Ok(())
+    );
+    circuit
+        .public_input
+        .push(<C as GKRConfig>::SimdCircuitField::ONE);
+    assert_eq!(
+        executor::check_public_input_commitment(&circuit, &package),
+        Err(VerificationError::InputCommitmentMismatch("public inputs"))
+    );
+    assert_eq!(
+        executor::try_verify_package_on(&config, &mut circuit, &package).unwrap(),
+        Err(VerificationError::InputCommitmentMismatch("public inputs"))
+    );
+
+    std::fs::remove_file(&other_path).unwrap();
+    std::fs::remove_file(&public_input_path).unwrap();
+}
+
+#[test]
+fn test_executor_verify_batch() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    let mut claimed_v = package.claimed_v::<C>().unwrap();
+    claimed_v[0] += <C as GKRConfig>::ChallengeField::ONE;
+    let wrong_bytes =
+        executor::package_proof(&config, &package.proof, &claimed_v, CompressionCodec::None)
+            .unwrap();
+
+    let dir = std::env::temp_dir();
+    let file = |name: &str| {
+        dir.join(format!("{}_{}", std::process::id(), name))
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+    let proofs = [
+        (file("batch_valid.bin"), proof_bytes.clone()),
+        (file("batch_wrong.bin"), wrong_bytes),
+        (file("batch_truncated.bin"), proof_bytes[..20].to_vec()),
+    ];
+    let mut manifest = "# witness proof\n\n".to_string();
+    for (proof_path, bytes) in &proofs {
+        std::fs::write(proof_path, bytes).unwrap();
+        manifest += &format!("{} {}\n", witness_path, proof_path);
+    }
+    let manifest_path = file("batch_manifest.txt");
+    std::fs::write(&manifest_path, manifest).unwrap();
+
+    let pairs = executor::read_batch_manifest(&manifest_path).unwrap();
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[0], (witness_path.clone(), proofs[0].0.clone()));
+    let results = executor::verify_batch(&config, &circuit_path, &pairs, false).unwrap();
+    assert!(matches!(results[0], Ok(true)));
+    assert!(matches!(results[1], Ok(false)));
+    assert!(matches!(
+        results[2],
+        Err(ExpanderError::ProofDeserialize(
+            ProofPackageError::Truncated
+        ))
+    ));
+
+    std::fs::write(&manifest_path, format!("{}\n", witness_path)).unwrap();
+    assert!(matches!(
+        executor::read_batch_manifest(&manifest_path),
+        Err(ExpanderError::InvalidManifestLine { line: 1 })
+    ));
+
+    for (proof_path, _) in &proofs {
+        std::fs::remove_file(proof_path).unwrap();
+    }
+    std::fs::remove_file(&manifest_path).unwrap();
+}
+
+/// The witness and proof piped to `verify - -`, framed as the bodies of `/verify`.
+#[test]
+fn test_executor_verify_piped() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let witness_bytes = std::fs::read(&witness_path).unwrap();
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    let mut body = vec![];
+    body.extend_from_slice(&(witness_bytes.len() as u64).to_le_bytes());
+    body.extend_from_slice(&(proof_bytes.len() as u64).to_le_bytes());
+    body.extend_from_slice(&witness_bytes);
+    body.extend_from_slice(&proof_bytes);
+
+    let (witness, proof) = executor::split_witness_and_proof(&body).unwrap();
+    assert_eq!((witness, proof), (&witness_bytes[..], &proof_bytes[..]));
+    let package = ProofPackage::deserialize_from(&mut &proof[..]).unwrap();
+    assert!(
+        executor::try_verify_package_bytes(&config, &circuit_path, witness, &package)
+            .unwrap()
+            .is_ok()
+    );
+
+    // cut short, the lengths are past the end of the bytes
+    let error = executor::split_witness_and_proof(&body[..body.len() - 1]).unwrap_err();
+    assert!(error.contains("past the end of the body"));
+    assert!(executor::split_witness_and_proof(&body[..15])
+        .unwrap_err()
+        .contains("short of the 16 bytes"));
+    assert!(matches!(
+        executor::try_verify_package_bytes(&config, &circuit_path, &witness[..8], &package),
+        Err(ExpanderError::WitnessLoad(_))
+    ));
+}
+
+#[test]
+fn test_executor_public_values() {
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let mut circuit =
+        executor::load_circuit_and_witness::<C>(&circuit_path, &witness_path).unwrap();
+    let expected = circuit.stats().public_input_size;
+
+    let values = vec![<C as GKRConfig>::CircuitField::from(7u32); expected];
+    let mut bytes = vec![];
+    values.serialize_into(&mut bytes).unwrap();
+    assert_eq!(executor::parse_public_values::<C>(&bytes).unwrap(), values);
+    executor::load_public_values(&mut circuit, &values).unwrap();
+    assert_eq!(circuit.public_input.len(), expected);
+    assert!(circuit
+        .public_input
+        .iter()
+        .all(|value| value.unpack() == vec![values[0]; C::get_field_pack_size()]));
+
+    let mut too_many = values.clone();
+    too_many.push(<C as GKRConfig>::CircuitField::ONE);
+    match executor::load_public_values(&mut circuit, &too_many) {
+        Err(ExpanderError::PublicValuesCount { expected: e, found }) => {
+            assert_eq!((e, found), (expected, expected + 1))
+        }
+        other => panic!("expected a count error, got {:?}", other),
+    }
+    bytes.push(0);
+    assert!(matches!(
+        executor::parse_public_values::<C>(&bytes),
+        Err(ExpanderError::InvalidPublicValues(_))
+    ));
+    assert!(matches!(
+        executor::parse_public_values::<C>(&bytes[..4]),
+        Err(ExpanderError::InvalidPublicValues(_))
+    ));
+
+    let mut body = vec![];
+    body.extend_from_slice(&3u64.to_le_bytes());
+    body.extend_from_slice(&2u64.to_le_bytes());
+    body.extend_from_slice(&[1, 2, 3, 4, 5]);
+    assert_eq!(
+        executor::split_witness_and_public_values(&body).unwrap(),
+        (&[1u8, 2, 3][..], &[4u8, 5][..])
+    );
+    assert!(executor::split_witness_and_public_values(&body[..20])
+        .unwrap_err()
+        .contains("public values of 2 bytes"));
+}
+
+/// The witness file of each witness of the witness file, as the instances of a witness batch.
+fn single_witnesses(witness_bytes: &[u8]) -> Vec<Vec<u8>> {
+    let (header, payload) = witness_bytes.split_at(WITNESS_HEADER_SIZE);
+    let num_witnesses = u64::from_le_bytes(payload[..8].try_into().unwrap()) as usize;
+    let (counts, values) = payload.split_at(3 * 8 + 32);
+    values
+        .chunks_exact(values.len() / num_witnesses)
+        .map(|values| {
+            let mut witness = header.to_vec();
+            witness.extend_from_slice(&1u64.to_le_bytes());
+            witness.extend_from_slice(&counts[8..]);
+            witness.extend_from_slice(values);
+            witness
+        })
+        .collect()
+}
+
+#[test]
+fn test_executor_prove_witness_batch() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let file = |name: &str| {
+        std::env::temp_dir()
+            .join(format!("{}_{}", std::process::id(), name))
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+
+    // one instance past the simd lanes, the second prove call padded with zero witnesses
+    let pack_size = C::get_field_pack_size();
+    let num_instances = pack_size + 1;
+    let witnesses = single_witnesses(&std::fs::read(&witness_path).unwrap());
+    let instances = (0..num_instances)
+        .map(|i| witnesses[i % witnesses.len()].as_slice())
+        .collect::<Vec<_>>();
+    let batch_path = file("witness_batch.txt");
+    std::fs::write(&batch_path, encode_witness_batch(&instances)).unwrap();
+    assert!(executor::is_witness_batch(&batch_path).unwrap());
+    assert!(!executor::is_witness_batch(&witness_path).unwrap());
+
+    let (bytes, stats) = executor::prove_batch_with_compression(
+        &config,
+        &circuit_path,
+        &batch_path,
+        CompressionCodec::None,
+        false,
+    )
+    .unwrap();
+    assert_eq!(stats.len(), 2);
+    let proof_path = file("proof_batch.bin");
+    std::fs::write(&proof_path, &bytes).unwrap();
+    let Ok(ProofFile::Batch(batch)) = executor::read_proof_file(&proof_path, false) else {
+        panic!("expected a proof batch");
+    };
+    assert_eq!(batch.num_instances, num_instances);
+    assert_eq!(batch.packages.len(), 2);
+    let results = executor::verify_proof_batch(&config, &circuit_path, &batch_path, &batch);
+    assert_eq!(results.unwrap(), vec![Ok(()); num_instances]);
+    // the first prove call is of the witnesses of the witness file
+    let first_bytes = batch.packages[0].to_bytes();
+    assert!(executor::verify(&config, &circuit_path, &witness_path, &first_bytes).unwrap());
+
+    // the instances of a proof that does not verify fail, and only those
+    let mut wrong = batch.clone();
+    let mut claimed_v = wrong.packages[1].claimed_v::<C>().unwrap();
+    claimed_v[0] += <C as GKRConfig>::ChallengeField::ONE;
+    let wrong_bytes = executor::package_proof(
+        &config,
+        &wrong.packages[1].proof,
+        &claimed_v,
+        CompressionCodec::None,
+    )
+    .unwrap();
+    wrong.packages[1] = ProofPackage::deserialize_from(&mut wrong_bytes.as_slice()).unwrap();
+    let results = executor::verify_proof_batch(&config, &circuit_path, &batch_path, &wrong);
+    let results = results.unwrap();
+    assert!(results[..pack_size].iter().all(Result::is_ok));
+    assert!(results[pack_size].is_err());
+
+    executor::write_proof_package(&proof_path, bytes, true).unwrap();
+    assert!(matches!(
+        executor::read_proof_file(&proof_path, true),
+        Ok(ProofFile::Batch(hex)) if hex.packages == batch.packages
+    ));
+    let mut fewer = batch.clone();
+    fewer.num_instances -= 1;
+    assert!(matches!(
+        executor::verify_proof_batch(&config, &circuit_path, &batch_path, &fewer),
+        Err(ExpanderError::ProofBatchInstances {
+            witness,
+            proof
+        }) if witness == num_instances && proof == num_instances - 1
+    ));
+    let mut missing = batch.clone();
+    missing.packages.pop();
+    assert!(matches!(
+        executor::verify_proof_batch(&config, &circuit_path, &batch_path, &missing),
+        Err(ExpanderError::ProofBatchCount {
+            expected: 2,
+            found: 1
+        })
+    ));
+
+    std::fs::remove_file(&batch_path).unwrap();
+    std::fs::remove_file(&proof_path).unwrap();
+}
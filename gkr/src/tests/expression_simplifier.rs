@@ -0,0 +1,125 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+
+use super::common::{field_gate, C, F};
+use crate::{CircuitExpressionSimplifier, SimplificationRule};
+
+/// Layer 0 outputs the constant 1 on wire 0, relays the inputs 0 and 1 on wires 1 and 2, and
+/// leaves wire 3 at zero. Each gate of layer 1 but the last one is redundant.
+fn test_circuit() -> Circuit<C> {
+    let one = F::ONE;
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                add: vec![field_gate([0], 1, one), field_gate([1], 2, one)],
+                const_: vec![field_gate([], 0, one)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![
+                    // a * 1
+                    field_gate([0, 1], 0, F::from(2u32)),
+                    // a * 0
+                    field_gate([3, 2], 1, one),
+                    field_gate([1, 2], 3, one),
+                ],
+                add: vec![
+                    // a - a
+                    field_gate([2], 2, one),
+                    field_gate([2], 2, -one),
+                    // a + 0
+                    field_gate([1], 1, F::ZERO),
+                ],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit
+}
+
+#[test]
+fn test_expression_simplifier() {
+    let mut circuit = test_circuit();
+    let simplifier = CircuitExpressionSimplifier::<C>::new();
+
+    assert_eq!(simplifier.simplify(&mut circuit), 5);
+
+    let layer = &circuit.layers[1];
+    assert_eq!(layer.mul.len(), 1);
+    assert_eq!(layer.mul[0].i_ids, [1, 2]);
+    assert_eq!(layer.add.len(), 1);
+    assert_eq!(layer.add[0].i_ids, [1]);
+    assert_eq!(layer.add[0].o_id, 0);
+    assert_eq!(layer.add[0].coef, F::from(2u32));
+    // the input layer is left as is
+    assert_eq!(circuit.layers[0].add.len(), 2);
+
+    // nothing left to simplify
+    assert_eq!(simplifier.simplify(&mut circuit), 0);
+}
+
+#[test]
+fn test_expression_simplifier_keeps_random_coefs() {
+    let mut circuit = test_circuit();
+    circuit.layers[1]
+        .mul
+        .iter_mut()
+        .for_each(|g| g.coef_type = CoefType::Random);
+    circuit.identify_rnd_coefs();
+
+    // only the add gates are rewritten
+    let simplifier = CircuitExpressionSimplifier::<C>::new();
+    assert_eq!(simplifier.simplify(&mut circuit), 3);
+    assert_eq!(circuit.layers[1].mul.len(), 3);
+    assert!(circuit.layers[1].add.is_empty());
+    assert!(circuit.rnd_coefs_identified);
+}
+
+/// Merges the add gates between the same wires.
+struct MergeAddRule;
+
+impl SimplificationRule<C> for MergeAddRule {
+    fn name(&self) -> &str {
+        "a + a = 2a"
+    }
+
+    fn apply(&self, layer: &mut CircuitLayer<C>, _: &[Option<F>]) -> usize {
+        let n = layer.add.len();
+        let mut merged: Vec<Gate<C, 1>> = vec![];
+        for g in layer.add.drain(..) {
+            match merged
+                .iter_mut()
+                .find(|m| m.i_ids == g.i_ids && m.o_id == g.o_id)
+            {
+                Some(m) => m.coef += g.coef,
+                None => merged.push(g),
+            }
+        }
+        layer.add = merged;
+        n - layer.add.len()
+    }
+}
+
+#[test]
+fn test_expression_simplifier_custom_rule() {
+    let mut circuit = test_circuit();
+    circuit.layers[1].add = vec![field_gate([1], 1, F::ONE), field_gate([1], 1, F::ONE)];
+
+    let mut simplifier = CircuitExpressionSimplifier::<C>::empty();
+    simplifier.add_rule(Box::new(MergeAddRule));
+    assert_eq!(simplifier.rule_names(), vec!["a + a = 2a"]);
+
+    assert_eq!(simplifier.simplify(&mut circuit), 1);
+    assert_eq!(circuit.layers[1].add.len(), 1);
+    assert_eq!(circuit.layers[1].add[0].coef, F::from(2u32));
+    assert_eq!(circuit.layers[1].mul.len(), 3);
+}
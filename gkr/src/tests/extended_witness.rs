@@ -0,0 +1,42 @@
+use arith::Field;
+use config::{GKRConfig, M31ExtConfigSha2};
+
+use crate::SumcheckExtendedWitness;
+
+type F = <M31ExtConfigSha2 as GKRConfig>::ChallengeField;
+
+#[test]
+fn test_extended_witness() {
+    let mut rng = rand::thread_rng();
+    let values = (0..5)
+        .map(|_| F::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let mut witness = SumcheckExtendedWitness::from_witness(&values, 4);
+    assert_eq!(witness.num_vars(), 4);
+    assert_eq!(witness.evals.len(), 16);
+    assert_eq!(witness.sum(), values.iter().copied().sum::<F>());
+
+    let point = (0..4)
+        .map(|_| F::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let expected = witness.evaluate(&point);
+    assert_eq!(witness.evaluate_sparse(&point), expected);
+
+    // a sumcheck over the witness polynomial, one round per variable
+    let mut claim = witness.sum();
+    for r in &point {
+        let (p0, p1) = witness.round_evals();
+        assert_eq!(p0 + p1, claim);
+        claim = p0 + (p1 - p0) * *r;
+        witness.fix_first_variable(r);
+    }
+    assert_eq!(witness.num_vars(), 0);
+    assert_eq!(witness.evals[0], expected);
+    assert_eq!(claim, expected);
+}
+
+#[test]
+#[should_panic]
+fn test_extended_witness_too_large() {
+    SumcheckExtendedWitness::from_witness(&[F::ONE; 5], 2);
+}
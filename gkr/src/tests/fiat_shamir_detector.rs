@@ -0,0 +1,93 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+use transcript::{AuditedTranscript, BytesHashTranscript, SHA256hasher, Transcript};
+
+use super::common::{gate, C};
+use crate::{Prover, WeakFSWarning, WeakFiatShamirDetector};
+
+type F = <C as GKRConfig>::ChallengeField;
+type T = AuditedTranscript<F, BytesHashTranscript<F, SHA256hasher>>;
+
+#[test]
+fn test_prover_transcript() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![gate([0, 1], 0, 1), gate([2, 3], 1, 3)],
+            add: vec![gate([1], 0, 2)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (_, proof) = prover.prove_silent(&mut circuit.clone());
+    let mut transcript = T::new();
+    let (_, audited_proof) = prover.prove_with_transcript(&mut circuit, &mut transcript);
+    assert_eq!(audited_proof.bytes, proof.bytes);
+
+    // the first message is the commitment to the input, which does not bind the circuit
+    let warnings = WeakFiatShamirDetector::analyze(transcript.log());
+    assert_eq!(warnings, vec![WeakFSWarning::MissingCircuitCommitment]);
+
+    let mut transcript = T::with_statement(&circuit.hash());
+    transcript.append_u8_slice(&circuit.hash());
+    transcript.append_field_element(&F::ONE);
+    transcript.generate_challenge_field_element();
+    assert!(WeakFiatShamirDetector::analyze(transcript.log()).is_empty());
+}
+
+#[test]
+fn test_weak_transcripts() {
+    let statement = b"statement";
+
+    // challenges depending on no message
+    let mut transcript = T::with_statement(statement);
+    transcript.generate_challenge_field_element();
+    transcript.append_u8_slice(statement);
+    transcript.generate_challenge_field_element();
+    assert_eq!(
+        WeakFiatShamirDetector::analyze(transcript.log()),
+        vec![WeakFSWarning::ChallengeWithoutContext { event: 0 }]
+    );
+
+    // replayed rounds, events 0 and 1 forming the first round
+    let mut transcript = T::with_statement(statement);
+    transcript.append_u8_slice(statement);
+    transcript.append_field_element(&F::ONE);
+    transcript.generate_challenge_field_element();
+    transcript.append_u8_slice(statement);
+    transcript.append_field_element(&F::ONE);
+    transcript.generate_challenge_field_element();
+    transcript.append_field_element(&F::ONE);
+    transcript.generate_challenge_field_element();
+    assert_eq!(
+        WeakFiatShamirDetector::analyze(transcript.log()),
+        vec![WeakFSWarning::RepeatedMessage {
+            first: 0,
+            second: 3
+        }]
+    );
+
+    // a message lost when setting the state, and no statement
+    let mut transcript = T::new();
+    transcript.append_u8_slice(statement);
+    let state = transcript.hash_and_return_state();
+    transcript.append_field_element(&F::ONE);
+    transcript.set_state(&state);
+    transcript.generate_challenge_field_element();
+    assert_eq!(
+        WeakFiatShamirDetector::analyze(transcript.log()),
+        vec![
+            WeakFSWarning::MissingCircuitCommitment,
+            WeakFSWarning::DiscardedMessages { event: 3 }
+        ]
+    );
+}
@@ -0,0 +1,95 @@
+use arith::ExtensionField;
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+use transcript::Proof;
+
+use super::common::C;
+use crate::{
+    executor::{self, ExpanderError},
+    utils::*,
+    FieldProofError, FieldProofManifest, ProofFieldElements, ProofLayout, ProofPackage, Prover,
+    Verifier,
+};
+
+type ChallengeField = <C as GKRConfig>::ChallengeField;
+
+#[test]
+fn test_field_proof_round_trip() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = Circuit::<C>::load_circuit(&("../".to_owned() + KECCAK_M31_CIRCUIT));
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    let layout = ProofLayout::new(&circuit, &config);
+    let elements = proof.to_field_elements::<C>(&layout).unwrap();
+    let manifest = FieldProofManifest::new::<C>(&layout, claimed_v.len());
+    assert_eq!(manifest.num_elements(), elements.len() + claimed_v.len());
+    assert_eq!(manifest.layers.len(), circuit.layers.len());
+    assert_eq!(
+        manifest.commitment,
+        circuit.layers[0].input_vals.len() * <C as GKRConfig>::get_field_pack_size()
+    );
+
+    let decoded = Proof::from_field_elements::<C>(&elements, &layout).unwrap();
+    assert_eq!(decoded, proof);
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &decoded));
+
+    assert!(matches!(
+        Proof::from_field_elements::<C>(&elements[1..], &layout),
+        Err(FieldProofError::ElementCountMismatch { .. })
+    ));
+    // the commitment is of circuit field elements only
+    let mut lifted = elements.clone();
+    lifted[3] = ChallengeField::X;
+    assert!(matches!(
+        Proof::from_field_elements::<C>(&lifted, &layout),
+        Err(FieldProofError::NotInCircuitField(3))
+    ));
+    let truncated = Proof {
+        bytes: proof.bytes[1..].to_vec(),
+    };
+    assert!(matches!(
+        truncated.to_field_elements::<C>(&layout),
+        Err(FieldProofError::ProofSizeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_field_proof_files() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+    let proof_path = std::env::temp_dir().join(format!("field_proof_{}.bin", std::process::id()));
+    let proof_path = proof_path.to_str().unwrap();
+
+    let proof_bytes = executor::prove(&config, &circuit_path, &witness_path).unwrap();
+    executor::write_field_proof(&config, &circuit_path, &proof_bytes, proof_path).unwrap();
+    let package = executor::read_field_proof(&config, &circuit_path, proof_path).unwrap();
+    let original = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(package.proof, original.proof);
+    assert_eq!(package.claimed_v, original.claimed_v);
+    assert!(executor::verify_package(&config, &circuit_path, &witness_path, &package).unwrap());
+
+    // the manifest of another circuit is refused
+    let manifest_path = executor::field_proof_manifest_path(proof_path);
+    let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+    let mut manifest: FieldProofManifest = serde_json::from_str(&manifest).unwrap();
+    manifest.layers.pop();
+    std::fs::write(&manifest_path, serde_json::to_string(&manifest).unwrap()).unwrap();
+    assert!(matches!(
+        executor::read_field_proof(&config, &circuit_path, proof_path),
+        Err(ExpanderError::FieldProof(FieldProofError::ManifestMismatch))
+    ));
+
+    let square = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    assert!(matches!(
+        executor::read_field_proof(&square, &circuit_path, proof_path),
+        Err(ExpanderError::FieldProof(FieldProofError::UnsupportedProof))
+    ));
+    std::fs::remove_file(proof_path).unwrap();
+    std::fs::remove_file(manifest_path).unwrap();
+}
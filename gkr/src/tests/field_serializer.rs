@@ -0,0 +1,35 @@
+use arith::{Field, FieldSerde};
+use halo2curves::bn256::Fr;
+use mersenne31::M31Ext3;
+
+use crate::VecFieldSerializer;
+
+fn check_batch<F: Field + FieldSerde + Send + Sync>(n: usize) {
+    let mut rng = rand::thread_rng();
+    let elements = (0..n)
+        .map(|_| F::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut expected = vec![];
+    elements
+        .iter()
+        .for_each(|e| e.serialize_into(&mut expected).unwrap());
+
+    let mut bytes = vec![];
+    VecFieldSerializer::serialize_batch(&elements, &mut bytes).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(
+        VecFieldSerializer::<F>::deserialize_batch(&bytes).unwrap(),
+        elements
+    );
+    assert!(VecFieldSerializer::<F>::deserialize_batch(&bytes[1..]).is_err());
+}
+
+#[test]
+fn test_vec_field_serializer() {
+    // on both sides of the parallel threshold
+    for n in [0, 1, 100, (1 << 15) + 3] {
+        check_batch::<M31Ext3>(n);
+        check_batch::<Fr>(n);
+    }
+}
@@ -0,0 +1,72 @@
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use config::GKRConfig;
+
+use super::common::C;
+use crate::CircuitFingerprintRegistry;
+
+fn circuit(coef: u32) -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            add: vec![Gate {
+                i_ids: [1],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: <C as GKRConfig>::CircuitField::from(coef),
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit
+}
+
+#[test]
+fn test_fingerprint_registry() {
+    let mut registry = CircuitFingerprintRegistry::new();
+    assert!(registry.is_empty());
+    assert_eq!(registry.check_duplicate(&circuit(1), "a.txt"), None);
+    // registering the same file again
+    assert_eq!(registry.check_duplicate(&circuit(1), "a.txt"), None);
+    assert_eq!(registry.check_duplicate(&circuit(2), "b.txt"), None);
+    assert_eq!(registry.len(), 2);
+
+    // the witness is not part of the fingerprint
+    let mut duplicate = circuit(1);
+    duplicate.set_random_input_for_test();
+    assert_eq!(registry.check_duplicate(&duplicate, "c.txt"), Some("a.txt"));
+    assert_eq!(registry.len(), 2);
+
+    let fingerprint = CircuitFingerprintRegistry::fingerprint(&circuit(2));
+    assert_ne!(
+        fingerprint,
+        CircuitFingerprintRegistry::fingerprint(&circuit(1))
+    );
+    assert_eq!(registry.get(&fingerprint), Some("b.txt"));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_fingerprint_registry_sqlite() {
+    let db_path = std::env::temp_dir().join(format!(
+        "expander_fingerprints_{}.sqlite",
+        std::process::id()
+    ));
+    let db_path = db_path.to_str().unwrap();
+    let _ = std::fs::remove_file(db_path);
+
+    let mut registry = CircuitFingerprintRegistry::new();
+    registry.check_duplicate(&circuit(1), "a.txt");
+    registry.save_sqlite(db_path).unwrap();
+    let mut other = CircuitFingerprintRegistry::new();
+    other.check_duplicate(&circuit(2), "b.txt");
+    other.save_sqlite(db_path).unwrap();
+
+    let mut loaded = CircuitFingerprintRegistry::open_sqlite(db_path).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded.check_duplicate(&circuit(2), "c.txt"), Some("b.txt"));
+    std::fs::remove_file(db_path).unwrap();
+}
@@ -0,0 +1,348 @@
+use arith::FieldSerde;
+use circuit::{
+    migrate_v0_to_v1, Circuit, CircuitError, CircuitLayer, CoefType, Gate, WitnessError,
+};
+use config::{
+    FieldType, GKRConfig, GoldilocksExtConfigSha2, SENTINEL_BABYBEAR, SENTINEL_BN254, SENTINEL_GF2,
+    SENTINEL_GOLDILOCKS, SENTINEL_M31,
+};
+
+use super::common::C;
+use crate::{
+    detect_field_type, detect_field_type_from_circuit_file, utils::KECCAK_M31_CIRCUIT,
+    CircuitLoadError, FuzzSafeCircuitLoader, MAX_FLATTENED_GATES, MAX_SEGMENT_DEPTH, MAX_VAR_NUM,
+};
+
+fn put_usizes(bytes: &mut Vec<u8>, values: &[usize]) {
+    for value in values {
+        value.serialize_into(&mut *bytes).unwrap();
+    }
+}
+
+/// The header of an M31 circuit of a single output, up to its number of segments.
+fn circuit_header(num_segments: usize) -> Vec<u8> {
+    let mut bytes = b"CIRCUIT6".to_vec();
+    bytes.extend_from_slice(&SENTINEL_M31);
+    put_usizes(&mut bytes, &[0, 1, 0, num_segments]);
+    bytes
+}
+
+/// The bytes of a circuit of segments of a single input and output, the last one being its only
+/// layer. A segment is of children, each its id and a number of allocations at offset 0, and of a
+/// number of random constant gates.
+fn circuit_of_segments(segments: &[(Vec<(usize, usize)>, usize)]) -> Vec<u8> {
+    let mut bytes = circuit_header(segments.len());
+    for (children, num_consts) in segments {
+        put_usizes(&mut bytes, &[1, 1, children.len()]);
+        for &(child, num_allocs) in children {
+            put_usizes(&mut bytes, &[child, num_allocs]);
+            for _ in 0..num_allocs {
+                put_usizes(&mut bytes, &[0, 0]);
+            }
+        }
+        put_usizes(&mut bytes, &[0, 0, *num_consts]);
+        for _ in 0..*num_consts {
+            put_usizes(&mut bytes, &[0]);
+            bytes.push(2);
+        }
+        put_usizes(&mut bytes, &[0]);
+    }
+    put_usizes(&mut bytes, &[1, segments.len() - 1]);
+    bytes
+}
+
+/// A gate under `depth` segments, each allocating the one below `num_allocs` times.
+fn nested_segments(depth: usize, num_allocs: usize) -> Vec<u8> {
+    let mut segments = vec![(vec![], 1)];
+    segments.extend((0..depth).map(|child| (vec![(child, num_allocs)], 0)));
+    circuit_of_segments(&segments)
+}
+
+#[test]
+fn test_fuzz_safe_loader_rejects_garbage() {
+    let empty: &[u8] = &[];
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(empty),
+        Err(CircuitLoadError::ParseError(_))
+    ));
+
+    let garbage = vec![0xabu8; 1024];
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(garbage.as_slice()),
+        Err(CircuitLoadError::ParseError(_))
+    ));
+}
+
+#[test]
+fn test_fuzz_safe_loader_validation() {
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            add: vec![Gate {
+                i_ids: [1],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: Default::default(),
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(FuzzSafeCircuitLoader::validate(&circuit).is_ok());
+
+    circuit.layers[0].add[0].i_ids = [2];
+    assert!(matches!(
+        FuzzSafeCircuitLoader::validate(&circuit),
+        Err(CircuitLoadError::InvalidCircuit(_))
+    ));
+
+    // layers of 2^MAX_VAR_NUM wires, and no more
+    circuit.layers[0].add[0].i_ids = [1];
+    circuit.layers[0].output_var_num = MAX_VAR_NUM;
+    assert!(FuzzSafeCircuitLoader::validate(&circuit).is_ok());
+    circuit.layers[0].input_var_num = MAX_VAR_NUM + 1;
+    assert!(matches!(
+        FuzzSafeCircuitLoader::validate(&circuit),
+        Err(CircuitLoadError::InvalidCircuit(_))
+    ));
+}
+
+/// The witness bytes of `num_witnesses` witnesses of zeros, header included.
+fn witness_bytes(num_witnesses: usize, num_private_inputs_per_witness: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    num_private_inputs_per_witness
+        .serialize_into(&mut bytes)
+        .unwrap();
+    0usize.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for _ in 0..num_witnesses * num_private_inputs_per_witness {
+        <C as GKRConfig>::CircuitField::default()
+            .serialize_into(&mut bytes)
+            .unwrap();
+    }
+    migrate_v0_to_v1(&bytes, FieldType::M31)
+}
+
+#[test]
+fn test_try_load_errors() {
+    assert!(matches!(
+        Circuit::<C>::try_load_circuit("no/such/circuit.txt"),
+        Err(CircuitError::OtherError(_))
+    ));
+
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(
+        circuit.try_load_witness_file("no/such/witness.txt"),
+        Err(CircuitError::OtherError(_))
+    ));
+
+    let pack_size = C::get_field_pack_size();
+    assert!(circuit
+        .try_load_witness_bytes(&witness_bytes(pack_size, 2), false)
+        .is_ok());
+    assert_eq!(circuit.layers[0].input_vals.len(), 2);
+    assert!(matches!(
+        circuit.try_load_witness_bytes(&witness_bytes(pack_size, 4), false),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::PrivateInputMismatch {
+                expected: 2,
+                got: 4
+            }
+        ))
+    ));
+    assert!(matches!(
+        circuit.try_load_witness_bytes(&witness_bytes(1, 2), false),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::NotEnoughWitnesses { got: 1, .. }
+        ))
+    ));
+    assert!(circuit
+        .try_load_witness_bytes(&witness_bytes(1, 2), true)
+        .is_ok());
+}
+
+#[test]
+fn test_detect_field_type() {
+    assert!(matches!(
+        detect_field_type(&[]),
+        Err(CircuitLoadError::FileTooShort {
+            actual: 0,
+            required: 40
+        })
+    ));
+    assert!(matches!(
+        detect_field_type(&[0; 39]),
+        Err(CircuitLoadError::FileTooShort {
+            actual: 39,
+            required: 40
+        })
+    ));
+
+    let header = |sentinel: [u8; 32]| {
+        let mut bytes = 1usize.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&sentinel);
+        // the circuit itself is not read
+        bytes.extend_from_slice(&[0xff; 16]);
+        bytes
+    };
+    for (sentinel, field_type) in [
+        (SENTINEL_M31, FieldType::M31),
+        (SENTINEL_BN254, FieldType::BN254),
+        (SENTINEL_GF2, FieldType::GF2),
+        (SENTINEL_GOLDILOCKS, FieldType::Goldilocks),
+        (SENTINEL_BABYBEAR, FieldType::BabyBear),
+    ] {
+        assert_eq!(detect_field_type(&header(sentinel)).unwrap(), field_type);
+    }
+
+    let mut sentinel = SENTINEL_M31;
+    sentinel[0] = 0;
+    assert!(matches!(
+        detect_field_type(&header(sentinel)),
+        Err(CircuitLoadError::UnknownFieldSentinel { bytes }) if bytes == sentinel
+    ));
+    assert_eq!(
+        detect_field_type(&header(sentinel))
+            .unwrap_err()
+            .to_string(),
+        format!("unknown field sentinel 00ffff7f{}", "00".repeat(28))
+    );
+
+    let path = std::env::temp_dir().join(format!("short_circuit_{}.txt", std::process::id()));
+    std::fs::write(&path, [0; 12]).unwrap();
+    assert!(matches!(
+        detect_field_type_from_circuit_file(path.to_str().unwrap()),
+        Err(CircuitLoadError::FileTooShort {
+            actual: 12,
+            required: 40
+        })
+    ));
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        detect_field_type_from_circuit_file("no/such/circuit.txt"),
+        Err(CircuitLoadError::Io(_))
+    ));
+}
+
+#[test]
+fn test_load_circuit_from_bytes() {
+    let path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(detect_field_type(&bytes).unwrap(), FieldType::M31);
+    assert_eq!(
+        Circuit::<C>::load_circuit_from_bytes(&bytes).hash(),
+        Circuit::<C>::load_circuit(&path).hash()
+    );
+}
+
+/// A circuit is checked to be of the field of the config before it is parsed.
+#[test]
+fn test_load_circuit_of_another_field() {
+    let path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    assert!(matches!(
+        Circuit::<GoldilocksExtConfigSha2>::try_load_circuit(&path),
+        Err(CircuitError::WrongCircuitField { expected, found })
+            if expected == SENTINEL_GOLDILOCKS && found == SENTINEL_M31
+    ));
+
+    let short = std::env::temp_dir().join(format!("empty_circuit_{}.txt", std::process::id()));
+    for bytes in [&[][..], &[0; 39]] {
+        std::fs::write(&short, bytes).unwrap();
+        assert!(matches!(
+            Circuit::<C>::try_load_circuit(short.to_str().unwrap()),
+            Err(CircuitError::TruncatedCircuitHeader { actual, required: 40 })
+                if actual == bytes.len()
+        ));
+    }
+    std::fs::remove_file(&short).unwrap();
+}
+
+/// A length prefix past the end of the input fails on the first missing element, with nothing
+/// allocated for it.
+#[test]
+fn test_fuzz_safe_loader_huge_length_prefix() {
+    let bytes = circuit_header(usize::MAX);
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(bytes.as_slice()),
+        Err(CircuitLoadError::ParseError(_))
+    ));
+
+    // the allocations of a child segment
+    let mut bytes = circuit_header(1);
+    put_usizes(&mut bytes, &[1, 1, 1, 0, usize::MAX, 0, 0]);
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(bytes.as_slice()),
+        Err(CircuitLoadError::ParseError(_))
+    ));
+}
+
+/// Each segment reusing the one below twice doubles the gates, refused before flattening.
+#[test]
+fn test_fuzz_safe_loader_reused_segments() {
+    let circuit = FuzzSafeCircuitLoader::<C>::load(nested_segments(10, 2).as_slice()).unwrap();
+    assert_eq!(circuit.layers[0].const_.len(), 1 << 10);
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load_with_max_gates(nested_segments(10, 2).as_slice(), 1023),
+        Err(CircuitLoadError::TooManyGates {
+            num_gates: 1024,
+            max: 1023
+        })
+    ));
+
+    // 2^64 gates, of a few kilobytes
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(nested_segments(64, 2).as_slice()),
+        Err(CircuitLoadError::TooManyGates { num_gates: usize::MAX, max })
+            if max == MAX_FLATTENED_GATES
+    ));
+
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(nested_segments(MAX_SEGMENT_DEPTH, 1).as_slice()),
+        Err(CircuitLoadError::SegmentsTooDeep { depth, max: MAX_SEGMENT_DEPTH })
+            if depth == MAX_SEGMENT_DEPTH + 1
+    ));
+}
+
+#[test]
+fn test_fuzz_safe_loader_malformed_segments() {
+    let cycle = circuit_of_segments(&[(vec![(0, 1)], 1)]);
+    assert!(matches!(
+        Circuit::<C>::try_load_circuit_from_reader(cycle.as_slice()),
+        Err(CircuitError::SegmentCycle { id: 0 })
+    ));
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(cycle.as_slice()),
+        Err(CircuitLoadError::InvalidCircuit(_))
+    ));
+
+    let out_of_range = circuit_of_segments(&[(vec![(1, 1)], 1)]);
+    assert!(matches!(
+        Circuit::<C>::try_load_circuit_from_reader(out_of_range.as_slice()),
+        Err(CircuitError::SegmentOutOfRange {
+            id: 1,
+            num_segments: 1
+        })
+    ));
+
+    // an input length of 3
+    let mut not_power_of_two = circuit_of_segments(&[(vec![], 1)]);
+    not_power_of_two[circuit_header(1).len()] = 3;
+    assert!(matches!(
+        Circuit::<C>::try_load_circuit_from_reader(not_power_of_two.as_slice()),
+        Err(CircuitError::MalformedCircuit(_))
+    ));
+    assert!(matches!(
+        FuzzSafeCircuitLoader::<C>::load(not_power_of_two.as_slice()),
+        Err(CircuitLoadError::ParseError(_))
+    ));
+}
@@ -3,17 +3,28 @@ use std::panic::AssertUnwindSafe;
 use std::time::Instant;
 use std::{fs, panic};
 
-use arith::{Field, FieldSerde};
-use circuit::Circuit;
+use arith::{Field, FieldSerde, SimdField};
+use babybear::BabyBear;
+use circuit::{
+    migrate_v0_to_v1, Circuit, CircuitError, CircuitLayer, CoefType, Gate, WitnessError,
+    WITNESS_HEADER_SIZE,
+};
 use config::{
-    root_println, BN254ConfigKeccak, BN254ConfigMIMC5, BN254ConfigSha2, Config, FieldType,
-    GF2ExtConfigKeccak, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigKeccak,
-    M31ExtConfigSha2, MPIConfig,
+    root_println, BN254ConfigKeccak, BN254ConfigMIMC5, BN254ConfigSha2, BabyBearExtConfigSha2,
+    Config, ConfigError, FieldType, GF2ExtConfigKeccak, GF2ExtConfigSha2, GKRConfig, GKRScheme,
+    GoldilocksExtConfigSha2, HashType, M31ExtConfigKeccak, M31ExtConfigSha2, MPIConfig,
+    SENTINEL_BABYBEAR,
 };
 use rand::Rng;
 use sha2::Digest;
+use transcript::Proof;
 
-use crate::{utils::*, Prover, Verifier};
+use crate::{
+    detect_field_type_from_circuit_file,
+    executor::{self, ExpanderError},
+    utils::*,
+    ProofLayout, ProofPackage, Prover, VerificationError, VerificationStatus, Verifier,
+};
 
 #[test]
 fn test_gkr_correctness() {
@@ -92,11 +103,11 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>, write_proof_to:
         .iter()
         .all(|f| f.is_zero()));
 
-    let mut prover = Prover::new(config);
+    let mut prover = Prover::new(config).unwrap();
     prover.prepare_mem(&circuit);
 
     let proving_start = Instant::now();
-    let (claimed_v, proof) = prover.prove(&mut circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
     root_println!(
         config.mpi_config,
         "Proving time: {} μs",
@@ -163,6 +174,782 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>, write_proof_to:
 
         assert!(!final_result,);
         println!("Bad proof rejected.");
-        println!("============== end ===============");
+
+        let report =
+            verifier.verify_report(&mut circuit, &public_input_gathered, &claimed_v, &proof);
+        assert_eq!(report.status, VerificationStatus::Valid);
+        let report =
+            verifier.verify_report(&mut circuit, &public_input_gathered, &claimed_v, &bad_proof);
+        assert!(!report.is_valid());
+        let mut truncated_proof = proof.clone();
+        truncated_proof.bytes.pop();
+        let report = verifier.verify_report(
+            &mut circuit,
+            &public_input_gathered,
+            &claimed_v,
+            &truncated_proof,
+        );
+        assert_eq!(report.status, VerificationStatus::MalformedProof);
+        println!("Verification reports checked.");
+    }
+
+    // every rank passes its public input, the root verifies for all of them
+    let mut claimed_v = claimed_v;
+    let num_claims = (C::get_field_pack_size() * config.mpi_config.world_size())
+        << circuit.layers.last().unwrap().output_var_num;
+    claimed_v.resize(num_claims, C::ChallengeField::ZERO);
+    claimed_v
+        .iter_mut()
+        .for_each(|v| config.mpi_config.root_broadcast(v));
+    assert!(Verifier::new(config).mpi_verify(&mut circuit, &claimed_v, &proof));
+    root_println!(config.mpi_config, "Proof verified across the ranks.");
+    root_println!(config.mpi_config, "============== end ===============");
+}
+
+/// out_0 = in_0 * in_1, out_1 = in_2 + in_3
+fn small_circuit<C: GKRConfig>() -> Circuit<C> {
+    fn gate<C: GKRConfig, const N: usize>(i_ids: [usize; N], o_id: usize) -> Gate<C, N> {
+        Gate {
+            i_ids,
+            o_id,
+            coef_type: CoefType::Constant,
+            coef: C::CircuitField::ONE,
+            gate_type: 0,
+        }
+    }
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![gate([0, 1], 0)],
+            add: vec![gate([2], 1), gate([3], 1)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_prove_batch() {
+    type C = M31ExtConfigSha2;
+    let mut circuit = small_circuit::<C>();
+
+    // the witness of a full simd pack of the inputs 1, 2, 3, 4
+    let num_witnesses = C::get_field_pack_size();
+    let mut witness = vec![];
+    num_witnesses.serialize_into(&mut witness).unwrap();
+    4usize.serialize_into(&mut witness).unwrap();
+    0usize.serialize_into(&mut witness).unwrap();
+    [0u64; 4].serialize_into(&mut witness).unwrap();
+    for _ in 0..num_witnesses {
+        for v in 1..=4u32 {
+            <C as GKRConfig>::CircuitField::from(v)
+                .serialize_into(&mut witness)
+                .unwrap();
+        }
+    }
+    let witnesses = vec![witness; 3];
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    let proofs = prover.prove_batch(&mut circuit, &witnesses);
+    assert_eq!(proofs.len(), witnesses.len());
+
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    for (claimed_v, proof) in &proofs {
+        assert!(verifier.verify(&mut circuit, &public_input, claimed_v, proof));
+        assert_eq!(proof.bytes, proofs[0].1.bytes);
+    }
+}
+
+#[test]
+fn test_verify_batch() {
+    type C = M31ExtConfigSha2;
+    let mut circuit = small_circuit::<C>();
+
+    // full simd packs of the inputs w + 1, w + 2, w + 3, w + 4
+    let num_witnesses = C::get_field_pack_size();
+    let witnesses = (0..5u32)
+        .map(|w| {
+            let mut witness = vec![];
+            num_witnesses.serialize_into(&mut witness).unwrap();
+            4usize.serialize_into(&mut witness).unwrap();
+            0usize.serialize_into(&mut witness).unwrap();
+            [0u64; 4].serialize_into(&mut witness).unwrap();
+            for _ in 0..num_witnesses {
+                for v in 1..=4u32 {
+                    <C as GKRConfig>::CircuitField::from(w + v)
+                        .serialize_into(&mut witness)
+                        .unwrap();
+                }
+            }
+            witness
+        })
+        .collect::<Vec<_>>();
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    let mut inputs = prover
+        .prove_batch(&mut circuit, &witnesses)
+        .into_iter()
+        .map(|(claimed_v, proof)| (circuit.public_input.clone(), claimed_v, proof))
+        .collect::<Vec<_>>();
+    let verifier = Verifier::new(&config);
+    assert_eq!(verifier.verify_batch(&circuit, &inputs), vec![true; 5]);
+    assert!(verifier.verify_batch(&circuit, &[]).is_empty());
+
+    // a wrong claim, a proof of another witness, and a proof that cannot be parsed
+    inputs[1].1[0] += <C as GKRConfig>::ChallengeField::ONE;
+    inputs[2].2 = inputs[3].2.clone();
+    inputs[4].2.bytes.pop();
+    assert_eq!(
+        verifier.verify_batch(&circuit, &inputs),
+        vec![true, false, false, true, false]
+    );
+}
+
+/// The proofs corrupted at a given place are rejected for what is wrong there.
+#[test]
+fn test_verification_errors() {
+    type C = M31ExtConfigSha2;
+    type F = <C as GKRConfig>::ChallengeField;
+    let mut circuit = small_circuit::<C>();
+
+    let num_witnesses = C::get_field_pack_size();
+    let mut witness = vec![];
+    num_witnesses.serialize_into(&mut witness).unwrap();
+    4usize.serialize_into(&mut witness).unwrap();
+    0usize.serialize_into(&mut witness).unwrap();
+    [0u64; 4].serialize_into(&mut witness).unwrap();
+    for _ in 0..num_witnesses {
+        for v in 1..=4u32 {
+            <C as GKRConfig>::CircuitField::from(v)
+                .serialize_into(&mut witness)
+                .unwrap();
+        }
+    }
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let (claimed_v, proof) = Prover::new(&config)
+        .unwrap()
+        .prove_batch(&mut circuit, &[witness])
+        .pop()
+        .unwrap();
+    // the proof of the single layer is its commitment, its 2 rounds over x, 4 over simd, vx,
+    // its 2 rounds over y, and vy
+    let layout = ProofLayout::new(&circuit, &config);
+    assert_eq!(layout.segments.len(), 11);
+    let bumped = |segment: usize| {
+        let offset = layout.segments[..segment]
+            .iter()
+            .map(|segment| layout.segment_size(segment))
+            .sum::<usize>();
+        let mut proof = proof.clone();
+        let value = F::deserialize_from(&proof.bytes[offset..]).unwrap() + F::ONE;
+        value.serialize_into(&mut proof.bytes[offset..]).unwrap();
+        proof
+    };
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    let mut try_verify = |claimed_v: &[F], proof: &Proof| {
+        verifier.try_verify(&mut circuit, &public_input, claimed_v, proof)
+    };
+    assert_eq!(try_verify(&claimed_v, &proof), Ok(()));
+
+    let mut wrong_claim = claimed_v.clone();
+    wrong_claim[0] += F::ONE;
+    assert_eq!(
+        try_verify(&wrong_claim, &proof),
+        Err(VerificationError::ClaimedOutput { layer: 0 })
+    );
+    assert_eq!(
+        try_verify(&claimed_v[1..], &proof),
+        Err(VerificationError::ClaimedValuesCount {
+            expected: claimed_v.len(),
+            found: claimed_v.len() - 1,
+        })
+    );
+    assert_eq!(
+        try_verify(&claimed_v, &bumped(2)),
+        Err(VerificationError::SumcheckRound { layer: 0, round: 1 })
+    );
+    assert_eq!(
+        try_verify(&claimed_v, &bumped(8)),
+        Err(VerificationError::SumcheckRound { layer: 0, round: 6 })
+    );
+    assert_eq!(
+        try_verify(&claimed_v, &bumped(10)),
+        Err(VerificationError::FinalEvaluation { layer: 0 })
+    );
+    let mut truncated_proof = proof.clone();
+    truncated_proof.bytes.pop();
+    assert_eq!(
+        try_verify(&claimed_v, &truncated_proof),
+        Err(VerificationError::ProofSize {
+            expected: proof.bytes.len(),
+            found: proof.bytes.len() - 1,
+        })
+    );
+
+    // the bool wrappers agree
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+    assert!(!verifier.verify(&mut circuit, &public_input, &claimed_v, &bumped(10)));
+    assert_eq!(
+        verifier.try_mpi_verify(&mut circuit, &claimed_v, &bumped(8)),
+        Err(VerificationError::SumcheckRound { layer: 0, round: 6 })
+    );
+    let report = verifier.verify_report(&mut circuit, &public_input, &claimed_v, &bumped(8));
+    assert_eq!(report.status, VerificationStatus::InvalidSumcheck);
+    assert_eq!(report.first_failing_layer, Some(0));
+}
+
+/// A proof that cannot be parsed is rejected by each entry point rather than panicking, and the
+/// public input of a verifier key must be the one its gates read.
+#[test]
+fn test_malformed_proofs() {
+    type C = BN254ConfigSha2;
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = small_circuit::<C>();
+    circuit.layers[0].input_vals = vec![<C as GKRConfig>::SimdCircuitField::ONE; 4];
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let vk = circuit.extract_verifier_key();
+    let verifier = Verifier::new(&config);
+    assert_eq!(
+        verifier.try_verify_with_key(&vk, &[], &claimed_v, &proof),
+        Ok(())
+    );
+
+    // 0xff.. is not a canonical field element, in the commitment, or in the first round
+    let layout = ProofLayout::new(&circuit, &config);
+    let commitment_size = layout.segment_size(&layout.segments[0]);
+    let mut garbage_rounds = proof.clone();
+    garbage_rounds.bytes[commitment_size..].fill(0xff);
+    for garbage in [
+        Proof {
+            bytes: vec![0xff; proof.bytes.len()],
+        },
+        garbage_rounds,
+    ] {
+        assert!(matches!(
+            verifier.try_verify(&mut circuit, &[], &claimed_v, &garbage),
+            Err(VerificationError::MalformedProof(_))
+        ));
+        assert!(matches!(
+            verifier.try_verify_with_key(&vk, &[], &claimed_v, &garbage),
+            Err(VerificationError::MalformedProof(_))
+        ));
+        assert!(!verifier.verify(&mut circuit, &[], &claimed_v, &garbage));
+        assert!(!verifier.verify_with_key(&vk, &[], &claimed_v, &garbage));
     }
+
+    let truncated = Proof {
+        bytes: proof.bytes[..commitment_size - 1].to_vec(),
+    };
+    for short in [&truncated, &Proof::default()] {
+        let size = Err(VerificationError::ProofSize {
+            expected: proof.bytes.len(),
+            found: short.bytes.len(),
+        });
+        assert_eq!(
+            verifier.try_verify(&mut circuit, &[], &claimed_v, short),
+            size
+        );
+        assert_eq!(
+            verifier.try_verify_with_key(&vk, &[], &claimed_v, short),
+            size
+        );
+    }
+
+    // the gates read no public input
+    let one = [<C as GKRConfig>::SimdCircuitField::ONE];
+    assert_eq!(
+        verifier.try_verify_with_key(&vk, &one, &claimed_v, &proof),
+        Err(VerificationError::PublicInputCount {
+            expected: 0,
+            found: 1
+        })
+    );
+    assert!(!verifier.verify_with_key(&vk, &one, &claimed_v, &proof));
+
+    let mut empty = Circuit::<C>::default();
+    assert_eq!(
+        verifier.try_verify(&mut empty, &[], &claimed_v, &Proof::default()),
+        Err(VerificationError::NoLayer)
+    );
+}
+
+/// The claims are the values of all the outputs, one per output of each lane, and a proof is
+/// rejected if any of them is wrong or missing.
+#[test]
+fn test_output_claims() {
+    type C = M31ExtConfigSha2;
+    type F = <C as GKRConfig>::ChallengeField;
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let verifier = Verifier::new(&config);
+    let mut rng = rand::thread_rng();
+
+    // 1, 2 and 8 outputs, the first the product of two inputs and the others copies
+    for output_var_num in [0, 1, 3] {
+        let gate = |i_id: usize, o_id: usize| Gate {
+            i_ids: [i_id],
+            o_id,
+            coef_type: CoefType::Constant,
+            coef: <C as GKRConfig>::CircuitField::ONE,
+            gate_type: 0,
+        };
+        let mut circuit = Circuit::<C> {
+            layers: vec![CircuitLayer {
+                input_var_num: 3,
+                output_var_num,
+                mul: vec![Gate {
+                    i_ids: [1, 2],
+                    o_id: 0,
+                    coef_type: CoefType::Constant,
+                    coef: <C as GKRConfig>::CircuitField::ONE,
+                    gate_type: 0,
+                }],
+                add: (1..1 << output_var_num).map(|o| gate(o, o)).collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        circuit.layers[0].identify_structure_info();
+        circuit.identify_rnd_coefs();
+        circuit.layers[0].input_vals = (0..8)
+            .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+            .collect();
+
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+        let outputs = &circuit.layers[0].output_vals;
+        assert_eq!(claimed_v.len(), C::get_field_pack_size() << output_var_num);
+        for (o, output) in outputs.iter().enumerate() {
+            for (lane, v) in output.unpack().into_iter().enumerate() {
+                assert_eq!(claimed_v[o + (lane << output_var_num)], F::from(v));
+            }
+        }
+
+        let public_input = circuit.public_input.clone();
+        assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+        for i in [0, claimed_v.len() - 1] {
+            let mut wrong_claim = claimed_v.clone();
+            wrong_claim[i] += F::ONE;
+            assert!(!verifier.verify(&mut circuit, &public_input, &wrong_claim, &proof));
+        }
+        let mut extra_claim = claimed_v.clone();
+        extra_claim.push(F::ZERO);
+        assert_eq!(
+            verifier.try_verify(&mut circuit, &public_input, &extra_claim, &proof),
+            Err(VerificationError::ClaimedValuesCount {
+                expected: claimed_v.len(),
+                found: claimed_v.len() + 1,
+            })
+        );
+    }
+}
+
+/// The provers of a pool, each with its own circuit and scratch pad, prove at the same time.
+#[cfg(feature = "server")]
+#[tokio::test]
+async fn test_prover_pool() {
+    use std::{
+        sync::{Arc, Barrier},
+        time::Duration,
+    };
+
+    use crate::WorkerPool;
+
+    type C = M31ExtConfigSha2;
+    const NUM_WORKERS: usize = 4;
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit = small_circuit::<C>();
+    let workers = (0..NUM_WORKERS)
+        .map(|_| {
+            let mut prover = Prover::new(&config).unwrap();
+            prover.prepare_mem(&circuit);
+            (circuit.clone(), prover)
+        })
+        .collect();
+    let pool = Arc::new(WorkerPool::new(workers, Duration::from_secs(10)));
+
+    // each proof waits for the others to start, which only returns if they run at the same time
+    let barrier = Arc::new(Barrier::new(NUM_WORKERS));
+    let jobs = (0..NUM_WORKERS as u32)
+        .map(|w| {
+            let pool = pool.clone();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                pool.run(move |(circuit, prover): &mut (Circuit<C>, Prover<C>)| {
+                    barrier.wait();
+                    circuit.layers[0].input_vals = (1..=4u32)
+                        .map(|v| <C as GKRConfig>::SimdCircuitField::from(w + v))
+                        .collect();
+                    prover.prove_silent(circuit)
+                })
+                .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let verifier = Verifier::new(&config);
+    for (w, job) in jobs.into_iter().enumerate() {
+        let (claimed_v, proof) = job.await.unwrap().unwrap();
+        let mut circuit = small_circuit::<C>();
+        circuit.layers[0].input_vals = (1..=4u32)
+            .map(|v| <C as GKRConfig>::SimdCircuitField::from(w as u32 + v))
+            .collect();
+        circuit.evaluate();
+        let public_input = circuit.public_input.clone();
+        assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+    }
+}
+
+#[test]
+fn test_goldilocks_round_trip() {
+    type C = GoldilocksExtConfigSha2;
+    let mut circuit = small_circuit::<C>();
+    circuit.layers[0].input_vals = (1..=4u32)
+        .map(<C as GKRConfig>::SimdCircuitField::from)
+        .collect();
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    let bytes = ProofPackage::new(&config, &proof, &claimed_v)
+        .unwrap()
+        .to_bytes();
+    let package = ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(package.field_type, FieldType::Goldilocks);
+    let claimed_v = package.claimed_v::<C>().unwrap();
+
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &package.proof));
+}
+
+/// The witness file of the Goldilocks inputs 1, 2, 3, 4, of 8 bytes each after its sentinel.
+#[test]
+fn test_goldilocks_witness_file() {
+    type C = GoldilocksExtConfigSha2;
+    let mut bytes = vec![];
+    1usize.serialize_into(&mut bytes).unwrap();
+    4usize.serialize_into(&mut bytes).unwrap();
+    0usize.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for i in 1..=4u32 {
+        <C as GKRConfig>::CircuitField::from(i)
+            .serialize_into(&mut bytes)
+            .unwrap();
+    }
+    let bytes = migrate_v0_to_v1(&bytes, FieldType::Goldilocks);
+    assert_eq!(bytes.len(), WITNESS_HEADER_SIZE + 3 * 8 + 32 + 4 * 8);
+
+    let filename =
+        std::env::temp_dir().join(format!("goldilocks_witness_{}.txt", std::process::id()));
+    fs::write(&filename, &bytes).unwrap();
+    let mut circuit = small_circuit::<C>();
+    circuit
+        .try_load_witness_file(filename.to_str().unwrap())
+        .unwrap();
+    assert!(matches!(
+        small_circuit::<M31ExtConfigSha2>().try_load_witness_file(filename.to_str().unwrap()),
+        Err(CircuitError::WitnessFormat(WitnessError::WrongField { .. }))
+    ));
+    fs::remove_file(&filename).unwrap();
+    assert_eq!(
+        circuit.layers[0].input_vals,
+        (1..=4u32)
+            .map(<C as GKRConfig>::SimdCircuitField::from)
+            .collect::<Vec<_>>()
+    );
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
+
+fn put_usizes(bytes: &mut Vec<u8>, values: &[usize]) {
+    for value in values {
+        value.serialize_into(&mut *bytes).unwrap();
+    }
+}
+
+/// The circuit file of `small_circuit` over BabyBear, as the compiler writes it: one segment of
+/// 4 inputs and 2 outputs, and one layer of it.
+fn babybear_circuit_bytes() -> Vec<u8> {
+    let one = |bytes: &mut Vec<u8>| {
+        bytes.push(1);
+        bytes.extend_from_slice(&1usize.to_le_bytes());
+        bytes.extend_from_slice(&[0; 24]);
+    };
+    let mut bytes = b"CIRCUIT6".to_vec();
+    bytes.extend_from_slice(&SENTINEL_BABYBEAR);
+    // no public input, 2 outputs, none of them expected zero, then a single segment
+    put_usizes(&mut bytes, &[0, 2, 0, 1]);
+    // 4 inputs, 2 outputs, no child segment
+    put_usizes(&mut bytes, &[4, 2, 0]);
+    put_usizes(&mut bytes, &[1, 0, 1, 0]);
+    one(&mut bytes);
+    put_usizes(&mut bytes, &[2, 2, 1]);
+    one(&mut bytes);
+    put_usizes(&mut bytes, &[3, 1]);
+    one(&mut bytes);
+    // no const nor custom gate, and the layer of the segment
+    put_usizes(&mut bytes, &[0, 0, 1, 0]);
+    bytes
+}
+
+/// The witness file of 16 witnesses of the inputs w, w + 1, w + 2, w + 3 over BabyBear.
+fn babybear_witness_bytes() -> Vec<u8> {
+    let mut bytes = vec![];
+    put_usizes(&mut bytes, &[16, 4, 0]);
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for w in 0..16u32 {
+        for i in 0..4 {
+            BabyBear::from(w + i).serialize_into(&mut bytes).unwrap();
+        }
+    }
+    migrate_v0_to_v1(&bytes, FieldType::BabyBear)
+}
+
+#[test]
+fn test_babybear_circuit_file() {
+    type C = BabyBearExtConfigSha2;
+    let circuit_path = &("../".to_owned() + BABYBEAR_CIRCUIT);
+    let witness_path = &("../".to_owned() + BABYBEAR_WITNESS);
+    // the fixture is the file of the bytes written here
+    assert_eq!(fs::read(circuit_path).unwrap(), babybear_circuit_bytes());
+    assert_eq!(fs::read(witness_path).unwrap(), babybear_witness_bytes());
+    assert_eq!(
+        detect_field_type_from_circuit_file(circuit_path).unwrap(),
+        FieldType::BabyBear
+    );
+
+    let mut circuit = executor::load_circuit_and_witness::<C>(circuit_path, witness_path).unwrap();
+    circuit.evaluate();
+    let outputs = &circuit.layers[0].output_vals;
+    for (w, (out_0, out_1)) in outputs[0]
+        .unpack()
+        .into_iter()
+        .zip(outputs[1].unpack())
+        .enumerate()
+    {
+        let w = w as u32;
+        assert_eq!(out_0, BabyBear::from(w * (w + 1)));
+        assert_eq!(out_1, BabyBear::from(2 * w + 5));
+    }
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let proof_bytes = executor::prove(&config, circuit_path, witness_path).unwrap();
+    let package = ProofPackage::deserialize_from(&mut proof_bytes.as_slice()).unwrap();
+    assert_eq!(package.field_type, FieldType::BabyBear);
+    assert!(executor::verify(&config, circuit_path, witness_path, &proof_bytes).unwrap());
+
+    // the proof is over BabyBear, so the M31 config refuses it
+    assert!(matches!(
+        executor::verify(
+            &Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1)),
+            circuit_path,
+            witness_path,
+            &proof_bytes
+        ),
+        Err(ExpanderError::FieldTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_prove_with_seed() {
+    type C = M31ExtConfigSha2;
+    let mut rng = rand::thread_rng();
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    for _ in 0..8 {
+        let mut circuit = small_circuit::<C>();
+        circuit.layers[0].input_vals = (0..4)
+            .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+            .collect();
+        let seed: [u8; 32] = rng.gen();
+        let mut other_seed = seed;
+        other_seed[rng.gen_range(0..32)] ^= rng.gen_range(1..=255);
+
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_with_seed(&mut circuit, seed);
+        let mut other_prover = Prover::new(&config).unwrap();
+        other_prover.prepare_mem(&circuit);
+        let (_, same_seed_proof) = other_prover.prove_with_seed(&mut circuit, seed);
+        let (_, other_seed_proof) = prover.prove_with_seed(&mut circuit, other_seed);
+        assert_eq!(proof.bytes, same_seed_proof.bytes);
+        assert_ne!(proof.bytes, other_seed_proof.bytes);
+
+        // the seed of the config, and the verifier needs it
+        let seeded_config = config.clone().with_prover_seed(seed);
+        let mut seeded_prover = Prover::new(&seeded_config).unwrap();
+        seeded_prover.prepare_mem(&circuit);
+        let (_, config_seed_proof) = seeded_prover.prove_silent(&mut circuit);
+        assert_eq!(proof.bytes, config_seed_proof.bytes);
+        let public_input = circuit.public_input.clone();
+        assert!(Verifier::new(&seeded_config).verify(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &proof
+        ));
+        assert!(!Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+    }
+}
+
+#[test]
+fn test_prove_with_domain() {
+    type C = M31ExtConfigSha2;
+    let mut rng = rand::thread_rng();
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = small_circuit::<C>();
+    circuit.layers[0].input_vals = (0..4)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let public_input = circuit.public_input.clone();
+
+    let prove = |config: &Config<C>, circuit: &mut Circuit<C>| {
+        let mut prover = Prover::new(config).unwrap();
+        prover.prepare_mem(circuit);
+        prover.prove_silent(circuit)
+    };
+    let (claimed_v, proof) = prove(&config, &mut circuit);
+    let (_, empty_domain_proof) = prove(&config.clone().with_domain(b""), &mut circuit);
+    assert_eq!(proof.bytes, empty_domain_proof.bytes);
+
+    let app_a = config.clone().with_domain(b"app-a");
+    let app_b = config.clone().with_domain(b"app-b");
+    let (claimed_v_a, proof_a) = prove(&app_a, &mut circuit);
+    assert_ne!(proof.bytes, proof_a.bytes);
+    assert!(Verifier::new(&app_a).verify(&mut circuit, &public_input, &claimed_v_a, &proof_a));
+    assert!(!Verifier::new(&app_b).verify(&mut circuit, &public_input, &claimed_v_a, &proof_a));
+    assert!(!Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v_a, &proof_a));
+    assert!(!Verifier::new(&app_a).verify(&mut circuit, &public_input, &claimed_v, &proof));
+
+    // the domain of the prover and of the verifier, past their config
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    prover.set_domain(b"app-a");
+    let (_, set_domain_proof) = prover.prove_silent(&mut circuit);
+    assert_eq!(set_domain_proof.bytes, proof_a.bytes);
+    let mut verifier = Verifier::new(&app_b);
+    verifier.set_domain(b"app-a");
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v_a, &proof_a));
+
+    // a seed of the bytes the domain was once absorbed as, its length then the domain, is not
+    // the domain
+    let domain = [7u8; 24];
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&(domain.len() as u64).to_le_bytes());
+    seed[8..].copy_from_slice(&domain);
+    let seeded = config.clone().with_prover_seed(seed);
+    let (claimed_v_seed, proof_seed) = prove(&seeded, &mut circuit);
+    let (_, proof_domain) = prove(&config.clone().with_domain(&domain), &mut circuit);
+    assert_ne!(proof_seed.bytes, proof_domain.bytes);
+    assert!(!Verifier::new(&config.clone().with_domain(&domain)).verify(
+        &mut circuit,
+        &public_input,
+        &claimed_v_seed,
+        &proof_seed
+    ));
+}
+
+#[test]
+fn test_public_input_binding() {
+    type C = M31ExtConfigSha2;
+    let mut rng = rand::thread_rng();
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = small_circuit::<C>();
+    circuit.layers[0].input_vals = (0..4)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    // no gate reads them, so only the transcript tells them apart
+    circuit.public_input = (0..2)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+
+    let mut mutated = public_input.clone();
+    mutated[1] += <C as GKRConfig>::SimdCircuitField::ONE;
+    circuit.public_input = mutated.clone();
+    assert!(!Verifier::new(&config).verify(&mut circuit, &mutated, &claimed_v, &proof));
+}
+
+#[test]
+fn test_prove_with_hash_function() {
+    type C = M31ExtConfigSha2;
+    let mut rng = rand::thread_rng();
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = small_circuit::<C>();
+    circuit.layers[0].input_vals = (0..4)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let public_input = circuit.public_input.clone();
+
+    let prove = |config: &Config<C>, circuit: &mut Circuit<C>| {
+        let mut prover = Prover::new(config).unwrap();
+        prover.prepare_mem(circuit);
+        prover.prove_silent(circuit)
+    };
+    let (_, static_proof) = prove(&config, &mut circuit);
+
+    let configs = [HashType::Sha256, HashType::Blake3, HashType::Keccak256]
+        .map(|hash| config.clone().with_hash_function(hash).unwrap());
+    let proofs = configs
+        .iter()
+        .map(|config| prove(config, &mut circuit))
+        .collect::<Vec<_>>();
+    // the hash of M31ExtConfigSha2, chosen at runtime
+    assert_eq!(proofs[0].1.bytes, static_proof.bytes);
+    for (i, (claimed_v, proof)) in proofs.iter().enumerate() {
+        for (j, config) in configs.iter().enumerate() {
+            assert_eq!(
+                Verifier::new(config).verify(&mut circuit, &public_input, claimed_v, proof),
+                i == j
+            );
+            if i < j {
+                assert_ne!(proof.bytes, proofs[j].1.bytes);
+            }
+        }
+    }
+
+    assert_eq!(
+        config.clone().with_hash_function(HashType::Poseidon),
+        Err(ConfigError::HashUnavailable {
+            hash: HashType::Poseidon,
+            field: FieldType::M31
+        })
+    );
+
+    // a bytes hash for a config of a field hash
+    let mimc_config =
+        Config::<BN254ConfigMIMC5>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1))
+            .with_hash_function(HashType::Blake3)
+            .unwrap();
+    let mut circuit = small_circuit::<BN254ConfigMIMC5>();
+    circuit.layers[0].input_vals = (0..4)
+        .map(|_| <BN254ConfigMIMC5 as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let mut prover = Prover::new(&mimc_config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&mimc_config).verify(&mut circuit, &public_input, &claimed_v, &proof));
 }
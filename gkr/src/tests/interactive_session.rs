@@ -0,0 +1,112 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig, PolynomialCommitmentType};
+use transcript::{BytesHashTranscript, SHA256hasher, Transcript};
+
+use super::common::{finalize, unit_gate, C};
+use crate::{
+    absorb_claimed_values, absorb_public_input, InteractiveProverSession, Prover, SessionError,
+    SessionState, Verifier,
+};
+
+type SimdF = <C as GKRConfig>::SimdCircuitField;
+
+fn sample_circuit() -> Circuit<C> {
+    finalize(Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                input_vals: (1..=4u32).map(SimdF::from).collect(),
+                mul: vec![unit_gate([0, 1], 0)],
+                add: vec![unit_gate([2], 1), unit_gate([3], 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                mul: vec![unit_gate([0, 1], 0)],
+                add: vec![unit_gate([1], 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_interactive_session_matches_fiat_shamir() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut session = InteractiveProverSession::new(sample_circuit(), &config).unwrap();
+    assert_eq!(session.state(), SessionState::WaitingForChallenge);
+
+    // the verifier draws the challenges as Fiat-Shamir would
+    let mut transcript =
+        BytesHashTranscript::<<C as GKRConfig>::ChallengeField, SHA256hasher>::new();
+    transcript.append_u8_slice(&session.initial_message().bytes);
+    absorb_public_input::<C, _>(&mut transcript, &sample_circuit().public_input, 1);
+    // without random coefficients, the claims come with the commitment
+    let claimed_v = session.initial_message().claimed_v.clone().unwrap();
+    absorb_claimed_values::<C, _>(&mut transcript, &claimed_v);
+    let mut rounds = 0;
+    while !session.is_done() {
+        let message = session.receive_challenge(transcript.generate_challenge_field_element());
+        assert!(message.claimed_v.is_none());
+        transcript.append_u8_slice(&message.bytes);
+        rounds += 1;
+        if !session.is_done() {
+            assert_eq!(session.state(), SessionState::ProvingRound(rounds));
+        }
+    }
+    assert_eq!(session.state(), SessionState::Done);
+    let proof = session.take_proof().unwrap();
+    assert!(session.take_proof().is_none());
+
+    let mut circuit = sample_circuit();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    assert_eq!(prover.prove_silent(&mut circuit), (claimed_v, proof.clone()));
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
+
+#[test]
+fn test_interactive_session_dropped_early() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut session = InteractiveProverSession::new(sample_circuit(), &config).unwrap();
+    let message = session.receive_challenge(<C as GKRConfig>::ChallengeField::ONE);
+    assert!(message.claimed_v.is_none());
+    assert!(!session.is_done());
+    assert!(session.take_proof().is_none());
+}
+
+#[test]
+fn test_interactive_session_unsupported_config() {
+    let session = |config: &Config<C>| InteractiveProverSession::new(sample_circuit(), config);
+
+    // the transcripts of several processes are synced by their hash states
+    let mpi = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(2));
+    assert_eq!(
+        session(&mpi).err(),
+        Some(SessionError::UnsupportedWorldSize(2))
+    );
+
+    let gkr_square = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    assert_eq!(
+        session(&gkr_square).err(),
+        Some(SessionError::UnsupportedScheme(GKRScheme::GkrSquare))
+    );
+
+    let orion = Config::<C>::new_with_pcs(
+        GKRScheme::Vanilla,
+        PolynomialCommitmentType::Orion,
+        MPIConfig::new_for_verifier(1),
+    )
+    .unwrap();
+    assert_eq!(
+        session(&orion).err(),
+        Some(SessionError::UnsupportedPolynomialCommitment(
+            PolynomialCommitmentType::Orion
+        ))
+    );
+}
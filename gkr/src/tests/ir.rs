@@ -0,0 +1,149 @@
+use circuit::{Circuit, CircuitLayer, CoefType};
+use config::FieldType;
+
+use super::common::{typed_gate, C, F};
+use crate::{GKRCircuitIR, GateOpIR, IRCoef, IRConvertible, IRError, GKR_CIRCUIT_IR_VERSION};
+
+/// Two layers, with every kind of gate and of coefficient.
+fn circuit() -> Circuit<C> {
+    let mut pow5 = typed_gate::<C, 1>([3], 1, CoefType::Constant, 1);
+    pow5.gate_type = 12345;
+    let base = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 2,
+        mul: vec![typed_gate([0, 1], 0, CoefType::Constant, 3)],
+        add: vec![typed_gate([2], 2, CoefType::Random, 0)],
+        const_: vec![typed_gate([], 3, CoefType::PublicInput(0), 0)],
+        uni: vec![pow5],
+        ..Default::default()
+    };
+    let top = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        add: vec![
+            typed_gate([0], 0, CoefType::Constant, 1),
+            typed_gate([1], 1, CoefType::Constant, 5),
+        ],
+        const_: vec![typed_gate([], 0, CoefType::Constant, 7)],
+        ..Default::default()
+    };
+    let mut circuit = Circuit::<C> {
+        layers: vec![base, top],
+        expected_num_output_zeros: 1,
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.layers[1].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_ir_round_trip() {
+    let ir = circuit().to_ir();
+    assert_eq!(ir.version, GKR_CIRCUIT_IR_VERSION);
+    assert_eq!(ir.field_type, FieldType::M31);
+    assert_eq!(ir.layers[0].gates.len(), 4);
+    assert_eq!(ir.layers[0].gates[0].op, GateOpIR::Mul);
+    assert_eq!(
+        ir.layers[0].gates[0].coef,
+        IRCoef::Constant("3".to_string())
+    );
+    assert_eq!(ir.layers[0].gates[1].coef, IRCoef::Random);
+    assert_eq!(ir.layers[0].gates[3].op, GateOpIR::Uni { gate_type: 12345 });
+
+    let json = ir.to_json().unwrap();
+    assert_eq!(GKRCircuitIR::from_json(&json).unwrap(), ir);
+    let cbor = ir.to_cbor().unwrap();
+    assert_eq!(GKRCircuitIR::from_cbor(&cbor).unwrap(), ir);
+
+    let circuit = Circuit::<C>::from_ir(ir.clone()).unwrap();
+    assert_eq!(circuit.to_ir(), ir);
+    assert_eq!(circuit.expected_num_output_zeros, 1);
+    assert_eq!(circuit.rnd_coefs.len(), 1);
+    assert_eq!(circuit.layers[0].uni[0].gate_type, 12345);
+    assert_eq!(circuit.layers[1].add[1].coef, F::from(5u32));
+}
+
+#[test]
+fn test_ir_errors() {
+    let ir = circuit().to_ir();
+
+    let mut bad = ir.clone();
+    bad.version = GKR_CIRCUIT_IR_VERSION + 1;
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::UnsupportedVersion(_))
+    ));
+
+    let mut bad = ir.clone();
+    bad.field_type = FieldType::BN254;
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::FieldTypeMismatch { .. })
+    ));
+
+    let mut bad = ir.clone();
+    bad.layers[1].input_var_num = 3;
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::VarNumMismatch { layer: 1, .. })
+    ));
+
+    let mut bad = ir.clone();
+    bad.layers[0].gates[0].inputs.pop();
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::InputCountMismatch {
+            layer: 0,
+            gate: 0,
+            expected: 2,
+            found: 1
+        })
+    ));
+
+    let mut bad = ir.clone();
+    bad.layers[1].gates[0].inputs[0].layer = 0;
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::NonAdjacentWire { layer: 1, .. })
+    ));
+
+    let mut bad = ir.clone();
+    bad.layers[0].gates[0].inputs[0].wire = 4;
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::WireOutOfRange {
+            wire: 4,
+            size: 4,
+            ..
+        })
+    ));
+
+    let mut bad = ir.clone();
+    bad.layers[0].gates[3].op = GateOpIR::Uni { gate_type: 1 };
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::UnknownGateType { gate_type: 1, .. })
+    ));
+
+    // the M31 modulus
+    let mut bad = ir.clone();
+    bad.layers[0].gates[0].coef = IRCoef::Constant("2147483647".to_string());
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::InvalidCoef(_))
+    ));
+
+    let mut bad = ir;
+    bad.layers[0].gates[1].coef = IRCoef::PublicInput(0);
+    assert!(matches!(
+        Circuit::<C>::from_ir(bad),
+        Err(IRError::InvalidCoef(_))
+    ));
+
+    assert!(matches!(
+        GKRCircuitIR::from_json("{\"version\": 1, \"field_type\": \"P256\", \"layers\": []}"),
+        Err(IRError::JsonError(_))
+    ));
+}
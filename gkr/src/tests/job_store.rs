@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use crate::{JobState, JobStore};
+
+#[test]
+fn test_job_store_lifecycle() {
+    let store = JobStore::<Result<Vec<u8>, String>>::new(Duration::from_secs(3600), 16);
+    let id = store.submit().unwrap();
+    assert_eq!(id.len(), 32);
+    assert_eq!(store.get(&id), Some(JobState::Pending));
+    assert_ne!(store.submit().unwrap(), id);
+    assert_eq!(store.len(), 2);
+
+    store.start(&id);
+    assert_eq!(store.get(&id), Some(JobState::Running));
+    assert_eq!(store.unfinished(), 2);
+    store.finish(&id, Ok(vec![1, 2, 3]));
+    assert_eq!(store.get(&id), Some(JobState::Finished(Ok(vec![1, 2, 3]))));
+    assert_eq!(
+        store.inspect(
+            &id,
+            |state| matches!(state, JobState::Finished(Ok(proof)) if proof.len() == 3)
+        ),
+        Some(true)
+    );
+    assert_eq!(store.unfinished(), 1);
+
+    assert_eq!(store.get("no_such_job"), None);
+    // unknown ids are ignored
+    store.finish("no_such_job", Err("failed".to_string()));
+    assert_eq!(store.len(), 2);
+}
+
+#[test]
+fn test_job_store_eviction() {
+    let store = JobStore::<u32>::new(Duration::from_secs(60), 16);
+    let finished = store.submit().unwrap();
+    let pending = store.submit().unwrap();
+    store.finish(&finished, 7);
+
+    assert_eq!(store.evict_expired(Instant::now()), 0);
+    assert_eq!(store.get(&finished), Some(JobState::Finished(7)));
+
+    // the pending jobs are kept however long they wait, the finished ones for the ttl
+    let later = Instant::now() + Duration::from_secs(61);
+    assert_eq!(store.evict_expired(later), 1);
+    assert_eq!(store.get(&finished), None);
+    assert_eq!(store.get(&pending), Some(JobState::Pending));
+
+    // evicted on access, without waiting for a call to evict_expired
+    let store = JobStore::<u32>::new(Duration::ZERO, 16);
+    let id = store.submit().unwrap();
+    store.finish(&id, 7);
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(store.get(&id), None);
+    assert!(store.is_empty());
+}
+
+#[test]
+fn test_job_store_max_unfinished() {
+    let store = JobStore::<u32>::new(Duration::from_secs(60), 2);
+    let running = store.submit().unwrap();
+    store.start(&running);
+    let pending = store.submit().unwrap();
+    // the running and the pending jobs count, up to the maximum
+    assert_eq!(store.submit(), None);
+    assert_eq!(store.len(), 2);
+
+    // the finished ones do not, however long they are kept
+    store.finish(&running, 7);
+    let next = store.submit().unwrap();
+    assert_eq!(store.submit(), None);
+    store.finish(&pending, 8);
+    store.finish(&next, 9);
+    assert!(store.submit().is_some());
+    assert_eq!(store.len(), 4);
+}
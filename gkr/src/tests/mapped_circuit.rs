@@ -0,0 +1,177 @@
+use arith::{Field, FieldForECC};
+use circuit::{Allocation, Circuit, CircuitLayerStream, CoefType, Gate, RecursiveCircuit, Segment};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig, SENTINEL_M31};
+
+use super::common::{typed_gate, C};
+use crate::{Prover, VerificationError, Verifier};
+
+const LOG_WIDTH: usize = 8;
+const NUM_LAYERS: usize = 5;
+
+/// Every layer is the same segment of four wires over the whole width, with a gate of a random
+/// coefficient.
+fn recursive_circuit() -> RecursiveCircuit<C> {
+    let leaf = Segment::<C> {
+        i_var_num: 2,
+        o_var_num: 2,
+        gate_muls: vec![
+            typed_gate([0, 1], 0, CoefType::Constant, 3),
+            typed_gate([2, 3], 1, CoefType::Constant, 3),
+        ],
+        gate_adds: vec![
+            typed_gate([1], 2, CoefType::Random, 0),
+            typed_gate([3], 3, CoefType::Constant, 3),
+        ],
+        gate_consts: vec![typed_gate([], 3, CoefType::Constant, 3)],
+        ..Default::default()
+    };
+    let layer = Segment::<C> {
+        i_var_num: LOG_WIDTH,
+        o_var_num: LOG_WIDTH,
+        child_segs: vec![(
+            0,
+            (0..1 << (LOG_WIDTH - 2))
+                .map(|i| Allocation {
+                    i_offset: 4 * i,
+                    o_offset: 4 * i,
+                })
+                .collect(),
+        )],
+        ..Default::default()
+    };
+    RecursiveCircuit {
+        num_outputs: 1 << LOG_WIDTH,
+        segments: vec![leaf, layer],
+        layers: vec![1; NUM_LAYERS],
+        ..Default::default()
+    }
+}
+
+fn put_usizes(bytes: &mut Vec<u8>, values: &[usize]) {
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn put_gates<const INPUT_NUM: usize>(bytes: &mut Vec<u8>, gates: &[Gate<C, INPUT_NUM>]) {
+    put_usizes(bytes, &[gates.len()]);
+    for gate in gates {
+        put_usizes(bytes, &gate.i_ids);
+        put_usizes(bytes, &[gate.o_id]);
+        match gate.coef_type {
+            CoefType::Constant => {
+                bytes.push(1);
+                bytes.extend_from_slice(&gate.coef.to_u256().to_le_bytes());
+            }
+            CoefType::Random => bytes.push(2),
+            CoefType::PublicInput(index) => {
+                bytes.push(3);
+                put_usizes(bytes, &[index]);
+            }
+        }
+    }
+}
+
+/// The circuit file of the circuit, without custom gates.
+fn circuit_file_bytes(rc: &RecursiveCircuit<C>) -> Vec<u8> {
+    let mut bytes = b"CIRCUIT6".to_vec();
+    bytes.extend_from_slice(&SENTINEL_M31);
+    put_usizes(
+        &mut bytes,
+        &[
+            rc.num_public_inputs,
+            rc.num_outputs,
+            rc.expected_num_output_zeros,
+            rc.segments.len(),
+        ],
+    );
+    for segment in &rc.segments {
+        put_usizes(
+            &mut bytes,
+            &[
+                1 << segment.i_var_num,
+                1 << segment.o_var_num,
+                segment.child_segs.len(),
+            ],
+        );
+        for (id, allocs) in &segment.child_segs {
+            put_usizes(&mut bytes, &[*id, allocs.len()]);
+            for alloc in allocs {
+                put_usizes(&mut bytes, &[alloc.i_offset, alloc.o_offset]);
+            }
+        }
+        put_gates(&mut bytes, &segment.gate_muls);
+        put_gates(&mut bytes, &segment.gate_adds);
+        put_gates(&mut bytes, &segment.gate_consts);
+        put_usizes(&mut bytes, &[0]);
+    }
+    put_usizes(&mut bytes, &[rc.layers.len()]);
+    put_usizes(&mut bytes, &rc.layers);
+    bytes
+}
+
+fn write_circuit_file(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}_{}.txt", name, std::process::id()));
+    std::fs::write(&path, circuit_file_bytes(&recursive_circuit())).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_mapped_circuit_flatten() {
+    let path = write_circuit_file("mapped_circuit_flatten");
+    let circuit = Circuit::<C>::load_circuit(&path);
+    let mapped = Circuit::<C>::load_circuit_mmap(&path).unwrap();
+    assert_eq!(mapped.num_layers(), NUM_LAYERS);
+    assert_eq!(mapped.num_outputs, 1 << LOG_WIDTH);
+    let flattened = mapped.flatten();
+    assert_eq!(flattened.hash(), circuit.hash());
+    assert_eq!(flattened.hash(), recursive_circuit().flatten().hash());
+    assert_eq!(mapped.flatten_layer(2).add.len(), 2 << (LOG_WIDTH - 2));
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_mapped_circuit_proofs() {
+    let path = write_circuit_file("mapped_circuit_proofs");
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut rng = rand::thread_rng();
+    let input_vals = (0..1 << LOG_WIDTH)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut circuit = Circuit::<C>::load_circuit(&path);
+    circuit.layers[0].input_vals = input_vals.clone();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    for batch_size in [1, 2, NUM_LAYERS] {
+        let layers =
+            CircuitLayerStream::mapped(Circuit::<C>::load_circuit_mmap(&path).unwrap(), batch_size);
+        let (mapped_v, mapped_proof) = Prover::new(&config).unwrap().prove_streaming(
+            layers.clone(),
+            input_vals.clone(),
+            vec![],
+        );
+        assert_eq!(mapped_v, claimed_v);
+        assert_eq!(mapped_proof.bytes, proof.bytes);
+
+        let verifier = Verifier::new(&config);
+        assert!(verifier.verify_streaming(&layers, &[], &claimed_v, &proof));
+        let mut wrong_v = claimed_v.clone();
+        wrong_v[0] += <C as GKRConfig>::ChallengeField::ONE;
+        assert!(!verifier.verify_streaming(&layers, &[], &wrong_v, &proof));
+        let mut truncated = proof.clone();
+        truncated.bytes.pop();
+        assert_eq!(
+            verifier.try_verify_streaming(&layers, &[], &claimed_v, &truncated),
+            Err(VerificationError::ProofSize {
+                expected: proof.bytes.len(),
+                found: truncated.bytes.len(),
+            })
+        );
+    }
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &proof));
+    std::fs::remove_file(&path).unwrap();
+}
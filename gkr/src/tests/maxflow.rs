@@ -0,0 +1,87 @@
+use circuit::{Circuit, CircuitLayer, CoefType, GateKind, GateRef};
+
+use super::common::{unit_gate, C};
+use crate::{CircuitMaxflow, MaxflowResult};
+
+fn circuit(last: CircuitLayer<C>) -> Circuit<C> {
+    Circuit {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                add: vec![unit_gate([0], 0), unit_gate([1], 1), unit_gate([3], 3)],
+                mul: vec![unit_gate([1, 2], 2)],
+                ..Default::default()
+            },
+            last,
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_connected() {
+    // the 4 inputs reach the 2 outputs, limited by the 2 edges into the sink
+    let c = circuit(CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        add: vec![
+            unit_gate([0], 0),
+            unit_gate([1], 0),
+            unit_gate([2], 1),
+            unit_gate([3], 1),
+        ],
+        ..Default::default()
+    });
+    assert_eq!(
+        CircuitMaxflow::compute(&c),
+        MaxflowResult::Connected { flow: 2 }
+    );
+
+    // through a single wire
+    let c = circuit(CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 0,
+        mul: vec![unit_gate([0, 2], 0)],
+        ..Default::default()
+    });
+    assert_eq!(
+        CircuitMaxflow::compute(&c),
+        MaxflowResult::Connected { flow: 1 }
+    );
+
+    // public inputs are inputs
+    let mut c = circuit(CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        add: vec![unit_gate([0], 0)],
+        const_: vec![unit_gate([], 1)],
+        ..Default::default()
+    });
+    c.layers[1].const_[0].coef_type = CoefType::PublicInput(0);
+    assert_eq!(
+        CircuitMaxflow::compute(&c),
+        MaxflowResult::Connected { flow: 2 }
+    );
+}
+
+#[test]
+fn test_disconnected() {
+    let c = circuit(CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        add: vec![unit_gate([0], 0)],
+        const_: vec![unit_gate([], 0), unit_gate([], 1)],
+        ..Default::default()
+    });
+    assert_eq!(
+        CircuitMaxflow::compute(&c),
+        MaxflowResult::Disconnected {
+            output_gate: GateRef {
+                layer: 1,
+                kind: GateKind::Const,
+                index: 1
+            }
+        }
+    );
+}
@@ -0,0 +1,32 @@
+use arith::Field;
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::common::{finalize, two_layer_circuit, C};
+use crate::{MPIGroupProver, Verifier};
+
+#[test]
+fn test_mpi_group_prover() {
+    // a single process is a single group of the whole world
+    let mpi_config = MPIConfig::new();
+    if mpi_config.world_size() != 1 {
+        return;
+    }
+    let group_prover = MPIGroupProver::new(&mpi_config, 1);
+    assert_eq!(group_prover.num_groups(), 1);
+    assert_eq!(group_prover.group_id(), 0);
+    assert_eq!(group_prover.group_config().world_size(), 1);
+    assert!(group_prover.is_group_root());
+
+    let mut circuit = finalize(two_layer_circuit::<C>());
+    let mut rng = StdRng::seed_from_u64(0);
+    circuit.layers[0].input_vals = (0..1 << circuit.log_input_size())
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let config = Config::<C>::new(GKRScheme::Vanilla, mpi_config);
+    let (claimed_v, proof) = group_prover.prove(&config, &mut circuit).unwrap().unwrap();
+    drop(group_prover);
+
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use config::MPIConfig;
+
+use crate::MPIHealthCheck;
+
+#[test]
+fn test_verify_all_ranks_ready() {
+    // a single process has no other to wait for
+    let mpi_config = MPIConfig::new();
+    if mpi_config.world_size() == 1 {
+        assert_eq!(MPIHealthCheck::verify_all_ranks_ready(&mpi_config), Ok(()));
+        assert_eq!(
+            MPIHealthCheck::verify_all_ranks_ready_with_timeout(&mpi_config, Duration::ZERO),
+            Ok(())
+        );
+    }
+}
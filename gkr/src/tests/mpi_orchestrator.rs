@@ -0,0 +1,77 @@
+use circuit::{Circuit, CircuitLayer};
+
+use super::common::C;
+use crate::{LayerAssignment, MPIProveOrchestrator, RankCapability};
+
+/// Layers of 16, 4 and 2 outputs, without gates.
+fn test_circuit() -> Circuit<C> {
+    let layer = |input_var_num, output_var_num| CircuitLayer {
+        input_var_num,
+        output_var_num,
+        ..Default::default()
+    };
+    Circuit {
+        layers: vec![layer(4, 4), layer(4, 2), layer(2, 1)],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_assign_work() {
+    let circuit = test_circuit();
+    let ranks = [
+        RankCapability {
+            rank: 0,
+            cores: 2,
+            memory_gb: 1,
+        },
+        RankCapability {
+            rank: 1,
+            cores: 8,
+            memory_gb: 1,
+        },
+    ];
+    let assignments = MPIProveOrchestrator::assign_work(&circuit, &ranks);
+    assert_eq!(
+        assignments,
+        vec![
+            LayerAssignment {
+                layer: 0,
+                rank: 1,
+                cost: 32
+            },
+            LayerAssignment {
+                layer: 1,
+                rank: 1,
+                cost: 20
+            },
+            // 6 / 2 for rank 0, against (52 + 6) / 8 for rank 1
+            LayerAssignment {
+                layer: 2,
+                rank: 0,
+                cost: 6
+            },
+        ]
+    );
+    assert_eq!(MPIProveOrchestrator::layers_of(&assignments, 0), vec![2]);
+    assert_eq!(MPIProveOrchestrator::layers_of(&assignments, 1), vec![0, 1]);
+}
+
+#[test]
+fn test_assign_work_memory() {
+    let circuit = test_circuit();
+    let ranks = [
+        RankCapability {
+            rank: 3,
+            cores: 64,
+            memory_gb: 0,
+        },
+        RankCapability {
+            rank: 5,
+            cores: 1,
+            memory_gb: 1,
+        },
+    ];
+    let assignments = MPIProveOrchestrator::assign_work(&circuit, &ranks);
+    assert!(assignments.iter().all(|a| a.rank == 5));
+}
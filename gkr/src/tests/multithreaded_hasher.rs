@@ -0,0 +1,62 @@
+use arith::{Field, FieldSerde};
+use config::{Config, GKRScheme, M31ExtConfigKeccak, M31ExtConfigSha2, MPIConfig};
+use mersenne31::M31;
+use sha2::{Digest, Sha256};
+use transcript::SHA256hasher;
+
+use crate::{MultithreadedHasher, CHUNK_LEN};
+
+fn elements(n: usize) -> Vec<M31> {
+    (0..n as u32).map(M31::from).collect()
+}
+
+fn leaf(elements: &[M31]) -> [u8; 32] {
+    let mut bytes = vec![0u8];
+    elements
+        .iter()
+        .for_each(|f| f.serialize_into(&mut bytes).unwrap());
+    Sha256::digest(bytes).into()
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = vec![1u8];
+    bytes.extend_from_slice(left);
+    bytes.extend_from_slice(right);
+    Sha256::digest(bytes).into()
+}
+
+#[test]
+fn test_tree_hash() {
+    let sha2_config =
+        Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+
+    // a single chunk
+    let small = elements(10);
+    assert_eq!(
+        MultithreadedHasher::hash_parallel(&small, &sha2_config),
+        leaf(&small)
+    );
+
+    // three chunks, the last one carried up
+    let wide = elements(2 * CHUNK_LEN + 1);
+    let (a, rest) = wide.split_at(CHUNK_LEN);
+    let (b, c) = rest.split_at(CHUNK_LEN);
+    assert_eq!(
+        MultithreadedHasher::hash_parallel(&wide, &sha2_config),
+        node(&node(&leaf(a), &leaf(b)), &leaf(c))
+    );
+
+    let mut other = wide.clone();
+    other[CHUNK_LEN + 3] += M31::ONE;
+    assert_ne!(
+        MultithreadedHasher::<M31>::tree_hash::<SHA256hasher>(&other),
+        MultithreadedHasher::<M31>::tree_hash::<SHA256hasher>(&wide)
+    );
+
+    let keccak_config =
+        Config::<M31ExtConfigKeccak>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    assert_ne!(
+        MultithreadedHasher::hash_parallel(&wide, &keccak_config),
+        MultithreadedHasher::hash_parallel(&wide, &sha2_config)
+    );
+}
@@ -0,0 +1,219 @@
+use arith::{Field, SimdField};
+use circuit::{Circuit, CircuitLayer};
+use config::{
+    BN254ConfigSha2, Config, ConfigError, GF2ExtConfigSha2, GKRConfig, GKRScheme, MPIConfig,
+    PolynomialCommitmentType,
+};
+use polynomials::MultiLinearPoly;
+use rand::{rngs::StdRng, SeedableRng};
+use transcript::{BytesHashTranscript, SHA256hasher, Transcript};
+
+use super::common::{finalize, gate, two_layer_circuit, C};
+use crate::{
+    OrionCommitment, OrionParams, ProofLayout, ProofSegment, Prover, VerificationError, Verifier,
+};
+
+type T = BytesHashTranscript<<C as GKRConfig>::ChallengeField, SHA256hasher>;
+
+/// The evaluation of the values at `x`, with their lanes at `x_simd`.
+fn evaluate(
+    vals: &[<C as GKRConfig>::SimdCircuitField],
+    x: &[<C as GKRConfig>::ChallengeField],
+    x_simd: &[<C as GKRConfig>::ChallengeField],
+) -> <C as GKRConfig>::ChallengeField {
+    let mut scratch = vec![<C as GKRConfig>::Field::default(); vals.len()];
+    let lanes = C::eval_circuit_vals_at_challenge(vals, x, &mut scratch).unpack();
+    let mut scratch = vec![<C as GKRConfig>::ChallengeField::default(); lanes.len()];
+    MultiLinearPoly::evaluate_with_buffer(&lanes, x_simd, &mut scratch)
+}
+
+#[test]
+fn test_orion_opening() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let simd_var_num = <C as GKRConfig>::get_field_pack_size().trailing_zeros() as usize;
+    for num_vars in [0, 1, 4, 7] {
+        let vals = (0..1 << num_vars)
+            .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let x = (0..num_vars)
+            .map(|_| <C as GKRConfig>::ChallengeField::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let x_simd = (0..simd_var_num)
+            .map(|_| <C as GKRConfig>::ChallengeField::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let y = evaluate(&vals, &x, &x_simd);
+
+        let commitment = OrionCommitment::<C>::new(&vals, 100);
+        let params = commitment.params;
+        assert_eq!(params, OrionParams::new::<C>(num_vars, 100));
+        let mut transcript = T::new();
+        commitment.open(&x, &mut transcript);
+        let opening = transcript.finalize_and_get_proof().bytes;
+        assert_eq!(opening.len(), params.opening_size::<C>());
+
+        let verify = |y, opening: &[u8]| {
+            params.verify::<C, T>(&commitment.root(), &x, &x_simd, y, &mut T::new(), opening)
+        };
+        assert!(verify(y, &opening));
+        assert!(!verify(y + <C as GKRConfig>::ChallengeField::ONE, &opening));
+        assert!(!verify(y, &opening[..opening.len() - 1]));
+        // the first row value, then the last byte of the last merkle path
+        for i in [0, opening.len() - 1] {
+            let mut tampered = opening.clone();
+            tampered[i] ^= 1;
+            assert!(!verify(y, &tampered));
+        }
+    }
+}
+
+/// `(a * b, c + d)` of 4 inputs, as `two_layer_circuit` of a single layer of degree one for
+/// the other, with a single claim on the inputs.
+fn input_layers<Cfg: GKRConfig>() -> [Circuit<Cfg>; 2] {
+    let degree_one = Circuit::<Cfg> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            add: vec![gate([0], 0, 1), gate([1], 0, 2), gate([3], 1, 1)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    [finalize(two_layer_circuit::<Cfg>()), finalize(degree_one)]
+}
+
+fn prove_and_verify<Cfg: GKRConfig>(pcs: PolynomialCommitmentType) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let config =
+        Config::<Cfg>::new_with_pcs(GKRScheme::Vanilla, pcs, MPIConfig::new_for_verifier(1))
+            .unwrap();
+    for mut circuit in input_layers::<Cfg>() {
+        circuit.layers[0].input_vals = (0..4)
+            .map(|_| Cfg::SimdCircuitField::random_unsafe(&mut rng))
+            .collect();
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+        let layout = ProofLayout::new(&circuit, &config);
+        assert_eq!(proof.bytes.len(), layout.proof_size());
+        let public_input = circuit.public_input.clone();
+        let verifier = Verifier::new(&config);
+        assert_eq!(
+            verifier.try_verify(&mut circuit, &public_input, &claimed_v, &proof),
+            Ok(())
+        );
+
+        let mut tampered = proof.clone();
+        *tampered.bytes.last_mut().unwrap() ^= 1;
+        let result = verifier.try_verify(&mut circuit, &public_input, &claimed_v, &tampered);
+        if config.polynomial_commitment_type == PolynomialCommitmentType::Orion {
+            // the path of the last query of the last opening
+            let num_openings = layout
+                .segments
+                .iter()
+                .filter(|s| matches!(s, ProofSegment::Opening(_)))
+                .count();
+            assert_eq!(
+                result,
+                Err(VerificationError::CommitmentOpening {
+                    claim: num_openings - 1
+                })
+            );
+        } else {
+            assert!(result.is_err());
+        }
+    }
+}
+
+#[test]
+fn test_prove_and_verify_each_polynomial_commitment() {
+    for pcs in PolynomialCommitmentType::ALL {
+        if pcs.is_implemented() {
+            prove_and_verify::<C>(pcs.clone());
+            prove_and_verify::<BN254ConfigSha2>(pcs.clone());
+            prove_and_verify::<GF2ExtConfigSha2>(pcs);
+        }
+    }
+}
+
+#[test]
+fn test_orion_proof_layout() {
+    let [two_layers, degree_one] = input_layers::<C>();
+    let config = Config::<C>::new_with_pcs(
+        GKRScheme::Vanilla,
+        PolynomialCommitmentType::Orion,
+        MPIConfig::new_for_verifier(1),
+    )
+    .unwrap();
+    let params = OrionParams::new::<C>(2, config.security_bits);
+    let raw = ProofLayout::new(
+        &two_layers,
+        &Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1)),
+    );
+
+    let layout = ProofLayout::new(&two_layers, &config);
+    assert_eq!(layout.segments[0], ProofSegment::Commitment(32));
+    assert_eq!(
+        layout.segments[raw.segments.len()..],
+        [ProofSegment::Opening(params.opening_size::<C>()); 2]
+    );
+    assert_eq!(layout.segments[1..raw.segments.len()], raw.segments[1..]);
+    assert_eq!(layout.layer_segments, raw.layer_segments);
+
+    let layout = ProofLayout::new(&degree_one, &config);
+    assert_eq!(
+        layout.segments.last(),
+        Some(&ProofSegment::Opening(params.opening_size::<C>()))
+    );
+    assert!(!matches!(
+        layout.segments[layout.segments.len() - 2],
+        ProofSegment::Opening(_)
+    ));
+}
+
+#[test]
+fn test_orion_vanilla_only() {
+    assert_eq!(
+        Config::<C>::new_with_pcs(
+            GKRScheme::GkrSquare,
+            PolynomialCommitmentType::Orion,
+            MPIConfig::new_for_verifier(1),
+        )
+        .unwrap_err(),
+        ConfigError::PolynomialCommitmentScheme {
+            pcs: PolynomialCommitmentType::Orion,
+            scheme: GKRScheme::GkrSquare,
+        }
+    );
+    let config = Config::<C> {
+        polynomial_commitment_type: PolynomialCommitmentType::Orion,
+        ..Config::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1))
+    };
+    assert!(matches!(
+        Prover::new(&config),
+        Err(ConfigError::PolynomialCommitmentScheme { .. })
+    ));
+}
+
+#[test]
+fn test_verifier_polynomial_commitment_not_implemented() {
+    let raw = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let [mut circuit, _] = input_layers::<C>();
+    circuit.layers[0].input_vals = vec![<C as GKRConfig>::SimdCircuitField::ONE; 4];
+    let mut prover = Prover::new(&raw).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    // of the size of a raw proof, refused for its commitment rather than its bytes
+    let kzg = Config::<C> {
+        polynomial_commitment_type: PolynomialCommitmentType::KZG,
+        ..raw
+    };
+    let public_input = circuit.public_input.clone();
+    assert_eq!(
+        Verifier::new(&kzg).try_verify(&mut circuit, &public_input, &claimed_v, &proof),
+        Err(VerificationError::PolynomialCommitmentUnavailable(
+            PolynomialCommitmentType::KZG
+        ))
+    );
+}
@@ -0,0 +1,59 @@
+use circuit::{Circuit, CircuitLayer, CoefType, GateKind, GateRef, WireRef};
+
+use super::common::{typed_gate, C};
+use crate::WitnessPrivacyAnalyzer;
+
+/// Wire 0 of the middle layer is in_0 * in_1, wire 1 is a public input, and wire 2 is in_3.
+/// The outputs read each of them, and a constant.
+fn circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![typed_gate([0, 1], 0, CoefType::Constant, 1)],
+                add: vec![typed_gate([3], 2, CoefType::Constant, 1)],
+                const_: vec![typed_gate([], 1, CoefType::PublicInput(0), 1)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                add: vec![
+                    typed_gate([0], 0, CoefType::Constant, 1),
+                    typed_gate([1], 1, CoefType::Constant, 1),
+                    typed_gate([2], 2, CoefType::Constant, 1),
+                ],
+                const_: vec![typed_gate([], 3, CoefType::Constant, 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_privacy_analyzer() {
+    let report = WitnessPrivacyAnalyzer::analyze(&circuit());
+    assert_eq!(report.outputs.len(), 4);
+
+    let add = |index| GateRef {
+        layer: 1,
+        kind: GateKind::Add,
+        index,
+    };
+    let input = |index| WireRef { layer: 0, index };
+    assert_eq!(
+        report.leaking_outputs(),
+        vec![(add(0), vec![input(0), input(1)]), (add(2), vec![input(3)]),]
+    );
+}
+
+#[test]
+fn test_privacy_analyzer_no_leak() {
+    let mut circuit = circuit();
+    circuit.layers[1].add.retain(|g| g.i_ids[0] == 1);
+    let report = WitnessPrivacyAnalyzer::analyze(&circuit);
+    assert_eq!(report.outputs.len(), 2);
+    assert!(report.leaking_outputs().is_empty());
+}
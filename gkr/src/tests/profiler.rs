@@ -0,0 +1,69 @@
+use arith::FieldSerde;
+use circuit::{Circuit, CircuitLayer, GateKind, GateRef};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{gate_of_type, C};
+use crate::GKRCircuitProfiler;
+
+#[test]
+fn test_profiler_attribution() {
+    // layer 0: 2 mul gates and 1 add gate, layer 1: a single add gate
+    let layer0 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 2,
+        mul: vec![gate_of_type([0, 1], 0, 1), gate_of_type([2, 3], 1, 1)],
+        add: vec![gate_of_type([0], 2, 1)],
+        ..Default::default()
+    };
+    let layer1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        add: vec![gate_of_type([0], 0, 1)],
+        ..Default::default()
+    };
+    let circuit = Circuit::<C> {
+        layers: vec![layer0, layer1],
+        ..Default::default()
+    };
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+
+    let elem_size = <C as GKRConfig>::ChallengeField::SERIALIZED_SIZE;
+    let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+    let phase_one = (2 * 3 + simd_var_num * 4 + 1) * elem_size;
+    let phase_two = (2 * 3 + 1) * elem_size;
+
+    let profile = GKRCircuitProfiler::profile(&circuit, &config);
+    assert_eq!(profile.len(), 4);
+
+    // the lonely gate of layer 1 pays for the whole phase one
+    assert_eq!(
+        profile[0].gate_ref,
+        GateRef {
+            layer: 1,
+            kind: GateKind::Add,
+            index: 0
+        }
+    );
+    assert_eq!(profile[0].estimated_proof_contribution_bytes, phase_one);
+
+    // the bytes of layer 0 are fully attributed to its gates, mul gates pay for phase two
+    let layer0_total: usize = profile
+        .iter()
+        .filter(|c| c.layer == 0)
+        .map(|c| c.estimated_proof_contribution_bytes)
+        .sum();
+    assert_eq!(layer0_total, phase_one + phase_two);
+    let add_gate = profile
+        .iter()
+        .find(|c| c.layer == 0 && c.gate_ref.kind == GateKind::Add)
+        .unwrap();
+    assert!(profile
+        .iter()
+        .filter(|c| c.gate_ref.kind == GateKind::Mul)
+        .all(
+            |c| c.estimated_proof_contribution_bytes > add_gate.estimated_proof_contribution_bytes
+        ));
+
+    let top = GKRCircuitProfiler::top_contributors(&circuit, &config, 2);
+    assert_eq!(top, profile[..2].to_vec());
+}
@@ -0,0 +1,510 @@
+use arith::{Field, FieldSerde};
+use config::{
+    BN254ConfigKeccak, BN254ConfigMIMC5, Config, FiatShamirHashType, FieldType, GKRConfig,
+    GKRScheme, MPIConfig, PolynomialCommitmentType,
+};
+use transcript::{Proof, ProofDecodeError};
+
+use super::common::C;
+use crate::{
+    input_commitment, CompressionCodec, ProofPackage, ProofPackageError, PROOF_PACKAGE_MAGIC,
+    PROOF_PACKAGE_VERSION,
+};
+
+fn test_package() -> ProofPackage {
+    let config = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    let proof = Proof {
+        bytes: (0..100).collect(),
+    };
+    let claimed_v = <C as GKRConfig>::ChallengeField::from(7u32);
+    ProofPackage::new(&config, &proof, &[claimed_v]).unwrap()
+}
+
+/// The bytes of the uncompressed package as of a version before 7, its single claimed value
+/// without its count, nor the commitment tag of version 8, nor the input commitments of
+/// version 9, nor the seed of version 10, of a package without them.
+fn single_claim_bytes(package: &ProofPackage) -> Vec<u8> {
+    let mut bytes = package.to_bytes();
+    bytes.truncate(bytes.len() - 4);
+    let offset = 16 + package.proof.bytes.len();
+    let len = package.claimed_v.len() as u64 - 8;
+    bytes.splice(offset..offset + 16, len.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn test_proof_package_round_trip() {
+    let package = test_package();
+    assert_eq!(package.field_type, FieldType::M31);
+    assert_eq!(package.gkr_scheme, GKRScheme::GkrSquare);
+
+    let bytes = package.to_bytes();
+    assert_eq!(bytes[..4], PROOF_PACKAGE_MAGIC);
+    assert_eq!(bytes[4], PROOF_PACKAGE_VERSION);
+
+    let decoded = ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded, package);
+    assert_eq!(
+        decoded.claimed_v::<C>().unwrap(),
+        vec![<C as GKRConfig>::ChallengeField::from(7u32)]
+    );
+}
+
+#[test]
+fn test_proof_package_claimed_values() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    for count in [0, 2, 8] {
+        let claimed_v = (0..count)
+            .map(<C as GKRConfig>::ChallengeField::from)
+            .collect::<Vec<_>>();
+        let package = ProofPackage::new(&config, &Proof::default(), &claimed_v).unwrap();
+        let decoded = ProofPackage::deserialize_from(&mut package.to_bytes().as_slice()).unwrap();
+        assert_eq!(decoded.claimed_v::<C>().unwrap(), claimed_v);
+    }
+
+    // a count of more values than the bytes have, and of fewer
+    let size = <C as GKRConfig>::ChallengeField::SERIALIZED_SIZE;
+    for count in [2u64, 0] {
+        let mut package = test_package();
+        package.claimed_v[..8].copy_from_slice(&count.to_le_bytes());
+        assert!(matches!(
+            package.claimed_v::<C>(),
+            Err(ProofPackageError::InvalidClaimedValues(len)) if len == 8 + size
+        ));
+    }
+    let mut package = test_package();
+    package.claimed_v.truncate(7);
+    assert!(matches!(
+        package.claimed_v::<C>(),
+        Err(ProofPackageError::InvalidClaimedValues(7))
+    ));
+}
+
+#[test]
+fn test_proof_package_field_type_mismatch() {
+    assert!(matches!(
+        test_package().claimed_v::<BN254ConfigMIMC5>(),
+        Err(ProofPackageError::FieldTypeMismatch {
+            expected: FieldType::BN254,
+            found: FieldType::M31,
+        })
+    ));
+}
+
+#[test]
+fn test_proof_package_invalid_bytes() {
+    let bytes = test_package().to_bytes();
+
+    for len in [0, 3, 6, 20, bytes.len() - 1] {
+        assert!(matches!(
+            ProofPackage::deserialize_from(&mut &bytes[..len]),
+            Err(ProofPackageError::Truncated)
+        ));
+    }
+
+    let mut future = bytes.clone();
+    future[4] = PROOF_PACKAGE_VERSION + 1;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut future.as_slice()),
+        Err(ProofPackageError::UnsupportedVersion(v)) if v == PROOF_PACKAGE_VERSION + 1
+    ));
+
+    let mut raw = bytes.clone();
+    raw[0] = 0;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut raw.as_slice()),
+        Err(ProofPackageError::InvalidMagic)
+    ));
+
+    let mut unknown_field = bytes;
+    unknown_field[5] = 0xff;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut unknown_field.as_slice()),
+        Err(ProofPackageError::UnknownFieldType(0xff))
+    ));
+}
+
+#[test]
+fn test_proof_package_compression() {
+    let uncompressed = ProofPackage {
+        proof: Proof {
+            bytes: vec![0; 1000],
+        },
+        ..test_package()
+    };
+    let package = uncompressed
+        .clone()
+        .with_compression(CompressionCodec::Zstd { level: 3 });
+    let bytes = package.to_bytes();
+    assert_eq!(bytes[7], 1);
+    assert!(bytes.len() < uncompressed.to_bytes().len());
+
+    let decoded = ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded, package);
+    assert_eq!(decoded.proof, uncompressed.proof);
+
+    let mut unknown_codec = bytes.clone();
+    unknown_codec[7] = 0xff;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut unknown_codec.as_slice()),
+        Err(ProofPackageError::UnknownCompressionCodec(0xff))
+    ));
+
+    // the proof length, then garbage instead of a zstd frame
+    let mut corrupted = bytes[..12].to_vec();
+    corrupted.extend_from_slice(&4u64.to_le_bytes());
+    corrupted.extend_from_slice(&[0xff; 4]);
+    corrupted.extend_from_slice(&bytes[bytes.len() - 8 - package.claimed_v.len()..]);
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut corrupted.as_slice()),
+        Err(ProofPackageError::Decompression(_))
+    ));
+}
+
+#[test]
+fn test_proof_package_decompression_bomb() {
+    // a MiB of zeros, compressed to a frame of a few dozen bytes
+    let package = ProofPackage {
+        proof: Proof {
+            bytes: vec![0; 1 << 20],
+        },
+        ..test_package()
+    }
+    .with_compression(CompressionCodec::Zstd { level: 19 });
+    let bytes = package.to_bytes();
+    assert!(bytes.len() < 1000);
+
+    assert!(matches!(
+        ProofPackage::deserialize_with_max_proof_size(&mut bytes.as_slice(), 1000),
+        Err(ProofPackageError::TooLarge { max_size: 1000 })
+    ));
+    assert!(matches!(
+        ProofPackage::deserialize_with_max_proof_size(&mut bytes.as_slice(), (1 << 20) - 1),
+        Err(ProofPackageError::TooLarge { .. })
+    ));
+    assert_eq!(
+        ProofPackage::deserialize_with_max_proof_size(&mut bytes.as_slice(), 1 << 20).unwrap(),
+        package
+    );
+}
+
+#[test]
+fn test_proof_package_version_1() {
+    // no codec tag, nor hash tag, world size and public inputs
+    let mut bytes = single_claim_bytes(&test_package());
+    bytes[4] = 1;
+    bytes.remove(7);
+    bytes.truncate(bytes.len() - 10);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        ProofPackage {
+            mpi_world_size: None,
+            ..test_package()
+        }
+    );
+}
+
+#[test]
+fn test_proof_package_version_2() {
+    // no hash tag, the hash of the BN254 proofs being MiMC
+    let config =
+        Config::<BN254ConfigMIMC5>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let claimed_v = <BN254ConfigMIMC5 as GKRConfig>::ChallengeField::from(7u32);
+    for package in [
+        test_package(),
+        ProofPackage::new(&config, &Proof::default(), &[claimed_v]).unwrap(),
+    ] {
+        let mut bytes = single_claim_bytes(&package);
+        bytes[4] = 2;
+        bytes.truncate(bytes.len() - 10);
+        assert_eq!(
+            ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+            ProofPackage {
+                mpi_world_size: None,
+                ..package
+            }
+        );
+    }
+}
+
+#[test]
+fn test_proof_package_version_3() {
+    // no world size, nor public inputs
+    let mut bytes = single_claim_bytes(&test_package());
+    bytes[4] = 3;
+    bytes.truncate(bytes.len() - 9);
+    let decoded = ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded.mpi_world_size, None);
+    assert_eq!(decoded.fiat_shamir_hash, FiatShamirHashType::SHA256);
+
+    // unknown when written again
+    let decoded_again = ProofPackage::deserialize_from(&mut decoded.to_bytes().as_slice());
+    assert_eq!(decoded_again.unwrap(), decoded);
+}
+
+#[test]
+fn test_proof_package_version_4() {
+    // no public inputs
+    let package = test_package()
+        .with_public_input::<C>(&[<C as GKRConfig>::SimdCircuitField::ONE])
+        .unwrap();
+    let mut bytes = single_claim_bytes(&package);
+    bytes[4] = 4;
+    bytes.truncate(bytes.len() - 8 - <C as GKRConfig>::SimdCircuitField::SERIALIZED_SIZE - 1);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        test_package()
+    );
+}
+
+#[test]
+fn test_proof_package_version_6() {
+    // a single claimed value, without its count
+    let package = test_package()
+        .with_public_input::<C>(&[<C as GKRConfig>::SimdCircuitField::ONE])
+        .unwrap();
+    let mut bytes = single_claim_bytes(&package);
+    bytes[4] = 6;
+    let decoded = ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(decoded, package);
+    assert_eq!(
+        decoded.claimed_v::<C>().unwrap(),
+        vec![<C as GKRConfig>::ChallengeField::from(7u32)]
+    );
+}
+
+#[test]
+fn test_proof_package_version_7() {
+    // no commitment tag, the commitment being raw
+    let mut bytes = test_package().to_bytes();
+    bytes[4] = 7;
+    bytes.truncate(bytes.len() - 4);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        test_package()
+    );
+}
+
+#[test]
+fn test_proof_package_version_8() {
+    // no input commitments
+    let mut bytes = test_package().to_bytes();
+    bytes[4] = 8;
+    bytes.truncate(bytes.len() - 3);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        test_package()
+    );
+}
+
+#[test]
+fn test_proof_package_version_9() {
+    // no seed
+    let mut bytes = test_package().to_bytes();
+    bytes[4] = 9;
+    bytes.truncate(bytes.len() - 1);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        test_package()
+    );
+}
+
+#[test]
+fn test_proof_package_polynomial_commitment() {
+    assert_eq!(
+        test_package().polynomial_commitment_type,
+        PolynomialCommitmentType::Raw
+    );
+    for pcs in PolynomialCommitmentType::ALL {
+        let package = ProofPackage {
+            polynomial_commitment_type: pcs,
+            ..test_package()
+        };
+        let decoded = ProofPackage::deserialize_from(&mut package.to_bytes().as_slice()).unwrap();
+        assert_eq!(decoded, package);
+    }
+
+    let mut bytes = test_package().to_bytes();
+    let pcs_tag = bytes.len() - 4;
+    bytes[pcs_tag] = 0xff;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()),
+        Err(ProofPackageError::UnknownPolynomialCommitment(0xff))
+    ));
+}
+
+#[test]
+fn test_proof_package_public_input() {
+    assert_eq!(test_package().public_input::<C>().unwrap(), None);
+
+    // of two processes
+    let public_input = [1u32, 2, 3, 4].map(<C as GKRConfig>::SimdCircuitField::from);
+    let package = test_package()
+        .with_public_input::<C>(&public_input)
+        .unwrap();
+    let decoded = ProofPackage::deserialize_from(&mut package.to_bytes().as_slice()).unwrap();
+    assert_eq!(decoded, package);
+    assert_eq!(
+        decoded.public_input::<C>().unwrap(),
+        Some(public_input.to_vec())
+    );
+    assert!(matches!(
+        decoded.public_input::<BN254ConfigMIMC5>(),
+        Err(ProofPackageError::FieldTypeMismatch { .. })
+    ));
+
+    // none is not the same as empty
+    let empty = test_package().with_public_input::<C>(&[]).unwrap();
+    let decoded = ProofPackage::deserialize_from(&mut empty.to_bytes().as_slice()).unwrap();
+    assert_eq!(decoded.public_input::<C>().unwrap(), Some(vec![]));
+
+    let mut partial = package;
+    partial.public_input.as_mut().unwrap().pop();
+    assert!(matches!(
+        partial.public_input::<C>(),
+        Err(ProofPackageError::InvalidPublicInput(len))
+            if len == 4 * <C as GKRConfig>::SimdCircuitField::SERIALIZED_SIZE - 1
+    ));
+}
+
+#[test]
+fn test_proof_package_input_commitment() {
+    let package = test_package();
+    assert_eq!(package.public_input_commitment, None);
+    assert_eq!(package.private_input_commitment, None);
+
+    let private_input = [1u32, 2, 3, 4].map(<C as GKRConfig>::SimdCircuitField::from);
+    let public_input = [5u32].map(<C as GKRConfig>::SimdCircuitField::from);
+    for private_input in [None, Some(&private_input[..])] {
+        let committed = test_package().with_input_commitment::<C>(private_input, &public_input);
+        assert_eq!(
+            committed.public_input_commitment,
+            Some(input_commitment::<C>(&public_input))
+        );
+        assert_eq!(
+            committed.private_input_commitment,
+            private_input.map(input_commitment::<C>)
+        );
+        let bytes = committed.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            package.to_bytes().len() + 32 * (1 + private_input.is_some() as usize)
+        );
+        assert_eq!(
+            ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+            committed
+        );
+        assert!(matches!(
+            ProofPackage::deserialize_from(&mut &bytes[..bytes.len() - 1]),
+            Err(ProofPackageError::Truncated)
+        ));
+    }
+
+    // of the count of the inputs as well as their values
+    assert_ne!(
+        input_commitment::<C>(&private_input),
+        input_commitment::<C>(&private_input[..3])
+    );
+    assert_ne!(
+        input_commitment::<C>(&public_input),
+        input_commitment::<C>(&[<C as GKRConfig>::SimdCircuitField::from(6u32)])
+    );
+    assert_ne!(input_commitment::<C>(&[]), [0; 32]);
+}
+
+#[test]
+fn test_proof_package_prover_seed() {
+    assert_eq!(test_package().prover_seed, None);
+
+    let config =
+        Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1)).with_rng_seed(7);
+    let claimed_v = <C as GKRConfig>::ChallengeField::from(7u32);
+    let proof = Proof {
+        bytes: (0..100).collect(),
+    };
+    let package = ProofPackage::new(&config, &proof, &[claimed_v]).unwrap();
+    assert_eq!(package.prover_seed, config.prover_seed);
+    let bytes = package.to_bytes();
+    assert_eq!(bytes.len(), test_package().to_bytes().len() + 32);
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        package
+    );
+}
+
+#[test]
+fn test_proof_package_mpi_world_size() {
+    assert_eq!(test_package().mpi_world_size, Some(1));
+
+    let config = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(8));
+    let claimed_v = <C as GKRConfig>::ChallengeField::from(7u32);
+    let package = ProofPackage::new(&config, &Proof::default(), &[claimed_v]).unwrap();
+    assert_eq!(package.mpi_world_size, Some(8));
+    let bytes = package.to_bytes();
+    assert_eq!(bytes[bytes.len() - 13..bytes.len() - 5], 8u64.to_le_bytes());
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        package
+    );
+
+    let mut bytes = bytes;
+    let len = bytes.len();
+    bytes[len - 13..len - 5].copy_from_slice(&3u64.to_le_bytes());
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()),
+        Err(ProofPackageError::InvalidMPIWorldSize(3))
+    ));
+}
+
+#[test]
+fn test_proof_package_fiat_shamir_hash() {
+    assert_eq!(test_package().fiat_shamir_hash, FiatShamirHashType::SHA256);
+
+    let config =
+        Config::<BN254ConfigKeccak>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let claimed_v = <BN254ConfigKeccak as GKRConfig>::ChallengeField::from(7u32);
+    let package = ProofPackage::new(&config, &Proof::default(), &[claimed_v]).unwrap();
+    assert_eq!(package.fiat_shamir_hash, FiatShamirHashType::Keccak256);
+    let mut bytes = package.to_bytes();
+    assert_eq!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()).unwrap(),
+        package
+    );
+
+    let hash_tag = bytes.len() - 14;
+    bytes[hash_tag] = 0xff;
+    assert!(matches!(
+        ProofPackage::deserialize_from(&mut bytes.as_slice()),
+        Err(ProofPackageError::UnknownFiatShamirHash(0xff))
+    ));
+}
+
+#[test]
+fn test_proof_package_hex() {
+    let package = test_package();
+    let hex = package.to_hex_string();
+    assert!(hex.starts_with("45585050"));
+    assert_eq!(ProofPackage::from_hex_string(&hex).unwrap(), package);
+
+    assert!(matches!(
+        ProofPackage::from_hex_string(&hex[1..]),
+        Err(ProofPackageError::Hex(ProofDecodeError::OddLength(_)))
+    ));
+    assert!(matches!(
+        ProofPackage::from_hex_string(&hex.replacen('5', "z", 1)),
+        Err(ProofPackageError::Hex(
+            ProofDecodeError::InvalidHexCharacter {
+                character: 'z',
+                index: 1
+            }
+        ))
+    ));
+    assert!(matches!(
+        ProofPackage::from_hex_string(&hex[..hex.len() - 2]),
+        Err(ProofPackageError::Truncated)
+    ));
+    assert!(matches!(
+        ProofPackage::from_hex_string(&(hex + "00")),
+        Err(ProofPackageError::TrailingBytes(1))
+    ));
+}
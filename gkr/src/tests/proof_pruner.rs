@@ -0,0 +1,43 @@
+use arith::Field;
+use circuit::Circuit;
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{finalize, gate, two_layer_circuit, C};
+use crate::{ProofLayout, ProofPruner, Prover, Verifier};
+
+/// A degree two layer followed by a degree one layer.
+fn test_circuit() -> Circuit<C> {
+    let mut circuit = two_layer_circuit::<C>();
+    circuit.layers[1].const_ = vec![gate([], 1, 7)];
+    finalize(circuit)
+}
+
+#[test]
+fn test_proof_pruner() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = test_circuit();
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    let layout = ProofLayout::new(&circuit, &config);
+    assert_eq!(proof.bytes.len(), layout.proof_size());
+
+    let pruned = ProofPruner::prune(&circuit, &config, &proof);
+    assert_eq!(pruned.bytes.len(), layout.pruned_proof_size());
+    assert!(pruned.bytes.len() < proof.bytes.len());
+
+    let verifier = Verifier::new(&config);
+    let public_input = circuit.public_input.clone();
+    assert!(verifier.verify_pruned(&mut circuit, &public_input, &claimed_v, &pruned));
+    assert_eq!(
+        pruned.expand(&mut circuit, &config, &public_input, &claimed_v),
+        proof
+    );
+
+    let mut wrong_claim = claimed_v.clone();
+    wrong_claim[0] += <C as GKRConfig>::ChallengeField::ONE;
+    assert!(!verifier.verify_pruned(&mut circuit, &public_input, &wrong_claim, &pruned));
+}
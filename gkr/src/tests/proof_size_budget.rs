@@ -0,0 +1,64 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{unit_gate, C};
+use crate::{
+    ProofLayout, ProofSizeBudget, ProofSizeBudgetError, ProofSizeBudgetExceeded, Prover, Verifier,
+};
+
+fn budget_circuit() -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            input_vals: vec![<C as GKRConfig>::SimdCircuitField::ONE; 4],
+            mul: vec![unit_gate([0, 1], 0)],
+            add: vec![unit_gate([2], 1), unit_gate([3], 1)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_prove_with_budget() {
+    let mut circuit = budget_circuit();
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let proof_size = ProofLayout::new(&circuit, &config).proof_size();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+
+    assert_eq!(
+        prover
+            .prove_with_budget(&mut circuit, &ProofSizeBudget::new(proof_size - 1))
+            .unwrap_err(),
+        ProofSizeBudgetError::Exceeded(ProofSizeBudgetExceeded {
+            estimated_final_bytes: proof_size,
+            max_bytes: proof_size - 1,
+        })
+    );
+
+    let (claimed_v, proof) = prover
+        .prove_with_budget(&mut circuit, &ProofSizeBudget::new(proof_size))
+        .unwrap();
+    assert_eq!(proof.bytes.len(), proof_size);
+    let public_input = circuit.public_input.clone();
+    assert!(Verifier::new(&config).verify(&mut circuit, &public_input, &claimed_v, &proof));
+}
+
+#[test]
+fn test_prove_with_budget_gkr_square() {
+    let mut circuit = budget_circuit();
+    let config = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    assert_eq!(
+        prover
+            .prove_with_budget(&mut circuit, &ProofSizeBudget::new(usize::MAX))
+            .unwrap_err(),
+        ProofSizeBudgetError::UnsupportedScheme(GKRScheme::GkrSquare)
+    );
+}
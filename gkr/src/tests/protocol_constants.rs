@@ -0,0 +1,65 @@
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRScheme, MPIConfig};
+
+use super::common::{finalize, unit_gate, C};
+use crate::{GKRProtocolConstants, Prover};
+
+/// A degree two layer followed by a degree one layer.
+fn test_circuit() -> Circuit<C> {
+    finalize(Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![unit_gate([0, 1], 0), unit_gate([2, 3], 1)],
+                add: vec![unit_gate([1], 2), unit_gate([3], 3)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                add: vec![unit_gate([0], 0), unit_gate([1], 0), unit_gate([2], 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_protocol_constants() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = test_circuit();
+    let constants = GKRProtocolConstants::compute(&circuit, &config);
+
+    // 16 simd lanes, and two phases for the first layer only
+    assert_eq!(constants.rounds_per_layer, vec![2 + 4 + 2, 2 + 4]);
+    assert_eq!(constants.total_rounds, 14);
+    assert_eq!(constants.max_sumcheck_degree, 3);
+    let p = ((1u64 << 31) - 1) as f64;
+    assert!((constants.soundness_error_per_round * p.powi(3) / 3.0 - 1.0).abs() < 1e-9);
+
+    circuit.set_random_input_for_test();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (_, proof) = prover.prove_silent(&mut circuit);
+    constants.assert_matches(&circuit, &config, &proof);
+
+    let square_config = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::new_for_verifier(1));
+    let constants = GKRProtocolConstants::compute(&circuit, &square_config);
+    assert_eq!(constants.rounds_per_layer, vec![2, 2]);
+    assert_eq!(constants.max_sumcheck_degree, 6);
+}
+
+#[test]
+#[should_panic(expected = "the proof does not follow the protocol constants")]
+fn test_protocol_constants_mismatch() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = test_circuit();
+    circuit.set_random_input_for_test();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (_, mut proof) = prover.prove_silent(&mut circuit);
+    proof.bytes.pop();
+    GKRProtocolConstants::compute(&circuit, &config).assert_matches(&circuit, &config, &proof);
+}
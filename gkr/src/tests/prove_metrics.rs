@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, FieldType, GKRScheme, MPIConfig};
+
+use super::common::{unit_gate, C};
+use crate::{GKRProtocolConstants, MetricsRequest, ProofStats, ProveMetrics, Prover};
+
+#[test]
+fn test_proof_stats() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![unit_gate([0, 1], 0)],
+            add: vec![unit_gate([2], 1), unit_gate([3], 1)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit.layers[0].identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof, stats) = prover.prove(&mut circuit);
+    assert_eq!(stats.proof_bytes, proof.bytes.len());
+    assert_eq!(
+        stats.num_sumcheck_rounds,
+        GKRProtocolConstants::compute(&circuit, &config).total_rounds
+    );
+    assert_eq!(prover.prove_silent(&mut circuit), (claimed_v, proof));
+}
+
+#[test]
+fn test_prove_metrics() {
+    let metrics = ProveMetrics::new();
+    metrics.record(&ProofStats {
+        proof_bytes: 2000,
+        num_sumcheck_rounds: 10,
+        prove_duration: Duration::from_millis(200),
+    });
+    metrics.record(&ProofStats {
+        proof_bytes: 100_000_000,
+        num_sumcheck_rounds: 10,
+        prove_duration: Duration::from_millis(20),
+    });
+
+    let text = metrics.render();
+    assert!(text.contains("# TYPE expander_prove_duration_seconds histogram\n"));
+    assert!(text.contains("expander_prove_duration_seconds_bucket{le=\"0.01\"} 0\n"));
+    assert!(text.contains("expander_prove_duration_seconds_bucket{le=\"0.05\"} 1\n"));
+    assert!(text.contains("expander_prove_duration_seconds_bucket{le=\"0.25\"} 2\n"));
+    assert!(text.contains("expander_prove_duration_seconds_count 2\n"));
+    // the largest proof is only in the +Inf bucket
+    assert!(text.contains("expander_proof_bytes_bucket{le=\"4096\"} 1\n"));
+    assert!(text.contains("expander_proof_bytes_bucket{le=\"67108864\"} 1\n"));
+    assert!(text.contains("expander_proof_bytes_bucket{le=\"+Inf\"} 2\n"));
+    assert!(text.contains("expander_proof_bytes_sum 100002000\n"));
+}
+
+#[test]
+fn test_prove_metrics_labels() {
+    let metrics = ProveMetrics::for_circuit(&FieldType::M31, &GKRScheme::GkrSquare);
+    metrics.record_request(MetricsRequest::Prove, true);
+    metrics.record_request(MetricsRequest::Prove, true);
+    metrics.record_request(MetricsRequest::Verify, false);
+    metrics.record_witness_load(Duration::from_micros(300));
+
+    let text = metrics.render();
+    let labels = "field=\"M31\",scheme=\"gkr_square\"";
+    assert!(text.contains("# TYPE expander_requests_total counter\n"));
+    for (request, result, count) in [
+        ("prove", "success", 2),
+        ("prove", "failure", 0),
+        ("verify", "success", 0),
+        ("verify", "failure", 1),
+    ] {
+        let sample = format!(
+            "expander_requests_total{{{},request=\"{}\",result=\"{}\"}} {}\n",
+            labels, request, result, count
+        );
+        assert!(text.contains(&sample), "{}", sample);
+    }
+    assert!(text.contains(&format!(
+        "expander_witness_load_duration_seconds_bucket{{{},le=\"0.0005\"}} 1\n",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "expander_witness_load_duration_seconds_count{{{}}} 1\n",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "expander_prove_duration_seconds_count{{{}}} 0\n",
+        labels
+    )));
+}
+
+#[test]
+fn test_prover_stats() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                add: vec![
+                    unit_gate([0], 0),
+                    unit_gate([1], 1),
+                    unit_gate([2], 2),
+                    unit_gate([3], 3),
+                ],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![unit_gate([0, 1], 0), unit_gate([2, 3], 1)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit.identify_rnd_coefs();
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof, stats) = prover.prove_with_stats(&mut circuit);
+    assert_eq!(stats.sumcheck_layer_durations.len(), circuit.layers.len());
+    assert_eq!(stats.proof.proof_bytes, proof.bytes.len());
+    assert!(stats.transcript_bytes < stats.proof.proof_bytes);
+    let phases = stats.witness_eval_duration
+        + stats.sumcheck_duration()
+        + stats.commit_duration
+        + stats.open_duration;
+    assert!(phases <= stats.proof.prove_duration);
+    assert!(stats.to_string().contains("over 2 layers"));
+
+    // the same proof as without the stats
+    assert_eq!(prover.prove_silent(&mut circuit), (claimed_v, proof));
+}
@@ -0,0 +1,59 @@
+use arith::Field;
+use circuit::{Circuit, GateBuilder};
+use config::{
+    BN254ConfigSha2, Config, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig,
+};
+
+use crate::{Prover, Verifier};
+
+/// More wires than a chunk of the threads of the sumcheck, for the first rounds to be split.
+const LOG_NUM_WIRES: usize = 14;
+
+/// 2 layers over the wires, the i-th wire of each the product of the i-th and the next one of the
+/// layer below, plus the one after.
+fn circuit<C: GKRConfig>() -> Circuit<C> {
+    let num_wires = 1 << LOG_NUM_WIRES;
+    let mut builder = GateBuilder::<C>::new(num_wires);
+    let mut wires = (0..num_wires).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..2 {
+        builder.next_layer();
+        wires = (0..num_wires)
+            .map(|i| {
+                let wire = builder.mul(wires[i], wires[(i + 1) % num_wires], C::CircuitField::ONE);
+                builder.add_into(wire, wires[(i + 2) % num_wires], C::CircuitField::ONE);
+                wire
+            })
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+fn check_proofs_do_not_depend_on_threads<C: GKRConfig>() {
+    let mut circuit = circuit::<C>();
+    let mut rng = rand::thread_rng();
+    circuit.layers[0].input_vals = (0..1 << LOG_NUM_WIRES)
+        .map(|_| C::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let prove = |circuit: &mut Circuit<C>, num_threads: usize| {
+        let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1))
+            .with_num_threads(num_threads);
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(circuit);
+        (config, prover.prove_silent(circuit))
+    };
+
+    let (_, (claimed_v, proof)) = prove(&mut circuit, 1);
+    let (config, (threaded_claimed_v, threaded_proof)) = prove(&mut circuit, 4);
+    assert_eq!(claimed_v, threaded_claimed_v);
+    assert_eq!(proof.bytes, threaded_proof.bytes);
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &threaded_claimed_v, &threaded_proof));
+}
+
+#[test]
+fn test_proofs_do_not_depend_on_threads() {
+    check_proofs_do_not_depend_on_threads::<M31ExtConfigSha2>();
+    check_proofs_do_not_depend_on_threads::<GF2ExtConfigSha2>();
+    check_proofs_do_not_depend_on_threads::<BN254ConfigSha2>();
+}
@@ -0,0 +1,117 @@
+use arith::{Field, FieldForECC};
+use circuit::{Circuit, CircuitError, R1cs};
+use config::{BN254ConfigSha2, Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+
+use crate::{Prover, Verifier};
+
+type C = BN254ConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+/// The binary R1CS of circom for `out = f(5)`, with `f(0) = a` and `f(1) = b` public and
+/// `f(i) = f(i - 1) + f(i - 2)`. Wires: 1 `out`, 2 `a`, 3 `b`, then `f(2)` to `f(5)`.
+fn fibonacci_r1cs() -> Vec<u8> {
+    let one = [(0u32, 1u32)];
+    let sum = |i: u32, j: u32| vec![(i, 1u32), (j, 1u32)];
+    let constraints = [
+        (sum(2, 3), one.to_vec(), vec![(4, 1)]),
+        (sum(3, 4), one.to_vec(), vec![(5, 1)]),
+        (sum(4, 5), one.to_vec(), vec![(6, 1)]),
+        (sum(5, 6), one.to_vec(), vec![(7, 1)]),
+        (vec![(7, 1)], one.to_vec(), vec![(1, 1)]),
+    ];
+
+    let mut header = vec![];
+    header.extend_from_slice(&32u32.to_le_bytes());
+    header.extend_from_slice(&F::modulus().to_le_bytes());
+    for n in [8u32, 1, 2, 0] {
+        header.extend_from_slice(&n.to_le_bytes());
+    }
+    header.extend_from_slice(&8u64.to_le_bytes());
+    header.extend_from_slice(&(constraints.len() as u32).to_le_bytes());
+
+    let mut constraints_section = vec![];
+    for (a, b, c) in &constraints {
+        for terms in [a, b, c] {
+            constraints_section.extend_from_slice(&(terms.len() as u32).to_le_bytes());
+            for (wire, coef) in terms {
+                constraints_section.extend_from_slice(&wire.to_le_bytes());
+                constraints_section.extend_from_slice(&F::from(*coef).to_u256().to_le_bytes());
+            }
+        }
+    }
+
+    let mut bytes = b"r1cs".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    // the constraints before the header, as the sections may come in any order
+    for (section_type, section) in [(2u32, constraints_section), (1, header)] {
+        bytes.extend_from_slice(&section_type.to_le_bytes());
+        bytes.extend_from_slice(&(section.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&section);
+    }
+    bytes
+}
+
+/// Prove the circuit on the wires, returning the claimed values if the proof verifies.
+fn prove(circuit: &mut Circuit<C>, wires: &[u32]) -> Option<Vec<F>> {
+    circuit.layers[0].input_vals = wires
+        .iter()
+        .map(|w| C::circuit_field_to_simd_circuit_field(&F::from(*w)))
+        .collect();
+    circuit.public_input = circuit.layers[0].input_vals[1..4].to_vec();
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(circuit);
+    let (claimed_v, proof) = prover.prove_silent(circuit);
+    let public_input = circuit.public_input.clone();
+    Verifier::new(&config)
+        .verify(circuit, &public_input, &claimed_v, &proof)
+        .then_some(claimed_v)
+}
+
+#[test]
+fn test_r1cs_fibonacci() {
+    let bytes = fibonacci_r1cs();
+    let r1cs = R1cs::<F>::from_bytes(&bytes).unwrap();
+    assert_eq!(r1cs.num_wires, 8);
+    assert_eq!(r1cs.num_public_wires(), 3);
+    assert_eq!(r1cs.constraints.len(), 5);
+    assert_eq!(r1cs.constraints[0][0], vec![(2, F::ONE), (3, F::ONE)]);
+
+    let path = std::env::temp_dir().join("expander_test_fibonacci.r1cs");
+    std::fs::write(&path, &bytes).unwrap();
+    let mut circuit = Circuit::<C>::load_from_r1cs(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(circuit.layers.len(), 2);
+    assert_eq!(circuit.layers[0].input_var_num, 3);
+    assert_eq!(circuit.expected_num_output_zeros, 8);
+
+    // 1, out, a, b, then f(2) to f(5)
+    let all_zero =
+        |claimed_v: Option<Vec<F>>| claimed_v.is_some_and(|v| v.iter().all(|v| v.is_zero()));
+    assert!(all_zero(prove(&mut circuit, &[1, 8, 1, 1, 2, 3, 5, 8])));
+    assert!(circuit.layers[1].output_vals.iter().all(|v| v.is_zero()));
+
+    // a wrong output, then a wrong internal wire
+    assert!(!all_zero(prove(&mut circuit, &[1, 9, 1, 1, 2, 3, 5, 8])));
+    assert!(!all_zero(prove(&mut circuit, &[1, 8, 1, 1, 2, 4, 5, 8])));
+}
+
+#[test]
+fn test_r1cs_invalid() {
+    let bytes = fibonacci_r1cs();
+    assert!(matches!(
+        R1cs::<<M31ExtConfigSha2 as GKRConfig>::CircuitField>::from_bytes(&bytes),
+        Err(CircuitError::InvalidR1cs(_))
+    ));
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = 0;
+    assert!(matches!(
+        R1cs::<F>::from_bytes(&bad_magic),
+        Err(CircuitError::InvalidR1cs(_))
+    ));
+
+    assert!(R1cs::<F>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
@@ -0,0 +1,70 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig, PolynomialCommitmentType};
+
+use super::common::{field_gate, finalize, C};
+use crate::{Prover, ReconstructionError, WitnessReconstructor};
+
+/// The output is `in_0 * in_1 - in_2`, which is zero iff the third input is the product
+/// of the first two.
+fn test_circuit() -> Circuit<C> {
+    let one = <C as GKRConfig>::CircuitField::ONE;
+    finalize(Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![field_gate([0, 1], 0, one)],
+            add: vec![field_gate([2], 0, -one)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+fn witness() -> Vec<<C as GKRConfig>::SimdCircuitField> {
+    let mut rng = rand::thread_rng();
+    let a = <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng);
+    let b = <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng);
+    let d = <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng);
+    vec![a, b, a * b, d]
+}
+
+#[test]
+fn test_witness_reconstruction() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let template = test_circuit();
+    let witness = witness();
+
+    let mut circuit = template.clone();
+    circuit.layers[0].input_vals.clone_from(&witness);
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (_, proof) = prover.prove_silent(&mut circuit);
+
+    let reconstructed = WitnessReconstructor::<C>::reconstruct(&template, &proof, &[]).unwrap();
+    assert_eq!(reconstructed, witness);
+
+    let mut truncated = proof.clone();
+    truncated.bytes.truncate(8);
+    assert!(matches!(
+        WitnessReconstructor::<C>::reconstruct(&template, &truncated, &[]),
+        Err(ReconstructionError::TruncatedProof)
+    ));
+
+    // the first lane of the first input no longer matches the product
+    let mut tampered = proof.clone();
+    tampered.bytes[0] ^= 1;
+    assert!(matches!(
+        WitnessReconstructor::<C>::reconstruct(&template, &tampered, &[]),
+        Err(ReconstructionError::UnsatisfiedCircuit)
+    ));
+
+    let mut hiding_config = config.clone();
+    hiding_config.polynomial_commitment_type = PolynomialCommitmentType::KZG;
+    assert!(matches!(
+        WitnessReconstructor::<C>::reconstruct_with_config(&hiding_config, &template, &proof, &[]),
+        Err(ReconstructionError::HidingCommitment(
+            PolynomialCommitmentType::KZG
+        ))
+    ));
+}
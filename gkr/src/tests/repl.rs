@@ -0,0 +1,60 @@
+use circuit::{Circuit, CircuitLayer};
+use config::GKRConfig;
+
+use super::common::{gate, C};
+use crate::{LiveWitnessDebugger, ReplError};
+
+/// The output wire 0 is `in_0 * in_1`, and the output wire 1 is `2 * in_2`.
+fn test_circuit() -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![gate([0, 1], 0, 1)],
+            add: vec![gate([2], 1, 2)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit
+}
+
+fn simd(v: u32) -> <C as GKRConfig>::SimdCircuitField {
+    C::circuit_field_to_simd_circuit_field(&<C as GKRConfig>::CircuitField::from(v))
+}
+
+#[test]
+fn test_live_witness_debugger() {
+    let mut debugger = LiveWitnessDebugger::<C>::new(test_circuit());
+
+    assert!(matches!(
+        debugger.execute("print 0:mul:0"),
+        Err(ReplError::GateNotEvaluated(_))
+    ));
+
+    debugger.execute("set 0 3").unwrap();
+    debugger.execute("set 1 5").unwrap();
+    debugger.execute("set 2 7").unwrap();
+    assert!(matches!(
+        debugger.execute("set 4 1"),
+        Err(ReplError::WireOutOfRange(4))
+    ));
+    debugger.execute("eval").unwrap();
+    assert_eq!(debugger.eval(), vec![simd(15), simd(14)]);
+
+    assert!(debugger.execute("print 0:add:0").is_ok());
+    assert!(matches!(
+        debugger.execute("print 0:sub:0"),
+        Err(ReplError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        debugger.execute("jump"),
+        Err(ReplError::UnknownCommand(_))
+    ));
+
+    assert!(debugger.execute("prove").is_ok());
+}
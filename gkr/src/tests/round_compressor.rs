@@ -0,0 +1,96 @@
+use arith::{Field, FieldSerde};
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{C, F};
+use crate::{
+    CompressedProof, CompressedRun, DecompressError, ProofLayout, Prover, SumcheckRoundCompressor,
+    Verifier,
+};
+
+/// `depth` identical layers over 4 wires: `(a * b, b, c + d, d)`.
+fn uniform_circuit(depth: usize) -> Circuit<C> {
+    let mut builder = GateBuilder::<C>::new(4);
+    let mut wires = (0..4).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..depth {
+        builder.next_layer();
+        let ab = builder.mul(wires[0], wires[1], F::ONE);
+        let b = builder.relay(wires[1]);
+        let cd = builder.add(wires[2], F::ONE);
+        builder.add_into(cd, wires[3], F::ONE);
+        let d = builder.relay(wires[3]);
+        wires = vec![ab, b, cd, d];
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+fn check_round_trip(input: [u32; 4]) -> (usize, usize) {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = uniform_circuit(16);
+    circuit.layers[0].input_vals = input
+        .iter()
+        .map(|v| C::circuit_field_to_simd_circuit_field(&F::from(*v)))
+        .collect();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    let compressed = SumcheckRoundCompressor::compress(&circuit, &config, &proof);
+    assert_eq!(compressed.decompress_for(&circuit, &config).unwrap(), proof);
+    let proof_size = ProofLayout::new(&circuit, &config).proof_size();
+    assert_eq!(compressed.decompressed_size(), Some(proof_size));
+    assert_eq!(
+        compressed.decompress(proof_size - 1),
+        Err(DecompressError::TooLarge {
+            max_size: proof_size - 1
+        })
+    );
+
+    let mut bytes = vec![];
+    compressed.serialize_into(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), compressed.size());
+    let deserialized = CompressedProof::deserialize_from(bytes.as_slice()).unwrap();
+    assert_eq!(deserialized, compressed);
+    assert!(CompressedProof::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+
+    let decompressed = deserialized.decompress_for(&circuit, &config).unwrap();
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &decompressed));
+    (proof.bytes.len(), compressed.size())
+}
+
+#[test]
+fn test_sumcheck_round_compressor() {
+    // on zero values, all the round polynomials vanish: the rounds of each phase compress to
+    // one message, the claims between the phases do not
+    let (size, compressed_size) = check_round_trip([0, 0, 0, 0]);
+    assert!(compressed_size * 4 < size * 3);
+
+    // otherwise, the compressed proof may be slightly larger, but still decompresses
+    let (size, compressed_size) = check_round_trip([3, 5, 7, 11]);
+    assert!(compressed_size <= size + 64);
+}
+
+#[test]
+fn test_decompress_bomb() {
+    // a few bytes claiming a message repeated beyond any proof, or beyond a usize
+    for count in [usize::MAX / 64, usize::MAX] {
+        let compressed = CompressedProof {
+            runs: vec![
+                CompressedRun::Raw(vec![1; 8]),
+                CompressedRun::Repeated {
+                    message: vec![0; 32],
+                    count,
+                },
+            ],
+        };
+        let mut bytes = vec![];
+        compressed.serialize_into(&mut bytes).unwrap();
+        let deserialized = CompressedProof::deserialize_from(bytes.as_slice()).unwrap();
+        assert_eq!(
+            deserialized.decompress(1 << 20),
+            Err(DecompressError::TooLarge { max_size: 1 << 20 })
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use circuit::{Circuit, CircuitLayer};
+use config::{Config, GKRScheme, MPIConfig};
+
+use super::common::{gate_of_type, C};
+use crate::{AutoSelectConfig, SchemeSelector};
+
+#[test]
+fn test_scheme_selector() {
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                uni: vec![gate_of_type([0], 0, 12345), gate_of_type([1], 1, 12346)],
+                ..Default::default()
+            };
+            2
+        ],
+        ..Default::default()
+    };
+    assert_eq!(SchemeSelector::select(&circuit), GKRScheme::GkrSquare);
+
+    let config = Config::auto_select(&circuit, MPIConfig::new_for_verifier(1));
+    assert_eq!(config.gkr_scheme, GKRScheme::GkrSquare);
+
+    circuit.layers[1].add.push(gate_of_type([0], 1, 0));
+    assert_eq!(SchemeSelector::select(&circuit), GKRScheme::Vanilla);
+    let config = Config::auto_select(&circuit, MPIConfig::new_for_verifier(1));
+    assert_eq!(config.gkr_scheme, GKRScheme::Vanilla);
+
+    assert_eq!(
+        SchemeSelector::select(&Circuit::<C>::default()),
+        GKRScheme::Vanilla
+    );
+}
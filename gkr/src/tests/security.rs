@@ -0,0 +1,62 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use config::{BN254ConfigSha2, Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+
+use crate::ProtocolSecurityEstimator;
+
+/// `depth` layers computing `x0 * x1` on both outputs.
+fn test_circuit<C: GKRConfig>(depth: usize) -> Circuit<C> {
+    let gate = |o_id| Gate {
+        i_ids: [0, 1],
+        o_id,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+    let mut circuit = Circuit::<C> {
+        layers: (0..depth)
+            .map(|_| CircuitLayer {
+                input_var_num: 1,
+                output_var_num: 1,
+                mul: vec![gate(0), gate(1)],
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    circuit
+        .layers
+        .iter_mut()
+        .for_each(|layer| layer.identify_structure_info());
+    circuit
+}
+
+#[test]
+fn test_security_estimate_m31() {
+    type C = M31ExtConfigSha2;
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let estimate = ProtocolSecurityEstimator::estimate(&config, &test_circuit::<C>(1));
+
+    // 1 + 1 rounds of degree 2 and 4 simd rounds of degree 3, one alpha, and 1 + 4 output
+    // variables: the error is 22 / |F|
+    let field_bits = 3.0 * ((1u64 << 31) as f64 - 1.0).log2();
+    assert!((estimate.soundness_bits - (field_bits - 22f64.log2())).abs() < 1e-9);
+    assert!((estimate.knowledge_soundness_bits - (field_bits - 3f64.log2())).abs() < 1e-9);
+    assert_eq!(estimate.zero_knowledge_bits, None);
+    assert!(!estimate.is_secure(128.0));
+
+    let deeper = ProtocolSecurityEstimator::estimate(&config, &test_circuit::<C>(10));
+    assert!(deeper.soundness_bits < estimate.soundness_bits);
+}
+
+#[test]
+fn test_security_estimate_bn254() {
+    type C = BN254ConfigSha2;
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let estimate = ProtocolSecurityEstimator::estimate(&config, &test_circuit::<C>(10));
+
+    assert!(estimate.soundness_bits > 240.0);
+    // capped by the collision resistance of SHA256
+    assert_eq!(estimate.knowledge_soundness_bits, 128.0);
+    assert!(estimate.is_secure(128.0));
+}
@@ -0,0 +1,158 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use arith::Field;
+use circuit::{Allocation, CircuitLayerStream, RecursiveCircuit, Segment};
+use config::{Config, GF2ExtConfigSha2, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::unit_gate;
+use crate::{Prover, Verifier};
+
+type C = GF2ExtConfigSha2;
+
+/// The most the streaming prover may allocate on top of what the test holds, for the circuit
+/// below, whose flattened layers take about 1.5 MiB each.
+const STREAMING_PEAK_BYTES_THRESHOLD: usize = 8 << 20;
+
+const LOG_WIDTH: usize = 12;
+const NUM_LAYERS: usize = 8;
+/// The gates of the two wires segment, repeated over the whole width of every layer.
+const GATES_PER_LEAF: usize = 16;
+
+/// Counts the bytes allocated by each thread, so that the tests run alongside do not count.
+struct PeakAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let _ = ALLOCATED.try_with(|allocated| {
+                allocated.set(allocated.get() + layout.size());
+                let _ = PEAK.try_with(|peak| peak.set(peak.get().max(allocated.get())));
+            });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        // the memory may have been allocated by another thread
+        let _ = ALLOCATED
+            .try_with(|allocated| allocated.set(allocated.get().saturating_sub(layout.size())));
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// The result of `f`, with the most this thread had allocated while running it, above what it
+/// held before.
+fn peak_allocated<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    PEAK.with(|peak| peak.set(before));
+    let result = f();
+    (result, PEAK.with(Cell::get) - before)
+}
+
+/// The result of `f`, with how much more this thread holds after running it.
+pub(super) fn retained_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    let result = f();
+    (result, ALLOCATED.with(Cell::get).saturating_sub(before))
+}
+
+/// Every layer is the same segment of two wires, mul and add gates, over the whole width.
+fn recursive_circuit() -> RecursiveCircuit<C> {
+    let leaf = Segment::<C> {
+        i_var_num: 1,
+        o_var_num: 1,
+        gate_muls: vec![unit_gate([0, 1], 0)],
+        gate_adds: (1..GATES_PER_LEAF).map(|i| unit_gate([i % 2], 1)).collect(),
+        ..Default::default()
+    };
+    let layer = Segment::<C> {
+        i_var_num: LOG_WIDTH,
+        o_var_num: LOG_WIDTH,
+        child_segs: vec![(
+            0,
+            (0..1 << (LOG_WIDTH - 1))
+                .map(|i| Allocation {
+                    i_offset: 2 * i,
+                    o_offset: 2 * i,
+                })
+                .collect(),
+        )],
+        ..Default::default()
+    };
+    RecursiveCircuit {
+        segments: vec![leaf, layer],
+        layers: vec![1; NUM_LAYERS],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_prove_streaming() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut rng = rand::thread_rng();
+    let input_vals = (0..1 << LOG_WIDTH)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let ((claimed_v, proof), streaming_peak) = peak_allocated(|| {
+        let layers = CircuitLayerStream::new(recursive_circuit(), 1);
+        Prover::new(&config)
+            .unwrap()
+            .prove_streaming(layers, input_vals.clone(), vec![])
+    });
+
+    let ((mut circuit, expected_v, expected_proof), full_peak) = peak_allocated(|| {
+        let mut circuit = recursive_circuit().flatten();
+        circuit.layers[0].input_vals = input_vals.clone();
+        let mut prover = Prover::new(&config).unwrap();
+        prover.prepare_mem(&circuit);
+        let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+        (circuit, claimed_v, proof)
+    });
+    assert_eq!(claimed_v, expected_v);
+    assert_eq!(proof.bytes, expected_proof.bytes);
+    assert!(Verifier::new(&config).verify(&mut circuit, &[], &claimed_v, &proof));
+
+    assert!(
+        streaming_peak < STREAMING_PEAK_BYTES_THRESHOLD,
+        "the streaming prover allocated up to {} bytes",
+        streaming_peak
+    );
+    assert!(
+        streaming_peak < full_peak / 2,
+        "the streaming prover allocated up to {} bytes, {} without streaming",
+        streaming_peak,
+        full_peak
+    );
+}
+
+#[test]
+fn test_circuit_layer_stream() {
+    let rc = recursive_circuit();
+    let expected = rc.flatten();
+    for batch_size in [1, 3, NUM_LAYERS, NUM_LAYERS + 1] {
+        let mut layers = CircuitLayerStream::new(recursive_circuit(), batch_size);
+        assert_eq!(layers.len(), NUM_LAYERS);
+        layers.next();
+        assert_eq!(layers.len(), NUM_LAYERS - 1);
+        let streamed = layers.collect::<Vec<_>>();
+        assert_eq!(streamed.len(), NUM_LAYERS - 1);
+        for (layer, expected) in streamed.iter().zip(&expected.layers[1..]) {
+            assert_eq!(layer.mul.len(), expected.mul.len());
+            assert_eq!(layer.add.len(), expected.add.len());
+            assert_eq!(layer.input_var_num, expected.input_var_num);
+        }
+    }
+}
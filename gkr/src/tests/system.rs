@@ -17,5 +17,10 @@ fn test_mutually_exclusive_flags() {
         enabled_ctr += 1;
     }
 
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        enabled_ctr += 1;
+    }
+
     assert_eq!(enabled_ctr, 1);
 }
@@ -0,0 +1,37 @@
+use opentelemetry::trace::{Span, TraceId};
+use warp::http::HeaderMap;
+
+use crate::{RequestSpan, TelemetryMiddleware};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+#[test]
+fn test_span_inherits_traceparent() {
+    // the agent is reached over udp, no agent needs to listen
+    let telemetry = TelemetryMiddleware::new("127.0.0.1:6831", "expander-test").unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "traceparent",
+        format!("00-{}-00f067aa0ba902b7-01", TRACE_ID)
+            .parse()
+            .unwrap(),
+    );
+    let mut span = telemetry.start_span("prove", &headers);
+    span.record_circuit(&config::FieldType::M31, &[0u8; 32]);
+    span.record_proof_size(1024);
+    let trace_id = TraceId::from_hex(TRACE_ID).unwrap();
+    assert_eq!(span.span().unwrap().span_context().trace_id(), trace_id);
+
+    let span = telemetry.start_span("verify", &HeaderMap::new());
+    let context = span.span().unwrap().span_context();
+    assert!(context.is_valid());
+    assert_ne!(context.trace_id(), trace_id);
+}
+
+#[test]
+fn test_disabled_span() {
+    let mut span = RequestSpan::start(None, "prove", &HeaderMap::new());
+    span.record_proof_size(1024);
+    assert!(span.span().is_none());
+}
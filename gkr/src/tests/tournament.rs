@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use config::{Config, GKRScheme, MPIConfig};
+
+use super::common::C;
+use crate::{utils::*, TournamentEntry, TournamentResults, TournamentScheduler};
+
+fn entry(circuit: &str, time_ms: u64, proof_size: usize, verified: bool) -> TournamentEntry {
+    TournamentEntry {
+        circuit: circuit.to_string(),
+        witness: "witness".to_string(),
+        prove_times: vec![Duration::from_millis(time_ms); 2],
+        proof_size,
+        verified,
+        score: 0.0,
+        rank: 0,
+    }
+}
+
+#[test]
+fn test_tournament_ranking() {
+    let results = TournamentResults::from_entries(vec![
+        entry("slow", 200, 1000, true),
+        entry("invalid", 10, 10, false),
+        entry("fast", 100, 2000, true),
+        entry("best", 100, 1000, true),
+    ]);
+    let ranking = results
+        .entries
+        .iter()
+        .map(|e| (e.circuit.as_str(), e.rank, e.score))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranking,
+        vec![
+            ("best", 1, 100.0),
+            ("slow", 2, 75.0),
+            ("fast", 3, 75.0),
+            ("invalid", 4, 0.0)
+        ]
+    );
+
+    let markdown = results.to_markdown();
+    let lines = markdown.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("| Rank | Circuit |"));
+    assert_eq!(
+        lines[2],
+        "| 1 | best | witness | 100.000 | 1000 | yes | 100.00 |"
+    );
+    assert_eq!(
+        lines[5],
+        "| 4 | invalid | witness | 10.000 | 10 | no | 0.00 |"
+    );
+}
+
+#[test]
+fn test_tournament_run() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness = "../".to_owned() + KECCAK_M31_WITNESS;
+    let mut scheduler =
+        TournamentScheduler::new(vec![&circuit, &circuit], vec![&witness, &witness]);
+    scheduler.rounds = 2;
+
+    let results = scheduler.run(&config).unwrap();
+    assert_eq!(results.entries.len(), 2);
+    for (i, entry) in results.entries.iter().enumerate() {
+        assert_eq!(entry.rank, i + 1);
+        assert!(entry.verified);
+        assert_eq!(entry.prove_times.len(), 2);
+        assert!(entry.proof_size > 0);
+    }
+    assert_eq!(results.entries[0].proof_size, results.entries[1].proof_size);
+    // the fastest entry gets all the points
+    assert_eq!(results.entries[0].score, 100.0);
+}
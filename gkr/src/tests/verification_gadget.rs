@@ -0,0 +1,50 @@
+use arith::Field;
+use circuit::Circuit;
+use config::{BN254ConfigSha2, Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{finalize, two_layer_circuit};
+use crate::{NativeVerificationGadget, Prover, VerificationKey};
+
+type C = BN254ConfigSha2;
+
+/// A degree two layer followed by a degree one layer.
+fn inner_circuit() -> Circuit<C> {
+    finalize(two_layer_circuit())
+}
+
+#[test]
+fn test_native_verification_gadget() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = inner_circuit();
+    circuit.set_random_input_for_test();
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+
+    let vk = VerificationKey::new(&circuit, &config);
+    let (mut gadget, input_map) = NativeVerificationGadget::build(&circuit, &config, &vk);
+    // every layer has two sumcheck rounds per phase, and the degree two layer has two phases
+    assert_eq!(input_map.challenge_wires.len(), 6);
+
+    let public_input = circuit.public_input.clone();
+    let witness = NativeVerificationGadget::witness(
+        &input_map,
+        &mut circuit,
+        &config,
+        &public_input,
+        &claimed_v,
+        &proof,
+    );
+    gadget.layers[0].input_vals.clone_from(&witness);
+    gadget.evaluate();
+    let output = &gadget.layers.last().unwrap().output_vals;
+    assert!(output.iter().all(|v| v.is_zero()));
+
+    // the second round of the first phase no longer matches the first one
+    let mut tampered = witness;
+    tampered[input_map.proof_wires[3].1] += <C as GKRConfig>::SimdCircuitField::ONE;
+    gadget.layers[0].input_vals = tampered;
+    gadget.evaluate();
+    assert!(!gadget.layers.last().unwrap().output_vals[0].is_zero());
+}
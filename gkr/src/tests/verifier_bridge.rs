@@ -0,0 +1,53 @@
+use arith::{Field, FieldSerde};
+use config::GKRConfig;
+use ethers::abi::{self, Token};
+use tiny_keccak::{Hasher, Keccak};
+use transcript::Proof;
+
+use super::common::C;
+use crate::ExternalVerifierBridge;
+
+#[test]
+fn test_verifier_bridge_calldata() {
+    let proof = Proof {
+        bytes: (0..100).collect(),
+    };
+    let claimed_v = [7u32, 8].map(<C as GKRConfig>::ChallengeField::from);
+    let public_inputs = vec![<C as GKRConfig>::SimdCircuitField::ONE; 2];
+    let calldata =
+        ExternalVerifierBridge::<C>::encode_calldata(&proof, &claimed_v, &public_inputs).unwrap();
+
+    let mut selector = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(b"verify(bytes,bytes,bytes)");
+    keccak.finalize(&mut selector);
+    assert_eq!(calldata[..4], selector[..4]);
+
+    let mut claimed_v_bytes = vec![];
+    for v in &claimed_v {
+        v.serialize_into(&mut claimed_v_bytes).unwrap();
+    }
+    let mut public_input_bytes = vec![];
+    for public_input in &public_inputs {
+        public_input
+            .serialize_into(&mut public_input_bytes)
+            .unwrap();
+    }
+    let args = abi::decode(
+        &[
+            abi::ParamType::Bytes,
+            abi::ParamType::Bytes,
+            abi::ParamType::Bytes,
+        ],
+        &calldata[4..],
+    )
+    .unwrap();
+    assert_eq!(
+        args,
+        vec![
+            Token::Bytes(proof.bytes),
+            Token::Bytes(claimed_v_bytes),
+            Token::Bytes(public_input_bytes),
+        ]
+    );
+}
@@ -0,0 +1,179 @@
+use arith::{Field, FieldSerde};
+use circuit::{
+    Allocation, Circuit, CircuitLayer, CoefType, RecursiveCircuit, Segment, VerifierKey,
+};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::{typed_gate, C};
+use super::streaming_prover::retained_allocation;
+use crate::{Prover, Verifier};
+
+/// Two layers, with random coefficients, a public input, and coefficients other than one.
+fn circuit() -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers: vec![
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 2,
+                mul: vec![typed_gate([0, 1], 0, CoefType::Random, 0)],
+                add: vec![
+                    typed_gate([2], 1, CoefType::Constant, 1),
+                    typed_gate([3], 1, CoefType::Constant, 5),
+                    typed_gate([1], 3, CoefType::Random, 0),
+                ],
+                const_: vec![typed_gate([], 2, CoefType::PublicInput(0), 0)],
+                ..Default::default()
+            },
+            CircuitLayer {
+                input_var_num: 2,
+                output_var_num: 1,
+                mul: vec![typed_gate([0, 3], 0, CoefType::Constant, 7)],
+                add: vec![typed_gate([1], 1, CoefType::Constant, 1)],
+                const_: vec![typed_gate([], 1, CoefType::Constant, 3)],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    circuit.identify_structure_info();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+#[test]
+fn test_verify_with_key() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut rng = rand::thread_rng();
+    let mut circuit = circuit();
+    circuit.layers[0].input_vals = (0..4)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    circuit.public_input = vec![<C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng)];
+    let public_input = circuit.public_input.clone();
+
+    let vk = circuit.extract_verifier_key();
+    assert_eq!(vk.num_layers(), 2);
+    assert_eq!(vk.log_input_size(), 2);
+    assert_eq!(vk.log_output_size(), 1);
+    assert_eq!(vk.num_public_inputs(), 1);
+    assert_eq!(vk.rnd_coef_nums(), &[2, 0]);
+
+    let mut bytes = vec![];
+    vk.serialize_into(&mut bytes).unwrap();
+    let vk = VerifierKey::<C>::deserialize_from(bytes.as_slice()).unwrap();
+    let mut round_trip = vec![];
+    vk.serialize_into(&mut round_trip).unwrap();
+    assert_eq!(bytes, round_trip);
+    for (i, expected) in circuit.layers.iter().enumerate() {
+        let layer = vk.layer(i);
+        assert_eq!(layer.mul.len(), expected.mul.len());
+        assert_eq!(layer.add.len(), expected.add.len());
+        assert_eq!(layer.const_.len(), expected.const_.len());
+        for (gate, expected) in layer.add.iter().zip(&expected.add) {
+            assert_eq!((gate.i_ids, gate.o_id), (expected.i_ids, expected.o_id));
+            assert_eq!(gate.coef_type, expected.coef_type);
+        }
+        assert_eq!(
+            layer.structure_info.max_degree_one,
+            expected.structure_info.max_degree_one
+        );
+    }
+
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    let verifier = Verifier::new(&config);
+    assert!(verifier.verify(&mut circuit, &public_input, &claimed_v, &proof));
+    assert!(verifier.verify_with_key(&vk, &public_input, &claimed_v, &proof));
+
+    let mut wrong_claim = claimed_v.clone();
+    wrong_claim[0] += <C as GKRConfig>::ChallengeField::ONE;
+    assert!(!verifier.verify_with_key(&vk, &public_input, &wrong_claim, &proof));
+    let mut other_public_input = public_input.clone();
+    other_public_input[0] += <C as GKRConfig>::SimdCircuitField::ONE;
+    assert!(!verifier.verify_with_key(&vk, &other_public_input, &claimed_v, &proof));
+    assert!(!verifier.verify_with_key(&vk, &[], &claimed_v, &proof));
+}
+
+#[test]
+fn test_verifier_key_rejects_bad_wires() {
+    let mut circuit = circuit();
+    circuit.layers[1].add[0].i_ids = [4];
+    let mut bytes = vec![];
+    circuit
+        .extract_verifier_key()
+        .serialize_into(&mut bytes)
+        .unwrap();
+    assert!(VerifierKey::<C>::deserialize_from(bytes.as_slice()).is_err());
+
+    let mut bytes = vec![];
+    self::circuit()
+        .extract_verifier_key()
+        .serialize_into(&mut bytes)
+        .unwrap();
+    assert!(VerifierKey::<C>::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+}
+
+const LOG_WIDTH: usize = 14;
+const NUM_LAYERS: usize = 8;
+const GATES_PER_LEAF: usize = 16;
+
+/// 2^20 gates: every layer repeats a segment of two wires and 16 gates over its whole width.
+fn large_circuit() -> Circuit<C> {
+    let leaf = Segment::<C> {
+        i_var_num: 1,
+        o_var_num: 1,
+        gate_muls: vec![typed_gate([0, 1], 0, CoefType::Constant, 1)],
+        gate_adds: (1..GATES_PER_LEAF)
+            .map(|i| typed_gate([i % 2], 1, CoefType::Constant, 1))
+            .collect(),
+        ..Default::default()
+    };
+    let layer = Segment::<C> {
+        i_var_num: LOG_WIDTH,
+        o_var_num: LOG_WIDTH,
+        child_segs: vec![(
+            0,
+            (0..1 << (LOG_WIDTH - 1))
+                .map(|i| Allocation {
+                    i_offset: 2 * i,
+                    o_offset: 2 * i,
+                })
+                .collect(),
+        )],
+        ..Default::default()
+    };
+    RecursiveCircuit {
+        segments: vec![leaf, layer],
+        layers: vec![1; NUM_LAYERS],
+        ..Default::default()
+    }
+    .flatten()
+}
+
+#[test]
+fn test_verifier_key_size() {
+    let (circuit, circuit_bytes) = retained_allocation(large_circuit);
+    let num_gates = circuit
+        .layers
+        .iter()
+        .map(|layer| layer.mul.len() + layer.add.len())
+        .sum::<usize>();
+    assert_eq!(num_gates, 1 << 20);
+
+    let mut bytes = vec![];
+    circuit
+        .extract_verifier_key()
+        .serialize_into(&mut bytes)
+        .unwrap();
+    drop(circuit);
+    let (vk, vk_bytes) =
+        retained_allocation(|| VerifierKey::<C>::deserialize_from(bytes.as_slice()).unwrap());
+    assert_eq!(vk.num_layers(), NUM_LAYERS);
+    assert!(
+        vk_bytes * 10 < circuit_bytes,
+        "the key takes {} bytes, the circuit {} bytes",
+        vk_bytes,
+        circuit_bytes
+    );
+}
@@ -0,0 +1,128 @@
+use arith::{Field, SimdField};
+use circuit::{Circuit, GateBuilder};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+use transcript::Proof;
+
+use super::common::C;
+use crate::{ProofLayout, Prover, RawCommitment, Verifier};
+
+const LOG_NUM_WIRES: usize = 6;
+
+/// 2 layers over the wires of each process, the i-th wire of each the product of the i-th and
+/// the next one of the layer below.
+fn circuit() -> Circuit<C> {
+    let num_wires = 1 << LOG_NUM_WIRES;
+    let mut builder = GateBuilder::<C>::new(num_wires);
+    let mut wires = (0..num_wires).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..2 {
+        builder.next_layer();
+        wires = (0..num_wires)
+            .map(|i| {
+                builder.mul(
+                    wires[i],
+                    wires[(i + 1) % num_wires],
+                    <C as GKRConfig>::CircuitField::ONE,
+                )
+            })
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit.layers[0].input_vals = vec![Default::default(); num_wires];
+    circuit
+}
+
+fn config(world_size: i32, num_threads: usize) -> Config<C> {
+    Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(world_size))
+        .with_num_threads(num_threads)
+}
+
+#[test]
+fn test_raw_opening_does_not_depend_on_threads() {
+    type F = <C as GKRConfig>::ChallengeField;
+    let mut rng = rand::thread_rng();
+    let (local_size, world_size) = (1 << 4, 8);
+    let pack_size = <C as GKRConfig>::SimdCircuitField::pack_size();
+    // a single value, on the first lane of the first value of the root, whose evaluation is
+    // that of the first lagrange polynomial
+    let value = <C as GKRConfig>::CircuitField::random_unsafe(&mut rng);
+    let mut lanes = vec![<C as GKRConfig>::CircuitField::ZERO; pack_size];
+    lanes[0] = value;
+    let mut poly_vals = vec![<C as GKRConfig>::SimdCircuitField::ZERO; local_size * world_size];
+    poly_vals[0] = <C as GKRConfig>::SimdCircuitField::pack(&lanes);
+    let commitment = RawCommitment::<C> { poly_vals };
+
+    let random = |n: usize, rng: &mut rand::rngs::ThreadRng| {
+        (0..n)
+            .map(|_| F::random_unsafe(&mut *rng))
+            .collect::<Vec<_>>()
+    };
+    let x = random(4, &mut rng);
+    let x_simd = random(pack_size.trailing_zeros() as usize, &mut rng);
+    let x_mpi = random(3, &mut rng);
+    let y = x
+        .iter()
+        .chain(&x_simd)
+        .chain(&x_mpi)
+        .fold(F::from(value), |y, r| y * (F::ONE - r));
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    for pool in [None, Some(&pool)] {
+        assert!(commitment.mpi_verify(&x, &x_simd, &x_mpi, y, pool));
+        assert!(!commitment.mpi_verify(&x, &x_simd, &x_mpi, y + F::ONE, pool));
+    }
+}
+
+#[test]
+fn test_verification_does_not_depend_on_threads() {
+    // one process, with a proof of the prover
+    let mut circuit = circuit();
+    let mut rng = rand::thread_rng();
+    circuit.layers[0].input_vals = (0..1 << LOG_NUM_WIRES)
+        .map(|_| <C as GKRConfig>::SimdCircuitField::random_unsafe(&mut rng))
+        .collect();
+    let mut prover = Prover::new(&config(1, 1)).unwrap();
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover.prove_silent(&mut circuit);
+    for num_threads in [1, 4] {
+        let verifier = Verifier::new(&config(1, num_threads));
+        assert!(verifier
+            .try_verify(&mut circuit, &[], &claimed_v, &proof)
+            .is_ok());
+        let inputs = vec![
+            (vec![], claimed_v.clone(), proof.clone()),
+            (vec![], claimed_v.clone(), Proof { bytes: vec![] }),
+        ];
+        assert_eq!(verifier.verify_batch(&circuit, &inputs), [true, false]);
+    }
+
+    // 8 processes, whose proofs are not made without mpi: the proof of zeros of the zero
+    // claimed values, and with a value of the commitment changed
+    let mut circuit = self::circuit();
+    let world_size = 8;
+    let proof_size = ProofLayout::new(&circuit, &config(world_size, 1)).proof_size();
+    let num_claimed = (1 << LOG_NUM_WIRES) * C::get_field_pack_size() * world_size as usize;
+    let claimed_v = vec![<C as GKRConfig>::ChallengeField::ZERO; num_claimed];
+    let zeros = Proof {
+        bytes: vec![0; proof_size],
+    };
+    let mut changed = zeros.clone();
+    changed.bytes[0] = 1;
+    for (proof, rejected) in [(zeros, false), (changed, true)] {
+        let results = [1, 4].map(|num_threads| {
+            Verifier::new(&config(world_size, num_threads)).try_verify(
+                &mut circuit,
+                &[],
+                &claimed_v,
+                &proof,
+            )
+        });
+        assert_eq!(results[0], results[1]);
+        if rejected {
+            assert!(results[0].is_err());
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fs, sync::Arc};
+
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use config::{Config, GKRConfig, GKRScheme, MPIConfig};
+
+use super::common::C;
+use crate::{utils::*, VerificationKey, VerifierKeyRegistry};
+
+fn test_vk(config: &Config<C>, coef: u32) -> Arc<VerificationKey<C>> {
+    let circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            add: vec![Gate {
+                i_ids: [0],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: <C as GKRConfig>::CircuitField::from(coef),
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    Arc::new(VerificationKey::new(&circuit, config))
+}
+
+#[test]
+fn test_vk_registry_lru() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let registry = VerifierKeyRegistry::new(&config, 2);
+    let (vk1, vk2, vk3) = (
+        test_vk(&config, 1),
+        test_vk(&config, 2),
+        test_vk(&config, 3),
+    );
+
+    registry.insert(vk1.clone());
+    registry.insert(vk2.clone());
+    // vk1 is now more recently used than vk2
+    assert_eq!(registry.get(&vk1.circuit_hash), Some(vk1.clone()));
+    registry.insert(vk3.clone());
+
+    assert_eq!(registry.len(), 2);
+    assert!(registry.contains(&vk1.circuit_hash));
+    assert!(!registry.contains(&vk2.circuit_hash));
+    assert!(registry.contains(&vk3.circuit_hash));
+}
+
+#[test]
+fn test_vk_registry_dir() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let registry = VerifierKeyRegistry::new(&config, 8);
+    let vks = (1..=3).map(|c| test_vk(&config, c)).collect::<Vec<_>>();
+    vks.iter().for_each(|vk| registry.insert(vk.clone()));
+
+    let dir = std::env::temp_dir().join("expander_vk_registry_test");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.to_str().unwrap();
+    registry.save_to_dir(path).unwrap();
+    fs::write(dir.join("README"), "not a key").unwrap();
+
+    let loaded = VerifierKeyRegistry::load_from_dir(path, &config, 8).unwrap();
+    assert_eq!(loaded.len(), 3);
+    for vk in &vks {
+        assert_eq!(loaded.get(&vk.circuit_hash), Some(vk.clone()));
+    }
+    assert_eq!(
+        VerifierKeyRegistry::load_from_dir(path, &config, 2)
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // keys for another world size are skipped
+    let other = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(2));
+    assert!(VerifierKeyRegistry::load_from_dir(path, &other, 8)
+        .unwrap()
+        .is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_vk_registry_get_or_compute() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let circuit = Circuit::<C>::load_circuit(&circuit_path);
+    let hash = circuit.hash();
+
+    let registry = VerifierKeyRegistry::new(&config, 4);
+    let vk = registry.get_or_compute(&hash, &circuit_path);
+    assert!(vk.matches(&circuit, &config));
+    // served from the cache
+    assert!(Arc::ptr_eq(
+        &vk,
+        &registry.get_or_compute(&hash, &circuit_path)
+    ));
+}
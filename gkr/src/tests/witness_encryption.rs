@@ -0,0 +1,73 @@
+use arith::{Field, FieldSerde};
+use mersenne31::M31;
+use rand::thread_rng;
+
+use crate::{DecryptError, EncryptedWitness, WitnessEncryptor, X25519PublicKey, X25519SecretKey};
+
+fn key_pair(seed: u8) -> (X25519SecretKey, X25519PublicKey) {
+    let sk = X25519SecretKey::from([seed; 32]);
+    let pk = X25519PublicKey::from(&sk);
+    (sk, pk)
+}
+
+#[test]
+fn test_witness_encryption() {
+    let mut rng = thread_rng();
+    let (sk, pk) = key_pair(1);
+    let witness = (0..100)
+        .map(|_| M31::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let enc = WitnessEncryptor::encrypt(&witness, &pk, &mut rng);
+    assert_eq!(
+        WitnessEncryptor::<M31>::decrypt(&enc, &sk).unwrap(),
+        witness
+    );
+
+    let mut bytes = vec![];
+    enc.serialize_into(&mut bytes).unwrap();
+    let deserialized = EncryptedWitness::deserialize_from(bytes.as_slice()).unwrap();
+    assert_eq!(deserialized, enc);
+    assert!(EncryptedWitness::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+
+    // a fresh ephemeral key for each encryption
+    let other = WitnessEncryptor::encrypt(&witness, &pk, &mut rng);
+    assert_ne!(other.ephemeral_pk, enc.ephemeral_pk);
+    assert_ne!(other.ciphertext, enc.ciphertext);
+}
+
+#[test]
+fn test_witness_decryption_failures() {
+    let mut rng = thread_rng();
+    let (sk, pk) = key_pair(1);
+    let (other_sk, _) = key_pair(2);
+    let witness = vec![M31::ONE; 10];
+    let enc = WitnessEncryptor::encrypt(&witness, &pk, &mut rng);
+
+    assert!(matches!(
+        WitnessEncryptor::<M31>::decrypt(&enc, &other_sk),
+        Err(DecryptError::AuthenticationFailed)
+    ));
+
+    let mut tampered = enc.clone();
+    tampered.ciphertext[0] ^= 1;
+    assert!(matches!(
+        WitnessEncryptor::<M31>::decrypt(&tampered, &sk),
+        Err(DecryptError::AuthenticationFailed)
+    ));
+
+    // the identity point gives an all zero shared secret
+    let mut low_order = enc.clone();
+    low_order.ephemeral_pk = [0; 32];
+    assert!(matches!(
+        WitnessEncryptor::<M31>::decrypt(&low_order, &sk),
+        Err(DecryptError::InvalidKey)
+    ));
+
+    // bytes that are not a list of elements
+    let enc = WitnessEncryptor::<M31>::encrypt_bytes(&[1, 2, 3], &pk, &mut rng);
+    assert!(matches!(
+        WitnessEncryptor::<M31>::decrypt(&enc, &sk),
+        Err(DecryptError::DeserializeError(_))
+    ));
+}
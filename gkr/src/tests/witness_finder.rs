@@ -0,0 +1,58 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, GateKind, GateRef};
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::common::{gate, C, F};
+use crate::{ConvergenceError, GradientDescentWitnessFinder};
+
+fn gate_ref(kind: GateKind, index: usize) -> GateRef {
+    GateRef {
+        layer: 0,
+        kind,
+        index,
+    }
+}
+
+/// `out0 = x0 + 2 x1`, `out1 = x0 * x1`, `out2 = 2 x2`, with `x3` unconstrained.
+fn test_circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 2,
+            mul: vec![gate([0, 1], 1, 1)],
+            add: vec![gate([0], 0, 1), gate([1], 0, 2), gate([2], 2, 2)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_gradient_descent_witness_finder() {
+    let circuit = test_circuit();
+    let finder = GradientDescentWitnessFinder::<C>::new(0.01, 20_000);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    // x0 + 2 x1 = 8 and x0 x1 = 6, solved by (6, 1) and (2, 3)
+    let targets = [
+        (gate_ref(GateKind::Add, 0), F::from(8)),
+        (gate_ref(GateKind::Mul, 0), F::from(6)),
+    ];
+    let witness = finder.find(&circuit, &targets, &mut rng).unwrap();
+    assert_eq!(witness[0] + witness[1] * F::from(2), F::from(8));
+    assert_eq!(witness[0] * witness[1], F::from(6));
+    // the same start, and so the same witness, of the same seed
+    let same_seed_witness = finder
+        .find(&circuit, &targets, StdRng::seed_from_u64(0))
+        .unwrap();
+    assert_eq!(same_seed_witness, witness);
+
+    // 2 x2 = 1 has no integer solution
+    let result = finder.find(&circuit, &[(gate_ref(GateKind::Add, 2), F::ONE)], &mut rng);
+    assert!(matches!(result, Err(ConvergenceError::NotConverged { .. })));
+
+    assert_eq!(
+        finder.find(&circuit, &[(gate_ref(GateKind::Mul, 1), F::ONE)], &mut rng),
+        Err(ConvergenceError::UnknownGate(gate_ref(GateKind::Mul, 1)))
+    );
+}
@@ -0,0 +1,286 @@
+use arith::{Field, FieldSerde, SimdField};
+use circuit::{
+    encode_witness_batch, migrate_v0_to_v1, split_witness_header, Circuit, CircuitError,
+    WitnessError, WITNESS_BATCH_MAGIC, WITNESS_HEADER_SIZE, WITNESS_MAGIC, WITNESS_VERSION,
+};
+use config::{FieldType, GKRConfig, SENTINEL_GF2, SENTINEL_M31};
+
+use super::common::{single_layer_circuit, C, F};
+use crate::FileBackedWitnessOracle;
+
+/// The witness bytes of the format before the header, of one input per witness.
+fn legacy_witness_bytes(modulus: [u64; 4]) -> Vec<u8> {
+    let num_witnesses = C::get_field_pack_size();
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    2usize.serialize_into(&mut bytes).unwrap();
+    0usize.serialize_into(&mut bytes).unwrap();
+    modulus.serialize_into(&mut bytes).unwrap();
+    for i in 0..num_witnesses * 2 {
+        F::from(i as u32).serialize_into(&mut bytes).unwrap();
+    }
+    bytes
+}
+
+fn circuit() -> Circuit<C> {
+    single_layer_circuit(1, 1)
+}
+
+fn witness_error(bytes: &[u8]) -> WitnessError {
+    match circuit().try_load_witness_bytes(bytes, false) {
+        Err(CircuitError::WitnessFormat(e)) => e,
+        other => panic!("expected a witness format error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_witness_header() {
+    let legacy = legacy_witness_bytes([0; 4]);
+    let migrated = migrate_v0_to_v1(&legacy, FieldType::M31);
+    assert_eq!(migrated.len(), WITNESS_HEADER_SIZE + legacy.len());
+    assert_eq!(
+        split_witness_header(&migrated),
+        Ok((SENTINEL_M31, &legacy[..]))
+    );
+    let mut circuit = circuit();
+    circuit.try_load_witness_bytes(&migrated, false).unwrap();
+    assert_eq!(circuit.layers[0].input_vals.len(), 2);
+
+    assert_eq!(witness_error(&legacy), WitnessError::WrongMagic);
+    assert_eq!(witness_error(&[]), WitnessError::WrongMagic);
+    assert_eq!(
+        witness_error(&migrated[..WITNESS_HEADER_SIZE - 1]),
+        WitnessError::Truncated
+    );
+
+    let mut next_version = migrated.clone();
+    next_version[4..8].copy_from_slice(&(WITNESS_VERSION + 1).to_le_bytes());
+    assert_eq!(
+        witness_error(&next_version),
+        WitnessError::UnsupportedVersion {
+            found: WITNESS_VERSION + 1,
+            supported: WITNESS_VERSION,
+        }
+    );
+
+    assert_eq!(
+        witness_error(&migrate_v0_to_v1(&legacy, FieldType::GF2)),
+        WitnessError::WrongField {
+            expected: SENTINEL_M31,
+            found: SENTINEL_GF2,
+        }
+    );
+}
+
+#[test]
+fn test_witness_length_mismatch() {
+    let witness = migrate_v0_to_v1(&legacy_witness_bytes([0; 4]), FieldType::M31);
+    let mut long = witness.clone();
+    long.push(0);
+    for bytes in [&witness[..witness.len() - 1], &long] {
+        assert_eq!(
+            witness_error(bytes),
+            WitnessError::LengthMismatch {
+                expected: witness.len(),
+                got: bytes.len(),
+            }
+        );
+    }
+    // cut in the number of witnesses and of inputs
+    assert_eq!(
+        witness_error(&witness[..WITNESS_HEADER_SIZE + 8]),
+        WitnessError::Truncated
+    );
+    // a header of more values than fit in memory
+    let mut huge = witness.clone();
+    huge[WITNESS_HEADER_SIZE..WITNESS_HEADER_SIZE + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(
+        witness_error(&huge),
+        WitnessError::LengthMismatch {
+            expected: usize::MAX,
+            got: witness.len(),
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_load_legacy_witness_bytes() {
+    circuit().load_witness_bytes(&legacy_witness_bytes([0; 4]), false);
+}
+
+#[test]
+fn test_migrate_native_witness() {
+    // the legacy witness carries the modulus of its field
+    let modulus = [2u64.pow(31) - 1, 0, 0, 0];
+    let migrated = migrate_v0_to_v1(&legacy_witness_bytes(modulus), FieldType::Native);
+    assert_eq!(&migrated[..WITNESS_MAGIC.len()], &WITNESS_MAGIC);
+    assert!(circuit().try_load_witness_bytes(&migrated, false).is_ok());
+}
+
+#[test]
+fn test_witness_oracle_rejects_legacy_witness() {
+    let filename = std::env::temp_dir().join(format!("legacy_witness_{}.txt", std::process::id()));
+    std::fs::write(&filename, legacy_witness_bytes([0; 4])).unwrap();
+    assert!(FileBackedWitnessOracle::open::<C>(filename.to_str().unwrap()).is_err());
+    std::fs::remove_file(&filename).unwrap();
+}
+
+#[test]
+fn test_public_input_bytes() {
+    // of two private inputs and one public input per witness
+    let num_witnesses = C::get_field_pack_size();
+    let mut legacy = vec![];
+    num_witnesses.serialize_into(&mut legacy).unwrap();
+    2usize.serialize_into(&mut legacy).unwrap();
+    1usize.serialize_into(&mut legacy).unwrap();
+    [0u64; 4].serialize_into(&mut legacy).unwrap();
+    for i in 0..num_witnesses * 3 {
+        F::from(i as u32).serialize_into(&mut legacy).unwrap();
+    }
+    let witness = migrate_v0_to_v1(&legacy, FieldType::M31);
+
+    let mut prover = circuit();
+    prover.try_load_witness_bytes(&witness, false).unwrap();
+    let public_input = prover.public_input_bytes(&witness).unwrap();
+    assert_eq!(
+        public_input.len(),
+        WITNESS_HEADER_SIZE + 56 + num_witnesses * F::SERIALIZED_SIZE
+    );
+
+    // the verifier loads the same public inputs from either file, without the private inputs
+    for bytes in [&public_input, &witness] {
+        let mut verifier = circuit();
+        verifier.try_load_public_input_bytes(bytes, false).unwrap();
+        assert_eq!(verifier.public_input, prover.public_input);
+        assert!(verifier.layers[0].input_vals.is_empty());
+    }
+    assert!(matches!(
+        circuit().try_load_witness_bytes(&public_input, false),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::PrivateInputMismatch {
+                expected: 2,
+                got: 0
+            }
+        ))
+    ));
+    assert!(matches!(
+        circuit().try_load_public_input_bytes(&public_input[..public_input.len() - 1], false),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::LengthMismatch { .. }
+        ))
+    ));
+}
+
+/// The witness bytes of `num_witnesses` witnesses of two private inputs and `num_public_inputs`
+/// public inputs, the values counting up from `first`.
+fn instance_bytes(num_witnesses: usize, num_public_inputs: usize, first: u32) -> Vec<u8> {
+    let mut legacy = vec![];
+    num_witnesses.serialize_into(&mut legacy).unwrap();
+    2usize.serialize_into(&mut legacy).unwrap();
+    num_public_inputs.serialize_into(&mut legacy).unwrap();
+    [0u64; 4].serialize_into(&mut legacy).unwrap();
+    for i in 0..num_witnesses * (2 + num_public_inputs) {
+        F::from(first + i as u32)
+            .serialize_into(&mut legacy)
+            .unwrap();
+    }
+    migrate_v0_to_v1(&legacy, FieldType::M31)
+}
+
+fn batch_error(bytes: &[u8]) -> WitnessError {
+    match circuit().load_witness_batch_bytes(bytes) {
+        Err(CircuitError::WitnessFormat(e)) => e,
+        other => panic!("expected a witness format error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_witness_batch() {
+    // more instances than the simd lanes, but not twice as many
+    let pack_size = C::get_field_pack_size();
+    let num_instances = pack_size + 3;
+    let instances = (0..num_instances)
+        .map(|i| instance_bytes(1, 1, 3 * i as u32))
+        .collect::<Vec<_>>();
+    let instance_refs = instances.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let batch = encode_witness_batch(&instance_refs);
+    assert!(batch.starts_with(&WITNESS_BATCH_MAGIC));
+
+    let packed = circuit().load_witness_batch_bytes(&batch).unwrap();
+    assert_eq!(packed.num_instances, num_instances);
+    assert_eq!(packed.packs.len(), 2);
+    for (pack, (private_input, public_input)) in packed.packs.iter().enumerate() {
+        assert_eq!(private_input.len(), 2);
+        assert_eq!(public_input.len(), 1);
+        let values = [&private_input[0], &private_input[1], &public_input[0]];
+        for (offset, value) in values.into_iter().enumerate() {
+            for (lane, value) in value.unpack().into_iter().enumerate() {
+                let instance = pack * pack_size + lane;
+                // the lanes past the instances are zero witnesses
+                let expected = if instance < num_instances {
+                    F::from((3 * instance + offset) as u32)
+                } else {
+                    F::ZERO
+                };
+                assert_eq!(value, expected);
+            }
+        }
+    }
+
+    // the verifier packs the same public inputs from the public inputs alone
+    let prover = circuit();
+    let public_instances = instances
+        .iter()
+        .map(|instance| prover.public_input_bytes(instance).unwrap())
+        .collect::<Vec<_>>();
+    let public_refs = public_instances
+        .iter()
+        .map(Vec::as_slice)
+        .collect::<Vec<_>>();
+    for bytes in [encode_witness_batch(&public_refs), batch.clone()] {
+        let public = prover.load_public_input_batch_bytes(&bytes).unwrap();
+        assert_eq!(public.num_instances, num_instances);
+        for (public, packed) in public.packs.iter().zip(&packed.packs) {
+            assert!(public.0.is_empty());
+            assert_eq!(public.1, packed.1);
+        }
+    }
+
+    assert_eq!(
+        batch_error(&batch[..batch.len() - 1]),
+        WitnessError::BatchTruncated {
+            instance: num_instances - 1
+        }
+    );
+    let mut long = batch.clone();
+    long.push(0);
+    assert_eq!(batch_error(&long), WitnessError::BatchTrailingBytes(1));
+    assert_eq!(batch_error(&batch[..8]), WitnessError::Truncated);
+    assert_eq!(batch_error(&instances[0]), WitnessError::WrongMagic);
+    assert_eq!(
+        batch_error(&encode_witness_batch(&[])),
+        WitnessError::NoWitnesses
+    );
+    assert_eq!(
+        batch_error(&encode_witness_batch(&[
+            &instances[0],
+            &instance_bytes(2, 1, 0)
+        ])),
+        WitnessError::NotSingleWitness {
+            instance: 1,
+            got: 2
+        }
+    );
+    assert_eq!(
+        batch_error(&encode_witness_batch(&[
+            &instances[0],
+            &instance_bytes(1, 2, 0)
+        ])),
+        WitnessError::PublicInputMismatch {
+            instance: 1,
+            expected: 1,
+            got: 2
+        }
+    );
+}
@@ -0,0 +1,104 @@
+use arith::SimdField;
+use circuit::Circuit;
+use mersenne31::M31;
+
+use super::circuit_dump::{circuit_bytes, witness_bytes};
+use super::common::C;
+use crate::{
+    load_witness_json, parse_witness_json, witness_bytes_to_json, witness_json_to_bytes,
+    WitnessJsonError,
+};
+
+fn circuit() -> Circuit<C> {
+    Circuit::load_circuit_from_bytes(&circuit_bytes())
+}
+
+#[test]
+fn test_witness_json_to_bytes() {
+    let circuit = circuit();
+    // the witness files of the tests carry no modulus after their counts
+    let to_json = |bytes: &[u8]| witness_bytes_to_json::<C>(bytes).unwrap();
+    let json = r#"{"private": ["0", "0x1", 2, "0X3"]}"#;
+    let bytes = witness_json_to_bytes(&circuit, json).unwrap();
+    assert_eq!(bytes.len(), witness_bytes(1).len());
+    assert_eq!(to_json(&bytes), to_json(&witness_bytes(1)));
+    assert!(to_json(&bytes).trim_start().starts_with('{'));
+
+    let json = to_json(&witness_bytes(2));
+    assert!(json.trim_start().starts_with('['));
+    let witness = parse_witness_json(&circuit, &json).unwrap();
+    assert_eq!(witness.num_witnesses, 2);
+    assert_eq!(witness.values[5], M31::from(2));
+    assert_eq!(
+        to_json(&witness_json_to_bytes(&circuit, &json).unwrap()),
+        json
+    );
+
+    let mut circuit = circuit;
+    load_witness_json(&mut circuit, r#"{"private": ["5", "6", "7", "8"]}"#).unwrap();
+    assert_eq!(circuit.layers[0].input_vals[1].unpack()[0], M31::from(6));
+}
+
+#[test]
+fn test_witness_json_errors() {
+    let circuit = circuit();
+    let parse = |json: &str| parse_witness_json(&circuit, json).unwrap_err();
+
+    match parse(r#"{"private": ["0", "0xzz", "2", "3"]}"#) {
+        WitnessJsonError::InvalidValue {
+            witness: 0,
+            section: "private",
+            index: 1,
+            value,
+        } => assert_eq!(value, "\"0xzz\""),
+        other => panic!("expected an invalid value, got {:?}", other),
+    }
+    assert!(matches!(
+        parse(r#"[{"private": [0, 1, 2, 3]}, {"private": [0, 1, -2, 3]}]"#),
+        WitnessJsonError::InvalidValue {
+            witness: 1,
+            index: 2,
+            ..
+        }
+    ));
+    // the modulus of M31
+    assert!(matches!(
+        parse(r#"{"private": ["0", "1", "2", "2147483647"]}"#),
+        WitnessJsonError::ValueOutOfRange { index: 3, .. }
+    ));
+    assert!(matches!(
+        parse(&format!(
+            r#"{{"private": ["0x1{}", "1", "2", "3"]}}"#,
+            "0".repeat(64)
+        )),
+        WitnessJsonError::ValueOutOfRange { index: 0, .. }
+    ));
+    assert!(matches!(
+        parse(r#"{"private": ["0", "1", "2"]}"#),
+        WitnessJsonError::PrivateInputCount {
+            witness: 0,
+            expected: 4,
+            found: 3
+        }
+    ));
+    assert!(matches!(
+        parse(r#"[{"private": [0, 1, 2, 3], "public": [1]}, {"private": [0, 1, 2, 3]}]"#),
+        WitnessJsonError::PublicInputCount {
+            witness: 1,
+            expected: 1,
+            found: 0
+        }
+    ));
+    assert!(matches!(parse("[]"), WitnessJsonError::NoWitnesses));
+    assert!(matches!(
+        parse(r#"{"privat": [0, 1, 2, 3]}"#),
+        WitnessJsonError::Json(_)
+    ));
+
+    let mut truncated = witness_bytes(2);
+    truncated.pop();
+    assert!(matches!(
+        witness_bytes_to_json::<C>(&truncated),
+        Err(WitnessJsonError::Witness(_))
+    ));
+}
@@ -0,0 +1,77 @@
+use std::fs;
+
+use arith::FieldSerde;
+use circuit::{migrate_v0_to_v1, Circuit, CircuitError, WitnessError};
+use config::{FieldType, GKRConfig};
+
+use super::common::{single_layer_circuit, C, F};
+use crate::BatchWitnessLoader;
+
+/// A witness of `num_witnesses` witnesses, of 2 private inputs and 1 public input each.
+fn witness_bytes(num_witnesses: usize, offset: u32) -> Vec<u8> {
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    2usize.serialize_into(&mut bytes).unwrap();
+    1usize.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    (0..num_witnesses as u32 * 3)
+        .for_each(|i| F::from(offset + i).serialize_into(&mut bytes).unwrap());
+    migrate_v0_to_v1(&bytes, FieldType::M31)
+}
+
+#[test]
+fn test_batch_witness_loader() {
+    let circuit = single_layer_circuit(1, 1);
+    let num_witnesses = C::get_field_pack_size();
+    let dir = std::env::temp_dir();
+    let files = (0..3)
+        .map(|i| {
+            let file = dir.join(format!("witness_loader_{}_{}.txt", std::process::id(), i));
+            fs::write(&file, witness_bytes(num_witnesses, 100 * i)).unwrap();
+            file.to_str().unwrap().to_owned()
+        })
+        .collect::<Vec<_>>();
+    let file_names = files.iter().map(String::as_str).collect::<Vec<_>>();
+
+    // in the order of the files, as parsed one at a time
+    let loaded = BatchWitnessLoader::load_parallel(&circuit, &file_names).unwrap();
+    assert_eq!(loaded.len(), files.len());
+    for (witness, i) in loaded.iter().zip(0..) {
+        let (private_input, public_input) =
+            Circuit::<C>::parse_witness_bytes(1, &witness_bytes(num_witnesses, 100 * i), false);
+        assert_eq!(witness.private_input, private_input);
+        assert_eq!(witness.public_input, public_input);
+    }
+    let mut applied = single_layer_circuit(1, 1);
+    loaded[1].apply_to(&mut applied);
+    assert_eq!(applied.layers[0].input_vals, loaded[1].private_input);
+
+    // a witness of too few witnesses is an error, unless padded
+    fs::write(&files[1], witness_bytes(num_witnesses - 1, 0)).unwrap();
+    assert!(matches!(
+        BatchWitnessLoader::load_parallel(&circuit, &file_names),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::NotEnoughWitnesses { .. }
+        ))
+    ));
+    assert!(BatchWitnessLoader::load_parallel_with_padding(&circuit, &file_names, true).is_ok());
+
+    // as are a truncated witness and a missing file
+    let mut truncated = witness_bytes(num_witnesses, 0);
+    truncated.pop();
+    fs::write(&files[1], truncated).unwrap();
+    assert!(matches!(
+        BatchWitnessLoader::load_parallel(&circuit, &file_names),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::LengthMismatch { .. }
+        ))
+    ));
+    fs::remove_file(&files[1]).unwrap();
+    assert!(matches!(
+        BatchWitnessLoader::load_parallel(&circuit, &file_names),
+        Err(CircuitError::OtherError(_))
+    ));
+
+    fs::remove_file(&files[0]).unwrap();
+    fs::remove_file(&files[2]).unwrap();
+}
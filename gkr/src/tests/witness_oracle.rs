@@ -0,0 +1,87 @@
+use std::fs;
+
+use arith::{Field, FieldSerde};
+use circuit::{migrate_v0_to_v1, Circuit, WITNESS_HEADER_SIZE};
+use config::{FieldType, GKRConfig};
+
+use super::common::C;
+use crate::{FileBackedWitnessOracle, WitnessOracle};
+
+#[test]
+fn test_file_backed_witness_oracle() {
+    let log_input_size = 1;
+    let num_witnesses = C::get_field_pack_size();
+    let num_private = 1 << log_input_size;
+    let num_public = 1;
+
+    let mut rng = rand::thread_rng();
+    let values = (0..num_witnesses * (num_private + num_public))
+        .map(|_| <C as GKRConfig>::CircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    num_private.serialize_into(&mut bytes).unwrap();
+    num_public.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    values
+        .iter()
+        .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+    let bytes = migrate_v0_to_v1(&bytes, FieldType::M31);
+
+    let filename = std::env::temp_dir().join(format!("witness_oracle_{}.txt", std::process::id()));
+    fs::write(&filename, &bytes).unwrap();
+    let oracle = FileBackedWitnessOracle::open::<C>(filename.to_str().unwrap()).unwrap();
+
+    assert_eq!(WitnessOracle::<C>::num_witnesses(&oracle), num_witnesses);
+    for (i, v) in values.iter().enumerate() {
+        assert_eq!(WitnessOracle::<C>::get(&oracle, i), *v);
+    }
+
+    let expected = Circuit::<C>::parse_witness_bytes(log_input_size, &bytes, false);
+    let packed = Circuit::<C>::pack_witness_values(
+        log_input_size,
+        num_witnesses,
+        num_private,
+        num_public,
+        false,
+        |idx| WitnessOracle::<C>::get(&oracle, idx),
+    );
+    assert_eq!(packed, expected);
+
+    fs::remove_file(&filename).unwrap();
+}
+
+#[test]
+fn test_file_backed_witness_oracle_length() {
+    let witness = |num_witnesses: usize, num_values: usize| {
+        let mut bytes = vec![];
+        num_witnesses.serialize_into(&mut bytes).unwrap();
+        1usize.serialize_into(&mut bytes).unwrap();
+        1usize.serialize_into(&mut bytes).unwrap();
+        [0u64; 4].serialize_into(&mut bytes).unwrap();
+        (0..num_values).for_each(|i| {
+            <C as GKRConfig>::CircuitField::from(i as u32)
+                .serialize_into(&mut bytes)
+                .unwrap()
+        });
+        migrate_v0_to_v1(&bytes, FieldType::M31)
+    };
+    let filename =
+        std::env::temp_dir().join(format!("witness_oracle_length_{}.txt", std::process::id()));
+    let open = |bytes: Vec<u8>| {
+        fs::write(&filename, bytes).unwrap();
+        FileBackedWitnessOracle::open::<C>(filename.to_str().unwrap())
+    };
+
+    assert!(open(witness(4, 8)).is_ok());
+    // a value missing or in excess, counts of more values than a usize, and a bare header
+    assert!(open(witness(4, 7)).is_err());
+    assert!(open(witness(4, 9)).is_err());
+    assert!(open(witness(usize::MAX / 2, 8)).is_err());
+    let mut header = witness(4, 8);
+    header.truncate(WITNESS_HEADER_SIZE + 4);
+    assert!(open(header).is_err());
+
+    fs::remove_file(&filename).unwrap();
+}
@@ -0,0 +1,111 @@
+use arith::FieldSerde;
+use circuit::{migrate_v0_to_v1, Circuit, CircuitError, WitnessError, WITNESS_CHUNK_SIZE};
+use config::{FieldType, GKRConfig};
+
+use super::common::{single_layer_circuit, C, F};
+
+/// The witness bytes of `num_witnesses` witnesses, the values numbered in order.
+fn witness_bytes(num_witnesses: usize, num_private: usize, num_public: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    num_private.serialize_into(&mut bytes).unwrap();
+    num_public.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for i in 0..num_witnesses * (num_private + num_public) {
+        F::from(i as u32).serialize_into(&mut bytes).unwrap();
+    }
+    migrate_v0_to_v1(&bytes, FieldType::M31)
+}
+
+fn circuit() -> Circuit<C> {
+    single_layer_circuit(3, 1)
+}
+
+#[test]
+fn test_load_witness_parallel() {
+    let path = std::env::temp_dir().join(format!("witness_parallel_{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, witness_bytes(C::get_field_pack_size(), 8, 3)).unwrap();
+
+    let mut expected = circuit();
+    expected.load_witness_file(path);
+    for n_threads in [1, 3, 7, 64] {
+        let mut circuit = circuit();
+        circuit.load_witness_parallel(path, n_threads).unwrap();
+        assert_eq!(circuit.layers[0].input_vals, expected.layers[0].input_vals);
+        assert_eq!(circuit.public_input, expected.public_input);
+    }
+
+    // one value short
+    let mut bytes = witness_bytes(C::get_field_pack_size(), 8, 3);
+    bytes.truncate(bytes.len() - 1);
+    std::fs::write(path, bytes).unwrap();
+    assert!(matches!(
+        circuit().load_witness_parallel(path, 4),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::LengthMismatch { .. }
+        ))
+    ));
+
+    std::fs::write(path, witness_bytes(C::get_field_pack_size(), 4, 0)).unwrap();
+    assert!(matches!(
+        circuit().load_witness_parallel(path, 4),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::PrivateInputMismatch {
+                expected: 8,
+                got: 4
+            }
+        ))
+    ));
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        circuit().load_witness_parallel("no/such/witness.txt", 4),
+        Err(CircuitError::OtherError(_))
+    ));
+}
+
+/// The witness read a chunk at a time is packed as the one deserialized as a whole, over
+/// several chunks.
+#[test]
+fn test_load_witness_reader() {
+    let input_var_num = WITNESS_CHUNK_SIZE.trailing_zeros() as usize + 1;
+    let path = std::env::temp_dir().join(format!("witness_reader_{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+    for num_witnesses in [C::get_field_pack_size(), 3, C::get_field_pack_size() + 2] {
+        let bytes = witness_bytes(num_witnesses, 1 << input_var_num, 5);
+        let mut expected = single_layer_circuit(input_var_num, 1);
+        expected.load_witness_bytes(&bytes, true);
+
+        let mut circuit = single_layer_circuit(input_var_num, 1);
+        circuit
+            .try_load_witness_reader(std::io::Cursor::new(&bytes), true)
+            .unwrap();
+        assert_eq!(circuit.layers[0].input_vals, expected.layers[0].input_vals);
+        assert_eq!(circuit.public_input, expected.public_input);
+
+        let mut circuit = single_layer_circuit(input_var_num, 1);
+        circuit.try_load_public_input_bytes(&bytes, true).unwrap();
+        assert_eq!(circuit.public_input, expected.public_input);
+
+        if num_witnesses >= C::get_field_pack_size() {
+            std::fs::write(path, &bytes).unwrap();
+            let mut circuit = single_layer_circuit(input_var_num, 1);
+            circuit.try_load_witness_file(path).unwrap();
+            assert_eq!(circuit.layers[0].input_vals, expected.layers[0].input_vals);
+            assert_eq!(circuit.public_input, expected.public_input);
+        }
+    }
+    std::fs::remove_file(path).unwrap();
+
+    // one value short, in the last chunk
+    let mut bytes = witness_bytes(C::get_field_pack_size(), 1 << input_var_num, 5);
+    bytes.truncate(bytes.len() - 1);
+    assert!(matches!(
+        single_layer_circuit(input_var_num, 1)
+            .try_load_witness_reader(std::io::Cursor::new(&bytes), true),
+        Err(CircuitError::WitnessFormat(
+            WitnessError::LengthMismatch { .. }
+        ))
+    ));
+}
@@ -0,0 +1,123 @@
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+
+use super::common::{C, F};
+use crate::{
+    ElementRange, SchemaValidationError, WitnessConstraint, WitnessSchema, WitnessSchemaRegistry,
+};
+
+fn test_circuit() -> Circuit<C> {
+    Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            mul: vec![Gate {
+                i_ids: [0, 1],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: F::from(1),
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn test_schema() -> WitnessSchema {
+    WitnessSchema {
+        field_type: "M31".to_string(),
+        element_count: 4,
+        ranges: vec![ElementRange {
+            start: 2,
+            end: 4,
+            min: 0,
+            max: 255,
+        }],
+        constraints: vec![
+            WitnessConstraint::Boolean { start: 0, end: 2 },
+            WitnessConstraint::Equal { a: 2, b: 3 },
+        ],
+    }
+}
+
+fn witness(values: &[u32]) -> Vec<F> {
+    values.iter().map(|v| F::from(*v)).collect()
+}
+
+#[test]
+fn test_witness_schema_registry() {
+    let circuit = test_circuit();
+    let mut registry = WitnessSchemaRegistry::new();
+    assert_eq!(
+        registry.validate(&circuit, &witness(&[0, 1, 7, 7])),
+        Err(SchemaValidationError::UnknownCircuit)
+    );
+
+    registry.register(&circuit.hash(), test_schema());
+    assert_eq!(registry.validate(&circuit, &witness(&[0, 1, 7, 7])), Ok(()));
+    assert_eq!(
+        registry.validate(&circuit, &witness(&[0, 1, 7])),
+        Err(SchemaValidationError::CountMismatch {
+            expected: 4,
+            found: 3
+        })
+    );
+    assert_eq!(
+        registry.validate(&circuit, &witness(&[0, 1, 7, 256])),
+        Err(SchemaValidationError::OutOfRange {
+            index: 3,
+            min: 0,
+            max: 255
+        })
+    );
+    assert_eq!(
+        registry.validate(&circuit, &witness(&[2, 1, 7, 7])),
+        Err(SchemaValidationError::ConstraintViolated(
+            WitnessConstraint::Boolean { start: 0, end: 2 }
+        ))
+    );
+    assert_eq!(
+        registry.validate(&circuit, &witness(&[0, 1, 7, 8])),
+        Err(SchemaValidationError::ConstraintViolated(
+            WitnessConstraint::Equal { a: 2, b: 3 }
+        ))
+    );
+
+    let mut schema = test_schema();
+    schema.field_type = "BN254".to_string();
+    registry.register(&circuit.hash(), schema);
+    assert!(matches!(
+        registry.validate(&circuit, &witness(&[0, 1, 7, 7])),
+        Err(SchemaValidationError::FieldMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_witness_schema_registry_toml() {
+    let mut registry = WitnessSchemaRegistry::new();
+    registry.register(&test_circuit().hash(), test_schema());
+    registry.register(&[7; 32], test_schema());
+
+    let s = registry.to_toml().unwrap();
+    assert!(s.contains("kind = \"boolean\""));
+    let loaded = WitnessSchemaRegistry::from_toml(&s).unwrap();
+    assert_eq!(loaded, registry);
+    assert_eq!(loaded.len(), 2);
+
+    let parsed = WitnessSchemaRegistry::from_toml(
+        "[0707070707070707070707070707070707070707070707070707070707070707]\n\
+         field_type = \"M31\"\n\
+         element_count = 1\n\
+         constraints = [{ kind = \"non_zero\", index = 0 }]\n",
+    )
+    .unwrap();
+    let schema = parsed.lookup(&[7; 32]).unwrap();
+    assert!(schema.ranges.is_empty());
+    assert_eq!(schema.validate(&witness(&[3])), Ok(()));
+    assert!(schema.validate(&witness(&[0])).is_err());
+
+    assert!(
+        WitnessSchemaRegistry::from_toml("[abc]\nfield_type = \"M31\"\nelement_count = 1\n")
+            .is_err()
+    );
+}
@@ -0,0 +1,44 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, Gate, WitnessTypedArray};
+use config::GKRConfig;
+
+use super::common::C;
+
+type F = <C as GKRConfig>::SimdCircuitField;
+
+#[test]
+fn test_load_witness_array() {
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 0,
+            mul: vec![Gate {
+                i_ids: [1, 3],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: <C as GKRConfig>::CircuitField::ONE,
+                gate_type: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let mut rng = rand::thread_rng();
+    let witness = WitnessTypedArray::from([(); 4].map(|_| F::random_unsafe(&mut rng)));
+    circuit.load_witness_array(witness);
+    let buffer = circuit.layers[0].input_vals.as_ptr();
+    circuit.evaluate();
+    assert_eq!(
+        circuit.layers[0].output_vals[0],
+        witness.0[1] * witness.0[3]
+    );
+
+    // reloading reuses the input buffer
+    let mut witness = witness;
+    witness.as_mut()[1] = F::ZERO;
+    circuit.load_witness_array(witness);
+    assert_eq!(circuit.layers[0].input_vals.as_ptr(), buffer);
+    circuit.evaluate();
+    assert_eq!(circuit.layers[0].output_vals[0], F::ZERO);
+}
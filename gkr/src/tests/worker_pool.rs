@@ -0,0 +1,129 @@
+use std::{
+    sync::{mpsc, Arc, Barrier, Mutex},
+    time::Duration,
+};
+
+use crate::{WorkerPool, WorkerPoolError};
+
+#[tokio::test]
+async fn test_worker_pool_timeout() {
+    let pool = Arc::new(WorkerPool::new(vec![0usize], Duration::from_millis(50)));
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let first = tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            pool.run(move |count: &mut usize| {
+                started_tx.send(()).unwrap();
+                release_rx.recv().unwrap();
+                *count += 1;
+                *count
+            })
+            .await
+        }
+    });
+    tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(pool.busy(), 1);
+
+    // the only worker is busy
+    assert_eq!(
+        pool.run(|count: &mut usize| *count).await,
+        Err(WorkerPoolError::Timeout)
+    );
+
+    release_tx.send(()).unwrap();
+    assert_eq!(first.await.unwrap(), Ok(1));
+    assert_eq!(pool.busy(), 0);
+    assert_eq!(pool.run(|count: &mut usize| *count).await, Ok(1));
+}
+
+#[tokio::test]
+async fn test_worker_pool_parallel() {
+    // each job waits for the other one, which only returns if they run at the same time
+    let pool = Arc::new(WorkerPool::new(vec![(); 2], Duration::from_secs(10)));
+    let barrier = Arc::new(Barrier::new(2));
+    let jobs = (0..2)
+        .map(|i| {
+            let pool = pool.clone();
+            let barrier = barrier.clone();
+            tokio::spawn(async move {
+                pool.run(move |_: &mut ()| {
+                    barrier.wait();
+                    i
+                })
+                .await
+            })
+        })
+        .collect::<Vec<_>>();
+    for (i, job) in jobs.into_iter().enumerate() {
+        assert_eq!(job.await.unwrap(), Ok(i));
+    }
+}
+
+#[tokio::test]
+async fn test_worker_pool_panic() {
+    let pool = Arc::new(WorkerPool::new(vec![0usize], Duration::from_secs(10)));
+    assert_eq!(
+        pool.run(|_: &mut usize| -> usize { panic!("job failure") })
+            .await,
+        Err(WorkerPoolError::Panicked)
+    );
+    assert_eq!(pool.busy(), 0);
+    assert_eq!(pool.run(|count: &mut usize| *count).await, Ok(0));
+}
+
+#[tokio::test]
+async fn test_worker_pool_run_queued() {
+    let pool = Arc::new(WorkerPool::new(vec![0usize], Duration::from_millis(10)));
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let first = tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            pool.run(move |count: &mut usize| {
+                release_rx.recv().unwrap();
+                *count += 1;
+            })
+            .await
+        }
+    });
+    while pool.busy() == 0 {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    // waits past the timeout of the pool for the worker to be free
+    let queued = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.run_queued(|count: &mut usize| *count).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!queued.is_finished());
+    release_tx.send(()).unwrap();
+    assert_eq!(first.await.unwrap(), Ok(()));
+    assert_eq!(queued.await.unwrap(), Ok(1));
+}
+
+#[tokio::test]
+async fn test_worker_pool_run_on_all() {
+    let pool = Arc::new(WorkerPool::new(vec![0usize; 3], Duration::from_secs(10)));
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let release_rx = Mutex::new(release_rx);
+    let prepared = pool.run_on_all(move |count: &mut usize| {
+        release_rx.lock().unwrap().recv().unwrap();
+        *count += 1;
+    });
+    // taken before the future is awaited
+    let job = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.run(|count: &mut usize| *count).await }
+    });
+    let prepared = tokio::spawn(prepared);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!job.is_finished());
+    for _ in 0..3 {
+        release_tx.send(()).unwrap();
+    }
+    assert_eq!(prepared.await.unwrap(), Ok(()));
+    assert_eq!(job.await.unwrap(), Ok(1));
+}
@@ -0,0 +1,172 @@
+//! Proving competitions between (circuit, witness) entries, for benchmarking.
+//!
+//! The entries are proven for several rounds, the order of the entries rotating from one round
+//! to the next so that no entry always runs first on a cold machine. An entry scores up to 50
+//! points for its mean proving time and 50 for its proof size, relative to the best entry,
+//! e.g., an entry twice as slow as the fastest gets 25 time points. Entries whose proof does not
+//! verify are disqualified with a score of 0.
+
+use std::{fmt::Write, time::Duration};
+
+use circuit::Circuit;
+use config::{Config, ConfigError, GKRConfig};
+
+use crate::{Prover, Verifier};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentEntry {
+    pub circuit: String,
+    pub witness: String,
+    /// The proving time of each round.
+    pub prove_times: Vec<Duration>,
+    pub proof_size: usize,
+    pub verified: bool,
+    pub score: f64,
+    /// 1 for the first entry.
+    pub rank: usize,
+}
+
+impl TournamentEntry {
+    pub fn mean_prove_time(&self) -> Duration {
+        self.prove_times.iter().sum::<Duration>() / self.prove_times.len().max(1) as u32
+    }
+}
+
+/// The entries, best first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TournamentResults {
+    pub entries: Vec<TournamentEntry>,
+}
+
+impl TournamentResults {
+    /// Score and rank the entries, ignoring their current scores and ranks.
+    /// Ties keep the order of the entries.
+    pub fn from_entries(mut entries: Vec<TournamentEntry>) -> Self {
+        let qualified = entries.iter().filter(|e| e.verified);
+        let best_time = qualified
+            .clone()
+            .map(|e| e.mean_prove_time().as_secs_f64())
+            .fold(f64::INFINITY, f64::min);
+        let best_size = qualified.map(|e| e.proof_size).min().unwrap_or(0);
+
+        for entry in &mut entries {
+            entry.score = if entry.verified {
+                let time = entry.mean_prove_time().as_secs_f64();
+                let time_score = if time > 0.0 { best_time / time } else { 1.0 };
+                let size_score = if entry.proof_size > 0 {
+                    best_size as f64 / entry.proof_size as f64
+                } else {
+                    1.0
+                };
+                50.0 * (time_score + size_score)
+            } else {
+                0.0
+            };
+        }
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.rank = i + 1;
+        }
+        Self { entries }
+    }
+
+    /// The leaderboard as a markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "| Rank | Circuit | Witness | Mean prove time (ms) | Proof size (bytes) | Verified | Score |"
+        )
+        .unwrap();
+        writeln!(out, "|---:|---|---|---:|---:|:---:|---:|").unwrap();
+        for e in &self.entries {
+            writeln!(
+                out,
+                "| {} | {} | {} | {:.3} | {} | {} | {:.2} |",
+                e.rank,
+                e.circuit,
+                e.witness,
+                e.mean_prove_time().as_secs_f64() * 1000.0,
+                e.proof_size,
+                if e.verified { "yes" } else { "no" },
+                e.score
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+pub struct TournamentScheduler {
+    circuits: Vec<String>,
+    witnesses: Vec<String>,
+    pub rounds: usize,
+}
+
+impl TournamentScheduler {
+    /// The entries are the circuit files with the witness files at the same index.
+    /// Three rounds by default.
+    pub fn new(circuits: Vec<&str>, witnesses: Vec<&str>) -> Self {
+        assert_eq!(
+            circuits.len(),
+            witnesses.len(),
+            "one witness per circuit expected"
+        );
+        Self {
+            circuits: circuits.into_iter().map(String::from).collect(),
+            witnesses: witnesses.into_iter().map(String::from).collect(),
+            rounds: 3,
+        }
+    }
+
+    /// Fails as `Prover::new` for the config.
+    pub fn run<C: GKRConfig>(&self, config: &Config<C>) -> Result<TournamentResults, ConfigError> {
+        let n = self.circuits.len();
+        let mut circuits = self
+            .circuits
+            .iter()
+            .zip(&self.witnesses)
+            .map(|(circuit_file, witness_file)| {
+                let mut circuit = Circuit::<C>::load_circuit(circuit_file);
+                circuit.load_witness_file(witness_file);
+                circuit
+            })
+            .collect::<Vec<_>>();
+        let mut entries = self
+            .circuits
+            .iter()
+            .zip(&self.witnesses)
+            .map(|(circuit, witness)| TournamentEntry {
+                circuit: circuit.clone(),
+                witness: witness.clone(),
+                prove_times: vec![],
+                proof_size: 0,
+                verified: true,
+                score: 0.0,
+                rank: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let verifier = Verifier::new(config);
+        for round in 0..self.rounds {
+            for i in (0..n).map(|k| (k + round) % n) {
+                let circuit = &mut circuits[i];
+                let mut prover = Prover::new(config)?;
+                prover.prepare_mem(circuit);
+                let start = std::time::Instant::now();
+                let (claimed_v, proof) = prover.prove_silent(circuit);
+                entries[i].prove_times.push(start.elapsed());
+
+                // the proof is the same in all rounds, check it once
+                if round == 0 {
+                    let public_input = circuit.public_input.clone();
+                    entries[i].proof_size = proof.bytes.len();
+                    entries[i].verified =
+                        verifier.verify(circuit, &public_input, &claimed_v, &proof);
+                }
+            }
+        }
+
+        Ok(TournamentResults::from_entries(entries))
+    }
+}
@@ -1,6 +1,9 @@
 use std::fs;
 use std::process::Command;
 
+use circuit::{migrate_v0_to_v1, WITNESS_MAGIC};
+use config::FieldType;
+
 const DATA_PREFIX: &str = "data/";
 
 // circuit for repeating Keccak for 2 times
@@ -49,6 +52,11 @@ pub const POSEIDON_WITNESS_M31_URL: &str =
 // pub const POSEIDON_WITNESS_BN254_URL: &str =
 //         "https://storage.googleapis.com/expander-compiled-circuits/poseidon-ci/poseidon_120_witness_bn254.txt";
 
+// circuit of `small_circuit` over BabyBear, checked in rather than downloaded
+pub const BABYBEAR_CIRCUIT: &str = "data/circuit_babybear.txt";
+
+pub const BABYBEAR_WITNESS: &str = "data/witness_babybear.txt";
+
 // NOTE(Hang 08/23/24):
 // CI process is unhappy about reqwest as a dependency,
 // so we use wget as a backup option.
@@ -62,6 +70,15 @@ fn download_and_store(url: &str, file: &str) {
     assert!(download.status.success(), "Circuit download failure")
 }
 
+// the CI witnesses are of the format before the witness header
+fn download_and_store_witness(url: &str, file: &str, field: FieldType) {
+    download_and_store(url, file);
+    let bytes = fs::read(file).unwrap();
+    if !bytes.starts_with(&WITNESS_MAGIC) {
+        fs::write(file, migrate_v0_to_v1(&bytes, field)).unwrap();
+    }
+}
+
 pub fn dev_env_data_setup() {
     fs::create_dir_all(DATA_PREFIX).unwrap();
 
@@ -69,14 +86,22 @@ pub fn dev_env_data_setup() {
     download_and_store(KECCAK_CIRCUIT_GF2_URL, KECCAK_GF2_CIRCUIT);
     download_and_store(KECCAK_CIRCUIT_BN254_URL, KECCAK_BN254_CIRCUIT);
 
-    download_and_store(KECCAK_WITNESS_M31_URL, KECCAK_M31_WITNESS);
-    download_and_store(KECCAK_WITNESS_GF2_URL, KECCAK_GF2_WITNESS);
-    download_and_store(KECCAK_WITNESS_BN254_URL, KECCAK_BN254_WITNESS);
+    download_and_store_witness(KECCAK_WITNESS_M31_URL, KECCAK_M31_WITNESS, FieldType::M31);
+    download_and_store_witness(KECCAK_WITNESS_GF2_URL, KECCAK_GF2_WITNESS, FieldType::GF2);
+    download_and_store_witness(
+        KECCAK_WITNESS_BN254_URL,
+        KECCAK_BN254_WITNESS,
+        FieldType::BN254,
+    );
 
     download_and_store(POSEIDON_CIRCUIT_M31_URL, POSEIDON_M31_CIRCUIT);
     // download_and_store(POSEIDON_CIRCUIT_BN254_URL, POSEIDON_BN254_CIRCUIT);
 
-    download_and_store(POSEIDON_WITNESS_M31_URL, POSEIDON_M31_WITNESS);
+    download_and_store_witness(
+        POSEIDON_WITNESS_M31_URL,
+        POSEIDON_M31_WITNESS,
+        FieldType::M31,
+    );
     // download_and_store(POSEIDON_WITNESS_BN254_URL, POSEIDON_BN254_WITNESS);
 }
 
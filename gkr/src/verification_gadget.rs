@@ -0,0 +1,242 @@
+//! Encoding the sumcheck checks of the GKR verifier as a GKR circuit, towards recursion.
+//!
+//! The gadget circuit takes as inputs the round polynomials of an inner proof, given by their
+//! evaluations at 0, ..., d, and the round challenges. For every round of a sumcheck phase but
+//! the first, it computes `p_k(0) + p_k(1) - p_{k-1}(r_{k-1})` on an output wire, so that the
+//! checks pass iff the output is all zero.
+//!
+//! Still to be checked outside of the gadget: the Fiat-Shamir derivation of the challenges, and
+//! the first and last rounds of each phase, which are tied to the claims through the wiring of
+//! the inner circuit. The arithmetic is over the circuit field, so the challenge field has to be
+//! the circuit field itself, as for BN254.
+
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, Field, FieldSerde};
+use circuit::{Circuit, GateBuilder, WireRef};
+use config::{Config, GKRConfig, GKRScheme, PolynomialCommitmentType};
+use transcript::Proof;
+
+use crate::{verifier::SumcheckMessages, ProofLayout, ProofSegment, VerificationKey, Verifier};
+
+/// Where the gadget inputs come from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputMap {
+    /// `(offset, wire)` pairs: the proof element at byte `offset` is fed to the input `wire`.
+    pub proof_wires: Vec<(usize, usize)>,
+    /// The input wire of the challenge of each sumcheck round, in proof order.
+    pub challenge_wires: Vec<usize>,
+    /// Size of the input layer of the gadget circuit.
+    pub input_size: usize,
+}
+
+impl InputMap {
+    pub fn assign<C: GKRConfig>(
+        &self,
+        proof_bytes: &[u8],
+        challenges: &[C::ChallengeField],
+    ) -> Vec<C::SimdCircuitField> {
+        assert_eq!(challenges.len(), self.challenge_wires.len());
+        let element_size = C::CircuitField::SERIALIZED_SIZE;
+
+        let mut input = vec![C::SimdCircuitField::ZERO; self.input_size];
+        for &(offset, wire) in &self.proof_wires {
+            let v = C::CircuitField::deserialize_from(&proof_bytes[offset..offset + element_size])
+                .unwrap();
+            input[wire] = C::circuit_field_to_simd_circuit_field(&v);
+        }
+        for (r, &wire) in challenges.iter().zip(&self.challenge_wires) {
+            // the challenge field is the circuit field
+            let mut bytes = vec![];
+            r.serialize_into(&mut bytes).unwrap();
+            let v = C::CircuitField::deserialize_from(bytes.as_slice()).unwrap();
+            input[wire] = C::circuit_field_to_simd_circuit_field(&v);
+        }
+        input
+    }
+}
+
+/// The input wires of a sumcheck round.
+struct RoundWires {
+    degree: usize,
+    /// The evaluations at 0, ..., degree.
+    evals: Vec<WireRef>,
+    challenge: WireRef,
+}
+
+pub struct NativeVerificationGadget<C: GKRConfig> {
+    _marker: PhantomData<C>,
+}
+
+impl<C: GKRConfig> NativeVerificationGadget<C> {
+    /// Build the gadget checking the proofs of the inner circuit.
+    ///
+    /// Panics unless the key is the key of the circuit and config, for vanilla GKR with the raw
+    /// commitment, or if the challenge field is an extension of the circuit field.
+    pub fn build(
+        inner_circuit: &Circuit<C>,
+        inner_config: &Config<C>,
+        inner_vk: &VerificationKey<C>,
+    ) -> (Circuit<C>, InputMap) {
+        assert_eq!(
+            <C::ChallengeField as ExtensionField>::DEGREE,
+            1,
+            "the challenge field must be the circuit field"
+        );
+        assert!(
+            inner_vk.matches(inner_circuit, inner_config),
+            "the verification key is not the key of the inner circuit"
+        );
+        assert!(
+            inner_vk.gkr_scheme == GKRScheme::Vanilla
+                && inner_vk.polynomial_commitment_type == PolynomialCommitmentType::Raw,
+            "only vanilla GKR proofs with the raw commitment are supported"
+        );
+
+        // the sumcheck rounds as (offset, degree), split into phases of consecutive rounds
+        let layout = ProofLayout::new(inner_circuit, inner_config);
+        let mut phases: Vec<Vec<(usize, usize)>> = vec![];
+        let mut in_phase = false;
+        let mut offset = 0;
+        for segment in &layout.segments {
+            match segment {
+                ProofSegment::SumcheckRound { degree } => {
+                    assert!(
+                        (2..=3).contains(degree),
+                        "unsupported sumcheck degree {}",
+                        degree
+                    );
+                    if !in_phase {
+                        phases.push(vec![]);
+                    }
+                    phases.last_mut().unwrap().push((offset, *degree));
+                    in_phase = true;
+                }
+                _ => in_phase = false,
+            }
+            offset += layout.segment_size(segment);
+        }
+
+        let input_size = phases.iter().flatten().map(|(_, d)| d + 2).sum();
+        let mut builder = GateBuilder::<C>::new(input_size);
+        let mut input_map = InputMap::default();
+        let mut rounds = vec![];
+        let mut next_input = 0;
+        for &(offset, degree) in phases.iter().flatten() {
+            let evals = (0..=degree)
+                .map(|i| {
+                    let wire = next_input + i;
+                    input_map
+                        .proof_wires
+                        .push((offset + i * layout.element_size, wire));
+                    builder.input(wire)
+                })
+                .collect();
+            let challenge = builder.input(next_input + degree + 1);
+            input_map.challenge_wires.push(challenge.index);
+            next_input += degree + 2;
+            rounds.push(RoundWires {
+                degree,
+                evals,
+                challenge,
+            });
+        }
+
+        let one = C::CircuitField::ONE;
+
+        // layer 1: r, r^2, p(0) + p(1), and the coefficients of p
+        builder.next_layer();
+        let mut layer_1 = vec![];
+        for round in &rounds {
+            let r = round.challenge;
+            let sum = builder.add(round.evals[0], one);
+            builder.add_into(sum, round.evals[1], one);
+            let mut coefs = vec![];
+            for lagrange in lagrange_coefficients::<C::CircuitField>(round.degree) {
+                let coef = builder.new_wire();
+                for (&eval, c) in round.evals.iter().zip(lagrange) {
+                    builder.add_into(coef, eval, c);
+                }
+                coefs.push(coef);
+            }
+            layer_1.push((builder.relay(r), builder.mul(r, r, one), sum, coefs));
+        }
+
+        // layer 2: r, p(0) + p(1), and p(r) split as v + w * r
+        builder.next_layer();
+        let mut layer_2 = vec![];
+        for (r, r_2, sum, coefs) in &layer_1 {
+            let v = builder.add(coefs[0], one);
+            builder.mul_into(v, coefs[1], *r, one);
+            builder.mul_into(v, coefs[2], *r_2, one);
+            let w = coefs.get(3).map(|&c| builder.mul(c, *r_2, one));
+            layer_2.push((builder.relay(*r), builder.relay(*sum), v, w));
+        }
+
+        // layer 3: p_k(0) + p_k(1) - p_{k-1}(r_{k-1}) within each phase
+        builder.next_layer();
+        let mut first = 0;
+        for phase in &phases {
+            for k in first + 1..first + phase.len() {
+                let (r, _, v, w) = layer_2[k - 1];
+                let check = builder.add(layer_2[k].1, one);
+                builder.add_into(check, v, -one);
+                if let Some(w) = w {
+                    builder.mul_into(check, w, r, -one);
+                }
+            }
+            first += phase.len();
+        }
+
+        let circuit = builder.build();
+        input_map.input_size = 1 << circuit.layers[0].input_var_num;
+        (circuit, input_map)
+    }
+
+    /// The gadget input for a proof of the inner circuit, with the challenges replayed by the
+    /// inner verifier.
+    pub fn witness(
+        input_map: &InputMap,
+        inner_circuit: &mut Circuit<C>,
+        inner_config: &Config<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Vec<C::SimdCircuitField> {
+        let mut messages = SumcheckMessages::default();
+        Verifier::new(inner_config).verify_sumcheck_messages(
+            inner_circuit,
+            public_input,
+            claimed_v,
+            &proof.bytes,
+            &mut messages,
+        );
+        input_map.assign::<C>(&proof.bytes, &messages.challenges)
+    }
+}
+
+/// `coefs[m][i]` is the coefficient of `x^m` in the Lagrange polynomial of the node `i`,
+/// for the nodes 0, ..., degree.
+fn lagrange_coefficients<F: Field>(degree: usize) -> Vec<Vec<F>> {
+    let mut coefs = vec![vec![F::ZERO; degree + 1]; degree + 1];
+    for i in 0..=degree {
+        let mut poly = vec![F::ONE];
+        let mut denominator = F::ONE;
+        for j in (0..=degree).filter(|&j| j != i) {
+            // poly *= x - j
+            let node = F::from(j as u32);
+            let mut next = vec![F::ZERO; poly.len() + 1];
+            for (m, c) in poly.iter().enumerate() {
+                next[m + 1] += *c;
+                next[m] -= *c * node;
+            }
+            poly = next;
+            denominator *= F::from(i as u32) - node;
+        }
+        let denominator_inv = denominator.inv().unwrap();
+        for (m, c) in poly.into_iter().enumerate() {
+            coefs[m][i] = c * denominator_inv;
+        }
+    }
+    coefs
+}
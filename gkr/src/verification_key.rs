@@ -0,0 +1,116 @@
+//! What a verifier has to agree on with the prover, besides the proof itself.
+//!
+//! There is no preprocessing in GKR with the raw commitment: the key only pins down the circuit,
+//! through its hash, and the parameters of the proof system.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use circuit::Circuit;
+use config::{Config, FiatShamirHashType, GKRConfig, GKRScheme, PolynomialCommitmentType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationKey<C: GKRConfig> {
+    /// See `Circuit::hash`.
+    pub circuit_hash: [u8; 32],
+    pub gkr_scheme: GKRScheme,
+    pub polynomial_commitment_type: PolynomialCommitmentType,
+    /// Number of processes the proofs are generated by.
+    pub world_size: usize,
+    _marker: PhantomData<C>,
+}
+
+impl<C: GKRConfig> VerificationKey<C> {
+    pub fn new(circuit: &Circuit<C>, config: &Config<C>) -> Self {
+        Self {
+            circuit_hash: circuit.hash(),
+            gkr_scheme: config.gkr_scheme.clone(),
+            polynomial_commitment_type: config.polynomial_commitment_type.clone(),
+            world_size: config.mpi_config.world_size(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Whether proofs of the circuit generated with the config verify against this key.
+    pub fn matches(&self, circuit: &Circuit<C>, config: &Config<C>) -> bool {
+        *self == Self::new(circuit, config)
+    }
+}
+
+pub(crate) fn fiat_shamir_hash_tag(hash: &FiatShamirHashType) -> u8 {
+    match hash {
+        FiatShamirHashType::SHA256 => 0,
+        FiatShamirHashType::Keccak256 => 1,
+        FiatShamirHashType::Poseidon => 2,
+        FiatShamirHashType::Animoe => 3,
+        FiatShamirHashType::MIMC5 => 4,
+        FiatShamirHashType::Blake3 => 5,
+    }
+}
+
+pub(crate) fn polynomial_commitment_tag(pcs: &PolynomialCommitmentType) -> u8 {
+    match pcs {
+        PolynomialCommitmentType::Raw => 0,
+        PolynomialCommitmentType::KZG => 1,
+        PolynomialCommitmentType::Orion => 2,
+        PolynomialCommitmentType::FRI => 3,
+    }
+}
+
+impl<C: GKRConfig> FieldSerde for VerificationKey<C> {
+    /// circuit hash, fiat shamir hash, gkr scheme, polynomial commitment, world size
+    const SERIALIZED_SIZE: usize = 32 + 3 + 8;
+
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(&self.circuit_hash)?;
+        let gkr_scheme_tag = match self.gkr_scheme {
+            GKRScheme::Vanilla => 0u8,
+            GKRScheme::GkrSquare => 1,
+        };
+        writer.write_all(&[
+            fiat_shamir_hash_tag(&C::FIAT_SHAMIR_HASH),
+            gkr_scheme_tag,
+            polynomial_commitment_tag(&self.polynomial_commitment_type),
+        ])?;
+        self.world_size.serialize_into(&mut writer)
+    }
+
+    /// Fails on a key for another transcript hash than the one of `C`.
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut circuit_hash = [0u8; 32];
+        reader.read_exact(&mut circuit_hash)?;
+        let mut tags = [0u8; 3];
+        reader.read_exact(&mut tags)?;
+        if tags[0] != fiat_shamir_hash_tag(&C::FIAT_SHAMIR_HASH) {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        let gkr_scheme = match tags[1] {
+            0 => GKRScheme::Vanilla,
+            1 => GKRScheme::GkrSquare,
+            _ => return Err(FieldSerdeError::DeserializeError),
+        };
+        let polynomial_commitment_type = match tags[2] {
+            0 => PolynomialCommitmentType::Raw,
+            1 => PolynomialCommitmentType::KZG,
+            2 => PolynomialCommitmentType::Orion,
+            3 => PolynomialCommitmentType::FRI,
+            _ => return Err(FieldSerdeError::DeserializeError),
+        };
+        let world_size = usize::deserialize_from(&mut reader)?;
+
+        Ok(Self {
+            circuit_hash,
+            gkr_scheme,
+            polynomial_commitment_type,
+            world_size,
+            _marker: PhantomData,
+        })
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("not implemented for VerificationKey")
+    }
+}
@@ -1,22 +1,60 @@
 use std::{
+    borrow::Cow,
     io::{Cursor, Read},
+    sync::OnceLock,
     vec,
 };
 
 use arith::{Field, FieldSerde};
 use ark_std::{end_timer, start_timer};
-use circuit::{Circuit, CircuitLayer};
-use config::{Config, FiatShamirHashType, GKRConfig, PolynomialCommitmentType};
+use circuit::{Circuit, CircuitLayer, CircuitLayerStream, CoefType, VerifierKey};
+use config::{Config, FiatShamirHashType, GKRConfig, MPIConfig, PolynomialCommitmentType};
+use polynomials::MultiLinearPoly;
+use rayon::ThreadPool;
 use sumcheck::{GKRVerifierHelper, VerifierScratchPad};
 use transcript::{
-    BytesHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher, Proof, SHA256hasher,
-    Transcript,
+    BytesHashTranscript, DynHashTranscript, FieldHashTranscript, Keccak256hasher, MIMCHasher,
+    Proof, SHA256hasher, Transcript,
 };
 
 #[cfg(feature = "grinding")]
 use crate::grind;
-use crate::RawCommitment;
+use crate::{
+    absorb_claimed_values, absorb_public_input, absorb_seed_and_domain,
+    chunked_prover::{draw_rnd_coefs, set_rnd_coefs},
+    OrionParams, ProofLayout, RawCommitment,
+};
+
+mod batch;
+
+mod error;
+pub use error::*;
+
+mod report;
+pub use report::*;
+
+/// How the sumcheck round messages are read from the proof.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SumcheckMessages<F> {
+    /// Whether the evaluations at 1 of the round polynomials are left out of the proof,
+    /// see `ProofPruner`.
+    pub pruned: bool,
+    /// The evaluations at 1 of the round polynomials, in proof order.
+    pub evals_at_one: Vec<F>,
+    /// The challenges of the sumcheck rounds, in proof order.
+    pub challenges: Vec<F>,
+    /// The point the output claim is on: `rz0`, then `r_simd`, then `r_mpi`.
+    pub output_challenges: Vec<F>,
+}
+
+/// The next field element of the proof, or why the proof is malformed.
+#[inline(always)]
+fn read_element<F: FieldSerde>(proof_reader: impl Read) -> Result<F, VerificationError> {
+    F::deserialize_from(proof_reader).map_err(|e| VerificationError::MalformedProof(e.to_string()))
+}
 
+/// Whether the round message of the proof sums to the claim, or an error if the proof ends
+/// before it or has an invalid field element.
 #[inline(always)]
 fn verify_sumcheck_step<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     mut proof_reader: impl Read,
@@ -25,15 +63,23 @@ fn verify_sumcheck_step<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     claimed_sum: &mut C::ChallengeField,
     randomness_vec: &mut Vec<C::ChallengeField>,
     sp: &VerifierScratchPad<C>,
-) -> bool {
+    messages: &mut SumcheckMessages<C::ChallengeField>,
+) -> Result<bool, VerificationError> {
     let mut ps = vec![];
     for i in 0..(degree + 1) {
-        ps.push(C::ChallengeField::deserialize_from(&mut proof_reader).unwrap());
+        if messages.pruned && i == 1 {
+            // the only value passing the check below
+            ps.push(*claimed_sum - ps[0]);
+        } else {
+            ps.push(read_element(&mut proof_reader)?);
+        }
         transcript.append_field_element(&ps[i]);
     }
+    messages.evals_at_one.push(ps[1]);
 
     let r = transcript.generate_challenge_field_element();
     randomness_vec.push(r);
+    messages.challenges.push(r);
 
     let verified = (ps[0] + ps[1]) == *claimed_sum;
 
@@ -43,13 +89,35 @@ fn verify_sumcheck_step<C: GKRConfig, T: Transcript<C::ChallengeField>>(
         *claimed_sum = GKRVerifierHelper::degree_3_eval(&ps, r, sp);
     }
 
-    verified
+    Ok(verified)
+}
+
+/// Record the sumcheck round as the failure of the layer if it fails first, and move on to the
+/// next round. The first round of the output layer checks the claimed output.
+#[inline(always)]
+fn check_round(
+    verified: bool,
+    layer_index: usize,
+    is_output_layer: bool,
+    round: &mut usize,
+    failure: &mut Option<VerificationError>,
+) {
+    if !verified && failure.is_none() {
+        *failure = Some(if is_output_layer && *round == 0 {
+            VerificationError::ClaimedOutput { layer: layer_index }
+        } else {
+            VerificationError::SumcheckRound {
+                layer: layer_index,
+                round: *round,
+            }
+        });
+    }
+    *round += 1;
 }
 
 // todo: FIXME
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
-#[allow(clippy::unnecessary_unwrap)]
 fn sumcheck_verify_gkr_layer<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     config: &Config<C>,
     layer: &CircuitLayer<C>,
@@ -64,16 +132,21 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     mut proof_reader: impl Read,
     transcript: &mut T,
     sp: &mut VerifierScratchPad<C>,
+    layer_index: usize,
     is_output_layer: bool,
-) -> (
-    bool,
-    Vec<C::ChallengeField>,
-    Option<Vec<C::ChallengeField>>,
-    Vec<C::ChallengeField>,
-    Vec<C::ChallengeField>,
-    C::ChallengeField,
-    Option<C::ChallengeField>,
-) {
+    messages: &mut SumcheckMessages<C::ChallengeField>,
+) -> Result<
+    (
+        Option<VerificationError>,
+        Vec<C::ChallengeField>,
+        Option<Vec<C::ChallengeField>>,
+        Vec<C::ChallengeField>,
+        Vec<C::ChallengeField>,
+        C::ChallengeField,
+        Option<C::ChallengeField>,
+    ),
+    VerificationError,
+> {
     assert_eq!(rz1.is_none(), claimed_v1.is_none());
     assert_eq!(rz1.is_none(), alpha.is_none());
 
@@ -82,8 +155,8 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     let var_num = layer.input_var_num;
     let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
     let mut sum = claimed_v0;
-    if claimed_v1.is_some() && alpha.is_some() {
-        sum += claimed_v1.unwrap() * alpha.unwrap();
+    if let (Some(claimed_v1), Some(alpha)) = (claimed_v1, alpha) {
+        sum += claimed_v1 * alpha;
     }
 
     sum -= GKRVerifierHelper::eval_cst(&layer.const_, public_input, sp);
@@ -92,42 +165,73 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     let mut ry = None;
     let mut r_simd_xy = vec![];
     let mut r_mpi_xy = vec![];
-    let mut verified = true;
+    let mut failure = None;
+    let mut round = 0;
 
     for _i_var in 0..var_num {
-        verified &=
-            verify_sumcheck_step::<C, T>(&mut proof_reader, 2, transcript, &mut sum, &mut rx, sp);
+        let verified = verify_sumcheck_step::<C, T>(
+            &mut proof_reader,
+            2,
+            transcript,
+            &mut sum,
+            &mut rx,
+            sp,
+            messages,
+        )?;
+        check_round(
+            verified,
+            layer_index,
+            is_output_layer,
+            &mut round,
+            &mut failure,
+        );
         // println!("x {} var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_rx(&rx, sp);
 
     for _i_var in 0..simd_var_num {
-        verified &= verify_sumcheck_step::<C, T>(
+        let verified = verify_sumcheck_step::<C, T>(
             &mut proof_reader,
             3,
             transcript,
             &mut sum,
             &mut r_simd_xy,
             sp,
+            messages,
+        )?;
+        check_round(
+            verified,
+            layer_index,
+            is_output_layer,
+            &mut round,
+            &mut failure,
         );
         // println!("{} simd var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
 
     for _i_var in 0..config.mpi_config.world_size().trailing_zeros() {
-        verified &= verify_sumcheck_step::<C, T>(
+        let verified = verify_sumcheck_step::<C, T>(
             &mut proof_reader,
             3,
             transcript,
             &mut sum,
             &mut r_mpi_xy,
             sp,
+            messages,
+        )?;
+        check_round(
+            verified,
+            layer_index,
+            is_output_layer,
+            &mut round,
+            &mut failure,
         );
         // println!("{} mpi var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_mpi_xy(&r_mpi_xy, sp);
 
-    let vx_claim = C::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+    let vx_claim = read_element::<C::ChallengeField>(&mut proof_reader)?;
 
     sum -= vx_claim * GKRVerifierHelper::eval_add(&layer.add, sp);
     transcript.append_field_element(&vx_claim);
@@ -135,58 +239,157 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     let vy_claim = if !layer.structure_info.max_degree_one {
         ry = Some(vec![]);
         for _i_var in 0..var_num {
-            verified &= verify_sumcheck_step::<C, T>(
+            let verified = verify_sumcheck_step::<C, T>(
                 &mut proof_reader,
                 2,
                 transcript,
                 &mut sum,
                 ry.as_mut().unwrap(),
                 sp,
+                messages,
+            )?;
+            check_round(
+                verified,
+                layer_index,
+                is_output_layer,
+                &mut round,
+                &mut failure,
             );
             // println!("y {} var, verified? {}", _i_var, verified);
         }
         GKRVerifierHelper::set_ry(ry.as_ref().unwrap(), sp);
 
-        let vy_claim = C::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+        let vy_claim = read_element::<C::ChallengeField>(&mut proof_reader)?;
         transcript.append_field_element(&vy_claim);
-        verified &= sum == vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, sp);
+        if sum != vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, sp) {
+            failure.get_or_insert(VerificationError::FinalEvaluation { layer: layer_index });
+        }
         Some(vy_claim)
     } else {
-        verified &= sum == C::ChallengeField::ZERO;
+        if sum != C::ChallengeField::ZERO {
+            failure.get_or_insert(VerificationError::FinalEvaluation { layer: layer_index });
+        }
         None
     };
 
-    (verified, rx, ry, r_simd_xy, r_mpi_xy, vx_claim, vy_claim)
+    Ok((failure, rx, ry, r_simd_xy, r_mpi_xy, vx_claim, vy_claim))
 }
 
+/// Whether the sumchecks of the layers pass, with the claims on the input layer, or an error if
+/// the proof cannot be parsed.
 // todo: FIXME
 #[allow(clippy::type_complexity)]
 pub fn gkr_verify<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     config: &Config<C>,
     circuit: &Circuit<C>,
     public_input: &[C::SimdCircuitField],
-    claimed_v: &C::ChallengeField,
+    claimed_v: &[C::ChallengeField],
+    transcript: &mut T,
+    proof_reader: impl Read,
+) -> Result<
+    (
+        bool,
+        Vec<C::ChallengeField>,
+        Option<Vec<C::ChallengeField>>,
+        Vec<C::ChallengeField>,
+        Vec<C::ChallengeField>,
+        C::ChallengeField,
+        Option<C::ChallengeField>,
+    ),
+    VerificationError,
+> {
+    let (first_failure, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) = gkr_verify_layers(
+        config,
+        circuit,
+        public_input,
+        claimed_v,
+        transcript,
+        proof_reader,
+        &mut SumcheckMessages::default(),
+    )?;
+    Ok((
+        first_failure.is_none(),
+        rz0,
+        rz1,
+        r_simd,
+        r_mpi,
+        claimed_v0,
+        claimed_v1,
+    ))
+}
+
+/// Same as `gkr_verify`, but returns the first failure, in verification order, of the
+/// sumchecks of the layers instead of a bool.
+// todo: FIXME
+#[allow(clippy::type_complexity)]
+pub(crate) fn gkr_verify_layers<C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    config: &Config<C>,
+    circuit: &Circuit<C>,
+    public_input: &[C::SimdCircuitField],
+    claimed_v: &[C::ChallengeField],
+    transcript: &mut T,
+    proof_reader: impl Read,
+    messages: &mut SumcheckMessages<C::ChallengeField>,
+) -> Result<
+    (
+        Option<VerificationError>,
+        Vec<C::ChallengeField>,
+        Option<Vec<C::ChallengeField>>,
+        Vec<C::ChallengeField>,
+        Vec<C::ChallengeField>,
+        C::ChallengeField,
+        Option<C::ChallengeField>,
+    ),
+    VerificationError,
+> {
+    let mut sp = VerifierScratchPad::<C>::new(config, circuit);
+    gkr_verify_layer_by_layer(
+        config,
+        circuit.layers.len(),
+        |i| Cow::Borrowed(&circuit.layers[i]),
+        public_input,
+        claimed_v,
+        transcript,
+        proof_reader,
+        &mut sp,
+        messages,
+    )
+}
+
+/// As `gkr_verify_layers`, on the layers given one at a time from the output layer down.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn gkr_verify_layer_by_layer<'a, C: GKRConfig, T: Transcript<C::ChallengeField>>(
+    config: &Config<C>,
+    layer_num: usize,
+    mut layer: impl FnMut(usize) -> Cow<'a, CircuitLayer<C>>,
+    public_input: &[C::SimdCircuitField],
+    claimed_v: &[C::ChallengeField],
     transcript: &mut T,
     mut proof_reader: impl Read,
-) -> (
-    bool,
-    Vec<C::ChallengeField>,
-    Option<Vec<C::ChallengeField>>,
-    Vec<C::ChallengeField>,
-    Vec<C::ChallengeField>,
-    C::ChallengeField,
-    Option<C::ChallengeField>,
-) {
+    sp: &mut VerifierScratchPad<C>,
+    messages: &mut SumcheckMessages<C::ChallengeField>,
+) -> Result<
+    (
+        Option<VerificationError>,
+        Vec<C::ChallengeField>,
+        Option<Vec<C::ChallengeField>>,
+        Vec<C::ChallengeField>,
+        Vec<C::ChallengeField>,
+        C::ChallengeField,
+        Option<C::ChallengeField>,
+    ),
+    VerificationError,
+> {
     let timer = start_timer!(|| "gkr verify");
-    let mut sp = VerifierScratchPad::<C>::new(config, circuit);
 
-    let layer_num = circuit.layers.len();
+    let mut output_layer = Some(layer(layer_num - 1));
     let mut rz0 = vec![];
     let mut rz1 = None;
     let mut r_simd = vec![];
     let mut r_mpi = vec![];
 
-    for _ in 0..circuit.layers.last().unwrap().output_var_num {
+    for _ in 0..output_layer.as_ref().unwrap().output_var_num {
         rz0.push(transcript.generate_challenge_field_element());
     }
 
@@ -197,25 +400,32 @@ pub fn gkr_verify<C: GKRConfig, T: Transcript<C::ChallengeField>>(
     for _ in 0..config.mpi_config.world_size().trailing_zeros() {
         r_mpi.push(transcript.generate_challenge_field_element());
     }
+    messages.output_challenges = rz0.iter().chain(&r_simd).chain(&r_mpi).copied().collect();
 
-    let mut alpha = None;
-    let mut claimed_v0 = *claimed_v;
+    // the claim on the output layer is the evaluation of the claimed values at the challenges
+    let mut first_failure = None;
+    let num_outputs = 1 << messages.output_challenges.len();
+    let mut claimed_v0 = if claimed_v.len() == num_outputs {
+        MultiLinearPoly::evaluate_with_buffer(
+            claimed_v,
+            &messages.output_challenges,
+            &mut vec![C::ChallengeField::ZERO; num_outputs],
+        )
+    } else {
+        first_failure = Some(VerificationError::ClaimedValuesCount {
+            expected: num_outputs,
+            found: claimed_v.len(),
+        });
+        C::ChallengeField::ZERO
+    };
     let mut claimed_v1 = None;
+    let mut alpha = None;
 
-    let mut verified = true;
     for i in (0..layer_num).rev() {
-        let cur_verified;
-        (
-            cur_verified,
-            rz0,
-            rz1,
-            r_simd,
-            r_mpi,
-            claimed_v0,
-            claimed_v1,
-        ) = sumcheck_verify_gkr_layer(
+        let cur_failure;
+        (cur_failure, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) = sumcheck_verify_gkr_layer(
             config,
-            &circuit.layers[i],
+            &output_layer.take().unwrap_or_else(|| layer(i)),
             public_input,
             &rz0,
             &rz1,
@@ -226,10 +436,14 @@ pub fn gkr_verify<C: GKRConfig, T: Transcript<C::ChallengeField>>(
             alpha,
             &mut proof_reader,
             transcript,
-            &mut sp,
+            sp,
+            i,
             i == layer_num - 1,
-        );
-        verified &= cur_verified;
+            messages,
+        )?;
+        if first_failure.is_none() {
+            first_failure = cur_failure;
+        }
         alpha = if rz1.is_some() {
             Some(transcript.generate_challenge_field_element())
         } else {
@@ -237,18 +451,116 @@ pub fn gkr_verify<C: GKRConfig, T: Transcript<C::ChallengeField>>(
         };
     }
     end_timer!(timer);
-    (verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1)
+    Ok((
+        first_failure,
+        rz0,
+        rz1,
+        r_simd,
+        r_mpi,
+        claimed_v0,
+        claimed_v1,
+    ))
+}
+
+/// Result of the individual checks of the verifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VerificationChecks {
+    /// The first failure, in verification order, of the sumchecks of the layers.
+    pub sumcheck_failure: Option<VerificationError>,
+    /// The first claim on the input layer, 0 for vx and 1 for vy, that the commitment does
+    /// not open to.
+    pub failed_opening: Option<usize>,
+}
+
+impl VerificationChecks {
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.sumcheck_failure.is_none() && self.failed_opening.is_none()
+    }
+
+    /// The checks of a proof refused before its sumchecks.
+    fn refused(error: VerificationError) -> Self {
+        Self {
+            sumcheck_failure: Some(error),
+            failed_opening: None,
+        }
+    }
+
+    /// The failure of the sumchecks, or else of the opening of the commitment.
+    pub fn into_result(self) -> Result<(), VerificationError> {
+        match (self.sumcheck_failure, self.failed_opening) {
+            (Some(failure), _) => Err(failure),
+            (None, Some(claim)) => Err(VerificationError::CommitmentOpening { claim }),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The commitment read from the proof, of the polynomial commitment of the config.
+enum InputCommitment<C: GKRConfig> {
+    Raw(RawCommitment<C>),
+    /// The Merkle root of the committed columns.
+    Orion(OrionParams, [u8; 32]),
+}
+
+/// What the proof is verified against.
+enum VerifierCircuit<'a, C: GKRConfig> {
+    Circuit(&'a mut Circuit<C>),
+    Key(&'a VerifierKey<C>),
+    /// Flattened a layer at a time, with the shapes of its layers of a first pass.
+    Stream(&'a CircuitLayerStream<C>, LayerShapes),
+}
+
+/// What the verifier needs of the layers of a stream before the first one it verifies.
+struct LayerShapes {
+    log_input_size: usize,
+    rnd_coef_nums: Vec<usize>,
+    max_num_var: usize,
+    /// The number of input variables and whether the layer is of degree one, of each layer.
+    layers: Vec<(usize, bool)>,
+    num_public_inputs: usize,
+}
+
+impl LayerShapes {
+    fn of<C: GKRConfig>(layers: &CircuitLayerStream<C>) -> Self {
+        let mut shapes = LayerShapes {
+            log_input_size: 0,
+            rnd_coef_nums: vec![],
+            max_num_var: 0,
+            layers: vec![],
+            num_public_inputs: 0,
+        };
+        for (i, mut layer) in layers.clone().enumerate() {
+            if i == 0 {
+                shapes.log_input_size = layer.input_var_num;
+            }
+            let num_var = layer.input_var_num.max(layer.output_var_num);
+            shapes.max_num_var = shapes.max_num_var.max(num_var);
+            let mut rnd_coefs = vec![];
+            layer.identify_rnd_coefs(&mut rnd_coefs);
+            shapes.rnd_coef_nums.push(rnd_coefs.len());
+            layer.identify_structure_info();
+            shapes
+                .layers
+                .push((layer.input_var_num, layer.structure_info.max_degree_one));
+            shapes.num_public_inputs = shapes
+                .num_public_inputs
+                .max(num_public_inputs_read(std::slice::from_ref(&layer)));
+        }
+        shapes
+    }
 }
 
 pub struct Verifier<C: GKRConfig> {
     config: Config<C>,
+    /// The threads of `config.num_threads` over which the commitment of several processes is
+    /// opened, built by the first proof of several processes, and none for one thread.
+    pool: OnceLock<Option<ThreadPool>>,
 }
 
 impl<C: GKRConfig> Default for Verifier<C> {
     fn default() -> Self {
-        Self {
-            config: Config::<C>::default(),
-        }
+        Self::new(&Config::<C>::default())
     }
 }
 
@@ -256,27 +568,137 @@ impl<C: GKRConfig> Verifier<C> {
     pub fn new(config: &Config<C>) -> Self {
         Verifier {
             config: config.clone(),
+            pool: OnceLock::new(),
+        }
+    }
+
+    /// The threads of the openings of the commitment, for several processes only: that of a
+    /// single one is evaluated on the calling thread.
+    fn pool(&self) -> Option<&ThreadPool> {
+        if self.config.num_threads <= 1 || self.config.mpi_config.world_size() == 1 {
+            return None;
+        }
+        self.pool
+            .get_or_init(|| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(self.config.num_threads)
+                    .build()
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    /// Verify the next proofs in the domain, in place of the domain of the config, see
+    /// `Config::with_domain`.
+    pub fn set_domain(&mut self, domain: &[u8]) {
+        self.config.domain.clear();
+        self.config.domain.extend_from_slice(domain);
+    }
+
+    /// Verify the next proofs of the seed, in place of the seed of the config, e.g., the one
+    /// their package records, see `ProofPackage::prover_seed`.
+    pub fn set_prover_seed(&mut self, seed: Option<[u8; 32]>) {
+        self.config.prover_seed = seed;
+    }
+
+    /// Absorb the claimed values as the prover does once the circuit is evaluated, with the
+    /// hash of its sync up on several processes.
+    fn absorb_claims<T: Transcript<C::ChallengeField>>(
+        &self,
+        transcript: &mut T,
+        claimed_v: &[C::ChallengeField],
+    ) {
+        absorb_claimed_values::<C, T>(transcript, claimed_v);
+        if self.config.mpi_config.world_size() > 1 {
+            let _ = transcript.hash_and_return_state();
         }
     }
 
     fn verify_internal<T: Transcript<C::ChallengeField>>(
         &self,
-        circuit: &mut Circuit<C>,
+        circuit: VerifierCircuit<'_, C>,
         public_input: &[C::SimdCircuitField],
-        claimed_v: &C::ChallengeField,
-        proof: &Proof,
+        claimed_v: &[C::ChallengeField],
+        proof_bytes: &[u8],
         transcript: &mut T,
-    ) -> bool {
+        messages: &mut SumcheckMessages<C::ChallengeField>,
+    ) -> VerificationChecks {
         let timer = start_timer!(|| "verify");
 
-        let poly_size =
-            circuit.layers.first().unwrap().input_vals.len() * self.config.mpi_config.world_size();
-        let mut cursor = Cursor::new(&proof.bytes);
+        absorb_seed_and_domain::<C, T>(transcript, &self.config);
 
-        let commitment = RawCommitment::<C>::deserialize_from(&mut cursor, poly_size);
-        transcript.append_u8_slice(&proof.bytes[..commitment.size()]);
+        let (num_layers, log_input_size, num_public_inputs) = match &circuit {
+            VerifierCircuit::Circuit(circuit) => (
+                circuit.layers.len(),
+                circuit
+                    .layers
+                    .first()
+                    .map_or(0, |layer| layer.input_var_num),
+                num_public_inputs_read(&circuit.layers),
+            ),
+            VerifierCircuit::Key(vk) => {
+                (vk.num_layers(), vk.log_input_size(), vk.num_public_inputs())
+            }
+            VerifierCircuit::Stream(layers, shapes) => (
+                layers.num_layers(),
+                shapes.log_input_size,
+                shapes.num_public_inputs,
+            ),
+        };
+        if num_layers == 0 {
+            return VerificationChecks::refused(VerificationError::NoLayer);
+        }
+        // the gates read the public input of each process at their index
+        let world_size = self.config.mpi_config.world_size();
+        if public_input.len() % world_size != 0
+            || public_input.len() / world_size < num_public_inputs
+        {
+            return VerificationChecks::refused(VerificationError::PublicInputCount {
+                expected: num_public_inputs * world_size,
+                found: public_input.len(),
+            });
+        }
+        let poly_size = (1 << log_input_size) * world_size;
+        let mut cursor = Cursor::new(proof_bytes);
+        let malformed = |message: &str| {
+            VerificationChecks::refused(VerificationError::MalformedProof(message.to_string()))
+        };
 
-        if self.config.mpi_config.world_size() > 1 {
+        let commitment = match self.config.polynomial_commitment_type {
+            PolynomialCommitmentType::Raw => {
+                let size = poly_size * C::SimdCircuitField::SERIALIZED_SIZE;
+                let Some(commitment_bytes) = proof_bytes.get(..size) else {
+                    return malformed("the proof is shorter than the commitment");
+                };
+                let commitment = match RawCommitment::<C>::deserialize_from(&mut cursor, poly_size)
+                {
+                    Ok(commitment) => commitment,
+                    Err(e) => return malformed(&e.to_string()),
+                };
+                transcript.append_u8_slice(commitment_bytes);
+                InputCommitment::Raw(commitment)
+            }
+            PolynomialCommitmentType::Orion => {
+                let params = OrionParams::new::<C>(
+                    poly_size.trailing_zeros() as usize,
+                    self.config.security_bits,
+                );
+                let mut root = [0u8; 32];
+                if cursor.read_exact(&mut root).is_err() {
+                    return malformed("the proof is shorter than the commitment");
+                }
+                transcript.append_u8_slice(&root);
+                InputCommitment::Orion(params, root)
+            }
+            ref pcs => {
+                return VerificationChecks::refused(
+                    VerificationError::PolynomialCommitmentUnavailable(pcs.clone()),
+                )
+            }
+        };
+        absorb_public_input::<C, T>(transcript, public_input, world_size);
+
+        if world_size > 1 {
             let _ = transcript.hash_and_return_state(); // Trigger an additional hash
         }
 
@@ -285,69 +707,508 @@ impl<C: GKRConfig> Verifier<C> {
         #[cfg(feature = "grinding")]
         grind::<C, T>(transcript, &self.config);
 
-        circuit.fill_rnd_coefs(transcript);
-
-        let (mut verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) = gkr_verify(
-            &self.config,
-            circuit,
-            public_input,
-            claimed_v,
-            transcript,
-            &mut cursor,
-        );
+        let layers = match circuit {
+            VerifierCircuit::Circuit(circuit) => {
+                circuit.fill_rnd_coefs(transcript);
+                self.absorb_claims(transcript, claimed_v);
+                gkr_verify_layers(
+                    &self.config,
+                    circuit,
+                    public_input,
+                    claimed_v,
+                    transcript,
+                    &mut cursor,
+                    messages,
+                )
+            }
+            VerifierCircuit::Key(vk) => {
+                let rnd_coefs = draw_rnd_coefs::<C, T>(vk.rnd_coef_nums(), transcript);
+                self.absorb_claims(transcript, claimed_v);
+                let mut sp =
+                    VerifierScratchPad::<C>::with_max_num_var(&self.config, vk.max_num_var());
+                gkr_verify_layer_by_layer(
+                    &self.config,
+                    vk.num_layers(),
+                    |i| {
+                        let mut layer = vk.layer(i);
+                        set_rnd_coefs(&mut layer, &rnd_coefs[i]);
+                        Cow::Owned(layer)
+                    },
+                    public_input,
+                    claimed_v,
+                    transcript,
+                    &mut cursor,
+                    &mut sp,
+                    messages,
+                )
+            }
+            VerifierCircuit::Stream(layers, shapes) => {
+                let rnd_coefs = draw_rnd_coefs::<C, T>(&shapes.rnd_coef_nums, transcript);
+                self.absorb_claims(transcript, claimed_v);
+                let mut sp =
+                    VerifierScratchPad::<C>::with_max_num_var(&self.config, shapes.max_num_var);
+                gkr_verify_layer_by_layer(
+                    &self.config,
+                    layers.num_layers(),
+                    |i| {
+                        let mut layer = layers.layers(i..i + 1).pop().unwrap();
+                        set_rnd_coefs(&mut layer, &rnd_coefs[i]);
+                        layer.identify_structure_info();
+                        Cow::Owned(layer)
+                    },
+                    public_input,
+                    claimed_v,
+                    transcript,
+                    &mut cursor,
+                    &mut sp,
+                    messages,
+                )
+            }
+        };
+        let (sumcheck_failure, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) = match layers {
+            Ok(layers) => layers,
+            Err(e) => return VerificationChecks::refused(e),
+        };
 
-        log::info!("GKR verification: {}", verified);
+        log::info!("GKR verification: {}", sumcheck_failure.is_none());
 
-        match self.config.polynomial_commitment_type {
-            PolynomialCommitmentType::Raw => {
+        let mut failed_opening = None;
+        match commitment {
+            InputCommitment::Raw(commitment) => {
                 // for Raw, no need to load from proof
                 log::trace!("rz0.size() = {}", rz0.len());
                 log::trace!("Poly_vals.size() = {}", commitment.poly_vals.len());
 
-                let v1 = commitment.mpi_verify(&rz0, &r_simd, &r_mpi, claimed_v0);
-                verified &= v1;
-
-                if rz1.is_some() {
-                    let v2 = commitment.mpi_verify(
-                        rz1.as_ref().unwrap(),
+                let pool = self.pool();
+                let open = |rz: &[C::ChallengeField], claimed: C::ChallengeField| {
+                    commitment.mpi_verify(rz, &r_simd, &r_mpi, claimed, pool)
+                };
+                // the openings at rz0 and rz1 are independent, and so checked side by side
+                // the layer of the claim vy returns both rz1 and claimed_v1
+                let claim_1 = rz1.as_deref().zip(claimed_v1);
+                let (v1, v2) = match (pool, claim_1) {
+                    (Some(pool), Some((rz1, claimed_v1))) => {
+                        let (v1, v2) =
+                            pool.join(|| open(&rz0, claimed_v0), || open(rz1, claimed_v1));
+                        (v1, Some(v2))
+                    }
+                    _ => (
+                        open(&rz0, claimed_v0),
+                        claim_1.map(|(rz1, claimed_v1)| open(rz1, claimed_v1)),
+                    ),
+                };
+                if !v1 {
+                    failed_opening = Some(0);
+                }
+                if v2 == Some(false) {
+                    failed_opening.get_or_insert(1);
+                }
+            }
+            InputCommitment::Orion(params, root) => {
+                // in proof order, the openings sharing the transcript
+                let mut open = |rz: &[C::ChallengeField], claimed: C::ChallengeField| {
+                    params.verify::<C, T>(
+                        &root,
+                        &[rz, &r_mpi[..]].concat(),
                         &r_simd,
-                        &r_mpi,
-                        claimed_v1.unwrap(),
-                    );
-                    verified &= v2;
+                        claimed,
+                        transcript,
+                        &mut cursor,
+                    )
+                };
+                if !open(&rz0, claimed_v0) {
+                    failed_opening = Some(0);
+                } else if let Some((rz1, claimed_v1)) = rz1.as_deref().zip(claimed_v1) {
+                    if !open(rz1, claimed_v1) {
+                        failed_opening = Some(1);
+                    }
                 }
             }
-            _ => todo!(),
         }
 
         end_timer!(timer);
 
-        verified
+        VerificationChecks {
+            sumcheck_failure,
+            failed_opening,
+        }
     }
 
+    /// Verify the proof of the claimed values, the outputs of the circuit laid out as by
+    /// `gather_claimed_values`.
+    #[cfg_attr(feature = "telemetry", tracing::instrument(skip_all))]
     pub fn verify(
         &self,
         circuit: &mut Circuit<C>,
         public_input: &[C::SimdCircuitField],
-        claimed_v: &C::ChallengeField,
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> bool {
+        self.try_verify(circuit, public_input, claimed_v, proof)
+            .is_ok()
+    }
+
+    /// As `verify`, with why the proof is rejected: the first check that fails, in
+    /// verification order. A proof of the wrong length, or that cannot be parsed, is rejected
+    /// as such rather than panicking.
+    pub fn try_verify(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let layout = ProofLayout::new(circuit, &self.config);
+        self.try_verify_against(
+            VerifierCircuit::Circuit(circuit),
+            &layout,
+            public_input,
+            claimed_v,
+            proof,
+        )
+    }
+
+    /// Verify the proof of a prover run on `world_size` processes, on as many processes as
+    /// the config has, each with its shard of the witness loaded in the circuit. The public
+    /// inputs and the claimed values of the ranks are gathered to the root, which checks that
+    /// the claimed values agree and verifies the proof; every rank returns the result of the
+    /// root. With a single process, the same as `verify` on the public input of the circuit.
+    pub fn mpi_verify(
+        &self,
+        circuit: &mut Circuit<C>,
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> bool {
+        if self.config.mpi_config.world_size() == 1 {
+            let public_input = circuit.public_input.clone();
+            return self.verify(circuit, &public_input, claimed_v, proof);
+        }
+        self.try_mpi_verify(circuit, claimed_v, proof).is_ok()
+    }
+
+    /// As `mpi_verify`, with why the proof is rejected, as `try_verify`. Only the root knows
+    /// why: the other processes are told whether the proof passed, and rejected with
+    /// `RejectedByRoot` if not.
+    pub fn try_mpi_verify(
+        &self,
+        circuit: &mut Circuit<C>,
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let mpi_config = &self.config.mpi_config;
+        if mpi_config.world_size() == 1 {
+            let public_input = circuit.public_input.clone();
+            return self.try_verify(circuit, &public_input, claimed_v, proof);
+        }
+
+        let public_input = gather_public_input::<C>(mpi_config, &circuit.public_input);
+        // the ranks may have as many claimed values as they like, so they gather their digests
+        let digest = claimed_values_digest(claimed_v);
+        let mut digests = if mpi_config.is_root() {
+            vec![C::ChallengeField::ZERO; mpi_config.world_size()]
+        } else {
+            vec![]
+        };
+        mpi_config.gather_vec(&vec![digest], &mut digests);
+
+        let mut result = Err(VerificationError::RejectedByRoot);
+        if mpi_config.is_root() {
+            result = match digests.iter().position(|v| *v != digest) {
+                Some(rank) => Err(VerificationError::ClaimedOutputMismatch { rank }),
+                None => self.try_verify(circuit, &public_input, claimed_v, proof),
+            };
+        }
+        // the result as a field element, to broadcast it
+        let mut verified = if result.is_ok() {
+            C::ChallengeField::ONE
+        } else {
+            C::ChallengeField::ZERO
+        };
+        mpi_config.root_broadcast(&mut verified);
+        if verified == C::ChallengeField::ONE {
+            Ok(())
+        } else {
+            result
+        }
+    }
+
+    /// Verify the proof of a prover run on as many processes as the config has, here on a
+    /// single one, given the public inputs of all of them as the verifier has them, in the order
+    /// of the ranks, see `executor::load_rank_public_inputs`. The circuit has the witness of the
+    /// root loaded, whose public input must be the first of them. The config is usually of
+    /// `MPIConfig::new_for_verifier` with the world size of the prover.
+    pub fn try_verify_gathered(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let local_len = circuit.public_input.len();
+        if public_input.len() != local_len * self.config.mpi_config.world_size()
+            || public_input[..local_len] != circuit.public_input[..]
+        {
+            return Err(VerificationError::PublicInputMismatch);
+        }
+        self.try_verify(circuit, public_input, claimed_v, proof)
+    }
+
+    pub(crate) fn verify_checks(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> VerificationChecks {
+        self.verify_sumcheck_messages(
+            circuit,
+            public_input,
+            claimed_v,
+            &proof.bytes,
+            &mut SumcheckMessages::default(),
+        )
+    }
+
+    /// Run the verification with the given transcript, which may have absorbed data beforehand,
+    /// see `Prover::prove_with_transcript`.
+    pub(crate) fn verify_with_transcript<T: Transcript<C::ChallengeField>>(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+        transcript: &mut T,
+        messages: &mut SumcheckMessages<C::ChallengeField>,
+    ) -> VerificationChecks {
+        self.verify_internal(
+            VerifierCircuit::Circuit(circuit),
+            public_input,
+            claimed_v,
+            &proof.bytes,
+            transcript,
+            messages,
+        )
+    }
+
+    /// Run the verification on proof bytes whose sumcheck messages are laid out as specified.
+    pub(crate) fn verify_sumcheck_messages(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof_bytes: &[u8],
+        messages: &mut SumcheckMessages<C::ChallengeField>,
+    ) -> VerificationChecks {
+        self.verify_against(
+            VerifierCircuit::Circuit(circuit),
+            public_input,
+            claimed_v,
+            proof_bytes,
+            messages,
+        )
+    }
+
+    /// Verify against the verifier key of the circuit rather than the circuit, see
+    /// `Circuit::extract_verifier_key`. The layers of the key are unpacked one at a time, and
+    /// the public input is the public input of the circuit, gathered from all the processes.
+    pub fn verify_with_key(
+        &self,
+        vk: &VerifierKey<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
         proof: &Proof,
     ) -> bool {
+        self.try_verify_with_key(vk, public_input, claimed_v, proof)
+            .is_ok()
+    }
+
+    /// As `verify_with_key`, with why the proof is rejected, as `try_verify`. The public input
+    /// must be exactly the ones the gates read, of each process.
+    pub fn try_verify_with_key(
+        &self,
+        vk: &VerifierKey<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let expected = vk.num_public_inputs() * self.config.mpi_config.world_size();
+        if public_input.len() != expected {
+            return Err(VerificationError::PublicInputCount {
+                expected,
+                found: public_input.len(),
+            });
+        }
+        self.try_verify_against(
+            VerifierCircuit::Key(vk),
+            &ProofLayout::for_key(vk, &self.config),
+            public_input,
+            claimed_v,
+            proof,
+        )
+    }
+
+    /// Verify against the layers of the stream rather than the circuit, e.g., of a
+    /// `MappedCircuit`, flattened one at a time after a first pass for their sizes, as
+    /// `Prover::prove_streaming` proves them. The public input is that of `verify`.
+    pub fn verify_streaming(
+        &self,
+        layers: &CircuitLayerStream<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> bool {
+        self.try_verify_streaming(layers, public_input, claimed_v, proof)
+            .is_ok()
+    }
+
+    /// As `verify_streaming`, with why the proof is rejected, as `try_verify`.
+    pub fn try_verify_streaming(
+        &self,
+        layers: &CircuitLayerStream<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        let shapes = LayerShapes::of(layers);
+        let layout = ProofLayout::of_layers(&shapes.layers, &self.config);
+        self.try_verify_against(
+            VerifierCircuit::Stream(layers, shapes),
+            &layout,
+            public_input,
+            claimed_v,
+            proof,
+        )
+    }
+
+    /// The path of the public entry points: a proof that is not of the size of the layout of
+    /// the layers is rejected before it is parsed, and one that cannot be parsed is rejected as
+    /// malformed, without panicking.
+    fn try_verify_against(
+        &self,
+        circuit: VerifierCircuit<'_, C>,
+        layout: &ProofLayout,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        if layout.layer_segments.is_empty() {
+            return Err(VerificationError::NoLayer);
+        }
+        let expected = layout.proof_size();
+        if proof.bytes.len() != expected {
+            return Err(VerificationError::ProofSize {
+                expected,
+                found: proof.bytes.len(),
+            });
+        }
+        self.verify_against(
+            circuit,
+            public_input,
+            claimed_v,
+            &proof.bytes,
+            &mut SumcheckMessages::default(),
+        )
+        .into_result()
+    }
+
+    fn verify_against(
+        &self,
+        circuit: VerifierCircuit<'_, C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof_bytes: &[u8],
+        messages: &mut SumcheckMessages<C::ChallengeField>,
+    ) -> VerificationChecks {
+        if let Some(hash) = self.config.hash_function {
+            let mut transcript = DynHashTranscript::<C::ChallengeField>::with_hasher(
+                hash.hasher().expect("no transcript for the hash function"),
+            );
+            return self.verify_internal(
+                circuit,
+                public_input,
+                claimed_v,
+                proof_bytes,
+                &mut transcript,
+                messages,
+            );
+        }
         match C::FIAT_SHAMIR_HASH {
             FiatShamirHashType::Keccak256 => {
                 let mut transcript =
                     BytesHashTranscript::<C::ChallengeField, Keccak256hasher>::new();
-                self.verify_internal(circuit, public_input, claimed_v, proof, &mut transcript)
+                self.verify_internal(
+                    circuit,
+                    public_input,
+                    claimed_v,
+                    proof_bytes,
+                    &mut transcript,
+                    messages,
+                )
             }
             FiatShamirHashType::SHA256 => {
                 let mut transcript = BytesHashTranscript::<C::ChallengeField, SHA256hasher>::new();
-                self.verify_internal(circuit, public_input, claimed_v, proof, &mut transcript)
+                self.verify_internal(
+                    circuit,
+                    public_input,
+                    claimed_v,
+                    proof_bytes,
+                    &mut transcript,
+                    messages,
+                )
             }
             FiatShamirHashType::MIMC5 => {
                 let mut transcript =
                     FieldHashTranscript::<C::ChallengeField, MIMCHasher<C::ChallengeField>>::new();
-                self.verify_internal(circuit, public_input, claimed_v, proof, &mut transcript)
+                self.verify_internal(
+                    circuit,
+                    public_input,
+                    claimed_v,
+                    proof_bytes,
+                    &mut transcript,
+                    messages,
+                )
             }
             _ => unreachable!(),
         }
     }
 }
+
+/// One more than the largest public input index read by the gates of the layers, as
+/// `VerifierKey::num_public_inputs`.
+fn num_public_inputs_read<C: GKRConfig>(layers: &[CircuitLayer<C>]) -> usize {
+    layers
+        .iter()
+        .flat_map(|layer| &layer.const_)
+        .filter_map(|gate| match gate.coef_type {
+            CoefType::PublicInput(idx) => Some(idx + 1),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// The claimed values hashed into a field element, prefixed by their number.
+fn claimed_values_digest<F: Field + FieldSerde>(claimed_v: &[F]) -> F {
+    let mut bytes = (claimed_v.len() as u64).to_le_bytes().to_vec();
+    claimed_v
+        .iter()
+        .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+    let mut transcript = BytesHashTranscript::<F, SHA256hasher>::new();
+    transcript.append_u8_slice(&bytes);
+    transcript.generate_challenge_field_element()
+}
+
+/// The public inputs of all the processes in the order of their ranks on the root, and none on
+/// the other processes.
+pub fn gather_public_input<C: GKRConfig>(
+    mpi_config: &MPIConfig,
+    public_input: &[C::SimdCircuitField],
+) -> Vec<C::SimdCircuitField> {
+    let mut gathered = if mpi_config.is_root() {
+        vec![C::SimdCircuitField::ZERO; public_input.len() * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&public_input.to_vec(), &mut gathered);
+    gathered
+}
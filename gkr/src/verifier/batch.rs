@@ -0,0 +1,63 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    thread,
+};
+
+use circuit::Circuit;
+use config::GKRConfig;
+use transcript::Proof;
+
+use crate::{ProofLayout, Verifier};
+
+impl<C: GKRConfig> Verifier<C> {
+    /// Verify each of the proofs with its public input and claimed values, as `verify` does, on
+    /// the circuit. The batch is split into contiguous chunks, one per thread of the config, each
+    /// with its own copy of the chunk, of the circuit and of a single threaded verifier, reused
+    /// for all the proofs of the chunk. A proof of the wrong length, or whose parsing panics, is rejected without affecting
+    /// the others.
+    pub fn verify_batch(
+        &self,
+        circuit: &Circuit<C>,
+        inputs: &[(Vec<C::SimdCircuitField>, Vec<C::ChallengeField>, Proof)],
+    ) -> Vec<bool> {
+        if inputs.is_empty() {
+            return vec![];
+        }
+        let proof_size = ProofLayout::new(circuit, &self.config).proof_size();
+        let num_threads = self.config.num_threads.clamp(1, inputs.len());
+        let chunk_len = inputs.len().div_ceil(num_threads);
+
+        thread::scope(|s| {
+            inputs
+                .chunks(chunk_len)
+                .map(|chunk| {
+                    // the fields are not required to be Sync, so the threads share nothing
+                    let chunk = chunk.to_vec();
+                    // the threads are those of the chunks
+                    let verifier = Verifier::new(&self.config.clone().with_num_threads(1));
+                    let mut circuit = circuit.clone();
+                    s.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(public_input, claimed_v, proof)| {
+                                proof.bytes.len() == proof_size
+                                    && panic::catch_unwind(AssertUnwindSafe(|| {
+                                        verifier.verify(
+                                            &mut circuit,
+                                            public_input,
+                                            claimed_v,
+                                            proof,
+                                        )
+                                    }))
+                                    .unwrap_or(false)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use config::PolynomialCommitmentType;
+use thiserror::Error;
+
+/// Why a proof is rejected, see `Verifier::try_verify`. The layers are indexed as in
+/// `Circuit::layers`, from the input layer, and the rounds of a layer in proof order, over its
+/// input variables, its simd and mpi variables, then its second input variables.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerificationError {
+    #[error("proof has {found} bytes, expected {expected}")]
+    ProofSize { expected: usize, found: usize },
+
+    /// The proof cannot be parsed, e.g., it has an invalid field element.
+    #[error("malformed proof: {0}")]
+    MalformedProof(String),
+
+    /// The first round of the output layer does not sum to the claimed output: either the
+    /// claimed output or the first round message is wrong.
+    #[error("the claimed output does not match the sumcheck of the output layer {layer}")]
+    ClaimedOutput { layer: usize },
+
+    /// The claimed values are not one per output of the circuit, for each simd lane and mpi
+    /// process, see `gather_claimed_values`.
+    #[error("{found} claimed values for the {expected} outputs of the circuit")]
+    ClaimedValuesCount { expected: usize, found: usize },
+
+    #[error("round {round} of the sumcheck of layer {layer} does not match its claim")]
+    SumcheckRound { layer: usize, round: usize },
+
+    /// All the rounds of the layer pass, but their last claim is not the evaluation of the
+    /// gates of the layer at the claims on its inputs.
+    #[error("the final evaluation of layer {layer} does not match its gates")]
+    FinalEvaluation { layer: usize },
+
+    /// The sumchecks pass, but the claim on the input layer, 0 for vx and 1 for vy, is not the
+    /// evaluation of the committed input.
+    #[error("the commitment does not open to claim {claim} on the input layer")]
+    CommitmentOpening { claim: usize },
+
+    /// The config is of a polynomial commitment that is not implemented, see
+    /// `PolynomialCommitmentType::is_implemented`.
+    #[error("the {0:?} polynomial commitment is not implemented")]
+    PolynomialCommitmentUnavailable(PolynomialCommitmentType),
+
+    /// The circuit, the key or the stream the proof is verified against has no layer.
+    #[error("the circuit has no layer")]
+    NoLayer,
+
+    /// The public inputs are fewer than the gates read, or not as many for each mpi process,
+    /// and for a verifier key, not exactly as many, see `Verifier::try_verify_with_key`.
+    #[error("{found} public inputs, expected {expected}")]
+    PublicInputCount { expected: usize, found: usize },
+
+    /// The claimed values of the mpi processes differ.
+    #[error("the claimed values of process {rank} differ from the ones of the root")]
+    ClaimedOutputMismatch { rank: usize },
+
+    /// The public inputs the prover gathered from its processes are not as many as its world
+    /// size requires, or the first of them, of the root, are not the ones of the witness.
+    #[error("the public inputs of the proof are not the ones of the witness")]
+    PublicInputMismatch,
+
+    /// The proof commits to other inputs than the ones it is verified on, `public inputs` or
+    /// `private inputs`, see `ProofPackage::public_input_commitment`: the proof and the witness
+    /// are not of the same prove call. Checked before the sumchecks.
+    #[error("proof/input mismatch: the proof commits to other {0} than the ones given")]
+    InputCommitmentMismatch(&'static str),
+
+    /// On the processes other than the root, which verifies for all of them.
+    #[error("rejected by the root process")]
+    RejectedByRoot,
+}
+
+impl VerificationError {
+    /// The layer of the failure, if of a sumcheck.
+    pub fn layer(&self) -> Option<usize> {
+        match self {
+            Self::ClaimedOutput { layer }
+            | Self::SumcheckRound { layer, .. }
+            | Self::FinalEvaluation { layer } => Some(*layer),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Instant;
+
+use circuit::Circuit;
+use config::GKRConfig;
+use transcript::Proof;
+
+use crate::{VerificationError, Verifier};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    Valid,
+    /// The sumcheck of a layer fails.
+    InvalidSumcheck,
+    /// All the sumchecks pass, but the claims on the input layer do not match the commitment.
+    InvalidFinalCheck,
+    /// The proof cannot be parsed, e.g., it has a wrong length or an invalid field element, or
+    /// its claimed values are not as many as the outputs.
+    MalformedProof,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofVerificationReport {
+    pub status: VerificationStatus,
+    /// The first layer, in verification order (i.e., from the output layer), that fails.
+    pub first_failing_layer: Option<usize>,
+    pub error_message: Option<String>,
+    pub verification_time_ns: u64,
+}
+
+impl ProofVerificationReport {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.status == VerificationStatus::Valid
+    }
+}
+
+impl<C: GKRConfig> Verifier<C> {
+    /// Verify the proof, and report why it is rejected instead of returning a plain bool, see
+    /// `try_verify`.
+    pub fn verify_report(
+        &self,
+        circuit: &mut Circuit<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &[C::ChallengeField],
+        proof: &Proof,
+    ) -> ProofVerificationReport {
+        let timer = Instant::now();
+        let result = self.try_verify(circuit, public_input, claimed_v, proof);
+        let status = match &result {
+            Ok(()) => VerificationStatus::Valid,
+            Err(
+                VerificationError::ProofSize { .. }
+                | VerificationError::MalformedProof(_)
+                | VerificationError::ClaimedValuesCount { .. }
+                | VerificationError::PublicInputCount { .. }
+                | VerificationError::NoLayer,
+            ) => VerificationStatus::MalformedProof,
+            Err(VerificationError::CommitmentOpening { .. }) => {
+                VerificationStatus::InvalidFinalCheck
+            }
+            Err(_) => VerificationStatus::InvalidSumcheck,
+        };
+        let error = result.err();
+
+        ProofVerificationReport {
+            status,
+            first_failing_layer: error.as_ref().and_then(VerificationError::layer),
+            error_message: error.as_ref().map(VerificationError::to_string),
+            verification_time_ns: timer.elapsed().as_nanos() as u64,
+        }
+    }
+}
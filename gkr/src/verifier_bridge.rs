@@ -0,0 +1,111 @@
+//! Verifying proofs with a verifier contract deployed on chain, e.g., by
+//! `EthereumVerifierDeployer`.
+//!
+//! The contract is called, not sent a transaction, with the ABI
+//! `function verify(bytes proof, bytes claimedV, bytes publicInput) external view returns (bool)`,
+//! the claimed values and the public input being in the serialization of `FieldSerde`, one after
+//! the other.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use arith::{FieldSerde, FieldSerdeError};
+use config::GKRConfig;
+use ethers::{
+    abi::{self, Function, Token},
+    providers::{Http, Middleware, Provider, ProviderError},
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest},
+};
+use thiserror::Error;
+use transcript::Proof;
+
+const VERIFY_ABI: &str =
+    "function verify(bytes proof, bytes claimedV, bytes publicInput) external view returns (bool)";
+
+#[derive(Debug, Error)]
+pub enum BridgeError {
+    #[error("abi error: {0}")]
+    AbiError(#[from] abi::Error),
+
+    #[error("provider error: {0}")]
+    ProviderError(#[from] ProviderError),
+
+    #[error("unable to serialize the field elements: {0}")]
+    SerializeError(FieldSerdeError),
+
+    #[error("the verifier returned {0:?} instead of a bool")]
+    UnexpectedOutput(Vec<Token>),
+}
+
+pub struct ExternalVerifierBridge<C: GKRConfig> {
+    contract_address: Address,
+    provider: Arc<Provider<Http>>,
+    verify_function: Function,
+    _marker: PhantomData<C>,
+}
+
+impl<C: GKRConfig> ExternalVerifierBridge<C> {
+    pub fn new(contract_address: Address, provider: Arc<Provider<Http>>) -> Self {
+        Self {
+            contract_address,
+            provider,
+            verify_function: verify_function(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn contract_address(&self) -> Address {
+        self.contract_address
+    }
+
+    /// Whether the contract accepts the proof, at the latest block.
+    pub async fn verify_on_chain(
+        &self,
+        proof: &Proof,
+        claimed_v: &[C::ChallengeField],
+        public_inputs: &[C::SimdCircuitField],
+    ) -> Result<bool, BridgeError> {
+        let calldata = Self::encode_calldata(proof, claimed_v, public_inputs)?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.contract_address)
+            .data(calldata)
+            .into();
+        let output = self.provider.call(&tx, None).await?;
+        match self.verify_function.decode_output(&output)?.as_slice() {
+            [Token::Bool(verified)] => Ok(*verified),
+            tokens => Err(BridgeError::UnexpectedOutput(tokens.to_vec())),
+        }
+    }
+
+    /// The calldata of the call to `verify`: its selector, then its abi encoded arguments.
+    pub fn encode_calldata(
+        proof: &Proof,
+        claimed_v: &[C::ChallengeField],
+        public_inputs: &[C::SimdCircuitField],
+    ) -> Result<Vec<u8>, BridgeError> {
+        let mut claimed_v_bytes = vec![];
+        for v in claimed_v {
+            v.serialize_into(&mut claimed_v_bytes)
+                .map_err(BridgeError::SerializeError)?;
+        }
+        let mut public_input_bytes = vec![];
+        for public_input in public_inputs {
+            public_input
+                .serialize_into(&mut public_input_bytes)
+                .map_err(BridgeError::SerializeError)?;
+        }
+        Ok(verify_function().encode_input(&[
+            Token::Bytes(proof.bytes.clone()),
+            Token::Bytes(claimed_v_bytes),
+            Token::Bytes(public_input_bytes),
+        ])?)
+    }
+}
+
+fn verify_function() -> Function {
+    abi::parse_abi(&[VERIFY_ABI])
+        .unwrap()
+        .function("verify")
+        .unwrap()
+        .clone()
+}
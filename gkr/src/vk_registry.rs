@@ -0,0 +1,186 @@
+//! An in-memory cache of verification keys, for servers verifying proofs of many circuits.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use arith::FieldSerde;
+use circuit::Circuit;
+use config::{Config, GKRConfig};
+
+use crate::{
+    circuit_library::{decode_hash, encode_hash},
+    VerificationKey,
+};
+
+/// Extension of the key files in a cache directory, named after the hex encoded circuit hash.
+const KEY_FILE_EXTENSION: &str = "vk";
+
+struct Entry<C: GKRConfig> {
+    vk: Arc<VerificationKey<C>>,
+    last_used: u64,
+}
+
+struct Cache<C: GKRConfig> {
+    entries: HashMap<[u8; 32], Entry<C>>,
+    /// Incremented on every access, to find the least recently used entry.
+    clock: u64,
+}
+
+/// Verification keys by circuit hash, for the proof system parameters of a config.
+/// Past `max_size` keys, the least recently used key is evicted.
+pub struct VerifierKeyRegistry<C: GKRConfig> {
+    config: Config<C>,
+    max_size: usize,
+    cache: Mutex<Cache<C>>,
+}
+
+impl<C: GKRConfig> VerifierKeyRegistry<C> {
+    pub fn new(config: &Config<C>, max_size: usize) -> Self {
+        assert!(max_size > 0);
+        Self {
+            config: config.clone(),
+            max_size,
+            cache: Mutex::new(Cache {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// The key of the circuit, loading the circuit file and computing the key on a miss.
+    ///
+    /// Panics if the circuit of the file does not have the given hash.
+    pub fn get_or_compute(
+        &self,
+        circuit_hash: &[u8; 32],
+        circuit_file: &str,
+    ) -> Arc<VerificationKey<C>> {
+        if let Some(vk) = self.get(circuit_hash) {
+            return vk;
+        }
+
+        // computed without holding the lock, other requests are served meanwhile
+        let circuit = Circuit::<C>::load_circuit(circuit_file);
+        let vk = VerificationKey::new(&circuit, &self.config);
+        assert_eq!(
+            vk.circuit_hash, *circuit_hash,
+            "the circuit of {} does not have the requested hash",
+            circuit_file
+        );
+        let vk = Arc::new(vk);
+        self.insert(vk.clone());
+        vk
+    }
+
+    pub fn get(&self, circuit_hash: &[u8; 32]) -> Option<Arc<VerificationKey<C>>> {
+        let mut cache = self.cache.lock().unwrap();
+        cache.clock += 1;
+        let clock = cache.clock;
+        cache.entries.get_mut(circuit_hash).map(|entry| {
+            entry.last_used = clock;
+            entry.vk.clone()
+        })
+    }
+
+    pub fn insert(&self, vk: Arc<VerificationKey<C>>) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.clock += 1;
+        let clock = cache.clock;
+        if !cache.entries.contains_key(&vk.circuit_hash) && cache.entries.len() == self.max_size {
+            let lru = *cache
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .unwrap()
+                .0;
+            cache.entries.remove(&lru);
+        }
+        cache.entries.insert(
+            vk.circuit_hash,
+            Entry {
+                vk,
+                last_used: clock,
+            },
+        );
+    }
+
+    pub fn contains(&self, circuit_hash: &[u8; 32]) -> bool {
+        self.cache
+            .lock()
+            .unwrap()
+            .entries
+            .contains_key(circuit_hash)
+    }
+
+    #[inline]
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write each cached key to `<hex encoded circuit hash>.vk` in the directory, created if
+    /// needed.
+    pub fn save_to_dir(&self, path: &str) -> std::io::Result<()> {
+        fs::create_dir_all(path)?;
+        let cache = self.cache.lock().unwrap();
+        for (hash, entry) in &cache.entries {
+            let mut bytes = vec![];
+            entry.vk.serialize_into(&mut bytes).unwrap();
+            let file =
+                Path::new(path).join(format!("{}.{}", encode_hash(hash), KEY_FILE_EXTENSION));
+            fs::write(file, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// A registry holding the keys of the directory, as written by `save_to_dir`.
+    /// Other files, and keys for other proof system parameters than the config's, are skipped.
+    /// Past `max_size` keys, the remaining ones are not loaded.
+    pub fn load_from_dir(path: &str, config: &Config<C>, max_size: usize) -> std::io::Result<Self> {
+        let registry = Self::new(config, max_size);
+        let mut files = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        files.sort();
+
+        for file in files {
+            if registry.len() == max_size {
+                break;
+            }
+            let hash = file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| decode_hash(stem).ok());
+            let (Some(hash), true) = (
+                hash,
+                file.extension()
+                    .is_some_and(|ext| ext == KEY_FILE_EXTENSION),
+            ) else {
+                continue;
+            };
+            let Ok(vk) = VerificationKey::<C>::deserialize_from(fs::read(&file)?.as_slice()) else {
+                continue;
+            };
+            if vk.circuit_hash != hash
+                || vk.gkr_scheme != config.gkr_scheme
+                || vk.polynomial_commitment_type != config.polynomial_commitment_type
+                || vk.world_size != config.mpi_config.world_size()
+            {
+                continue;
+            }
+            registry.insert(Arc::new(vk));
+        }
+        Ok(registry)
+    }
+}
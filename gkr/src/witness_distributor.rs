@@ -0,0 +1,42 @@
+//! Distributing a witness held by the root process to all the MPI processes.
+
+use circuit::Circuit;
+use config::{GKRConfig, MPIConfig};
+
+pub struct MPIWitnessDistributor;
+
+impl MPIWitnessDistributor {
+    /// Scatter the packed input layers of all the processes, concatenated in rank order,
+    /// from the root process, and return the input layer of the current process.
+    ///
+    /// `root_witness` is only read on the root process, where it must be provided.
+    /// Every process receives the same number of values, `1 << circuit.log_input_size()`,
+    /// hence a plain scatter instead of a scatterv.
+    pub fn scatter<C: GKRConfig>(
+        root_witness: Option<&[C::SimdCircuitField]>,
+        circuit: &Circuit<C>,
+        mpi_config: &MPIConfig,
+    ) -> Vec<C::SimdCircuitField> {
+        let local_size = 1 << circuit.log_input_size();
+        let global_witness = if mpi_config.is_root() {
+            let witness = root_witness.expect("the root process must provide the witness");
+            assert_eq!(witness.len(), local_size * mpi_config.world_size());
+            witness
+        } else {
+            &[]
+        };
+
+        let mut local_witness = vec![C::SimdCircuitField::default(); local_size];
+        mpi_config.scatter_vec(global_witness, &mut local_witness);
+        local_witness
+    }
+
+    /// Scatter the witness and set it as the input layer of the circuit.
+    pub fn scatter_into_circuit<C: GKRConfig>(
+        root_witness: Option<&[C::SimdCircuitField]>,
+        circuit: &mut Circuit<C>,
+        mpi_config: &MPIConfig,
+    ) {
+        circuit.layers[0].input_vals = Self::scatter(root_witness, circuit, mpi_config);
+    }
+}
@@ -0,0 +1,181 @@
+//! Witnesses encrypted to a designated verifier, with an ephemeral X25519 key exchange and
+//! ChaCha20-Poly1305.
+//!
+//! The AEAD key is the Sha256 of a domain separator, the shared secret, and both public keys.
+//! Each key is only used once, as the ephemeral secret is drawn for each encryption.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+pub type X25519PublicKey = x25519_dalek::PublicKey;
+pub type X25519SecretKey = x25519_dalek::StaticSecret;
+
+const DOMAIN_SEPARATOR: &[u8] = b"expander witness encryption v1";
+
+#[derive(Debug, Error)]
+pub enum DecryptError {
+    #[error("the ephemeral key is a low order point")]
+    InvalidKey,
+
+    #[error("the ciphertext is not authentic, or not for this key")]
+    AuthenticationFailed,
+
+    #[error("the plaintext is not a list of field elements")]
+    DeserializeError(#[from] FieldSerdeError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedWitness {
+    pub ephemeral_pk: [u8; 32],
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+}
+
+impl FieldSerde for EncryptedWitness {
+    const SERIALIZED_SIZE: usize = unimplemented!();
+
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(&self.ephemeral_pk)?;
+        writer.write_all(&self.nonce)?;
+        self.ciphertext.len().serialize_into(&mut writer)?;
+        writer.write_all(&self.ciphertext)?;
+        Ok(())
+    }
+
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut ephemeral_pk = [0u8; 32];
+        reader.read_exact(&mut ephemeral_pk)?;
+        let mut nonce = [0u8; 12];
+        reader.read_exact(&mut nonce)?;
+        let len = usize::deserialize_from(&mut reader)?;
+        let mut ciphertext = vec![];
+        reader.take(len as u64).read_to_end(&mut ciphertext)?;
+        if ciphertext.len() != len {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(Self {
+            ephemeral_pk,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("not implemented for EncryptedWitness")
+    }
+}
+
+pub struct WitnessEncryptor<F: FieldSerde> {
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldSerde> WitnessEncryptor<F> {
+    pub fn encrypt(
+        witness: &[F],
+        verifier_pk: &X25519PublicKey,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> EncryptedWitness {
+        let mut plaintext = Vec::with_capacity(witness.len() * F::SERIALIZED_SIZE);
+        witness
+            .iter()
+            .for_each(|f| f.serialize_into(&mut plaintext).unwrap());
+        Self::encrypt_bytes(&plaintext, verifier_pk, rng)
+    }
+
+    pub fn decrypt(
+        enc: &EncryptedWitness,
+        verifier_sk: &X25519SecretKey,
+    ) -> Result<Vec<F>, DecryptError> {
+        let plaintext = Self::decrypt_bytes(enc, verifier_sk)?;
+        if plaintext.len() % F::SERIALIZED_SIZE != 0 {
+            return Err(FieldSerdeError::DeserializeError.into());
+        }
+        plaintext
+            .chunks(F::SERIALIZED_SIZE)
+            .map(|chunk| F::deserialize_from(chunk).map_err(DecryptError::from))
+            .collect()
+    }
+
+    /// Encrypt raw bytes, e.g., a witness file, see `Circuit::load_witness_bytes`. The rng draws
+    /// the ephemeral secret and the nonce, so it must be cryptographically secure.
+    pub fn encrypt_bytes(
+        plaintext: &[u8],
+        verifier_pk: &X25519PublicKey,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> EncryptedWitness {
+        let mut ephemeral_sk = [0u8; 32];
+        rng.fill_bytes(&mut ephemeral_sk);
+        let ephemeral_sk = X25519SecretKey::from(ephemeral_sk);
+        let ephemeral_pk = X25519PublicKey::from(&ephemeral_sk);
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut nonce);
+
+        let shared = ephemeral_sk.diffie_hellman(verifier_pk);
+        let cipher = cipher(shared.as_bytes(), &ephemeral_pk, verifier_pk);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: ephemeral_pk.as_bytes(),
+                },
+            )
+            .unwrap();
+
+        EncryptedWitness {
+            ephemeral_pk: ephemeral_pk.to_bytes(),
+            nonce,
+            ciphertext,
+        }
+    }
+
+    pub fn decrypt_bytes(
+        enc: &EncryptedWitness,
+        verifier_sk: &X25519SecretKey,
+    ) -> Result<Vec<u8>, DecryptError> {
+        let ephemeral_pk = X25519PublicKey::from(enc.ephemeral_pk);
+        let shared = verifier_sk.diffie_hellman(&ephemeral_pk);
+        if !shared.was_contributory() {
+            return Err(DecryptError::InvalidKey);
+        }
+        let cipher = cipher(
+            shared.as_bytes(),
+            &ephemeral_pk,
+            &X25519PublicKey::from(verifier_sk),
+        );
+        cipher
+            .decrypt(
+                Nonce::from_slice(&enc.nonce),
+                Payload {
+                    msg: &enc.ciphertext,
+                    aad: ephemeral_pk.as_bytes(),
+                },
+            )
+            .map_err(|_| DecryptError::AuthenticationFailed)
+    }
+}
+
+fn cipher(
+    shared: &[u8; 32],
+    ephemeral_pk: &X25519PublicKey,
+    verifier_pk: &X25519PublicKey,
+) -> ChaCha20Poly1305 {
+    let key = Sha256::new()
+        .chain_update(DOMAIN_SEPARATOR)
+        .chain_update(shared)
+        .chain_update(ephemeral_pk.as_bytes())
+        .chain_update(verifier_pk.as_bytes())
+        .finalize();
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
@@ -0,0 +1,255 @@
+//! Searching witnesses of underconstrained circuits by gradient descent.
+//!
+//! The circuit is relaxed to the reals: each field element is embedded as its representative in
+//! `(-p/2, p/2]`, and the gates are evaluated with `f64` arithmetic. Gradient descent minimizes
+//! the sum of the squared differences between the targeted wires and their targets, then the
+//! inputs are rounded to the nearest integers and checked over the field. This is a heuristic:
+//! it only succeeds when a solution with small integer inputs is near the descent path, which
+//! is mostly the case for small circuits over prime fields.
+
+use std::marker::PhantomData;
+
+use arith::{FieldForECC, SimdField};
+use circuit::{Circuit, CircuitLayer, CoefType, GateKind, GateRef};
+use config::GKRConfig;
+use ethnum::U256;
+use rand::Rng;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConvergenceError {
+    #[error("gate {0:?} is not a gate of the circuit")]
+    UnknownGate(GateRef),
+
+    #[error("the descent diverged at iteration {0}")]
+    Diverged(usize),
+
+    #[error("no witness found after {iterations} iterations, loss {loss}")]
+    NotConverged { iterations: usize, loss: f64 },
+}
+
+/// Under this loss, the descent stops and the inputs are rounded.
+const LOSS_TOLERANCE: f64 = 1e-10;
+
+pub struct GradientDescentWitnessFinder<C: GKRConfig> {
+    pub learning_rate: f64,
+    pub max_iterations: usize,
+    _marker: PhantomData<C>,
+}
+
+/// The gates of a layer with their coefficients embedded in the reals.
+struct RealLayer {
+    mul: Vec<(usize, usize, usize, f64)>,
+    add: Vec<(usize, usize, f64)>,
+    const_: Vec<(usize, f64)>,
+    /// Input, output, coefficient, and whether the gate is the pow5 gate or the identity.
+    uni: Vec<(usize, usize, f64, bool)>,
+    output_size: usize,
+}
+
+impl<C: GKRConfig> GradientDescentWitnessFinder<C> {
+    pub fn new(learning_rate: f64, max_iterations: usize) -> Self {
+        Self {
+            learning_rate,
+            max_iterations,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Find input values such that the output wire of each targeted gate holds its target.
+    /// The public input of the circuit is taken from its first simd lane. The descent starts
+    /// from inputs drawn from `rng`, e.g., a seeded one for the same witness on every run.
+    pub fn find(
+        &self,
+        circuit: &Circuit<C>,
+        output_targets: &[(GateRef, C::CircuitField)],
+        mut rng: impl Rng,
+    ) -> Result<Vec<C::CircuitField>, ConvergenceError> {
+        // the targets as (value layer, wire, target), where value layer l + 1 is written by
+        // circuit layer l
+        let targets = output_targets
+            .iter()
+            .map(|(gate, target)| {
+                let o_id =
+                    output_wire(circuit, gate).ok_or(ConvergenceError::UnknownGate(*gate))?;
+                Ok((gate.layer + 1, o_id, to_real(target)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let layers = circuit
+            .layers
+            .iter()
+            .map(|layer| self.real_layer(circuit, layer))
+            .collect::<Vec<_>>();
+
+        let mut x = (0..1 << circuit.layers[0].input_var_num)
+            .map(|_| rng.gen_range(-1.0..1.0))
+            .collect::<Vec<f64>>();
+
+        let mut loss = f64::INFINITY;
+        for iteration in 0..self.max_iterations {
+            let vals = forward(&layers, &x);
+            loss = targets
+                .iter()
+                .map(|&(l, wire, target)| (vals[l][wire] - target).powi(2))
+                .sum();
+            if !loss.is_finite() {
+                return Err(ConvergenceError::Diverged(iteration));
+            }
+            if loss < LOSS_TOLERANCE {
+                break;
+            }
+
+            let grad = backward(&layers, &vals, &targets);
+            x.iter_mut()
+                .zip(grad)
+                .for_each(|(x, g)| *x -= self.learning_rate * g);
+        }
+
+        let witness = x.iter().map(|v| from_real::<C>(*v)).collect::<Vec<_>>();
+        if self.satisfies(circuit, &witness, output_targets) {
+            Ok(witness)
+        } else {
+            Err(ConvergenceError::NotConverged {
+                iterations: self.max_iterations,
+                loss,
+            })
+        }
+    }
+
+    fn real_layer(&self, circuit: &Circuit<C>, layer: &CircuitLayer<C>) -> RealLayer {
+        RealLayer {
+            mul: layer
+                .mul
+                .iter()
+                .map(|g| (g.i_ids[0], g.i_ids[1], g.o_id, to_real(&g.coef)))
+                .collect(),
+            add: layer
+                .add
+                .iter()
+                .map(|g| (g.i_ids[0], g.o_id, to_real(&g.coef)))
+                .collect(),
+            const_: layer
+                .const_
+                .iter()
+                .map(|g| {
+                    let coef = match g.coef_type {
+                        CoefType::PublicInput(idx) => circuit.public_input[idx].unpack()[0],
+                        _ => g.coef,
+                    };
+                    (g.o_id, to_real(&coef))
+                })
+                .collect(),
+            uni: layer
+                .uni
+                .iter()
+                .map(|g| (g.i_ids[0], g.o_id, to_real(&g.coef), g.gate_type == 12345))
+                .collect(),
+            output_size: 1 << layer.output_var_num,
+        }
+    }
+
+    fn satisfies(
+        &self,
+        circuit: &Circuit<C>,
+        witness: &[C::CircuitField],
+        output_targets: &[(GateRef, C::CircuitField)],
+    ) -> bool {
+        let mut circuit = circuit.clone();
+        circuit.layers[0].input_vals = witness
+            .iter()
+            .map(C::circuit_field_to_simd_circuit_field)
+            .collect();
+        circuit.evaluate();
+
+        output_targets.iter().all(|(gate, target)| {
+            let o_id = output_wire(&circuit, gate).unwrap();
+            let vals = match circuit.layers.get(gate.layer + 1) {
+                Some(next_layer) => &next_layer.input_vals,
+                None => &circuit.layers[gate.layer].output_vals,
+            };
+            vals[o_id].unpack()[0] == *target
+        })
+    }
+}
+
+fn output_wire<C: GKRConfig>(circuit: &Circuit<C>, gate: &GateRef) -> Option<usize> {
+    let layer = circuit.layers.get(gate.layer)?;
+    match gate.kind {
+        GateKind::Mul => layer.mul.get(gate.index).map(|g| g.o_id),
+        GateKind::Add => layer.add.get(gate.index).map(|g| g.o_id),
+        GateKind::Const => layer.const_.get(gate.index).map(|g| g.o_id),
+        GateKind::Uni => layer.uni.get(gate.index).map(|g| g.o_id),
+    }
+}
+
+/// The values of all the layers, the input layer first.
+fn forward(layers: &[RealLayer], x: &[f64]) -> Vec<Vec<f64>> {
+    let mut vals = vec![x.to_vec()];
+    for layer in layers {
+        let a = vals.last().unwrap();
+        let mut out = vec![0.0; layer.output_size];
+        layer
+            .mul
+            .iter()
+            .for_each(|&(i0, i1, o, c)| out[o] += c * a[i0] * a[i1]);
+        layer.add.iter().for_each(|&(i, o, c)| out[o] += c * a[i]);
+        layer.const_.iter().for_each(|&(o, c)| out[o] += c);
+        layer
+            .uni
+            .iter()
+            .for_each(|&(i, o, c, pow5)| out[o] += if pow5 { c * a[i].powi(5) } else { c * a[i] });
+        vals.push(out);
+    }
+    vals
+}
+
+/// The gradient of the loss with respect to the inputs.
+fn backward(layers: &[RealLayer], vals: &[Vec<f64>], targets: &[(usize, usize, f64)]) -> Vec<f64> {
+    let mut grads = vals.iter().map(|v| vec![0.0; v.len()]).collect::<Vec<_>>();
+    for &(l, wire, target) in targets {
+        grads[l][wire] += 2.0 * (vals[l][wire] - target);
+    }
+
+    for (l, layer) in layers.iter().enumerate().rev() {
+        let (below, above) = grads.split_at_mut(l + 1);
+        let (g_in, g_out, a) = (&mut below[l], &above[0], &vals[l]);
+        layer.mul.iter().for_each(|&(i0, i1, o, c)| {
+            g_in[i0] += c * a[i1] * g_out[o];
+            g_in[i1] += c * a[i0] * g_out[o];
+        });
+        layer
+            .add
+            .iter()
+            .for_each(|&(i, o, c)| g_in[i] += c * g_out[o]);
+        layer.uni.iter().for_each(|&(i, o, c, pow5)| {
+            g_in[i] += if pow5 { 5.0 * c * a[i].powi(4) } else { c } * g_out[o]
+        });
+    }
+    grads.swap_remove(0)
+}
+
+/// The representative of the element in `(-p/2, p/2]`.
+fn to_real<F: FieldForECC>(v: &F) -> f64 {
+    let to_f64 = |x: U256| {
+        let (high, low) = x.into_words();
+        high as f64 * 2f64.powi(128) + low as f64
+    };
+    let x = v.to_u256();
+    let p = F::modulus();
+    if x > p / U256::new(2) {
+        -to_f64(p - x)
+    } else {
+        to_f64(x)
+    }
+}
+
+fn from_real<C: GKRConfig>(v: f64) -> C::CircuitField {
+    let rounded = v.round() as i128;
+    let abs = C::CircuitField::from_u256(U256::new(rounded.unsigned_abs()));
+    if rounded < 0 {
+        -abs
+    } else {
+        abs
+    }
+}
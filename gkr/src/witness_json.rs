@@ -0,0 +1,289 @@
+//! Witnesses as JSON, for the small circuits whose witnesses are written by hand, e.g.:
+//!
+//! ```json
+//! {"private": ["1", "0x2", "3", "4"], "public": ["42"]}
+//! ```
+//!
+//! or an array of such objects, one per witness. Each value is a decimal or `0x` hex string, or
+//! a JSON integer, less than the modulus of the circuit field. The JSON witness is converted to
+//! the bytes of a witness file, see `witness_json_to_bytes`, and back.
+
+use arith::{FieldForECC, FieldSerde};
+use circuit::{
+    witness_payload, Circuit, CircuitError, Witness, WitnessError, WITNESS_HEADER_SIZE,
+    WITNESS_MAGIC, WITNESS_VERSION,
+};
+use config::GKRConfig;
+use ethnum::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// The number of witnesses, of private and of public inputs, and the modulus, after the header.
+const WITNESS_PAYLOAD_HEADER_SIZE: usize = 3 * 8 + 32;
+
+#[derive(Debug, Error)]
+pub enum WitnessJsonError {
+    #[error("invalid json witness: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("no witnesses")]
+    NoWitnesses,
+
+    #[error(
+        "{section} input {index} of witness {witness}, {value}, is not a decimal or 0x hex number"
+    )]
+    InvalidValue {
+        witness: usize,
+        section: &'static str,
+        index: usize,
+        value: String,
+    },
+
+    #[error(
+        "{section} input {index} of witness {witness}, {value}, is not less than the modulus \
+         {modulus}"
+    )]
+    ValueOutOfRange {
+        witness: usize,
+        section: &'static str,
+        index: usize,
+        value: String,
+        modulus: U256,
+    },
+
+    #[error("witness {witness} has {found} private inputs, the circuit has {expected}")]
+    PrivateInputCount {
+        witness: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    /// Fewer public inputs than the const gates of the circuit read, or not as many as those of
+    /// the first witness.
+    #[error("witness {witness} has {found} public inputs, expected {expected}")]
+    PublicInputCount {
+        witness: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error(transparent)]
+    Witness(#[from] CircuitError),
+}
+
+/// A witness of a JSON witness file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonWitness {
+    pub private: Vec<Value>,
+    #[serde(default)]
+    pub public: Vec<Value>,
+}
+
+enum ValueError {
+    Invalid,
+    OutOfRange,
+}
+
+/// The field element of a decimal or `0x` hex string, or of a JSON integer.
+fn parse_value<F: FieldForECC>(value: &Value) -> Result<F, ValueError> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64().map(U256::from).ok_or(ValueError::Invalid)?,
+        Value::String(s) => {
+            let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(digits) => (digits, 16),
+                None => (s.as_str(), 10),
+            };
+            if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+                return Err(ValueError::Invalid);
+            }
+            // only the digits are left, a value too large for 256 bits
+            U256::from_str_radix(digits, radix).map_err(|_| ValueError::OutOfRange)?
+        }
+        _ => return Err(ValueError::Invalid),
+    };
+    if parsed >= F::modulus() {
+        return Err(ValueError::OutOfRange);
+    }
+    Ok(F::from_u256(parsed))
+}
+
+/// The witnesses of the JSON, checked against the inputs of the circuit: each has as many
+/// private inputs as the input layer, and at least as many public inputs as the const gates
+/// read, the same number for all of them.
+pub fn parse_witness_json<C: GKRConfig>(
+    circuit: &Circuit<C>,
+    json: &str,
+) -> Result<Witness<C>, WitnessJsonError> {
+    let witnesses = match serde_json::from_str::<Value>(json)? {
+        value @ Value::Array(_) => serde_json::from_value::<Vec<JsonWitness>>(value)?,
+        value => vec![serde_json::from_value::<JsonWitness>(value)?],
+    };
+    let Some(first) = witnesses.first() else {
+        return Err(WitnessJsonError::NoWitnesses);
+    };
+    let num_private_inputs = 1 << circuit.log_input_size();
+    let num_public_inputs = first.public.len();
+    let min_public_inputs = circuit.stats().public_input_size;
+
+    let mut values = Vec::with_capacity(witnesses.len() * (num_private_inputs + num_public_inputs));
+    for (w, witness) in witnesses.iter().enumerate() {
+        if witness.private.len() != num_private_inputs {
+            return Err(WitnessJsonError::PrivateInputCount {
+                witness: w,
+                expected: num_private_inputs,
+                found: witness.private.len(),
+            });
+        }
+        if witness.public.len() < min_public_inputs || witness.public.len() != num_public_inputs {
+            return Err(WitnessJsonError::PublicInputCount {
+                witness: w,
+                expected: num_public_inputs.max(min_public_inputs),
+                found: witness.public.len(),
+            });
+        }
+        let sections = [("private", &witness.private), ("public", &witness.public)];
+        for (section, section_values) in sections {
+            for (index, value) in section_values.iter().enumerate() {
+                let value = parse_value::<C::CircuitField>(value).map_err(|e| {
+                    let value = value.to_string();
+                    match e {
+                        ValueError::Invalid => WitnessJsonError::InvalidValue {
+                            witness: w,
+                            section,
+                            index,
+                            value,
+                        },
+                        ValueError::OutOfRange => WitnessJsonError::ValueOutOfRange {
+                            witness: w,
+                            section,
+                            index,
+                            value,
+                            modulus: C::CircuitField::modulus(),
+                        },
+                    }
+                })?;
+                values.push(value);
+            }
+        }
+    }
+    Ok(Witness {
+        num_witnesses: witnesses.len(),
+        num_private_inputs_per_witness: num_private_inputs,
+        num_public_inputs_per_witness: num_public_inputs,
+        values,
+    })
+}
+
+/// The bytes of the witness file of the witnesses, header included.
+pub fn encode_witness<C: GKRConfig>(witness: &Witness<C>) -> Vec<u8> {
+    let modulus = C::CircuitField::modulus().to_le_bytes();
+    let value_size = C::CircuitField::SERIALIZED_SIZE;
+    let mut bytes = Vec::with_capacity(
+        WITNESS_HEADER_SIZE + WITNESS_PAYLOAD_HEADER_SIZE + witness.values.len() * value_size,
+    );
+    bytes.extend_from_slice(&WITNESS_MAGIC);
+    bytes.extend_from_slice(&WITNESS_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&modulus);
+    for count in [
+        witness.num_witnesses,
+        witness.num_private_inputs_per_witness,
+        witness.num_public_inputs_per_witness,
+    ] {
+        bytes.extend_from_slice(&(count as u64).to_le_bytes());
+    }
+    bytes.extend_from_slice(&modulus);
+    for value in &witness.values {
+        // into a vec, which does not fail
+        value.serialize_into(&mut bytes).unwrap();
+    }
+    bytes
+}
+
+/// The witnesses of the bytes of a witness file, header included, checked to be exactly as
+/// many values as its header tells.
+pub fn decode_witness<C: GKRConfig>(bytes: &[u8]) -> Result<Witness<C>, WitnessJsonError> {
+    let payload = witness_payload::<C::CircuitField>(bytes).map_err(CircuitError::from)?;
+    if payload.len() < WITNESS_PAYLOAD_HEADER_SIZE {
+        return Err(CircuitError::from(WitnessError::Truncated).into());
+    }
+    let count = |i: usize| usize::from_le_bytes(payload[8 * i..8 * i + 8].try_into().unwrap());
+    let (num_witnesses, num_private_inputs, num_public_inputs) = (count(0), count(1), count(2));
+    let value_size = C::CircuitField::SERIALIZED_SIZE;
+    let values = &payload[WITNESS_PAYLOAD_HEADER_SIZE..];
+    let values_len = num_private_inputs
+        .checked_add(num_public_inputs)
+        .and_then(|num_inputs| num_inputs.checked_mul(num_witnesses))
+        .and_then(|num_values| num_values.checked_mul(value_size));
+    if values_len != Some(values.len()) {
+        return Err(CircuitError::from(WitnessError::LengthMismatch {
+            expected: (WITNESS_HEADER_SIZE + WITNESS_PAYLOAD_HEADER_SIZE)
+                .saturating_add(values_len.unwrap_or(usize::MAX)),
+            got: bytes.len(),
+        })
+        .into());
+    }
+    let values = values
+        .chunks_exact(value_size)
+        .map(C::CircuitField::deserialize_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CircuitError::from)?;
+    Ok(Witness {
+        num_witnesses,
+        num_private_inputs_per_witness: num_private_inputs,
+        num_public_inputs_per_witness: num_public_inputs,
+        values,
+    })
+}
+
+/// The witnesses as JSON, their values as decimal strings, a single object for a single
+/// witness.
+pub fn witness_to_json<C: GKRConfig>(witness: &Witness<C>) -> String {
+    let num_inputs = witness.num_private_inputs_per_witness + witness.num_public_inputs_per_witness;
+    let to_json = |values: &[C::CircuitField]| {
+        values
+            .iter()
+            .map(|value| Value::String(value.to_u256().to_string()))
+            .collect()
+    };
+    let witnesses = (0..witness.num_witnesses)
+        .map(|w| {
+            let values = &witness.values[w * num_inputs..(w + 1) * num_inputs];
+            let (private, public) = values.split_at(witness.num_private_inputs_per_witness);
+            JsonWitness {
+                private: to_json(private),
+                public: to_json(public),
+            }
+        })
+        .collect::<Vec<_>>();
+    match witnesses.as_slice() {
+        [witness] => serde_json::to_string_pretty(witness),
+        _ => serde_json::to_string_pretty(&witnesses),
+    }
+    .unwrap()
+}
+
+/// The bytes of the witness file of the JSON witnesses, see `parse_witness_json`.
+pub fn witness_json_to_bytes<C: GKRConfig>(
+    circuit: &Circuit<C>,
+    json: &str,
+) -> Result<Vec<u8>, WitnessJsonError> {
+    Ok(encode_witness(&parse_witness_json(circuit, json)?))
+}
+
+/// The JSON of the witnesses of the bytes of a witness file, see `witness_to_json`.
+pub fn witness_bytes_to_json<C: GKRConfig>(bytes: &[u8]) -> Result<String, WitnessJsonError> {
+    Ok(witness_to_json(&decode_witness::<C>(bytes)?))
+}
+
+/// Load the JSON witnesses into the circuit, as `Circuit::try_load_witness_bytes` the bytes of
+/// their witness file, fewer witnesses than the simd lanes being padded.
+pub fn load_witness_json<C: GKRConfig>(
+    circuit: &mut Circuit<C>,
+    json: &str,
+) -> Result<(), WitnessJsonError> {
+    let bytes = witness_json_to_bytes(circuit, json)?;
+    circuit.try_load_witness_bytes(&bytes, true)?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Loading a batch of witness files ahead of proving.
+//!
+//! Reading and deserializing the witnesses is I/O bound, while proving is CPU bound,
+//! so all the witnesses of a batch are loaded concurrently before the sequential proving loop.
+
+use std::{fs, marker::PhantomData};
+
+use circuit::{Circuit, CircuitError};
+use config::GKRConfig;
+use rayon::prelude::*;
+
+/// Packed inputs parsed from a witness file, ready to be set into the circuit.
+#[derive(Debug, Clone)]
+pub struct LoadedWitness<C: GKRConfig> {
+    pub private_input: Vec<C::SimdCircuitField>,
+    pub public_input: Vec<C::SimdCircuitField>,
+}
+
+impl<C: GKRConfig> LoadedWitness<C> {
+    /// Set the inputs into the circuit, the same way `Circuit::load_witness_file` does.
+    pub fn apply_to(&self, circuit: &mut Circuit<C>) {
+        assert_eq!(self.private_input.len(), 1 << circuit.log_input_size());
+        circuit.layers[0].input_vals.clone_from(&self.private_input);
+        circuit.public_input.clone_from(&self.public_input);
+    }
+}
+
+pub struct BatchWitnessLoader<C: GKRConfig> {
+    phantom: PhantomData<C>,
+}
+
+impl<C: GKRConfig> BatchWitnessLoader<C> {
+    /// Load and deserialize the witness files on the rayon thread pool.
+    /// The results are in the same order as `witness_files`, or the error of a file that cannot
+    /// be read or whose witness does not fit the circuit.
+    pub fn load_parallel(
+        circuit: &Circuit<C>,
+        witness_files: &[&str],
+    ) -> Result<Vec<LoadedWitness<C>>, CircuitError> {
+        Self::load_parallel_with_padding(circuit, witness_files, false)
+    }
+
+    /// Same as `load_parallel`, but witness files with fewer witnesses than the simd pack size
+    /// are padded, as in `Circuit::load_non_simd_witness_file`.
+    pub fn load_parallel_with_padding(
+        circuit: &Circuit<C>,
+        witness_files: &[&str],
+        allow_padding: bool,
+    ) -> Result<Vec<LoadedWitness<C>>, CircuitError> {
+        // the circuit holds raw pointers and cannot be shared across threads,
+        // the input size is all the parsing needs
+        let log_input_size = circuit.log_input_size();
+
+        witness_files
+            .par_iter()
+            .map(|filename| {
+                let file_bytes = fs::read(filename)?;
+                let (private_input, public_input) = Circuit::<C>::try_parse_witness_bytes(
+                    log_input_size,
+                    &file_bytes,
+                    allow_padding,
+                )?;
+                Ok(LoadedWitness {
+                    private_input,
+                    public_input,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,149 @@
+//! Access to witness values without materializing the witness file in memory.
+//!
+//! The GKR prover still needs the packed input layer, whose size is the private input size
+//! of a single witness times the simd pack size. What an oracle avoids is keeping the whole
+//! witness file, i.e. all the witnesses with their public inputs, in memory: only the values
+//! that end up in the packed input layer are read.
+
+use std::{fs::File, io::Cursor};
+
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use circuit::{split_witness_header, Circuit, WITNESS_HEADER_SIZE};
+use config::GKRConfig;
+use transcript::Proof;
+
+use crate::Prover;
+
+/// Random access to the values of a witness, in the layout of `Witness::values`:
+/// each witness is its private inputs followed by its public inputs.
+pub trait WitnessOracle<C: GKRConfig> {
+    fn num_witnesses(&self) -> usize;
+
+    fn num_private_inputs_per_witness(&self) -> usize;
+
+    fn num_public_inputs_per_witness(&self) -> usize;
+
+    fn get(&self, index: usize) -> C::CircuitField;
+}
+
+/// Witness oracle mapping a witness file, of which only the elements read are paged in.
+#[derive(Debug)]
+pub struct FileBackedWitnessOracle {
+    mmap: memmap2::Mmap,
+    /// The serialized size of the elements of the file, of the circuit field it was opened for
+    element_size: usize,
+    num_witnesses: usize,
+    num_private_inputs_per_witness: usize,
+    num_public_inputs_per_witness: usize,
+}
+
+/// num_witnesses, num_private_inputs_per_witness, num_public_inputs_per_witness, and the modulus,
+/// after the witness header
+const PAYLOAD_HEADER_SIZE: usize = 3 * <usize as FieldSerde>::SERIALIZED_SIZE + 32;
+
+fn invalid_data(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> FieldSerdeError {
+    FieldSerdeError::IOError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+impl FileBackedWitnessOracle {
+    /// Map the witness file and read its header, the values are read lazily. The magic and the
+    /// version of the header are checked, not its field, and the file must hold the values of
+    /// its counts, of elements of the circuit field of `C`.
+    pub fn open<C: GKRConfig>(filename: &str) -> FieldSerdeResult<Self> {
+        let element_size = <C::CircuitField as FieldSerde>::SERIALIZED_SIZE;
+        let file = File::open(filename)?;
+        // the witness files are not expected to change while being mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        if mmap.len() < WITNESS_HEADER_SIZE + PAYLOAD_HEADER_SIZE {
+            return Err(invalid_data("the witness file is shorter than its header"));
+        }
+        split_witness_header(&mmap[..WITNESS_HEADER_SIZE]).map_err(invalid_data)?;
+        let mut reader = Cursor::new(&mmap[WITNESS_HEADER_SIZE..]);
+        let num_witnesses = usize::deserialize_from(&mut reader)?;
+        let num_private_inputs_per_witness = usize::deserialize_from(&mut reader)?;
+        let num_public_inputs_per_witness = usize::deserialize_from(&mut reader)?;
+
+        let size = num_private_inputs_per_witness
+            .checked_add(num_public_inputs_per_witness)
+            .and_then(|n| n.checked_mul(num_witnesses))
+            .and_then(|n| n.checked_mul(element_size))
+            .and_then(|n| n.checked_add(WITNESS_HEADER_SIZE + PAYLOAD_HEADER_SIZE));
+        if size != Some(mmap.len()) {
+            return Err(invalid_data(format!(
+                "the witness file is of {} bytes, not of the {} witnesses of {} and {} inputs",
+                mmap.len(),
+                num_witnesses,
+                num_private_inputs_per_witness,
+                num_public_inputs_per_witness
+            )));
+        }
+
+        Ok(Self {
+            mmap,
+            element_size,
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+        })
+    }
+
+    fn read_element<F: FieldSerde>(&self, index: usize) -> FieldSerdeResult<F> {
+        if F::SERIALIZED_SIZE != self.element_size {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        let offset = WITNESS_HEADER_SIZE + PAYLOAD_HEADER_SIZE + index * F::SERIALIZED_SIZE;
+        F::deserialize_from(&self.mmap[offset..offset + F::SERIALIZED_SIZE])
+    }
+}
+
+impl<C: GKRConfig> WitnessOracle<C> for FileBackedWitnessOracle {
+    fn num_witnesses(&self) -> usize {
+        self.num_witnesses
+    }
+
+    fn num_private_inputs_per_witness(&self) -> usize {
+        self.num_private_inputs_per_witness
+    }
+
+    fn num_public_inputs_per_witness(&self) -> usize {
+        self.num_public_inputs_per_witness
+    }
+
+    /// Panics if the index is out of range, or the element is not of the field, e.g., of a file
+    /// opened for another field, the file being of the size of its elements, see
+    /// `FileBackedWitnessOracle::open`.
+    fn get(&self, index: usize) -> C::CircuitField {
+        let total = self.num_witnesses
+            * (self.num_private_inputs_per_witness + self.num_public_inputs_per_witness);
+        assert!(
+            index < total,
+            "witness index {} out of range {}",
+            index,
+            total
+        );
+        self.read_element::<C::CircuitField>(index)
+            .expect("the witness element is not of the field")
+    }
+}
+
+impl<C: GKRConfig> Prover<C> {
+    /// Prove the circuit on the witness provided by the oracle.
+    pub fn prove_with_oracle(
+        &mut self,
+        c: &mut Circuit<C>,
+        oracle: &dyn WitnessOracle<C>,
+    ) -> (Vec<C::ChallengeField>, Proof) {
+        let (private_input, public_input) = Circuit::<C>::pack_witness_values(
+            c.log_input_size(),
+            oracle.num_witnesses(),
+            oracle.num_private_inputs_per_witness(),
+            oracle.num_public_inputs_per_witness(),
+            false,
+            |idx| oracle.get(idx),
+        );
+        c.layers[0].input_vals = private_input;
+        c.public_input = public_input;
+
+        self.prove_silent(c)
+    }
+}
@@ -0,0 +1,224 @@
+//! Declared formats of the witnesses of known circuits, identified by their hash.
+//!
+//! The registry is stored as a TOML file, with a table per circuit, e.g.:
+//!
+//! ```toml
+//! [<hex encoded hash>]
+//! field_type = "M31"
+//! element_count = 8
+//! ranges = [{ start = 4, end = 8, min = 0, max = 255 }]
+//! constraints = [
+//!     { kind = "boolean", start = 0, end = 4 },
+//!     { kind = "equal", a = 4, b = 5 },
+//! ]
+//! ```
+
+use std::{collections::BTreeMap, fmt, fs};
+
+use arith::{Field, FieldForECC};
+use circuit::Circuit;
+use config::GKRConfig;
+use ethnum::U256;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::circuit_library::{decode_hash, encode_hash};
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("io error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    #[error("toml error: {0}")]
+    TomlDeError(#[from] toml::de::Error),
+
+    #[error("toml error: {0}")]
+    TomlSerError(#[from] toml::ser::Error),
+
+    #[error("invalid circuit hash: {0}")]
+    InvalidHash(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SchemaValidationError {
+    #[error("no schema registered for the circuit")]
+    UnknownCircuit,
+
+    #[error("the schema is for field {expected}, the circuit is over {found}")]
+    FieldMismatch { expected: String, found: String },
+
+    #[error("expected {expected} witness elements, found {found}")]
+    CountMismatch { expected: usize, found: usize },
+
+    #[error("element {index} is out of the range [{min}, {max}]")]
+    OutOfRange { index: usize, min: u64, max: u64 },
+
+    #[error("constraint {0} is violated")]
+    ConstraintViolated(WitnessConstraint),
+}
+
+/// The elements `start..end` must lie in `[min, max]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementRange {
+    pub start: usize,
+    pub end: usize,
+    pub min: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WitnessConstraint {
+    /// The elements `start..end` are 0 or 1.
+    Boolean { start: usize, end: usize },
+    /// The elements `a` and `b` are equal.
+    Equal { a: usize, b: usize },
+    /// The element `index` is not zero.
+    NonZero { index: usize },
+}
+
+impl fmt::Display for WitnessConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean { start, end } => write!(f, "elements {}..{} are boolean", start, end),
+            Self::Equal { a, b } => write!(f, "element {} equals element {}", a, b),
+            Self::NonZero { index } => write!(f, "element {} is not zero", index),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessSchema {
+    /// As `FieldType` is debug printed, e.g., `M31`.
+    pub field_type: String,
+    pub element_count: usize,
+    #[serde(default)]
+    pub ranges: Vec<ElementRange>,
+    #[serde(default)]
+    pub constraints: Vec<WitnessConstraint>,
+}
+
+impl WitnessSchema {
+    /// Check the witness, the elements given by their canonical representatives.
+    pub fn validate<F: FieldForECC>(&self, witness: &[F]) -> Result<(), SchemaValidationError> {
+        if witness.len() != self.element_count {
+            return Err(SchemaValidationError::CountMismatch {
+                expected: self.element_count,
+                found: witness.len(),
+            });
+        }
+        // out of bounds indices are counted as violations
+        let get = |index: usize| witness.get(index).map(|v| v.to_u256());
+
+        for range in &self.ranges {
+            for index in range.start..range.end {
+                let in_range = get(index)
+                    .is_some_and(|v| v >= U256::from(range.min) && v <= U256::from(range.max));
+                if !in_range {
+                    return Err(SchemaValidationError::OutOfRange {
+                        index,
+                        min: range.min,
+                        max: range.max,
+                    });
+                }
+            }
+        }
+
+        for constraint in &self.constraints {
+            let satisfied = match *constraint {
+                WitnessConstraint::Boolean { start, end } => {
+                    (start..end).all(|index| get(index).is_some_and(|v| v <= U256::ONE))
+                }
+                WitnessConstraint::Equal { a, b } => get(a).is_some() && get(a) == get(b),
+                WitnessConstraint::NonZero { index } => {
+                    witness.get(index).is_some_and(|v| !v.is_zero())
+                }
+            };
+            if !satisfied {
+                return Err(SchemaValidationError::ConstraintViolated(
+                    constraint.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WitnessSchemaRegistry {
+    schemas: BTreeMap<[u8; 32], WitnessSchema>,
+}
+
+impl WitnessSchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &str) -> Result<Self, RegistryError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), RegistryError> {
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, RegistryError> {
+        let entries: BTreeMap<String, WitnessSchema> = toml::from_str(s)?;
+        let schemas = entries
+            .into_iter()
+            .map(|(hash, schema)| {
+                let hash = decode_hash(&hash).map_err(|_| RegistryError::InvalidHash(hash))?;
+                Ok((hash, schema))
+            })
+            .collect::<Result<_, RegistryError>>()?;
+        Ok(Self { schemas })
+    }
+
+    pub fn to_toml(&self) -> Result<String, RegistryError> {
+        let entries = self
+            .schemas
+            .iter()
+            .map(|(hash, schema)| (encode_hash(hash), schema))
+            .collect::<BTreeMap<_, _>>();
+        Ok(toml::to_string_pretty(&entries)?)
+    }
+
+    /// Register the schema of the circuit of the given hash, see `Circuit::hash`, replacing
+    /// any previous one.
+    pub fn register(&mut self, circuit_hash: &[u8; 32], schema: WitnessSchema) {
+        self.schemas.insert(*circuit_hash, schema);
+    }
+
+    pub fn lookup(&self, circuit_hash: &[u8; 32]) -> Option<&WitnessSchema> {
+        self.schemas.get(circuit_hash)
+    }
+
+    pub fn validate<C: GKRConfig>(
+        &self,
+        circuit: &Circuit<C>,
+        witness: &[C::CircuitField],
+    ) -> Result<(), SchemaValidationError> {
+        let schema = self
+            .lookup(&circuit.hash())
+            .ok_or(SchemaValidationError::UnknownCircuit)?;
+        let field_type = format!("{:?}", C::FIELD_TYPE);
+        if schema.field_type != field_type {
+            return Err(SchemaValidationError::FieldMismatch {
+                expected: schema.field_type.clone(),
+                found: field_type,
+            });
+        }
+        schema.validate(witness)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+}
@@ -0,0 +1,171 @@
+//! A fixed set of workers, e.g., a circuit and its prover each, shared by concurrent requests.
+//!
+//! A request waits for a free worker up to a timeout, then runs on the blocking threads of
+//! tokio, holding the worker until it is done. The requests on different workers run in
+//! parallel, and none of them blocks the async runtime.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError, TryLockError,
+    },
+    time::Duration,
+};
+
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum WorkerPoolError {
+    #[error("no worker was free before the timeout")]
+    Timeout,
+
+    #[error("the job panicked")]
+    Panicked,
+}
+
+pub struct WorkerPool<W> {
+    workers: Vec<Mutex<W>>,
+    /// One permit per worker: a job holding a permit finds a free worker.
+    semaphore: Arc<Semaphore>,
+    busy: AtomicUsize,
+    acquire_timeout: Duration,
+}
+
+impl<W: Send + 'static> WorkerPool<W> {
+    /// Panics if there is no worker.
+    pub fn new(workers: Vec<W>, acquire_timeout: Duration) -> Self {
+        assert!(!workers.is_empty(), "no worker");
+        Self {
+            semaphore: Arc::new(Semaphore::new(workers.len())),
+            workers: workers.into_iter().map(Mutex::new).collect(),
+            busy: AtomicUsize::new(0),
+            acquire_timeout,
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// The number of workers running a job, without waiting for any of them.
+    #[inline]
+    pub fn busy(&self) -> usize {
+        self.busy.load(Ordering::SeqCst)
+    }
+
+    /// Run the job on a free worker, waiting for one at most the timeout of the pool.
+    pub async fn run<T, F>(self: &Arc<Self>, job: F) -> Result<T, WorkerPoolError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut W) -> T + Send + 'static,
+    {
+        let permit =
+            tokio::time::timeout(self.acquire_timeout, self.semaphore.clone().acquire_owned())
+                .await
+                .map_err(|_| WorkerPoolError::Timeout)?
+                .expect("the semaphore is never closed");
+        self.run_with_permit(permit, job).await
+    }
+
+    /// Run the job on a free worker, waiting for one however long it takes, e.g., for the jobs
+    /// whose requests are answered before they run. The jobs waiting are served in order.
+    pub async fn run_queued<T, F>(self: &Arc<Self>, job: F) -> Result<T, WorkerPoolError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut W) -> T + Send + 'static,
+    {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        self.run_with_permit(permit, job).await
+    }
+
+    /// Run the job on every worker in turn, e.g., to prepare them, ahead of the jobs run after
+    /// the call, which wait for it. The workers are taken when called rather than when awaited,
+    /// unless some of them are busy.
+    pub fn run_on_all<F>(
+        self: &Arc<Self>,
+        job: F,
+    ) -> impl Future<Output = Result<(), WorkerPoolError>> + Send + 'static
+    where
+        F: Fn(&mut W) + Send + 'static,
+    {
+        let pool = self.clone();
+        let num_workers = self.workers.len() as u32;
+        let permits = self
+            .semaphore
+            .clone()
+            .try_acquire_many_owned(num_workers)
+            .ok();
+        async move {
+            let permits = match permits {
+                Some(permits) => permits,
+                None => pool
+                    .semaphore
+                    .clone()
+                    .acquire_many_owned(num_workers)
+                    .await
+                    .expect("the semaphore is never closed"),
+            };
+            tokio::task::spawn_blocking(move || {
+                let _permits = permits;
+                for worker in &pool.workers {
+                    job(&mut worker.lock().unwrap_or_else(PoisonError::into_inner));
+                }
+            })
+            .await
+            .map_err(|_| WorkerPoolError::Panicked)
+        }
+    }
+
+    async fn run_with_permit<T, F>(
+        self: &Arc<Self>,
+        permit: OwnedSemaphorePermit,
+        job: F,
+    ) -> Result<T, WorkerPoolError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut W) -> T + Send + 'static,
+    {
+        let pool = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let mut worker = pool
+                .workers
+                .iter()
+                .find_map(|worker| match worker.try_lock() {
+                    Ok(worker) => Some(worker),
+                    // the job that panicked left the worker to be overwritten by the next one
+                    Err(TryLockError::Poisoned(worker)) => Some(worker.into_inner()),
+                    Err(TryLockError::WouldBlock) => None,
+                })
+                .expect("a free worker for each permit");
+            let _busy = BusyGuard::new(&pool.busy);
+            job(&mut worker)
+        })
+        .await
+        .map_err(|_| WorkerPoolError::Panicked)
+    }
+}
+
+/// Counts a worker as busy until dropped, even if its job panics.
+struct BusyGuard<'a>(&'a AtomicUsize);
+
+impl<'a> BusyGuard<'a> {
+    fn new(busy: &'a AtomicUsize) -> Self {
+        busy.fetch_add(1, Ordering::SeqCst);
+        Self(busy)
+    }
+}
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
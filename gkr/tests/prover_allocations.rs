@@ -0,0 +1,111 @@
+//! The allocations of consecutive proofs, counted by the global allocator of this test binary,
+//! apart from the unit tests which would count along.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use arith::{Field, FieldSerde};
+use circuit::{migrate_v0_to_v1, Circuit, GateBuilder};
+use config::{Config, FieldType, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use gkr::Prover;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const NUM_WIRES: usize = 256;
+
+/// 8 layers over the wires, the i-th wire of each the product of the i-th and the next one of
+/// the layer below.
+fn circuit() -> Circuit<C> {
+    let mut builder = GateBuilder::<C>::new(NUM_WIRES);
+    let mut wires = (0..NUM_WIRES).map(|i| builder.input(i)).collect::<Vec<_>>();
+    for _ in 0..8 {
+        builder.next_layer();
+        wires = (0..NUM_WIRES)
+            .map(|i| builder.mul(wires[i], wires[(i + 1) % NUM_WIRES], F::ONE))
+            .collect();
+    }
+    let mut circuit = builder.build();
+    circuit.identify_rnd_coefs();
+    circuit
+}
+
+fn witness_bytes() -> Vec<u8> {
+    let num_witnesses = C::get_field_pack_size();
+    let mut bytes = vec![];
+    num_witnesses.serialize_into(&mut bytes).unwrap();
+    NUM_WIRES.serialize_into(&mut bytes).unwrap();
+    0usize.serialize_into(&mut bytes).unwrap();
+    [0u64; 4].serialize_into(&mut bytes).unwrap();
+    for i in 0..num_witnesses * NUM_WIRES {
+        F::from(i as u32 + 1).serialize_into(&mut bytes).unwrap();
+    }
+    migrate_v0_to_v1(&bytes, FieldType::M31)
+}
+
+#[test]
+fn test_prove_allocations_do_not_grow() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::new_for_verifier(1));
+    let mut circuit = circuit();
+    let witness = witness_bytes();
+    let mut prover = Prover::new(&config).unwrap();
+    prover.prepare_mem(&circuit);
+
+    // the bytes allocated by, and the bytes still live after, each call, the proof dropped
+    let mut calls = Vec::with_capacity(100);
+    for _ in 0..100 {
+        let allocated = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        circuit.try_load_witness_bytes(&witness, false).unwrap();
+        let (_, proof) = prover.prove_silent(&mut circuit);
+        drop(proof);
+        calls.push((
+            ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated,
+            LIVE_BYTES.load(Ordering::Relaxed),
+        ));
+    }
+
+    let (second, second_live) = calls[1];
+    let (hundredth, hundredth_live) = calls[99];
+    assert!(
+        hundredth <= second + second / 20,
+        "the hundredth proof allocated {hundredth} bytes, the second {second}"
+    );
+    assert!(
+        hundredth_live <= second_live + 1024,
+        "{hundredth_live} bytes live after the hundredth proof, {second_live} after the second"
+    );
+
+    // the inputs and the values of the layers are only allocated by the first call
+    let input_bytes = NUM_WIRES * <C as GKRConfig>::SimdCircuitField::SIZE;
+    assert!(calls[0].0 >= second + input_bytes);
+}
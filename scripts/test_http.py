@@ -29,7 +29,7 @@ if __name__ == '__main__':
     response = requests.post(url+"/verify", headers=verify_headers, data=verifier_input)
     print(response)
     # check success message
-    assert response.text == "success", f"Failed to verify proof: {response.text}"
+    assert response.json()["ok"], f"Failed to verify proof: {response.text}"
     print("Proof verified successfully")
     
     # try tempered proof
@@ -40,8 +40,8 @@ if __name__ == '__main__':
     tempered_proof = proof[:random_byte_index] + bytes([proof[random_byte_index] ^ (1 << random_bit_index)]) + proof[random_byte_index+1:]
     tempered_input = witness_len + proof_len + witness + tempered_proof
     response = requests.post(url+"/verify", headers=verify_headers, data=tempered_input)
-    # check failure message
-    assert response.text == "failure", f"Failed to detect tempered proof: {response.text}"
+    # check failure message, a 422 if the tempered proof cannot even be read
+    assert not response.json()["ok"], f"Failed to detect tempered proof: {response.text}"
     print("Tempered proof detected successfully")
 
     # try prove using witness with invalid length
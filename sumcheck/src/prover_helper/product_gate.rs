@@ -3,6 +3,11 @@
 
 use arith::{ExtensionField, Field, SimdField};
 use config::{FieldType, GKRConfig};
+use rayon::{prelude::*, ThreadPool};
+
+/// The evaluations summed, or folded, by each task of a pool. The tables of at most as many
+/// pairs are left to the calling thread.
+const PARALLEL_CHUNK_SIZE: usize = 1 << 12;
 
 pub(crate) struct SumcheckProductGateHelper {
     var_num: usize,
@@ -21,9 +26,11 @@ impl SumcheckProductGateHelper {
     // - bk_f: bookkeeping table of f(x)
     // - bk_hg: bookkeeping table of h_g(x)
     // - init_v: input values; will be processed iff var_idex == 0
+    // - pool: the threads to sum over, if any
     // Output:
     // - the univariate polynomial that prover sends to the verifier
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn poly_eval_at<C: GKRConfig>(
         &self,
         var_idx: usize,
@@ -32,12 +39,10 @@ impl SumcheckProductGateHelper {
         bk_hg: &[C::Field],
         init_v: &[C::SimdCircuitField],
         gate_exists: &[bool],
+        pool: Option<&ThreadPool>,
     ) -> [C::Field; 3] {
         assert_eq!(degree, 2);
 
-        let mut p0 = C::Field::zero();
-        let mut p1 = C::Field::zero();
-        let mut p2 = C::Field::zero();
         log::trace!("bk_f: {:?}", &bk_f[..4]);
         log::trace!("bk_hg: {:?}", &bk_hg[..4]);
         log::trace!("init_v: {:?}", &init_v[..4]);
@@ -45,10 +50,61 @@ impl SumcheckProductGateHelper {
         let eval_size = 1 << (self.var_num - var_idx - 1);
         log::trace!("Eval size: {}", eval_size);
 
+        // the sums of the chunks are exact, and so the same whatever the number of threads
+        let sum_range = |start: usize, end: usize| {
+            Self::sum_range::<C>(start, end, var_idx, bk_f, bk_hg, init_v, gate_exists)
+        };
+        let [p0, p1, mut p2] = match pool {
+            Some(pool) if eval_size > PARALLEL_CHUNK_SIZE => pool.install(|| {
+                (0..eval_size / PARALLEL_CHUNK_SIZE)
+                    .into_par_iter()
+                    .map(|chunk| {
+                        sum_range(
+                            chunk * PARALLEL_CHUNK_SIZE,
+                            (chunk + 1) * PARALLEL_CHUNK_SIZE,
+                        )
+                    })
+                    .reduce(
+                        || [C::Field::zero(); 3],
+                        |[a0, a1, a2], [b0, b1, b2]| [a0 + b0, a1 + b1, a2 + b2],
+                    )
+            }),
+            _ => sum_range(0, eval_size),
+        };
+
+        if C::FIELD_TYPE == FieldType::GF2 {
+            // over GF2_128, the three points are at 0, 1 and X
+            let p2x = p2.mul_by_x();
+            let p2x2 = p2x.mul_by_x();
+            let linear_term = p1 + p0 + p2;
+            p2 = p2x2 + linear_term.mul_by_x() + p0;
+        } else {
+            // when Field size > 2, the three points are 0, 1, -2
+            p2 = p1.mul_by_6() + p0.mul_by_3() - p2.double();
+        }
+        [p0, p1, p2]
+    }
+
+    /// The sums of `poly_eval_at` over the pairs of `start..end`, before the interpolation.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn sum_range<C: GKRConfig>(
+        start: usize,
+        end: usize,
+        var_idx: usize,
+        bk_f: &[C::Field],
+        bk_hg: &[C::Field],
+        init_v: &[C::SimdCircuitField],
+        gate_exists: &[bool],
+    ) -> [C::Field; 3] {
+        let mut p0 = C::Field::zero();
+        let mut p1 = C::Field::zero();
+        let mut p2 = C::Field::zero();
+
         if var_idx == 0 {
             // this is the first layer, we are able to accelerate by
             // avoiding the extension field operations
-            for i in 0..eval_size {
+            for i in start..end {
                 if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                     continue;
                 }
@@ -70,7 +126,7 @@ impl SumcheckProductGateHelper {
             }
         } else {
             // for the rest of layers we use extension field operations.
-            for i in 0..eval_size {
+            for i in start..end {
                 if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                     continue;
                 }
@@ -90,22 +146,12 @@ impl SumcheckProductGateHelper {
                 p2 += (f_v_0 + f_v_1) * (hg_v_0 + hg_v_1);
             }
         }
-
-        if C::FIELD_TYPE == FieldType::GF2 {
-            // over GF2_128, the three points are at 0, 1 and X
-            let p2x = p2.mul_by_x();
-            let p2x2 = p2x.mul_by_x();
-            let linear_term = p1 + p0 + p2;
-            p2 = p2x2 + linear_term.mul_by_x() + p0;
-        } else {
-            // when Field size > 2, the three points are 0, 1, -2
-            p2 = p1.mul_by_6() + p0.mul_by_3() - p2.double();
-        }
         [p0, p1, p2]
     }
 
     // process the challenge and update the bookkeeping tables for f and h_g accordingly
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn receive_challenge<C: GKRConfig>(
         &mut self,
         var_idx: usize,
@@ -114,12 +160,83 @@ impl SumcheckProductGateHelper {
         bk_hg: &mut [C::Field],
         init_v: &[C::SimdCircuitField],
         gate_exists: &mut [bool],
+        pool: Option<&ThreadPool>,
     ) {
         assert!(var_idx < self.var_num);
 
         let eval_size = 1 << (self.var_num - var_idx - 1);
+        let Some(pool) = pool.filter(|_| eval_size > PARALLEL_CHUNK_SIZE) else {
+            Self::fold_prefix::<C>(eval_size, var_idx, r, bk_f, bk_hg, init_v, gate_exists);
+            return;
+        };
+
+        // the i-th entries are folded in place from the 2i-th and the (2i + 1)-th: after the
+        // first chunk, the ranges k..2k are folded one after the other, each by all the threads,
+        // from 2k..4k, which is only folded after
+        Self::fold_prefix::<C>(
+            PARALLEL_CHUNK_SIZE,
+            var_idx,
+            r,
+            bk_f,
+            bk_hg,
+            init_v,
+            gate_exists,
+        );
+        let mut k = PARALLEL_CHUNK_SIZE;
+        while k < eval_size {
+            let (f_out, f_in) = bk_f.split_at_mut(2 * k);
+            let (hg_out, hg_in) = bk_hg.split_at_mut(2 * k);
+            let (exists_out, exists_in) = gate_exists.split_at_mut(2 * k);
+            let (f_in, hg_in, exists_in) = (&*f_in, &*hg_in, &*exists_in);
+            let v_in = if var_idx == 0 { &init_v[2 * k..] } else { &[] };
+            pool.install(|| {
+                f_out[k..]
+                    .par_chunks_mut(PARALLEL_CHUNK_SIZE)
+                    .zip(hg_out[k..].par_chunks_mut(PARALLEL_CHUNK_SIZE))
+                    .zip(exists_out[k..].par_chunks_mut(PARALLEL_CHUNK_SIZE))
+                    .enumerate()
+                    .for_each(|(chunk, ((f, hg), exists))| {
+                        let outputs = f.iter_mut().zip(hg.iter_mut()).zip(exists.iter_mut());
+                        for (j, ((f, hg), exists)) in outputs.enumerate() {
+                            let i = chunk * PARALLEL_CHUNK_SIZE + j;
+                            *exists = exists_in[2 * i] || exists_in[2 * i + 1];
+                            *f = if var_idx == 0 {
+                                C::field_add_simd_circuit_field(
+                                    &C::simd_circuit_field_mul_challenge_field(
+                                        &(v_in[2 * i + 1] - v_in[2 * i]),
+                                        &r,
+                                    ),
+                                    &v_in[2 * i],
+                                )
+                            } else {
+                                f_in[2 * i] + (f_in[2 * i + 1] - f_in[2 * i]).scale(&r)
+                            };
+                            *hg = if *exists {
+                                hg_in[2 * i] + (hg_in[2 * i + 1] - hg_in[2 * i]).scale(&r)
+                            } else {
+                                C::Field::zero()
+                            };
+                        }
+                    });
+            });
+            k *= 2;
+        }
+    }
+
+    /// Fold the first `len` entries of the tables, in order, on the calling thread.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn fold_prefix<C: GKRConfig>(
+        len: usize,
+        var_idx: usize,
+        r: C::ChallengeField,
+        bk_f: &mut [C::Field],
+        bk_hg: &mut [C::Field],
+        init_v: &[C::SimdCircuitField],
+        gate_exists: &mut [bool],
+    ) {
         if var_idx == 0 {
-            for i in 0..eval_size {
+            for i in 0..len {
                 if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                     gate_exists[i] = false;
 
@@ -145,7 +262,7 @@ impl SumcheckProductGateHelper {
                 }
             }
         } else {
-            for i in 0..eval_size {
+            for i in 0..len {
                 if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                     gate_exists[i] = false;
                     bk_f[i] = bk_f[2 * i] + (bk_f[2 * i + 1] - bk_f[2 * i]).scale(&r);
@@ -43,6 +43,7 @@ impl<'a, C: GKRConfig> SumcheckGkrVanillaHelper<'a, C> {
             &mut self.sp.hg_evals,
             &self.layer.input_vals,
             &mut self.sp.gate_exists_5,
+            self.sp.pool.as_deref(),
         );
     }
 }
@@ -103,6 +104,7 @@ impl<'a, C: GKRConfig> SumcheckGkrVanillaHelper<'a, C> {
             &self.sp.hg_evals,
             &self.layer.input_vals,
             &self.sp.gate_exists_5,
+            self.sp.pool.as_deref(),
         );
 
         // SIMD
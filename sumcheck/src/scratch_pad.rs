@@ -1,6 +1,6 @@
 //! Scratch pad for prover and verifier to store intermediate values during the sumcheck protocol.
 
-use std::{cmp::max, ptr};
+use std::{cmp::max, ptr, sync::Arc};
 
 use arith::{ExtensionField, Field};
 use circuit::Circuit;
@@ -28,6 +28,10 @@ pub struct ProverScratchPad<C: GKRConfig> {
     pub gate_exists_1: Vec<bool>,
 
     pub phase2_coef: C::ChallengeField,
+
+    /// The threads the rounds of the vanilla sumcheck are evaluated over, if more than the
+    /// caller's, see `Prover::prepare_mem`.
+    pub pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl<C: GKRConfig> ProverScratchPad<C> {
@@ -66,6 +70,7 @@ impl<C: GKRConfig> ProverScratchPad<C> {
             gate_exists_5: vec![false; max_input_num],
             gate_exists_1: vec![false; max_input_num],
             phase2_coef: C::ChallengeField::ZERO,
+            pool: None,
         }
     }
 }
@@ -102,7 +107,11 @@ impl<C: GKRConfig> VerifierScratchPad<C> {
             .max()
             .unwrap();
         max_num_var = max(max_num_var, circuit.log_input_size());
+        Self::with_max_num_var(config, max_num_var)
+    }
 
+    /// For the layers of at most `max_num_var` input and output variables.
+    pub fn with_max_num_var(config: &Config<C>, max_num_var: usize) -> Self {
         let max_io_size = 1usize << max_num_var;
         let simd_size = C::get_field_pack_size();
 
@@ -0,0 +1,62 @@
+// this module benchmarks the sequential and the parallel bytes-hash transcripts
+
+use arith::{BN254Fr, Field};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use transcript::{BytesHashTranscript, ParallelHashTranscript, SHA256hasher, Transcript};
+
+const N_ROUNDS: usize = 1 << 12;
+
+/// Mimic the sumcheck transcript: each round absorbs a few field elements and squeezes a challenge.
+fn sumcheck_rounds<T: Transcript<BN254Fr>>(transcript: &mut T) {
+    let mut r = BN254Fr::ONE;
+    for _ in 0..N_ROUNDS {
+        for _ in 0..3 {
+            transcript.append_field_element(&r);
+        }
+        r = transcript.generate_challenge_field_element();
+    }
+    black_box(r);
+}
+
+/// Mimic the commitment absorption: a single large message followed by a challenge.
+fn large_message<T: Transcript<BN254Fr>>(transcript: &mut T, message: &[u8]) {
+    transcript.append_u8_slice(message);
+    black_box(transcript.generate_challenge_field_element());
+}
+
+fn criterion_transcript(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transcript sumcheck rounds");
+    group.bench_function(BenchmarkId::new("sequential", N_ROUNDS), |b| {
+        b.iter(|| sumcheck_rounds(&mut BytesHashTranscript::<BN254Fr, SHA256hasher>::new()))
+    });
+    group.bench_function(BenchmarkId::new("parallel", N_ROUNDS), |b| {
+        b.iter(|| sumcheck_rounds(&mut ParallelHashTranscript::<BN254Fr, SHA256hasher>::new()))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("transcript large message");
+    for log_size in [20, 24] {
+        let message = vec![1u8; 1 << log_size];
+        group.bench_function(BenchmarkId::new("sequential", 1 << log_size), |b| {
+            b.iter(|| {
+                large_message(
+                    &mut BytesHashTranscript::<BN254Fr, SHA256hasher>::new(),
+                    &message,
+                )
+            })
+        });
+        group.bench_function(BenchmarkId::new("parallel", 1 << log_size), |b| {
+            b.iter(|| {
+                large_message(
+                    &mut ParallelHashTranscript::<BN254Fr, SHA256hasher>::new(),
+                    &message,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_transcript);
+criterion_main!(benches);
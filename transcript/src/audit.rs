@@ -0,0 +1,127 @@
+use arith::{Field, FieldSerde};
+
+use crate::{Proof, Transcript, TranscriptState};
+
+/// A call made to a transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// Bytes appended to the transcript, a field element being appended as its serialization.
+    Absorb(Vec<u8>),
+    /// A challenge drawn, `n_bytes` long.
+    Challenge {
+        n_bytes: usize,
+    },
+    /// `hash_and_return_state`.
+    HashState,
+    /// `set_state`, with the new state.
+    SetState(Vec<u8>),
+    LockProof,
+    UnlockProof,
+}
+
+/// The calls made to a transcript, in order, see `AuditedTranscript`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptAuditLog {
+    pub events: Vec<TranscriptEvent>,
+    /// The bytes binding the transcript to the statement, e.g., the hash of the circuit, which
+    /// the first absorbed message is expected to contain.
+    pub statement: Option<Vec<u8>>,
+}
+
+/// A transcript recording all the calls made to it before forwarding them to `T`.
+/// The challenges are the ones of `T`.
+#[derive(Debug, Clone)]
+pub struct AuditedTranscript<F: Field + FieldSerde, T: Transcript<F>> {
+    inner: T,
+    log: TranscriptAuditLog,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: Field + FieldSerde, T: Transcript<F>> AuditedTranscript<F, T> {
+    /// Record the calls, expecting the transcript to be bound to the statement.
+    pub fn with_statement(statement: &[u8]) -> Self {
+        let mut transcript = Self::new();
+        transcript.log.statement = Some(statement.to_vec());
+        transcript
+    }
+
+    #[inline]
+    pub fn log(&self) -> &TranscriptAuditLog {
+        &self.log
+    }
+
+    #[inline]
+    pub fn into_log(self) -> TranscriptAuditLog {
+        self.log
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    #[inline]
+    fn record(&mut self, event: TranscriptEvent) {
+        self.log.events.push(event);
+    }
+}
+
+impl<F: Field + FieldSerde, T: Transcript<F>> Transcript<F> for AuditedTranscript<F, T> {
+    fn new() -> Self {
+        Self {
+            inner: T::new(),
+            log: TranscriptAuditLog::default(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn append_field_element(&mut self, f: &F) {
+        let mut buf = vec![];
+        f.serialize_into(&mut buf).unwrap();
+        self.record(TranscriptEvent::Absorb(buf));
+        self.inner.append_field_element(f);
+    }
+
+    fn append_u8_slice(&mut self, buffer: &[u8]) {
+        self.record(TranscriptEvent::Absorb(buffer.to_vec()));
+        self.inner.append_u8_slice(buffer);
+    }
+
+    fn generate_challenge_field_element(&mut self) -> F {
+        self.record(TranscriptEvent::Challenge { n_bytes: F::SIZE });
+        self.inner.generate_challenge_field_element()
+    }
+
+    fn generate_challenge_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
+        self.record(TranscriptEvent::Challenge { n_bytes });
+        self.inner.generate_challenge_u8_slice(n_bytes)
+    }
+
+    fn finalize_and_get_proof(&self) -> Proof {
+        self.inner.finalize_and_get_proof()
+    }
+
+    fn hash_and_return_state(&mut self) -> Vec<u8> {
+        self.record(TranscriptEvent::HashState);
+        self.inner.hash_and_return_state()
+    }
+
+    fn set_state(&mut self, state: &[u8]) {
+        self.record(TranscriptEvent::SetState(state.to_vec()));
+        self.inner.set_state(state);
+    }
+
+    fn lock_proof(&mut self) {
+        self.record(TranscriptEvent::LockProof);
+        self.inner.lock_proof();
+    }
+
+    fn unlock_proof(&mut self) {
+        self.record(TranscriptEvent::UnlockProof);
+        self.inner.unlock_proof();
+    }
+
+    fn capture_state(&self) -> TranscriptState {
+        self.inner.capture_state()
+    }
+}
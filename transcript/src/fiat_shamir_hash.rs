@@ -6,10 +6,13 @@ pub use sha2_256::*;
 pub mod keccak_256;
 pub use keccak_256::*;
 
+pub mod blake3_256;
+pub use blake3_256::*;
+
 pub mod mimc;
 pub use mimc::*;
 
-pub trait FiatShamirBytesHash {
+pub trait FiatShamirBytesHash: Send + Sync {
     /// The size of the hash output in bytes.
     const DIGEST_SIZE: usize;
 
@@ -23,6 +26,52 @@ pub trait FiatShamirBytesHash {
     fn hash_inplace(buffer: &mut [u8]);
 }
 
+/// A `FiatShamirBytesHash` chosen at runtime, see `DynHashTranscript`.
+pub trait HashTranscript: Send + Sync {
+    /// The size of the hash output in bytes.
+    fn digest_size(&self) -> usize;
+
+    /// Hash the input into the output.
+    fn hash(&self, output: &mut [u8], input: &[u8]);
+
+    /// Hash the input in place.
+    fn hash_inplace(&self, buffer: &mut [u8]);
+}
+
+impl<H: FiatShamirBytesHash> HashTranscript for H {
+    #[inline]
+    fn digest_size(&self) -> usize {
+        <H as FiatShamirBytesHash>::DIGEST_SIZE
+    }
+
+    #[inline]
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        <H as FiatShamirBytesHash>::hash(output, input)
+    }
+
+    #[inline]
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        <H as FiatShamirBytesHash>::hash_inplace(buffer)
+    }
+}
+
+impl HashTranscript for Box<dyn HashTranscript> {
+    #[inline]
+    fn digest_size(&self) -> usize {
+        (**self).digest_size()
+    }
+
+    #[inline]
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        (**self).hash(output, input)
+    }
+
+    #[inline]
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        (**self).hash_inplace(buffer)
+    }
+}
+
 pub trait FiatShamirFieldHash<F: Field + FieldSerde> {
     /// Create a new hash instance.
     fn new() -> Self;
@@ -0,0 +1,24 @@
+use super::FiatShamirBytesHash;
+
+#[derive(Debug, Clone, Default)]
+pub struct Blake3hasher;
+
+impl FiatShamirBytesHash for Blake3hasher {
+    const DIGEST_SIZE: usize = 32;
+
+    #[inline]
+    fn new() -> Blake3hasher {
+        Blake3hasher
+    }
+
+    #[inline]
+    fn hash(output: &mut [u8], input: &[u8]) {
+        output.copy_from_slice(blake3::hash(input).as_bytes());
+    }
+
+    #[inline]
+    fn hash_inplace(buffer: &mut [u8]) {
+        let digest = blake3::hash(buffer);
+        buffer.copy_from_slice(digest.as_bytes());
+    }
+}
@@ -1,13 +1,23 @@
 mod fiat_shamir_hash;
 pub use fiat_shamir_hash::{
-    FiatShamirBytesHash, FiatShamirFieldHash, Keccak256hasher, MIMCHasher, SHA256hasher,
+    Blake3hasher, FiatShamirBytesHash, FiatShamirFieldHash, HashTranscript, Keccak256hasher,
+    MIMCHasher, SHA256hasher,
 };
 
 mod transcript;
-pub use transcript::{BytesHashTranscript, FieldHashTranscript, Transcript};
+pub use transcript::{BytesHashTranscript, DynHashTranscript, FieldHashTranscript, Transcript};
+
+mod parallel_transcript;
+pub use parallel_transcript::{ParallelHashTranscript, PARALLEL_THRESHOLD};
 
 mod proof;
-pub use proof::Proof;
+pub use proof::{decode_hex, encode_hex, Proof, ProofDecodeError};
+
+mod audit;
+pub use audit::{AuditedTranscript, TranscriptAuditLog, TranscriptEvent};
+
+mod state;
+pub use state::{TranscriptState, TranscriptStateCapture, MAX_DIGEST_SIZE};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,137 @@
+//! A bytes-hash transcript that hashes large absorbed messages with multiple threads.
+//!
+//! Every Fiat-Shamir challenge depends on the messages absorbed before it, and the
+//! prover's next message depends on that challenge. The chain of challenges is therefore
+//! inherently sequential: a challenge cannot be computed before the message it binds to exists,
+//! and "speculating" on the next message would require the prover to commit to it before seeing
+//! the challenge, which breaks soundness.
+//!
+//! What can be parallelized is the hashing of a single large message, e.g., the raw commitment
+//! of the input layer that is absorbed before the first challenge. When the data pending to be
+//! hashed exceeds `PARALLEL_THRESHOLD` bytes, it is split into chunks that are hashed by
+//! separate threads, and the digest is the hash of the concatenated chunk digests. Below the
+//! threshold the transcript behaves exactly like `BytesHashTranscript`.
+//!
+//! Note: since the digest of large messages differs from `BytesHashTranscript`, prover and
+//! verifier must both use `ParallelHashTranscript`.
+
+use std::{marker::PhantomData, thread};
+
+use arith::{Field, FieldSerde};
+
+use crate::{
+    fiat_shamir_hash::{FiatShamirBytesHash, HashTranscript},
+    transcript::{forward_to_hasher_transcript, HasherTranscript},
+    Proof, Transcript, TranscriptState,
+};
+
+/// Pending data strictly larger than this many bytes is hashed in parallel.
+pub const PARALLEL_THRESHOLD: usize = 1 << 16;
+
+/// Size of the chunks that are hashed independently.
+const CHUNK_SIZE: usize = 1 << 14;
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct ParallelHashTranscript<F: Field + FieldSerde, H: FiatShamirBytesHash> {
+    phantom: PhantomData<F>,
+
+    inner: HasherTranscript<ParallelHasher<H>>,
+}
+
+impl<F: Field + FieldSerde, H: FiatShamirBytesHash> Transcript<F> for ParallelHashTranscript<F, H> {
+    fn new() -> Self {
+        let n_threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self::new_with_threads(n_threads)
+    }
+
+    forward_to_hasher_transcript!();
+}
+
+impl<F: Field + FieldSerde, H: FiatShamirBytesHash> ParallelHashTranscript<F, H> {
+    /// Create a transcript using a fixed number of hashing threads.
+    /// The digest does not depend on the number of threads.
+    pub fn new_with_threads(n_threads: usize) -> Self {
+        Self {
+            phantom: PhantomData,
+            inner: HasherTranscript::new(ParallelHasher {
+                phantom: PhantomData,
+                n_threads: n_threads.max(1),
+            }),
+        }
+    }
+
+    /// Hash the pending input into the digest.
+    pub fn hash_to_digest(&mut self) {
+        self.inner.hash_to_digest()
+    }
+}
+
+/// The hasher of `ParallelHashTranscript`: `H`, of the chunk digests for a large input.
+#[derive(Clone, Default, Debug, PartialEq)]
+struct ParallelHasher<H: FiatShamirBytesHash> {
+    phantom: PhantomData<H>,
+
+    /// Number of threads used for hashing large messages.
+    n_threads: usize,
+}
+
+impl<H: FiatShamirBytesHash> HashTranscript for ParallelHasher<H> {
+    #[inline]
+    fn digest_size(&self) -> usize {
+        H::DIGEST_SIZE
+    }
+
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        if input.len() > PARALLEL_THRESHOLD {
+            let chunk_digests = Self::hash_chunks(input, self.n_threads);
+            <H as FiatShamirBytesHash>::hash(output, &chunk_digests);
+        } else {
+            <H as FiatShamirBytesHash>::hash(output, input);
+        }
+    }
+
+    #[inline]
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        <H as FiatShamirBytesHash>::hash_inplace(buffer)
+    }
+}
+
+impl<H: FiatShamirBytesHash> ParallelHasher<H> {
+    /// Split the data into fixed-size chunks, hash the chunks in parallel and return the
+    /// concatenation of the chunk digests.
+    /// The chunking is independent of the number of threads, and so is the result.
+    fn hash_chunks(data: &[u8], n_threads: usize) -> Vec<u8> {
+        let chunks = data.chunks(CHUNK_SIZE).collect::<Vec<_>>();
+        let mut chunk_digests = vec![0u8; chunks.len() * H::DIGEST_SIZE];
+
+        // a single thread hashes in place, without spawning, e.g., on wasm where it cannot
+        if n_threads == 1 {
+            chunk_digests
+                .chunks_mut(H::DIGEST_SIZE)
+                .zip(chunks.iter())
+                .for_each(|(output, input)| <H as FiatShamirBytesHash>::hash(output, input));
+            return chunk_digests;
+        }
+
+        let chunks_per_thread = chunks.len().div_ceil(n_threads);
+        thread::scope(|s| {
+            chunk_digests
+                .chunks_mut(chunks_per_thread * H::DIGEST_SIZE)
+                .zip(chunks.chunks(chunks_per_thread))
+                .for_each(|(outputs, inputs)| {
+                    s.spawn(move || {
+                        outputs
+                            .chunks_mut(H::DIGEST_SIZE)
+                            .zip(inputs.iter())
+                            .for_each(|(output, input)| {
+                                <H as FiatShamirBytesHash>::hash(output, input)
+                            });
+                    });
+                });
+        });
+
+        chunk_digests
+    }
+}
@@ -1,6 +1,46 @@
 use std::io::{Read, Write};
 
 use arith::{FieldSerde, FieldSerdeResult};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProofDecodeError {
+    #[error("odd number of hex digits {0}")]
+    OddLength(usize),
+
+    #[error("invalid hex character {character:?} at {index}")]
+    InvalidHexCharacter { character: char, index: usize },
+
+    #[error("no proof length")]
+    Truncated,
+
+    #[error("the proof claims {claimed} bytes, but has {found}")]
+    LengthMismatch { claimed: u64, found: usize },
+}
+
+/// Lowercase hex, without prefix.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    bytes
+        .iter()
+        .flat_map(|b| [DIGITS[(b >> 4) as usize], DIGITS[(b & 0xf) as usize]])
+        .map(char::from)
+        .collect()
+}
+
+/// The bytes of hex digits of either case, without prefix.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, ProofDecodeError> {
+    if let Some((index, character)) = s.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(ProofDecodeError::InvalidHexCharacter { character, index });
+    }
+    if s.len() % 2 != 0 {
+        return Err(ProofDecodeError::OddLength(s.len()));
+    }
+    Ok(s.as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect())
+}
 
 /// Proof. In the serialized mode.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -13,6 +53,32 @@ impl Proof {
     pub fn append_u8_slice(&mut self, buffer: &[u8], size: usize) {
         self.bytes.extend_from_slice(&buffer[..size]);
     }
+
+    /// The serialized proof, its length included, as lowercase hex.
+    pub fn to_hex_string(&self) -> String {
+        let mut bytes = vec![];
+        self.serialize_into(&mut bytes).unwrap();
+        encode_hex(&bytes)
+    }
+
+    /// The proof of `to_hex_string`, whose length has to match its bytes exactly.
+    pub fn from_hex_string(s: &str) -> Result<Proof, ProofDecodeError> {
+        let bytes = decode_hex(s)?;
+        if bytes.len() < 8 {
+            return Err(ProofDecodeError::Truncated);
+        }
+        let (len, bytes) = bytes.split_at(8);
+        let claimed = u64::from_le_bytes(len.try_into().unwrap());
+        if claimed != bytes.len() as u64 {
+            return Err(ProofDecodeError::LengthMismatch {
+                claimed,
+                found: bytes.len(),
+            });
+        }
+        Ok(Proof {
+            bytes: bytes.to_vec(),
+        })
+    }
 }
 
 impl FieldSerde for Proof {
@@ -29,7 +95,7 @@ impl FieldSerde for Proof {
     fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
         let proof_len = u64::deserialize_from(&mut reader)? as usize;
         let mut proof = vec![0u8; proof_len];
-        reader.read_exact(&mut proof).unwrap();
+        reader.read_exact(&mut proof)?;
         Ok(Self { bytes: proof })
     }
 
@@ -0,0 +1,56 @@
+use std::io::{Read, Write};
+
+use arith::{Field, FieldSerde, FieldSerdeError, FieldSerdeResult};
+
+use crate::Transcript;
+
+/// The largest digest of the hashers of the transcripts, of 32 bytes for all of them, e.g.,
+/// SHA-256, Keccak-256, Blake3 and MiMC over BN254.
+pub const MAX_DIGEST_SIZE: usize = 32;
+
+/// The hash state of a transcript at a phase boundary, i.e., with no absorbed data left unhashed.
+/// A transcript restored from the state produces the same challenges as the original one,
+/// and starts with an empty proof.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptState {
+    pub digest: Vec<u8>,
+}
+
+impl FieldSerde for TranscriptState {
+    const SERIALIZED_SIZE: usize = unimplemented!();
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        (self.digest.len() as u64).serialize_into(&mut writer)?;
+        writer.write_all(&self.digest)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        // the length is checked before the digest is allocated
+        let digest_len = u64::deserialize_from(&mut reader)?;
+        if digest_len > MAX_DIGEST_SIZE as u64 {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        let mut digest = vec![0u8; digest_len as usize];
+        reader.read_exact(&mut digest)?;
+        Ok(Self { digest })
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("not implemented for TranscriptState")
+    }
+}
+
+pub struct TranscriptStateCapture;
+
+impl TranscriptStateCapture {
+    /// Capture the state of the transcript, to continue the protocol with a fresh transcript,
+    /// e.g., in another process.
+    /// Panics if some absorbed data has not been hashed yet: capture right after a challenge.
+    #[inline]
+    pub fn capture<F: Field + FieldSerde, T: Transcript<F>>(transcript: &T) -> TranscriptState {
+        transcript.capture_state()
+    }
+}
@@ -38,3 +38,218 @@ fn check_mimc5_aligned() {
         BN254Fr::deserialize_from(&MIMC5_BN254_ONT[..]).unwrap()
     );
 }
+
+#[test]
+fn check_parallel_transcript_consistency() {
+    use crate::{
+        BytesHashTranscript, ParallelHashTranscript, SHA256hasher, Transcript, PARALLEL_THRESHOLD,
+    };
+
+    // small messages are hashed exactly as in the sequential transcript
+    let mut sequential = BytesHashTranscript::<BN254Fr, SHA256hasher>::new();
+    let mut parallel = ParallelHashTranscript::<BN254Fr, SHA256hasher>::new_with_threads(4);
+    for i in 0..16u32 {
+        sequential.append_field_element(&BN254Fr::from(i));
+        parallel.append_field_element(&BN254Fr::from(i));
+        assert_eq!(
+            sequential.generate_challenge_field_element(),
+            parallel.generate_challenge_field_element()
+        );
+    }
+
+    // large messages are hashed in chunks, independently of the number of threads
+    let large_message = (0..(PARALLEL_THRESHOLD * 3 + 17))
+        .map(|i| i as u8)
+        .collect::<Vec<_>>();
+    let mut single_thread = ParallelHashTranscript::<BN254Fr, SHA256hasher>::new_with_threads(1);
+    let mut multi_thread = ParallelHashTranscript::<BN254Fr, SHA256hasher>::new_with_threads(8);
+    single_thread.append_u8_slice(&large_message);
+    multi_thread.append_u8_slice(&large_message);
+    assert_eq!(
+        single_thread.generate_challenge_field_element(),
+        multi_thread.generate_challenge_field_element()
+    );
+}
+
+#[test]
+fn check_transcript_state_capture() {
+    use crate::{
+        Blake3hasher, BytesHashTranscript, DynHashTranscript, FiatShamirBytesHash,
+        FieldHashTranscript, Keccak256hasher, ParallelHashTranscript, SHA256hasher, Transcript,
+        TranscriptState, TranscriptStateCapture, MAX_DIGEST_SIZE,
+    };
+
+    /// The state of the transcript after a challenge, through its bytes.
+    fn saved_state<T: Transcript<BN254Fr>>(transcript: &mut T) -> TranscriptState {
+        transcript.append_field_element(&BN254Fr::from(1u32));
+        let _ = transcript.generate_challenge_field_element();
+
+        let state = TranscriptStateCapture::capture(transcript);
+        let mut buf = vec![];
+        state.serialize_into(&mut buf).unwrap();
+        TranscriptState::deserialize_from(buf.as_slice()).unwrap()
+    }
+
+    fn check_restored<T: Transcript<BN254Fr>, R: Transcript<BN254Fr>>(
+        original: &mut T,
+        restored: &mut R,
+    ) {
+        assert!(restored.finalize_and_get_proof().bytes.is_empty());
+        for i in 0..4u32 {
+            original.append_field_element(&BN254Fr::from(i));
+            restored.append_field_element(&BN254Fr::from(i));
+            assert_eq!(
+                original.generate_challenge_field_element(),
+                restored.generate_challenge_field_element()
+            );
+        }
+    }
+
+    fn check<T: Transcript<BN254Fr>>() {
+        let mut original = T::new();
+        let state = saved_state(&mut original);
+        check_restored(&mut original, &mut T::restore_from(&state));
+    }
+
+    check::<BytesHashTranscript<BN254Fr, SHA256hasher>>();
+    check::<FieldHashTranscript<BN254Fr, MIMCHasher<BN254Fr>>>();
+    check::<ParallelHashTranscript<BN254Fr, SHA256hasher>>();
+    check::<DynHashTranscript<BN254Fr>>();
+
+    // a dynamic transcript restored with SHA-256 by default, as of `new`, or with its hasher
+    let mut original = BytesHashTranscript::<BN254Fr, SHA256hasher>::new();
+    let state = saved_state(&mut original);
+    check_restored(
+        &mut original,
+        &mut DynHashTranscript::<BN254Fr>::restore_from(&state),
+    );
+    let mut original = BytesHashTranscript::<BN254Fr, Keccak256hasher>::new();
+    let state = saved_state(&mut original);
+    check_restored(
+        &mut original,
+        &mut DynHashTranscript::<BN254Fr>::restore_with_hasher(
+            Box::new(Keccak256hasher::new()),
+            &state,
+        ),
+    );
+
+    // a digest longer than those of the hashers is refused before it is allocated
+    let mut buf = vec![];
+    (MAX_DIGEST_SIZE as u64 + 1)
+        .serialize_into(&mut buf)
+        .unwrap();
+    buf.extend_from_slice(&[0u8; MAX_DIGEST_SIZE + 1]);
+    assert!(TranscriptState::deserialize_from(buf.as_slice()).is_err());
+    let mut buf = vec![];
+    u64::MAX.serialize_into(&mut buf).unwrap();
+    assert!(TranscriptState::deserialize_from(buf.as_slice()).is_err());
+    assert!(SHA256hasher::DIGEST_SIZE <= MAX_DIGEST_SIZE);
+    assert!(Keccak256hasher::DIGEST_SIZE <= MAX_DIGEST_SIZE);
+    assert!(Blake3hasher::DIGEST_SIZE <= MAX_DIGEST_SIZE);
+}
+
+#[test]
+fn check_proof_hex_round_trip() {
+    use crate::{Proof, ProofDecodeError};
+
+    let proof = Proof {
+        bytes: vec![0x00, 0x0f, 0xa5, 0xff],
+    };
+    let hex = proof.to_hex_string();
+    assert_eq!(hex, "0400000000000000000fa5ff");
+    assert_eq!(Proof::from_hex_string(&hex).unwrap(), proof);
+    assert_eq!(Proof::from_hex_string(&hex.to_uppercase()).unwrap(), proof);
+    assert_eq!(
+        Proof::from_hex_string(&Proof::default().to_hex_string()).unwrap(),
+        Proof::default()
+    );
+
+    assert_eq!(
+        Proof::from_hex_string(&hex[1..]),
+        Err(ProofDecodeError::OddLength(23))
+    );
+    assert_eq!(
+        Proof::from_hex_string("04000000000000000g0fa5ff"),
+        Err(ProofDecodeError::InvalidHexCharacter {
+            character: 'g',
+            index: 17
+        })
+    );
+    assert_eq!(
+        Proof::from_hex_string("0x0400000000000000000fa5ff"),
+        Err(ProofDecodeError::InvalidHexCharacter {
+            character: 'x',
+            index: 1
+        })
+    );
+    assert_eq!(
+        Proof::from_hex_string("04000000000000000é"),
+        Err(ProofDecodeError::InvalidHexCharacter {
+            character: 'é',
+            index: 17
+        })
+    );
+    assert_eq!(
+        Proof::from_hex_string("040000"),
+        Err(ProofDecodeError::Truncated)
+    );
+    assert_eq!(
+        Proof::from_hex_string("0400000000000000000fa5"),
+        Err(ProofDecodeError::LengthMismatch {
+            claimed: 4,
+            found: 3
+        })
+    );
+    assert_eq!(
+        Proof::from_hex_string("0400000000000000000fa5ff00"),
+        Err(ProofDecodeError::LengthMismatch {
+            claimed: 4,
+            found: 5
+        })
+    );
+}
+
+#[test]
+fn check_blake3_empty_input() {
+    use crate::{Blake3hasher, FiatShamirBytesHash};
+
+    let mut out = [0u8; 32];
+    Blake3hasher::hash(&mut out, &[]);
+    assert_eq!(
+        crate::encode_hex(&out),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+}
+
+#[test]
+fn check_dyn_transcript_consistency() {
+    use crate::{
+        Blake3hasher, BytesHashTranscript, DynHashTranscript, Keccak256hasher, SHA256hasher,
+        Transcript,
+    };
+
+    fn check<H: crate::FiatShamirBytesHash + Send + Sync + 'static>() {
+        let mut fixed = BytesHashTranscript::<BN254Fr, H>::new();
+        let mut dynamic = DynHashTranscript::<BN254Fr>::with_hasher(Box::new(H::new()));
+        for i in 0..8u32 {
+            fixed.append_field_element(&BN254Fr::from(i));
+            dynamic.append_field_element(&BN254Fr::from(i));
+            assert_eq!(
+                fixed.generate_challenge_field_element(),
+                dynamic.generate_challenge_field_element()
+            );
+        }
+        assert_eq!(
+            fixed.generate_challenge_u8_slice(45),
+            dynamic.generate_challenge_u8_slice(45)
+        );
+        assert_eq!(
+            fixed.finalize_and_get_proof(),
+            dynamic.finalize_and_get_proof()
+        );
+    }
+
+    check::<SHA256hasher>();
+    check::<Keccak256hasher>();
+    check::<Blake3hasher>();
+}
@@ -3,8 +3,8 @@ use std::marker::PhantomData;
 use arith::{Field, FieldSerde};
 
 use crate::{
-    fiat_shamir_hash::{FiatShamirBytesHash, FiatShamirFieldHash},
-    Proof,
+    fiat_shamir_hash::{FiatShamirBytesHash, FiatShamirFieldHash, HashTranscript, SHA256hasher},
+    Proof, TranscriptState,
 };
 
 pub trait Transcript<F: Field + FieldSerde> {
@@ -51,14 +51,30 @@ pub trait Transcript<F: Field + FieldSerde> {
 
     /// unlock proof
     fn unlock_proof(&mut self);
+
+    /// Return the state without hashing, see `TranscriptStateCapture::capture`
+    fn capture_state(&self) -> TranscriptState;
+
+    /// Create a new transcript continuing from the captured state, with an empty proof
+    fn restore_from(state: &TranscriptState) -> Self
+    where
+        Self: Sized,
+    {
+        let mut transcript = Self::new();
+        transcript.set_state(&state.digest);
+        transcript
+    }
 }
 
+/// The absorb and squeeze logic of the transcripts of a bytes hasher, of a hash known at compile
+/// time for `BytesHashTranscript` and `ParallelHashTranscript`, or chosen at runtime for
+/// `DynHashTranscript`, which forward the `Transcript` methods to it.
 #[derive(Clone, Default, Debug, PartialEq)]
-pub struct BytesHashTranscript<F: Field + FieldSerde, H: FiatShamirBytesHash> {
-    phantom: PhantomData<(F, H)>,
+pub(crate) struct HasherTranscript<H: HashTranscript> {
+    hasher: H,
 
     /// The digest bytes.
-    pub digest: Vec<u8>,
+    digest: Vec<u8>,
 
     /// The proof bytes.
     proof: Proof,
@@ -71,11 +87,11 @@ pub struct BytesHashTranscript<F: Field + FieldSerde, H: FiatShamirBytesHash> {
     proof_locked_at: usize,
 }
 
-impl<F: Field + FieldSerde, H: FiatShamirBytesHash> Transcript<F> for BytesHashTranscript<F, H> {
-    fn new() -> Self {
+impl<H: HashTranscript> HasherTranscript<H> {
+    pub(crate) fn new(hasher: H) -> Self {
         Self {
-            phantom: PhantomData,
-            digest: vec![0u8; H::DIGEST_SIZE],
+            digest: vec![0u8; hasher.digest_size()],
+            hasher,
             proof: Proof::default(),
             hash_start_index: 0,
             proof_locked: false,
@@ -83,60 +99,54 @@ impl<F: Field + FieldSerde, H: FiatShamirBytesHash> Transcript<F> for BytesHashT
         }
     }
 
-    fn append_field_element(&mut self, f: &F) {
+    pub(crate) fn append_field_element<F: Field + FieldSerde>(&mut self, f: &F) {
         let mut buf = vec![];
         f.serialize_into(&mut buf).unwrap();
         self.append_u8_slice(&buf);
     }
 
-    /// Append a byte slice to the transcript.
-    fn append_u8_slice(&mut self, buffer: &[u8]) {
+    pub(crate) fn append_u8_slice(&mut self, buffer: &[u8]) {
         self.proof.bytes.extend_from_slice(buffer);
     }
 
-    /// Generate a challenge.
-    fn generate_challenge_field_element(&mut self) -> F {
+    pub(crate) fn generate_challenge_field_element<F: Field + FieldSerde>(&mut self) -> F {
         self.hash_to_digest();
-        assert!(F::SIZE <= H::DIGEST_SIZE);
+        assert!(F::SIZE <= self.digest.len());
         F::from_uniform_bytes(&self.digest.clone().try_into().unwrap())
     }
 
-    fn generate_challenge_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
+    pub(crate) fn generate_challenge_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
         let mut ret = vec![];
-        let mut cur_n_bytes = 0usize;
-
-        while cur_n_bytes < n_bytes {
+        while ret.len() < n_bytes {
             self.hash_to_digest();
             ret.extend_from_slice(&self.digest);
-            cur_n_bytes += H::DIGEST_SIZE;
         }
-
         ret.resize(n_bytes, 0);
         ret
     }
 
-    fn finalize_and_get_proof(&self) -> Proof {
+    pub(crate) fn finalize_and_get_proof(&self) -> Proof {
         self.proof.clone()
     }
 
-    fn hash_and_return_state(&mut self) -> Vec<u8> {
+    pub(crate) fn hash_and_return_state(&mut self) -> Vec<u8> {
         self.hash_to_digest();
         self.digest.clone()
     }
 
-    fn set_state(&mut self, state: &[u8]) {
+    pub(crate) fn set_state(&mut self, state: &[u8]) {
         self.hash_start_index = self.proof.bytes.len(); // discard unhashed data
-        assert!(state.len() == H::DIGEST_SIZE);
+        assert!(state.len() == self.hasher.digest_size());
         self.digest = state.to_vec();
     }
 
-    fn lock_proof(&mut self) {
+    pub(crate) fn lock_proof(&mut self) {
         assert!(!self.proof_locked);
         self.proof_locked = true;
         self.proof_locked_at = self.proof.bytes.len();
     }
 
-    fn unlock_proof(&mut self) {
+    pub(crate) fn unlock_proof(&mut self) {
         assert!(self.proof_locked);
         self.proof_locked = false;
         if self.hash_start_index < self.proof.bytes.len() {
@@ -145,24 +155,157 @@ impl<F: Field + FieldSerde, H: FiatShamirBytesHash> Transcript<F> for BytesHashT
         self.proof.bytes.resize(self.proof_locked_at, 0);
         self.hash_start_index = self.proof.bytes.len();
     }
-}
 
-impl<F: Field + FieldSerde, H: FiatShamirBytesHash> BytesHashTranscript<F, H> {
+    pub(crate) fn capture_state(&self) -> TranscriptState {
+        assert_eq!(
+            self.hash_start_index,
+            self.proof.bytes.len(),
+            "cannot capture a transcript with unhashed data"
+        );
+        TranscriptState {
+            digest: self.digest.clone(),
+        }
+    }
+
     /// Hash the input into the output.
-    pub fn hash_to_digest(&mut self) {
+    pub(crate) fn hash_to_digest(&mut self) {
         let hash_end_index = self.proof.bytes.len();
         if hash_end_index > self.hash_start_index {
-            H::hash(
+            self.hasher.hash(
                 &mut self.digest,
                 &self.proof.bytes[self.hash_start_index..hash_end_index],
             );
             self.hash_start_index = hash_end_index;
         } else {
-            H::hash_inplace(&mut self.digest);
+            self.hasher.hash_inplace(&mut self.digest);
         }
     }
 }
 
+/// Forward the `Transcript` methods but `new` to the `HasherTranscript` of the field `inner`.
+macro_rules! forward_to_hasher_transcript {
+    () => {
+        #[inline]
+        fn append_field_element(&mut self, f: &F) {
+            self.inner.append_field_element(f)
+        }
+
+        #[inline]
+        fn append_u8_slice(&mut self, buffer: &[u8]) {
+            self.inner.append_u8_slice(buffer)
+        }
+
+        #[inline]
+        fn generate_challenge_field_element(&mut self) -> F {
+            self.inner.generate_challenge_field_element()
+        }
+
+        #[inline]
+        fn generate_challenge_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
+            self.inner.generate_challenge_u8_slice(n_bytes)
+        }
+
+        #[inline]
+        fn finalize_and_get_proof(&self) -> Proof {
+            self.inner.finalize_and_get_proof()
+        }
+
+        #[inline]
+        fn hash_and_return_state(&mut self) -> Vec<u8> {
+            self.inner.hash_and_return_state()
+        }
+
+        #[inline]
+        fn set_state(&mut self, state: &[u8]) {
+            self.inner.set_state(state)
+        }
+
+        #[inline]
+        fn lock_proof(&mut self) {
+            self.inner.lock_proof()
+        }
+
+        #[inline]
+        fn unlock_proof(&mut self) {
+            self.inner.unlock_proof()
+        }
+
+        #[inline]
+        fn capture_state(&self) -> TranscriptState {
+            self.inner.capture_state()
+        }
+    };
+}
+pub(crate) use forward_to_hasher_transcript;
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct BytesHashTranscript<F: Field + FieldSerde, H: FiatShamirBytesHash> {
+    phantom: PhantomData<F>,
+
+    inner: HasherTranscript<H>,
+}
+
+impl<F: Field + FieldSerde, H: FiatShamirBytesHash> Transcript<F> for BytesHashTranscript<F, H> {
+    fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+            inner: HasherTranscript::new(H::new()),
+        }
+    }
+
+    forward_to_hasher_transcript!();
+}
+
+impl<F: Field + FieldSerde, H: FiatShamirBytesHash> BytesHashTranscript<F, H> {
+    /// Hash the input into the output.
+    pub fn hash_to_digest(&mut self) {
+        self.inner.hash_to_digest()
+    }
+}
+
+/// As `BytesHashTranscript`, with the hash chosen at runtime. The same hash gives the same
+/// challenges and the same proof as its `BytesHashTranscript`. `Transcript::new` and
+/// `Transcript::restore_from` hash with SHA-256, the default of the configs, see `with_hasher`
+/// and `restore_with_hasher` for another hash.
+pub struct DynHashTranscript<F: Field + FieldSerde> {
+    phantom: PhantomData<F>,
+
+    inner: HasherTranscript<Box<dyn HashTranscript>>,
+}
+
+impl<F: Field + FieldSerde> DynHashTranscript<F> {
+    pub fn with_hasher(hasher: Box<dyn HashTranscript>) -> Self {
+        Self {
+            phantom: PhantomData,
+            inner: HasherTranscript::new(hasher),
+        }
+    }
+
+    /// As `Transcript::restore_from`, hashing with the hasher.
+    pub fn restore_with_hasher(hasher: Box<dyn HashTranscript>, state: &TranscriptState) -> Self {
+        let mut transcript = Self::with_hasher(hasher);
+        transcript.set_state(&state.digest);
+        transcript
+    }
+
+    /// Hash the input into the output.
+    pub fn hash_to_digest(&mut self) {
+        self.inner.hash_to_digest()
+    }
+}
+
+impl<F: Field + FieldSerde> Transcript<F> for DynHashTranscript<F> {
+    fn new() -> Self {
+        Self::with_hasher(Box::new(SHA256hasher::new()))
+    }
+
+    fn restore_from(state: &TranscriptState) -> Self {
+        Self::restore_with_hasher(Box::new(SHA256hasher::new()), state)
+    }
+
+    forward_to_hasher_transcript!();
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct FieldHashTranscript<F: Field + FieldSerde, H: FiatShamirFieldHash<F>> {
     /// Internal hasher, it's a little costly to create a new hasher
@@ -265,6 +408,16 @@ impl<F: Field + FieldSerde, H: FiatShamirFieldHash<F>> Transcript<F> for FieldHa
         assert!(self.proof_locked);
         self.proof_locked = false;
     }
+
+    fn capture_state(&self) -> TranscriptState {
+        assert!(
+            self.data_pool.is_empty(),
+            "cannot capture a transcript with unhashed data"
+        );
+        let mut digest = vec![];
+        self.digest.serialize_into(&mut digest).unwrap();
+        TranscriptState { digest }
+    }
 }
 
 impl<F: Field + FieldSerde, H: FiatShamirFieldHash<F>> FieldHashTranscript<F, H> {
@@ -0,0 +1,55 @@
+//! The verifier for the wasm hosts, e.g., a browser, without mpi, the servers or the file
+//! system: the circuit, the public inputs and the proof are bytes, the proof as written by
+//! `expander-exec prove`. Build with `cargo build -p expander-wasm --target wasm32-unknown-unknown`.
+//!
+//! The config is the one of `expander-exec` for the field of the circuit, as for the ffi crate.
+
+use config::{
+    BN254ConfigMIMC5, BabyBearExtConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig,
+    GoldilocksExtConfigSha2, M31ExtConfigSha2, MPIConfig,
+};
+use gkr::{
+    detect_field_type, executor, FuzzSafeCircuitLoader, ProofLayout, ProofPackage, SchemeSelector,
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Verify the proof package bytes for the circuit bytes, of the field of its sentinel, on the
+/// public inputs, in the witness format without the private inputs, or on the whole witness.
+/// False for a proof that does not verify, as for circuit, public input or proof bytes that
+/// are truncated or malformed: a panic aborts the wasm instance, so these are errors of the
+/// loader and the verifier rather than panics, and a circuit is refused if it is larger than
+/// the loader accepts, see `FuzzSafeCircuitLoader`.
+#[wasm_bindgen]
+pub fn verify(circuit_bytes: &[u8], public_input_bytes: &[u8], proof_bytes: &[u8]) -> bool {
+    let verify = match detect_field_type(circuit_bytes) {
+        Ok(FieldType::M31) => verify_with::<M31ExtConfigSha2>,
+        Ok(FieldType::BN254) => verify_with::<BN254ConfigMIMC5>,
+        Ok(FieldType::GF2) => verify_with::<GF2ExtConfigSha2>,
+        Ok(FieldType::Goldilocks) => verify_with::<GoldilocksExtConfigSha2>,
+        Ok(FieldType::BabyBear) => verify_with::<BabyBearExtConfigSha2>,
+        Err(_) => return false,
+    };
+    verify(circuit_bytes, public_input_bytes, proof_bytes).unwrap_or(false)
+}
+
+fn verify_with<C: GKRConfig>(
+    circuit_bytes: &[u8],
+    public_input_bytes: &[u8],
+    proof_bytes: &[u8],
+) -> Option<bool> {
+    let mut circuit = FuzzSafeCircuitLoader::<C>::load(circuit_bytes).ok()?;
+    circuit.validate().ok()?;
+    let config = Config::<C>::new(
+        SchemeSelector::select(&circuit),
+        MPIConfig::new_for_verifier(1),
+    );
+    circuit
+        .try_load_public_input_bytes(public_input_bytes, false)
+        .ok()?;
+    let max_proof_size = ProofLayout::new(&circuit, &config).proof_size();
+    let package =
+        ProofPackage::deserialize_with_max_proof_size(&mut &proof_bytes[..], max_proof_size)
+            .ok()?;
+    let verified = executor::try_verify_package_on(&config, &mut circuit, &package).ok()?;
+    Some(verified.is_ok())
+}
@@ -0,0 +1,101 @@
+//! Run with `wasm-pack test --node wasm`, on the fixture proofs of the readme, see "WASM verifier".
+#![cfg(target_arch = "wasm32")]
+
+use config::{SENTINEL_OFFSET, SENTINEL_SIZE};
+use expander_wasm::verify;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const M31_CIRCUIT: &[u8] = include_bytes!("../../data/circuit_m31.txt");
+const M31_WITNESS: &[u8] = include_bytes!("../../data/witness_m31.txt");
+const M31_PROOF: &[u8] = include_bytes!("../../data/proof_m31.bin");
+
+const BN254_CIRCUIT: &[u8] = include_bytes!("../../data/circuit_bn254.txt");
+const BN254_WITNESS: &[u8] = include_bytes!("../../data/witness_bn254.txt");
+const BN254_PROOF: &[u8] = include_bytes!("../../data/proof_bn254.bin");
+
+fn assert_verifies(circuit: &[u8], witness: &[u8], proof: &[u8]) {
+    assert!(verify(circuit, witness, proof));
+
+    let mut tampered = proof.to_vec();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(!verify(circuit, witness, &tampered));
+    assert!(!verify(circuit, witness, &proof[..proof.len() / 2]));
+    assert!(!verify(&circuit[..8], witness, proof));
+}
+
+#[wasm_bindgen_test]
+fn test_verify_m31() {
+    assert_verifies(M31_CIRCUIT, M31_WITNESS, M31_PROOF);
+}
+
+#[wasm_bindgen_test]
+fn test_verify_bn254() {
+    assert_verifies(BN254_CIRCUIT, BN254_WITNESS, BN254_PROOF);
+}
+
+/// The header of the circuit file, its version and the sentinel of its field.
+const CIRCUIT_HEADER_SIZE: usize = SENTINEL_OFFSET + SENTINEL_SIZE;
+
+/// The bytes of a xorshift generator, the same on each run.
+fn garbage(len: usize) -> Vec<u8> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// A panic aborts the wasm instance, so the malformed bytes must be refused by errors.
+fn assert_refuses_malformed(circuit: &[u8], witness: &[u8], proof: &[u8]) {
+    for len in [
+        0,
+        CIRCUIT_HEADER_SIZE - 1,
+        CIRCUIT_HEADER_SIZE,
+        CIRCUIT_HEADER_SIZE + 1,
+        circuit.len() / 4,
+        circuit.len() / 2,
+        circuit.len() - 1,
+    ] {
+        assert!(!verify(&circuit[..len], witness, proof));
+    }
+    for len in [8, 64, 4096] {
+        let mut garbage_circuit = circuit[..CIRCUIT_HEADER_SIZE].to_vec();
+        garbage_circuit.extend(garbage(len));
+        assert!(!verify(&garbage_circuit, witness, proof));
+        let mut all_ones = circuit[..CIRCUIT_HEADER_SIZE].to_vec();
+        all_ones.extend(vec![0xff; len]);
+        assert!(!verify(&all_ones, witness, proof));
+    }
+
+    for len in [0, 1, witness.len() / 2, witness.len() - 1] {
+        assert!(!verify(circuit, &witness[..len], proof));
+    }
+    assert!(!verify(circuit, &garbage(witness.len()), proof));
+
+    for len in [0, 4, 7, 8, 16, proof.len() - 1] {
+        assert!(!verify(circuit, witness, &proof[..len]));
+    }
+    assert!(!verify(circuit, witness, &garbage(proof.len())));
+    // the tags of the package, then garbage for its lengths and proof
+    let mut garbage_proof = proof[..7].to_vec();
+    garbage_proof.extend(garbage(proof.len() - 7));
+    assert!(!verify(circuit, witness, &garbage_proof));
+    let mut all_ones = proof[..7].to_vec();
+    all_ones.extend(vec![0xff; proof.len() - 7]);
+    assert!(!verify(circuit, witness, &all_ones));
+}
+
+#[wasm_bindgen_test]
+fn test_refuse_malformed_m31() {
+    assert_refuses_malformed(M31_CIRCUIT, M31_WITNESS, M31_PROOF);
+}
+
+#[wasm_bindgen_test]
+fn test_refuse_malformed_bn254() {
+    assert_refuses_malformed(BN254_CIRCUIT, BN254_WITNESS, BN254_PROOF);
+}